@@ -102,7 +102,9 @@ impl TryFrom<ConcreteDataType> for ColumnDataTypeWrapper {
                 TimestampType::Microsecond(_) => ColumnDataType::TimestampMicrosecond,
                 TimestampType::Nanosecond(_) => ColumnDataType::TimestampNanosecond,
             },
+            // TODO: map decimal once `ColumnDataType` is able to carry the precision and scale.
             ConcreteDataType::Null(_)
+            | ConcreteDataType::Decimal128(_)
             | ConcreteDataType::List(_)
             | ConcreteDataType::Dictionary(_) => {
                 return error::IntoColumnDataTypeSnafu { from: datatype }.fail()
@@ -225,7 +227,7 @@ pub fn push_vals(column: &mut Column, origin_count: usize, vector: VectorRef) {
             TimeUnit::Microsecond => values.ts_microsecond_values.push(val.value()),
             TimeUnit::Nanosecond => values.ts_nanosecond_values.push(val.value()),
         },
-        Value::List(_) => unreachable!(),
+        Value::Decimal128(_) | Value::List(_) => unreachable!(),
     });
     column.null_mask = null_mask.into_vec();
 }
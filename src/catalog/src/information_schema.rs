@@ -12,7 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod cluster_info;
 mod columns;
+mod partitions;
+mod region_statistics;
+mod schemata;
 mod tables;
 
 use std::any::Any;
@@ -20,6 +24,7 @@ use std::sync::{Arc, Weak};
 
 use async_trait::async_trait;
 use common_error::ext::BoxedError;
+use common_meta::datanode::Stat;
 use common_recordbatch::{RecordBatchStreamAdaptor, SendableRecordBatchStream};
 use datatypes::schema::SchemaRef;
 use futures_util::StreamExt;
@@ -29,17 +34,56 @@ use table::error::{SchemaConversionSnafu, TablesRecordBatchSnafu};
 use table::metadata::TableType;
 use table::{Result as TableResult, Table, TableRef};
 
+use self::cluster_info::InformationSchemaClusterInfo;
 use self::columns::InformationSchemaColumns;
+use self::partitions::InformationSchemaPartitions;
+use self::region_statistics::InformationSchemaRegionStatistics;
+use self::schemata::InformationSchemaSchemata;
 use crate::error::Result;
 use crate::information_schema::tables::InformationSchemaTables;
 use crate::CatalogManager;
 
 const TABLES: &str = "tables";
 const COLUMNS: &str = "columns";
+const SCHEMATA: &str = "schemata";
+const PARTITIONS: &str = "partitions";
+const REGION_STATISTICS: &str = "region_statistics";
+const CLUSTER_INFO: &str = "cluster_info";
+
+/// A partition of a table, i.e. a region and the rows it holds.
+#[derive(Debug, Clone, Default)]
+pub struct TablePartition {
+    pub region_id: u64,
+    /// The partitioning method, e.g. `RANGE COLUMNS` or `HASH`.
+    pub method: Option<String>,
+    /// The columns the table is partitioned by.
+    pub columns: Vec<String>,
+    /// The rows held by the partition, e.g. `VALUES LESS THAN (10, MAXVALUE)`.
+    pub description: Option<String>,
+    /// Address of the datanode serving the region.
+    pub peer_addr: Option<String>,
+}
+
+/// Provides the cluster level information of the `information_schema` tables, which a
+/// standalone catalog manager builds from its local tables instead.
+#[async_trait]
+pub trait InformationExtension: Send + Sync {
+    /// Returns the partitions of the table, ordered by their bounds.
+    async fn partitions(&self, table: &TableRef) -> Result<Vec<TablePartition>>;
+
+    /// Returns the latest stat of each datanode.
+    async fn datanode_stats(&self) -> Result<Vec<Stat>>;
+
+    /// Returns the addresses of all metasrv peers and the leader among them.
+    async fn metasrv_peers(&self) -> Result<(Vec<String>, String)>;
+}
+
+pub type InformationExtensionRef = Arc<dyn InformationExtension>;
 
 pub struct InformationSchemaProvider {
     catalog_name: String,
     catalog_manager: Weak<dyn CatalogManager>,
+    extension: Option<InformationExtensionRef>,
 }
 
 impl InformationSchemaProvider {
@@ -47,6 +91,14 @@ impl InformationSchemaProvider {
         Self {
             catalog_name,
             catalog_manager,
+            extension: None,
+        }
+    }
+
+    pub fn with_extension(self, extension: InformationExtensionRef) -> Self {
+        Self {
+            extension: Some(extension),
+            ..self
         }
     }
 }
@@ -62,6 +114,23 @@ impl InformationSchemaProvider {
                 self.catalog_name.clone(),
                 self.catalog_manager.clone(),
             )) as _,
+            SCHEMATA => Arc::new(InformationSchemaSchemata::new(
+                self.catalog_name.clone(),
+                self.catalog_manager.clone(),
+            )) as _,
+            PARTITIONS => Arc::new(InformationSchemaPartitions::new(
+                self.catalog_name.clone(),
+                self.catalog_manager.clone(),
+                self.extension.clone(),
+            )) as _,
+            REGION_STATISTICS => Arc::new(InformationSchemaRegionStatistics::new(
+                self.catalog_name.clone(),
+                self.catalog_manager.clone(),
+                self.extension.clone(),
+            )) as _,
+            CLUSTER_INFO => {
+                Arc::new(InformationSchemaClusterInfo::new(self.extension.clone())) as _
+            }
             _ => {
                 return Ok(None);
             }
@@ -0,0 +1,199 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::Arc;
+
+use arrow_schema::SchemaRef as ArrowSchemaRef;
+use common_error::ext::BoxedError;
+use common_query::physical_plan::TaskContext;
+use common_recordbatch::adapter::RecordBatchStreamAdapter;
+use common_recordbatch::{RecordBatch, SendableRecordBatchStream};
+use datafusion::datasource::streaming::PartitionStream as DfPartitionStream;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter as DfRecordBatchStreamAdapter;
+use datafusion::physical_plan::SendableRecordBatchStream as DfSendableRecordBatchStream;
+use datatypes::prelude::{ConcreteDataType, ScalarVectorBuilder, VectorRef};
+use datatypes::schema::{ColumnSchema, Schema, SchemaRef};
+use datatypes::timestamp::TimestampMillisecond;
+use datatypes::vectors::{
+    BooleanVectorBuilder, StringVectorBuilder, TimestampMillisecondVectorBuilder,
+    UInt64VectorBuilder,
+};
+use snafu::ResultExt;
+
+use crate::error::{CreateRecordBatchSnafu, InternalSnafu, Result};
+use crate::information_schema::{InformationExtensionRef, InformationStreamBuilder};
+
+const PEER_TYPE_METASRV: &str = "METASRV";
+const PEER_TYPE_DATANODE: &str = "DATANODE";
+const PEER_TYPE_STANDALONE: &str = "STANDALONE";
+
+pub(super) struct InformationSchemaClusterInfo {
+    schema: SchemaRef,
+    extension: Option<InformationExtensionRef>,
+}
+
+impl InformationSchemaClusterInfo {
+    pub(super) fn new(extension: Option<InformationExtensionRef>) -> Self {
+        let schema = Arc::new(Schema::new(vec![
+            ColumnSchema::new("peer_id", ConcreteDataType::uint64_datatype(), true),
+            ColumnSchema::new("peer_type", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("peer_addr", ConcreteDataType::string_datatype(), true),
+            ColumnSchema::new("is_leader", ConcreteDataType::boolean_datatype(), true),
+            ColumnSchema::new(
+                "last_active_time",
+                ConcreteDataType::timestamp_millisecond_datatype(),
+                true,
+            ),
+        ]));
+        Self { schema, extension }
+    }
+
+    fn builder(&self) -> InformationSchemaClusterInfoBuilder {
+        InformationSchemaClusterInfoBuilder::new(self.schema.clone(), self.extension.clone())
+    }
+}
+
+impl InformationStreamBuilder for InformationSchemaClusterInfo {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn to_stream(&self) -> Result<SendableRecordBatchStream> {
+        let schema = self.schema.arrow_schema().clone();
+        let mut builder = self.builder();
+        let stream = Box::pin(DfRecordBatchStreamAdapter::new(
+            schema,
+            futures::stream::once(async move {
+                builder
+                    .make_cluster_info()
+                    .await
+                    .map(|x| x.into_df_record_batch())
+                    .map_err(Into::into)
+            }),
+        ));
+        Ok(Box::pin(
+            RecordBatchStreamAdapter::try_new(stream)
+                .map_err(BoxedError::new)
+                .context(InternalSnafu)?,
+        ))
+    }
+}
+
+/// Builds the `information_schema.CLUSTER_INFO` table row by row, one row per node.
+///
+/// A standalone instance is shown as a single `STANDALONE` node.
+struct InformationSchemaClusterInfoBuilder {
+    schema: SchemaRef,
+    extension: Option<InformationExtensionRef>,
+
+    peer_ids: UInt64VectorBuilder,
+    peer_types: StringVectorBuilder,
+    peer_addrs: StringVectorBuilder,
+    is_leaders: BooleanVectorBuilder,
+    last_active_times: TimestampMillisecondVectorBuilder,
+}
+
+impl InformationSchemaClusterInfoBuilder {
+    fn new(schema: SchemaRef, extension: Option<InformationExtensionRef>) -> Self {
+        Self {
+            schema,
+            extension,
+            peer_ids: UInt64VectorBuilder::with_capacity(42),
+            peer_types: StringVectorBuilder::with_capacity(42),
+            peer_addrs: StringVectorBuilder::with_capacity(42),
+            is_leaders: BooleanVectorBuilder::with_capacity(42),
+            last_active_times: TimestampMillisecondVectorBuilder::with_capacity(42),
+        }
+    }
+
+    /// Construct the `information_schema.cluster_info` virtual table
+    async fn make_cluster_info(&mut self) -> Result<RecordBatch> {
+        let Some(extension) = self.extension.clone() else {
+            self.add_node(None, PEER_TYPE_STANDALONE, None, None, None);
+            return self.finish();
+        };
+
+        let (peers, leader) = extension.metasrv_peers().await?;
+        for peer in &peers {
+            self.add_node(
+                None,
+                PEER_TYPE_METASRV,
+                Some(peer),
+                Some(*peer == leader),
+                None,
+            );
+        }
+
+        let mut stats = extension.datanode_stats().await?;
+        stats.sort_by_key(|stat| stat.id);
+        for stat in &stats {
+            self.add_node(
+                Some(stat.id),
+                PEER_TYPE_DATANODE,
+                Some(&stat.addr),
+                None,
+                Some(stat.timestamp_millis),
+            );
+        }
+
+        self.finish()
+    }
+
+    fn add_node(
+        &mut self,
+        peer_id: Option<u64>,
+        peer_type: &str,
+        peer_addr: Option<&str>,
+        is_leader: Option<bool>,
+        last_active_time: Option<i64>,
+    ) {
+        self.peer_ids.push(peer_id);
+        self.peer_types.push(Some(peer_type));
+        self.peer_addrs.push(peer_addr);
+        self.is_leaders.push(is_leader);
+        self.last_active_times
+            .push(last_active_time.map(TimestampMillisecond::new));
+    }
+
+    fn finish(&mut self) -> Result<RecordBatch> {
+        let columns: Vec<VectorRef> = vec![
+            Arc::new(self.peer_ids.finish()),
+            Arc::new(self.peer_types.finish()),
+            Arc::new(self.peer_addrs.finish()),
+            Arc::new(self.is_leaders.finish()),
+            Arc::new(self.last_active_times.finish()),
+        ];
+        RecordBatch::new(self.schema.clone(), columns).context(CreateRecordBatchSnafu)
+    }
+}
+
+impl DfPartitionStream for InformationSchemaClusterInfo {
+    fn schema(&self) -> &ArrowSchemaRef {
+        self.schema.arrow_schema()
+    }
+
+    fn execute(&self, _: Arc<TaskContext>) -> DfSendableRecordBatchStream {
+        let schema = self.schema.arrow_schema().clone();
+        let mut builder = self.builder();
+        Box::pin(DfRecordBatchStreamAdapter::new(
+            schema,
+            futures::stream::once(async move {
+                builder
+                    .make_cluster_info()
+                    .await
+                    .map(|x| x.into_df_record_batch())
+                    .map_err(Into::into)
+            }),
+        ))
+    }
+}
@@ -0,0 +1,292 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::{Arc, Weak};
+
+use arrow_schema::SchemaRef as ArrowSchemaRef;
+use common_catalog::consts::INFORMATION_SCHEMA_NAME;
+use common_error::ext::BoxedError;
+use common_query::physical_plan::TaskContext;
+use common_recordbatch::adapter::RecordBatchStreamAdapter;
+use common_recordbatch::{RecordBatch, SendableRecordBatchStream};
+use datafusion::datasource::streaming::PartitionStream as DfPartitionStream;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter as DfRecordBatchStreamAdapter;
+use datafusion::physical_plan::SendableRecordBatchStream as DfSendableRecordBatchStream;
+use datatypes::prelude::{ConcreteDataType, ScalarVectorBuilder, VectorRef};
+use datatypes::schema::{ColumnSchema, Schema, SchemaRef};
+use datatypes::vectors::{StringVectorBuilder, UInt32VectorBuilder, UInt64VectorBuilder};
+use snafu::{OptionExt, ResultExt};
+use store_api::storage::RegionId;
+use table::TableRef;
+
+use crate::error::{
+    CreateRecordBatchSnafu, InternalSnafu, Result, UpgradeWeakCatalogManagerRefSnafu,
+};
+use crate::information_schema::{
+    InformationExtensionRef, InformationStreamBuilder, TablePartition,
+};
+use crate::CatalogManager;
+
+pub(super) struct InformationSchemaPartitions {
+    schema: SchemaRef,
+    catalog_name: String,
+    catalog_manager: Weak<dyn CatalogManager>,
+    extension: Option<InformationExtensionRef>,
+}
+
+impl InformationSchemaPartitions {
+    pub(super) fn new(
+        catalog_name: String,
+        catalog_manager: Weak<dyn CatalogManager>,
+        extension: Option<InformationExtensionRef>,
+    ) -> Self {
+        let schema = Arc::new(Schema::new(vec![
+            ColumnSchema::new("table_catalog", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("table_schema", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("table_name", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("partition_name", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new(
+                "partition_ordinal_position",
+                ConcreteDataType::uint32_datatype(),
+                false,
+            ),
+            ColumnSchema::new(
+                "partition_method",
+                ConcreteDataType::string_datatype(),
+                true,
+            ),
+            ColumnSchema::new(
+                "partition_expression",
+                ConcreteDataType::string_datatype(),
+                true,
+            ),
+            ColumnSchema::new(
+                "partition_description",
+                ConcreteDataType::string_datatype(),
+                true,
+            ),
+            ColumnSchema::new("region_id", ConcreteDataType::uint64_datatype(), false),
+            ColumnSchema::new("peer_addr", ConcreteDataType::string_datatype(), true),
+        ]));
+        Self {
+            schema,
+            catalog_name,
+            catalog_manager,
+            extension,
+        }
+    }
+
+    fn builder(&self) -> InformationSchemaPartitionsBuilder {
+        InformationSchemaPartitionsBuilder::new(
+            self.schema.clone(),
+            self.catalog_name.clone(),
+            self.catalog_manager.clone(),
+            self.extension.clone(),
+        )
+    }
+}
+
+impl InformationStreamBuilder for InformationSchemaPartitions {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn to_stream(&self) -> Result<SendableRecordBatchStream> {
+        let schema = self.schema.arrow_schema().clone();
+        let mut builder = self.builder();
+        let stream = Box::pin(DfRecordBatchStreamAdapter::new(
+            schema,
+            futures::stream::once(async move {
+                builder
+                    .make_partitions()
+                    .await
+                    .map(|x| x.into_df_record_batch())
+                    .map_err(Into::into)
+            }),
+        ));
+        Ok(Box::pin(
+            RecordBatchStreamAdapter::try_new(stream)
+                .map_err(BoxedError::new)
+                .context(InternalSnafu)?,
+        ))
+    }
+}
+
+/// Builds the `information_schema.PARTITIONS` table row by row, one row per region.
+///
+/// Columns are based on <https://dev.mysql.com/doc/refman/8.0/en/information-schema-partitions-table.html>
+struct InformationSchemaPartitionsBuilder {
+    schema: SchemaRef,
+    catalog_name: String,
+    catalog_manager: Weak<dyn CatalogManager>,
+    extension: Option<InformationExtensionRef>,
+
+    catalog_names: StringVectorBuilder,
+    schema_names: StringVectorBuilder,
+    table_names: StringVectorBuilder,
+    partition_names: StringVectorBuilder,
+    ordinal_positions: UInt32VectorBuilder,
+    partition_methods: StringVectorBuilder,
+    partition_expressions: StringVectorBuilder,
+    partition_descriptions: StringVectorBuilder,
+    region_ids: UInt64VectorBuilder,
+    peer_addrs: StringVectorBuilder,
+}
+
+impl InformationSchemaPartitionsBuilder {
+    fn new(
+        schema: SchemaRef,
+        catalog_name: String,
+        catalog_manager: Weak<dyn CatalogManager>,
+        extension: Option<InformationExtensionRef>,
+    ) -> Self {
+        Self {
+            schema,
+            catalog_name,
+            catalog_manager,
+            extension,
+            catalog_names: StringVectorBuilder::with_capacity(42),
+            schema_names: StringVectorBuilder::with_capacity(42),
+            table_names: StringVectorBuilder::with_capacity(42),
+            partition_names: StringVectorBuilder::with_capacity(42),
+            ordinal_positions: UInt32VectorBuilder::with_capacity(42),
+            partition_methods: StringVectorBuilder::with_capacity(42),
+            partition_expressions: StringVectorBuilder::with_capacity(42),
+            partition_descriptions: StringVectorBuilder::with_capacity(42),
+            region_ids: UInt64VectorBuilder::with_capacity(42),
+            peer_addrs: StringVectorBuilder::with_capacity(42),
+        }
+    }
+
+    /// Construct the `information_schema.partitions` virtual table
+    async fn make_partitions(&mut self) -> Result<RecordBatch> {
+        let catalog_name = self.catalog_name.clone();
+        let catalog_manager = self
+            .catalog_manager
+            .upgrade()
+            .context(UpgradeWeakCatalogManagerRefSnafu)?;
+
+        for schema_name in catalog_manager.schema_names(&catalog_name).await? {
+            if schema_name == INFORMATION_SCHEMA_NAME {
+                continue;
+            }
+            if !catalog_manager
+                .schema_exist(&catalog_name, &schema_name)
+                .await?
+            {
+                continue;
+            }
+
+            for table_name in catalog_manager
+                .table_names(&catalog_name, &schema_name)
+                .await?
+            {
+                let Some(table) = catalog_manager.table(&catalog_name, &schema_name, &table_name).await? else { continue };
+                let partitions = match &self.extension {
+                    Some(extension) => extension.partitions(&table).await?,
+                    None => local_partitions(&table),
+                };
+
+                for (i, partition) in partitions.iter().enumerate() {
+                    self.add_partition(
+                        &catalog_name,
+                        &schema_name,
+                        &table_name,
+                        i as u32 + 1,
+                        partition,
+                    );
+                }
+            }
+        }
+
+        self.finish()
+    }
+
+    fn add_partition(
+        &mut self,
+        catalog_name: &str,
+        schema_name: &str,
+        table_name: &str,
+        ordinal_position: u32,
+        partition: &TablePartition,
+    ) {
+        self.catalog_names.push(Some(catalog_name));
+        self.schema_names.push(Some(schema_name));
+        self.table_names.push(Some(table_name));
+        self.partition_names
+            .push(Some(&format!("r{}", partition.region_id)));
+        self.ordinal_positions.push(Some(ordinal_position));
+        self.partition_methods.push(partition.method.as_deref());
+        if partition.columns.is_empty() {
+            self.partition_expressions.push(None);
+        } else {
+            self.partition_expressions
+                .push(Some(&partition.columns.join(", ")));
+        }
+        self.partition_descriptions
+            .push(partition.description.as_deref());
+        self.region_ids.push(Some(partition.region_id));
+        self.peer_addrs.push(partition.peer_addr.as_deref());
+    }
+
+    fn finish(&mut self) -> Result<RecordBatch> {
+        let columns: Vec<VectorRef> = vec![
+            Arc::new(self.catalog_names.finish()),
+            Arc::new(self.schema_names.finish()),
+            Arc::new(self.table_names.finish()),
+            Arc::new(self.partition_names.finish()),
+            Arc::new(self.ordinal_positions.finish()),
+            Arc::new(self.partition_methods.finish()),
+            Arc::new(self.partition_expressions.finish()),
+            Arc::new(self.partition_descriptions.finish()),
+            Arc::new(self.region_ids.finish()),
+            Arc::new(self.peer_addrs.finish()),
+        ];
+        RecordBatch::new(self.schema.clone(), columns).context(CreateRecordBatchSnafu)
+    }
+}
+
+/// Each region of a local table is a partition without partition rule.
+fn local_partitions(table: &TableRef) -> Vec<TablePartition> {
+    let table_info = table.table_info();
+    table_info
+        .meta
+        .region_numbers
+        .iter()
+        .map(|region_number| TablePartition {
+            region_id: RegionId::new(table_info.ident.table_id, *region_number).as_u64(),
+            ..Default::default()
+        })
+        .collect()
+}
+
+impl DfPartitionStream for InformationSchemaPartitions {
+    fn schema(&self) -> &ArrowSchemaRef {
+        self.schema.arrow_schema()
+    }
+
+    fn execute(&self, _: Arc<TaskContext>) -> DfSendableRecordBatchStream {
+        let schema = self.schema.arrow_schema().clone();
+        let mut builder = self.builder();
+        Box::pin(DfRecordBatchStreamAdapter::new(
+            schema,
+            futures::stream::once(async move {
+                builder
+                    .make_partitions()
+                    .await
+                    .map(|x| x.into_df_record_batch())
+                    .map_err(Into::into)
+            }),
+        ))
+    }
+}
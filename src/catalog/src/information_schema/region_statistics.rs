@@ -0,0 +1,258 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::{Arc, Weak};
+
+use arrow_schema::SchemaRef as ArrowSchemaRef;
+use common_catalog::consts::INFORMATION_SCHEMA_NAME;
+use common_error::ext::BoxedError;
+use common_meta::datanode::{RegionStat, Stat};
+use common_query::physical_plan::TaskContext;
+use common_recordbatch::adapter::RecordBatchStreamAdapter;
+use common_recordbatch::{RecordBatch, SendableRecordBatchStream};
+use datafusion::datasource::streaming::PartitionStream as DfPartitionStream;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter as DfRecordBatchStreamAdapter;
+use datafusion::physical_plan::SendableRecordBatchStream as DfSendableRecordBatchStream;
+use datatypes::prelude::{ConcreteDataType, ScalarVectorBuilder, VectorRef};
+use datatypes::schema::{ColumnSchema, Schema, SchemaRef};
+use datatypes::vectors::{StringVectorBuilder, UInt32VectorBuilder, UInt64VectorBuilder};
+use snafu::{OptionExt, ResultExt};
+use store_api::storage::RegionId;
+
+use crate::error::{
+    CreateRecordBatchSnafu, InternalSnafu, Result, UpgradeWeakCatalogManagerRefSnafu,
+};
+use crate::information_schema::{InformationExtensionRef, InformationStreamBuilder};
+use crate::CatalogManager;
+
+pub(super) struct InformationSchemaRegionStatistics {
+    schema: SchemaRef,
+    catalog_name: String,
+    catalog_manager: Weak<dyn CatalogManager>,
+    extension: Option<InformationExtensionRef>,
+}
+
+impl InformationSchemaRegionStatistics {
+    pub(super) fn new(
+        catalog_name: String,
+        catalog_manager: Weak<dyn CatalogManager>,
+        extension: Option<InformationExtensionRef>,
+    ) -> Self {
+        let schema = Arc::new(Schema::new(vec![
+            ColumnSchema::new("region_id", ConcreteDataType::uint64_datatype(), false),
+            ColumnSchema::new("table_catalog", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("table_schema", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("table_name", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("region_number", ConcreteDataType::uint32_datatype(), false),
+            ColumnSchema::new("region_rows", ConcreteDataType::uint64_datatype(), false),
+            ColumnSchema::new("disk_size", ConcreteDataType::uint64_datatype(), false),
+            ColumnSchema::new("memtable_size", ConcreteDataType::uint64_datatype(), false),
+            ColumnSchema::new("peer_id", ConcreteDataType::uint64_datatype(), true),
+            ColumnSchema::new("peer_addr", ConcreteDataType::string_datatype(), true),
+        ]));
+        Self {
+            schema,
+            catalog_name,
+            catalog_manager,
+            extension,
+        }
+    }
+
+    fn builder(&self) -> InformationSchemaRegionStatisticsBuilder {
+        InformationSchemaRegionStatisticsBuilder::new(
+            self.schema.clone(),
+            self.catalog_name.clone(),
+            self.catalog_manager.clone(),
+            self.extension.clone(),
+        )
+    }
+}
+
+impl InformationStreamBuilder for InformationSchemaRegionStatistics {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn to_stream(&self) -> Result<SendableRecordBatchStream> {
+        let schema = self.schema.arrow_schema().clone();
+        let mut builder = self.builder();
+        let stream = Box::pin(DfRecordBatchStreamAdapter::new(
+            schema,
+            futures::stream::once(async move {
+                builder
+                    .make_region_statistics()
+                    .await
+                    .map(|x| x.into_df_record_batch())
+                    .map_err(Into::into)
+            }),
+        ));
+        Ok(Box::pin(
+            RecordBatchStreamAdapter::try_new(stream)
+                .map_err(BoxedError::new)
+                .context(InternalSnafu)?,
+        ))
+    }
+}
+
+/// Builds the `information_schema.REGION_STATISTICS` table row by row
+///
+/// In cluster mode, the stats are the ones last reported by the datanodes' heartbeats.
+struct InformationSchemaRegionStatisticsBuilder {
+    schema: SchemaRef,
+    catalog_name: String,
+    catalog_manager: Weak<dyn CatalogManager>,
+    extension: Option<InformationExtensionRef>,
+
+    region_ids: UInt64VectorBuilder,
+    catalog_names: StringVectorBuilder,
+    schema_names: StringVectorBuilder,
+    table_names: StringVectorBuilder,
+    region_numbers: UInt32VectorBuilder,
+    region_rows: UInt64VectorBuilder,
+    disk_sizes: UInt64VectorBuilder,
+    memtable_sizes: UInt64VectorBuilder,
+    peer_ids: UInt64VectorBuilder,
+    peer_addrs: StringVectorBuilder,
+}
+
+impl InformationSchemaRegionStatisticsBuilder {
+    fn new(
+        schema: SchemaRef,
+        catalog_name: String,
+        catalog_manager: Weak<dyn CatalogManager>,
+        extension: Option<InformationExtensionRef>,
+    ) -> Self {
+        Self {
+            schema,
+            catalog_name,
+            catalog_manager,
+            extension,
+            region_ids: UInt64VectorBuilder::with_capacity(42),
+            catalog_names: StringVectorBuilder::with_capacity(42),
+            schema_names: StringVectorBuilder::with_capacity(42),
+            table_names: StringVectorBuilder::with_capacity(42),
+            region_numbers: UInt32VectorBuilder::with_capacity(42),
+            region_rows: UInt64VectorBuilder::with_capacity(42),
+            disk_sizes: UInt64VectorBuilder::with_capacity(42),
+            memtable_sizes: UInt64VectorBuilder::with_capacity(42),
+            peer_ids: UInt64VectorBuilder::with_capacity(42),
+            peer_addrs: StringVectorBuilder::with_capacity(42),
+        }
+    }
+
+    /// Construct the `information_schema.region_statistics` virtual table
+    async fn make_region_statistics(&mut self) -> Result<RecordBatch> {
+        if let Some(extension) = self.extension.clone() {
+            for stat in extension.datanode_stats().await? {
+                for region_stat in &stat.region_stats {
+                    if region_stat.catalog == self.catalog_name {
+                        self.add_region_stat(region_stat, Some(&stat));
+                    }
+                }
+            }
+
+            return self.finish();
+        }
+
+        let catalog_name = self.catalog_name.clone();
+        let catalog_manager = self
+            .catalog_manager
+            .upgrade()
+            .context(UpgradeWeakCatalogManagerRefSnafu)?;
+
+        for schema_name in catalog_manager.schema_names(&catalog_name).await? {
+            if schema_name == INFORMATION_SCHEMA_NAME {
+                continue;
+            }
+
+            for table_name in catalog_manager
+                .table_names(&catalog_name, &schema_name)
+                .await?
+            {
+                let Some(table) = catalog_manager.table(&catalog_name, &schema_name, &table_name).await? else { continue };
+                // Tables not backed by regions, e.g. `numbers`, don't have region stats.
+                let Ok(stats) = table.region_stats() else { continue };
+
+                for stat in stats {
+                    let region_stat = RegionStat {
+                        id: stat.region_id,
+                        catalog: catalog_name.clone(),
+                        schema: schema_name.clone(),
+                        table: table_name.clone(),
+                        approximate_bytes: stat.disk_usage_bytes as i64,
+                        approximate_rows: stat.num_rows as i64,
+                        memtable_size: stat.memtable_size as i64,
+                        ..Default::default()
+                    };
+                    self.add_region_stat(&region_stat, None);
+                }
+            }
+        }
+
+        self.finish()
+    }
+
+    fn add_region_stat(&mut self, region_stat: &RegionStat, peer: Option<&Stat>) {
+        self.region_ids.push(Some(region_stat.id));
+        self.catalog_names.push(Some(&region_stat.catalog));
+        self.schema_names.push(Some(&region_stat.schema));
+        self.table_names.push(Some(&region_stat.table));
+        self.region_numbers
+            .push(Some(RegionId::from(region_stat.id).region_number()));
+        self.region_rows
+            .push(Some(region_stat.approximate_rows.max(0) as u64));
+        self.disk_sizes
+            .push(Some(region_stat.approximate_bytes.max(0) as u64));
+        self.memtable_sizes
+            .push(Some(region_stat.memtable_size.max(0) as u64));
+        self.peer_ids.push(peer.map(|stat| stat.id));
+        self.peer_addrs.push(peer.map(|stat| stat.addr.as_str()));
+    }
+
+    fn finish(&mut self) -> Result<RecordBatch> {
+        let columns: Vec<VectorRef> = vec![
+            Arc::new(self.region_ids.finish()),
+            Arc::new(self.catalog_names.finish()),
+            Arc::new(self.schema_names.finish()),
+            Arc::new(self.table_names.finish()),
+            Arc::new(self.region_numbers.finish()),
+            Arc::new(self.region_rows.finish()),
+            Arc::new(self.disk_sizes.finish()),
+            Arc::new(self.memtable_sizes.finish()),
+            Arc::new(self.peer_ids.finish()),
+            Arc::new(self.peer_addrs.finish()),
+        ];
+        RecordBatch::new(self.schema.clone(), columns).context(CreateRecordBatchSnafu)
+    }
+}
+
+impl DfPartitionStream for InformationSchemaRegionStatistics {
+    fn schema(&self) -> &ArrowSchemaRef {
+        self.schema.arrow_schema()
+    }
+
+    fn execute(&self, _: Arc<TaskContext>) -> DfSendableRecordBatchStream {
+        let schema = self.schema.arrow_schema().clone();
+        let mut builder = self.builder();
+        Box::pin(DfRecordBatchStreamAdapter::new(
+            schema,
+            futures::stream::once(async move {
+                builder
+                    .make_region_statistics()
+                    .await
+                    .map(|x| x.into_df_record_batch())
+                    .map_err(Into::into)
+            }),
+        ))
+    }
+}
@@ -22,6 +22,7 @@ use std::fmt::{Debug, Formatter};
 use std::sync::Arc;
 
 use api::v1::meta::{RegionStat, TableIdent, TableName};
+use common_meta::datanode::REGION_STAT_MEMTABLE_SIZE;
 use common_telemetry::{info, warn};
 use snafu::ResultExt;
 use table::engine::{EngineContext, TableEngineRef};
@@ -246,7 +247,14 @@ pub async fn datanode_stat(catalog_manager: &CatalogManagerRef) -> (u64, Vec<Reg
                                 engine: engine.clone(),
                             }),
                             approximate_bytes: stat.disk_usage_bytes as i64,
-                            attrs: HashMap::from([("engine_name".to_owned(), engine.clone())]),
+                            approximate_rows: stat.num_rows as i64,
+                            attrs: HashMap::from([
+                                ("engine_name".to_owned(), engine.clone()),
+                                (
+                                    REGION_STAT_MEMTABLE_SIZE.to_owned(),
+                                    stat.memtable_size.to_string(),
+                                ),
+                            ]),
                             ..Default::default()
                         });
 
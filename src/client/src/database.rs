@@ -23,12 +23,16 @@ use api::v1::{
 };
 use arrow_flight::{FlightData, Ticket};
 use common_error::ext::{BoxedError, ErrorExt};
-use common_grpc::flight::{flight_messages_to_recordbatches, FlightDecoder, FlightMessage};
+use common_grpc::flight::{
+    flight_messages_to_recordbatches, FlightDecoder, FlightMessage, FLIGHT_METRICS_KEY,
+};
 use common_query::Output;
+use common_recordbatch::RecordBatchMetrics;
 use common_telemetry::{logging, timer};
 use futures_util::{TryFutureExt, TryStreamExt};
 use prost::Message;
 use snafu::{ensure, OptionExt, ResultExt};
+use tonic::metadata::MetadataValue;
 
 use crate::error::{
     ConvertFlightDataSnafu, IllegalDatabaseResponseSnafu, IllegalFlightMessagesSnafu,
@@ -185,6 +189,22 @@ impl Database {
         .await
     }
 
+    /// Executes the `logical_plan` like [Database::logical_plan], and also returns the
+    /// metrics of the physical plan executed at the server side.
+    pub async fn logical_plan_with_metrics(
+        &self,
+        logical_plan: Vec<u8>,
+    ) -> Result<(Output, Option<RecordBatchMetrics>)> {
+        let _timer = timer!(metrics::METRIC_GRPC_LOGICAL_PLAN);
+        self.do_get_inner(
+            Request::Query(QueryRequest {
+                query: Some(Query::LogicalPlan(logical_plan)),
+            }),
+            true,
+        )
+        .await
+    }
+
     pub async fn prom_range_query(
         &self,
         promql: &str,
@@ -245,12 +265,26 @@ impl Database {
     }
 
     async fn do_get(&self, request: Request) -> Result<Output> {
+        let (output, _) = self.do_get_inner(request, false).await?;
+        Ok(output)
+    }
+
+    async fn do_get_inner(
+        &self,
+        request: Request,
+        with_metrics: bool,
+    ) -> Result<(Output, Option<RecordBatchMetrics>)> {
         // FIXME(paomian): should be added some labels for metrics
         let _timer = timer!(metrics::METRIC_GRPC_DO_GET);
         let request = self.to_rpc_request(request);
-        let request = Ticket {
+        let mut request = tonic::Request::new(Ticket {
             ticket: request.encode_to_vec().into(),
-        };
+        });
+        if with_metrics {
+            let _ = request
+                .metadata_mut()
+                .insert(FLIGHT_METRICS_KEY, MetadataValue::from_static("true"));
+        }
 
         let mut client = self.client.make_flight_client()?;
 
@@ -284,11 +318,25 @@ impl Database {
             })?;
 
         let decoder = &mut FlightDecoder::default();
-        let flight_messages = flight_data
+        let mut flight_messages = flight_data
             .into_iter()
             .map(|x| decoder.try_decode(x).context(ConvertFlightDataSnafu))
             .collect::<Result<Vec<_>>>()?;
 
+        // The plan metrics, if any, are sent after the last record batch.
+        let metrics = if let Some(FlightMessage::Metrics(_)) = flight_messages.last() {
+            let Some(FlightMessage::Metrics(metrics)) = flight_messages.pop() else { unreachable!() };
+            let metrics = serde_json::from_str::<RecordBatchMetrics>(&metrics).map_err(|e| {
+                IllegalFlightMessagesSnafu {
+                    reason: format!("Failed to parse plan metrics: {e}"),
+                }
+                .build()
+            })?;
+            Some(metrics)
+        } else {
+            None
+        };
+
         let output = if let Some(FlightMessage::AffectedRows(rows)) = flight_messages.get(0) {
             ensure!(
                 flight_messages.len() == 1,
@@ -302,7 +350,7 @@ impl Database {
                 .context(ConvertFlightDataSnafu)?;
             Output::RecordBatches(recordbatches)
         };
-        Ok(output)
+        Ok((output, metrics))
     }
 }
 
@@ -19,6 +19,7 @@ use common_base::Plugins;
 use common_telemetry::logging;
 use frontend::frontend::FrontendOptions;
 use frontend::instance::{FrontendInstance, Instance as FeInstance};
+use frontend::quota::install_quota_interceptors;
 use frontend::service_config::{InfluxdbOptions, PrometheusOptions};
 use meta_client::MetaClientOptions;
 use servers::auth::UserProviderRef;
@@ -207,6 +208,7 @@ impl StartCommand {
         logging::info!("Frontend options: {:#?}", opts);
 
         let plugins = Arc::new(load_frontend_plugins(&self.user_provider)?);
+        install_quota_interceptors(&plugins, &opts.quota).context(error::StartFrontendSnafu)?;
 
         let mut instance = FeInstance::try_new_distributed(&opts, plugins.clone())
             .await
@@ -22,9 +22,10 @@ use datanode::datanode::{Datanode, DatanodeOptions, ProcedureConfig, StorageConf
 use datanode::instance::InstanceRef;
 use frontend::frontend::FrontendOptions;
 use frontend::instance::{FrontendInstance, Instance as FeInstance};
+use frontend::quota::{install_quota_interceptors, QuotaOptions};
 use frontend::service_config::{
-    GrpcOptions, InfluxdbOptions, MysqlOptions, OpentsdbOptions, PostgresOptions, PromStoreOptions,
-    PrometheusOptions,
+    GraphiteOptions, GrpcOptions, InfluxdbOptions, MysqlOptions, OpentsdbOptions, PostgresOptions,
+    PromStoreOptions, PrometheusOptions,
 };
 use serde::{Deserialize, Serialize};
 use servers::http::HttpOptions;
@@ -88,6 +89,7 @@ pub struct StandaloneOptions {
     pub mysql_options: Option<MysqlOptions>,
     pub postgres_options: Option<PostgresOptions>,
     pub opentsdb_options: Option<OpentsdbOptions>,
+    pub graphite_options: Option<GraphiteOptions>,
     pub influxdb_options: Option<InfluxdbOptions>,
     pub prom_store_options: Option<PromStoreOptions>,
     pub prometheus_options: Option<PrometheusOptions>,
@@ -95,6 +97,7 @@ pub struct StandaloneOptions {
     pub storage: StorageConfig,
     pub procedure: ProcedureConfig,
     pub logging: LoggingOptions,
+    pub quota: QuotaOptions,
 }
 
 impl Default for StandaloneOptions {
@@ -107,6 +110,7 @@ impl Default for StandaloneOptions {
             mysql_options: Some(MysqlOptions::default()),
             postgres_options: Some(PostgresOptions::default()),
             opentsdb_options: Some(OpentsdbOptions::default()),
+            graphite_options: None,
             influxdb_options: Some(InfluxdbOptions::default()),
             prom_store_options: Some(PromStoreOptions::default()),
             prometheus_options: Some(PrometheusOptions::default()),
@@ -114,6 +118,7 @@ impl Default for StandaloneOptions {
             storage: StorageConfig::default(),
             procedure: ProcedureConfig::default(),
             logging: LoggingOptions::default(),
+            quota: QuotaOptions::default(),
         }
     }
 }
@@ -127,11 +132,13 @@ impl StandaloneOptions {
             mysql_options: self.mysql_options,
             postgres_options: self.postgres_options,
             opentsdb_options: self.opentsdb_options,
+            graphite_options: self.graphite_options,
             influxdb_options: self.influxdb_options,
             prom_store_options: self.prom_store_options,
             prometheus_options: self.prometheus_options,
             meta_client_options: None,
             logging: self.logging,
+            quota: self.quota,
             ..Default::default()
         }
     }
@@ -302,6 +309,7 @@ impl StartCommand {
 
     async fn build(self, fe_opts: FrontendOptions, dn_opts: DatanodeOptions) -> Result<Instance> {
         let plugins = Arc::new(load_frontend_plugins(&self.user_provider)?);
+        install_quota_interceptors(&plugins, &fe_opts.quota).context(StartFrontendSnafu)?;
 
         info!("Standalone start command: {:#?}", self);
         info!(
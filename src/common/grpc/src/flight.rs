@@ -33,11 +33,17 @@ use crate::error::{
     Result,
 };
 
+/// The key of gRPC request metadata for asking the server to send the plan metrics
+/// ([FlightMessage::Metrics]) after the last record batch.
+pub const FLIGHT_METRICS_KEY: &str = "x-greptime-flight-metrics";
+
 #[derive(Debug, Clone)]
 pub enum FlightMessage {
     Schema(SchemaRef),
     Recordbatch(RecordBatch),
     AffectedRows(usize),
+    /// JSON encoded [RecordBatchMetrics](common_recordbatch::RecordBatchMetrics).
+    Metrics(String),
 }
 
 pub struct FlightEncoder {
@@ -93,6 +99,16 @@ impl FlightEncoder {
                     vec![],
                 )
             }
+            FlightMessage::Metrics(metrics) => {
+                // Metrics are carried in the data body, with an empty `FlightMetadata` to
+                // tell them apart from the affected rows.
+                FlightData::new(
+                    None,
+                    IpcMessage(build_none_flight_msg().into()),
+                    vec![],
+                    metrics.into_bytes(),
+                )
+            }
         }
     }
 }
@@ -118,6 +134,16 @@ impl FlightDecoder {
                 if let Some(AffectedRows { value }) = metadata.affected_rows {
                     return Ok(FlightMessage::AffectedRows(value as _));
                 }
+                if !flight_data.data_body.is_empty() {
+                    let metrics =
+                        String::from_utf8(flight_data.data_body.to_vec()).map_err(|e| {
+                            InvalidFlightDataSnafu {
+                                reason: e.to_string(),
+                            }
+                            .build()
+                        })?;
+                    return Ok(FlightMessage::Metrics(metrics));
+                }
                 InvalidFlightDataSnafu {
                     reason: "Expecting FlightMetadata have some meaningful content.",
                 }
@@ -278,6 +304,22 @@ mod test {
         assert_eq!(actual_batch, batch2);
     }
 
+    #[test]
+    fn test_encode_decode_affected_rows_and_metrics() {
+        let mut encoder = FlightEncoder::default();
+        let decoder = &mut FlightDecoder::default();
+
+        let flight_data = encoder.encode(FlightMessage::AffectedRows(42));
+        let message = decoder.try_decode(flight_data).unwrap();
+        assert!(matches!(message, FlightMessage::AffectedRows(42)));
+
+        let metrics = r#"{"plan_metrics":[]}"#.to_string();
+        let flight_data = encoder.encode(FlightMessage::Metrics(metrics.clone()));
+        let message = decoder.try_decode(flight_data).unwrap();
+        let FlightMessage::Metrics(actual) = message else { unreachable!() };
+        assert_eq!(actual, metrics);
+    }
+
     #[test]
     fn test_flight_messages_to_recordbatches() {
         let schema = Arc::new(Schema::new(vec![ColumnSchema::new(
@@ -12,11 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+//! Datanode statistics reported to metasrv by heartbeats.
+
 use api::v1::meta::HeartbeatRequest;
 use common_time::util as time_util;
 use serde::{Deserialize, Serialize};
 
-use crate::keys::StatKey;
+/// Key prefix of the datanode stats kept in the in-memory store of metasrv. The stats of
+/// a datanode are stored under `{DN_STAT_PREFIX}-{cluster_id}-{node_id}` as a JSON array
+/// of the recent [Stat]s.
+pub const DN_STAT_PREFIX: &str = "__meta_dnstat";
+
+/// Attribute of [api::v1::meta::RegionStat] holding the bytes allocated by memtables.
+pub const REGION_STAT_MEMTABLE_SIZE: &str = "memtable_size";
 
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct Stat {
@@ -51,15 +59,9 @@ pub struct RegionStat {
     pub approximate_bytes: i64,
     /// Approximate number of rows in this region
     pub approximate_rows: i64,
-}
-
-impl Stat {
-    pub fn stat_key(&self) -> StatKey {
-        StatKey {
-            cluster_id: self.cluster_id,
-            node_id: self.id,
-        }
-    }
+    /// Bytes allocated by the memtables of this region
+    #[serde(default)]
+    pub memtable_size: i64,
 }
 
 impl TryFrom<HeartbeatRequest> for Stat {
@@ -116,26 +118,42 @@ impl From<api::v1::meta::RegionStat> for RegionStat {
             wcus: value.wcus,
             approximate_bytes: value.approximate_bytes,
             approximate_rows: value.approximate_rows,
+            memtable_size: value
+                .attrs
+                .get(REGION_STAT_MEMTABLE_SIZE)
+                .and_then(|size| size.parse().ok())
+                .unwrap_or_default(),
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::handler::node_stat::Stat;
+    use std::collections::HashMap;
+
+    use super::*;
 
     #[test]
-    fn test_stat_key() {
-        let stat = Stat {
-            cluster_id: 3,
-            id: 101,
-            region_num: Some(10),
+    fn test_region_stat_from_pb() {
+        let pb = api::v1::meta::RegionStat {
+            region_id: 42,
+            approximate_bytes: 1024,
+            approximate_rows: 10,
+            attrs: HashMap::from([(REGION_STAT_MEMTABLE_SIZE.to_string(), "512".to_string())]),
             ..Default::default()
         };
+        let stat = RegionStat::from(pb);
+        assert_eq!(42, stat.id);
+        assert_eq!(1024, stat.approximate_bytes);
+        assert_eq!(10, stat.approximate_rows);
+        assert_eq!(512, stat.memtable_size);
+        assert_eq!("", stat.table);
 
-        let stat_key = stat.stat_key();
-
-        assert_eq!(3, stat_key.cluster_id);
-        assert_eq!(101, stat_key.node_id);
+        // Stats persisted before the memtable size was reported.
+        let stat: RegionStat = serde_json::from_str(
+            r#"{"id":1,"catalog":"","schema":"","table":"","rcus":0,"wcus":0,"approximate_bytes":0,"approximate_rows":0}"#,
+        )
+        .unwrap();
+        assert_eq!(0, stat.memtable_size);
     }
 }
@@ -14,6 +14,7 @@
 
 #![feature(btree_drain_filter)]
 
+pub mod datanode;
 pub mod error;
 pub mod heartbeat;
 pub mod helper;
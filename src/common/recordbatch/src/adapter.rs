@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::convert::Infallible;
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
@@ -21,7 +22,10 @@ use datafusion::arrow::datatypes::SchemaRef as DfSchemaRef;
 use datafusion::error::Result as DfResult;
 use datafusion::parquet::arrow::async_reader::{AsyncFileReader, ParquetRecordBatchStream};
 use datafusion::physical_plan::metrics::BaselineMetrics;
-use datafusion::physical_plan::RecordBatchStream as DfRecordBatchStream;
+use datafusion::physical_plan::{
+    accept, displayable, ExecutionPlan, ExecutionPlanVisitor,
+    RecordBatchStream as DfRecordBatchStream,
+};
 use datafusion_common::DataFusionError;
 use datatypes::schema::{Schema, SchemaRef};
 use futures::ready;
@@ -29,8 +33,8 @@ use snafu::ResultExt;
 
 use crate::error::{self, Result};
 use crate::{
-    DfRecordBatch, DfSendableRecordBatchStream, RecordBatch, RecordBatchStream,
-    SendableRecordBatchStream, Stream,
+    DfRecordBatch, DfSendableRecordBatchStream, PlanMetrics, RecordBatch, RecordBatchMetrics,
+    RecordBatchStream, SendableRecordBatchStream, Stream,
 };
 
 type FutureStream = Pin<
@@ -117,6 +121,15 @@ pub struct RecordBatchStreamAdapter {
     schema: SchemaRef,
     stream: DfSendableRecordBatchStream,
     metrics: Option<BaselineMetrics>,
+    /// Metrics of the plan that produced `stream`.
+    plan_metrics: PlanMetricsState,
+}
+
+enum PlanMetricsState {
+    Unavailable,
+    /// The plan is still running, metrics are collected once the stream ends.
+    Unresolved(Arc<dyn ExecutionPlan>),
+    Resolved(RecordBatchMetrics),
 }
 
 impl RecordBatchStreamAdapter {
@@ -127,6 +140,7 @@ impl RecordBatchStreamAdapter {
             schema,
             stream,
             metrics: None,
+            plan_metrics: PlanMetricsState::Unavailable,
         })
     }
 
@@ -140,14 +154,34 @@ impl RecordBatchStreamAdapter {
             schema,
             stream,
             metrics: Some(metrics),
+            plan_metrics: PlanMetricsState::Unavailable,
         })
     }
+
+    /// Creates an adapter for the `stream` executed from `plan`. Metrics of the
+    /// whole plan tree are available by [RecordBatchStream::metrics] once the
+    /// stream is exhausted.
+    pub fn try_new_with_plan(
+        stream: DfSendableRecordBatchStream,
+        plan: Arc<dyn ExecutionPlan>,
+    ) -> Result<Self> {
+        let mut adapter = Self::try_new(stream)?;
+        adapter.plan_metrics = PlanMetricsState::Unresolved(plan);
+        Ok(adapter)
+    }
 }
 
 impl RecordBatchStream for RecordBatchStreamAdapter {
     fn schema(&self) -> SchemaRef {
         self.schema.clone()
     }
+
+    fn metrics(&self) -> Option<RecordBatchMetrics> {
+        match &self.plan_metrics {
+            PlanMetricsState::Resolved(metrics) => Some(metrics.clone()),
+            PlanMetricsState::Unavailable | PlanMetricsState::Unresolved(_) => None,
+        }
+    }
 }
 
 impl Stream for RecordBatchStreamAdapter {
@@ -169,7 +203,13 @@ impl Stream for RecordBatchStreamAdapter {
                     df_record_batch,
                 )))
             }
-            Poll::Ready(None) => Poll::Ready(None),
+            Poll::Ready(None) => {
+                if let PlanMetricsState::Unresolved(plan) = &self.plan_metrics {
+                    let metrics = collect_plan_metrics(plan.as_ref());
+                    self.plan_metrics = PlanMetricsState::Resolved(metrics);
+                }
+                Poll::Ready(None)
+            }
         }
     }
 
@@ -179,6 +219,58 @@ impl Stream for RecordBatchStreamAdapter {
     }
 }
 
+/// Collects the metrics of every node in the `plan` tree.
+pub fn collect_plan_metrics(plan: &dyn ExecutionPlan) -> RecordBatchMetrics {
+    let mut collector = MetricCollector::default();
+    // `MetricCollector` never fails.
+    let _ = accept(plan, &mut collector);
+    collector.record_batch_metrics
+}
+
+#[derive(Default)]
+struct MetricCollector {
+    current_level: usize,
+    record_batch_metrics: RecordBatchMetrics,
+}
+
+impl ExecutionPlanVisitor for MetricCollector {
+    type Error = Infallible;
+
+    fn pre_visit(&mut self, plan: &dyn ExecutionPlan) -> std::result::Result<bool, Self::Error> {
+        let metrics = plan
+            .metrics()
+            .map(|metrics| {
+                metrics
+                    .aggregate_by_name()
+                    .sorted_for_display()
+                    .timestamps_removed()
+                    .iter()
+                    .map(|metric| {
+                        let value = metric.value();
+                        (value.name().to_string(), value.as_usize())
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        self.record_batch_metrics.plan_metrics.push(PlanMetrics {
+            plan: displayable(plan)
+                .one_line()
+                .to_string()
+                .trim_end()
+                .to_string(),
+            level: self.current_level,
+            metrics,
+        });
+        self.current_level += 1;
+        Ok(true)
+    }
+
+    fn post_visit(&mut self, _plan: &dyn ExecutionPlan) -> std::result::Result<bool, Self::Error> {
+        self.current_level -= 1;
+        Ok(true)
+    }
+}
+
 enum AsyncRecordBatchStreamAdapterState {
     Uninit(FutureStream),
     Ready(DfSendableRecordBatchStream),
@@ -248,9 +340,12 @@ mod test {
     use common_error::ext::BoxedError;
     use common_error::mock::MockError;
     use common_error::status_code::StatusCode;
+    use datafusion::physical_plan::memory::MemoryExec;
+    use datafusion::prelude::SessionContext;
     use datatypes::prelude::ConcreteDataType;
     use datatypes::schema::ColumnSchema;
     use datatypes::vectors::Int32Vector;
+    use futures::StreamExt;
     use snafu::IntoError;
 
     use super::*;
@@ -343,4 +438,42 @@ mod test {
             "Failed to init Recordbatch stream, source: External error: External error, source: Internal"
         );
     }
+
+    #[tokio::test]
+    async fn test_recordbatch_stream_adapter_with_plan() {
+        let schema = Arc::new(Schema::new(vec![ColumnSchema::new(
+            "a",
+            ConcreteDataType::int32_datatype(),
+            false,
+        )]));
+        let batch = RecordBatch::new(
+            schema.clone(),
+            vec![Arc::new(Int32Vector::from_slice([1, 2, 3])) as _],
+        )
+        .unwrap();
+        let plan: Arc<dyn ExecutionPlan> = Arc::new(
+            MemoryExec::try_new(
+                &[vec![batch.clone().into_df_record_batch()]],
+                schema.arrow_schema().clone(),
+                None,
+            )
+            .unwrap(),
+        );
+        let df_stream = plan.execute(0, SessionContext::new().task_ctx()).unwrap();
+
+        let mut adapter = RecordBatchStreamAdapter::try_new_with_plan(df_stream, plan).unwrap();
+        // Metrics are not available until the stream is exhausted.
+        assert!(adapter.metrics().is_none());
+
+        let mut collected = vec![];
+        while let Some(batch) = adapter.next().await {
+            collected.push(batch.unwrap());
+        }
+        assert_eq!(collected, vec![batch]);
+
+        let metrics = adapter.metrics().unwrap();
+        assert_eq!(metrics.plan_metrics.len(), 1);
+        assert!(metrics.plan_metrics[0].plan.starts_with("MemoryExec"));
+        assert_eq!(metrics.plan_metrics[0].level, 0);
+    }
 }
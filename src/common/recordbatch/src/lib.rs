@@ -17,6 +17,7 @@ pub mod error;
 mod recordbatch;
 pub mod util;
 
+use std::fmt::{Display, Formatter};
 use std::pin::Pin;
 use std::sync::Arc;
 
@@ -31,6 +32,7 @@ use error::Result;
 use futures::task::{Context, Poll};
 use futures::{Stream, TryStreamExt};
 pub use recordbatch::RecordBatch;
+use serde::{Deserialize, Serialize};
 use snafu::{ensure, ResultExt};
 
 pub trait RecordBatchStream: Stream<Item = Result<RecordBatch>> {
@@ -39,6 +41,12 @@ pub trait RecordBatchStream: Stream<Item = Result<RecordBatch>> {
     fn output_ordering(&self) -> Option<&[OrderOption]> {
         None
     }
+
+    /// Metrics of the plan (or scan) that produced this stream. Only available
+    /// once the stream is exhausted.
+    fn metrics(&self) -> Option<RecordBatchMetrics> {
+        None
+    }
 }
 
 pub type SendableRecordBatchStream = Pin<Box<dyn RecordBatchStream + Send>>;
@@ -49,6 +57,46 @@ pub struct OrderOption {
     pub options: SortOptions,
 }
 
+/// Metrics of the plan tree that produced a [RecordBatchStream].
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordBatchMetrics {
+    /// Metrics of each plan node, in pre-order.
+    pub plan_metrics: Vec<PlanMetrics>,
+}
+
+/// Metrics of a single plan node.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlanMetrics {
+    /// One-line description of the plan node.
+    pub plan: String,
+    /// Depth of the node in the plan tree, the root is at level 0.
+    pub level: usize,
+    /// Metric values aggregated by name.
+    pub metrics: Vec<(String, usize)>,
+}
+
+impl Display for RecordBatchMetrics {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for plan_metrics in &self.plan_metrics {
+            write!(
+                f,
+                "{:indent$}{}, metrics=[",
+                "",
+                plan_metrics.plan,
+                indent = plan_metrics.level * 2
+            )?;
+            for (i, (name, value)) in plan_metrics.metrics.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{name}={value}")?;
+            }
+            writeln!(f, "]")?;
+        }
+        Ok(())
+    }
+}
+
 /// EmptyRecordBatchStream can be used to create a RecordBatchStream
 /// that will produce no results
 pub struct EmptyRecordBatchStream {
@@ -316,4 +364,27 @@ mod tests {
         assert_eq!(collected[0], batch1);
         assert_eq!(collected[1], batch2);
     }
+
+    #[test]
+    fn test_display_record_batch_metrics() {
+        let metrics = RecordBatchMetrics {
+            plan_metrics: vec![
+                PlanMetrics {
+                    plan: "CoalescePartitionsExec".to_string(),
+                    level: 0,
+                    metrics: vec![("output_rows".to_string(), 3)],
+                },
+                PlanMetrics {
+                    plan: "MitoTableScan".to_string(),
+                    level: 1,
+                    metrics: vec![("sst_files".to_string(), 2), ("row_groups".to_string(), 4)],
+                },
+            ],
+        };
+        let expected = "\
+CoalescePartitionsExec, metrics=[output_rows=3]
+  MitoTableScan, metrics=[sst_files=2, row_groups=4]
+";
+        assert_eq!(expected, metrics.to_string());
+    }
 }
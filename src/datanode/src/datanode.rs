@@ -41,7 +41,8 @@ use crate::heartbeat::HeartbeatTask;
 use crate::instance::{Instance, InstanceRef};
 use crate::server::Services;
 
-pub const DEFAULT_OBJECT_STORE_CACHE_SIZE: ReadableSize = ReadableSize(1024);
+/// Default capacity of the local read cache of object stores, in bytes.
+pub const DEFAULT_OBJECT_STORE_CACHE_SIZE: ReadableSize = ReadableSize::gb(1);
 
 /// Default data home in file storage
 const DEFAULT_DATA_HOME: &str = "/tmp/greptimedb";
@@ -204,10 +205,24 @@ impl Default for ObjectStoreConfig {
     }
 }
 
+/// Where the WAL is stored.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WalProvider {
+    /// Local files managed by raft-engine.
+    #[default]
+    RaftEngine,
+    /// The object store of the datanode, so regions can be replayed by other datanodes
+    /// sharing the same object store, e.g. after region failover.
+    ObjectStore,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct WalConfig {
-    // wal directory
+    // wal provider
+    pub provider: WalProvider,
+    // wal directory, a path in the object store if the provider is `object_store`
     pub dir: Option<String>,
     // wal file size in bytes
     pub file_size: ReadableSize,
@@ -225,6 +240,7 @@ pub struct WalConfig {
 impl Default for WalConfig {
     fn default() -> Self {
         Self {
+            provider: WalProvider::default(),
             dir: None,
             file_size: ReadableSize::mb(256), // log file size 256MB
             purge_threshold: ReadableSize::gb(4), // purge threshold 4GB
@@ -485,6 +501,21 @@ mod tests {
         let _parsed: DatanodeOptions = toml::from_str(&toml_string).unwrap();
     }
 
+    #[test]
+    fn test_wal_provider() {
+        let opts: DatanodeOptions = toml::from_str("").unwrap();
+        assert_eq!(WalProvider::RaftEngine, opts.wal.provider);
+
+        let toml_str = r#"
+            [wal]
+            provider = "object_store"
+            dir = "cluster_wal"
+        "#;
+        let opts: DatanodeOptions = toml::from_str(toml_str).unwrap();
+        assert_eq!(WalProvider::ObjectStore, opts.wal.provider);
+        assert_eq!("cluster_wal", opts.wal.dir.unwrap());
+    }
+
     #[test]
     fn test_secstr() {
         let toml_str = r#"
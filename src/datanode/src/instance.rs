@@ -34,8 +34,9 @@ use common_procedure::store::state_store::ObjectStateStore;
 use common_procedure::ProcedureManagerRef;
 use common_telemetry::logging::info;
 use file_table_engine::engine::immutable::ImmutableFileTableEngine;
+use log_store::object_store_log::ObjectStoreLogStore;
 use log_store::raft_engine::log_store::RaftEngineLogStore;
-use log_store::LogConfig;
+use log_store::{LogConfig, LogStoreImpl};
 use meta_client::client::{MetaClient, MetaClientBuilder};
 use meta_client::MetaClientOptions;
 use mito::config::EngineConfig as TableEngineConfig;
@@ -57,7 +58,9 @@ use table::table::numbers::NumbersTable;
 use table::table::TableIdProviderRef;
 use table::Table;
 
-use crate::datanode::{DatanodeOptions, ObjectStoreConfig, ProcedureConfig, WalConfig};
+use crate::datanode::{
+    DatanodeOptions, ObjectStoreConfig, ProcedureConfig, WalConfig, WalProvider,
+};
 use crate::error::{
     self, CatalogSnafu, IncorrectInternalStateSnafu, MetaClientInitSnafu, MissingMetasrvOptsSnafu,
     MissingNodeIdSnafu, NewCatalogSnafu, OpenLogStoreSnafu, RecoverProcedureSnafu, Result,
@@ -72,7 +75,7 @@ use crate::store;
 mod grpc;
 pub mod sql;
 
-pub(crate) type DefaultEngine = MitoEngine<EngineImpl<RaftEngineLogStore>>;
+pub(crate) type DefaultEngine = MitoEngine<EngineImpl<LogStoreImpl>>;
 
 // An abstraction to read/write services.
 pub struct Instance {
@@ -158,11 +161,12 @@ impl Instance {
     pub(crate) async fn new(
         opts: &DatanodeOptions,
         meta_client: Option<Arc<MetaClient>>,
-        compaction_scheduler: CompactionSchedulerRef<RaftEngineLogStore>,
+        compaction_scheduler: CompactionSchedulerRef<LogStoreImpl>,
         plugins: Arc<Plugins>,
     ) -> Result<(InstanceRef, Option<HeartbeatTask>)> {
         let object_store = store::new_object_store(&opts.storage.store).await?;
-        let log_store = Arc::new(create_log_store(&opts.storage.store, &opts.wal).await?);
+        let log_store =
+            Arc::new(create_log_store(&opts.storage.store, &opts.wal, object_store.clone()).await?);
 
         let mito_engine = Arc::new(DefaultEngine::new(
             TableEngineConfig {
@@ -435,7 +439,17 @@ async fn new_metasrv_client(node_id: u64, meta_config: &MetaClientOptions) -> Re
 pub(crate) async fn create_log_store(
     store_config: &ObjectStoreConfig,
     wal_config: &WalConfig,
-) -> Result<RaftEngineLogStore> {
+    object_store: ObjectStore,
+) -> Result<LogStoreImpl> {
+    if wal_config.provider == WalProvider::ObjectStore {
+        let wal_dir = wal_config.dir.as_deref().unwrap_or(WAL_DIR);
+        info!(
+            "Creating object store logstore with config: {:?} and directory: {}",
+            wal_config, wal_dir
+        );
+        return Ok(ObjectStoreLogStore::new(wal_dir, object_store).into());
+    }
+
     let wal_dir = match (&wal_config.dir, store_config) {
         (Some(dir), _) => dir.to_string(),
         (None, ObjectStoreConfig::File(file_config)) => {
@@ -464,7 +478,7 @@ pub(crate) async fn create_log_store(
         .await
         .map_err(Box::new)
         .context(OpenLogStoreSnafu)?;
-    Ok(logstore)
+    Ok(logstore.into())
 }
 
 pub(crate) async fn create_procedure_manager(
@@ -187,7 +187,7 @@ impl Instance {
         };
         let mut stmt = QueryLanguageParser::parse_promql(&query).context(ExecuteSqlSnafu)?;
         match &mut stmt {
-            QueryStatement::Sql(_) => unreachable!(),
+            QueryStatement::Sql(_) | QueryStatement::Influxql(_) => unreachable!(),
             QueryStatement::Promql(eval_stmt) => {
                 eval_stmt.start = start;
                 eval_stmt.end = end;
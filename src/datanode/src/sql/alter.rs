@@ -17,7 +17,8 @@ use common_query::Output;
 use common_telemetry::logging::info;
 use snafu::prelude::*;
 use sql::statements::alter::{AlterTable, AlterTableOperation};
-use sql::statements::column_def_to_schema;
+use sql::statements::{column_def_to_schema, sql_data_type_to_concrete_data_type};
+use sql::util::to_lowercase_options_map;
 use table::engine::TableReference;
 use table::metadata::TableId;
 use table::requests::{AddColumnRequest, AlterKind, AlterTableRequest};
@@ -88,6 +89,24 @@ impl SqlHandler {
             AlterTableOperation::RenameTable { new_table_name } => AlterKind::RenameTable {
                 new_table_name: new_table_name.clone(),
             },
+            AlterTableOperation::SetTableOptions { options } => AlterKind::SetTableOptions {
+                options: to_lowercase_options_map(options),
+            },
+            AlterTableOperation::ModifyColumnType {
+                column_name,
+                target_type,
+            } => AlterKind::ModifyColumnType {
+                column_name: column_name.value.clone(),
+                target_type: sql_data_type_to_concrete_data_type(target_type)
+                    .context(error::ParseSqlSnafu)?,
+            },
+            AlterTableOperation::RenameColumn {
+                column_name,
+                new_column_name,
+            } => AlterKind::RenameColumn {
+                name: column_name.value.clone(),
+                new_name: new_column_name.value.clone(),
+            },
         };
         Ok(AlterTableRequest {
             catalog_name: table_ref.catalog.to_string(),
@@ -177,6 +196,57 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_alter_to_request_with_altering_columns_and_options() {
+        let alter_table = parse_sql("ALTER TABLE test_table SET ('TTL'='7d');");
+        let req = SqlHandler::alter_to_request(
+            alter_table,
+            TableReference::full("greptime", "public", "test_table"),
+            1,
+        )
+        .unwrap();
+        match req.alter_kind {
+            AlterKind::SetTableOptions { options } => {
+                assert_eq!(1, options.len());
+                assert_eq!("7d", options["ttl"]);
+            }
+            _ => unreachable!(),
+        }
+
+        let alter_table = parse_sql("ALTER TABLE test_table MODIFY COLUMN cpu BIGINT;");
+        let req = SqlHandler::alter_to_request(
+            alter_table,
+            TableReference::full("greptime", "public", "test_table"),
+            1,
+        )
+        .unwrap();
+        match req.alter_kind {
+            AlterKind::ModifyColumnType {
+                column_name,
+                target_type,
+            } => {
+                assert_eq!("cpu", column_name);
+                assert_eq!(ConcreteDataType::int64_datatype(), target_type);
+            }
+            _ => unreachable!(),
+        }
+
+        let alter_table = parse_sql("ALTER TABLE test_table RENAME COLUMN cpu TO cpu_util;");
+        let req = SqlHandler::alter_to_request(
+            alter_table,
+            TableReference::full("greptime", "public", "test_table"),
+            1,
+        )
+        .unwrap();
+        match req.alter_kind {
+            AlterKind::RenameColumn { name, new_name } => {
+                assert_eq!("cpu", name);
+                assert_eq!("cpu_util", new_name);
+            }
+            _ => unreachable!(),
+        }
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_alter_table_by_procedure() {
         let instance = MockInstance::new("alter_table_by_procedure").await;
@@ -23,8 +23,8 @@ use serde::{Deserialize, Serialize};
 use crate::error::{self, Error, Result};
 use crate::type_id::LogicalTypeId;
 use crate::types::{
-    BinaryType, BooleanType, DateTimeType, DateType, DictionaryType, Float32Type, Float64Type,
-    Int16Type, Int32Type, Int64Type, Int8Type, ListType, NullType, StringType,
+    BinaryType, BooleanType, DateTimeType, DateType, Decimal128Type, DictionaryType, Float32Type,
+    Float64Type, Int16Type, Int32Type, Int64Type, Int8Type, ListType, NullType, StringType,
     TimestampMicrosecondType, TimestampMillisecondType, TimestampNanosecondType,
     TimestampSecondType, TimestampType, UInt16Type, UInt32Type, UInt64Type, UInt8Type,
 };
@@ -49,6 +49,9 @@ pub enum ConcreteDataType {
     Float32(Float32Type),
     Float64(Float64Type),
 
+    // Decimal types:
+    Decimal128(Decimal128Type),
+
     // String types:
     Binary(BinaryType),
     String(StringType),
@@ -78,6 +81,9 @@ impl fmt::Display for ConcreteDataType {
             ConcreteDataType::UInt64(_) => write!(f, "UInt64"),
             ConcreteDataType::Float32(_) => write!(f, "Float32"),
             ConcreteDataType::Float64(_) => write!(f, "Float64"),
+            ConcreteDataType::Decimal128(t) => {
+                write!(f, "Decimal128({}, {})", t.precision(), t.scale())
+            }
             ConcreteDataType::Binary(_) => write!(f, "Binary"),
             ConcreteDataType::String(_) => write!(f, "String"),
             ConcreteDataType::Date(_) => write!(f, "Date"),
@@ -126,6 +132,10 @@ impl ConcreteDataType {
         )
     }
 
+    pub fn is_decimal(&self) -> bool {
+        matches!(self, ConcreteDataType::Decimal128(_))
+    }
+
     pub fn is_unsigned(&self) -> bool {
         matches!(
             self,
@@ -171,6 +181,14 @@ impl ConcreteDataType {
         }
     }
 
+    /// Try to cast the type as a [`Decimal128Type`].
+    pub fn as_decimal128(&self) -> Option<Decimal128Type> {
+        match self {
+            ConcreteDataType::Decimal128(t) => Some(*t),
+            _ => None,
+        }
+    }
+
     /// Try to cast data type as a [`TimestampType`].
     pub fn as_timestamp(&self) -> Option<TimestampType> {
         match self {
@@ -206,6 +224,9 @@ impl TryFrom<&ArrowDataType> for ConcreteDataType {
             ArrowDataType::Int64 => Self::int64_datatype(),
             ArrowDataType::Float32 => Self::float32_datatype(),
             ArrowDataType::Float64 => Self::float64_datatype(),
+            ArrowDataType::Decimal128(precision, scale) => {
+                Self::decimal128_datatype(*precision, *scale)
+            }
             ArrowDataType::Date32 => Self::date_datatype(),
             ArrowDataType::Date64 => Self::datetime_datatype(),
             ArrowDataType::Timestamp(u, _) => ConcreteDataType::from_arrow_time_unit(u),
@@ -290,6 +311,14 @@ impl ConcreteDataType {
         }
     }
 
+    pub fn decimal128_datatype(precision: u8, scale: i8) -> ConcreteDataType {
+        ConcreteDataType::Decimal128(Decimal128Type::new(precision, scale))
+    }
+
+    pub fn decimal128_default_datatype() -> ConcreteDataType {
+        ConcreteDataType::Decimal128(Decimal128Type::default())
+    }
+
     pub fn list_datatype(item_type: ConcreteDataType) -> ConcreteDataType {
         ConcreteDataType::List(ListType::new(item_type))
     }
@@ -417,6 +446,10 @@ mod tests {
             ConcreteDataType::from_arrow_type(&ArrowDataType::Date32),
             ConcreteDataType::Date(_)
         ));
+        assert_eq!(
+            ConcreteDataType::from_arrow_type(&ArrowDataType::Decimal128(10, 2)),
+            ConcreteDataType::decimal128_datatype(10, 2)
+        );
     }
 
     #[test]
@@ -634,5 +667,9 @@ mod tests {
             ConcreteDataType::from_arrow_type(&ArrowDataType::Date32).to_string(),
             "Date"
         );
+        assert_eq!(
+            ConcreteDataType::decimal128_datatype(10, 2).to_string(),
+            "Decimal128(10, 2)"
+        );
     }
 }
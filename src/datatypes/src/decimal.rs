@@ -0,0 +1,344 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
+
+pub use arrow::datatypes::{DECIMAL128_MAX_PRECISION, DECIMAL128_MAX_SCALE, DECIMAL_DEFAULT_SCALE};
+use serde::{Deserialize, Serialize};
+use snafu::ensure;
+
+use crate::error::{self, Result};
+
+/// Decimal value with 128 bits, stored as an unscaled integer `value` whose
+/// real value is `value * 10^(-scale)`.
+///
+/// `precision` is the max number of significant digits, and `scale` is the
+/// number of digits after the decimal point.
+///
+/// Decimals are compared by their real values regardless of the precision and
+/// scale, e.g. `1.0` equals to `1.00`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Decimal128 {
+    value: i128,
+    precision: u8,
+    scale: i8,
+}
+
+impl Decimal128 {
+    /// Creates a new decimal without checking the precision and scale.
+    pub fn new(value: i128, precision: u8, scale: i8) -> Self {
+        Self {
+            value,
+            precision,
+            scale,
+        }
+    }
+
+    /// Creates a new decimal, returns error if the precision and scale are
+    /// invalid or the value overflows the precision.
+    pub fn try_new(value: i128, precision: u8, scale: i8) -> Result<Self> {
+        validate_precision_scale(precision, scale)?;
+        ensure!(
+            value.unsigned_abs() < 10u128.pow(precision as u32),
+            error::InvalidDecimalSnafu {
+                value: value.to_string(),
+                reason: format!("out of range of precision {precision}"),
+            }
+        );
+        Ok(Self::new(value, precision, scale))
+    }
+
+    /// Parses a decimal from string `s` with given `precision` and `scale`.
+    ///
+    /// Digits beyond the scale are rounded half away from zero.
+    pub fn from_str_with(s: &str, precision: u8, scale: i8) -> Result<Self> {
+        validate_precision_scale(precision, scale)?;
+        let invalid = |reason: &str| error::InvalidDecimalSnafu { value: s, reason }.fail();
+        ensure!(
+            scale >= 0,
+            error::InvalidDecimalSnafu {
+                value: s,
+                reason: "negative scale is not supported",
+            }
+        );
+
+        let trimmed = s.trim();
+        let (negative, unsigned) = match trimmed.as_bytes().first() {
+            Some(b'-') => (true, &trimmed[1..]),
+            Some(b'+') => (false, &trimmed[1..]),
+            _ => (false, trimmed),
+        };
+        let (integer, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+        if integer.is_empty() && fraction.is_empty() {
+            return invalid("no digits");
+        }
+        if !integer
+            .bytes()
+            .chain(fraction.bytes())
+            .all(|b| b.is_ascii_digit())
+        {
+            return invalid("invalid digit");
+        }
+
+        let scale_digits = scale as usize;
+        let mut value: i128 = 0;
+        let digits = integer.bytes().chain(
+            fraction
+                .bytes()
+                .chain(std::iter::repeat(b'0'))
+                .take(scale_digits),
+        );
+        for digit in digits {
+            value = match value
+                .checked_mul(10)
+                .and_then(|v| v.checked_add((digit - b'0') as i128))
+            {
+                Some(v) => v,
+                None => return invalid("overflow"),
+            };
+        }
+        if fraction.len() > scale_digits && fraction.as_bytes()[scale_digits] >= b'5' {
+            value = value.saturating_add(1);
+        }
+        if negative {
+            value = -value;
+        }
+
+        Self::try_new(value, precision, scale)
+    }
+
+    /// Returns the unscaled value.
+    pub fn val(&self) -> i128 {
+        self.value
+    }
+
+    pub fn precision(&self) -> u8 {
+        self.precision
+    }
+
+    pub fn scale(&self) -> i8 {
+        self.scale
+    }
+
+    /// Converts the decimal to a (lossy) float.
+    pub fn to_f64(&self) -> f64 {
+        self.value as f64 / 10f64.powi(self.scale as i32)
+    }
+}
+
+/// Checks whether `precision` and `scale` are valid for a 128 bits decimal.
+pub fn validate_precision_scale(precision: u8, scale: i8) -> Result<()> {
+    ensure!(
+        precision > 0
+            && precision <= DECIMAL128_MAX_PRECISION
+            && scale <= DECIMAL128_MAX_SCALE
+            && scale <= precision as i8,
+        error::InvalidDecimalPrecisionSnafu { precision, scale }
+    );
+    Ok(())
+}
+
+impl Default for Decimal128 {
+    fn default() -> Self {
+        Self::new(0, DECIMAL128_MAX_PRECISION, DECIMAL_DEFAULT_SCALE)
+    }
+}
+
+impl Display for Decimal128 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.scale <= 0 {
+            let zeros = if self.value == 0 {
+                0
+            } else {
+                self.scale.unsigned_abs() as usize
+            };
+            return write!(f, "{}{}", self.value, "0".repeat(zeros));
+        }
+
+        let scale = self.scale as usize;
+        let digits = format!("{:0>width$}", self.value.unsigned_abs(), width = scale + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - scale);
+        let sign = if self.value < 0 { "-" } else { "" };
+        write!(f, "{sign}{integer}.{fraction}")
+    }
+}
+
+impl PartialOrd for Decimal128 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Decimal128 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.scale.cmp(&other.scale) {
+            Ordering::Equal => self.value.cmp(&other.value),
+            Ordering::Less => cmp_rescaled(
+                self.value,
+                (other.scale as i32 - self.scale as i32) as u32,
+                other.value,
+            ),
+            Ordering::Greater => cmp_rescaled(
+                other.value,
+                (self.scale as i32 - other.scale as i32) as u32,
+                self.value,
+            )
+            .reverse(),
+        }
+    }
+}
+
+/// Compares `value * 10^exp` with `other`.
+fn cmp_rescaled(value: i128, exp: u32, other: i128) -> Ordering {
+    if value == 0 {
+        return 0i128.cmp(&other);
+    }
+    match 10i128
+        .checked_pow(exp)
+        .and_then(|factor| value.checked_mul(factor))
+    {
+        Some(rescaled) => rescaled.cmp(&other),
+        // The rescaled value is out of the range of i128, so it's either
+        // greater or less than any i128.
+        None if value > 0 => Ordering::Greater,
+        None => Ordering::Less,
+    }
+}
+
+impl PartialEq for Decimal128 {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal128 {}
+
+impl Hash for Decimal128 {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Strips the trailing zeros so equal decimals have the same hash.
+        let (mut value, mut scale) = (self.value, self.scale as i32);
+        if value == 0 {
+            scale = 0;
+        }
+        while value != 0 && value % 10 == 0 {
+            value /= 10;
+            scale -= 1;
+        }
+        value.hash(state);
+        scale.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decimal_display() {
+        assert_eq!("123.45", Decimal128::new(12345, 10, 2).to_string());
+        assert_eq!("-123.45", Decimal128::new(-12345, 10, 2).to_string());
+        assert_eq!("0.05", Decimal128::new(5, 10, 2).to_string());
+        assert_eq!("-0.005", Decimal128::new(-5, 10, 3).to_string());
+        assert_eq!("12", Decimal128::new(12, 10, 0).to_string());
+        assert_eq!("1200", Decimal128::new(12, 10, -2).to_string());
+        assert_eq!("0", Decimal128::new(0, 10, -2).to_string());
+    }
+
+    #[test]
+    fn test_decimal_from_str() {
+        let check = |s: &str, precision, scale, expect| {
+            let decimal = Decimal128::from_str_with(s, precision, scale).unwrap();
+            assert_eq!(Decimal128::new(expect, precision, scale), decimal);
+        };
+        check("123.45", 10, 2, 12345);
+        check("-123.45", 10, 2, -12345);
+        check("+1", 10, 2, 100);
+        check(".5", 10, 2, 50);
+        check("7.", 10, 2, 700);
+        check("0.125", 10, 2, 13);
+        check("-0.125", 10, 2, -13);
+        check("0.124", 10, 2, 12);
+        check(" 42 ", 3, 0, 42);
+
+        assert!(Decimal128::from_str_with("", 10, 2).is_err());
+        assert!(Decimal128::from_str_with(".", 10, 2).is_err());
+        assert!(Decimal128::from_str_with("1.2.3", 10, 2).is_err());
+        assert!(Decimal128::from_str_with("abc", 10, 2).is_err());
+        // Out of precision.
+        assert!(Decimal128::from_str_with("1000", 5, 2).is_err());
+        assert!(Decimal128::from_str_with("1", 10, -1).is_err());
+    }
+
+    #[test]
+    fn test_validate_precision_scale() {
+        assert!(validate_precision_scale(38, 10).is_ok());
+        assert!(validate_precision_scale(1, 0).is_ok());
+        assert!(validate_precision_scale(0, 0).is_err());
+        assert!(validate_precision_scale(39, 0).is_err());
+        assert!(validate_precision_scale(5, 6).is_err());
+
+        assert!(Decimal128::try_new(99999, 5, 2).is_ok());
+        assert!(Decimal128::try_new(-100000, 5, 2).is_err());
+    }
+
+    #[test]
+    fn test_decimal_cmp() {
+        let a = Decimal128::new(100, 10, 2);
+        let b = Decimal128::new(-100, 10, 2);
+        assert!(a > b);
+        assert_eq!(Ordering::Equal, a.cmp(&Decimal128::new(100, 10, 2)));
+        assert_eq!(1.0, a.to_f64());
+
+        // Decimals of different scales are compared by their real values.
+        assert_eq!(Decimal128::new(10, 5, 1), Decimal128::new(100, 10, 2));
+        assert_eq!(Decimal128::new(0, 5, 1), Decimal128::new(0, 10, 3));
+        assert_eq!(Decimal128::new(12, 10, -2), Decimal128::new(1200, 10, 0));
+        assert!(Decimal128::new(11, 5, 1) > Decimal128::new(109, 10, 2));
+        assert!(Decimal128::new(-11, 5, 1) < Decimal128::new(-109, 10, 2));
+        assert!(Decimal128::new(1, 38, 0) > Decimal128::new(99, 38, 2));
+        // Rescaling overflows i128.
+        assert!(Decimal128::new(i128::MAX, 38, 0) > Decimal128::new(i128::MAX, 38, 38));
+        assert!(Decimal128::new(-2, 38, -38) < Decimal128::new(i128::MIN, 38, 0));
+        assert!(Decimal128::new(0, 38, -38) > Decimal128::new(-1, 38, 38));
+    }
+
+    #[test]
+    fn test_decimal_hash() {
+        use std::collections::hash_map::DefaultHasher;
+
+        let hash = |decimal: Decimal128| {
+            let mut hasher = DefaultHasher::new();
+            decimal.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(
+            hash(Decimal128::new(10, 5, 1)),
+            hash(Decimal128::new(100, 10, 2))
+        );
+        assert_eq!(
+            hash(Decimal128::new(12, 10, -2)),
+            hash(Decimal128::new(1200, 10, 0))
+        );
+        assert_eq!(
+            hash(Decimal128::new(0, 5, 1)),
+            hash(Decimal128::new(0, 10, 3))
+        );
+        assert_ne!(
+            hash(Decimal128::new(10, 5, 1)),
+            hash(Decimal128::new(10, 10, 2))
+        );
+    }
+}
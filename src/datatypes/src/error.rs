@@ -106,6 +106,20 @@ pub enum Error {
 
     #[snafu(display("Invalid timestamp precision: {}", precision))]
     InvalidTimestampPrecision { precision: u64, location: Location },
+
+    #[snafu(display("Invalid decimal precision: {}, scale: {}", precision, scale))]
+    InvalidDecimalPrecision {
+        precision: u8,
+        scale: i8,
+        location: Location,
+    },
+
+    #[snafu(display("Invalid decimal value: {}, reason: {}", value, reason))]
+    InvalidDecimal {
+        value: String,
+        reason: String,
+        location: Location,
+    },
 }
 
 impl ErrorExt for Error {
@@ -30,6 +30,8 @@ pub enum LogicalTypeId {
     Float32,
     Float64,
 
+    Decimal128,
+
     // String types:
     String,
     Binary,
@@ -74,6 +76,7 @@ impl LogicalTypeId {
             LogicalTypeId::UInt64 => ConcreteDataType::uint64_datatype(),
             LogicalTypeId::Float32 => ConcreteDataType::float32_datatype(),
             LogicalTypeId::Float64 => ConcreteDataType::float64_datatype(),
+            LogicalTypeId::Decimal128 => ConcreteDataType::decimal128_default_datatype(),
             LogicalTypeId::String => ConcreteDataType::string_datatype(),
             LogicalTypeId::Binary => ConcreteDataType::binary_datatype(),
             LogicalTypeId::Date => ConcreteDataType::date_datatype(),
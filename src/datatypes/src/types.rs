@@ -16,6 +16,7 @@ mod binary_type;
 mod boolean_type;
 mod date_type;
 mod datetime_type;
+mod decimal_type;
 mod dictionary_type;
 mod list_type;
 mod null_type;
@@ -27,6 +28,7 @@ pub use binary_type::BinaryType;
 pub use boolean_type::BooleanType;
 pub use date_type::DateType;
 pub use datetime_type::DateTimeType;
+pub use decimal_type::Decimal128Type;
 pub use dictionary_type::DictionaryType;
 pub use list_type::ListType;
 pub use null_type::NullType;
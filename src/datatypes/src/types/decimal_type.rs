@@ -0,0 +1,110 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use arrow::datatypes::DataType as ArrowDataType;
+use serde::{Deserialize, Serialize};
+
+use crate::data_type::DataType;
+use crate::decimal::{Decimal128, DECIMAL128_MAX_PRECISION, DECIMAL_DEFAULT_SCALE};
+use crate::type_id::LogicalTypeId;
+use crate::value::Value;
+use crate::vectors::{Decimal128VectorBuilder, MutableVector};
+
+/// Decimal type with 128 bits, parameterized by precision and scale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Decimal128Type {
+    precision: u8,
+    scale: i8,
+}
+
+impl Default for Decimal128Type {
+    fn default() -> Self {
+        Self::new(DECIMAL128_MAX_PRECISION, DECIMAL_DEFAULT_SCALE)
+    }
+}
+
+impl Decimal128Type {
+    pub fn new(precision: u8, scale: i8) -> Self {
+        Self { precision, scale }
+    }
+
+    pub fn precision(&self) -> u8 {
+        self.precision
+    }
+
+    pub fn scale(&self) -> i8 {
+        self.scale
+    }
+}
+
+impl DataType for Decimal128Type {
+    fn name(&self) -> &str {
+        "Decimal128"
+    }
+
+    fn logical_type_id(&self) -> LogicalTypeId {
+        LogicalTypeId::Decimal128
+    }
+
+    fn default_value(&self) -> Value {
+        Value::Decimal128(Decimal128::new(0, self.precision, self.scale))
+    }
+
+    fn as_arrow_type(&self) -> ArrowDataType {
+        ArrowDataType::Decimal128(self.precision, self.scale)
+    }
+
+    fn create_mutable_vector(&self, capacity: usize) -> Box<dyn MutableVector> {
+        Box::new(Decimal128VectorBuilder::with_precision_and_scale(
+            capacity,
+            self.precision,
+            self.scale,
+        ))
+    }
+
+    fn is_timestamp_compatible(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_type::ConcreteDataType;
+
+    #[test]
+    fn test_decimal128_type() {
+        let t = Decimal128Type::new(10, 2);
+        assert_eq!("Decimal128", t.name());
+        assert_eq!(LogicalTypeId::Decimal128, t.logical_type_id());
+        assert_eq!(
+            Value::Decimal128(Decimal128::new(0, 10, 2)),
+            t.default_value()
+        );
+        assert_eq!(ArrowDataType::Decimal128(10, 2), t.as_arrow_type());
+
+        let mut builder = t.create_mutable_vector(2);
+        builder.push_value_ref(Decimal128::new(123, 10, 2).into());
+        builder.push_null();
+        let vector = builder.to_vector();
+        assert_eq!(
+            ConcreteDataType::decimal128_datatype(10, 2),
+            vector.data_type()
+        );
+        assert_eq!(
+            Value::Decimal128(Decimal128::new(123, 10, 2)),
+            vector.get(0)
+        );
+    }
+}
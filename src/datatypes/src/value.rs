@@ -28,6 +28,7 @@ pub use ordered_float::OrderedFloat;
 use serde::{Deserialize, Serialize};
 use snafu::ensure;
 
+use crate::decimal::Decimal128;
 use crate::error;
 use crate::error::Result;
 use crate::prelude::*;
@@ -58,6 +59,9 @@ pub enum Value {
     Float32(OrderedF32),
     Float64(OrderedF64),
 
+    // Decimal types:
+    Decimal128(Decimal128),
+
     // String types:
     String(StringBytes),
     Binary(Bytes),
@@ -85,6 +89,7 @@ impl Display for Value {
             Value::Int64(v) => write!(f, "{v}"),
             Value::Float32(v) => write!(f, "{v}"),
             Value::Float64(v) => write!(f, "{v}"),
+            Value::Decimal128(v) => write!(f, "{v}"),
             Value::String(v) => write!(f, "{}", v.as_utf8()),
             Value::Binary(v) => {
                 let hex = v
@@ -130,6 +135,7 @@ impl Value {
             Value::Int64(_) => ConcreteDataType::int64_datatype(),
             Value::Float32(_) => ConcreteDataType::float32_datatype(),
             Value::Float64(_) => ConcreteDataType::float64_datatype(),
+            Value::Decimal128(v) => ConcreteDataType::decimal128_datatype(v.precision(), v.scale()),
             Value::String(_) => ConcreteDataType::string_datatype(),
             Value::Binary(_) => ConcreteDataType::binary_datatype(),
             Value::Date(_) => ConcreteDataType::date_datatype(),
@@ -171,6 +177,7 @@ impl Value {
             Value::Int64(v) => ValueRef::Int64(*v),
             Value::Float32(v) => ValueRef::Float32(*v),
             Value::Float64(v) => ValueRef::Float64(*v),
+            Value::Decimal128(v) => ValueRef::Decimal128(*v),
             Value::String(v) => ValueRef::String(v.as_utf8()),
             Value::Binary(v) => ValueRef::Binary(v),
             Value::Date(v) => ValueRef::Date(*v),
@@ -204,6 +211,7 @@ impl Value {
             Value::Int64(_) => LogicalTypeId::Int64,
             Value::Float32(_) => LogicalTypeId::Float32,
             Value::Float64(_) => LogicalTypeId::Float64,
+            Value::Decimal128(_) => LogicalTypeId::Decimal128,
             Value::String(_) => LogicalTypeId::String,
             Value::Binary(_) => LogicalTypeId::Binary,
             Value::List(_) => LogicalTypeId::List,
@@ -244,6 +252,9 @@ impl Value {
             Value::Int64(v) => ScalarValue::Int64(Some(*v)),
             Value::Float32(v) => ScalarValue::Float32(Some(v.0)),
             Value::Float64(v) => ScalarValue::Float64(Some(v.0)),
+            Value::Decimal128(v) => {
+                ScalarValue::Decimal128(Some(v.val()), v.precision(), v.scale())
+            }
             Value::String(v) => ScalarValue::Utf8(Some(v.as_utf8().to_string())),
             Value::Binary(v) => ScalarValue::LargeBinary(Some(v.to_vec())),
             Value::Date(v) => ScalarValue::Date32(Some(v.val())),
@@ -275,6 +286,7 @@ pub fn to_null_scalar_value(output_type: &ConcreteDataType) -> ScalarValue {
         ConcreteDataType::UInt64(_) => ScalarValue::UInt64(None),
         ConcreteDataType::Float32(_) => ScalarValue::Float32(None),
         ConcreteDataType::Float64(_) => ScalarValue::Float64(None),
+        ConcreteDataType::Decimal128(t) => ScalarValue::Decimal128(None, t.precision(), t.scale()),
         ConcreteDataType::Binary(_) => ScalarValue::LargeBinary(None),
         ConcreteDataType::String(_) => ScalarValue::Utf8(None),
         ConcreteDataType::Date(_) => ScalarValue::Date32(None),
@@ -343,6 +355,7 @@ macro_rules! impl_ord_for_value_like {
                 ($Type::Int64(v1), $Type::Int64(v2)) => v1.cmp(v2),
                 ($Type::Float32(v1), $Type::Float32(v2)) => v1.cmp(v2),
                 ($Type::Float64(v1), $Type::Float64(v2)) => v1.cmp(v2),
+                ($Type::Decimal128(v1), $Type::Decimal128(v2)) => v1.cmp(v2),
                 ($Type::String(v1), $Type::String(v2)) => v1.cmp(v2),
                 ($Type::Binary(v1), $Type::Binary(v2)) => v1.cmp(v2),
                 ($Type::Date(v1), $Type::Date(v2)) => v1.cmp(v2),
@@ -400,6 +413,7 @@ impl_value_from!(Int32, i32);
 impl_value_from!(Int64, i64);
 impl_value_from!(Float32, f32);
 impl_value_from!(Float64, f64);
+impl_value_from!(Decimal128, Decimal128);
 impl_value_from!(String, StringBytes);
 impl_value_from!(Binary, Bytes);
 impl_value_from!(Date, Date);
@@ -447,6 +461,7 @@ impl TryFrom<Value> for serde_json::Value {
             Value::Int64(v) => serde_json::Value::from(v),
             Value::Float32(v) => serde_json::Value::from(v.0),
             Value::Float64(v) => serde_json::Value::from(v.0),
+            Value::Decimal128(v) => serde_json::Value::String(v.to_string()),
             Value::String(bytes) => serde_json::Value::String(bytes.as_utf8().to_string()),
             Value::Binary(bytes) => serde_json::to_value(bytes)?,
             Value::Date(v) => serde_json::Value::Number(v.val().into()),
@@ -590,8 +605,10 @@ impl TryFrom<ScalarValue> for Value {
             ScalarValue::TimestampNanosecond(t, _) => t
                 .map(|x| Value::Timestamp(Timestamp::new(x, TimeUnit::Nanosecond)))
                 .unwrap_or(Value::Null),
-            ScalarValue::Decimal128(_, _, _)
-            | ScalarValue::IntervalYearMonth(_)
+            ScalarValue::Decimal128(v, precision, scale) => v
+                .map(|v| Value::Decimal128(Decimal128::new(v, precision, scale)))
+                .unwrap_or(Value::Null),
+            ScalarValue::IntervalYearMonth(_)
             | ScalarValue::IntervalDayTime(_)
             | ScalarValue::IntervalMonthDayNano(_)
             | ScalarValue::Struct(_, _)
@@ -628,6 +645,9 @@ pub enum ValueRef<'a> {
     Float32(OrderedF32),
     Float64(OrderedF64),
 
+    // Decimal types:
+    Decimal128(Decimal128),
+
     // String types:
     String(&'a str),
     Binary(&'a [u8]),
@@ -677,6 +697,11 @@ impl<'a> ValueRef<'a> {
         impl_as_for_value_ref!(self, Boolean)
     }
 
+    /// Cast itself to [Decimal128].
+    pub fn as_decimal128(&self) -> Result<Option<Decimal128>> {
+        impl_as_for_value_ref!(self, Decimal128)
+    }
+
     /// Cast itself to [Date].
     pub fn as_date(&self) -> Result<Option<Date>> {
         impl_as_for_value_ref!(self, Date)
@@ -739,6 +764,7 @@ impl_value_ref_from!(Int32, i32);
 impl_value_ref_from!(Int64, i64);
 impl_value_ref_from!(Float32, f32);
 impl_value_ref_from!(Float64, f64);
+impl_value_ref_from!(Decimal128, Decimal128);
 impl_value_ref_from!(Date, Date);
 impl_value_ref_from!(DateTime, DateTime);
 impl_value_ref_from!(Timestamp, Timestamp);
@@ -1005,11 +1031,22 @@ mod tests {
                 .unwrap()
         );
 
-        let result: Result<Value> = ScalarValue::Decimal128(Some(1), 0, 0).try_into();
+        assert_eq!(
+            Value::Decimal128(Decimal128::new(12345, 10, 2)),
+            ScalarValue::Decimal128(Some(12345), 10, 2)
+                .try_into()
+                .unwrap()
+        );
+        assert_eq!(
+            Value::Null,
+            ScalarValue::Decimal128(None, 10, 2).try_into().unwrap()
+        );
+
+        let result: Result<Value> = ScalarValue::IntervalYearMonth(Some(1)).try_into();
         assert!(result
             .unwrap_err()
             .to_string()
-            .contains("Unsupported arrow data type, type: Decimal128(0, 0)"));
+            .contains("Unsupported arrow data type, type: Interval(YearMonth)"));
     }
 
     #[test]
@@ -1242,6 +1279,11 @@ mod tests {
                 datatype: ConcreteDataType::int32_datatype(),
             }))
         );
+
+        assert_eq!(
+            serde_json::Value::String("-1.05".to_string()),
+            to_json(Value::Decimal128(Decimal128::new(-105, 10, 2)))
+        );
     }
 
     #[test]
@@ -1423,6 +1465,10 @@ mod tests {
             .to_string(),
             "TimestampNanosecond[]"
         );
+        assert_eq!(
+            Value::Decimal128(Decimal128::new(12345, 10, 3)).to_string(),
+            "12.345"
+        );
     }
 
     #[test]
@@ -1511,6 +1557,12 @@ mod tests {
                 .try_to_scalar_value(&ConcreteDataType::binary_datatype())
                 .unwrap()
         );
+        assert_eq!(
+            ScalarValue::Decimal128(Some(12345), 10, 2),
+            Value::Decimal128(Decimal128::new(12345, 10, 2))
+                .try_to_scalar_value(&ConcreteDataType::decimal128_datatype(10, 2))
+                .unwrap()
+        );
     }
 
     #[test]
@@ -1593,6 +1645,12 @@ mod tests {
                 .try_to_scalar_value(&ConcreteDataType::binary_datatype())
                 .unwrap()
         );
+        assert_eq!(
+            ScalarValue::Decimal128(None, 10, 2),
+            Value::Null
+                .try_to_scalar_value(&ConcreteDataType::decimal128_datatype(10, 2))
+                .unwrap()
+        );
     }
 
     #[test]
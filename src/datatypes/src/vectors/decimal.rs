@@ -0,0 +1,402 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use arrow::array::{
+    Array, ArrayBuilder, ArrayData, ArrayIter, ArrayRef, Decimal128Array, Decimal128Builder,
+};
+use arrow::datatypes::DataType as ArrowDataType;
+use snafu::{ensure, ResultExt};
+
+use crate::data_type::ConcreteDataType;
+use crate::decimal::{Decimal128, DECIMAL128_MAX_PRECISION, DECIMAL_DEFAULT_SCALE};
+use crate::error::{self, Result};
+use crate::scalars::{ScalarVector, ScalarVectorBuilder};
+use crate::serialize::Serializable;
+use crate::value::{Value, ValueRef};
+use crate::vectors::{self, MutableVector, Validity, Vector, VectorRef};
+
+/// Vector of [Decimal128]. All values in the vector share the same precision and scale.
+#[derive(Debug, PartialEq)]
+pub struct Decimal128Vector {
+    array: Decimal128Array,
+}
+
+impl Decimal128Vector {
+    pub fn new(array: Decimal128Array) -> Self {
+        Self { array }
+    }
+
+    /// Returns a vector with the same values but with given `precision` and `scale`.
+    pub fn with_precision_and_scale(self, precision: u8, scale: i8) -> Result<Self> {
+        let array = self
+            .array
+            .with_precision_and_scale(precision, scale)
+            .context(error::ArrowComputeSnafu)?;
+        Ok(Self { array })
+    }
+
+    pub fn precision(&self) -> u8 {
+        self.array.precision()
+    }
+
+    pub fn scale(&self) -> i8 {
+        self.array.scale()
+    }
+
+    pub(crate) fn as_arrow(&self) -> &dyn Array {
+        &self.array
+    }
+
+    fn to_array_data(&self) -> ArrayData {
+        self.array.to_data()
+    }
+
+    fn decimal(&self, value: i128) -> Decimal128 {
+        Decimal128::new(value, self.precision(), self.scale())
+    }
+}
+
+impl From<Decimal128Array> for Decimal128Vector {
+    fn from(array: Decimal128Array) -> Self {
+        Self { array }
+    }
+}
+
+impl Vector for Decimal128Vector {
+    fn data_type(&self) -> ConcreteDataType {
+        ConcreteDataType::decimal128_datatype(self.precision(), self.scale())
+    }
+
+    fn vector_type_name(&self) -> String {
+        "Decimal128Vector".to_string()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn len(&self) -> usize {
+        self.array.len()
+    }
+
+    fn to_arrow_array(&self) -> ArrayRef {
+        let data = self.to_array_data();
+        Arc::new(Decimal128Array::from(data))
+    }
+
+    fn to_boxed_arrow_array(&self) -> Box<dyn Array> {
+        let data = self.to_array_data();
+        Box::new(Decimal128Array::from(data))
+    }
+
+    fn validity(&self) -> Validity {
+        vectors::impl_validity_for_vector!(self.array)
+    }
+
+    fn memory_size(&self) -> usize {
+        self.array.get_buffer_memory_size()
+    }
+
+    fn null_count(&self) -> usize {
+        self.array.null_count()
+    }
+
+    fn is_null(&self, row: usize) -> bool {
+        self.array.is_null(row)
+    }
+
+    fn slice(&self, offset: usize, length: usize) -> VectorRef {
+        let array = self.array.slice(offset, length);
+        Arc::new(Self { array })
+    }
+
+    fn get(&self, index: usize) -> Value {
+        match self.get_data(index) {
+            Some(v) => Value::Decimal128(v),
+            None => Value::Null,
+        }
+    }
+
+    fn get_ref(&self, index: usize) -> ValueRef {
+        match self.get_data(index) {
+            Some(v) => ValueRef::Decimal128(v),
+            None => ValueRef::Null,
+        }
+    }
+}
+
+/// Iterator of [Decimal128Vector].
+pub struct Decimal128Iter<'a> {
+    precision: u8,
+    scale: i8,
+    iter: ArrayIter<&'a Decimal128Array>,
+}
+
+impl<'a> Iterator for Decimal128Iter<'a> {
+    type Item = Option<Decimal128>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next()
+            .map(|v| v.map(|v| Decimal128::new(v, self.precision, self.scale)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl ScalarVector for Decimal128Vector {
+    type OwnedItem = Decimal128;
+    type RefItem<'a> = Decimal128;
+    type Iter<'a> = Decimal128Iter<'a>;
+    type Builder = Decimal128VectorBuilder;
+
+    fn get_data(&self, idx: usize) -> Option<Self::RefItem<'_>> {
+        if self.array.is_valid(idx) {
+            Some(self.decimal(self.array.value(idx)))
+        } else {
+            None
+        }
+    }
+
+    fn iter_data(&self) -> Self::Iter<'_> {
+        Decimal128Iter {
+            precision: self.precision(),
+            scale: self.scale(),
+            iter: self.array.iter(),
+        }
+    }
+}
+
+/// Builder of [Decimal128Vector].
+///
+/// Values pushed by [ScalarVectorBuilder::push()] are stored by their unscaled
+/// value, so they should have the same scale as the builder.
+pub struct Decimal128VectorBuilder {
+    precision: u8,
+    scale: i8,
+    mutable_array: Decimal128Builder,
+}
+
+impl Decimal128VectorBuilder {
+    pub fn with_precision_and_scale(capacity: usize, precision: u8, scale: i8) -> Self {
+        Self {
+            precision,
+            scale,
+            mutable_array: Decimal128Builder::with_capacity(capacity)
+                .with_data_type(ArrowDataType::Decimal128(precision, scale)),
+        }
+    }
+}
+
+impl MutableVector for Decimal128VectorBuilder {
+    fn data_type(&self) -> ConcreteDataType {
+        ConcreteDataType::decimal128_datatype(self.precision, self.scale)
+    }
+
+    fn len(&self) -> usize {
+        self.mutable_array.len()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn to_vector(&mut self) -> VectorRef {
+        Arc::new(self.finish())
+    }
+
+    fn try_push_value_ref(&mut self, value: ValueRef) -> Result<()> {
+        match value.as_decimal128()? {
+            Some(v) => {
+                ensure!(
+                    v.scale() == self.scale,
+                    error::CastTypeSnafu {
+                        msg: format!(
+                            "Failed to push decimal {v} with scale {} to vector with scale {}",
+                            v.scale(),
+                            self.scale
+                        ),
+                    }
+                );
+                self.mutable_array.append_value(v.val());
+            }
+            None => self.mutable_array.append_null(),
+        }
+        Ok(())
+    }
+
+    fn extend_slice_of(&mut self, vector: &dyn Vector, offset: usize, length: usize) -> Result<()> {
+        vectors::impl_extend_for_builder!(self, vector, Decimal128Vector, offset, length)
+    }
+
+    fn push_null(&mut self) {
+        self.mutable_array.append_null()
+    }
+}
+
+impl ScalarVectorBuilder for Decimal128VectorBuilder {
+    type VectorType = Decimal128Vector;
+
+    fn with_capacity(capacity: usize) -> Self {
+        Self::with_precision_and_scale(capacity, DECIMAL128_MAX_PRECISION, DECIMAL_DEFAULT_SCALE)
+    }
+
+    fn push(&mut self, value: Option<<Self::VectorType as ScalarVector>::RefItem<'_>>) {
+        match value {
+            Some(v) => self.mutable_array.append_value(v.val()),
+            None => self.mutable_array.append_null(),
+        }
+    }
+
+    fn finish(&mut self) -> Self::VectorType {
+        Decimal128Vector {
+            array: self.mutable_array.finish(),
+        }
+    }
+}
+
+impl Serializable for Decimal128Vector {
+    fn serialize_to_json(&self) -> Result<Vec<serde_json::Value>> {
+        // Serializes decimals as strings to avoid losing precision.
+        Ok(self
+            .iter_data()
+            .map(|v| match v {
+                None => serde_json::Value::Null,
+                Some(v) => serde_json::Value::String(v.to_string()),
+            })
+            .collect())
+    }
+}
+
+vectors::impl_try_from_arrow_array_for_vector!(Decimal128Array, Decimal128Vector);
+
+pub(crate) fn replicate_decimal(vector: &Decimal128Vector, offsets: &[usize]) -> VectorRef {
+    assert_eq!(offsets.len(), vector.len());
+
+    let mut builder = Decimal128VectorBuilder::with_precision_and_scale(
+        offsets.last().copied().unwrap_or(0),
+        vector.precision(),
+        vector.scale(),
+    );
+    let mut previous_offset = 0;
+    for (offset, value) in offsets.iter().zip(vector.array.iter()) {
+        let repeat_times = *offset - previous_offset;
+        match value {
+            Some(data) => {
+                for _ in 0..repeat_times {
+                    builder.mutable_array.append_value(data);
+                }
+            }
+            None => builder.mutable_array.append_nulls(repeat_times),
+        }
+        previous_offset = *offset;
+    }
+    builder.to_vector()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_type::DataType;
+    use crate::types::Decimal128Type;
+    use crate::vectors::operations::VectorOp;
+    use crate::vectors::{BooleanVector, UInt32Vector};
+
+    fn new_vector(values: Vec<Option<i128>>) -> Decimal128Vector {
+        Decimal128Vector::new(Decimal128Array::from(values))
+            .with_precision_and_scale(10, 2)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_decimal128_vector_misc() {
+        let v = new_vector(vec![Some(12345), None, Some(-1)]);
+
+        assert_eq!(3, v.len());
+        assert_eq!("Decimal128Vector", v.vector_type_name());
+        assert_eq!(ConcreteDataType::decimal128_datatype(10, 2), v.data_type());
+        assert_eq!(1, v.null_count());
+        assert!(!v.is_const());
+        assert_eq!(Value::Decimal128(Decimal128::new(12345, 10, 2)), v.get(0));
+        assert_eq!(Value::Null, v.get(1));
+        assert_eq!(
+            ValueRef::Decimal128(Decimal128::new(-1, 10, 2)),
+            v.get_ref(2)
+        );
+
+        let arrow_arr = v.to_arrow_array();
+        assert_eq!(&ArrowDataType::Decimal128(10, 2), arrow_arr.data_type());
+        let v2 = Decimal128Vector::try_from_arrow_array(arrow_arr).unwrap();
+        assert_eq!(v, v2);
+
+        let sliced = v.slice(1, 2);
+        assert_eq!(
+            ConcreteDataType::decimal128_datatype(10, 2),
+            sliced.data_type()
+        );
+        assert_eq!(Value::Null, sliced.get(0));
+    }
+
+    #[test]
+    fn test_decimal128_vector_builder() {
+        let input = new_vector(vec![Some(1), Some(2), Some(3)]);
+
+        let mut builder = Decimal128Type::new(10, 2).create_mutable_vector(3);
+        builder.push_value_ref(ValueRef::Decimal128(Decimal128::new(100, 10, 2)));
+        assert!(builder.try_push_value_ref(ValueRef::Int32(123)).is_err());
+        assert!(builder
+            .try_push_value_ref(ValueRef::Decimal128(Decimal128::new(100, 10, 3)))
+            .is_err());
+        builder.extend_slice_of(&input, 1, 2).unwrap();
+        let vector = builder.to_vector();
+
+        let expect: VectorRef = Arc::new(new_vector(vec![Some(100), Some(2), Some(3)]));
+        assert_eq!(expect, vector);
+    }
+
+    #[test]
+    fn test_serialize_decimal128_vector() {
+        let vector = new_vector(vec![Some(12345), None, Some(-5)]);
+        let json = serde_json::to_string(&vector.serialize_to_json().unwrap()).unwrap();
+        assert_eq!(r#"["123.45",null,"-0.05"]"#, json);
+    }
+
+    #[test]
+    fn test_decimal128_vector_op() {
+        let vector = new_vector(vec![Some(1), None, Some(3)]);
+
+        let replicated = vector.replicate(&[1, 1, 3]);
+        let expect: VectorRef = Arc::new(new_vector(vec![Some(1), Some(3), Some(3)]));
+        assert_eq!(expect, replicated);
+
+        let filtered = vector
+            .filter(&BooleanVector::from_slice(&[true, false, true]))
+            .unwrap();
+        let expect: VectorRef = Arc::new(new_vector(vec![Some(1), Some(3)]));
+        assert_eq!(expect, filtered);
+
+        let taken = vector.take(&UInt32Vector::from_slice(&[2, 0])).unwrap();
+        let expect: VectorRef = Arc::new(new_vector(vec![Some(3), Some(1)]));
+        assert_eq!(expect, taken);
+    }
+}
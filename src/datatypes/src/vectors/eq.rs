@@ -18,8 +18,8 @@ use crate::data_type::DataType;
 use crate::types::TimestampType;
 use crate::vectors::constant::ConstantVector;
 use crate::vectors::{
-    BinaryVector, BooleanVector, DateTimeVector, DateVector, ListVector, PrimitiveVector,
-    StringVector, TimestampMicrosecondVector, TimestampMillisecondVector,
+    BinaryVector, BooleanVector, DateTimeVector, DateVector, Decimal128Vector, ListVector,
+    PrimitiveVector, StringVector, TimestampMicrosecondVector, TimestampMillisecondVector,
     TimestampNanosecondVector, TimestampSecondVector, Vector,
 };
 use crate::with_match_primitive_type_id;
@@ -94,6 +94,7 @@ fn equal(lhs: &dyn Vector, rhs: &dyn Vector) -> bool {
             }
         },
         List(_) => is_vector_eq!(ListVector, lhs, rhs),
+        Decimal128(_) => is_vector_eq!(Decimal128Vector, lhs, rhs),
         UInt8(_) | UInt16(_) | UInt32(_) | UInt64(_) | Int8(_) | Int16(_) | Int32(_) | Int64(_)
         | Float32(_) | Float64(_) | Dictionary(_) => {
             with_match_primitive_type_id!(lhs_type.logical_type_id(), |$T| {
@@ -25,15 +25,17 @@ use datafusion_common::ScalarValue;
 use snafu::{OptionExt, ResultExt};
 
 use crate::data_type::ConcreteDataType;
+use crate::decimal::Decimal128;
 use crate::error::{self, Result};
 use crate::scalars::{Scalar, ScalarVectorBuilder};
 use crate::value::{ListValue, ListValueRef};
 use crate::vectors::{
-    BinaryVector, BooleanVector, ConstantVector, DateTimeVector, DateVector, Float32Vector,
-    Float64Vector, Int16Vector, Int32Vector, Int64Vector, Int8Vector, ListVector,
-    ListVectorBuilder, MutableVector, NullVector, StringVector, TimestampMicrosecondVector,
-    TimestampMillisecondVector, TimestampNanosecondVector, TimestampSecondVector, UInt16Vector,
-    UInt32Vector, UInt64Vector, UInt8Vector, Vector, VectorRef,
+    BinaryVector, BooleanVector, ConstantVector, DateTimeVector, DateVector, Decimal128Vector,
+    Decimal128VectorBuilder, Float32Vector, Float64Vector, Int16Vector, Int32Vector, Int64Vector,
+    Int8Vector, ListVector, ListVectorBuilder, MutableVector, NullVector, StringVector,
+    TimestampMicrosecondVector, TimestampMillisecondVector, TimestampNanosecondVector,
+    TimestampSecondVector, UInt16Vector, UInt32Vector, UInt64Vector, UInt8Vector, Vector,
+    VectorRef,
 };
 
 /// Helper functions for `Vector`.
@@ -194,8 +196,13 @@ impl Helper {
                 // Timezone is unimplemented now.
                 ConstantVector::new(Arc::new(TimestampNanosecondVector::from(vec![v])), length)
             }
-            ScalarValue::Decimal128(_, _, _)
-            | ScalarValue::IntervalYearMonth(_)
+            ScalarValue::Decimal128(v, precision, scale) => {
+                let mut builder =
+                    Decimal128VectorBuilder::with_precision_and_scale(1, precision, scale);
+                builder.push(v.map(|v| Decimal128::new(v, precision, scale)));
+                ConstantVector::new(builder.to_vector(), length)
+            }
+            ScalarValue::IntervalYearMonth(_)
             | ScalarValue::IntervalDayTime(_)
             | ScalarValue::IntervalMonthDayNano(_)
             | ScalarValue::Struct(_, _)
@@ -247,6 +254,9 @@ impl Helper {
             ArrowDataType::Date32 => Arc::new(DateVector::try_from_arrow_array(array)?),
             ArrowDataType::Date64 => Arc::new(DateTimeVector::try_from_arrow_array(array)?),
             ArrowDataType::List(_) => Arc::new(ListVector::try_from_arrow_array(array)?),
+            ArrowDataType::Decimal128(_, _) => {
+                Arc::new(Decimal128Vector::try_from_arrow_array(array)?)
+            }
             ArrowDataType::Timestamp(unit, _) => match unit {
                 TimeUnit::Second => Arc::new(
                     TimestampSecondVector::try_from_arrow_timestamp_array(array)?,
@@ -271,7 +281,6 @@ impl Helper {
             | ArrowDataType::Struct(_)
             | ArrowDataType::Union(_, _)
             | ArrowDataType::Dictionary(_, _)
-            | ArrowDataType::Decimal128(_, _)
             | ArrowDataType::Decimal256(_, _)
             | ArrowDataType::Map(_, _)
             | ArrowDataType::RunEndEncoded(_, _) => {
@@ -299,9 +308,9 @@ impl Helper {
 #[cfg(test)]
 mod tests {
     use arrow::array::{
-        ArrayRef, BooleanArray, Date32Array, Date64Array, Float32Array, Float64Array, Int16Array,
-        Int32Array, Int64Array, Int8Array, LargeBinaryArray, ListArray, NullArray,
-        TimestampMicrosecondArray, TimestampMillisecondArray, TimestampNanosecondArray,
+        ArrayRef, BooleanArray, Date32Array, Date64Array, Decimal128Array, Float32Array,
+        Float64Array, Int16Array, Int32Array, Int64Array, Int8Array, LargeBinaryArray, ListArray,
+        NullArray, TimestampMicrosecondArray, TimestampMillisecondArray, TimestampNanosecondArray,
         TimestampSecondArray, UInt16Array, UInt32Array, UInt64Array, UInt8Array,
     };
     use arrow::datatypes::{Field, Int32Type};
@@ -379,6 +388,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_try_from_scalar_decimal128_value() {
+        let vector =
+            Helper::try_from_scalar_value(ScalarValue::Decimal128(Some(12345), 10, 2), 3).unwrap();
+        assert_eq!(
+            ConcreteDataType::decimal128_datatype(10, 2),
+            vector.data_type()
+        );
+        assert_eq!(3, vector.len());
+        for i in 0..vector.len() {
+            assert_eq!(
+                Value::Decimal128(Decimal128::new(12345, 10, 2)),
+                vector.get(i)
+            );
+        }
+    }
+
     #[test]
     fn test_like_utf8() {
         fn assert_vector(expected: Vec<&str>, actual: &VectorRef) {
@@ -438,5 +464,10 @@ mod tests {
         check_try_into_vector(TimestampMillisecondArray::from(vec![1, 2, 3]));
         check_try_into_vector(TimestampMicrosecondArray::from(vec![1, 2, 3]));
         check_try_into_vector(TimestampNanosecondArray::from(vec![1, 2, 3]));
+        check_try_into_vector(
+            Decimal128Array::from(vec![Some(1), None, Some(3)])
+                .with_precision_and_scale(10, 2)
+                .unwrap(),
+        );
     }
 }
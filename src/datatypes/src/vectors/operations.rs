@@ -24,8 +24,8 @@ use crate::error::{self, Result};
 use crate::types::LogicalPrimitiveType;
 use crate::vectors::constant::ConstantVector;
 use crate::vectors::{
-    BinaryVector, BooleanVector, ConcreteDataType, ListVector, NullVector, PrimitiveVector,
-    StringVector, UInt32Vector, Vector, VectorRef,
+    BinaryVector, BooleanVector, ConcreteDataType, Decimal128Vector, ListVector, NullVector,
+    PrimitiveVector, StringVector, UInt32Vector, Vector, VectorRef,
 };
 
 /// Vector compute operations.
@@ -125,6 +125,29 @@ impl<T: LogicalPrimitiveType> VectorOp for PrimitiveVector<T> {
     }
 }
 
+impl VectorOp for Decimal128Vector {
+    fn replicate(&self, offsets: &[usize]) -> VectorRef {
+        replicate::replicate_decimal(self, offsets)
+    }
+
+    fn find_unique(&self, selected: &mut BitVec, prev_vector: Option<&dyn Vector>) {
+        let prev_vector = prev_vector.and_then(|pv| pv.as_any().downcast_ref::<Decimal128Vector>());
+        find_unique::find_unique_scalar(self, selected, prev_vector);
+    }
+
+    fn filter(&self, filter: &BooleanVector) -> Result<VectorRef> {
+        filter::filter_non_constant!(self, Decimal128Vector, filter)
+    }
+
+    fn cast(&self, to_type: &ConcreteDataType) -> Result<VectorRef> {
+        cast::cast_non_constant!(self, to_type)
+    }
+
+    fn take(&self, indices: &UInt32Vector) -> Result<VectorRef> {
+        take::take_indices!(self, Decimal128Vector, indices)
+    }
+}
+
 impl VectorOp for NullVector {
     fn replicate(&self, offsets: &[usize]) -> VectorRef {
         replicate::replicate_null(self, offsets)
@@ -409,14 +409,14 @@ impl CatalogManager for FrontendCatalogManager {
         if schema == INFORMATION_SCHEMA_NAME {
             // hack: use existing cyclin reference to get Arc<Self>.
             // This can be remove by refactoring the struct into something like Arc<Inner>
-            let manager = if let Some(instance) = self.dist_instance.as_ref() {
-                instance.catalog_manager() as _
-            } else {
+            let Some(instance) = self.dist_instance.as_ref() else {
                 return Ok(None);
             };
+            let manager: Arc<dyn CatalogManager> = instance.catalog_manager();
 
             let provider =
-                InformationSchemaProvider::new(catalog.to_string(), Arc::downgrade(&manager));
+                InformationSchemaProvider::new(catalog.to_string(), Arc::downgrade(&manager))
+                    .with_extension(instance.clone());
             return provider.table(table_name);
         }
 
@@ -362,6 +362,49 @@ pub enum Error {
         source: datatypes::error::Error,
     },
 
+    #[snafu(display("Auth error, source: {}", source))]
+    Auth {
+        source: servers::auth::Error,
+        location: Location,
+    },
+
+    #[snafu(display("Access denied, the request is not issued by an authenticated user"))]
+    Unauthenticated { location: Location },
+
+    #[snafu(display(
+        "Failed to collect the tables accessed by the plan, source: {}",
+        source
+    ))]
+    CollectPlanTables {
+        source: datafusion_common::DataFusionError,
+        location: Location,
+    },
+
+    #[snafu(display(
+        "Quota exceeded for user '{}' on database '{}', reason: {}",
+        username,
+        database,
+        reason
+    ))]
+    QuotaExceeded {
+        username: String,
+        database: String,
+        reason: String,
+        location: Location,
+    },
+
+    #[snafu(display(
+        "{} query interceptor exists, quotas can't be enforced along with it",
+        kind
+    ))]
+    QuotaInterceptorConflict { kind: String, location: Location },
+
+    #[snafu(display("Query timed out after {:?}", timeout))]
+    QueryTimeout {
+        timeout: std::time::Duration,
+        location: Location,
+    },
+
     #[snafu(display("SQL execution intercepted, source: {}", source))]
     SqlExecIntercepted {
         #[snafu(backtrace)]
@@ -499,6 +542,13 @@ pub enum Error {
         source: object_store::Error,
     },
 
+    #[snafu(display("Failed to write object to path: {}, source: {}", path, source))]
+    WriteObject {
+        path: String,
+        location: Location,
+        source: object_store::Error,
+    },
+
     #[snafu(display("Failed to read record batch, source: {}", source))]
     ReadRecordBatch {
         source: datafusion::error::DataFusionError,
@@ -610,6 +660,12 @@ impl ErrorExt for Error {
             | Error::ExecutePromql { source, .. } => source.status_code(),
 
             Error::SqlExecIntercepted { source, .. } => source.status_code(),
+            Error::Auth { source, .. } => source.status_code(),
+            Error::Unauthenticated { .. } => StatusCode::AccessDenied,
+            Error::CollectPlanTables { .. } => StatusCode::Internal,
+            Error::QuotaExceeded { .. } => StatusCode::RateLimited,
+            Error::QueryTimeout { .. } => StatusCode::Cancelled,
+            Error::QuotaInterceptorConflict { .. } => StatusCode::InvalidArguments,
             Error::StartServer { source, .. } => source.status_code(),
             Error::ShutdownServer { source, .. } => source.status_code(),
 
@@ -692,9 +748,10 @@ impl ErrorExt for Error {
 
             Error::TableScanExec { source, .. } => source.status_code(),
 
-            Error::ReadObject { .. } | Error::ReadParquet { .. } | Error::ReadOrc { .. } => {
-                StatusCode::StorageUnavailable
-            }
+            Error::ReadObject { .. }
+            | Error::WriteObject { .. }
+            | Error::ReadParquet { .. }
+            | Error::ReadOrc { .. } => StatusCode::StorageUnavailable,
 
             Error::ListObjects { source }
             | Error::ParseUrl { source }
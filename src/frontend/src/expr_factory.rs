@@ -313,6 +313,25 @@ pub(crate) fn to_alter_expr(
         AlterTableOperation::RenameTable { new_table_name } => Kind::RenameTable(RenameTable {
             new_table_name: new_table_name.to_string(),
         }),
+        // `AlterExpr` is not able to carry these operations to the meta server and datanodes yet.
+        AlterTableOperation::SetTableOptions { .. } => {
+            return error::NotSupportedSnafu {
+                feat: "ALTER TABLE SET options in distributed mode",
+            }
+            .fail();
+        }
+        AlterTableOperation::ModifyColumnType { .. } => {
+            return error::NotSupportedSnafu {
+                feat: "ALTER TABLE MODIFY COLUMN in distributed mode",
+            }
+            .fail();
+        }
+        AlterTableOperation::RenameColumn { .. } => {
+            return error::NotSupportedSnafu {
+                feat: "ALTER TABLE RENAME COLUMN in distributed mode",
+            }
+            .fail();
+        }
     };
 
     Ok(AlterExpr {
@@ -13,12 +13,15 @@
 // limitations under the License.
 
 pub mod distributed;
+mod graphite;
 mod grpc;
 mod influxdb;
 mod opentsdb;
+mod otlp;
 mod prom_store;
 mod script;
 mod standalone;
+mod user;
 
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -44,6 +47,7 @@ use common_query::Output;
 use common_telemetry::logging::{debug, info};
 use common_telemetry::timer;
 use datafusion::sql::sqlparser::ast::ObjectName;
+use datafusion_expr::LogicalPlan as DfLogicalPlan;
 use datanode::instance::sql::table_idents_to_full_name;
 use datanode::instance::InstanceRef as DnInstanceRef;
 use datatypes::schema::Schema;
@@ -56,22 +60,25 @@ use query::plan::LogicalPlan;
 use query::query_engine::options::{validate_catalog_and_schema, QueryOptions};
 use query::query_engine::DescribeResult;
 use query::{QueryEngineFactory, QueryEngineRef};
+use servers::auth::permission::Permission;
 use servers::error as server_error;
 use servers::error::{ExecuteQuerySnafu, ParsePromQLSnafu};
 use servers::interceptor::{
     PromQueryInterceptor, PromQueryInterceptorRef, SqlQueryInterceptor, SqlQueryInterceptorRef,
 };
-use servers::prometheus::PrometheusHandler;
+use servers::prometheus::{retrieve_metadata, PromMetadata, PrometheusHandler};
 use servers::query_handler::grpc::{GrpcQueryHandler, GrpcQueryHandlerRef};
 use servers::query_handler::sql::SqlQueryHandler;
 use servers::query_handler::{
-    InfluxdbLineProtocolHandler, OpentsdbProtocolHandler, PromStoreProtocolHandler, ScriptHandler,
+    GraphiteProtocolHandler, InfluxdbLineProtocolHandler, OpenTelemetryProtocolHandler,
+    OpentsdbProtocolHandler, PromStoreProtocolHandler, ScriptHandler,
 };
 use session::context::QueryContextRef;
 use snafu::prelude::*;
+use snafu::IntoError;
 use sql::dialect::Dialect;
 use sql::parser::ParserContext;
-use sql::statements::copy::CopyTable;
+use sql::statements::copy::{CopyDatabase, CopyTable};
 use sql::statements::statement::Statement;
 
 use crate::catalog::FrontendCatalogManager;
@@ -85,7 +92,9 @@ use crate::frontend::FrontendOptions;
 use crate::heartbeat::handler::invalidate_table_cache::InvalidateTableCacheHandler;
 use crate::heartbeat::HeartbeatTask;
 use crate::instance::standalone::StandaloneGrpcQueryHandler;
+use crate::instance::user::Access;
 use crate::metrics;
+use crate::quota::{execute_with_timeout, QuotaManagerRef};
 use crate::script::ScriptExecutor;
 use crate::server::{start_server, ServerHandlers, Services};
 use crate::statement::StatementExecutor;
@@ -95,8 +104,10 @@ pub trait FrontendInstance:
     GrpcQueryHandler<Error = Error>
     + SqlQueryHandler<Error = Error>
     + OpentsdbProtocolHandler
+    + GraphiteProtocolHandler
     + InfluxdbLineProtocolHandler
     + PromStoreProtocolHandler
+    + OpenTelemetryProtocolHandler
     + ScriptHandler
     + PrometheusHandler
     + Send
@@ -243,6 +254,7 @@ impl Instance {
             .enable_store()
             .enable_heartbeat()
             .enable_ddl()
+            .enable_cluster()
             .channel_manager(channel_manager)
             .ddl_channel_manager(ddl_channel_manager)
             .build();
@@ -295,13 +307,26 @@ impl Instance {
         requests: InsertRequests,
         ctx: QueryContextRef,
     ) -> Result<Output> {
+        self.authorize(Access::CurrentDatabase(Permission::Write), &ctx)
+            .await?;
+
+        let quota_manager = self.plugins.get::<QuotaManagerRef>();
+        if let Some(quota_manager) = &quota_manager {
+            quota_manager.check_write(&ctx)?;
+        }
+
         for req in requests.inserts.iter() {
             self.create_or_alter_table_on_demand(ctx.clone(), req)
                 .await?;
         }
 
         let query = Request::Inserts(requests);
-        GrpcQueryHandler::do_query(&*self.grpc_query_handler, query, ctx).await
+        let output =
+            GrpcQueryHandler::do_query(&*self.grpc_query_handler, query, ctx.clone()).await?;
+        if let (Output::AffectedRows(rows), Some(quota_manager)) = (&output, quota_manager) {
+            quota_manager.consume_write_rows(*rows, &ctx);
+        }
+        Ok(output)
     }
 
     // check if table already exist:
@@ -462,11 +487,50 @@ fn parse_stmt(sql: &str, dialect: &(dyn Dialect + Send + Sync)) -> Result<Vec<St
 }
 
 impl Instance {
+    async fn execute_promql(
+        &self,
+        query: &PromQuery,
+        query_ctx: QueryContextRef,
+    ) -> server_error::Result<Output> {
+        // PromQL only reads the metrics of the current database.
+        self.authorize(Access::CurrentDatabase(Permission::Read), &query_ctx)
+            .await
+            .map_err(BoxedError::new)
+            .context(server_error::OtherSnafu)?;
+
+        let stmt = QueryLanguageParser::parse_promql(query).with_context(|_| ParsePromQLSnafu {
+            query: query.clone(),
+        })?;
+
+        self.statement_executor
+            .execute_stmt(stmt, query_ctx)
+            .await
+            .map_err(BoxedError::new)
+            .with_context(|_| ExecuteQuerySnafu {
+                query: format!("{query:?}"),
+            })
+    }
+
+    /// Returns the query timeout of the quota, if any.
+    fn query_timeout(&self, query_ctx: &QueryContextRef) -> Option<Duration> {
+        self.plugins
+            .get::<QuotaManagerRef>()
+            .and_then(|quota_manager| quota_manager.query_timeout(query_ctx))
+    }
+
     async fn query_statement(&self, stmt: Statement, query_ctx: QueryContextRef) -> Result<Output> {
         check_permission(self.plugins.clone(), &stmt, &query_ctx)?;
+        self.authorize(Access::Statement(&stmt), &query_ctx).await?;
 
-        let stmt = QueryStatement::Sql(stmt);
-        self.statement_executor.execute_stmt(stmt, query_ctx).await
+        match stmt {
+            Statement::CreateUser(_) | Statement::Grant(_) | Statement::Revoke(_) => {
+                self.execute_user_management(stmt, &query_ctx).await
+            }
+            _ => {
+                let stmt = QueryStatement::Sql(stmt);
+                self.statement_executor.execute_stmt(stmt, query_ctx).await
+            }
+        }
     }
 }
 
@@ -496,13 +560,20 @@ impl SqlQueryHandler for Instance {
                         results.push(Err(e));
                         break;
                     }
-                    match self.query_statement(stmt, query_ctx.clone()).await {
+                    let result = execute_with_timeout(
+                        self.query_timeout(&query_ctx),
+                        self.query_statement(stmt, query_ctx.clone()),
+                        |e| e,
+                    )
+                    .await;
+                    match result {
                         Ok(output) => {
                             let output_result =
                                 query_interceptor.post_execute(output, query_ctx.clone());
                             results.push(output_result);
                         }
                         Err(e) => {
+                            query_interceptor.on_execute_error(&e, query_ctx.clone());
                             results.push(Err(e));
                             break;
                         }
@@ -518,10 +589,36 @@ impl SqlQueryHandler for Instance {
 
     async fn do_exec_plan(&self, plan: LogicalPlan, query_ctx: QueryContextRef) -> Result<Output> {
         let _timer = timer!(metrics::METRIC_EXEC_PLAN_ELAPSED);
-        self.query_engine
-            .execute(plan, query_ctx)
-            .await
-            .context(ExecLogicalPlanSnafu)
+        self.authorize(Access::Plan(&plan), &query_ctx).await?;
+
+        // Prepared statements are executed as plans without going through the
+        // SQL query interceptor, so the quotas are enforced here.
+        let quota_manager = self.plugins.get::<QuotaManagerRef>();
+        if let Some(quota_manager) = &quota_manager {
+            if matches!(plan, LogicalPlan::DfPlan(DfLogicalPlan::Dml(_))) {
+                quota_manager.check_write(&query_ctx)?;
+            }
+            quota_manager.start_query(&query_ctx)?;
+        }
+        let result = execute_with_timeout(
+            self.query_timeout(&query_ctx),
+            async {
+                self.query_engine
+                    .execute(plan, query_ctx.clone())
+                    .await
+                    .context(ExecLogicalPlanSnafu)
+            },
+            |e| e,
+        )
+        .await;
+        match (result, quota_manager) {
+            (Ok(output), Some(quota_manager)) => Ok(quota_manager.finish_query(output, &query_ctx)),
+            (Err(e), Some(quota_manager)) => {
+                quota_manager.abort_query(&query_ctx);
+                Err(e)
+            }
+            (result, None) => result,
+        }
     }
 
     async fn do_promql_query(
@@ -582,20 +679,42 @@ impl PrometheusHandler for Instance {
             .get::<PromQueryInterceptorRef<server_error::Error>>();
         interceptor.pre_execute(query, query_ctx.clone())?;
 
-        let stmt = QueryLanguageParser::parse_promql(query).with_context(|_| ParsePromQLSnafu {
-            query: query.clone(),
-        })?;
+        let result = execute_with_timeout(
+            self.query_timeout(&query_ctx),
+            self.execute_promql(query, query_ctx.clone()),
+            |e| server_error::OtherSnafu.into_error(BoxedError::new(e)),
+        )
+        .await;
+        let output = match result {
+            Ok(output) => output,
+            Err(e) => {
+                interceptor.on_execute_error(&e, query_ctx);
+                return Err(e);
+            }
+        };
 
-        let output = self
-            .statement_executor
-            .execute_stmt(stmt, query_ctx.clone())
+        Ok(interceptor.post_execute(output, query_ctx)?)
+    }
+
+    async fn metric_metadata(
+        &self,
+        metric: Option<String>,
+        limit: Option<usize>,
+        query_ctx: QueryContextRef,
+    ) -> server_error::Result<HashMap<String, Vec<PromMetadata>>> {
+        self.authorize(Access::CurrentDatabase(Permission::Read), &query_ctx)
             .await
             .map_err(BoxedError::new)
-            .with_context(|_| ExecuteQuerySnafu {
-                query: format!("{query:?}"),
-            })?;
-
-        Ok(interceptor.post_execute(output, query_ctx)?)
+            .context(server_error::OtherSnafu)?;
+
+        retrieve_metadata(
+            self.catalog_manager.clone(),
+            &query_ctx.current_catalog(),
+            &query_ctx.current_schema(),
+            metric,
+            limit,
+        )
+        .await
     }
 }
 
@@ -618,6 +737,8 @@ pub fn check_permission(
         Statement::Query(_) | Statement::Explain(_) | Statement::Tql(_) | Statement::Delete(_) => {}
         // database ops won't be checked
         Statement::CreateDatabase(_) | Statement::ShowDatabases(_) | Statement::Use(_) => {}
+        // user management is authorized by the user provider
+        Statement::CreateUser(_) | Statement::Grant(_) | Statement::Revoke(_) => {}
         // show create table and alter are not supported yet
         Statement::ShowCreateTable(_) | Statement::CreateExternalTable(_) | Statement::Alter(_) => {
         }
@@ -647,9 +768,14 @@ pub fn check_permission(
                 validate_param(&copy_table_from.table_name, query_ctx)?
             }
         },
-        Statement::Copy(sql::statements::copy::Copy::CopyDatabase(stmt)) => {
-            validate_param(&stmt.database_name, query_ctx)?
-        }
+        Statement::Copy(sql::statements::copy::Copy::CopyDatabase(stmt)) => match stmt {
+            CopyDatabase::To(copy_database_to) => {
+                validate_param(&copy_database_to.database_name, query_ctx)?
+            }
+            CopyDatabase::From(copy_database_from) => {
+                validate_param(&copy_database_from.database_name, query_ctx)?
+            }
+        },
         Statement::TruncateTable(stmt) => {
             validate_param(stmt.table_name(), query_ctx)?;
         }
@@ -775,6 +901,7 @@ mod tests {
         let plugins = Plugins::new();
         plugins.insert(QueryOptions {
             disallow_cross_schema_query: true,
+            ..Default::default()
         });
         let plugins = Arc::new(plugins);
 
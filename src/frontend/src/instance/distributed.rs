@@ -25,6 +25,8 @@ use api::v1::{
     FlushTableExpr, InsertRequests,
 };
 use async_trait::async_trait;
+use catalog::error::{InternalSnafu as CatalogInternalSnafu, Result as CatalogResult};
+use catalog::information_schema::{InformationExtension, TablePartition};
 use catalog::{CatalogManager, RegisterTableRequest};
 use chrono::DateTime;
 use client::client_manager::DatanodeClients;
@@ -32,6 +34,7 @@ use client::Database;
 use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
 use common_catalog::format_full_table_name;
 use common_error::ext::BoxedError;
+use common_meta::datanode::Stat;
 use common_meta::helper::{SchemaKey, SchemaValue};
 use common_meta::peer::Peer;
 use common_meta::rpc::ddl::{DdlTask, SubmitDdlTaskRequest, SubmitDdlTaskResponse};
@@ -53,7 +56,7 @@ use servers::query_handler::grpc::GrpcQueryHandler;
 use session::context::QueryContextRef;
 use snafu::{ensure, OptionExt, ResultExt};
 use sql::ast::{Ident, Value as SqlValue};
-use sql::statements::create::{PartitionEntry, Partitions};
+use sql::statements::create::{PartitionEntry, PartitionMethod, Partitions};
 use sql::statements::statement::Statement;
 use sql::statements::{self, sql_value_to_value};
 use store_api::storage::RegionNumber;
@@ -609,6 +612,62 @@ impl DistInstance {
     pub fn catalog_manager(&self) -> Arc<FrontendCatalogManager> {
         self.catalog_manager.clone()
     }
+
+    async fn table_partitions(&self, table_name: &TableName) -> Result<Vec<TablePartition>> {
+        let partition_manager = self.catalog_manager.partition_manager();
+        let partitions = partition_manager
+            .find_table_partitions(table_name)
+            .await
+            .context(error::FindTablePartitionRuleSnafu {
+                table_name: &table_name.table_name,
+            })?;
+        let route = partition_manager
+            .find_table_route(table_name)
+            .await
+            .context(error::FindTableRouteSnafu {
+                table_name: table_name.to_string(),
+            })?;
+
+        let partitions = partitions
+            .into_iter()
+            .map(|info| {
+                let (method, description) = describe_partition(info.partition.partition_bounds());
+                TablePartition {
+                    region_id: info.id.as_u64(),
+                    method: Some(method.to_string()),
+                    columns: info.partition.partition_columns().clone(),
+                    description: Some(description),
+                    peer_addr: route
+                        .find_region_leader(info.id.region_number())
+                        .map(|peer| peer.addr.clone()),
+                }
+            })
+            .collect();
+        Ok(partitions)
+    }
+}
+
+/// Returns the partitioning method and the description of the partition bounds, in the
+/// form of MySQL's `information_schema.partitions`.
+fn describe_partition(bounds: &[PartitionBound]) -> (&'static str, String) {
+    if let Some(PartitionBound::Hash { modulus, remainder }) = bounds.first() {
+        return ("HASH", format!("MODULUS {modulus}, REMAINDER {remainder}"));
+    }
+
+    let values = bounds
+        .iter()
+        .map(|bound| match bound {
+            PartitionBound::Value(v) => statements::value_to_sql_value(v)
+                .map(|v| v.to_string())
+                .unwrap_or_else(|_| v.to_string()),
+            PartitionBound::MaxValue => MAX_VALUE.to_string(),
+            PartitionBound::Hash { .. } => unreachable!(),
+        })
+        .collect::<Vec<_>>();
+    (
+        "RANGE COLUMNS",
+        format!("VALUES LESS THAN ({})", values.join(", ")),
+    )
 }
 
 #[async_trait]
@@ -625,6 +684,44 @@ impl SqlStatementExecutor for DistInstance {
     }
 }
 
+#[async_trait]
+impl InformationExtension for DistInstance {
+    async fn partitions(&self, table: &TableRef) -> CatalogResult<Vec<TablePartition>> {
+        if table.as_any().downcast_ref::<DistTable>().is_none() {
+            return Ok(vec![]);
+        }
+
+        let table_info = table.table_info();
+        let table_name = TableName::new(
+            &table_info.catalog_name,
+            &table_info.schema_name,
+            &table_info.name,
+        );
+        self.table_partitions(&table_name)
+            .await
+            .map_err(BoxedError::new)
+            .context(CatalogInternalSnafu)
+    }
+
+    async fn datanode_stats(&self) -> CatalogResult<Vec<Stat>> {
+        self.meta_client
+            .datanode_stats()
+            .await
+            .context(RequestMetaSnafu)
+            .map_err(BoxedError::new)
+            .context(CatalogInternalSnafu)
+    }
+
+    async fn metasrv_peers(&self) -> CatalogResult<(Vec<String>, String)> {
+        self.meta_client
+            .metasrv_peers()
+            .await
+            .context(RequestMetaSnafu)
+            .map_err(BoxedError::new)
+            .context(CatalogInternalSnafu)
+    }
+}
+
 #[async_trait]
 impl GrpcQueryHandler for DistInstance {
     type Error = error::Error;
@@ -680,6 +777,18 @@ fn create_partitions_stmt(partitions: Vec<PartitionInfo>) -> Result<Option<Parti
         .map(|name| name[..].into())
         .collect();
 
+    if let Some(PartitionBound::Hash { modulus, .. }) =
+        partitions[0].partition.partition_bounds().first()
+    {
+        return Ok(Some(Partitions {
+            method: PartitionMethod::Hash {
+                partitions: *modulus,
+            },
+            column_list,
+            entries: vec![],
+        }));
+    }
+
     let entries = partitions
         .into_iter()
         .map(|info| {
@@ -692,6 +801,7 @@ fn create_partitions_stmt(partitions: Vec<PartitionInfo>) -> Result<Option<Parti
                     PartitionBound::Value(v) => statements::value_to_sql_value(v)
                         .with_context(|_| error::ConvertSqlValueSnafu { value: v.clone() }),
                     PartitionBound::MaxValue => Ok(SqlValue::Number(MAX_VALUE.to_string(), false)),
+                    PartitionBound::Hash { .. } => unreachable!(),
                 })
                 .collect::<Result<Vec<_>>>()?;
 
@@ -703,6 +813,7 @@ fn create_partitions_stmt(partitions: Vec<PartitionInfo>) -> Result<Option<Parti
         .collect::<Result<Vec<_>>>()?;
 
     Ok(Some(Partitions {
+        method: PartitionMethod::RangeColumns,
         column_list,
         entries,
     }))
@@ -800,6 +911,23 @@ fn find_partition_entries(
     partitions: &Option<Partitions>,
     partition_columns: &[String],
 ) -> Result<Vec<Vec<PartitionBound>>> {
+    if let Some(Partitions {
+        method: PartitionMethod::Hash { partitions },
+        ..
+    }) = partitions
+    {
+        // Each hash partition is bounded by its remainder of the hash value.
+        let entries = (0..*partitions)
+            .map(|remainder| {
+                vec![PartitionBound::Hash {
+                    modulus: *partitions,
+                    remainder,
+                }]
+            })
+            .collect();
+        return Ok(entries);
+    }
+
     let entries = if let Some(partitions) = partitions {
         let column_defs = partition_columns
             .iter()
@@ -895,6 +1023,13 @@ PARTITION BY RANGE COLUMNS (b, a) (
 ENGINE=mito",
                 r#"[{"column_list":"b,a","value_list":"{\"Value\":{\"String\":\"hz\"}},{\"Value\":{\"Int32\":10}}"},{"column_list":"b,a","value_list":"{\"Value\":{\"String\":\"sh\"}},{\"Value\":{\"Int32\":20}}"},{"column_list":"b,a","value_list":"\"MaxValue\",\"MaxValue\""}]"#,
             ),
+            (
+                r"
+CREATE TABLE rcx ( a INT, b STRING, c TIMESTAMP, TIME INDEX (c) )
+PARTITION BY HASH (a) PARTITIONS 2
+ENGINE=mito",
+                r#"[{"column_list":"a","value_list":"{\"Hash\":{\"modulus\":2,\"remainder\":0}}"},{"column_list":"a","value_list":"{\"Hash\":{\"modulus\":2,\"remainder\":1}}"}]"#,
+            ),
         ];
         for (sql, expected) in cases {
             let result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
@@ -909,4 +1044,34 @@ ENGINE=mito",
             }
         }
     }
+
+    #[test]
+    fn test_describe_partition() {
+        let bounds = [
+            PartitionBound::Value(datatypes::value::Value::from("hz")),
+            PartitionBound::Value(datatypes::value::Value::from(10i32)),
+        ];
+        assert_eq!(
+            ("RANGE COLUMNS", "VALUES LESS THAN ('hz', 10)".to_string()),
+            describe_partition(&bounds)
+        );
+
+        let bounds = [PartitionBound::MaxValue, PartitionBound::MaxValue];
+        assert_eq!(
+            (
+                "RANGE COLUMNS",
+                "VALUES LESS THAN (MAXVALUE, MAXVALUE)".to_string()
+            ),
+            describe_partition(&bounds)
+        );
+
+        let bounds = [PartitionBound::Hash {
+            modulus: 4,
+            remainder: 1,
+        }];
+        assert_eq!(
+            ("HASH", "MODULUS 4, REMAINDER 1".to_string()),
+            describe_partition(&bounds)
+        );
+    }
 }
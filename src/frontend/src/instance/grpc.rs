@@ -17,6 +17,7 @@ use api::v1::query_request::Query;
 use async_trait::async_trait;
 use common_query::Output;
 use query::parser::PromQuery;
+use servers::auth::permission::Permission;
 use servers::interceptor::{GrpcQueryInterceptor, GrpcQueryInterceptorRef};
 use servers::query_handler::grpc::GrpcQueryHandler;
 use servers::query_handler::sql::SqlQueryHandler;
@@ -24,6 +25,7 @@ use session::context::QueryContextRef;
 use snafu::{ensure, OptionExt};
 
 use crate::error::{Error, IncompleteGrpcResultSnafu, NotSupportedSnafu, Result};
+use crate::instance::user::Access;
 use crate::instance::Instance;
 
 #[async_trait]
@@ -35,6 +37,21 @@ impl GrpcQueryHandler for Instance {
         let interceptor = interceptor_ref.as_ref();
         interceptor.pre_execute(&request, ctx.clone())?;
 
+        let output = match self.handle_grpc_request(request, ctx.clone()).await {
+            Ok(output) => output,
+            Err(e) => {
+                interceptor.on_execute_error(&e, ctx);
+                return Err(e);
+            }
+        };
+
+        let output = interceptor.post_execute(output, ctx)?;
+        Ok(output)
+    }
+}
+
+impl Instance {
+    async fn handle_grpc_request(&self, request: Request, ctx: QueryContextRef) -> Result<Output> {
         let output = match request {
             Request::Inserts(requests) => self.handle_inserts(requests, ctx.clone()).await?,
             Request::Query(query_request) => {
@@ -77,13 +94,22 @@ impl GrpcQueryHandler for Instance {
                     }
                 }
             }
-            Request::Ddl(_) | Request::Delete(_) => {
+            Request::Delete(_) => {
+                self.authorize(Access::CurrentDatabase(Permission::Write), &ctx)
+                    .await?;
+                GrpcQueryHandler::do_query(self.grpc_query_handler.as_ref(), request, ctx.clone())
+                    .await?
+            }
+            Request::Ddl(ref ddl) => {
+                let access = match &ddl.expr {
+                    Some(expr) => Access::Ddl(expr),
+                    None => Access::CurrentDatabase(Permission::Write),
+                };
+                self.authorize(access, &ctx).await?;
                 GrpcQueryHandler::do_query(self.grpc_query_handler.as_ref(), request, ctx.clone())
                     .await?
             }
         };
-
-        let output = interceptor.post_execute(output, ctx)?;
         Ok(output)
     }
 }
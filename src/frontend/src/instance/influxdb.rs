@@ -14,12 +14,19 @@
 
 use async_trait::async_trait;
 use common_error::ext::BoxedError;
+use common_query::Output;
+use query::influxql::InfluxqlStatement;
+use query::parser::QueryStatement;
+use servers::auth::permission::Permission;
 use servers::influxdb::InfluxdbRequest;
+use servers::interceptor::{InfluxqlQueryInterceptor, InfluxqlQueryInterceptorRef};
 use servers::query_handler::InfluxdbLineProtocolHandler;
 use session::context::QueryContextRef;
-use snafu::ResultExt;
+use snafu::{IntoError, ResultExt};
 
+use crate::instance::user::Access;
 use crate::instance::Instance;
+use crate::quota::execute_with_timeout;
 
 #[async_trait]
 impl InfluxdbLineProtocolHandler for Instance {
@@ -36,4 +43,43 @@ impl InfluxdbLineProtocolHandler for Instance {
             .context(servers::error::ExecuteGrpcQuerySnafu)?;
         Ok(())
     }
+
+    async fn query(
+        &self,
+        stmt: InfluxqlStatement,
+        ctx: QueryContextRef,
+    ) -> servers::error::Result<Output> {
+        let query = stmt.to_string();
+        // InfluxQL statements only read the measurements of the current database.
+        self.authorize(Access::CurrentDatabase(Permission::Read), &ctx)
+            .await
+            .map_err(BoxedError::new)
+            .with_context(|_| servers::error::ExecuteQuerySnafu {
+                query: query.clone(),
+            })?;
+
+        let interceptor = self
+            .plugins
+            .get::<InfluxqlQueryInterceptorRef<servers::error::Error>>();
+        interceptor.pre_execute(&stmt, ctx.clone())?;
+
+        let execute = async {
+            self.statement_executor
+                .execute_stmt(QueryStatement::Influxql(stmt), ctx.clone())
+                .await
+                .map_err(BoxedError::new)
+                .context(servers::error::ExecuteQuerySnafu { query })
+        };
+        let result = execute_with_timeout(self.query_timeout(&ctx), execute, |e| {
+            servers::error::OtherSnafu.into_error(BoxedError::new(e))
+        })
+        .await;
+        match result {
+            Ok(output) => interceptor.post_execute(output, ctx),
+            Err(e) => {
+                interceptor.on_execute_error(&e, ctx);
+                Err(e)
+            }
+        }
+    }
 }
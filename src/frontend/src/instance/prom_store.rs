@@ -32,6 +32,7 @@ use crate::error::{
     CatalogSnafu, ExecLogicalPlanSnafu, PromStoreRemoteQueryPlanSnafu, ReadTableSnafu, Result,
     TableNotFoundSnafu,
 };
+use crate::instance::user::Access;
 use crate::instance::Instance;
 use crate::metrics::PROM_STORE_REMOTE_WRITE_SAMPLES;
 
@@ -110,6 +111,8 @@ impl Instance {
             logical_plan.display_indent(),
         );
 
+        self.authorize(Access::Plan(&logical_plan), ctx).await?;
+
         self.query_engine
             .execute(logical_plan, ctx.clone())
             .await
@@ -0,0 +1,665 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::ops::ControlFlow;
+
+use api::v1::ddl_request::Expr as DdlExpr;
+use common_catalog::build_db_string;
+use common_error::ext::BoxedError;
+use common_query::Output;
+use datafusion_common::tree_node::{TreeNode, TreeNodeVisitor, VisitRecursion};
+use datafusion_common::{OwnedTableReference, Result as DfResult};
+use datafusion_expr::expr::{Exists, InSubquery};
+use datafusion_expr::{Expr, LogicalPlan as DfLogicalPlan};
+use datanode::instance::sql::{idents_to_full_database_name, table_idents_to_full_name};
+use query::plan::LogicalPlan;
+use servers::auth::permission::{Grant, Grantee, Permission, ANY_DATABASE};
+use servers::auth::UserProviderRef;
+use session::context::QueryContextRef;
+use snafu::{OptionExt, ResultExt};
+use sql::ast::{ObjectName, Query as SpQuery, Visit, Visitor};
+use sql::statements::copy::{Copy, CopyDatabase, CopyTable};
+use sql::statements::statement::Statement;
+use sql::statements::user::{CreateUser, GrantObject, Grantee as SqlGrantee, Privilege};
+
+use crate::error::{self, AuthSnafu, ExternalSnafu, Result};
+use crate::instance::Instance;
+
+/// A permission required on a catalog/schema.
+type RequiredPermission = (String, String, Permission);
+
+/// What a request accesses, to be authorized by [Instance::authorize].
+pub(crate) enum Access<'a> {
+    /// A SQL statement.
+    Statement(&'a Statement),
+    /// A logical plan, e.g. of a prepared statement.
+    Plan(&'a LogicalPlan),
+    /// A DDL expression of a gRPC request.
+    Ddl(&'a DdlExpr),
+    /// The current database, which is the only database accessed by PromQL,
+    /// InfluxQL, Prometheus remote read and metadata, and by the inserts and
+    /// deletes of gRPC and the write protocols.
+    CurrentDatabase(Permission),
+}
+
+impl Instance {
+    /// Checks whether the user issuing the request has the permissions required
+    /// by the access. This is the only place where requests are authorized, so
+    /// every entry point that reads or writes data must call it.
+    ///
+    /// Requests are denied if a user provider is configured but the request is
+    /// not issued by an authenticated user, e.g. by a protocol without
+    /// authentication.
+    pub(crate) async fn authorize(
+        &self,
+        access: Access<'_>,
+        query_ctx: &QueryContextRef,
+    ) -> Result<()> {
+        let Some(user_provider) = self.plugins.get::<UserProviderRef>() else { return Ok(()) };
+        let user_info = query_ctx
+            .current_user()
+            .context(error::UnauthenticatedSnafu)?;
+
+        let required = match access {
+            Access::Statement(stmt) => required_permissions(stmt, query_ctx)?,
+            Access::Plan(plan) => plan_permissions(plan, query_ctx)?,
+            Access::Ddl(expr) => ddl_permissions(expr, query_ctx),
+            Access::CurrentDatabase(permission) => vec![(
+                query_ctx.current_catalog(),
+                query_ctx.current_schema(),
+                permission,
+            )],
+        };
+        for (catalog, schema, permission) in required {
+            user_provider
+                .authorize_permission(&catalog, &schema, &user_info, permission)
+                .await
+                .context(AuthSnafu)?;
+        }
+        Ok(())
+    }
+
+    /// Executes `CREATE USER`, `GRANT` and `REVOKE` by the user provider.
+    pub(crate) async fn execute_user_management(
+        &self,
+        stmt: Statement,
+        query_ctx: &QueryContextRef,
+    ) -> Result<Output> {
+        let user_provider =
+            self.plugins
+                .get::<UserProviderRef>()
+                .context(error::NotSupportedSnafu {
+                    feat: "user management without user provider",
+                })?;
+
+        match stmt {
+            Statement::CreateUser(CreateUser {
+                name,
+                password,
+                if_not_exists,
+            }) => user_provider
+                .create_user(&name, &password, if_not_exists)
+                .await
+                .context(AuthSnafu)?,
+            Statement::Grant(object) => user_provider
+                .grant(&to_grant(object, query_ctx)?)
+                .await
+                .context(AuthSnafu)?,
+            Statement::Revoke(object) => user_provider
+                .revoke(&to_grant(object, query_ctx)?)
+                .await
+                .context(AuthSnafu)?,
+            _ => {
+                return error::NotSupportedSnafu {
+                    feat: format!("executing {stmt:?} as user management statement"),
+                }
+                .fail()
+            }
+        }
+        Ok(Output::AffectedRows(0))
+    }
+}
+
+/// Returns the permissions required by the statement. Queries and deletes
+/// require the permission on the database of every table they reference,
+/// including tables in subqueries and CTEs. TQL is checked against the
+/// current database, which its metrics are resolved in.
+fn required_permissions(
+    stmt: &Statement,
+    query_ctx: &QueryContextRef,
+) -> Result<Vec<RequiredPermission>> {
+    let current_database = |permission: Permission| -> Result<Vec<RequiredPermission>> {
+        Ok(vec![(
+            query_ctx.current_catalog(),
+            query_ctx.current_schema(),
+            permission,
+        )])
+    };
+    let table_database =
+        |name: &ObjectName, permission: Permission| -> Result<Vec<RequiredPermission>> {
+            let (catalog, schema, _) = table_idents_to_full_name(name, query_ctx.clone())
+                .map_err(BoxedError::new)
+                .context(ExternalSnafu)?;
+            Ok(vec![(catalog, schema, permission)])
+        };
+    let referenced_databases =
+        |tables: Vec<ObjectName>, permission: Permission| -> Result<Vec<RequiredPermission>> {
+            let mut required = Vec::with_capacity(tables.len());
+            for table in &tables {
+                for permission in table_database(table, permission)? {
+                    if !required.contains(&permission) {
+                        required.push(permission);
+                    }
+                }
+            }
+            Ok(required)
+        };
+
+    match stmt {
+        Statement::Query(query) => {
+            referenced_databases(referenced_tables(&query.inner), Permission::Read)
+        }
+        Statement::Explain(explain) => {
+            referenced_databases(referenced_tables(&explain.inner), Permission::Read)
+        }
+        Statement::Tql(_) => current_database(Permission::Read),
+        Statement::Delete(delete) => {
+            referenced_databases(referenced_tables(&delete.inner), Permission::Write)
+        }
+        Statement::ShowDatabases(_) => Ok(vec![]),
+        Statement::ShowTables(stmt) => match &stmt.database {
+            Some(database) => Ok(vec![(
+                query_ctx.current_catalog(),
+                database.clone(),
+                Permission::Read,
+            )]),
+            None => current_database(Permission::Read),
+        },
+        Statement::Use(database) => Ok(vec![(
+            query_ctx.current_catalog(),
+            database.clone(),
+            Permission::Read,
+        )]),
+        Statement::ShowCreateTable(stmt) => table_database(&stmt.table_name, Permission::Read),
+        Statement::DescribeTable(stmt) => table_database(stmt.name(), Permission::Read),
+
+        Statement::Insert(insert) => {
+            let mut required = table_database(insert.table_name(), Permission::Write)?;
+            let sources = referenced_tables(&insert.inner)
+                .into_iter()
+                .filter(|table| table != insert.table_name())
+                .collect();
+            for permission in referenced_databases(sources, Permission::Read)? {
+                if !required.contains(&permission) {
+                    required.push(permission);
+                }
+            }
+            Ok(required)
+        }
+        Statement::CreateTable(stmt) => table_database(&stmt.name, Permission::Write),
+        Statement::CreateExternalTable(stmt) => table_database(&stmt.name, Permission::Write),
+        Statement::DropTable(stmt) => table_database(stmt.table_name(), Permission::Write),
+        Statement::Alter(stmt) => table_database(stmt.table_name(), Permission::Write),
+        Statement::TruncateTable(stmt) => table_database(stmt.table_name(), Permission::Write),
+        Statement::CreateDatabase(stmt) => {
+            let (catalog, schema) = idents_to_full_database_name(&stmt.name, query_ctx)
+                .map_err(BoxedError::new)
+                .context(ExternalSnafu)?;
+            Ok(vec![(catalog, schema, Permission::Write)])
+        }
+
+        Statement::Copy(Copy::CopyTable(CopyTable::To(arg))) => {
+            table_database(&arg.table_name, Permission::Read)
+        }
+        Statement::Copy(Copy::CopyTable(CopyTable::From(arg))) => {
+            table_database(&arg.table_name, Permission::Write)
+        }
+        Statement::Copy(Copy::CopyDatabase(CopyDatabase::To(arg))) => {
+            let (catalog, schema) = idents_to_full_database_name(&arg.database_name, query_ctx)
+                .map_err(BoxedError::new)
+                .context(ExternalSnafu)?;
+            Ok(vec![(catalog, schema, Permission::Read)])
+        }
+        Statement::Copy(Copy::CopyDatabase(CopyDatabase::From(arg))) => {
+            let (catalog, schema) = idents_to_full_database_name(&arg.database_name, query_ctx)
+                .map_err(BoxedError::new)
+                .context(ExternalSnafu)?;
+            Ok(vec![(catalog, schema, Permission::Write)])
+        }
+
+        // Managing users requires the write permission on all databases.
+        Statement::CreateUser(_) | Statement::Grant(_) | Statement::Revoke(_) => Ok(vec![(
+            ANY_DATABASE.to_string(),
+            ANY_DATABASE.to_string(),
+            Permission::Write,
+        )]),
+    }
+}
+
+/// Collects the tables referenced by a statement. Names of CTEs are skipped as
+/// they are not tables.
+#[derive(Default)]
+struct TableCollector {
+    cte_names: HashSet<String>,
+    tables: Vec<ObjectName>,
+}
+
+impl Visitor for TableCollector {
+    type Break = ();
+
+    fn pre_visit_query(&mut self, query: &SpQuery) -> ControlFlow<Self::Break> {
+        if let Some(with) = &query.with {
+            self.cte_names.extend(
+                with.cte_tables
+                    .iter()
+                    .map(|cte| cte.alias.name.value.clone()),
+            );
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn pre_visit_relation(&mut self, relation: &ObjectName) -> ControlFlow<Self::Break> {
+        let is_cte = relation.0.len() == 1 && self.cte_names.contains(&relation.0[0].value);
+        if !is_cte && !self.tables.contains(relation) {
+            self.tables.push(relation.clone());
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+/// Returns the tables referenced by the node, including tables in subqueries and CTEs.
+fn referenced_tables(node: &impl Visit) -> Vec<ObjectName> {
+    let mut collector = TableCollector::default();
+    let _ = node.visit(&mut collector);
+    collector.tables
+}
+
+/// Returns the permissions required to execute the plan: the read permission
+/// on the database of every scanned table, including tables in subqueries, and
+/// the write permission on the database of the table written by DML.
+fn plan_permissions(
+    plan: &LogicalPlan,
+    query_ctx: &QueryContextRef,
+) -> Result<Vec<RequiredPermission>> {
+    let LogicalPlan::DfPlan(plan) = plan;
+    let mut collector = PlanPermissionCollector {
+        catalog: query_ctx.current_catalog(),
+        schema: query_ctx.current_schema(),
+        required: vec![],
+    };
+    let _ = plan
+        .visit(&mut collector)
+        .context(error::CollectPlanTablesSnafu)?;
+    Ok(collector.required)
+}
+
+/// Collects the permissions required by a plan, see [plan_permissions].
+struct PlanPermissionCollector {
+    catalog: String,
+    schema: String,
+    required: Vec<RequiredPermission>,
+}
+
+impl PlanPermissionCollector {
+    fn require(&mut self, table_name: &OwnedTableReference, permission: Permission) {
+        let table_name = table_name.clone().resolve(&self.catalog, &self.schema);
+        let required = (
+            table_name.catalog.to_string(),
+            table_name.schema.to_string(),
+            permission,
+        );
+        if !self.required.contains(&required) {
+            self.required.push(required);
+        }
+    }
+}
+
+impl TreeNodeVisitor for PlanPermissionCollector {
+    type N = DfLogicalPlan;
+
+    fn pre_visit(&mut self, node: &Self::N) -> DfResult<VisitRecursion> {
+        match node {
+            DfLogicalPlan::TableScan(scan) => self.require(&scan.table_name, Permission::Read),
+            DfLogicalPlan::Dml(dml) => self.require(&dml.table_name, Permission::Write),
+            _ => {}
+        }
+
+        // Subqueries in expressions are not children of the node.
+        let mut subqueries = vec![];
+        for expr in node.expressions() {
+            let _ = expr.apply(&mut |expr| {
+                match expr {
+                    Expr::Exists(Exists { subquery, .. })
+                    | Expr::InSubquery(InSubquery { subquery, .. })
+                    | Expr::ScalarSubquery(subquery) => subqueries.push(subquery.subquery.clone()),
+                    _ => {}
+                }
+                Ok(VisitRecursion::Continue)
+            })?;
+        }
+        for subquery in subqueries {
+            let _ = subquery.visit(self)?;
+        }
+        Ok(VisitRecursion::Continue)
+    }
+}
+
+/// Returns the permissions required by a gRPC DDL expression, which is the
+/// write permission on the database it changes. Empty catalog and schema names
+/// default to the current ones.
+fn ddl_permissions(expr: &DdlExpr, query_ctx: &QueryContextRef) -> Vec<RequiredPermission> {
+    let (catalog, schema) = match expr {
+        DdlExpr::CreateDatabase(expr) => ("", expr.database_name.as_str()),
+        DdlExpr::CreateTable(expr) => (expr.catalog_name.as_str(), expr.schema_name.as_str()),
+        DdlExpr::Alter(expr) => (expr.catalog_name.as_str(), expr.schema_name.as_str()),
+        DdlExpr::DropTable(expr) => (expr.catalog_name.as_str(), expr.schema_name.as_str()),
+        DdlExpr::FlushTable(expr) => (expr.catalog_name.as_str(), expr.schema_name.as_str()),
+        DdlExpr::CompactTable(expr) => (expr.catalog_name.as_str(), expr.schema_name.as_str()),
+    };
+    let or_current = |name: &str, current: String| {
+        if name.is_empty() {
+            current
+        } else {
+            name.to_string()
+        }
+    };
+    vec![(
+        or_current(catalog, query_ctx.current_catalog()),
+        or_current(schema, query_ctx.current_schema()),
+        Permission::Write,
+    )]
+}
+
+fn to_grant(object: GrantObject, query_ctx: &QueryContextRef) -> Result<Grant> {
+    let grant = match object {
+        GrantObject::Privileges {
+            privileges,
+            database,
+            grantee,
+        } => {
+            let database = match database {
+                Some(database) => {
+                    let (catalog, schema) = idents_to_full_database_name(&database, query_ctx)
+                        .map_err(BoxedError::new)
+                        .context(ExternalSnafu)?;
+                    build_db_string(&catalog, &schema)
+                }
+                None => ANY_DATABASE.to_string(),
+            };
+            Grant::Permissions {
+                permissions: privileges
+                    .into_iter()
+                    .map(|privilege| match privilege {
+                        Privilege::Read => Permission::Read,
+                        Privilege::Write => Permission::Write,
+                    })
+                    .collect(),
+                database,
+                grantee: match grantee {
+                    SqlGrantee::User(name) => Grantee::User(name),
+                    SqlGrantee::Role(name) => Grantee::Role(name),
+                },
+            }
+        }
+        GrantObject::Role { role, user } => Grant::Role { role, user },
+    };
+    Ok(grant)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use api::v1::DropTableExpr;
+    use common_test_util::temp_dir::create_temp_dir;
+    use datafusion::datasource::DefaultTableSource;
+    use datafusion_expr::{exists, LogicalPlanBuilder};
+    use servers::auth::file_user_provider::FileUserProvider;
+    use servers::auth::UserProvider;
+    use session::context::{QueryContext, UserInfo};
+    use sql::dialect::GreptimeDbDialect;
+    use sql::parser::ParserContext;
+    use table::table::adapter::DfTableProviderAdapter;
+    use table::table::numbers::NumbersTable;
+
+    use super::*;
+
+    fn parse(sql: &str) -> Statement {
+        ParserContext::create_with_dialect(sql, &GreptimeDbDialect {})
+            .unwrap()
+            .pop()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_required_permissions() {
+        let query_ctx = QueryContext::arc();
+        let expected = |catalog: &str, schema: &str, permission| {
+            vec![(catalog.to_string(), schema.to_string(), permission)]
+        };
+
+        for (sql, required) in [
+            (
+                "SELECT * FROM demo",
+                expected("greptime", "public", Permission::Read),
+            ),
+            (
+                "DESC TABLE other.demo",
+                expected("greptime", "other", Permission::Read),
+            ),
+            (
+                "INSERT INTO other.demo VALUES (1)",
+                expected("greptime", "other", Permission::Write),
+            ),
+            ("DROP TABLE c.s.demo", expected("c", "s", Permission::Write)),
+            (
+                "CREATE DATABASE foo",
+                expected("greptime", "foo", Permission::Write),
+            ),
+            (
+                "COPY demo TO '/tmp/demo.parquet'",
+                expected("greptime", "public", Permission::Read),
+            ),
+            (
+                "COPY demo FROM '/tmp/demo.parquet'",
+                expected("greptime", "public", Permission::Write),
+            ),
+            (
+                "COPY DATABASE other FROM '/tmp/backup/'",
+                expected("greptime", "other", Permission::Write),
+            ),
+            (
+                "GRANT READ ON public TO alice",
+                expected(ANY_DATABASE, ANY_DATABASE, Permission::Write),
+            ),
+        ] {
+            assert_eq!(
+                required_permissions(&parse(sql), &query_ctx).unwrap(),
+                required,
+                "sql: {sql}"
+            );
+        }
+
+        assert!(required_permissions(&parse("SHOW DATABASES"), &query_ctx)
+            .unwrap()
+            .is_empty());
+        assert!(required_permissions(&parse("SELECT 1"), &query_ctx)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_required_permissions_of_referenced_tables() {
+        let query_ctx = QueryContext::arc();
+        let read = |schema: &str| ("greptime".to_string(), schema.to_string(), Permission::Read);
+        let write = |schema: &str| {
+            (
+                "greptime".to_string(),
+                schema.to_string(),
+                Permission::Write,
+            )
+        };
+
+        for (sql, required) in [
+            ("SELECT * FROM db2.t", vec![read("db2")]),
+            (
+                "SELECT * FROM db1.t WHERE a IN (SELECT a FROM db2.t)",
+                vec![read("db1"), read("db2")],
+            ),
+            (
+                "SELECT * FROM t JOIN db2.t AS t2 ON t.a = t2.a",
+                vec![read("public"), read("db2")],
+            ),
+            (
+                "WITH cte AS (SELECT * FROM db2.t) SELECT * FROM cte",
+                vec![read("db2")],
+            ),
+            ("EXPLAIN SELECT * FROM db2.t", vec![read("db2")]),
+            (
+                "INSERT INTO db1.t SELECT * FROM db2.t",
+                vec![write("db1"), read("db2")],
+            ),
+            ("DELETE FROM db2.t WHERE a = 1", vec![write("db2")]),
+        ] {
+            assert_eq!(
+                required_permissions(&parse(sql), &query_ctx).unwrap(),
+                required,
+                "sql: {sql}"
+            );
+        }
+    }
+
+    async fn is_authorized(provider: &dyn UserProvider, user_info: &UserInfo, sql: &str) -> bool {
+        for (catalog, schema, permission) in
+            required_permissions(&parse(sql), &QueryContext::arc()).unwrap()
+        {
+            if provider
+                .authorize_permission(&catalog, &schema, user_info, permission)
+                .await
+                .is_err()
+            {
+                return false;
+            }
+        }
+        true
+    }
+
+    #[tokio::test]
+    async fn test_deny_tables_of_other_databases() {
+        let dir = create_temp_dir("test_deny_tables_of_other_databases");
+        let path = dir.path().join("users.json");
+        std::fs::write(
+            &path,
+            r#"{"users": {"alice": {"password": "654321", "grants": {"db1": ["read"]}}}}"#,
+        )
+        .unwrap();
+        let provider = FileUserProvider::try_from(path.to_str().unwrap()).unwrap();
+        let alice = UserInfo::new("alice");
+
+        assert!(is_authorized(&provider, &alice, "SELECT * FROM db1.t").await);
+        assert!(!is_authorized(&provider, &alice, "SELECT * FROM db2.t").await);
+        assert!(
+            !is_authorized(
+                &provider,
+                &alice,
+                "SELECT * FROM db1.t WHERE a IN (SELECT a FROM db2.t)"
+            )
+            .await
+        );
+        assert!(
+            !is_authorized(
+                &provider,
+                &alice,
+                "WITH cte AS (SELECT * FROM db2.t) SELECT * FROM cte"
+            )
+            .await
+        );
+    }
+
+    #[test]
+    fn test_plan_permissions() {
+        let scan = |name: &str| {
+            let table = Arc::new(NumbersTable::new(0)) as _;
+            let source = Arc::new(DefaultTableSource::new(Arc::new(
+                DfTableProviderAdapter::new(table),
+            )));
+            LogicalPlanBuilder::scan(name, source, None).unwrap()
+        };
+        let subquery = scan("c.s.u").build().unwrap();
+        let plan = scan("other.t")
+            .filter(exists(Arc::new(subquery)))
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let required = plan_permissions(&LogicalPlan::DfPlan(plan), &QueryContext::arc()).unwrap();
+        assert_eq!(
+            required,
+            vec![
+                (
+                    "greptime".to_string(),
+                    "other".to_string(),
+                    Permission::Read
+                ),
+                ("c".to_string(), "s".to_string(), Permission::Read),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ddl_permissions() {
+        let expr = DdlExpr::DropTable(DropTableExpr {
+            schema_name: "other".to_string(),
+            table_name: "demo".to_string(),
+            ..Default::default()
+        });
+        assert_eq!(
+            ddl_permissions(&expr, &QueryContext::arc()),
+            vec![(
+                "greptime".to_string(),
+                "other".to_string(),
+                Permission::Write
+            )]
+        );
+    }
+
+    #[test]
+    fn test_to_grant() {
+        let query_ctx = QueryContext::arc();
+        let Statement::Grant(object) = parse("GRANT READ, WRITE ON other TO ROLE writer") else {
+            unreachable!()
+        };
+        assert_eq!(
+            to_grant(object, &query_ctx).unwrap(),
+            Grant::Permissions {
+                permissions: vec![Permission::Read, Permission::Write],
+                database: "other".to_string(),
+                grantee: Grantee::Role("writer".to_string()),
+            }
+        );
+
+        let Statement::Revoke(object) = parse("REVOKE ALL ON * FROM alice") else {
+            unreachable!()
+        };
+        assert_eq!(
+            to_grant(object, &query_ctx).unwrap(),
+            Grant::Permissions {
+                permissions: vec![Permission::Read, Permission::Write],
+                database: ANY_DATABASE.to_string(),
+                grantee: Grantee::User("alice".to_string()),
+            }
+        );
+    }
+}
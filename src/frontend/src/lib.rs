@@ -22,6 +22,7 @@ pub mod frontend;
 pub mod heartbeat;
 pub mod instance;
 pub(crate) mod metrics;
+pub mod quota;
 mod script;
 mod server;
 pub mod service_config;
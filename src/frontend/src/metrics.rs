@@ -24,3 +24,6 @@ pub const DIST_INGEST_ROW_COUNT: &str = "frontend.dist.ingest_rows";
 
 /// The samples count of Prometheus remote write.
 pub const PROM_STORE_REMOTE_WRITE_SAMPLES: &str = "frontend.prometheus.remote_write.samples";
+
+/// The rows count of OpenTelemetry metrics.
+pub const OTLP_METRICS_ROWS: &str = "frontend.otlp.metrics.rows";
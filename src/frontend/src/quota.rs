@@ -0,0 +1,843 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Quotas limit the resources each user can use on each database. They are
+//! enforced by the query interceptors, see [install_quota_interceptors], except
+//! the query timeout which bounds the execution by [execute_with_timeout].
+
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use common_base::Plugins;
+use common_error::ext::BoxedError;
+use common_query::Output;
+use common_recordbatch::error::ExternalSnafu;
+use common_recordbatch::{OrderOption, RecordBatch, RecordBatchStream, SendableRecordBatchStream};
+use datatypes::schema::SchemaRef;
+use futures::{Future, Stream};
+use query::influxql::InfluxqlStatement;
+use query::parser::PromQuery;
+use query::plan::LogicalPlan;
+use serde::{Deserialize, Serialize};
+use servers::error as server_error;
+use servers::interceptor::{
+    InfluxqlQueryInterceptor, InfluxqlQueryInterceptorRef, PromQueryInterceptor,
+    PromQueryInterceptorRef, SqlQueryInterceptor, SqlQueryInterceptorRef,
+};
+use session::context::QueryContextRef;
+use snafu::ResultExt;
+use sql::statements::statement::Statement;
+use tokio::time::Sleep;
+
+use crate::error::{
+    Error, QueryTimeoutSnafu, QuotaExceededSnafu, QuotaInterceptorConflictSnafu, Result,
+};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuotaOptions {
+    /// Limits of each user on each database, unless overridden by `rules`.
+    pub default_limits: QuotaLimits,
+    /// Limits of specific users or databases. The first matched rule takes
+    /// effect, and the limits it leaves unset fall back to `default_limits`.
+    pub rules: Vec<QuotaRule>,
+}
+
+impl QuotaOptions {
+    pub fn is_unlimited(&self) -> bool {
+        self.default_limits == QuotaLimits::default() && self.rules.is_empty()
+    }
+
+    fn limits_of(&self, username: &str, database: &str) -> QuotaLimits {
+        let Some(rule) = self.rules.iter().find(|rule| rule.matches(username, database)) else {
+            return self.default_limits.clone();
+        };
+        let limits = &rule.limits;
+        let default_limits = &self.default_limits;
+        QuotaLimits {
+            max_concurrent_queries: limits
+                .max_concurrent_queries
+                .or(default_limits.max_concurrent_queries),
+            max_write_rows_per_second: limits
+                .max_write_rows_per_second
+                .or(default_limits.max_write_rows_per_second),
+            query_timeout: limits.query_timeout.or(default_limits.query_timeout),
+        }
+    }
+}
+
+/// Limits of a user on a database. `None` means unlimited.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuotaLimits {
+    /// Max number of queries running at the same time.
+    pub max_concurrent_queries: Option<usize>,
+    /// Max number of rows written per second, averaged over time. A write
+    /// request is rejected while the rows written before exceed the rate.
+    pub max_write_rows_per_second: Option<u64>,
+    /// Max duration of executing a query and reading its results.
+    #[serde(with = "humantime_serde")]
+    pub query_timeout: Option<Duration>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuotaRule {
+    /// The user this rule applies to, or all users if not set.
+    pub username: Option<String>,
+    /// The database this rule applies to, or all databases if not set.
+    pub database: Option<String>,
+    #[serde(flatten)]
+    pub limits: QuotaLimits,
+}
+
+impl QuotaRule {
+    fn matches(&self, username: &str, database: &str) -> bool {
+        self.username.as_deref().map_or(true, |x| x == username)
+            && self.database.as_deref().map_or(true, |x| x == database)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct QuotaKey {
+    username: String,
+    catalog: String,
+    schema: String,
+}
+
+impl QuotaKey {
+    fn new(query_ctx: &QueryContextRef) -> Self {
+        Self {
+            username: query_ctx
+                .current_user()
+                .map(|user| user.username().to_string())
+                .unwrap_or_default(),
+            catalog: query_ctx.current_catalog(),
+            schema: query_ctx.current_schema(),
+        }
+    }
+}
+
+/// The resource usage of a user on a database.
+struct QuotaState {
+    key: QuotaKey,
+    limits: QuotaLimits,
+    running_queries: AtomicUsize,
+    write_bucket: Mutex<WriteBucket>,
+}
+
+/// A token bucket refilled by `max_write_rows_per_second` tokens per second,
+/// and holds at most one second of tokens. Written rows are taken from the
+/// bucket after the writes, which may leave the bucket in debt.
+struct WriteBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl QuotaState {
+    fn new(key: QuotaKey, limits: QuotaLimits) -> Self {
+        let tokens = limits.max_write_rows_per_second.unwrap_or_default() as f64;
+        Self {
+            key,
+            limits,
+            running_queries: AtomicUsize::new(0),
+            write_bucket: Mutex::new(WriteBucket {
+                tokens,
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    fn quota_exceeded(&self, reason: String) -> Error {
+        QuotaExceededSnafu {
+            username: &self.key.username,
+            database: &self.key.schema,
+            reason,
+        }
+        .build()
+    }
+
+    fn acquire_query(&self) -> Result<()> {
+        let max = self.limits.max_concurrent_queries.unwrap_or(usize::MAX);
+        self.running_queries
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |running| {
+                (running < max).then_some(running + 1)
+            })
+            .map_err(|_| {
+                self.quota_exceeded(format!("too many concurrent queries, limit: {max}"))
+            })?;
+        Ok(())
+    }
+
+    fn release_query(&self) {
+        let _ = self
+            .running_queries
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |running| {
+                running.checked_sub(1)
+            });
+    }
+
+    /// Refills the write bucket and returns the available tokens.
+    fn refill_write_bucket(&self, bucket: &mut WriteBucket, rate: u64) -> f64 {
+        let now = Instant::now();
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate as f64).min(rate as f64);
+        bucket.last_refill = now;
+        bucket.tokens
+    }
+
+    fn check_write(&self) -> Result<()> {
+        let Some(rate) = self.limits.max_write_rows_per_second else { return Ok(()) };
+        let mut bucket = self.write_bucket.lock().unwrap();
+        if self.refill_write_bucket(&mut bucket, rate) <= 0.0 {
+            return Err(self.quota_exceeded(format!(
+                "too many rows written, limit: {rate} rows per second"
+            )));
+        }
+        Ok(())
+    }
+
+    fn consume_write_rows(&self, rows: usize) {
+        let Some(rate) = self.limits.max_write_rows_per_second else { return };
+        let mut bucket = self.write_bucket.lock().unwrap();
+        let _ = self.refill_write_bucket(&mut bucket, rate);
+        bucket.tokens -= rows as f64;
+    }
+
+    /// Returns whether the state is the same as a new one, i.e. there is no
+    /// running query and the write bucket is full.
+    fn is_idle(&self) -> bool {
+        if self.running_queries.load(Ordering::Acquire) > 0 {
+            return false;
+        }
+        let Some(rate) = self.limits.max_write_rows_per_second else { return true };
+        let mut bucket = self.write_bucket.lock().unwrap();
+        self.refill_write_bucket(&mut bucket, rate) >= rate as f64
+    }
+}
+
+/// Interval to evict the idle states.
+const IDLE_STATE_EVICTION_INTERVAL: Duration = Duration::from_secs(60);
+
+struct QuotaStates {
+    states: HashMap<QuotaKey, Arc<QuotaState>>,
+    last_eviction: Instant,
+}
+
+impl QuotaStates {
+    /// Removes the states not referenced elsewhere and [QuotaState::is_idle],
+    /// which are created again on the next request of the user.
+    fn evict_idle(&mut self) {
+        self.states
+            .retain(|_, state| Arc::strong_count(state) > 1 || !state.is_idle());
+        self.last_eviction = Instant::now();
+    }
+}
+
+/// Tracks the resource usage and checks the limits of each user on each
+/// database.
+pub struct QuotaManager {
+    options: QuotaOptions,
+    states: Mutex<QuotaStates>,
+}
+
+pub type QuotaManagerRef = Arc<QuotaManager>;
+
+impl QuotaManager {
+    pub fn new(options: QuotaOptions) -> Self {
+        Self {
+            options,
+            states: Mutex::new(QuotaStates {
+                states: HashMap::new(),
+                last_eviction: Instant::now(),
+            }),
+        }
+    }
+
+    fn state(&self, query_ctx: &QueryContextRef) -> Arc<QuotaState> {
+        let key = QuotaKey::new(query_ctx);
+        let mut states = self.states.lock().unwrap();
+        if states.last_eviction.elapsed() >= IDLE_STATE_EVICTION_INTERVAL {
+            states.evict_idle();
+        }
+        if let Some(state) = states.states.get(&key) {
+            return state.clone();
+        }
+        let limits = self.options.limits_of(&key.username, &key.schema);
+        let state = Arc::new(QuotaState::new(key.clone(), limits));
+        let _ = states.states.insert(key, state.clone());
+        state
+    }
+
+    /// Returns the query timeout of the user on the database.
+    pub fn query_timeout(&self, query_ctx: &QueryContextRef) -> Option<Duration> {
+        self.state(query_ctx).limits.query_timeout
+    }
+
+    /// Rejects the write if the rows written before exceed the rate limit.
+    pub fn check_write(&self, query_ctx: &QueryContextRef) -> Result<()> {
+        self.state(query_ctx).check_write()
+    }
+
+    /// Records the rows written, should be called after the write succeeds.
+    pub fn consume_write_rows(&self, rows: usize, query_ctx: &QueryContextRef) {
+        self.state(query_ctx).consume_write_rows(rows)
+    }
+
+    /// Starts a query, or rejects it if there are too many running queries.
+    /// The query must be finished by [QuotaManager::finish_query] or
+    /// [QuotaManager::abort_query].
+    pub fn start_query(&self, query_ctx: &QueryContextRef) -> Result<()> {
+        self.state(query_ctx).acquire_query()
+    }
+
+    /// Finishes a query by its output. The written rows are recorded, and the
+    /// query keeps running until the stream output is exhausted or dropped.
+    pub fn finish_query(&self, output: Output, query_ctx: &QueryContextRef) -> Output {
+        let state = self.state(query_ctx);
+        match output {
+            Output::AffectedRows(rows) => {
+                state.consume_write_rows(rows);
+                state.release_query();
+                Output::AffectedRows(rows)
+            }
+            Output::RecordBatches(_) => {
+                state.release_query();
+                output
+            }
+            Output::Stream(stream) => Output::Stream(Box::pin(QuotaStream { stream, state })),
+        }
+    }
+
+    /// Finishes a query that failed.
+    pub fn abort_query(&self, query_ctx: &QueryContextRef) {
+        self.state(query_ctx).release_query()
+    }
+}
+
+/// The stream of query results, which counts as a running query until it's
+/// dropped.
+struct QuotaStream {
+    stream: SendableRecordBatchStream,
+    state: Arc<QuotaState>,
+}
+
+impl Drop for QuotaStream {
+    fn drop(&mut self) {
+        self.state.release_query();
+    }
+}
+
+impl RecordBatchStream for QuotaStream {
+    fn schema(&self) -> SchemaRef {
+        self.stream.schema()
+    }
+
+    fn output_ordering(&self) -> Option<&[OrderOption]> {
+        self.stream.output_ordering()
+    }
+}
+
+impl Stream for QuotaStream {
+    type Item = common_recordbatch::error::Result<RecordBatch>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.stream.as_mut().poll_next(cx)
+    }
+}
+
+/// Executes a query within the timeout. The timeout starts before the execution,
+/// and also bounds reading the results if the output is a stream. The timeout
+/// error is converted by `timeout_error`.
+pub async fn execute_with_timeout<F, E>(
+    timeout: Option<Duration>,
+    execute: F,
+    timeout_error: fn(Error) -> E,
+) -> std::result::Result<Output, E>
+where
+    F: Future<Output = std::result::Result<Output, E>>,
+{
+    let Some(timeout) = timeout else { return execute.await };
+    let deadline = tokio::time::Instant::now() + timeout;
+    let output = match tokio::time::timeout_at(deadline, execute).await {
+        Ok(output) => output?,
+        Err(_) => return Err(timeout_error(QueryTimeoutSnafu { timeout }.build())),
+    };
+
+    Ok(match output {
+        Output::Stream(stream) => Output::Stream(Box::pin(TimeoutStream {
+            stream,
+            deadline: Box::pin(tokio::time::sleep_until(deadline)),
+            timeout,
+            timed_out: false,
+        })),
+        output => output,
+    })
+}
+
+/// The stream of query results, which fails once the query timeout elapses.
+struct TimeoutStream {
+    stream: SendableRecordBatchStream,
+    deadline: Pin<Box<Sleep>>,
+    timeout: Duration,
+    timed_out: bool,
+}
+
+impl RecordBatchStream for TimeoutStream {
+    fn schema(&self) -> SchemaRef {
+        self.stream.schema()
+    }
+
+    fn output_ordering(&self) -> Option<&[OrderOption]> {
+        self.stream.output_ordering()
+    }
+}
+
+impl Stream for TimeoutStream {
+    type Item = common_recordbatch::error::Result<RecordBatch>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.timed_out {
+            return Poll::Ready(None);
+        }
+        if this.deadline.as_mut().poll(cx).is_ready() {
+            this.timed_out = true;
+            let error = QueryTimeoutSnafu {
+                timeout: this.timeout,
+            }
+            .build();
+            return Poll::Ready(Some(Err(BoxedError::new(error)).context(ExternalSnafu)));
+        }
+        this.stream.as_mut().poll_next(cx)
+    }
+}
+
+/// Enforces the quotas on SQL, PromQL and InfluxQL queries, which are limited
+/// by the concurrent queries, and SQL writes are also limited by the write rate.
+/// The inserts of gRPC and the other write protocols are limited by the frontend
+/// in `Instance::handle_inserts` instead.
+pub struct QuotaInterceptor {
+    quota_manager: QuotaManagerRef,
+}
+
+impl QuotaInterceptor {
+    pub fn new(quota_manager: QuotaManagerRef) -> Self {
+        Self { quota_manager }
+    }
+}
+
+impl SqlQueryInterceptor for QuotaInterceptor {
+    type Error = Error;
+
+    fn pre_execute(
+        &self,
+        statement: &Statement,
+        _plan: Option<&LogicalPlan>,
+        query_ctx: QueryContextRef,
+    ) -> Result<()> {
+        if matches!(
+            statement,
+            Statement::Insert(_) | Statement::Delete(_) | Statement::Copy(_)
+        ) {
+            self.quota_manager.check_write(&query_ctx)?;
+        }
+        self.quota_manager.start_query(&query_ctx)
+    }
+
+    fn post_execute(&self, output: Output, query_ctx: QueryContextRef) -> Result<Output> {
+        Ok(self.quota_manager.finish_query(output, &query_ctx))
+    }
+
+    fn on_execute_error(&self, _error: &Error, query_ctx: QueryContextRef) {
+        self.quota_manager.abort_query(&query_ctx)
+    }
+}
+
+impl PromQueryInterceptor for QuotaInterceptor {
+    type Error = server_error::Error;
+
+    fn pre_execute(
+        &self,
+        _query: &PromQuery,
+        query_ctx: QueryContextRef,
+    ) -> server_error::Result<()> {
+        self.quota_manager
+            .start_query(&query_ctx)
+            .map_err(BoxedError::new)
+            .context(server_error::OtherSnafu)
+    }
+
+    fn post_execute(
+        &self,
+        output: Output,
+        query_ctx: QueryContextRef,
+    ) -> server_error::Result<Output> {
+        Ok(self.quota_manager.finish_query(output, &query_ctx))
+    }
+
+    fn on_execute_error(&self, _error: &server_error::Error, query_ctx: QueryContextRef) {
+        self.quota_manager.abort_query(&query_ctx)
+    }
+}
+
+impl InfluxqlQueryInterceptor for QuotaInterceptor {
+    type Error = server_error::Error;
+
+    fn pre_execute(
+        &self,
+        _statement: &InfluxqlStatement,
+        query_ctx: QueryContextRef,
+    ) -> server_error::Result<()> {
+        self.quota_manager
+            .start_query(&query_ctx)
+            .map_err(BoxedError::new)
+            .context(server_error::OtherSnafu)
+    }
+
+    fn post_execute(
+        &self,
+        output: Output,
+        query_ctx: QueryContextRef,
+    ) -> server_error::Result<Output> {
+        Ok(self.quota_manager.finish_query(output, &query_ctx))
+    }
+
+    fn on_execute_error(&self, _error: &server_error::Error, query_ctx: QueryContextRef) {
+        self.quota_manager.abort_query(&query_ctx)
+    }
+}
+
+/// Installs the [QuotaInterceptor] and the [QuotaManager] into the plugins if any
+/// quota is configured. Fails if any kind of the query interceptors is already in
+/// the plugins, as the quotas can't be enforced along with it.
+pub fn install_quota_interceptors(plugins: &Plugins, options: &QuotaOptions) -> Result<()> {
+    if options.is_unlimited() {
+        return Ok(());
+    }
+    let installed = [
+        (
+            "SQL",
+            plugins.get::<SqlQueryInterceptorRef<Error>>().is_some(),
+        ),
+        (
+            "PromQL",
+            plugins
+                .get::<PromQueryInterceptorRef<server_error::Error>>()
+                .is_some(),
+        ),
+        (
+            "InfluxQL",
+            plugins
+                .get::<InfluxqlQueryInterceptorRef<server_error::Error>>()
+                .is_some(),
+        ),
+    ];
+    if let Some((kind, _)) = installed.iter().find(|(_, installed)| *installed) {
+        return QuotaInterceptorConflictSnafu { kind: *kind }.fail();
+    }
+
+    let quota_manager = Arc::new(QuotaManager::new(options.clone()));
+    plugins.insert::<QuotaManagerRef>(quota_manager.clone());
+    let interceptor = Arc::new(QuotaInterceptor::new(quota_manager));
+    plugins.insert::<SqlQueryInterceptorRef<Error>>(interceptor.clone());
+    plugins.insert::<PromQueryInterceptorRef<server_error::Error>>(interceptor.clone());
+    plugins.insert::<InfluxqlQueryInterceptorRef<server_error::Error>>(interceptor);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches::assert_matches;
+
+    use common_error::ext::ErrorExt;
+    use common_error::status_code::StatusCode;
+    use common_recordbatch::RecordBatchStreamAdaptor;
+    use datatypes::schema::Schema;
+    use futures::StreamExt;
+    use session::context::{QueryContext, UserInfo};
+
+    use super::*;
+
+    fn query_ctx(username: &str, schema: &str) -> QueryContextRef {
+        let query_ctx = Arc::new(QueryContext::with("greptime", schema));
+        query_ctx.set_current_user(Some(UserInfo::new(username)));
+        query_ctx
+    }
+
+    #[test]
+    fn test_parse_quota_options() {
+        let toml_str = r#"
+            [default_limits]
+            max_concurrent_queries = 8
+            query_timeout = "30s"
+
+            [[rules]]
+            username = "alice"
+            max_write_rows_per_second = 1000
+
+            [[rules]]
+            database = "metrics"
+            max_concurrent_queries = 2
+        "#;
+        let options: QuotaOptions = toml::from_str(toml_str).unwrap();
+        assert!(!options.is_unlimited());
+        assert_eq!(
+            QuotaLimits {
+                max_concurrent_queries: Some(8),
+                max_write_rows_per_second: None,
+                query_timeout: Some(Duration::from_secs(30)),
+            },
+            options.default_limits
+        );
+        assert_eq!(2, options.rules.len());
+        assert_eq!(Some("alice"), options.rules[0].username.as_deref());
+        assert_eq!(
+            Some(1000),
+            options.rules[0].limits.max_write_rows_per_second
+        );
+
+        assert!(QuotaOptions::default().is_unlimited());
+    }
+
+    #[test]
+    fn test_limits_of() {
+        let options = QuotaOptions {
+            default_limits: QuotaLimits {
+                max_concurrent_queries: Some(8),
+                max_write_rows_per_second: None,
+                query_timeout: Some(Duration::from_secs(30)),
+            },
+            rules: vec![
+                QuotaRule {
+                    username: Some("alice".to_string()),
+                    database: None,
+                    limits: QuotaLimits {
+                        max_write_rows_per_second: Some(1000),
+                        ..Default::default()
+                    },
+                },
+                QuotaRule {
+                    username: None,
+                    database: Some("metrics".to_string()),
+                    limits: QuotaLimits {
+                        max_concurrent_queries: Some(2),
+                        ..Default::default()
+                    },
+                },
+            ],
+        };
+
+        // The first matched rule takes effect.
+        let limits = options.limits_of("alice", "metrics");
+        assert_eq!(Some(8), limits.max_concurrent_queries);
+        assert_eq!(Some(1000), limits.max_write_rows_per_second);
+        assert_eq!(Some(Duration::from_secs(30)), limits.query_timeout);
+
+        let limits = options.limits_of("bob", "metrics");
+        assert_eq!(Some(2), limits.max_concurrent_queries);
+        assert_eq!(None, limits.max_write_rows_per_second);
+
+        assert_eq!(options.default_limits, options.limits_of("bob", "public"));
+    }
+
+    #[test]
+    fn test_concurrent_queries() {
+        let manager = QuotaManager::new(QuotaOptions {
+            default_limits: QuotaLimits {
+                max_concurrent_queries: Some(2),
+                ..Default::default()
+            },
+            rules: vec![],
+        });
+        let alice = query_ctx("alice", "public");
+        let bob = query_ctx("bob", "public");
+
+        manager.start_query(&alice).unwrap();
+        manager.start_query(&alice).unwrap();
+        let err = manager.start_query(&alice).unwrap_err();
+        assert_matches!(err, Error::QuotaExceeded { .. });
+        assert_eq!(StatusCode::RateLimited, err.status_code());
+
+        // Quotas of other users are not affected.
+        manager.start_query(&bob).unwrap();
+
+        manager.abort_query(&alice);
+        manager.start_query(&alice).unwrap();
+
+        let output = manager.finish_query(Output::AffectedRows(1), &alice);
+        assert_matches!(output, Output::AffectedRows(1));
+        manager.start_query(&alice).unwrap();
+    }
+
+    #[test]
+    fn test_write_rows_per_second() {
+        let manager = QuotaManager::new(QuotaOptions {
+            default_limits: QuotaLimits {
+                max_write_rows_per_second: Some(10),
+                ..Default::default()
+            },
+            rules: vec![],
+        });
+        let alice = query_ctx("alice", "public");
+        let bob = query_ctx("bob", "public");
+
+        manager.check_write(&alice).unwrap();
+        manager.consume_write_rows(1000, &alice);
+        assert_matches!(
+            manager.check_write(&alice),
+            Err(Error::QuotaExceeded { .. })
+        );
+        manager.check_write(&bob).unwrap();
+    }
+
+    #[test]
+    fn test_evict_idle_states() {
+        let manager = QuotaManager::new(QuotaOptions {
+            default_limits: QuotaLimits {
+                max_concurrent_queries: Some(2),
+                max_write_rows_per_second: Some(10),
+                ..Default::default()
+            },
+            rules: vec![],
+        });
+        let alice = query_ctx("alice", "public");
+        let bob = query_ctx("bob", "public");
+        let num_states = || manager.states.lock().unwrap().states.len();
+
+        manager.start_query(&alice).unwrap();
+        manager.check_write(&bob).unwrap();
+        manager.consume_write_rows(1000, &bob);
+        assert_eq!(2, num_states());
+
+        // Neither the running query nor the rows written are forgotten.
+        manager.states.lock().unwrap().evict_idle();
+        assert_eq!(2, num_states());
+        assert!(manager.check_write(&bob).is_err());
+
+        manager.abort_query(&alice);
+        manager.states.lock().unwrap().evict_idle();
+        assert_eq!(1, num_states());
+        assert!(!manager
+            .states
+            .lock()
+            .unwrap()
+            .states
+            .contains_key(&QuotaKey::new(&alice)));
+    }
+
+    fn pending_stream() -> SendableRecordBatchStream {
+        Box::pin(RecordBatchStreamAdaptor {
+            schema: Arc::new(Schema::new(vec![])),
+            stream: Box::pin(futures::stream::pending::<
+                common_recordbatch::error::Result<RecordBatch>,
+            >()),
+            output_ordering: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_query_timeout() {
+        let manager = QuotaManager::new(QuotaOptions {
+            default_limits: QuotaLimits {
+                max_concurrent_queries: Some(1),
+                query_timeout: Some(Duration::from_millis(10)),
+                ..Default::default()
+            },
+            rules: vec![],
+        });
+        let alice = query_ctx("alice", "public");
+
+        manager.start_query(&alice).unwrap();
+        let output = execute_with_timeout(
+            manager.query_timeout(&alice),
+            async { Ok(Output::Stream(pending_stream())) },
+            |e| e,
+        )
+        .await
+        .unwrap();
+        let Output::Stream(mut stream) = manager.finish_query(output, &alice) else { unreachable!() };
+
+        // The query keeps running until the stream is dropped.
+        assert!(manager.start_query(&alice).is_err());
+
+        let err = stream.next().await.unwrap().unwrap_err();
+        assert!(err.to_string().contains("Query timed out"), "{err}");
+        assert!(stream.next().await.is_none());
+
+        drop(stream);
+        manager.start_query(&alice).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_query_timeout_includes_execution() {
+        let timeout = Some(Duration::from_millis(50));
+
+        // The execution itself times out.
+        let result = execute_with_timeout(
+            timeout,
+            async {
+                tokio::time::sleep(Duration::from_secs(10)).await;
+                Ok(Output::AffectedRows(1))
+            },
+            |e| e,
+        )
+        .await;
+        assert_matches!(result, Err(Error::QueryTimeout { .. }));
+
+        // The time spent on execution is deducted from reading the stream.
+        let Output::Stream(mut stream) = execute_with_timeout(
+            timeout,
+            async {
+                tokio::time::sleep(Duration::from_millis(40)).await;
+                Ok(Output::Stream(pending_stream()))
+            },
+            |e| e,
+        )
+        .await
+        .unwrap() else { unreachable!() };
+        let start = Instant::now();
+        assert!(stream.next().await.unwrap().is_err());
+        assert!(start.elapsed() < Duration::from_millis(40));
+
+        let result = execute_with_timeout(None, async { Ok(Output::AffectedRows(1)) }, |e| e).await;
+        assert_matches!(result, Ok(Output::AffectedRows(1)));
+    }
+
+    #[test]
+    fn test_install_quota_interceptors() {
+        let plugins = Plugins::new();
+        install_quota_interceptors(&plugins, &QuotaOptions::default()).unwrap();
+        assert!(plugins.get::<QuotaManagerRef>().is_none());
+
+        let options = QuotaOptions {
+            default_limits: QuotaLimits {
+                max_concurrent_queries: Some(2),
+                ..Default::default()
+            },
+            rules: vec![],
+        };
+        install_quota_interceptors(&plugins, &options).unwrap();
+        assert!(plugins.get::<QuotaManagerRef>().is_some());
+        assert!(plugins.get::<SqlQueryInterceptorRef<Error>>().is_some());
+
+        // Other interceptors are not overridden.
+        let err = install_quota_interceptors(&plugins, &options).unwrap_err();
+        assert_matches!(err, Error::QuotaInterceptorConflict { .. });
+    }
+}
@@ -22,6 +22,8 @@ use common_telemetry::info;
 use servers::auth::UserProviderRef;
 use servers::configurator::ConfiguratorRef;
 use servers::error::Error::InternalIo;
+use servers::graphite::template::Templates;
+use servers::graphite::GraphiteServer;
 use servers::grpc::GrpcServer;
 use servers::http::HttpServerBuilder;
 use servers::metrics_handler::MetricsHandler;
@@ -149,6 +151,23 @@ impl Services {
             set_opentsdb_handler = true;
         }
 
+        if let Some(opts) = &opts.graphite_options {
+            let addr = parse_addr(&opts.addr)?;
+
+            let io_runtime = Arc::new(
+                RuntimeBuilder::default()
+                    .worker_threads(opts.runtime_size)
+                    .thread_name("graphite-io-handlers")
+                    .build()
+                    .context(error::RuntimeResourceSnafu)?,
+            );
+
+            let templates = Templates::try_new(&opts.templates).context(error::StartServerSnafu)?;
+            let server = GraphiteServer::create_server(instance.clone(), templates, io_runtime);
+
+            result.push((server, addr));
+        }
+
         if let Some(http_options) = &opts.http_options {
             let http_addr = parse_addr(&http_options.addr)?;
 
@@ -180,6 +199,7 @@ impl Services {
 
             let http_server = http_server_builder
                 .with_metrics_handler(MetricsHandler)
+                .with_otlp_handler(instance.clone())
                 .with_script_handler(instance.clone())
                 .with_configurator(plugins.get::<ConfiguratorRef>())
                 .with_greptime_config_options(opts.to_toml_string())
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod graphite;
 pub mod grpc;
 pub mod influxdb;
 pub mod mysql;
@@ -20,6 +21,7 @@ pub mod postgres;
 pub mod prom_store;
 pub mod prometheus;
 
+pub use graphite::GraphiteOptions;
 pub use grpc::GrpcOptions;
 pub use influxdb::InfluxdbOptions;
 pub use mysql::MysqlOptions;
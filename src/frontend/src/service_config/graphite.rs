@@ -0,0 +1,34 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GraphiteOptions {
+    pub addr: String,
+    pub runtime_size: usize,
+    /// Template rules mapping metric paths to tables and tags, see
+    /// [Templates](servers::graphite::template::Templates).
+    pub templates: Vec<String>,
+}
+
+impl Default for GraphiteOptions {
+    fn default() -> Self {
+        Self {
+            addr: "127.0.0.1:2003".to_string(),
+            runtime_size: 2,
+            templates: vec![],
+        }
+    }
+}
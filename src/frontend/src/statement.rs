@@ -34,7 +34,7 @@ use query::query_engine::SqlStatementExecutorRef;
 use query::QueryEngineRef;
 use session::context::QueryContextRef;
 use snafu::{ensure, OptionExt, ResultExt};
-use sql::statements::copy::{CopyDatabaseArgument, CopyTable, CopyTableArgument};
+use sql::statements::copy::{CopyDatabase, CopyDatabaseArgument, CopyTable, CopyTableArgument};
 use sql::statements::statement::Statement;
 use table::engine::TableReference;
 use table::requests::{CopyDatabaseRequest, CopyDirection, CopyTableRequest};
@@ -74,7 +74,9 @@ impl StatementExecutor {
     ) -> Result<Output> {
         match stmt {
             QueryStatement::Sql(stmt) => self.execute_sql(stmt, query_ctx).await,
-            QueryStatement::Promql(_) => self.plan_exec(stmt, query_ctx).await,
+            QueryStatement::Promql(_) | QueryStatement::Influxql(_) => {
+                self.plan_exec(stmt, query_ctx).await
+            }
         }
     }
 
@@ -120,10 +122,16 @@ impl StatementExecutor {
                 }
             }
 
-            Statement::Copy(sql::statements::copy::Copy::CopyDatabase(arg)) => {
-                self.copy_database(to_copy_database_request(arg, &query_ctx)?)
-                    .await
-            }
+            Statement::Copy(sql::statements::copy::Copy::CopyDatabase(stmt)) => match stmt {
+                CopyDatabase::To(arg) => {
+                    self.copy_database_to(to_copy_database_request(arg, &query_ctx)?, query_ctx)
+                        .await
+                }
+                CopyDatabase::From(arg) => {
+                    self.copy_database_from(to_copy_database_request(arg, &query_ctx)?, query_ctx)
+                        .await
+                }
+            },
 
             Statement::CreateDatabase(_)
             | Statement::CreateTable(_)
@@ -136,6 +144,13 @@ impl StatementExecutor {
                 .execute_sql(stmt, query_ctx)
                 .await
                 .context(ExecuteStatementSnafu),
+
+            Statement::CreateUser(_) | Statement::Grant(_) | Statement::Revoke(_) => {
+                error::NotSupportedSnafu {
+                    feat: "user management statements in statement executor",
+                }
+                .fail()
+            }
         }
     }
 
@@ -12,21 +12,48 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
 use common_datasource::file_format::Format;
+use common_datasource::lister::{Lister, Source};
+use common_datasource::object_store::{build_backend, parse_url};
 use common_query::Output;
 use common_telemetry::info;
-use snafu::{ensure, ResultExt};
+use datatypes::value::Value;
+use futures::{StreamExt, TryStreamExt};
+use object_store::ObjectStore;
+use session::context::{QueryContext, QueryContextRef};
+use snafu::{ensure, OptionExt, ResultExt};
+use sql::ast::{Ident, ObjectName};
+use sql::dialect::GreptimeDbDialect;
+use sql::parser::ParserContext;
+use sql::statements::show::ShowCreateTable;
+use sql::statements::statement::Statement;
 use table::requests::{CopyDatabaseRequest, CopyDirection, CopyTableRequest};
 
 use crate::error;
-use crate::error::{CatalogSnafu, InvalidCopyParameterSnafu};
+use crate::error::{
+    CatalogSnafu, ExecuteStatementSnafu, InvalidCopyParameterSnafu, InvalidSqlSnafu,
+    UnexpectedSnafu,
+};
 use crate::statement::StatementExecutor;
 
 pub(crate) const COPY_DATABASE_TIME_START_KEY: &str = "start_time";
 pub(crate) const COPY_DATABASE_TIME_END_KEY: &str = "end_time";
 
+/// Suffix of the files holding the `CREATE TABLE` statements of exported tables.
+const TABLE_SCHEMA_FILE_SUFFIX: &str = ".sql";
+/// Max number of data files imported at the same time.
+const COPY_DATABASE_FROM_PARALLELISM: usize = 8;
+
 impl StatementExecutor {
-    pub(crate) async fn copy_database(&self, req: CopyDatabaseRequest) -> error::Result<Output> {
+    /// Exports every table of the database to a data file, along with a schema
+    /// file holding its `SHOW CREATE TABLE` output.
+    pub(crate) async fn copy_database_to(
+        &self,
+        req: CopyDatabaseRequest,
+        ctx: QueryContextRef,
+    ) -> error::Result<Output> {
         // location must end with / so that every table is exported to a file.
         ensure!(
             req.location.ends_with('/'),
@@ -50,12 +77,29 @@ impl StatementExecutor {
             .context(error::ParseFileFormatSnafu)?
             .suffix();
 
+        let (_schema, _host, path) = parse_url(&req.location).context(error::ParseUrlSnafu)?;
+        let object_store =
+            build_backend(&req.location, &req.connection).context(error::BuildBackendSnafu)?;
+        let query_ctx = Arc::new(QueryContext::with(&req.catalog_name, &req.schema_name));
+        // Statements issued on behalf of the copy run as the user who issued it.
+        query_ctx.set_current_user(ctx.current_user());
+
         let mut exported_rows = 0;
         for table_name in table_names {
             // TODO(hl): remove this hardcode once we've removed numbers table.
             if table_name == "numbers" {
                 continue;
             }
+            let schema_file = format!("{path}{table_name}{TABLE_SCHEMA_FILE_SUFFIX}");
+            self.export_table_schema(
+                &req,
+                &table_name,
+                &object_store,
+                &schema_file,
+                query_ctx.clone(),
+            )
+            .await?;
+
             let mut table_file = req.location.clone();
             table_file.push_str(&table_name);
             table_file.push_str(suffix);
@@ -81,4 +125,154 @@ impl StatementExecutor {
         }
         Ok(Output::AffectedRows(exported_rows))
     }
+
+    /// Imports a database exported by [StatementExecutor::copy_database_to].
+    /// The missing tables are created from the schema files first, then the
+    /// data files are imported in parallel.
+    pub(crate) async fn copy_database_from(
+        &self,
+        req: CopyDatabaseRequest,
+        ctx: QueryContextRef,
+    ) -> error::Result<Output> {
+        ensure!(
+            req.location.ends_with('/'),
+            InvalidCopyParameterSnafu {
+                key: "location",
+                value: req.location,
+            }
+        );
+
+        info!(
+            "Copy database {}.{} from dir: {}",
+            req.catalog_name, req.schema_name, req.location
+        );
+        let suffix = Format::try_from(&req.with)
+            .context(error::ParseFileFormatSnafu)?
+            .suffix();
+
+        let (_schema, _host, path) = parse_url(&req.location).context(error::ParseUrlSnafu)?;
+        let object_store =
+            build_backend(&req.location, &req.connection).context(error::BuildBackendSnafu)?;
+        let entries = Lister::new(object_store.clone(), Source::Dir, path, None)
+            .list()
+            .await
+            .context(error::ListObjectsSnafu)?;
+
+        let query_ctx = Arc::new(QueryContext::with(&req.catalog_name, &req.schema_name));
+        // Statements issued on behalf of the copy run as the user who issued it.
+        query_ctx.set_current_user(ctx.current_user());
+        for entry in &entries {
+            if entry.name().ends_with(TABLE_SCHEMA_FILE_SUFFIX) {
+                self.import_table_schema(&object_store, entry.path(), query_ctx.clone())
+                    .await?;
+            }
+        }
+
+        let requests = entries
+            .iter()
+            .filter_map(|entry| {
+                let table_name = entry.name().strip_suffix(suffix)?;
+                Some(CopyTableRequest {
+                    catalog_name: req.catalog_name.clone(),
+                    schema_name: req.schema_name.clone(),
+                    table_name: table_name.to_string(),
+                    location: format!("{}{}", req.location, entry.name()),
+                    with: req.with.clone(),
+                    connection: req.connection.clone(),
+                    pattern: None,
+                    direction: CopyDirection::Import,
+                    timestamp_range: None,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let imported_rows = futures::stream::iter(requests)
+            .map(|req| {
+                info!(
+                    "Copy table: {}.{}.{} from {}",
+                    req.catalog_name, req.schema_name, req.table_name, req.location
+                );
+                self.copy_table_from(req)
+            })
+            .buffer_unordered(COPY_DATABASE_FROM_PARALLELISM)
+            .try_fold(0, |acc, rows| async move { Ok(acc + rows) })
+            .await?;
+        Ok(Output::AffectedRows(imported_rows))
+    }
+
+    async fn export_table_schema(
+        &self,
+        req: &CopyDatabaseRequest,
+        table_name: &str,
+        object_store: &ObjectStore,
+        path: &str,
+        query_ctx: QueryContextRef,
+    ) -> error::Result<()> {
+        let stmt = Statement::ShowCreateTable(ShowCreateTable {
+            table_name: ObjectName(vec![
+                Ident::new(&req.catalog_name),
+                Ident::new(&req.schema_name),
+                Ident::new(table_name),
+            ]),
+        });
+        let output = self
+            .sql_stmt_executor
+            .execute_sql(stmt, query_ctx)
+            .await
+            .context(ExecuteStatementSnafu)?;
+
+        // The output of SHOW CREATE TABLE has two columns: the table name and the
+        // CREATE TABLE statement.
+        let sql = match output {
+            Output::RecordBatches(batches) => {
+                batches.iter().next().map(|batch| batch.column(1).get(0))
+            }
+            _ => None,
+        };
+        let Some(Value::String(sql)) = sql else {
+            return UnexpectedSnafu {
+                violated: format!("SHOW CREATE TABLE of {table_name} returns no statement"),
+            }
+            .fail();
+        };
+
+        object_store
+            .write(path, sql.as_utf8().as_bytes().to_vec())
+            .await
+            .context(error::WriteObjectSnafu { path })
+    }
+
+    async fn import_table_schema(
+        &self,
+        object_store: &ObjectStore,
+        path: &str,
+        query_ctx: QueryContextRef,
+    ) -> error::Result<()> {
+        let bytes = object_store
+            .read(path)
+            .await
+            .context(error::ReadObjectSnafu { path })?;
+        let sql = String::from_utf8_lossy(&bytes);
+
+        let mut stmts = ParserContext::create_with_dialect(&sql, &GreptimeDbDialect {})
+            .context(error::ParseSqlSnafu)?;
+        let create_table = match stmts.pop() {
+            Some(Statement::CreateTable(create_table)) if stmts.is_empty() => Some(create_table),
+            _ => None,
+        };
+        let mut create_table = create_table.with_context(|| InvalidSqlSnafu {
+            err_msg: format!("expect a CREATE TABLE statement in schema file {path}"),
+        })?;
+
+        // Keeps the existing tables.
+        create_table.if_not_exists = true;
+        info!("Create table {} from {}", create_table.name, path);
+
+        let _ = self
+            .sql_stmt_executor
+            .execute_sql(Statement::CreateTable(create_table), query_ctx)
+            .await
+            .context(ExecuteStatementSnafu)?;
+        Ok(())
+    }
 }
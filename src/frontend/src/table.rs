@@ -383,6 +383,7 @@ pub(crate) mod test {
     use meter_core::global::global_registry;
     use meter_core::write_calc::WriteCalculator;
     use partition::columns::RangeColumnsPartitionRule;
+    use partition::hash::HashPartitionRule;
     use partition::manager::{PartitionRuleManager, PartitionRuleManagerRef};
     use partition::partition::{PartitionBound, PartitionDef};
     use partition::range::RangePartitionRule;
@@ -731,6 +732,66 @@ pub(crate) mod test {
         ));
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_find_regions_by_hash_partition_rule() {
+        let partition_manager = Arc::new(PartitionRuleManager::new(Arc::new(TableRoutes::new(
+            Arc::new(MetaClient::default()),
+        ))));
+
+        // PARTITION BY HASH (a) PARTITIONS 4
+        let partition_rule: PartitionRuleRef =
+            Arc::new(HashPartitionRule::new("a", vec![0_u32, 1, 2, 3])) as _;
+        let region_of = |v: i32| partition_rule.find_region(&[v.into()]).unwrap();
+
+        let test = |filters: Vec<Expr>, expect_regions: Vec<RegionNumber>| {
+            let mut regions = partition_manager
+                .find_regions_by_filters(partition_rule.clone(), filters.as_slice())
+                .unwrap();
+            regions.sort();
+            assert_eq!(regions, expect_regions);
+        };
+
+        test(
+            vec![binary_expr(col("a"), Operator::Eq, lit(45)).into()], // a = 45
+            vec![region_of(45)],
+        );
+        test(
+            vec![binary_expr(lit(45), Operator::Eq, col("a")).into()], // 45 = a
+            vec![region_of(45)],
+        );
+        // The literal of a wider integer type goes to the same region.
+        test(
+            vec![binary_expr(col("a"), Operator::Eq, lit(45_i64)).into()], // a = 45
+            vec![region_of(45)],
+        );
+
+        let mut expect_regions = vec![region_of(1), region_of(2)];
+        expect_regions.sort();
+        expect_regions.dedup();
+        test(
+            vec![or(
+                binary_expr(col("a"), Operator::Eq, lit(1)),
+                binary_expr(col("a"), Operator::Eq, lit(2)),
+            )
+            .into()], // a = 1 OR a = 2
+            expect_regions,
+        );
+
+        // Only equality filters on the partition column are able to prune regions.
+        test(
+            vec![binary_expr(col("a"), Operator::Lt, lit(10)).into()], // a < 10
+            vec![0, 1, 2, 3],
+        );
+        test(
+            vec![binary_expr(col("a"), Operator::NotEq, lit(45)).into()], // a != 45
+            vec![0, 1, 2, 3],
+        );
+        test(
+            vec![binary_expr(col("b"), Operator::Eq, lit(45)).into()], // b = 45
+            vec![0, 1, 2, 3],
+        );
+    }
+
     #[derive(Default)]
     struct MockCollector {
         pub write_sum: AtomicU32,
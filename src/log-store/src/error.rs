@@ -80,6 +80,63 @@ pub enum Error {
         attempt_index: u64,
         location: Location,
     },
+
+    #[snafu(display("Failed to write segment {}, source: {}", path, source))]
+    WriteSegment {
+        path: String,
+        source: object_store::Error,
+        location: Location,
+    },
+
+    #[snafu(display("Failed to read segment {}, source: {}", path, source))]
+    ReadSegment {
+        path: String,
+        source: object_store::Error,
+        location: Location,
+    },
+
+    #[snafu(display("Failed to list segments in {}, source: {}", path, source))]
+    ListSegments {
+        path: String,
+        source: object_store::Error,
+        location: Location,
+    },
+
+    #[snafu(display("Failed to delete segments {}, source: {}", path, source))]
+    DeleteSegment {
+        path: String,
+        source: object_store::Error,
+        location: Location,
+    },
+
+    #[snafu(display("Segment {} is corrupted, reason: {}", path, reason))]
+    CorruptedSegment {
+        path: String,
+        reason: String,
+        location: Location,
+    },
+
+    #[snafu(display(
+        "Namespace {} is claimed by another log store, current epoch: {}",
+        namespace,
+        epoch
+    ))]
+    Fenced {
+        namespace: u64,
+        epoch: u64,
+        location: Location,
+    },
+
+    #[snafu(display(
+        "Failed to claim an epoch of namespace {} in {} attempts, it's being claimed by other log stores",
+        namespace,
+        attempts
+    ))]
+    ClaimEpoch {
+        namespace: u64,
+        attempts: usize,
+        location: Location,
+    },
 }
 
 impl ErrorExt for Error {
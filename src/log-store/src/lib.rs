@@ -14,9 +14,12 @@
 
 mod config;
 pub mod error;
+mod log_store_impl;
 mod noop;
+pub mod object_store_log;
 pub mod raft_engine;
 pub mod test_util;
 
 pub use config::LogConfig;
+pub use log_store_impl::LogStoreImpl;
 pub use noop::NoopLogStore;
@@ -0,0 +1,123 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use store_api::logstore::entry::Id;
+use store_api::logstore::entry_stream::SendableEntryStream;
+use store_api::logstore::{AppendResponse, LogStore};
+
+use crate::error::{Error, Result};
+use crate::object_store_log::ObjectStoreLogStore;
+use crate::raft_engine::log_store::RaftEngineLogStore;
+use crate::raft_engine::protos::logstore::{EntryImpl, NamespaceImpl as Namespace};
+
+/// A [LogStore] backed by one of the log store implementations, so the
+/// implementation can be chosen by config at runtime.
+#[derive(Debug)]
+pub enum LogStoreImpl {
+    RaftEngine(RaftEngineLogStore),
+    ObjectStore(ObjectStoreLogStore),
+}
+
+impl From<RaftEngineLogStore> for LogStoreImpl {
+    fn from(log_store: RaftEngineLogStore) -> Self {
+        LogStoreImpl::RaftEngine(log_store)
+    }
+}
+
+impl From<ObjectStoreLogStore> for LogStoreImpl {
+    fn from(log_store: ObjectStoreLogStore) -> Self {
+        LogStoreImpl::ObjectStore(log_store)
+    }
+}
+
+#[async_trait::async_trait]
+impl LogStore for LogStoreImpl {
+    type Error = Error;
+    type Namespace = Namespace;
+    type Entry = EntryImpl;
+
+    async fn stop(&self) -> Result<()> {
+        match self {
+            LogStoreImpl::RaftEngine(log_store) => log_store.stop().await,
+            LogStoreImpl::ObjectStore(log_store) => log_store.stop().await,
+        }
+    }
+
+    async fn append(&self, e: Self::Entry) -> Result<AppendResponse> {
+        match self {
+            LogStoreImpl::RaftEngine(log_store) => log_store.append(e).await,
+            LogStoreImpl::ObjectStore(log_store) => log_store.append(e).await,
+        }
+    }
+
+    async fn append_batch(&self, entries: Vec<Self::Entry>) -> Result<()> {
+        match self {
+            LogStoreImpl::RaftEngine(log_store) => log_store.append_batch(entries).await,
+            LogStoreImpl::ObjectStore(log_store) => log_store.append_batch(entries).await,
+        }
+    }
+
+    async fn read(
+        &self,
+        ns: &Self::Namespace,
+        id: Id,
+    ) -> Result<SendableEntryStream<'_, Self::Entry, Self::Error>> {
+        match self {
+            LogStoreImpl::RaftEngine(log_store) => log_store.read(ns, id).await,
+            LogStoreImpl::ObjectStore(log_store) => log_store.read(ns, id).await,
+        }
+    }
+
+    async fn create_namespace(&self, ns: &Self::Namespace) -> Result<()> {
+        match self {
+            LogStoreImpl::RaftEngine(log_store) => log_store.create_namespace(ns).await,
+            LogStoreImpl::ObjectStore(log_store) => log_store.create_namespace(ns).await,
+        }
+    }
+
+    async fn delete_namespace(&self, ns: &Self::Namespace) -> Result<()> {
+        match self {
+            LogStoreImpl::RaftEngine(log_store) => log_store.delete_namespace(ns).await,
+            LogStoreImpl::ObjectStore(log_store) => log_store.delete_namespace(ns).await,
+        }
+    }
+
+    async fn list_namespaces(&self) -> Result<Vec<Self::Namespace>> {
+        match self {
+            LogStoreImpl::RaftEngine(log_store) => log_store.list_namespaces().await,
+            LogStoreImpl::ObjectStore(log_store) => log_store.list_namespaces().await,
+        }
+    }
+
+    fn entry<D: AsRef<[u8]>>(&self, data: D, id: Id, ns: Self::Namespace) -> Self::Entry {
+        match self {
+            LogStoreImpl::RaftEngine(log_store) => log_store.entry(data, id, ns),
+            LogStoreImpl::ObjectStore(log_store) => log_store.entry(data, id, ns),
+        }
+    }
+
+    fn namespace(&self, id: store_api::logstore::namespace::Id) -> Self::Namespace {
+        match self {
+            LogStoreImpl::RaftEngine(log_store) => log_store.namespace(id),
+            LogStoreImpl::ObjectStore(log_store) => log_store.namespace(id),
+        }
+    }
+
+    async fn obsolete(&self, namespace: Self::Namespace, id: Id) -> Result<()> {
+        match self {
+            LogStoreImpl::RaftEngine(log_store) => log_store.obsolete(namespace, id).await,
+            LogStoreImpl::ObjectStore(log_store) => log_store.obsolete(namespace, id).await,
+        }
+    }
+}
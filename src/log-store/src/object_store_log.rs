@@ -0,0 +1,937 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A [LogStore] that stores entries in an [ObjectStore], so the WAL of a region
+//! can be replayed by any datanode that has access to the same object store.
+//!
+//! Each namespace has its own directory under the log store directory. Entries
+//! appended in one batch are written to a segment object of their namespace,
+//! named by the epoch of the writer and ids of the first and the last entry in
+//! the segment:
+//!
+//! ```text
+//! {dir}/{namespace}/{epoch:020}-{first_id:020}-{last_id:020}.segment
+//! ```
+//!
+//! A log store claims a new epoch of a namespace before it reads or writes the
+//! namespace, e.g. when a region is opened on another datanode after failover, by
+//! writing an epoch object named by the epoch and a random token of the claim:
+//!
+//! ```text
+//! {dir}/{namespace}/epoch/{epoch:020}-{token:020}.epoch
+//! ```
+//!
+//! Object stores can't create an object only if it's absent, so the claim is checked
+//! by listing the epoch objects after writing one: it fails if another epoch object
+//! of the same or a newer epoch exists, and is retried with a newer epoch. Then the
+//! log store lists the segments, and writes the id next to the last entry it can
+//! read to the epoch object. An epoch object without the id fences nothing.
+//!
+//! A log store checks that no newer epoch is claimed after writing a segment and
+//! before acknowledging the append. So a segment of an older epoch that is written
+//! after the claim lists the segments is never acknowledged, and readers ignore it
+//! if it contains entries not less than the id in the epoch object.
+//!
+//! Epoch objects are deleted with obsolete segments once no segment of an older
+//! epoch is left, except the ones of the latest epoch.
+//!
+//! An entry in a segment is encoded as:
+//!
+//! ```text
+//! | namespace (u64) | id (u64) | data length (u32) | data | crc32 (u32) |
+//! ```
+//!
+//! All integers are little-endian and the crc covers the fields before it.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Debug, Formatter};
+use std::time::Duration;
+
+use async_stream::stream;
+use bytes::{Buf, BufMut};
+use common_telemetry::{debug, info, warn};
+use futures::future::try_join_all;
+use futures::TryStreamExt;
+use object_store::{util, ErrorKind, ObjectStore};
+use snafu::{ensure, ResultExt};
+use store_api::logstore::entry::Id;
+use store_api::logstore::entry_stream::SendableEntryStream;
+use store_api::logstore::namespace::Namespace as NamespaceTrait;
+use store_api::logstore::{AppendResponse, LogStore};
+use tokio::sync::Mutex;
+
+use crate::error::{
+    ClaimEpochSnafu, CorruptedSegmentSnafu, DeleteSegmentSnafu, Error, FencedSnafu,
+    ListSegmentsSnafu, ReadSegmentSnafu, Result, WriteSegmentSnafu,
+};
+use crate::raft_engine::protos::logstore::{EntryImpl, NamespaceImpl as Namespace};
+
+const SEGMENT_SUFFIX: &str = ".segment";
+const EPOCH_DIR: &str = "epoch/";
+const EPOCH_SUFFIX: &str = ".epoch";
+/// Attempts to claim an epoch, claims only conflict when log stores claim the same
+/// namespace at the same time.
+const MAX_CLAIM_ATTEMPTS: usize = 3;
+/// Size of the namespace, id and data length of an entry.
+const ENTRY_HEADER_SIZE: usize = 20;
+const CRC_SIZE: usize = 4;
+const CRC: crc::Crc<u32> = crc::Crc::<u32>::new(&crc::CRC_32_ISCSI);
+
+/// Epoch of a namespace, increased each time a log store claims the namespace.
+type Epoch = u64;
+
+/// A log store that writes entries to an [ObjectStore].
+pub struct ObjectStoreLogStore {
+    /// Directory of the log store, ends with `/`.
+    dir: String,
+    object_store: ObjectStore,
+    /// Epochs claimed by this log store, keyed by namespace id.
+    epochs: Mutex<HashMap<u64, Epoch>>,
+}
+
+impl ObjectStoreLogStore {
+    pub fn new(dir: &str, object_store: ObjectStore) -> ObjectStoreLogStore {
+        ObjectStoreLogStore {
+            dir: util::normalize_dir(dir),
+            object_store,
+            epochs: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn namespace_dir(&self, ns_id: u64) -> String {
+        format!("{}{}/", self.dir, ns_id)
+    }
+
+    fn epoch_dir(&self, ns_id: u64) -> String {
+        format!("{}{}", self.namespace_dir(ns_id), EPOCH_DIR)
+    }
+
+    /// Returns the epoch of the namespace claimed by this log store, claims a new one
+    /// if there isn't.
+    async fn epoch(&self, ns_id: u64) -> Result<Epoch> {
+        let mut epochs = self.epochs.lock().await;
+        if let Some(epoch) = epochs.get(&ns_id) {
+            return Ok(*epoch);
+        }
+
+        for attempt in 1..=MAX_CLAIM_ATTEMPTS {
+            if let Some(epoch) = self.try_claim(ns_id).await? {
+                let _ = epochs.insert(ns_id, epoch);
+                return Ok(epoch);
+            }
+            if attempt < MAX_CLAIM_ATTEMPTS {
+                // Backs off randomly so log stores claiming at the same time don't
+                // conflict again.
+                let backoff = Duration::from_millis(rand::random::<u64>() % 100);
+                tokio::time::sleep(backoff).await;
+            }
+        }
+        ClaimEpochSnafu {
+            namespace: ns_id,
+            attempts: MAX_CLAIM_ATTEMPTS,
+        }
+        .fail()
+    }
+
+    /// Claims the epoch next to the latest one of the namespace. Returns `None` if
+    /// another log store claims the same or a newer epoch at the same time.
+    async fn try_claim(&self, ns_id: u64) -> Result<Option<Epoch>> {
+        let (segments, markers) = self.list_objects(ns_id).await?;
+        let epoch = segments
+            .iter()
+            .map(|segment| segment.epoch)
+            .chain(markers.iter().map(|marker| marker.epoch))
+            .max()
+            .unwrap_or(0)
+            + 1;
+        let token = rand::random();
+        let path = format!("{}{}", self.epoch_dir(ns_id), epoch_name(epoch, token));
+        // The epoch object fences nothing until the next entry id is written.
+        self.object_store
+            .write(&path, Vec::new())
+            .await
+            .context(WriteSegmentSnafu { path: &path })?;
+
+        let conflicted = self
+            .list_markers(ns_id)
+            .await?
+            .iter()
+            .any(|marker| marker.epoch >= epoch && marker.token != token);
+        if conflicted {
+            warn!(
+                "Claiming epoch {} of namespace {} conflicts with another log store",
+                epoch, ns_id
+            );
+            self.object_store
+                .delete(&path)
+                .await
+                .context(DeleteSegmentSnafu { path })?;
+            return Ok(None);
+        }
+
+        // Segments are listed after the claim, so any segment of older epochs written
+        // later is not acknowledged, as its writer finds this claim.
+        let (segments, markers) = self.list_objects(ns_id).await?;
+        let next_id = valid_segments(segments, &markers)
+            .iter()
+            .map(|segment| segment.last_id + 1)
+            .chain(markers.iter().filter_map(|marker| marker.next_id))
+            .max()
+            .unwrap_or(0);
+        info!(
+            "Claim epoch {} of namespace {}, next entry id: {}",
+            epoch, ns_id, next_id
+        );
+        self.object_store
+            .write(&path, next_id.to_le_bytes().to_vec())
+            .await
+            .context(WriteSegmentSnafu { path })?;
+        Ok(Some(epoch))
+    }
+
+    /// Writes entries of the namespace to a segment.
+    async fn write_segment(&self, ns_id: u64, entries: &[EntryImpl]) -> Result<()> {
+        let (Some(first), Some(last)) = (entries.first(), entries.last()) else {
+            return Ok(());
+        };
+        let epoch = self.epoch(ns_id).await?;
+        let path = format!(
+            "{}{}",
+            self.namespace_dir(ns_id),
+            segment_name(epoch, first.id, last.id)
+        );
+        let data = encode_entries(entries);
+        debug!(
+            "Write {} entries to segment {}, size: {}",
+            entries.len(),
+            path,
+            data.len()
+        );
+
+        self.object_store
+            .write(&path, data)
+            .await
+            .context(WriteSegmentSnafu { path })?;
+
+        // The append is not acknowledged if another log store has claimed the
+        // namespace, as readers may ignore the segment.
+        let fenced = self
+            .list_markers(ns_id)
+            .await?
+            .iter()
+            .any(|marker| marker.epoch > epoch);
+        ensure!(
+            !fenced,
+            FencedSnafu {
+                namespace: ns_id,
+                epoch,
+            }
+        );
+        Ok(())
+    }
+
+    /// Lists all segments and epochs of the namespace.
+    async fn list_objects(&self, ns_id: u64) -> Result<(Vec<Segment>, Vec<EpochMarker>)> {
+        let dir = self.namespace_dir(ns_id);
+        let lister = match self.object_store.list(&dir).await {
+            Ok(lister) => lister,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok((Vec::new(), Vec::new())),
+            Err(e) => return Err(e).context(ListSegmentsSnafu { path: dir }),
+        };
+        let entries = util::collect(lister)
+            .await
+            .context(ListSegmentsSnafu { path: &dir })?;
+
+        let segments = entries
+            .iter()
+            .filter_map(|entry| {
+                let (epoch, first_id, last_id) = parse_segment_name(entry.name())?;
+                Some(Segment {
+                    path: entry.path().to_string(),
+                    epoch,
+                    first_id,
+                    last_id,
+                })
+            })
+            .collect();
+        let markers = self.list_markers(ns_id).await?;
+
+        Ok((segments, markers))
+    }
+
+    /// Lists all epoch objects of the namespace.
+    async fn list_markers(&self, ns_id: u64) -> Result<Vec<EpochMarker>> {
+        let dir = self.epoch_dir(ns_id);
+        let lister = match self.object_store.list(&dir).await {
+            Ok(lister) => lister,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e).context(ListSegmentsSnafu { path: dir }),
+        };
+        let entries = util::collect(lister)
+            .await
+            .context(ListSegmentsSnafu { path: &dir })?;
+
+        let mut markers = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let Some((epoch, token)) = parse_epoch_name(entry.name()) else {
+                continue;
+            };
+            let path = entry.path();
+            let data = self
+                .object_store
+                .read(path)
+                .await
+                .context(ReadSegmentSnafu { path })?;
+            let next_id = if data.is_empty() {
+                None
+            } else {
+                let next_id = data.try_into().map(Id::from_le_bytes).map_err(|_| {
+                    CorruptedSegmentSnafu {
+                        path,
+                        reason: "invalid epoch",
+                    }
+                    .build()
+                })?;
+                Some(next_id)
+            };
+            markers.push(EpochMarker {
+                path: path.to_string(),
+                epoch,
+                token,
+                next_id,
+            });
+        }
+        Ok(markers)
+    }
+
+    /// Lists segments of the namespace that are not fenced, sorted by ids of their
+    /// entries.
+    async fn list_segments(&self, ns_id: u64) -> Result<Vec<Segment>> {
+        let (segments, markers) = self.list_objects(ns_id).await?;
+        let mut segments = valid_segments(segments, &markers);
+        segments.sort_unstable_by_key(|segment| (segment.first_id, segment.last_id));
+
+        Ok(segments)
+    }
+}
+
+impl Debug for ObjectStoreLogStore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ObjectStoreLogStore")
+            .field("dir", &self.dir)
+            .finish()
+    }
+}
+
+/// A segment object of a namespace.
+#[derive(Debug)]
+struct Segment {
+    path: String,
+    /// Epoch of the log store writing the segment.
+    epoch: Epoch,
+    /// Id of the first entry in the segment.
+    first_id: Id,
+    /// Id of the last entry in the segment.
+    last_id: Id,
+}
+
+/// An epoch object of a namespace.
+#[derive(Debug)]
+struct EpochMarker {
+    path: String,
+    epoch: Epoch,
+    /// Random token of the claim, tells claims of the same epoch apart.
+    token: u64,
+    /// Id next to the last entry readable when the epoch is claimed, `None` if the
+    /// claim hasn't finished.
+    next_id: Option<Id>,
+}
+
+/// Removes segments written by stale writers, i.e. segments containing entries
+/// written after a newer epoch is claimed.
+fn valid_segments(segments: Vec<Segment>, markers: &[EpochMarker]) -> Vec<Segment> {
+    segments
+        .into_iter()
+        .filter(|segment| {
+            markers
+                .iter()
+                .filter(|marker| marker.epoch > segment.epoch)
+                .all(|marker| {
+                    marker
+                        .next_id
+                        .map_or(true, |next_id| segment.last_id < next_id)
+                })
+        })
+        .collect()
+}
+
+/// Returns the epoch objects that fence no segments left. An epoch object only
+/// fences segments of older epochs, the ones of the latest epoch are always kept to
+/// fence stale writers and to claim the next epoch.
+fn obsolete_markers<'a>(
+    segments: &[Segment],
+    markers: &'a [EpochMarker],
+) -> impl Iterator<Item = &'a EpochMarker> {
+    let latest = markers.iter().map(|marker| marker.epoch).max();
+    let oldest_segment = segments.iter().map(|segment| segment.epoch).min();
+    markers.iter().filter(move |marker| {
+        Some(marker.epoch) != latest && oldest_segment.map_or(true, |epoch| epoch >= marker.epoch)
+    })
+}
+
+fn segment_name(epoch: Epoch, first_id: Id, last_id: Id) -> String {
+    format!("{epoch:020}-{first_id:020}-{last_id:020}{SEGMENT_SUFFIX}")
+}
+
+/// Parses the epoch and ids of the first and the last entry from the segment name.
+fn parse_segment_name(name: &str) -> Option<(Epoch, Id, Id)> {
+    let mut parts = name.strip_suffix(SEGMENT_SUFFIX)?.split('-');
+    let epoch = parts.next()?.parse().ok()?;
+    let first = parts.next()?.parse().ok()?;
+    let last = parts.next()?.parse().ok()?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((epoch, first, last))
+}
+
+fn epoch_name(epoch: Epoch, token: u64) -> String {
+    format!("{epoch:020}-{token:020}{EPOCH_SUFFIX}")
+}
+
+/// Parses the epoch and the token of the claim from the epoch object name.
+fn parse_epoch_name(name: &str) -> Option<(Epoch, u64)> {
+    let (epoch, token) = name.strip_suffix(EPOCH_SUFFIX)?.split_once('-')?;
+    Some((epoch.parse().ok()?, token.parse().ok()?))
+}
+
+fn encode_entries(entries: &[EntryImpl]) -> Vec<u8> {
+    let size = entries
+        .iter()
+        .map(|e| ENTRY_HEADER_SIZE + e.data.len() + CRC_SIZE)
+        .sum();
+    let mut buf = Vec::with_capacity(size);
+    for e in entries {
+        let start = buf.len();
+        buf.put_u64_le(e.namespace_id);
+        buf.put_u64_le(e.id);
+        buf.put_u32_le(e.data.len() as u32);
+        buf.put_slice(&e.data);
+        let crc = CRC.checksum(&buf[start..]);
+        buf.put_u32_le(crc);
+    }
+    buf
+}
+
+fn decode_entries(path: &str, mut data: &[u8]) -> Result<Vec<EntryImpl>> {
+    let mut entries = Vec::new();
+    while !data.is_empty() {
+        ensure!(
+            data.len() >= ENTRY_HEADER_SIZE,
+            CorruptedSegmentSnafu {
+                path,
+                reason: format!("truncated entry header, remaining: {}", data.len()),
+            }
+        );
+        let entry_data = data;
+        let ns_id = data.get_u64_le();
+        let id = data.get_u64_le();
+        let len = data.get_u32_le() as usize;
+        ensure!(
+            data.len() >= len + CRC_SIZE,
+            CorruptedSegmentSnafu {
+                path,
+                reason: format!("truncated entry {id}, length: {len}"),
+            }
+        );
+        let crc = CRC.checksum(&entry_data[..ENTRY_HEADER_SIZE + len]);
+        let entry = EntryImpl::create(id, ns_id, data[..len].to_vec());
+        data.advance(len);
+        let expect_crc = data.get_u32_le();
+        ensure!(
+            crc == expect_crc,
+            CorruptedSegmentSnafu {
+                path,
+                reason: format!("checksum mismatch of entry {id}"),
+            }
+        );
+        entries.push(entry);
+    }
+
+    Ok(entries)
+}
+
+#[async_trait::async_trait]
+impl LogStore for ObjectStoreLogStore {
+    type Error = Error;
+    type Namespace = Namespace;
+    type Entry = EntryImpl;
+
+    async fn stop(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Append an entry to a new segment of its namespace.
+    async fn append(&self, e: Self::Entry) -> Result<AppendResponse> {
+        let entry_id = e.id;
+        self.write_segment(e.namespace_id, &[e]).await?;
+        Ok(AppendResponse { entry_id })
+    }
+
+    /// Append a batch of entries to logstore. Entries of each namespace are written
+    /// to one segment, so the batch is atomic within a namespace.
+    async fn append_batch(&self, entries: Vec<Self::Entry>) -> Result<()> {
+        let mut namespaces: BTreeMap<_, Vec<_>> = BTreeMap::new();
+        for e in entries {
+            namespaces.entry(e.namespace_id).or_default().push(e);
+        }
+
+        let _ = try_join_all(
+            namespaces
+                .iter()
+                .map(|(ns_id, entries)| self.write_segment(*ns_id, entries)),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Create a stream of entries from logstore in the given namespace. Entries are
+    /// yielded segment by segment in the order of their ids.
+    ///
+    /// Reading a namespace claims it, so other log stores can't append to it anymore.
+    async fn read(
+        &self,
+        ns: &Self::Namespace,
+        id: Id,
+    ) -> Result<SendableEntryStream<'_, Self::Entry, Self::Error>> {
+        let _ = self.epoch(ns.id()).await?;
+        let segments: Vec<_> = self
+            .list_segments(ns.id())
+            .await?
+            .into_iter()
+            .filter(|segment| segment.last_id >= id)
+            .collect();
+        info!(
+            "Read logstore, namespace: {}, start: {}, segments: {}",
+            ns.id(),
+            id,
+            segments.len()
+        );
+
+        let object_store = self.object_store.clone();
+        let s = stream!({
+            // Ids of entries yielded, segments written again by retries may contain
+            // entries already yielded.
+            let mut next_id = id;
+            for segment in segments {
+                let data = match object_store.read(&segment.path).await {
+                    Ok(data) => data,
+                    Err(e) => {
+                        yield Err(e).context(ReadSegmentSnafu { path: segment.path });
+                        return;
+                    }
+                };
+                match decode_entries(&segment.path, &data) {
+                    Ok(entries) => {
+                        let entries: Vec<_> =
+                            entries.into_iter().filter(|e| e.id >= next_id).collect();
+                        if let Some(last) = entries.last() {
+                            next_id = last.id + 1;
+                            yield Ok(entries);
+                        }
+                    }
+                    Err(e) => {
+                        yield Err(e);
+                        return;
+                    }
+                }
+            }
+        });
+        Ok(Box::pin(s))
+    }
+
+    async fn create_namespace(&self, ns: &Self::Namespace) -> Result<()> {
+        let dir = self.namespace_dir(ns.id());
+        self.object_store
+            .create_dir(&dir)
+            .await
+            .context(WriteSegmentSnafu { path: dir })
+    }
+
+    async fn delete_namespace(&self, ns: &Self::Namespace) -> Result<()> {
+        let mut epochs = self.epochs.lock().await;
+        let dir = self.namespace_dir(ns.id());
+        self.object_store
+            .remove_all(&dir)
+            .await
+            .context(DeleteSegmentSnafu { path: dir })?;
+        let _ = epochs.remove(&ns.id());
+        Ok(())
+    }
+
+    async fn list_namespaces(&self) -> Result<Vec<Self::Namespace>> {
+        let lister = match self.object_store.list(&self.dir).await {
+            Ok(lister) => lister,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e).context(ListSegmentsSnafu { path: &self.dir }),
+        };
+        let entries = util::collect(lister)
+            .await
+            .context(ListSegmentsSnafu { path: &self.dir })?;
+
+        let mut namespaces = Vec::with_capacity(entries.len());
+        for entry in entries {
+            // Skips objects that are not directories of namespaces.
+            let Some(name) = entry.name().strip_suffix('/') else {
+                continue;
+            };
+            let Ok(ns_id) = name.parse() else {
+                continue;
+            };
+            namespaces.push(Namespace::with_id(ns_id));
+        }
+        Ok(namespaces)
+    }
+
+    fn entry<D: AsRef<[u8]>>(&self, data: D, id: Id, ns: Self::Namespace) -> Self::Entry {
+        EntryImpl::create(id, ns.id(), data.as_ref().to_vec())
+    }
+
+    fn namespace(&self, id: store_api::logstore::namespace::Id) -> Self::Namespace {
+        Namespace::with_id(id)
+    }
+
+    /// Deletes segments whose entries are all obsolete, including fenced ones, and
+    /// epoch objects that fence no segments left.
+    async fn obsolete(&self, namespace: Self::Namespace, id: Id) -> Result<()> {
+        let (segments, markers) = self.list_objects(namespace.id()).await?;
+        let (obsolete, remaining): (Vec<_>, Vec<_>) = segments
+            .into_iter()
+            .partition(|segment| segment.last_id <= id);
+        let segment_num = obsolete.len();
+        let paths: Vec<_> = obsolete
+            .into_iter()
+            .map(|segment| segment.path)
+            .chain(obsolete_markers(&remaining, &markers).map(|marker| marker.path.clone()))
+            .collect();
+        if paths.is_empty() {
+            return Ok(());
+        }
+
+        info!(
+            "Deleting {} obsolete segments and {} epochs of namespace {}, id: {}",
+            segment_num,
+            paths.len() - segment_num,
+            namespace.id(),
+            id
+        );
+        self.object_store
+            .remove(paths.clone())
+            .await
+            .context(DeleteSegmentSnafu {
+                path: paths.join(","),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_test_util::temp_dir::{create_temp_dir, TempDir};
+    use futures_util::StreamExt;
+    use object_store::services::Fs;
+
+    use super::*;
+
+    fn new_object_store(dir: &TempDir) -> ObjectStore {
+        let mut builder = Fs::default();
+        let _ = builder.root(dir.path().to_str().unwrap());
+        ObjectStore::new(builder).unwrap().finish()
+    }
+
+    fn new_log_store(name: &str) -> (TempDir, ObjectStoreLogStore) {
+        let dir = create_temp_dir(name);
+        let object_store = new_object_store(&dir);
+
+        (dir, ObjectStoreLogStore::new("wal", object_store))
+    }
+
+    fn new_entry(ns_id: u64, id: Id) -> EntryImpl {
+        EntryImpl::create(id, ns_id, id.to_string().into_bytes())
+    }
+
+    async fn collect_ids(logstore: &ObjectStoreLogStore, ns_id: u64, id: Id) -> Vec<Id> {
+        let mut s = logstore.read(&Namespace::with_id(ns_id), id).await.unwrap();
+        let mut ids = Vec::new();
+        while let Some(r) = s.next().await {
+            for e in r.unwrap() {
+                assert_eq!(ns_id, e.namespace_id);
+                assert_eq!(e.id.to_string().into_bytes(), e.data);
+                ids.push(e.id);
+            }
+        }
+        ids
+    }
+
+    #[test]
+    fn test_encode_decode_entries() {
+        let entries = vec![new_entry(1, 3), new_entry(1, 4), new_entry(1, 10)];
+        let data = encode_entries(&entries);
+        assert_eq!(entries, decode_entries("test", &data).unwrap());
+
+        let err = decode_entries("test", &data[..data.len() - 1]).unwrap_err();
+        assert!(matches!(err, Error::CorruptedSegment { .. }), "{err:?}");
+
+        let mut data = data;
+        data[ENTRY_HEADER_SIZE] ^= 1;
+        let err = decode_entries("test", &data).unwrap_err();
+        assert!(matches!(err, Error::CorruptedSegment { .. }), "{err:?}");
+    }
+
+    #[test]
+    fn test_segment_name() {
+        let name = segment_name(2, 1, 20);
+        assert_eq!(
+            "00000000000000000002-00000000000000000001-00000000000000000020.segment",
+            name
+        );
+        assert_eq!(Some((2, 1, 20)), parse_segment_name(&name));
+        assert_eq!(None, parse_segment_name("2-1-20"));
+        assert_eq!(None, parse_segment_name("1-20.segment"));
+        assert_eq!(None, parse_segment_name("2-a-20.segment"));
+        assert_eq!(None, parse_segment_name("2-1-20-3.segment"));
+
+        let name = epoch_name(3, 7);
+        assert_eq!("00000000000000000003-00000000000000000007.epoch", name);
+        assert_eq!(Some((3, 7)), parse_epoch_name(&name));
+        assert_eq!(None, parse_epoch_name("3.epoch"));
+        assert_eq!(None, parse_epoch_name(&segment_name(3, 1, 2)));
+    }
+
+    #[tokio::test]
+    async fn test_manage_namespace() {
+        let (_dir, logstore) = new_log_store("object-store-logstore-namespace");
+        assert!(logstore.list_namespaces().await.unwrap().is_empty());
+
+        logstore
+            .create_namespace(&Namespace::with_id(42))
+            .await
+            .unwrap();
+        logstore.append(new_entry(43, 1)).await.unwrap();
+        let mut namespaces = logstore.list_namespaces().await.unwrap();
+        namespaces.sort_unstable_by_key(|ns| ns.id);
+        assert_eq!(
+            vec![Namespace::with_id(42), Namespace::with_id(43)],
+            namespaces
+        );
+
+        logstore
+            .delete_namespace(&Namespace::with_id(43))
+            .await
+            .unwrap();
+        assert_eq!(
+            vec![Namespace::with_id(42)],
+            logstore.list_namespaces().await.unwrap()
+        );
+        assert!(collect_ids(&logstore, 43, 0).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_append_and_read() {
+        let (_dir, logstore) = new_log_store("object-store-logstore-read");
+        // Appends more than 10 segments so segment names don't sort as strings of
+        // unpadded numbers.
+        for i in 0..12 {
+            let response = logstore.append(new_entry(1, i)).await.unwrap();
+            assert_eq!(i, response.entry_id);
+        }
+        logstore
+            .append_batch(vec![
+                new_entry(1, 12),
+                new_entry(2, 1),
+                new_entry(1, 13),
+                new_entry(2, 2),
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            (0..14).collect::<Vec<_>>(),
+            collect_ids(&logstore, 1, 0).await
+        );
+        assert_eq!(
+            (5..14).collect::<Vec<_>>(),
+            collect_ids(&logstore, 1, 5).await
+        );
+        assert_eq!(vec![1, 2], collect_ids(&logstore, 2, 0).await);
+        assert!(collect_ids(&logstore, 1, 14).await.is_empty());
+        assert!(collect_ids(&logstore, 3, 0).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_read_skips_rewritten_entries() {
+        let (_dir, logstore) = new_log_store("object-store-logstore-rewrite");
+        logstore
+            .append_batch(vec![new_entry(1, 1), new_entry(1, 2)])
+            .await
+            .unwrap();
+        // A retry writes entry 2 again with a new entry.
+        logstore
+            .append_batch(vec![new_entry(1, 2), new_entry(1, 3)])
+            .await
+            .unwrap();
+
+        assert_eq!(vec![1, 2, 3], collect_ids(&logstore, 1, 0).await);
+    }
+
+    #[tokio::test]
+    async fn test_obsolete() {
+        let (_dir, logstore) = new_log_store("object-store-logstore-obsolete");
+        for i in 0..3 {
+            logstore
+                .append_batch(vec![new_entry(1, i * 2), new_entry(1, i * 2 + 1)])
+                .await
+                .unwrap();
+        }
+        logstore.append(new_entry(2, 0)).await.unwrap();
+
+        // Only segments whose entries are all obsolete are deleted.
+        logstore.obsolete(Namespace::with_id(1), 2).await.unwrap();
+        assert_eq!(2, logstore.list_segments(1).await.unwrap().len());
+        assert_eq!(vec![2, 3, 4, 5], collect_ids(&logstore, 1, 2).await);
+
+        logstore.obsolete(Namespace::with_id(1), 5).await.unwrap();
+        assert!(logstore.list_segments(1).await.unwrap().is_empty());
+        assert!(collect_ids(&logstore, 1, 0).await.is_empty());
+        // Other namespaces are not affected.
+        assert_eq!(vec![0], collect_ids(&logstore, 2, 0).await);
+    }
+
+    #[tokio::test]
+    async fn test_replay_from_another_store() {
+        let (dir, logstore) = new_log_store("object-store-logstore-replay");
+        logstore
+            .append_batch((0..10).map(|i| new_entry(1, i)).collect())
+            .await
+            .unwrap();
+        logstore.stop().await.unwrap();
+        drop(logstore);
+
+        // A log store on another node shares the same object store.
+        let logstore = ObjectStoreLogStore::new("wal/", new_object_store(&dir));
+        assert_eq!(
+            vec![Namespace::with_id(1)],
+            logstore.list_namespaces().await.unwrap()
+        );
+        assert_eq!(
+            (0..10).collect::<Vec<_>>(),
+            collect_ids(&logstore, 1, 0).await
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fence_stale_writer() {
+        let (dir, stale) = new_log_store("object-store-logstore-fence");
+        stale
+            .append_batch((0..3).map(|i| new_entry(1, i)).collect())
+            .await
+            .unwrap();
+
+        // Another node opens the region and replays its entries.
+        let logstore = ObjectStoreLogStore::new("wal", new_object_store(&dir));
+        assert_eq!(vec![0, 1, 2], collect_ids(&logstore, 1, 0).await);
+
+        // The stale writer can't append anymore, and its segment is ignored.
+        let err = stale.append(new_entry(1, 3)).await.unwrap_err();
+        assert!(matches!(err, Error::Fenced { .. }), "{err:?}");
+        assert_eq!(1, logstore.list_segments(1).await.unwrap().len());
+
+        logstore
+            .append_batch(vec![new_entry(1, 3), new_entry(1, 4)])
+            .await
+            .unwrap();
+        let segments = logstore.list_segments(1).await.unwrap();
+        assert_eq!(2, segments.len());
+        assert_eq!(2, segments[1].epoch);
+        assert_eq!(vec![0, 1, 2, 3, 4], collect_ids(&logstore, 1, 0).await);
+
+        // Obsolete segments are deleted no matter whether they are fenced.
+        logstore.obsolete(Namespace::with_id(1), 3).await.unwrap();
+        let (segments, _) = logstore.list_objects(1).await.unwrap();
+        assert_eq!(1, segments.len());
+        assert_eq!((3, 4), (segments[0].first_id, segments[0].last_id));
+    }
+
+    #[tokio::test]
+    async fn test_unfinished_claim() {
+        let (dir, stale) = new_log_store("object-store-logstore-unfinished-claim");
+        stale
+            .append_batch((0..3).map(|i| new_entry(1, i)).collect())
+            .await
+            .unwrap();
+
+        // Another log store fails after writing the epoch object of its claim.
+        let object_store = new_object_store(&dir);
+        object_store
+            .write(
+                &format!("wal/1/{EPOCH_DIR}{}", epoch_name(2, 0)),
+                Vec::new(),
+            )
+            .await
+            .unwrap();
+
+        // The stale writer is fenced, but the unfinished claim doesn't fence its segments.
+        let err = stale.append(new_entry(1, 3)).await.unwrap_err();
+        assert!(matches!(err, Error::Fenced { .. }), "{err:?}");
+        let logstore = ObjectStoreLogStore::new("wal", object_store);
+        assert_eq!(vec![0, 1, 2, 3], collect_ids(&logstore, 1, 0).await);
+        assert_eq!(3, logstore.epoch(1).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_obsolete_epochs() {
+        let (dir, stale) = new_log_store("object-store-logstore-obsolete-epochs");
+        stale
+            .append_batch((0..3).map(|i| new_entry(1, i)).collect())
+            .await
+            .unwrap();
+        let logstore = ObjectStoreLogStore::new("wal", new_object_store(&dir));
+        assert_eq!(vec![0, 1, 2], collect_ids(&logstore, 1, 0).await);
+        logstore.append(new_entry(1, 3)).await.unwrap();
+        let logstore = ObjectStoreLogStore::new("wal", new_object_store(&dir));
+        assert_eq!(vec![0, 1, 2, 3], collect_ids(&logstore, 1, 0).await);
+        let epochs = |markers: Vec<EpochMarker>| {
+            let mut epochs: Vec<_> = markers.into_iter().map(|marker| marker.epoch).collect();
+            epochs.sort_unstable();
+            epochs
+        };
+        assert_eq!(
+            vec![1, 2, 3],
+            epochs(logstore.list_markers(1).await.unwrap())
+        );
+
+        // The epoch 2 still fences segments of the epoch 1.
+        logstore.obsolete(Namespace::with_id(1), 1).await.unwrap();
+        assert_eq!(vec![2, 3], epochs(logstore.list_markers(1).await.unwrap()));
+
+        // The latest epoch is kept even if there are no segments.
+        logstore.obsolete(Namespace::with_id(1), 3).await.unwrap();
+        assert_eq!(vec![3], epochs(logstore.list_markers(1).await.unwrap()));
+        assert!(logstore.list_segments(1).await.unwrap().is_empty());
+
+        // The stale writer is still fenced.
+        let err = stale.append(new_entry(1, 4)).await.unwrap_err();
+        assert!(matches!(err, Error::Fenced { .. }), "{err:?}");
+        assert!(collect_ids(&logstore, 1, 0).await.is_empty());
+    }
+}
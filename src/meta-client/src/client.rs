@@ -13,6 +13,7 @@
 // limitations under the License.
 
 mod ask_leader;
+mod cluster;
 mod ddl;
 mod heartbeat;
 mod load_balance;
@@ -21,7 +22,10 @@ mod router;
 mod store;
 
 use api::v1::meta::Role;
+use cluster::Client as ClusterClient;
 use common_grpc::channel_manager::{ChannelConfig, ChannelManager};
+use common_meta::datanode::{Stat, DN_STAT_PREFIX};
+use common_meta::error::DecodeJsonSnafu;
 use common_meta::rpc::ddl::{SubmitDdlTaskRequest, SubmitDdlTaskResponse};
 use common_meta::rpc::lock::{LockRequest, LockResponse, UnlockRequest};
 use common_meta::rpc::router::{RouteRequest, RouteResponse};
@@ -54,6 +58,7 @@ pub struct MetaClientBuilder {
     enable_store: bool,
     enable_lock: bool,
     enable_ddl: bool,
+    enable_cluster: bool,
     channel_manager: Option<ChannelManager>,
     ddl_channel_manager: Option<ChannelManager>,
 }
@@ -102,6 +107,13 @@ impl MetaClientBuilder {
         }
     }
 
+    pub fn enable_cluster(self) -> Self {
+        Self {
+            enable_cluster: true,
+            ..self
+        }
+    }
+
     pub fn channel_manager(self, channel_manager: ChannelManager) -> Self {
         Self {
             channel_manager: Some(channel_manager),
@@ -141,6 +153,9 @@ impl MetaClientBuilder {
         if self.enable_lock {
             client.lock = Some(LockClient::new(self.id, self.role, mgr.clone()));
         }
+        if self.enable_cluster {
+            client.cluster = Some(ClusterClient::new(self.id, self.role, mgr.clone()));
+        }
         if self.enable_ddl {
             let mgr = self.ddl_channel_manager.unwrap_or(mgr);
             client.ddl = Some(DdlClient::new(self.id, self.role, mgr));
@@ -159,6 +174,7 @@ pub struct MetaClient {
     store: Option<StoreClient>,
     lock: Option<LockClient>,
     ddl: Option<DdlClient>,
+    cluster: Option<ClusterClient>,
 }
 
 impl MetaClient {
@@ -200,6 +216,10 @@ impl MetaClient {
             client.start(urls.clone()).await?;
             info!("Lock client started");
         }
+        if let Some(client) = &mut self.cluster {
+            client.start(urls.clone()).await?;
+            info!("Cluster client started");
+        }
         if let Some(client) = &mut self.ddl {
             client.start(urls).await?;
             info!("DDL client started");
@@ -350,6 +370,30 @@ impl MetaClient {
         Ok(res)
     }
 
+    /// Returns the latest stat of each datanode, which is reported to the `metasrv` leader
+    /// by heartbeats.
+    pub async fn datanode_stats(&self) -> Result<Vec<Stat>> {
+        let req = RangeRequest::new().with_prefix(format!("{DN_STAT_PREFIX}-"));
+        let res = self.cluster_client()?.range(req.into()).await?;
+
+        let mut stats = Vec::with_capacity(res.kvs.len());
+        for kv in res.kvs {
+            let mut history: Vec<Stat> = serde_json::from_slice(&kv.value)
+                .context(DecodeJsonSnafu)
+                .context(ConvertMetaResponseSnafu)?;
+            if let Some(stat) = history.pop() {
+                stats.push(stat);
+            }
+        }
+
+        Ok(stats)
+    }
+
+    /// Returns the addresses of all `metasrv` peers and the leader among them.
+    pub async fn metasrv_peers(&self) -> Result<(Vec<String>, String)> {
+        self.cluster_client()?.peers().await
+    }
+
     #[inline]
     pub fn heartbeat_client(&self) -> Result<HeartbeatClient> {
         self.heartbeat.clone().context(error::NotStartedSnafu {
@@ -385,6 +429,13 @@ impl MetaClient {
             .context(error::NotStartedSnafu { name: "ddl_client" })
     }
 
+    #[inline]
+    pub fn cluster_client(&self) -> Result<ClusterClient> {
+        self.cluster.clone().context(error::NotStartedSnafu {
+            name: "cluster_client",
+        })
+    }
+
     #[inline]
     pub fn channel_config(&self) -> &ChannelConfig {
         self.channel_manager.config()
@@ -63,6 +63,10 @@ impl AskLeader {
         self.leadership_group.read().unwrap().leader.clone()
     }
 
+    pub fn get_peers(&self) -> Vec<String> {
+        self.leadership_group.read().unwrap().peers.clone()
+    }
+
     pub async fn ask_leader(&self) -> Result<String> {
         let mut peers = {
             let leadership_group = self.leadership_group.read().unwrap();
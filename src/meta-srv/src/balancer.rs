@@ -0,0 +1,495 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Region balancer that moves regions away from overloaded Datanodes.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use common_meta::table_name::TableName;
+use common_meta::{ClusterId, DatanodeId};
+use common_telemetry::{error, info};
+use serde::{Deserialize, Serialize};
+use store_api::storage::{RegionId, RegionNumber};
+
+use crate::cluster::MetaPeerClientRef;
+use crate::error::Result;
+use crate::keys::StatValue;
+use crate::lease;
+use crate::metasrv::ElectionRef;
+use crate::procedure::region_migration::RegionMigrationManagerRef;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BalancerOptions {
+    /// Whether to run the region balancer.
+    pub enable: bool,
+    /// Only logs the planned region migrations instead of submitting them.
+    pub dry_run: bool,
+    /// Interval between two balancing rounds.
+    #[serde(with = "humantime_serde")]
+    pub interval: Duration,
+    /// Max number of region migrations submitted in one balancing round.
+    pub max_migrations_per_round: usize,
+    /// A Datanode is left alone for this period after a region is moved from or to it, so the
+    /// stats it reports could catch up with the migration.
+    #[serde(with = "humantime_serde")]
+    pub cooldown: Duration,
+    /// A Datanode is overloaded if its region number or write load exceeds the average of the
+    /// cluster by this percent.
+    pub tolerance_percent: u64,
+}
+
+impl Default for BalancerOptions {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            dry_run: true,
+            interval: Duration::from_secs(300),
+            max_migrations_per_round: 1,
+            cooldown: Duration::from_secs(600),
+            tolerance_percent: 20,
+        }
+    }
+}
+
+/// A region move planned by the balancer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RegionMove {
+    pub(crate) table_name: TableName,
+    pub(crate) region_number: RegionNumber,
+    pub(crate) from: DatanodeId,
+    pub(crate) to: DatanodeId,
+}
+
+#[derive(Debug, Clone)]
+struct RegionLoad {
+    table_name: TableName,
+    region_number: RegionNumber,
+    wcus: i64,
+    approximate_bytes: i64,
+}
+
+/// The load of a Datanode, built from the latest stat it reported.
+#[derive(Debug, Clone)]
+struct NodeLoad {
+    id: DatanodeId,
+    regions: Vec<RegionLoad>,
+}
+
+impl NodeLoad {
+    fn from_stat_value(id: DatanodeId, stat_value: &StatValue) -> Self {
+        let regions = stat_value
+            .stats
+            .last()
+            .map(|stat| {
+                stat.region_stats
+                    .iter()
+                    .map(|region_stat| RegionLoad {
+                        table_name: TableName::new(
+                            &region_stat.catalog,
+                            &region_stat.schema,
+                            &region_stat.table,
+                        ),
+                        region_number: RegionId::from(region_stat.id).region_number(),
+                        wcus: region_stat.wcus,
+                        approximate_bytes: region_stat.approximate_bytes,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self { id, regions }
+    }
+
+    fn wcus(&self) -> i64 {
+        self.regions.iter().map(|r| r.wcus).sum()
+    }
+
+    fn contains_table(&self, table_name: &TableName) -> bool {
+        self.regions.iter().any(|r| &r.table_name == table_name)
+    }
+}
+
+/// Plans at most `max_moves` region moves that make the load of `nodes` more even. Nodes in
+/// `frozen` are neither the source nor the destination of a move.
+///
+/// Region number is balanced first. Write load is only balanced when the region number is
+/// balanced, and never by making the region number unbalanced again. Like the load based
+/// selector, a region is not moved to a Datanode that already has a region of the same table.
+fn plan_moves(
+    mut nodes: Vec<NodeLoad>,
+    mut frozen: HashSet<DatanodeId>,
+    tolerance_percent: u64,
+    max_moves: usize,
+) -> Vec<RegionMove> {
+    let mut moves = Vec::new();
+    if nodes.len() < 2 {
+        return moves;
+    }
+    let ratio = 1.0 + tolerance_percent as f64 / 100.0;
+    let total_regions: usize = nodes.iter().map(|n| n.regions.len()).sum();
+    let region_num_limit = (total_regions as f64 / nodes.len() as f64 * ratio).ceil() as usize;
+
+    while moves.len() < max_moves {
+        let candidates = nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, n)| !frozen.contains(&n.id))
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        if candidates.len() < 2 {
+            break;
+        }
+
+        let planned = plan_region_num_move(&nodes, &candidates, ratio)
+            .or_else(|| plan_write_load_move(&nodes, &candidates, ratio, region_num_limit));
+        let Some((from, to, region_idx)) = planned else {
+            break;
+        };
+
+        let region = nodes[from].regions.remove(region_idx);
+        moves.push(RegionMove {
+            table_name: region.table_name.clone(),
+            region_number: region.region_number,
+            from: nodes[from].id,
+            to: nodes[to].id,
+        });
+        nodes[to].regions.push(region);
+
+        // The stats of the two nodes are no longer accurate.
+        let _ = frozen.insert(nodes[from].id);
+        let _ = frozen.insert(nodes[to].id);
+    }
+    moves
+}
+
+/// Returns `(from, to, region index)` that moves the smallest region from the node with most
+/// regions to the node with least regions.
+fn plan_region_num_move(
+    nodes: &[NodeLoad],
+    candidates: &[usize],
+    ratio: f64,
+) -> Option<(usize, usize, usize)> {
+    let avg = nodes.iter().map(|n| n.regions.len()).sum::<usize>() as f64 / nodes.len() as f64;
+    let from = *candidates.iter().max_by_key(|i| nodes[**i].regions.len())?;
+    let to = *candidates.iter().min_by_key(|i| nodes[**i].regions.len())?;
+
+    let (from_num, to_num) = (nodes[from].regions.len(), nodes[to].regions.len());
+    if from_num as f64 <= avg * ratio || from_num < to_num + 2 {
+        return None;
+    }
+
+    let region_idx = nodes[from]
+        .regions
+        .iter()
+        .enumerate()
+        .filter(|(_, r)| !nodes[to].contains_table(&r.table_name))
+        .min_by_key(|(_, r)| r.approximate_bytes)
+        .map(|(i, _)| i)?;
+    Some((from, to, region_idx))
+}
+
+/// Returns `(from, to, region index)` that moves a region from the node with highest write
+/// load to the node with lowest write load, if it lowers the higher load of the two nodes.
+fn plan_write_load_move(
+    nodes: &[NodeLoad],
+    candidates: &[usize],
+    ratio: f64,
+    region_num_limit: usize,
+) -> Option<(usize, usize, usize)> {
+    let avg = nodes.iter().map(|n| n.wcus()).sum::<i64>() as f64 / nodes.len() as f64;
+    let from = *candidates.iter().max_by_key(|i| nodes[**i].wcus())?;
+    let to = *candidates
+        .iter()
+        .min_by_key(|i| (nodes[**i].wcus(), nodes[**i].regions.len()))?;
+
+    let (from_wcus, to_wcus) = (nodes[from].wcus(), nodes[to].wcus());
+    if avg <= 0.0 || from_wcus as f64 <= avg * ratio || nodes[to].regions.len() >= region_num_limit
+    {
+        return None;
+    }
+
+    let region_idx = nodes[from]
+        .regions
+        .iter()
+        .enumerate()
+        .filter(|(_, r)| {
+            r.wcus > 0 && to_wcus + r.wcus < from_wcus && !nodes[to].contains_table(&r.table_name)
+        })
+        .max_by_key(|(_, r)| r.wcus)
+        .map(|(i, _)| i)?;
+    Some((from, to, region_idx))
+}
+
+/// Periodically reads the node stats persisted by the heartbeat handlers, and migrates regions
+/// from the overloaded Datanodes to the others. It only works on the leader.
+pub struct RegionBalancer {
+    options: BalancerOptions,
+    datanode_lease_secs: i64,
+    meta_peer_client: MetaPeerClientRef,
+    election: Option<ElectionRef>,
+    region_migration_manager: RegionMigrationManagerRef,
+    /// When the Datanodes were last involved in a region migration.
+    last_moved: Mutex<HashMap<(ClusterId, DatanodeId), Instant>>,
+}
+
+pub type RegionBalancerRef = Arc<RegionBalancer>;
+
+impl RegionBalancer {
+    pub(crate) fn new(
+        options: BalancerOptions,
+        datanode_lease_secs: i64,
+        meta_peer_client: MetaPeerClientRef,
+        election: Option<ElectionRef>,
+        region_migration_manager: RegionMigrationManagerRef,
+    ) -> Self {
+        Self {
+            options,
+            datanode_lease_secs,
+            meta_peer_client,
+            election,
+            region_migration_manager,
+            last_moved: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Runs balancing rounds in background until `started` is set to false.
+    pub(crate) fn start(self: &Arc<Self>, started: Arc<AtomicBool>) {
+        let balancer = self.clone();
+        let _handle = common_runtime::spawn_bg(async move {
+            let mut interval = tokio::time::interval(balancer.options.interval);
+            // The first tick completes immediately, skips it so the Datanodes have time to
+            // report their stats.
+            let _ = interval.tick().await;
+            while started.load(Ordering::Relaxed) {
+                let _ = interval.tick().await;
+                if !balancer.is_leader() {
+                    continue;
+                }
+                if let Err(e) = balancer.balance().await {
+                    error!(e; "Failed to balance regions");
+                }
+            }
+            info!("Region balancer stopped");
+        });
+    }
+
+    fn is_leader(&self) -> bool {
+        self.election
+            .as_ref()
+            .map_or(true, |election| election.is_leader())
+    }
+
+    /// Runs one balancing round, returns the planned region moves.
+    pub(crate) async fn balance(&self) -> Result<Vec<RegionMove>> {
+        let mut clusters: HashMap<ClusterId, Vec<NodeLoad>> = HashMap::new();
+        for (stat_key, stat_value) in self.meta_peer_client.get_all_dn_stat_kvs().await? {
+            clusters
+                .entry(stat_key.cluster_id)
+                .or_default()
+                .push(NodeLoad::from_stat_value(stat_key.node_id, &stat_value));
+        }
+
+        let mut all_moves = Vec::new();
+        for (cluster_id, mut nodes) in clusters {
+            let alive = lease::alive_datanodes(
+                cluster_id,
+                &self.meta_peer_client,
+                self.datanode_lease_secs,
+            )
+            .await?;
+            nodes.retain(|n| alive.keys().any(|k| k.node_id == n.id));
+
+            let frozen = self.frozen_nodes(cluster_id);
+            let moves = plan_moves(
+                nodes,
+                frozen,
+                self.options.tolerance_percent,
+                self.options.max_migrations_per_round,
+            );
+            for region_move in &moves {
+                self.submit(cluster_id, region_move).await;
+            }
+            all_moves.extend(moves);
+        }
+        Ok(all_moves)
+    }
+
+    fn frozen_nodes(&self, cluster_id: ClusterId) -> HashSet<DatanodeId> {
+        let mut last_moved = self.last_moved.lock().unwrap();
+        last_moved.retain(|_, moved_at| moved_at.elapsed() < self.options.cooldown);
+        last_moved
+            .keys()
+            .filter(|(c, _)| *c == cluster_id)
+            .map(|(_, id)| *id)
+            .collect()
+    }
+
+    async fn submit(&self, cluster_id: ClusterId, region_move: &RegionMove) {
+        let RegionMove {
+            table_name,
+            region_number,
+            from,
+            to,
+        } = region_move;
+        if self.options.dry_run {
+            info!(
+                "Region balancer (dry run) plans to migrate region {region_number} of table \
+                {table_name} from Datanode {from} to Datanode {to}"
+            );
+            return;
+        }
+
+        match self
+            .region_migration_manager
+            .submit_region_migration(cluster_id, table_name, *region_number, *to)
+            .await
+        {
+            Ok(procedure_id) => {
+                info!(
+                    "Region balancer submitted procedure {procedure_id} to migrate region \
+                    {region_number} of table {table_name} from Datanode {from} to Datanode {to}"
+                );
+                let now = Instant::now();
+                let mut last_moved = self.last_moved.lock().unwrap();
+                let _ = last_moved.insert((cluster_id, *from), now);
+                let _ = last_moved.insert((cluster_id, *to), now);
+            }
+            Err(e) => {
+                error!(e; "Region balancer failed to migrate region {region_number} of table {table_name} to Datanode {to}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_meta::datanode::{RegionStat, Stat};
+
+    use super::*;
+
+    fn region(table: &str, region_number: RegionNumber, wcus: i64) -> RegionLoad {
+        RegionLoad {
+            table_name: TableName::new("greptime", "public", table),
+            region_number,
+            wcus,
+            approximate_bytes: region_number as i64,
+        }
+    }
+
+    fn node(id: DatanodeId, regions: Vec<RegionLoad>) -> NodeLoad {
+        NodeLoad { id, regions }
+    }
+
+    fn region_move(
+        table: &str,
+        region_number: RegionNumber,
+        from: DatanodeId,
+        to: DatanodeId,
+    ) -> RegionMove {
+        RegionMove {
+            table_name: TableName::new("greptime", "public", table),
+            region_number,
+            from,
+            to,
+        }
+    }
+
+    #[test]
+    fn test_node_load_from_stat_value() {
+        let stat_value = StatValue {
+            stats: vec![Stat {
+                region_stats: vec![RegionStat {
+                    id: RegionId::new(1024, 3).into(),
+                    catalog: "greptime".to_string(),
+                    schema: "public".to_string(),
+                    table: "foo".to_string(),
+                    wcus: 10,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        };
+        let load = NodeLoad::from_stat_value(1, &stat_value);
+        assert_eq!(1, load.id);
+        assert_eq!(1, load.regions.len());
+        assert_eq!(3, load.regions[0].region_number);
+        assert_eq!(10, load.wcus());
+        assert!(load.contains_table(&TableName::new("greptime", "public", "foo")));
+
+        let load = NodeLoad::from_stat_value(2, &StatValue { stats: vec![] });
+        assert!(load.regions.is_empty());
+    }
+
+    #[test]
+    fn test_plan_region_num_moves() {
+        let nodes = vec![
+            node(
+                1,
+                vec![
+                    region("a", 1, 0),
+                    region("b", 2, 0),
+                    region("c", 3, 0),
+                    region("d", 4, 0),
+                ],
+            ),
+            node(2, vec![region("a", 5, 0)]),
+            node(3, vec![region("b", 6, 0)]),
+        ];
+
+        // Table "a" is already on Datanode 2, so the smallest movable region is "b".
+        let moves = plan_moves(nodes.clone(), HashSet::new(), 20, 1);
+        assert_eq!(vec![region_move("b", 2, 1, 2)], moves);
+
+        // Datanode 2 and 1 are frozen after the first move.
+        let moves = plan_moves(nodes.clone(), HashSet::new(), 20, 3);
+        assert_eq!(vec![region_move("b", 2, 1, 2)], moves);
+
+        let moves = plan_moves(nodes.clone(), HashSet::from([2]), 20, 1);
+        assert_eq!(vec![region_move("a", 1, 1, 3)], moves);
+
+        // The loads are within the tolerance.
+        let moves = plan_moves(nodes.clone(), HashSet::new(), 200, 1);
+        assert!(moves.is_empty());
+
+        let moves = plan_moves(nodes, HashSet::from([1]), 20, 1);
+        assert!(moves.is_empty());
+    }
+
+    #[test]
+    fn test_plan_write_load_moves() {
+        let nodes = vec![
+            node(1, vec![region("a", 1, 100), region("b", 2, 50)]),
+            node(2, vec![region("c", 3, 10), region("a", 4, 10)]),
+            node(3, vec![region("d", 5, 5), region("e", 6, 5)]),
+        ];
+
+        // Moves the hottest region that lowers the load of Datanode 1.
+        let moves = plan_moves(nodes.clone(), HashSet::new(), 20, 1);
+        assert_eq!(vec![region_move("a", 1, 1, 3)], moves);
+
+        // Moving region 1 to Datanode 2 is not allowed as table "a" is already there, region
+        // 2 is moved instead.
+        let moves = plan_moves(nodes, HashSet::from([3]), 20, 1);
+        assert_eq!(vec![region_move("b", 2, 1, 2)], moves);
+
+        // A single hot region could not be balanced.
+        let nodes = vec![
+            node(1, vec![region("a", 1, 100)]),
+            node(2, vec![region("b", 2, 10)]),
+        ];
+        assert!(plan_moves(nodes, HashSet::new(), 20, 1).is_empty());
+    }
+}
@@ -24,6 +24,7 @@ use api::v1::meta::{
 };
 pub use check_leader_handler::CheckLeaderHandler;
 pub use collect_stats_handler::CollectStatsHandler;
+use common_meta::datanode::Stat;
 use common_meta::instruction::{Instruction, InstructionReply};
 use common_telemetry::{debug, info, timer, warn};
 use dashmap::DashMap;
@@ -37,7 +38,6 @@ use snafu::{OptionExt, ResultExt};
 use tokio::sync::mpsc::Sender;
 use tokio::sync::{oneshot, Notify, RwLock};
 
-use self::node_stat::Stat;
 use crate::error::{self, DeserializeFromJsonSnafu, Result, UnexpectedInstructionReplySnafu};
 use crate::metasrv::Context;
 use crate::metrics::{METRIC_META_HANDLER_EXECUTE, METRIC_META_HEARTBEAT_CONNECTION_NUM};
@@ -51,7 +51,6 @@ mod collect_stats_handler;
 pub(crate) mod failure_handler;
 mod keep_lease_handler;
 pub mod mailbox_handler;
-pub mod node_stat;
 mod on_leader_start_handler;
 mod persist_stats_handler;
 pub(crate) mod region_lease_handler;
@@ -109,8 +109,9 @@ impl HeartbeatHandler for RegionFailureHandler {
 
 #[cfg(test)]
 mod tests {
+    use common_meta::datanode::{RegionStat, Stat};
+
     use super::*;
-    use crate::handler::node_stat::{RegionStat, Stat};
     use crate::metasrv::builder::MetaSrvBuilder;
     use crate::test_util::create_region_failover_manager;
 
@@ -139,6 +140,7 @@ mod tests {
                 wcus: 0,
                 approximate_bytes: 0,
                 approximate_rows: 0,
+                memtable_size: 0,
             }
         }
         acc.stat = Some(Stat {
@@ -15,12 +15,12 @@
 use std::cmp::Ordering;
 
 use api::v1::meta::{HeartbeatRequest, Role};
+use common_meta::datanode::Stat;
 use common_meta::rpc::store::PutRequest;
 use common_telemetry::warn;
 use dashmap::DashMap;
 
 use crate::error::Result;
-use crate::handler::node_stat::Stat;
 use crate::handler::{HeartbeatAccumulator, HeartbeatHandler};
 use crate::keys::{StatKey, StatValue};
 use crate::metasrv::Context;
@@ -84,7 +84,7 @@ impl HeartbeatHandler for PersistStatsHandler {
     ) -> Result<()> {
         let Some(current_stat) = acc.stat.take() else { return Ok(()) };
 
-        let key = current_stat.stat_key();
+        let key = StatKey::from(&current_stat);
         let mut entry = self
             .stats_cache
             .entry(key)
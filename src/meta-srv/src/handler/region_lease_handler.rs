@@ -183,10 +183,10 @@ impl HeartbeatHandler for RegionLeaseHandler {
 #[cfg(test)]
 mod test {
     use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
+    use common_meta::datanode::{RegionStat, Stat};
     use common_meta::key::TableMetadataManager;
 
     use super::*;
-    use crate::handler::node_stat::{RegionStat, Stat};
     use crate::metasrv::builder::MetaSrvBuilder;
     use crate::service::store::kv::KvBackendAdapter;
     use crate::{table_routes, test_util};
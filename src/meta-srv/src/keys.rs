@@ -14,6 +14,7 @@
 
 use std::str::FromStr;
 
+use common_meta::datanode::{Stat, DN_STAT_PREFIX};
 use common_meta::key::TABLE_ROUTE_PREFIX;
 use lazy_static::lazy_static;
 use regex::Regex;
@@ -22,13 +23,10 @@ use snafu::{ensure, OptionExt, ResultExt};
 
 use crate::error;
 use crate::error::Result;
-use crate::handler::node_stat::Stat;
 
 pub(crate) const DN_LEASE_PREFIX: &str = "__meta_dnlease";
 pub(crate) const SEQ_PREFIX: &str = "__meta_seq";
 
-pub const DN_STAT_PREFIX: &str = "__meta_dnstat";
-
 lazy_static! {
     static ref DATANODE_LEASE_KEY_PATTERN: Regex =
         Regex::new(&format!("^{DN_LEASE_PREFIX}-([0-9]+)-([0-9]+)$")).unwrap();
@@ -151,6 +149,15 @@ impl From<&LeaseKey> for StatKey {
     }
 }
 
+impl From<&Stat> for StatKey {
+    fn from(stat: &Stat) -> Self {
+        StatKey {
+            cluster_id: stat.cluster_id,
+            node_id: stat.id,
+        }
+    }
+}
+
 impl From<StatKey> for Vec<u8> {
     fn from(value: StatKey) -> Self {
         format!("{}-{}-{}", DN_STAT_PREFIX, value.cluster_id, value.node_id).into_bytes()
@@ -364,4 +371,19 @@ mod tests {
         assert_eq!(1, stat_key.cluster_id);
         assert_eq!(101, stat_key.node_id);
     }
+
+    #[test]
+    fn test_stat_to_stat_key() {
+        let stat = Stat {
+            cluster_id: 3,
+            id: 101,
+            region_num: Some(10),
+            ..Default::default()
+        };
+
+        let stat_key = StatKey::from(&stat);
+
+        assert_eq!(3, stat_key.cluster_id);
+        assert_eq!(101, stat_key.node_id);
+    }
 }
@@ -16,6 +16,7 @@
 #![feature(btree_drain_filter)]
 #![feature(result_flattening)]
 
+pub mod balancer;
 pub mod bootstrap;
 pub mod cluster;
 pub mod ddl;
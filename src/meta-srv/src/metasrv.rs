@@ -30,6 +30,7 @@ use servers::http::HttpOptions;
 use snafu::ResultExt;
 use tokio::sync::broadcast::error::RecvError;
 
+use crate::balancer::{BalancerOptions, RegionBalancerRef};
 use crate::cluster::MetaPeerClientRef;
 use crate::ddl::DdlManagerRef;
 use crate::election::{Election, LeaderChangeMessage};
@@ -37,6 +38,7 @@ use crate::error::{RecoverProcedureSnafu, Result};
 use crate::handler::HeartbeatHandlerGroup;
 use crate::lock::DistLockRef;
 use crate::metadata_service::MetadataServiceRef;
+use crate::procedure::region_migration::RegionMigrationManagerRef;
 use crate::selector::{Selector, SelectorType};
 use crate::sequence::SequenceRef;
 use crate::service::mailbox::MailboxRef;
@@ -57,6 +59,7 @@ pub struct MetaSrvOptions {
     pub logging: LoggingOptions,
     pub procedure: ProcedureConfig,
     pub datanode: DatanodeOptions,
+    pub balancer: BalancerOptions,
 }
 
 impl Default for MetaSrvOptions {
@@ -73,6 +76,7 @@ impl Default for MetaSrvOptions {
             logging: LoggingOptions::default(),
             procedure: ProcedureConfig::default(),
             datanode: DatanodeOptions::default(),
+            balancer: BalancerOptions::default(),
         }
     }
 }
@@ -175,6 +179,8 @@ pub struct MetaSrv {
     mailbox: MailboxRef,
     ddl_manager: DdlManagerRef,
     table_metadata_manager: TableMetadataManagerRef,
+    region_migration_manager: RegionMigrationManagerRef,
+    region_balancer: Option<RegionBalancerRef>,
 }
 
 impl MetaSrv {
@@ -246,6 +252,10 @@ impl MetaSrv {
                 .context(RecoverProcedureSnafu)?;
         }
 
+        if let Some(region_balancer) = &self.region_balancer {
+            region_balancer.start(self.started.clone());
+        }
+
         info!("MetaSrv started");
         Ok(())
     }
@@ -317,6 +327,10 @@ impl MetaSrv {
         &self.table_metadata_manager
     }
 
+    pub fn region_migration_manager(&self) -> &RegionMigrationManagerRef {
+        &self.region_migration_manager
+    }
+
     #[inline]
     pub fn new_ctx(&self) -> Context {
         let server_addr = self.options().server_addr.clone();
@@ -21,6 +21,7 @@ use common_grpc::channel_manager::ChannelConfig;
 use common_meta::key::TableMetadataManager;
 use common_procedure::local::{LocalManager, ManagerConfig};
 
+use crate::balancer::RegionBalancer;
 use crate::cluster::{MetaPeerClientBuilder, MetaPeerClientRef};
 use crate::ddl::DdlManager;
 use crate::error::Result;
@@ -38,6 +39,7 @@ use crate::metasrv::{
     ElectionRef, MetaSrv, MetaSrvOptions, SelectorContext, SelectorRef, TABLE_ID_SEQ,
 };
 use crate::procedure::region_failover::RegionFailoverManager;
+use crate::procedure::region_migration::RegionMigrationManager;
 use crate::procedure::state_store::MetaStateStore;
 use crate::selector::lease_based::LeaseBasedSelector;
 use crate::sequence::Sequence;
@@ -204,6 +206,33 @@ impl MetaSrvBuilder {
 
         let _ = ddl_manager.try_start();
 
+        let region_migration_manager = Arc::new(RegionMigrationManager::new(
+            mailbox.clone(),
+            procedure_manager.clone(),
+            SelectorContext {
+                server_addr: options.server_addr.clone(),
+                datanode_lease_secs: options.datanode_lease_secs,
+                kv_store: kv_store.clone(),
+                meta_peer_client: meta_peer_client.clone(),
+                catalog: None,
+                schema: None,
+                table: None,
+            },
+            lock.clone(),
+            table_metadata_manager.clone(),
+        ));
+        region_migration_manager.try_start()?;
+
+        let region_balancer = options.balancer.enable.then(|| {
+            Arc::new(RegionBalancer::new(
+                options.balancer.clone(),
+                options.datanode_lease_secs,
+                meta_peer_client.clone(),
+                election.clone(),
+                region_migration_manager.clone(),
+            ))
+        });
+
         let handler_group = match handler_group {
             Some(handler_group) => handler_group,
             None => {
@@ -276,6 +305,8 @@ impl MetaSrvBuilder {
             mailbox,
             ddl_manager,
             table_metadata_manager,
+            region_migration_manager,
+            region_balancer,
         })
     }
 }
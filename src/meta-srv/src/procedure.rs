@@ -16,5 +16,6 @@ pub mod alter_table;
 pub mod create_table;
 pub mod drop_table;
 pub mod region_failover;
+pub mod region_migration;
 pub(crate) mod state_store;
 mod utils;
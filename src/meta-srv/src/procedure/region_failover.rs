@@ -371,7 +371,7 @@ impl Procedure for RegionFailoverProcedure {
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use std::collections::HashMap;
 
     use api::v1::meta::mailbox_message::Payload;
@@ -12,48 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use api::v1::meta::MailboxMessage;
 use async_trait::async_trait;
 use common_meta::ident::TableIdent;
-use common_meta::instruction::Instruction;
 use common_meta::RegionIdent;
-use common_telemetry::info;
 use serde::{Deserialize, Serialize};
-use snafu::ResultExt;
 
 use super::failover_end::RegionFailoverEnd;
 use super::{RegionFailoverContext, State};
-use crate::error::{self, Result};
-use crate::service::mailbox::BroadcastChannel;
+use crate::error::Result;
+use crate::procedure::utils::broadcast_invalidate_table_cache;
 
 #[derive(Serialize, Deserialize, Debug, Default)]
 pub(super) struct InvalidateCache;
 
-impl InvalidateCache {
-    async fn broadcast_invalidate_table_cache_messages(
-        &self,
-        ctx: &RegionFailoverContext,
-        table_ident: &TableIdent,
-    ) -> Result<()> {
-        let instruction = Instruction::InvalidateTableCache(table_ident.clone());
-
-        let msg = &MailboxMessage::json_message(
-            "Invalidate Table Cache",
-            &format!("Metasrv@{}", ctx.selector_ctx.server_addr),
-            "Frontend broadcast",
-            common_time::util::current_time_millis(),
-            &instruction,
-        )
-        .with_context(|_| error::SerializeToJsonSnafu {
-            input: instruction.to_string(),
-        })?;
-
-        ctx.mailbox
-            .broadcast(&BroadcastChannel::Frontend, msg)
-            .await
-    }
-}
-
 #[async_trait]
 #[typetag::serde]
 impl State for InvalidateCache {
@@ -63,11 +34,7 @@ impl State for InvalidateCache {
         failed_region: &RegionIdent,
     ) -> Result<Box<dyn State>> {
         let table_ident = TableIdent::from(failed_region.clone());
-        info!(
-            "Broadcast invalidate table({}) cache message to frontend",
-            table_ident
-        );
-        self.broadcast_invalidate_table_cache_messages(ctx, &table_ident)
+        broadcast_invalidate_table_cache(&ctx.mailbox, &ctx.selector_ctx.server_addr, &table_ident)
             .await?;
 
         Ok(Box::new(RegionFailoverEnd))
@@ -78,6 +45,7 @@ impl State for InvalidateCache {
 mod tests {
     use api::v1::meta::mailbox_message::Payload;
     use api::v1::meta::RequestHeader;
+    use common_meta::instruction::Instruction;
 
     use super::super::tests::TestingEnvBuilder;
     use super::*;
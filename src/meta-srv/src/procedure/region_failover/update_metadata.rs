@@ -12,26 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use api::v1::meta::{TableName as PbTableName, TableRouteValue};
 use async_trait::async_trait;
-use common_meta::key::TableRouteKey;
 use common_meta::peer::Peer;
-use common_meta::rpc::router::TableRoute;
-use common_meta::table_name::TableName;
 use common_meta::RegionIdent;
-use common_telemetry::info;
 use serde::{Deserialize, Serialize};
-use snafu::{OptionExt, ResultExt};
 
 use super::invalidate_cache::InvalidateCache;
 use super::{RegionFailoverContext, State};
-use crate::error::{
-    CorruptedTableRouteSnafu, Result, RetryLaterSnafu, TableMetadataManagerSnafu,
-    TableNotFoundSnafu, TableRouteConversionSnafu,
-};
-use crate::lock::keys::table_metadata_lock_key;
-use crate::lock::Opts;
-use crate::table_routes;
+use crate::error::{Result, RetryLaterSnafu};
+use crate::procedure::utils::move_region_leader;
 
 #[derive(Serialize, Deserialize, Debug)]
 pub(super) struct UpdateRegionMetadata {
@@ -42,152 +31,6 @@ impl UpdateRegionMetadata {
     pub(super) fn new(candidate: Peer) -> Self {
         Self { candidate }
     }
-
-    /// Updates the metadata of the table.
-    async fn update_metadata(
-        &self,
-        ctx: &RegionFailoverContext,
-        failed_region: &RegionIdent,
-    ) -> Result<()> {
-        let key = table_metadata_lock_key(failed_region);
-        let key = ctx.dist_lock.lock(key, Opts::default()).await?;
-
-        self.update_table_region_value(ctx, failed_region).await?;
-
-        self.update_table_route(ctx, failed_region).await?;
-
-        ctx.dist_lock.unlock(key).await?;
-        Ok(())
-    }
-
-    async fn update_table_region_value(
-        &self,
-        ctx: &RegionFailoverContext,
-        failed_region: &RegionIdent,
-    ) -> Result<()> {
-        let table_ident = &failed_region.table_ident;
-        let table_id = table_ident.table_id;
-        let table_name = TableName::new(
-            &table_ident.catalog,
-            &table_ident.schema,
-            &table_ident.table,
-        );
-        let value = ctx
-            .table_metadata_manager
-            .table_region_manager()
-            .get_old(&table_name)
-            .await
-            .context(TableMetadataManagerSnafu)?
-            .with_context(|| TableNotFoundSnafu {
-                name: table_ident.to_string(),
-            })?;
-        let mut region_distribution = value.region_distribution.clone();
-
-        if let Some(mut region_numbers) = region_distribution.remove(&failed_region.datanode_id) {
-            region_numbers.retain(|x| *x != failed_region.region_number);
-
-            if !region_numbers.is_empty() {
-                region_distribution.insert(failed_region.datanode_id, region_numbers);
-            }
-        }
-
-        let region_numbers = region_distribution
-            .entry(self.candidate.id)
-            .or_insert_with(Vec::new);
-        region_numbers.push(failed_region.region_number);
-
-        ctx.table_metadata_manager
-            .table_region_manager()
-            .put_old(&table_name, region_distribution.clone())
-            .await
-            .context(TableMetadataManagerSnafu)?;
-
-        info!(
-            "Region distribution of table (id = {table_id}) is updated to {:?}. \
-            Failed region {} was on Datanode {}.",
-            region_distribution, failed_region.region_number, failed_region.datanode_id,
-        );
-        Ok(())
-    }
-
-    async fn update_table_route(
-        &self,
-        ctx: &RegionFailoverContext,
-        failed_region: &RegionIdent,
-    ) -> Result<()> {
-        let table_name = PbTableName {
-            catalog_name: failed_region.table_ident.catalog.clone(),
-            schema_name: failed_region.table_ident.schema.clone(),
-            table_name: failed_region.table_ident.table.clone(),
-        };
-        let key =
-            TableRouteKey::with_table_name(failed_region.table_ident.table_id as _, &table_name);
-        let value = table_routes::get_table_route_value(&ctx.selector_ctx.kv_store, &key).await?;
-
-        let table_route = value
-            .table_route
-            .with_context(|| CorruptedTableRouteSnafu {
-                key: key.to_string(),
-                reason: "'table_route' is empty",
-            })?;
-        let mut table_route = TableRoute::try_from_raw(&value.peers, table_route)
-            .context(TableRouteConversionSnafu)?;
-
-        for region_route in table_route.region_routes.iter_mut() {
-            if region_route.region.id == failed_region.region_number as u64 {
-                region_route.leader_peer = Some(self.candidate.clone());
-                break;
-            }
-        }
-
-        pretty_log_table_route_change(&key, &table_route, failed_region);
-
-        let (peers, table_route) = table_route
-            .try_into_raw()
-            .context(TableRouteConversionSnafu)?;
-
-        let value = TableRouteValue {
-            peers,
-            table_route: Some(table_route),
-        };
-        table_routes::put_table_route_value(&ctx.selector_ctx.kv_store, &key, value).await?;
-        Ok(())
-    }
-}
-
-fn pretty_log_table_route_change(
-    key: &TableRouteKey,
-    table_route: &TableRoute,
-    failed_region: &RegionIdent,
-) {
-    let region_routes = table_route
-        .region_routes
-        .iter()
-        .map(|x| {
-            format!(
-                "{{region: {}, leader: {}, followers: [{}]}}",
-                x.region.id,
-                x.leader_peer
-                    .as_ref()
-                    .map(|p| p.id.to_string())
-                    .unwrap_or_else(|| "?".to_string()),
-                x.follower_peers
-                    .iter()
-                    .map(|p| p.id.to_string())
-                    .collect::<Vec<_>>()
-                    .join(","),
-            )
-        })
-        .collect::<Vec<_>>();
-
-    info!(
-        "Updating region routes in table route value (key = '{}') to [{}]. \
-        Failed region {} was on Datanode {}.",
-        key.to_string(),
-        region_routes.join(", "),
-        failed_region.region_number,
-        failed_region.datanode_id,
-    );
 }
 
 #[async_trait]
@@ -198,17 +41,23 @@ impl State for UpdateRegionMetadata {
         ctx: &RegionFailoverContext,
         failed_region: &RegionIdent,
     ) -> Result<Box<dyn State>> {
-        self.update_metadata(ctx, failed_region)
-            .await
-            .map_err(|e| {
-                RetryLaterSnafu {
-                    reason: format!(
-                        "Failed to update metadata for failed region: {}, error: {}",
-                        failed_region, e
-                    ),
-                }
-                .build()
-            })?;
+        move_region_leader(
+            &ctx.dist_lock,
+            &ctx.table_metadata_manager,
+            &ctx.selector_ctx.kv_store,
+            failed_region,
+            &self.candidate,
+        )
+        .await
+        .map_err(|e| {
+            RetryLaterSnafu {
+                reason: format!(
+                    "Failed to update metadata for failed region: {}, error: {}",
+                    failed_region, e
+                ),
+            }
+            .build()
+        })?;
         Ok(Box::new(InvalidateCache))
     }
 }
@@ -218,11 +67,14 @@ mod tests {
     use api::v1::meta::TableRouteValue;
     use common_meta::key::table_region::TableRegionValue;
     use common_meta::key::TableRouteKey;
+    use common_meta::table_name::TableName;
     use common_meta::DatanodeId;
     use store_api::storage::RegionNumber;
 
     use super::super::tests::{TestingEnv, TestingEnvBuilder};
     use super::{State, *};
+    use crate::procedure::utils::{update_table_region_value, update_table_route};
+    use crate::table_routes;
     use crate::table_routes::tests::new_region_route;
 
     #[tokio::test]
@@ -250,11 +102,13 @@ mod tests {
         ) -> TableRegionValue {
             let failed_region = env.failed_region(failed_region).await;
 
-            let state = UpdateRegionMetadata::new(Peer::new(candidate, ""));
-            state
-                .update_table_region_value(&env.context, &failed_region)
-                .await
-                .unwrap();
+            update_table_region_value(
+                &env.context.table_metadata_manager,
+                &failed_region,
+                &Peer::new(candidate, ""),
+            )
+            .await
+            .unwrap();
 
             let table_ident = failed_region.table_ident;
             env.context
@@ -324,11 +178,13 @@ mod tests {
         async fn test(env: TestingEnv, failed_region: u32, candidate: u64) -> TableRouteValue {
             let failed_region = env.failed_region(failed_region).await;
 
-            let state = UpdateRegionMetadata::new(Peer::new(candidate, ""));
-            state
-                .update_table_route(&env.context, &failed_region)
-                .await
-                .unwrap();
+            update_table_route(
+                &env.context.selector_ctx.kv_store,
+                &failed_region,
+                &Peer::new(candidate, ""),
+            )
+            .await
+            .unwrap();
 
             let key = TableRouteKey {
                 table_id: failed_region.table_ident.table_id,
@@ -471,18 +327,26 @@ mod tests {
 
             let _ = futures::future::join_all(vec![
                 tokio::spawn(async move {
-                    let state = UpdateRegionMetadata::new(Peer::new(2, ""));
-                    state
-                        .update_metadata(&ctx_1, &failed_region_1)
-                        .await
-                        .unwrap();
+                    move_region_leader(
+                        &ctx_1.dist_lock,
+                        &ctx_1.table_metadata_manager,
+                        &ctx_1.selector_ctx.kv_store,
+                        &failed_region_1,
+                        &Peer::new(2, ""),
+                    )
+                    .await
+                    .unwrap();
                 }),
                 tokio::spawn(async move {
-                    let state = UpdateRegionMetadata::new(Peer::new(3, ""));
-                    state
-                        .update_metadata(&ctx_2, &failed_region_2)
-                        .await
-                        .unwrap();
+                    move_region_leader(
+                        &ctx_2.dist_lock,
+                        &ctx_2.table_metadata_manager,
+                        &ctx_2.selector_ctx.kv_store,
+                        &failed_region_2,
+                        &Peer::new(3, ""),
+                    )
+                    .await
+                    .unwrap();
                 }),
             ])
             .await;
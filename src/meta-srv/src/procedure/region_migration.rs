@@ -0,0 +1,706 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod close_source_region;
+mod invalidate_cache;
+mod migration_end;
+mod open_candidate_region;
+mod reopen_source_region;
+mod update_region_route;
+
+use std::fmt::{Debug, Display, Formatter};
+use std::sync::Arc;
+use std::time::Duration;
+
+use api::v1::meta::MailboxMessage;
+use async_trait::async_trait;
+use common_meta::ident::TableIdent;
+use common_meta::instruction::Instruction;
+use common_meta::key::{TableMetadataManagerRef, TableRouteKey};
+use common_meta::peer::Peer;
+use common_meta::rpc::router::TableRoute;
+use common_meta::table_name::TableName;
+use common_meta::{ClusterId, DatanodeId, RegionIdent};
+use common_procedure::error::{FromJsonSnafu, Result as ProcedureResult, ToJsonSnafu};
+use common_procedure::{
+    watcher, Context as ProcedureContext, LockKey, Procedure, ProcedureId, ProcedureManagerRef,
+    ProcedureWithId, Status,
+};
+use common_telemetry::{error, info};
+use serde::{Deserialize, Serialize};
+use snafu::{ensure, OptionExt, ResultExt};
+use store_api::storage::RegionNumber;
+
+use self::close_source_region::CloseSourceRegion;
+use crate::error::{
+    self, CorruptedTableRouteSnafu, RegisterProcedureLoaderSnafu, Result,
+    TableMetadataManagerSnafu, TableNotFoundSnafu, TableRouteConversionSnafu,
+};
+use crate::lock::DistLockRef;
+use crate::metasrv::SelectorContext;
+use crate::procedure::utils::handle_retry_error;
+use crate::service::mailbox::{Channel, MailboxReceiver, MailboxRef};
+use crate::{lease, table_routes};
+
+/// Closing a region flushes its memtables, so it may take much longer than a plain
+/// "Close Region" in region failover.
+const CLOSE_REGION_MESSAGE_TIMEOUT: Duration = Duration::from_secs(30);
+const OPEN_REGION_MESSAGE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Manages the region migration procedures that are initiated by operators.
+pub struct RegionMigrationManager {
+    procedure_manager: ProcedureManagerRef,
+    context: RegionMigrationContext,
+}
+
+pub type RegionMigrationManagerRef = Arc<RegionMigrationManager>;
+
+impl RegionMigrationManager {
+    pub(crate) fn new(
+        mailbox: MailboxRef,
+        procedure_manager: ProcedureManagerRef,
+        selector_ctx: SelectorContext,
+        dist_lock: DistLockRef,
+        table_metadata_manager: TableMetadataManagerRef,
+    ) -> Self {
+        Self {
+            procedure_manager,
+            context: RegionMigrationContext {
+                mailbox,
+                selector_ctx,
+                dist_lock,
+                table_metadata_manager,
+            },
+        }
+    }
+
+    pub(crate) fn try_start(&self) -> Result<()> {
+        let context = self.context.clone();
+        self.procedure_manager
+            .register_loader(
+                RegionMigrationProcedure::TYPE_NAME,
+                Box::new(move |json| {
+                    let context = context.clone();
+                    RegionMigrationProcedure::from_json(json, context).map(|p| Box::new(p) as _)
+                }),
+            )
+            .context(RegisterProcedureLoaderSnafu {
+                type_name: RegionMigrationProcedure::TYPE_NAME,
+            })
+    }
+
+    /// Submits a procedure that migrates region `region_number` of table `table_name` to the
+    /// Datanode `to_peer_id`. Returns the id of the submitted procedure, the procedure itself
+    /// runs in background.
+    pub async fn submit_region_migration(
+        &self,
+        cluster_id: ClusterId,
+        table_name: &TableName,
+        region_number: RegionNumber,
+        to_peer_id: DatanodeId,
+    ) -> Result<ProcedureId> {
+        let task = self
+            .build_task(cluster_id, table_name, region_number, to_peer_id)
+            .await?;
+
+        let procedure = RegionMigrationProcedure::new(task.clone(), self.context.clone());
+        let procedure_with_id = ProcedureWithId::with_random_id(Box::new(procedure));
+        let procedure_id = procedure_with_id.id;
+        info!("Starting region migration procedure {procedure_id} for {task}");
+
+        let mut watcher = self
+            .procedure_manager
+            .submit(procedure_with_id)
+            .await
+            .context(error::SubmitProcedureSnafu)?;
+
+        let _handle = common_runtime::spawn_bg(async move {
+            if let Err(e) = watcher::wait(&mut watcher).await {
+                error!(e; "Failed to wait region migration procedure {procedure_id} for {task}");
+                return;
+            }
+            info!("Region migration procedure {procedure_id} for {task} is finished successfully!");
+        });
+        Ok(procedure_id)
+    }
+
+    /// Resolves where the region currently resides and checks that it could be moved to
+    /// Datanode `to_peer_id`.
+    async fn build_task(
+        &self,
+        cluster_id: ClusterId,
+        table_name: &TableName,
+        region_number: RegionNumber,
+        to_peer_id: DatanodeId,
+    ) -> Result<RegionMigrationTask> {
+        let ctx = &self.context;
+        let table_info = ctx
+            .table_metadata_manager
+            .table_info_manager()
+            .get_old(table_name)
+            .await
+            .context(TableMetadataManagerSnafu)?
+            .with_context(|| TableNotFoundSnafu {
+                name: table_name.to_string(),
+            })?
+            .table_info;
+        let table_ident = TableIdent {
+            catalog: table_info.catalog_name,
+            schema: table_info.schema_name,
+            table: table_info.name,
+            table_id: table_info.ident.table_id,
+            engine: table_info.meta.engine,
+        };
+
+        let key = TableRouteKey {
+            table_id: table_ident.table_id,
+            catalog_name: &table_ident.catalog,
+            schema_name: &table_ident.schema,
+            table_name: &table_ident.table,
+        };
+        let value = table_routes::get_table_route_value(&ctx.selector_ctx.kv_store, &key).await?;
+        let table_route = value
+            .table_route
+            .with_context(|| CorruptedTableRouteSnafu {
+                key: key.to_string(),
+                reason: "'table_route' is empty",
+            })?;
+        let table_route = TableRoute::try_from_raw(&value.peers, table_route)
+            .context(TableRouteConversionSnafu)?;
+
+        let from_peer = table_route
+            .find_region_leader(region_number)
+            .with_context(|| error::InvalidArgumentsSnafu {
+                err_msg: format!("Region {region_number} of table {table_name} has no leader"),
+            })?;
+        ensure!(
+            from_peer.id != to_peer_id,
+            error::InvalidArgumentsSnafu {
+                err_msg: format!(
+                    "Region {region_number} of table {table_name} is already on Datanode {to_peer_id}"
+                ),
+            }
+        );
+
+        let to_peer = lease::alive_datanodes(
+            cluster_id,
+            &ctx.selector_ctx.meta_peer_client,
+            ctx.selector_ctx.datanode_lease_secs,
+        )
+        .await?
+        .into_iter()
+        .find_map(|(k, v)| (k.node_id == to_peer_id).then(|| Peer::new(k.node_id, v.node_addr)))
+        .with_context(|| error::InvalidArgumentsSnafu {
+            err_msg: format!("Datanode {to_peer_id} is not alive"),
+        })?;
+
+        Ok(RegionMigrationTask {
+            region: RegionIdent {
+                cluster_id,
+                datanode_id: from_peer.id,
+                table_ident,
+                region_number,
+            },
+            to_peer,
+        })
+    }
+}
+
+/// Describes which region to migrate and where to.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RegionMigrationTask {
+    /// The region to migrate, its `datanode_id` is the Datanode it was on when the migration
+    /// started.
+    pub region: RegionIdent,
+    pub to_peer: Peer,
+}
+
+impl RegionMigrationTask {
+    /// The region ident of the migrated region on the candidate Datanode.
+    fn candidate_region(&self) -> RegionIdent {
+        RegionIdent {
+            datanode_id: self.to_peer.id,
+            ..self.region.clone()
+        }
+    }
+}
+
+impl Display for RegionMigrationTask {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} to Datanode {}", self.region, self.to_peer.id)
+    }
+}
+
+/// A "Node" in the state machine of region migration procedure.
+/// Contains the current state and the data.
+#[derive(Serialize, Deserialize, Debug)]
+struct Node {
+    task: RegionMigrationTask,
+    state: Option<Box<dyn State>>,
+}
+
+/// The "Context" of region migration procedure state machine.
+#[derive(Clone)]
+pub struct RegionMigrationContext {
+    pub mailbox: MailboxRef,
+    pub selector_ctx: SelectorContext,
+    pub dist_lock: DistLockRef,
+    pub table_metadata_manager: TableMetadataManagerRef,
+}
+
+impl RegionMigrationContext {
+    async fn send_instruction(
+        &self,
+        subject: &str,
+        datanode_id: DatanodeId,
+        instruction: Instruction,
+        timeout: Duration,
+    ) -> Result<MailboxReceiver> {
+        let msg = MailboxMessage::json_message(
+            subject,
+            &format!("Metasrv@{}", self.selector_ctx.server_addr),
+            &format!("Datanode-{datanode_id}"),
+            common_time::util::current_time_millis(),
+            &instruction,
+        )
+        .with_context(|_| error::SerializeToJsonSnafu {
+            input: instruction.to_string(),
+        })?;
+
+        let ch = Channel::Datanode(datanode_id);
+        self.mailbox.send(&ch, msg, timeout).await
+    }
+}
+
+/// The state machine of region migration procedure. Driven by the call to `next`.
+#[async_trait]
+#[typetag::serde(tag = "region_migration_state")]
+trait State: Sync + Send + Debug {
+    async fn next(
+        mut self: Box<Self>,
+        ctx: &RegionMigrationContext,
+        task: &RegionMigrationTask,
+    ) -> Result<Box<dyn State>>;
+
+    fn status(&self) -> Status {
+        Status::executing(true)
+    }
+}
+
+/// The states transition of region migration procedure:
+///
+/// ```text
+///                     ┌─────────────────┐
+///                     │CloseSourceRegion◄───────┐
+///                     └────────┬────────┘       │ Retry if failed to
+///                              │                │ close the region
+///                              ├────────────────┘
+///                              │ The source Datanode flushes
+///                              │ the region before closing it
+///                              │
+///                    ┌─────────▼─────────┐
+///                    │OpenCandidateRegion◄──────┐
+///                    └──┬──────┬─────────┘      │ Retry if the reply
+///                       │      │                │ timeout
+///                       │      ├────────────────┘
+///   Failed to open the  │      │ The candidate replays the
+///   region on candidate │      │ flushed data when opening
+///                       │      │
+///  ┌────────────────────▼┐   ┌─▼───────────────┐
+///  │ReopenSourceRegion   │   │UpdateRegionRoute│
+///  └──────────┬──────────┘   └────────┬────────┘
+///             │                       │ Moves the region to the
+///             │                       │ candidate in metadata
+///             │                       │
+///             │              ┌────────▼──────┐
+///             │              │InvalidateCache│
+///             │              └────────┬──────┘
+///             │                       │
+///             │    ┌──────────────────▼┐
+///             └────►RegionMigrationEnd │
+///                  └───────────────────┘
+/// ```
+///
+/// The region is closed on the source Datanode before it's opened on the candidate, so it's
+/// never opened on two Datanodes at the same time. Writes to the region are rejected during
+/// the migration instead of being written to a region that is going to be abandoned.
+pub struct RegionMigrationProcedure {
+    node: Node,
+    context: RegionMigrationContext,
+}
+
+impl RegionMigrationProcedure {
+    const TYPE_NAME: &'static str = "metasrv-procedure::RegionMigration";
+
+    pub fn new(task: RegionMigrationTask, context: RegionMigrationContext) -> Self {
+        let node = Node {
+            task,
+            state: Some(Box::new(CloseSourceRegion)),
+        };
+        Self { node, context }
+    }
+
+    fn from_json(json: &str, context: RegionMigrationContext) -> ProcedureResult<Self> {
+        let node: Node = serde_json::from_str(json).context(FromJsonSnafu)?;
+        Ok(Self { node, context })
+    }
+}
+
+#[async_trait]
+impl Procedure for RegionMigrationProcedure {
+    fn type_name(&self) -> &str {
+        Self::TYPE_NAME
+    }
+
+    async fn execute(&mut self, _ctx: &ProcedureContext) -> ProcedureResult<Status> {
+        if let Some(state) = self.node.state.take() {
+            let next_state = state
+                .next(&self.context, &self.node.task)
+                .await
+                .map_err(handle_retry_error)?;
+            self.node.state = Some(next_state);
+        }
+        Ok(self
+            .node
+            .state
+            .as_ref()
+            .map(|s| s.status())
+            .unwrap_or(Status::Done))
+    }
+
+    fn dump(&self) -> ProcedureResult<String> {
+        serde_json::to_string(&self.node).context(ToJsonSnafu)
+    }
+
+    /// Shares the lock key with region failover procedure, so that a region is never migrated
+    /// and failed over at the same time.
+    fn lock_key(&self) -> LockKey {
+        let region_ident = &self.node.task.region;
+        let key = format!(
+            "{}/region-{}",
+            common_catalog::format_full_table_name(
+                &region_ident.table_ident.catalog,
+                &region_ident.table_ident.schema,
+                &region_ident.table_ident.table
+            ),
+            region_ident.region_number
+        );
+        LockKey::single(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use api::v1::meta::mailbox_message::Payload;
+    use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
+    use common_meta::instruction::{InstructionReply, SimpleReply};
+    use common_meta::key::table_region::RegionDistribution;
+    use common_meta::kv_backend::KvBackend;
+    use common_meta::rpc::store::PutRequest;
+    use common_procedure::local::{LocalManager, ManagerConfig};
+    use common_procedure::BoxedProcedure;
+
+    use super::*;
+    use crate::cluster::MetaPeerClientBuilder;
+    use crate::error::Error;
+    use crate::keys::{LeaseKey, LeaseValue};
+    use crate::procedure::region_failover::tests::{TestingEnv, TestingEnvBuilder};
+    use crate::procedure::state_store::MetaStateStore;
+    use crate::service::store::memory::MemStore;
+
+    fn new_context(env: &TestingEnv) -> RegionMigrationContext {
+        RegionMigrationContext {
+            mailbox: env.context.mailbox.clone(),
+            selector_ctx: env.context.selector_ctx.clone(),
+            dist_lock: env.context.dist_lock.clone(),
+            table_metadata_manager: env.context.table_metadata_manager.clone(),
+        }
+    }
+
+    async fn new_task(env: &TestingEnv, region_number: u32, to_peer: u64) -> RegionMigrationTask {
+        RegionMigrationTask {
+            region: env.failed_region(region_number).await,
+            to_peer: Peer::new(to_peer, ""),
+        }
+    }
+
+    /// Simulates the Datanode replying `reply` to the instruction `expected`.
+    fn reply_instruction(
+        env: &mut TestingEnv,
+        datanode_id: u64,
+        expected: Instruction,
+        reply: InstructionReply,
+    ) {
+        let mut rx = env.heartbeat_receivers.remove(&datanode_id).unwrap();
+        let mailbox = env.context.mailbox.clone();
+        let _handle = common_runtime::spawn_bg(async move {
+            let resp = rx.recv().await.unwrap().unwrap();
+            let received = resp.mailbox_message.unwrap();
+            assert_eq!(
+                received.payload,
+                Some(Payload::Json(serde_json::to_string(&expected).unwrap()))
+            );
+
+            mailbox
+                .on_recv(
+                    received.id,
+                    Ok(MailboxMessage {
+                        id: received.id,
+                        subject: received.subject,
+                        from: format!("Datanode-{datanode_id}"),
+                        to: "Metasrv".to_string(),
+                        timestamp_millis: common_time::util::current_time_millis(),
+                        payload: Some(Payload::Json(serde_json::to_string(&reply).unwrap())),
+                    }),
+                )
+                .await
+                .unwrap();
+
+            // Keeps the receiver alive so that later messages to this Datanode could be
+            // pushed.
+            let _ = rx.recv().await;
+        });
+    }
+
+    async fn region_distribution(env: &TestingEnv) -> RegionDistribution {
+        env.context
+            .table_metadata_manager
+            .table_region_manager()
+            .get_old(&TableName::new(
+                DEFAULT_CATALOG_NAME,
+                DEFAULT_SCHEMA_NAME,
+                "my_table",
+            ))
+            .await
+            .unwrap()
+            .unwrap()
+            .region_distribution
+    }
+
+    async fn region_leader(env: &TestingEnv, region_number: u32) -> u64 {
+        let key = TableRouteKey {
+            table_id: 1,
+            catalog_name: DEFAULT_CATALOG_NAME,
+            schema_name: DEFAULT_SCHEMA_NAME,
+            table_name: "my_table",
+        };
+        let value = table_routes::get_table_route_value(&env.context.selector_ctx.kv_store, &key)
+            .await
+            .unwrap();
+        TableRoute::try_from_raw(&value.peers, value.table_route.unwrap())
+            .unwrap()
+            .find_region_leader(region_number)
+            .unwrap()
+            .id
+    }
+
+    #[tokio::test]
+    async fn test_region_migration_procedure() {
+        common_telemetry::init_default_ut_logging();
+
+        let mut env = TestingEnvBuilder::new().build().await;
+        let task = new_task(&env, 1, 2).await;
+
+        reply_instruction(
+            &mut env,
+            1,
+            Instruction::CloseRegion(task.region.clone()),
+            InstructionReply::CloseRegion(SimpleReply {
+                result: true,
+                error: None,
+            }),
+        );
+        reply_instruction(
+            &mut env,
+            2,
+            Instruction::OpenRegion(task.candidate_region()),
+            InstructionReply::OpenRegion(SimpleReply {
+                result: true,
+                error: None,
+            }),
+        );
+
+        let mut procedure = Box::new(RegionMigrationProcedure::new(
+            task.clone(),
+            new_context(&env),
+        )) as BoxedProcedure;
+        common_procedure_test::execute_procedure_until_done(&mut procedure).await;
+
+        assert!(procedure
+            .dump()
+            .unwrap()
+            .ends_with(r#""state":{"region_migration_state":"RegionMigrationEnd"}}"#));
+
+        let region_distribution = region_distribution(&env).await;
+        assert_eq!(region_distribution.get(&1).unwrap(), &vec![2]);
+        assert_eq!(region_distribution.get(&2).unwrap(), &vec![3, 1]);
+        assert_eq!(region_leader(&env, 1).await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_reopen_source_region_if_candidate_failed() {
+        common_telemetry::init_default_ut_logging();
+
+        let mut env = TestingEnvBuilder::new().build().await;
+        let task = new_task(&env, 1, 2).await;
+
+        let mut rx = env.heartbeat_receivers.remove(&1).unwrap();
+        let mailbox = env.context.mailbox.clone();
+        let region = task.region.clone();
+        let _handle = common_runtime::spawn_bg(async move {
+            let replies = [
+                (
+                    Instruction::CloseRegion(region.clone()),
+                    InstructionReply::CloseRegion(SimpleReply {
+                        result: true,
+                        error: None,
+                    }),
+                ),
+                (
+                    Instruction::OpenRegion(region),
+                    InstructionReply::OpenRegion(SimpleReply {
+                        result: true,
+                        error: None,
+                    }),
+                ),
+            ];
+            for (expected, reply) in replies {
+                let resp = rx.recv().await.unwrap().unwrap();
+                let received = resp.mailbox_message.unwrap();
+                assert_eq!(
+                    received.payload,
+                    Some(Payload::Json(serde_json::to_string(&expected).unwrap()))
+                );
+                mailbox
+                    .on_recv(
+                        received.id,
+                        Ok(MailboxMessage {
+                            id: received.id,
+                            subject: received.subject,
+                            from: "Datanode-1".to_string(),
+                            to: "Metasrv".to_string(),
+                            timestamp_millis: common_time::util::current_time_millis(),
+                            payload: Some(Payload::Json(serde_json::to_string(&reply).unwrap())),
+                        }),
+                    )
+                    .await
+                    .unwrap();
+            }
+        });
+        reply_instruction(
+            &mut env,
+            2,
+            Instruction::OpenRegion(task.candidate_region()),
+            InstructionReply::OpenRegion(SimpleReply {
+                result: false,
+                error: Some("mocked".to_string()),
+            }),
+        );
+
+        let mut procedure = Box::new(RegionMigrationProcedure::new(
+            task.clone(),
+            new_context(&env),
+        )) as BoxedProcedure;
+        common_procedure_test::execute_procedure_until_done(&mut procedure).await;
+
+        // The region stays on the source Datanode.
+        let region_distribution = region_distribution(&env).await;
+        assert_eq!(region_distribution.get(&1).unwrap(), &vec![1, 2]);
+        assert_eq!(region_distribution.get(&2).unwrap(), &vec![3]);
+        assert_eq!(region_leader(&env, 1).await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_close_source_region_timeout() {
+        let env = TestingEnvBuilder::new().build().await;
+        let task = new_task(&env, 1, 2).await;
+        let ctx = new_context(&env);
+
+        let mailbox_receiver = ctx
+            .send_instruction(
+                "Close Source Region",
+                task.region.datanode_id,
+                Instruction::CloseRegion(task.region.clone()),
+                Duration::from_millis(100),
+            )
+            .await
+            .unwrap();
+        let result = CloseSourceRegion
+            .handle_response(mailbox_receiver, &task)
+            .await;
+        assert!(matches!(result, Err(Error::RetryLater { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_build_task() {
+        let mut env = TestingEnvBuilder::new().build().await;
+
+        // Only Datanode 2 is alive.
+        let in_memory = Arc::new(MemStore::new());
+        let lease_key = LeaseKey {
+            cluster_id: 0,
+            node_id: 2,
+        };
+        let lease_value = LeaseValue {
+            timestamp_millis: common_time::util::current_time_millis(),
+            node_addr: "127.0.0.1:4100".to_string(),
+        };
+        let _ = in_memory
+            .put(PutRequest {
+                key: lease_key.try_into().unwrap(),
+                value: lease_value.try_into().unwrap(),
+                prev_kv: false,
+            })
+            .await
+            .unwrap();
+        env.context.selector_ctx.meta_peer_client = MetaPeerClientBuilder::default()
+            .election(None)
+            .in_memory(in_memory)
+            .build()
+            .map(Arc::new)
+            .unwrap();
+
+        let state_store = Arc::new(MetaStateStore::new(
+            env.context.selector_ctx.kv_store.clone(),
+        ));
+        let manager = RegionMigrationManager::new(
+            env.context.mailbox.clone(),
+            Arc::new(LocalManager::new(ManagerConfig::default(), state_store)),
+            env.context.selector_ctx.clone(),
+            env.context.dist_lock.clone(),
+            env.context.table_metadata_manager.clone(),
+        );
+        let table_name = TableName::new(DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, "my_table");
+
+        let task = manager.build_task(0, &table_name, 1, 2).await.unwrap();
+        assert_eq!(task.region, env.failed_region(1).await);
+        assert_eq!(task.to_peer, Peer::new(2, "127.0.0.1:4100"));
+
+        // Region 1 is already on Datanode 1.
+        let err = manager.build_task(0, &table_name, 1, 1).await.unwrap_err();
+        assert!(matches!(err, Error::InvalidArguments { .. }), "{err:?}");
+
+        // Region 5 does not exist.
+        let err = manager.build_task(0, &table_name, 5, 2).await.unwrap_err();
+        assert!(matches!(err, Error::InvalidArguments { .. }), "{err:?}");
+
+        // Datanode 3 is not alive.
+        let err = manager.build_task(0, &table_name, 1, 3).await.unwrap_err();
+        assert!(matches!(err, Error::InvalidArguments { .. }), "{err:?}");
+
+        let table_name = TableName::new(DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, "foo");
+        let err = manager.build_task(0, &table_name, 1, 2).await.unwrap_err();
+        assert!(matches!(err, Error::TableNotFound { .. }), "{err:?}");
+    }
+}
@@ -0,0 +1,93 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use async_trait::async_trait;
+use common_meta::instruction::{Instruction, InstructionReply, SimpleReply};
+use common_telemetry::{debug, info};
+use serde::{Deserialize, Serialize};
+
+use super::open_candidate_region::OpenCandidateRegion;
+use super::{RegionMigrationContext, RegionMigrationTask, State, CLOSE_REGION_MESSAGE_TIMEOUT};
+use crate::error::{Error, Result, RetryLaterSnafu, UnexpectedInstructionReplySnafu};
+use crate::handler::HeartbeatMailbox;
+use crate::service::mailbox::MailboxReceiver;
+
+/// Closes the region on the Datanode it resides. The Datanode flushes the region before
+/// closing it, so all the data written to the region is persisted and could be read by the
+/// candidate.
+#[derive(Serialize, Deserialize, Debug)]
+pub(super) struct CloseSourceRegion;
+
+impl CloseSourceRegion {
+    async fn handle_response(
+        self,
+        mailbox_receiver: MailboxReceiver,
+        task: &RegionMigrationTask,
+    ) -> Result<Box<dyn State>> {
+        match mailbox_receiver.await? {
+            Ok(msg) => {
+                debug!("Received close source region reply: {msg:?}");
+
+                let reply = HeartbeatMailbox::json_reply(&msg)?;
+                let InstructionReply::CloseRegion(SimpleReply { result, error }) = reply else {
+                    return UnexpectedInstructionReplySnafu {
+                        mailbox_message: msg.to_string(),
+                        reason: "expect close region reply",
+                    }.fail();
+                };
+                if result {
+                    info!("Region {} is closed on the source Datanode", task.region);
+                    Ok(Box::new(OpenCandidateRegion))
+                } else {
+                    let reason = format!(
+                        "Region {} is not closed by Datanode {}, error: {error:?}",
+                        task.region, task.region.datanode_id,
+                    );
+                    RetryLaterSnafu { reason }.fail()
+                }
+            }
+            // Unlike region failover, the source Datanode is expected to be alive. We must not
+            // assume the region is closed until the Datanode says so, otherwise the region
+            // could be opened on two Datanodes.
+            Err(e) if matches!(e, Error::MailboxTimeout { .. }) => {
+                let reason = format!(
+                    "Mailbox received timeout for closing region {} on Datanode {}",
+                    task.region, task.region.datanode_id,
+                );
+                RetryLaterSnafu { reason }.fail()
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[async_trait]
+#[typetag::serde]
+impl State for CloseSourceRegion {
+    async fn next(
+        mut self: Box<Self>,
+        ctx: &RegionMigrationContext,
+        task: &RegionMigrationTask,
+    ) -> Result<Box<dyn State>> {
+        let mailbox_receiver = ctx
+            .send_instruction(
+                "Close Source Region",
+                task.region.datanode_id,
+                Instruction::CloseRegion(task.region.clone()),
+                CLOSE_REGION_MESSAGE_TIMEOUT,
+            )
+            .await?;
+
+        self.handle_response(mailbox_receiver, task).await
+    }
+}
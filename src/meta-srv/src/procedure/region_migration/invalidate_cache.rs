@@ -0,0 +1,43 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::migration_end::RegionMigrationEnd;
+use super::{RegionMigrationContext, RegionMigrationTask, State};
+use crate::error::Result;
+use crate::procedure::utils::broadcast_invalidate_table_cache;
+
+/// Tells the Frontends to fetch the new table route.
+#[derive(Serialize, Deserialize, Debug)]
+pub(super) struct InvalidateCache;
+
+#[async_trait]
+#[typetag::serde]
+impl State for InvalidateCache {
+    async fn next(
+        mut self: Box<Self>,
+        ctx: &RegionMigrationContext,
+        task: &RegionMigrationTask,
+    ) -> Result<Box<dyn State>> {
+        broadcast_invalidate_table_cache(
+            &ctx.mailbox,
+            &ctx.selector_ctx.server_addr,
+            &task.region.table_ident,
+        )
+        .await?;
+
+        Ok(Box::new(RegionMigrationEnd))
+    }
+}
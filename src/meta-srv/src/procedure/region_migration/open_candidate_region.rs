@@ -0,0 +1,95 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use async_trait::async_trait;
+use common_meta::instruction::{Instruction, InstructionReply, SimpleReply};
+use common_telemetry::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+
+use super::reopen_source_region::ReopenSourceRegion;
+use super::update_region_route::UpdateRegionRoute;
+use super::{RegionMigrationContext, RegionMigrationTask, State, OPEN_REGION_MESSAGE_TIMEOUT};
+use crate::error::{Error, Result, RetryLaterSnafu, UnexpectedInstructionReplySnafu};
+use crate::handler::HeartbeatMailbox;
+use crate::service::mailbox::MailboxReceiver;
+
+/// Opens the region on the candidate Datanode. The source region has been flushed and closed,
+/// so the candidate catches up with all the data written to the region by opening it.
+#[derive(Serialize, Deserialize, Debug)]
+pub(super) struct OpenCandidateRegion;
+
+impl OpenCandidateRegion {
+    async fn handle_response(
+        self,
+        mailbox_receiver: MailboxReceiver,
+        task: &RegionMigrationTask,
+    ) -> Result<Box<dyn State>> {
+        match mailbox_receiver.await? {
+            Ok(msg) => {
+                debug!("Received open candidate region reply: {msg:?}");
+
+                let reply = HeartbeatMailbox::json_reply(&msg)?;
+                let InstructionReply::OpenRegion(SimpleReply { result, error }) = reply else {
+                    return UnexpectedInstructionReplySnafu {
+                        mailbox_message: msg.to_string(),
+                        reason: "expect open region reply",
+                    }.fail();
+                };
+                if result {
+                    info!(
+                        "Region {} is opened on the candidate Datanode {:?}",
+                        task.region, task.to_peer
+                    );
+                    Ok(Box::new(UpdateRegionRoute))
+                } else {
+                    // The candidate is chosen by the operator, give the region back to the
+                    // source Datanode rather than retrying on a Datanode that may never open it.
+                    warn!(
+                        "Region {} is not opened by Datanode {:?}, error: {error:?}",
+                        task.region, task.to_peer,
+                    );
+                    Ok(Box::new(ReopenSourceRegion::new(error)))
+                }
+            }
+            Err(e) if matches!(e, Error::MailboxTimeout { .. }) => {
+                let reason = format!(
+                    "Mailbox received timeout for opening region {} on Datanode {:?}",
+                    task.region, task.to_peer,
+                );
+                RetryLaterSnafu { reason }.fail()
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[async_trait]
+#[typetag::serde]
+impl State for OpenCandidateRegion {
+    async fn next(
+        mut self: Box<Self>,
+        ctx: &RegionMigrationContext,
+        task: &RegionMigrationTask,
+    ) -> Result<Box<dyn State>> {
+        let mailbox_receiver = ctx
+            .send_instruction(
+                "Open Candidate Region",
+                task.to_peer.id,
+                Instruction::OpenRegion(task.candidate_region()),
+                OPEN_REGION_MESSAGE_TIMEOUT,
+            )
+            .await?;
+
+        self.handle_response(mailbox_receiver, task).await
+    }
+}
@@ -0,0 +1,100 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use async_trait::async_trait;
+use common_meta::instruction::{Instruction, InstructionReply, SimpleReply};
+use common_telemetry::{debug, warn};
+use serde::{Deserialize, Serialize};
+
+use super::migration_end::RegionMigrationEnd;
+use super::{RegionMigrationContext, RegionMigrationTask, State, OPEN_REGION_MESSAGE_TIMEOUT};
+use crate::error::{Error, Result, RetryLaterSnafu, UnexpectedInstructionReplySnafu};
+use crate::handler::HeartbeatMailbox;
+use crate::service::mailbox::MailboxReceiver;
+
+/// Opens the region on the source Datanode again after the candidate failed to open it. The
+/// region route is left untouched, so the migration is aborted.
+#[derive(Serialize, Deserialize, Debug)]
+pub(super) struct ReopenSourceRegion {
+    /// Why the candidate failed to open the region.
+    candidate_error: Option<String>,
+}
+
+impl ReopenSourceRegion {
+    pub(super) fn new(candidate_error: Option<String>) -> Self {
+        Self { candidate_error }
+    }
+
+    async fn handle_response(
+        self,
+        mailbox_receiver: MailboxReceiver,
+        task: &RegionMigrationTask,
+    ) -> Result<Box<dyn State>> {
+        match mailbox_receiver.await? {
+            Ok(msg) => {
+                debug!("Received reopen source region reply: {msg:?}");
+
+                let reply = HeartbeatMailbox::json_reply(&msg)?;
+                let InstructionReply::OpenRegion(SimpleReply { result, error }) = reply else {
+                    return UnexpectedInstructionReplySnafu {
+                        mailbox_message: msg.to_string(),
+                        reason: "expect open region reply",
+                    }.fail();
+                };
+                if result {
+                    warn!(
+                        "Region migration of {task} is aborted, candidate error: {:?}",
+                        self.candidate_error
+                    );
+                    Ok(Box::new(RegionMigrationEnd))
+                } else {
+                    // The region is not opened anywhere now, keep trying.
+                    let reason = format!(
+                        "Region {} is not reopened by Datanode {}, error: {error:?}",
+                        task.region, task.region.datanode_id,
+                    );
+                    RetryLaterSnafu { reason }.fail()
+                }
+            }
+            Err(e) if matches!(e, Error::MailboxTimeout { .. }) => {
+                let reason = format!(
+                    "Mailbox received timeout for reopening region {} on Datanode {}",
+                    task.region, task.region.datanode_id,
+                );
+                RetryLaterSnafu { reason }.fail()
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[async_trait]
+#[typetag::serde]
+impl State for ReopenSourceRegion {
+    async fn next(
+        mut self: Box<Self>,
+        ctx: &RegionMigrationContext,
+        task: &RegionMigrationTask,
+    ) -> Result<Box<dyn State>> {
+        let mailbox_receiver = ctx
+            .send_instruction(
+                "Reopen Source Region",
+                task.region.datanode_id,
+                Instruction::OpenRegion(task.region.clone()),
+                OPEN_REGION_MESSAGE_TIMEOUT,
+            )
+            .await?;
+
+        self.handle_response(mailbox_receiver, task).await
+    }
+}
@@ -0,0 +1,54 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::invalidate_cache::InvalidateCache;
+use super::{RegionMigrationContext, RegionMigrationTask, State};
+use crate::error::{Result, RetryLaterSnafu};
+use crate::procedure::utils::move_region_leader;
+
+/// Moves the region from the source Datanode to the candidate in the region distribution and
+/// the table route.
+#[derive(Serialize, Deserialize, Debug)]
+pub(super) struct UpdateRegionRoute;
+
+#[async_trait]
+#[typetag::serde]
+impl State for UpdateRegionRoute {
+    async fn next(
+        mut self: Box<Self>,
+        ctx: &RegionMigrationContext,
+        task: &RegionMigrationTask,
+    ) -> Result<Box<dyn State>> {
+        move_region_leader(
+            &ctx.dist_lock,
+            &ctx.table_metadata_manager,
+            &ctx.selector_ctx.kv_store,
+            &task.region,
+            &task.to_peer,
+        )
+        .await
+        .map_err(|e| {
+            RetryLaterSnafu {
+                reason: format!(
+                    "Failed to update metadata for migrating region {}, error: {}",
+                    task.region, e
+                ),
+            }
+            .build()
+        })?;
+        Ok(Box::new(InvalidateCache))
+    }
+}
@@ -12,17 +12,27 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use api::v1::meta::TableRouteValue;
+use api::v1::meta::{MailboxMessage, TableRouteValue};
 use common_meta::helper::TableGlobalKey;
-use common_meta::key::TableRouteKey;
+use common_meta::ident::TableIdent;
+use common_meta::instruction::Instruction;
+use common_meta::key::{TableMetadataManagerRef, TableRouteKey};
 use common_meta::peer::Peer;
 use common_meta::rpc::router::TableRoute;
+use common_meta::table_name::TableName;
+use common_meta::RegionIdent;
 use common_procedure::error::Error as ProcedureError;
-use snafu::{location, Location, ResultExt};
+use common_telemetry::info;
+use snafu::{location, Location, OptionExt, ResultExt};
 use table::engine::TableReference;
 use table::metadata::TableId;
 
 use crate::error::{self, Error, Result};
+use crate::lock::keys::table_metadata_lock_key;
+use crate::lock::{DistLockRef, Opts};
+use crate::service::mailbox::{BroadcastChannel, MailboxRef};
+use crate::service::store::kv::KvStoreRef;
+use crate::table_routes;
 
 pub fn build_table_route_value(table_route: TableRoute) -> Result<TableRouteValue> {
     let (peers, table_route) = table_route
@@ -79,3 +89,178 @@ pub fn handle_retry_error(e: Error) -> ProcedureError {
         ProcedureError::external(e)
     }
 }
+
+/// Moves the leader of `region` to `to_peer` in both the region distribution and the table
+/// route, holding the table metadata lock while doing so.
+pub(crate) async fn move_region_leader(
+    dist_lock: &DistLockRef,
+    table_metadata_manager: &TableMetadataManagerRef,
+    kv_store: &KvStoreRef,
+    region: &RegionIdent,
+    to_peer: &Peer,
+) -> Result<()> {
+    let key = table_metadata_lock_key(region);
+    let key = dist_lock.lock(key, Opts::default()).await?;
+
+    update_table_region_value(table_metadata_manager, region, to_peer).await?;
+
+    update_table_route(kv_store, region, to_peer).await?;
+
+    dist_lock.unlock(key).await?;
+    Ok(())
+}
+
+/// Moves `region` from its current Datanode to `to_peer` in the region distribution of the
+/// table. Moving a region that is already on `to_peer` is a no-op, so the step can be retried.
+pub(crate) async fn update_table_region_value(
+    table_metadata_manager: &TableMetadataManagerRef,
+    region: &RegionIdent,
+    to_peer: &Peer,
+) -> Result<()> {
+    let table_ident = &region.table_ident;
+    let table_name = TableName::new(
+        &table_ident.catalog,
+        &table_ident.schema,
+        &table_ident.table,
+    );
+    let value = table_metadata_manager
+        .table_region_manager()
+        .get_old(&table_name)
+        .await
+        .context(error::TableMetadataManagerSnafu)?
+        .with_context(|| error::TableNotFoundSnafu {
+            name: table_ident.to_string(),
+        })?;
+    let mut region_distribution = value.region_distribution;
+
+    if let Some(mut region_numbers) = region_distribution.remove(&region.datanode_id) {
+        region_numbers.retain(|x| *x != region.region_number);
+
+        if !region_numbers.is_empty() {
+            let _ = region_distribution.insert(region.datanode_id, region_numbers);
+        }
+    }
+
+    let region_numbers = region_distribution
+        .entry(to_peer.id)
+        .or_insert_with(Vec::new);
+    if !region_numbers.contains(&region.region_number) {
+        region_numbers.push(region.region_number);
+    }
+
+    table_metadata_manager
+        .table_region_manager()
+        .put_old(&table_name, region_distribution.clone())
+        .await
+        .context(error::TableMetadataManagerSnafu)?;
+
+    info!(
+        "Region distribution of table (id = {}) is updated to {:?}. \
+        Region {} was on Datanode {}.",
+        table_ident.table_id, region_distribution, region.region_number, region.datanode_id,
+    );
+    Ok(())
+}
+
+/// Sets `to_peer` as the leader of `region` in the table route.
+pub(crate) async fn update_table_route(
+    kv_store: &KvStoreRef,
+    region: &RegionIdent,
+    to_peer: &Peer,
+) -> Result<()> {
+    let table_ident = &region.table_ident;
+    let key = TableRouteKey {
+        table_id: table_ident.table_id,
+        catalog_name: &table_ident.catalog,
+        schema_name: &table_ident.schema,
+        table_name: &table_ident.table,
+    };
+    let value = table_routes::get_table_route_value(kv_store, &key).await?;
+
+    let table_route = value
+        .table_route
+        .with_context(|| error::CorruptedTableRouteSnafu {
+            key: key.to_string(),
+            reason: "'table_route' is empty",
+        })?;
+    let mut table_route = TableRoute::try_from_raw(&value.peers, table_route)
+        .context(error::TableRouteConversionSnafu)?;
+
+    if let Some(region_route) = table_route
+        .region_routes
+        .iter_mut()
+        .find(|x| x.region.id == region.region_number as u64)
+    {
+        region_route.leader_peer = Some(to_peer.clone());
+    }
+
+    pretty_log_table_route_change(&key, &table_route, region);
+
+    let (peers, table_route) = table_route
+        .try_into_raw()
+        .context(error::TableRouteConversionSnafu)?;
+
+    let value = TableRouteValue {
+        peers,
+        table_route: Some(table_route),
+    };
+    table_routes::put_table_route_value(kv_store, &key, value).await
+}
+
+fn pretty_log_table_route_change(
+    key: &TableRouteKey,
+    table_route: &TableRoute,
+    region: &RegionIdent,
+) {
+    let region_routes = table_route
+        .region_routes
+        .iter()
+        .map(|x| {
+            format!(
+                "{{region: {}, leader: {}, followers: [{}]}}",
+                x.region.id,
+                x.leader_peer
+                    .as_ref()
+                    .map(|p| p.id.to_string())
+                    .unwrap_or_else(|| "?".to_string()),
+                x.follower_peers
+                    .iter()
+                    .map(|p| p.id.to_string())
+                    .collect::<Vec<_>>()
+                    .join(","),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    info!(
+        "Updating region routes in table route value (key = '{}') to [{}]. \
+        Region {} was on Datanode {}.",
+        key.to_string(),
+        region_routes.join(", "),
+        region.region_number,
+        region.datanode_id,
+    );
+}
+
+/// Tells all Frontends to drop their cached route of the table.
+pub(crate) async fn broadcast_invalidate_table_cache(
+    mailbox: &MailboxRef,
+    server_addr: &str,
+    table_ident: &TableIdent,
+) -> Result<()> {
+    info!("Broadcast invalidate table({table_ident}) cache message to frontend");
+
+    let instruction = Instruction::InvalidateTableCache(table_ident.clone());
+    let msg = &MailboxMessage::json_message(
+        "Invalidate Table Cache",
+        &format!("Metasrv@{server_addr}"),
+        "Frontend broadcast",
+        common_time::util::current_time_millis(),
+        &instruction,
+    )
+    .with_context(|_| error::SerializeToJsonSnafu {
+        input: instruction.to_string(),
+    })?;
+
+    mailbox.broadcast(&BroadcastChannel::Frontend, msg).await
+}
@@ -13,10 +13,10 @@
 // limitations under the License.
 
 use api::v1::meta::Peer;
+use common_meta::datanode::RegionStat;
 use common_telemetry::warn;
 
 use crate::error::Result;
-use crate::handler::node_stat::RegionStat;
 use crate::keys::{LeaseKey, LeaseValue, StatKey, StatValue};
 use crate::lease;
 use crate::metasrv::SelectorContext;
@@ -121,7 +121,8 @@ fn contains_table(
 
 #[cfg(test)]
 mod tests {
-    use crate::handler::node_stat::{RegionStat, Stat};
+    use common_meta::datanode::{RegionStat, Stat};
+
     use crate::keys::StatValue;
     use crate::selector::load_based::contains_table;
 
@@ -17,6 +17,7 @@ mod heartbeat;
 mod leader;
 mod meta;
 mod node_lease;
+mod region_migration;
 mod route;
 
 use std::collections::HashMap;
@@ -89,6 +90,13 @@ pub fn make_admin_service(meta_srv: MetaSrv) -> Admin {
         },
     );
 
+    let router = router.route(
+        "/migrate-region",
+        region_migration::RegionMigrationHandler {
+            region_migration_manager: meta_srv.region_migration_manager().clone(),
+        },
+    );
+
     let router = Router::nest("/admin", router);
 
     Admin::new(router)
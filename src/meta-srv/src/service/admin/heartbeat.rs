@@ -74,7 +74,8 @@ fn filter_by_addr(stat_vals: Vec<StatValue>, addr: &str) -> Vec<StatValue> {
 
 #[cfg(test)]
 mod tests {
-    use crate::handler::node_stat::Stat;
+    use common_meta::datanode::Stat;
+
     use crate::keys::StatValue;
     use crate::service::admin::heartbeat::filter_by_addr;
 
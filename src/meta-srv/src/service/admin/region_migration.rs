@@ -0,0 +1,102 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::collections::HashMap;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+use common_meta::key::table_name::TableNameKey;
+use common_meta::table_name::TableName;
+use serde::Serialize;
+use snafu::{OptionExt, ResultExt};
+use tonic::codegen::http;
+
+use crate::error::{self, Result, TableMetadataManagerSnafu};
+use crate::procedure::region_migration::RegionMigrationManagerRef;
+use crate::service::admin::HttpHandler;
+
+/// Submits a region migration procedure, e.g.
+/// `/admin/migrate-region?full_table_name=greptime.public.foo&region_number=1&to_peer_id=2`.
+pub struct RegionMigrationHandler {
+    pub region_migration_manager: RegionMigrationManagerRef,
+}
+
+#[derive(Debug, Serialize)]
+struct RegionMigrationResponse {
+    procedure_id: String,
+}
+
+#[async_trait::async_trait]
+impl HttpHandler for RegionMigrationHandler {
+    async fn handle(
+        &self,
+        _: &str,
+        params: &HashMap<String, String>,
+    ) -> Result<http::Response<String>> {
+        let cluster_id = parse_num_param(params, "cluster_id")?.unwrap_or_default();
+
+        let full_table_name =
+            params
+                .get("full_table_name")
+                .context(error::MissingRequiredParameterSnafu {
+                    param: "full_table_name",
+                })?;
+        let key: TableNameKey = full_table_name
+            .as_str()
+            .try_into()
+            .context(TableMetadataManagerSnafu)?;
+        let table_name = TableName::new(key.catalog, key.schema, key.table);
+
+        let region_number = parse_num_param(params, "region_number")?.context(
+            error::MissingRequiredParameterSnafu {
+                param: "region_number",
+            },
+        )?;
+        let to_peer_id = parse_num_param(params, "to_peer_id")?.context(
+            error::MissingRequiredParameterSnafu {
+                param: "to_peer_id",
+            },
+        )?;
+
+        let procedure_id = self
+            .region_migration_manager
+            .submit_region_migration(cluster_id, &table_name, region_number, to_peer_id)
+            .await?;
+
+        let response = RegionMigrationResponse {
+            procedure_id: procedure_id.to_string(),
+        };
+        let body = serde_json::to_string(&response).context(error::SerializeToJsonSnafu {
+            input: format!("{response:?}"),
+        })?;
+
+        http::Response::builder()
+            .status(http::StatusCode::OK)
+            .body(body)
+            .context(error::InvalidHttpBodySnafu)
+    }
+}
+
+fn parse_num_param<T>(params: &HashMap<String, String>, name: &str) -> Result<Option<T>>
+where
+    T: FromStr<Err = ParseIntError>,
+{
+    params
+        .get(name)
+        .map(|value| {
+            value.parse::<T>().context(error::ParseNumSnafu {
+                err_msg: format!("`{name}` is not a valid number"),
+            })
+        })
+        .transpose()
+}
@@ -64,7 +64,8 @@ impl cluster_server::Cluster for MetaSrv {
 }
 
 impl MetaSrv {
+    /// Checks if the metasrv is the leader. A metasrv without election is always the leader.
     pub fn is_leader(&self) -> bool {
-        self.election().map(|x| x.is_leader()).unwrap_or(false)
+        self.election().map(|x| x.is_leader()).unwrap_or(true)
     }
 }
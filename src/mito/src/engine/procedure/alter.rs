@@ -196,6 +196,8 @@ impl<S: StorageEngine> AlterMitoTable<S> {
 
         self.alter_regions().await?;
 
+        self.set_region_options().await?;
+
         self.update_table_manifest().await
     }
 
@@ -214,6 +216,20 @@ impl<S: StorageEngine> AlterMitoTable<S> {
             .map_err(Error::from_error_ext)
     }
 
+    /// Apply new table options to regions if they are changed.
+    async fn set_region_options(&mut self) -> Result<()> {
+        let AlterKind::SetTableOptions { .. } = &self.data.request.alter_kind else {
+            return Ok(());
+        };
+
+        // Safety: We init new info in engine_alter_table()
+        let new_info = self.new_info.as_ref().unwrap();
+        self.table
+            .set_region_options(&new_info.meta.options)
+            .await
+            .map_err(Error::from_error_ext)
+    }
+
     /// Persist the alteration to the manifest and update table info.
     async fn update_table_manifest(&mut self) -> Result<TableRef> {
         // Safety: We init new info in engine_alter_table()
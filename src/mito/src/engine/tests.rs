@@ -699,6 +699,69 @@ async fn test_alter_table_remove_column() {
     assert_eq!(new_meta.region_numbers, old_meta.region_numbers);
 }
 
+#[tokio::test]
+async fn test_alter_table_modify_and_rename_column() {
+    let TestEngineComponents {
+        table_engine,
+        table_ref: table,
+        dir: _dir,
+        ..
+    } = test_util::setup_test_engine_and_table().await;
+    let ctx = EngineContext::default();
+
+    let hosts: VectorRef = Arc::new(StringVector::from(vec!["host1", "host2"]));
+    let cpus: VectorRef = Arc::new(Float64Vector::from_vec(vec![1.0, 2.0]));
+    let memories: VectorRef = Arc::new(Float64Vector::from_vec(vec![3.0, 4.0]));
+    let tss: VectorRef = Arc::new(TimestampMillisecondVector::from_vec(vec![1, 2]));
+    let columns_values = HashMap::from([
+        ("host".to_string(), hosts),
+        ("cpu".to_string(), cpus),
+        ("memory".to_string(), memories),
+        ("ts".to_string(), tss),
+    ]);
+    let insert_req = new_insert_request("demo".to_string(), columns_values);
+    assert_eq!(2, table.insert(insert_req).await.unwrap());
+
+    let old_info = table.table_info();
+    let req = test_util::new_alter_request(AlterKind::ModifyColumnType {
+        column_name: "cpu".to_string(),
+        target_type: ConcreteDataType::float32_datatype(),
+    });
+    let _ = table_engine.alter_table(&ctx, req).await.unwrap();
+    let req = test_util::new_alter_request(AlterKind::RenameColumn {
+        name: "memory".to_string(),
+        new_name: "mem".to_string(),
+    });
+    let _ = table_engine.alter_table(&ctx, req).await.unwrap();
+    let req = test_util::new_alter_request(AlterKind::SetTableOptions {
+        options: HashMap::from([("ttl".to_string(), "7d".to_string())]),
+    });
+    let table = table_engine.alter_table(&ctx, req).await.unwrap();
+
+    let new_info = table.table_info();
+    let new_schema = &new_info.meta.schema;
+    assert_eq!(new_schema.version(), old_info.meta.schema.version() + 2);
+    assert_eq!(new_info.ident.version, old_info.ident.version + 3);
+    assert_eq!(new_info.meta.next_column_id, old_info.meta.next_column_id);
+    assert_eq!(
+        Some(std::time::Duration::from_secs(7 * 24 * 3600)),
+        new_info.meta.options.ttl
+    );
+
+    let stream = table.scan_to_stream(ScanRequest::default()).await.unwrap();
+    let batches = util::collect_batches(stream).await.unwrap();
+    assert_eq!(
+        batches.pretty_print().unwrap(),
+        "\
++-------+-----+-----+-------------------------+
+| host  | cpu | mem | ts                      |
++-------+-----+-----+-------------------------+
+| host1 | 1.0 | 3.0 | 1970-01-01T00:00:00.001 |
+| host2 | 2.0 | 4.0 | 1970-01-01T00:00:00.002 |
++-------+-----+-----+-------------------------+"
+    );
+}
+
 #[tokio::test]
 async fn test_alter_rename_table() {
     let TestEngineComponents {
@@ -17,25 +17,31 @@ pub mod test_util;
 
 use std::any::Any;
 use std::collections::HashMap;
+use std::pin::Pin;
 use std::sync::Arc;
+use std::task::{Context, Poll};
 
 use arc_swap::ArcSwap;
 use async_trait::async_trait;
 use common_datasource::compression::CompressionType;
 use common_error::ext::BoxedError;
 use common_query::logical_plan::Expr;
-use common_recordbatch::error::ExternalSnafu;
-use common_recordbatch::{RecordBatch, RecordBatchStreamAdaptor, SendableRecordBatchStream};
+use common_recordbatch::error::{ExternalSnafu, Result as RecordBatchResult};
+use common_recordbatch::{
+    OrderOption, PlanMetrics, RecordBatch, RecordBatchMetrics, RecordBatchStream,
+    RecordBatchStreamAdaptor, SendableRecordBatchStream,
+};
 use common_telemetry::{info, logging};
 use datatypes::schema::Schema;
+use futures::Stream;
 use metrics::histogram;
 use object_store::ObjectStore;
 use snafu::{ensure, OptionExt, ResultExt};
 use store_api::manifest::{self, Manifest, ManifestVersion, MetaActionIterator};
 use store_api::storage::{
     AddColumn, AlterOperation, AlterRequest, ChunkReader, CompactContext, FlushContext,
-    FlushReason, ReadContext, Region, RegionMeta, RegionNumber, ScanRequest, SchemaRef, Snapshot,
-    WriteContext, WriteRequest,
+    FlushReason, ReadContext, Region, RegionMeta, RegionNumber, RegionOptions, ScanMetricsRef,
+    ScanRequest, SchemaRef, Snapshot, WriteContext, WriteRequest,
 };
 use table::error::{
     InvalidTableSnafu, RegionSchemaMismatchSnafu, Result as TableResult, TableOperationSnafu,
@@ -44,7 +50,7 @@ use table::metadata::{
     FilterPushDownType, RawTableInfo, TableInfo, TableInfoRef, TableMeta, TableType, TableVersion,
 };
 use table::requests::{
-    AddColumnRequest, AlterKind, AlterTableRequest, DeleteRequest, InsertRequest,
+    AddColumnRequest, AlterKind, AlterTableRequest, DeleteRequest, InsertRequest, TableOptions,
 };
 use table::table::{AlterContext, Table};
 use table::{error as table_error, RegionStat};
@@ -204,6 +210,10 @@ impl<R: Region> Table for MitoTable<R> {
 
         let schema = stream_schema.clone();
         let output_ordering = readers.get(0).and_then(|reader| reader.output_ordering());
+        let scan_metrics = readers
+            .iter()
+            .filter_map(|reader| reader.metrics())
+            .collect();
 
         let stream = Box::pin(async_stream::try_stream! {
             for mut reader in readers {
@@ -214,10 +224,13 @@ impl<R: Region> Table for MitoTable<R> {
             }
         });
 
-        Ok(Box::pin(RecordBatchStreamAdaptor {
-            schema,
-            stream,
-            output_ordering,
+        Ok(Box::pin(ScanStream {
+            inner: RecordBatchStreamAdaptor {
+                schema,
+                stream,
+                output_ordering,
+            },
+            scan_metrics,
         }))
     }
 
@@ -238,6 +251,9 @@ impl<R: Region> Table for MitoTable<R> {
             self.alter_regions(table_name, table_version, alter_op)
                 .await?;
         }
+        if let AlterKind::SetTableOptions { .. } = &req.alter_kind {
+            self.set_region_options(&new_info.meta.options).await?;
+        }
 
         // Persist the alteration to the manifest.
         logging::debug!(
@@ -365,10 +381,7 @@ impl<R: Region> Table for MitoTable<R> {
 
         Ok(regions
             .values()
-            .map(|region| RegionStat {
-                region_id: region.id().into(),
-                disk_usage_bytes: region.disk_usage_bytes(),
-            })
+            .map(|region| region.region_stat())
             .collect())
     }
 
@@ -624,6 +637,29 @@ impl<R: Region> MitoTable<R> {
         Ok(())
     }
 
+    /// Applies the region related table options to all opened regions.
+    pub(crate) async fn set_region_options(&self, options: &TableOptions) -> TableResult<()> {
+        let region_options = RegionOptions {
+            write_buffer_size: options.write_buffer_size.map(|s| s.0 as usize),
+            ttl: options.ttl,
+        };
+        let regions = self.regions.load();
+        for region in regions.values() {
+            logging::debug!(
+                "start setting options of region {}, with options {:?}",
+                region.name(),
+                region_options,
+            );
+            region
+                .set_options(region_options.clone())
+                .await
+                .map_err(BoxedError::new)
+                .context(TableOperationSnafu)?;
+        }
+
+        Ok(())
+    }
+
     // Loads a region if the slot of the corresponding region number was not occupied.
     // Assuming the regions with the same region_number are the same.
     pub async fn load_region(&self, region_number: RegionNumber, region: R) -> TableResult<()> {
@@ -658,7 +694,11 @@ impl<R: Region> MitoTable<R> {
             AlterKind::RenameTable { new_table_name } => {
                 new_info.name = new_table_name.clone();
             }
-            AlterKind::AddColumns { .. } | AlterKind::DropColumns { .. } => {
+            AlterKind::AddColumns { .. }
+            | AlterKind::DropColumns { .. }
+            | AlterKind::SetTableOptions { .. }
+            | AlterKind::ModifyColumnType { .. }
+            | AlterKind::RenameColumn { .. } => {
                 let table_meta = &current_info.meta;
                 let new_meta = table_meta
                     .builder_with_alter_kind(table_name, alter_kind)?
@@ -692,8 +732,21 @@ pub(crate) fn create_alter_operation(
         AlterKind::DropColumns { names } => Ok(Some(AlterOperation::DropColumns {
             names: names.clone(),
         })),
-        // No need to build alter operation when reaming tables.
-        AlterKind::RenameTable { .. } => Ok(None),
+        AlterKind::ModifyColumnType {
+            column_name,
+            target_type,
+        } => Ok(Some(AlterOperation::ModifyColumnType {
+            name: column_name.clone(),
+            data_type: target_type.clone(),
+        })),
+        AlterKind::RenameColumn { name, new_name } => Ok(Some(AlterOperation::RenameColumn {
+            name: name.clone(),
+            new_name: new_name.clone(),
+        })),
+        // No need to build alter operation when reaming tables. Table options are
+        // applied to opened regions by `set_region_options()` instead of altering the
+        // region metadata.
+        AlterKind::RenameTable { .. } | AlterKind::SetTableOptions { .. } => Ok(None),
     }
 }
 
@@ -718,6 +771,52 @@ fn create_add_columns_operation(
     Ok(Some(AlterOperation::AddColumns { columns }))
 }
 
+/// Stream of a table scan, reports the scan metrics of all regions once it's exhausted.
+struct ScanStream {
+    inner: RecordBatchStreamAdaptor,
+    scan_metrics: Vec<ScanMetricsRef>,
+}
+
+impl RecordBatchStream for ScanStream {
+    fn schema(&self) -> SchemaRef {
+        self.inner.schema()
+    }
+
+    fn output_ordering(&self) -> Option<&[OrderOption]> {
+        self.inner.output_ordering()
+    }
+
+    fn metrics(&self) -> Option<RecordBatchMetrics> {
+        let (sst_files, row_groups) =
+            self.scan_metrics
+                .iter()
+                .fold((0, 0), |(sst_files, row_groups), metrics| {
+                    (
+                        sst_files + metrics.sst_files(),
+                        row_groups + metrics.row_groups(),
+                    )
+                });
+        Some(RecordBatchMetrics {
+            plan_metrics: vec![PlanMetrics {
+                plan: "MitoTableScan".to_string(),
+                level: 0,
+                metrics: vec![
+                    ("sst_files".to_string(), sst_files),
+                    ("row_groups".to_string(), row_groups),
+                ],
+            }],
+        })
+    }
+}
+
+impl Stream for ScanStream {
+    type Item = RecordBatchResult<RecordBatch>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.inner).poll_next(cx)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
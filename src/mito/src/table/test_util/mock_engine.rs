@@ -28,8 +28,8 @@ use storage::write_batch::WriteBatch;
 use store_api::storage::{
     AlterRequest, Chunk, ChunkReader, CloseOptions, CompactContext, CreateOptions, EngineContext,
     FlushContext, GetRequest, GetResponse, OpenOptions, ReadContext, Region, RegionDescriptor,
-    RegionId, ScanRequest, ScanResponse, SchemaRef, Snapshot, StorageEngine, WriteContext,
-    WriteResponse,
+    RegionId, RegionOptions, ScanRequest, ScanResponse, SchemaRef, Snapshot, StorageEngine,
+    WriteContext, WriteResponse,
 };
 
 pub type Result<T> = std::result::Result<T, MockError>;
@@ -194,6 +194,10 @@ impl Region for MockRegion {
         Ok(())
     }
 
+    async fn set_options(&self, _options: RegionOptions) -> Result<()> {
+        Ok(())
+    }
+
     async fn drop_region(&self) -> Result<()> {
         Ok(())
     }
@@ -14,13 +14,16 @@
 
 use std::sync::Arc;
 
+use common_recordbatch::SendableRecordBatchStream;
 use object_store::ObjectStore;
-use snafu::ResultExt;
+use snafu::{OptionExt, ResultExt};
 use store_api::logstore::LogStore;
+use store_api::storage::{RegionId, ScanRequest};
 
 use crate::config::MitoConfig;
-use crate::error::{RecvSnafu, Result};
-pub use crate::worker::request::CreateRequest;
+use crate::error::{RecvSnafu, RegionNotFoundSnafu, Result};
+use crate::read::scan_region::ScanRegion;
+pub use crate::worker::request::{CreateRequest, OpenRequest, RegionOptions, WriteRequest};
 use crate::worker::request::{RegionRequest, RequestBody};
 use crate::worker::WorkerGroup;
 
@@ -51,7 +54,34 @@ impl MitoEngine {
 
     /// Creates a new region.
     pub async fn create_region(&self, request: CreateRequest) -> Result<()> {
-        self.inner.create_region(request).await
+        self.inner
+            .handle_request_body(RequestBody::Create(request))
+            .await
+    }
+
+    /// Opens an existing region and replays its WAL.
+    pub async fn open_region(&self, request: OpenRequest) -> Result<()> {
+        self.inner
+            .handle_request_body(RequestBody::Open(request))
+            .await
+    }
+
+    /// Writes rows to a region.
+    pub async fn write_region(&self, request: WriteRequest) -> Result<()> {
+        self.inner
+            .handle_request_body(RequestBody::Write(request))
+            .await
+    }
+
+    /// Scans a region and returns a stream of record batches.
+    ///
+    /// Only the latest row of each primary key and timestamp is returned.
+    pub async fn handle_query(
+        &self,
+        region_id: RegionId,
+        request: ScanRequest,
+    ) -> Result<SendableRecordBatchStream> {
+        self.inner.handle_query(region_id, request).await
     }
 }
 
@@ -59,6 +89,7 @@ impl MitoEngine {
 struct EngineInner {
     /// Region workers group.
     workers: WorkerGroup,
+    object_store: ObjectStore,
 }
 
 impl EngineInner {
@@ -69,7 +100,8 @@ impl EngineInner {
         object_store: ObjectStore,
     ) -> EngineInner {
         EngineInner {
-            workers: WorkerGroup::start(&config, log_store, object_store),
+            workers: WorkerGroup::start(&config, log_store, object_store.clone()),
+            object_store,
         }
     }
 
@@ -78,25 +110,487 @@ impl EngineInner {
         self.workers.stop().await
     }
 
-    /// Creates a new region.
-    async fn create_region(&self, create_request: CreateRequest) -> Result<()> {
-        let (request, receiver) = RegionRequest::from_body(RequestBody::Create(create_request));
+    /// Submits a request to the worker of the region and waits for the result.
+    async fn handle_request_body(&self, body: RequestBody) -> Result<()> {
+        let (request, receiver) = RegionRequest::from_body(body);
         self.workers.submit_to_worker(request).await?;
 
         receiver.await.context(RecvSnafu)?
     }
+
+    /// Scans the current version of the region.
+    ///
+    /// Reads the region directly instead of sending a request to its worker.
+    async fn handle_query(
+        &self,
+        region_id: RegionId,
+        mut request: ScanRequest,
+    ) -> Result<SendableRecordBatchStream> {
+        let region = self
+            .workers
+            .get_region(region_id)
+            .context(RegionNotFoundSnafu { region_id })?;
+        // Acquires the version before the committed sequence.
+        let version = region.version_control.current();
+        if request.sequence.is_none() {
+            request.sequence = Some(region.version_control.committed_sequence());
+        }
+
+        ScanRegion::new(
+            version,
+            region.region_dir.clone(),
+            self.object_store.clone(),
+            request,
+        )
+        .scan()
+        .await
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use common_datasource::compression::CompressionType;
+    use common_query::logical_plan::Expr;
+    use common_recordbatch::RecordBatches;
+    use common_time::Timestamp;
+    use datafusion::logical_expr::{col, lit};
+    use datafusion_common::ScalarValue;
+    use datatypes::value::Value;
+    use datatypes::vectors::Float64Vector;
+    use object_store::util::join_dir;
+    use storage::sst::{FileId, FileMeta};
+    use store_api::storage::{OpType, SequenceNumber};
+
     use super::*;
-    use crate::test_util::TestEnv;
+    use crate::error::Error;
+    use crate::manifest::action::{RegionEdit, RegionMetaAction, RegionMetaActionList};
+    use crate::manifest::manager::{RegionManifestManager, RegionManifestOptions};
+    use crate::read::{Batch, BatchBuilder, ReadSchema, VecBatchReader};
+    use crate::sst::parquet::ParquetWriter;
+    use crate::sst::sst_file_path;
+    use crate::test_util::{build_columns, new_batch, CreateRequestBuilder, TestEnv};
 
     #[tokio::test]
     async fn test_engine_new_stop() {
-        let env = TestEnv::new("engine-stop");
+        let mut env = TestEnv::new("engine-stop");
         let engine = env.create_engine(MitoConfig::default()).await;
 
         engine.stop().await.unwrap();
     }
+
+    fn num_rows_in_memtable(engine: &MitoEngine, region_id: RegionId) -> usize {
+        let region = engine.inner.workers.get_region(region_id).unwrap();
+        let version = region.version_control.current();
+        version.memtables.mutable_memtable().num_rows()
+    }
+
+    #[tokio::test]
+    async fn test_engine_create_existing_region() {
+        let mut env = TestEnv::new("create-existing");
+        let engine = env.create_engine(MitoConfig::default()).await;
+
+        let region_id = RegionId::new(1, 1);
+        let builder = CreateRequestBuilder::new(region_id);
+        engine.create_region(builder.build()).await.unwrap();
+
+        let err = engine.create_region(builder.build()).await.unwrap_err();
+        assert!(matches!(err, Error::RegionExists { .. }), "{err}");
+        engine
+            .create_region(builder.create_if_not_exists(true).build())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_engine_write_region() {
+        let mut env = TestEnv::new("write-region");
+        let engine = env.create_engine(MitoConfig::default()).await;
+
+        let region_id = RegionId::new(1, 1);
+        engine
+            .create_region(CreateRequestBuilder::new(region_id).build())
+            .await
+            .unwrap();
+
+        engine
+            .write_region(WriteRequest {
+                region_id,
+                op_type: OpType::Put,
+                columns: build_columns(&["a", "b"], &[1, 2]),
+            })
+            .await
+            .unwrap();
+        assert_eq!(2, num_rows_in_memtable(&engine, region_id));
+
+        // Writes to a region that doesn't exist.
+        let err = engine
+            .write_region(WriteRequest {
+                region_id: RegionId::new(1, 2),
+                op_type: OpType::Put,
+                columns: build_columns(&["a"], &[1]),
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::RegionNotFound { .. }), "{err}");
+
+        // Writes without the time index.
+        let mut columns = build_columns(&["a"], &[1]);
+        columns.remove("ts");
+        let err = engine
+            .write_region(WriteRequest {
+                region_id,
+                op_type: OpType::Put,
+                columns,
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidRequest { .. }), "{err}");
+        assert_eq!(2, num_rows_in_memtable(&engine, region_id));
+    }
+
+    #[tokio::test]
+    async fn test_engine_open_region_replay_wal() {
+        let mut env = TestEnv::new("replay-wal");
+        let engine = env.create_engine(MitoConfig::default()).await;
+
+        let region_id = RegionId::new(1, 1);
+        let builder = CreateRequestBuilder::new(region_id);
+        engine.create_region(builder.build()).await.unwrap();
+        for timestamps in [&[1, 2], &[3, 4]] {
+            engine
+                .write_region(WriteRequest {
+                    region_id,
+                    op_type: OpType::Put,
+                    columns: build_columns(&["a", "a"], timestamps),
+                })
+                .await
+                .unwrap();
+        }
+        engine
+            .write_region(WriteRequest {
+                region_id,
+                op_type: OpType::Delete,
+                columns: build_columns(&["a"], &[1]),
+            })
+            .await
+            .unwrap();
+        assert_eq!(5, num_rows_in_memtable(&engine, region_id));
+
+        let engine = env.reopen_engine(engine, MitoConfig::default()).await;
+        assert!(engine.inner.workers.get_region(region_id).is_none());
+        engine
+            .open_region(OpenRequest {
+                region_id,
+                region_dir: builder.region_dir().to_string(),
+                options: RegionOptions::default(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(5, num_rows_in_memtable(&engine, region_id));
+
+        // Writes after replay continue from the committed sequence.
+        engine
+            .write_region(WriteRequest {
+                region_id,
+                op_type: OpType::Put,
+                columns: build_columns(&["b"], &[1]),
+            })
+            .await
+            .unwrap();
+        assert_eq!(6, num_rows_in_memtable(&engine, region_id));
+
+        let region = engine.inner.workers.get_region(region_id).unwrap();
+        assert_eq!(4, region.version_control.committed_sequence());
+    }
+
+    #[tokio::test]
+    async fn test_engine_open_nonexistent_region() {
+        let mut env = TestEnv::new("open-nonexistent");
+        let engine = env.create_engine(MitoConfig::default()).await;
+
+        let err = engine
+            .open_region(OpenRequest {
+                region_id: RegionId::new(1, 1),
+                region_dir: "empty".to_string(),
+                options: RegionOptions::default(),
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::RegionNotFound { .. }), "{err}");
+    }
+
+    /// Writes `rows` with specific `sequence` to a new SST of the region and
+    /// adds the SST to the manifest, as a flush does.
+    ///
+    /// The region must not be opened by any engine.
+    async fn add_sst(
+        env: &TestEnv,
+        builder: &CreateRequestBuilder,
+        rows: &[(&str, i64)],
+        sequence: SequenceNumber,
+    ) {
+        let read_schema = ReadSchema::all(builder.build_metadata());
+        let rows: Vec<_> = rows
+            .iter()
+            .map(|(tag, ts)| (*tag, *ts, sequence, OpType::Put))
+            .collect();
+        add_sst_batch(env, builder, new_batch(&read_schema, &rows), sequence).await;
+    }
+
+    /// Writes the `batch` to a new SST of the region, like [add_sst].
+    async fn add_sst_batch(
+        env: &TestEnv,
+        builder: &CreateRequestBuilder,
+        batch: Batch,
+        sequence: SequenceNumber,
+    ) {
+        let object_store = env.get_object_store().unwrap();
+        let metadata = builder.build_metadata();
+        let source = VecBatchReader::new(vec![batch]);
+
+        let file_id = FileId::random();
+        let file_path = sst_file_path(builder.region_dir(), file_id);
+        let info = ParquetWriter::new(
+            &file_path,
+            metadata.clone(),
+            Box::new(source),
+            object_store.clone(),
+        )
+        .write_all()
+        .await
+        .unwrap()
+        .unwrap();
+
+        let mut manager = RegionManifestManager::open(RegionManifestOptions {
+            manifest_dir: join_dir(builder.region_dir(), "manifest"),
+            object_store,
+            compress_type: CompressionType::Uncompressed,
+        })
+        .await
+        .unwrap()
+        .unwrap();
+        let edit = RegionEdit {
+            region_version: 0,
+            flushed_sequence: Some(sequence),
+            files_to_add: vec![FileMeta {
+                region_id: metadata.region_id(),
+                file_id,
+                time_range: Some(info.time_range),
+                level: 0,
+                file_size: info.file_size,
+                num_rows: info.num_rows as u64,
+                tag_index_size: 0,
+            }],
+            files_to_remove: Vec::new(),
+            compaction_time_window: None,
+        };
+        let _ = manager
+            .update(RegionMetaActionList::with_action(RegionMetaAction::Edit(
+                edit,
+            )))
+            .await
+            .unwrap();
+    }
+
+    async fn scan_to_string(
+        engine: &MitoEngine,
+        region_id: RegionId,
+        request: ScanRequest,
+    ) -> String {
+        let stream = engine.handle_query(region_id, request).await.unwrap();
+        let batches = RecordBatches::try_collect(stream).await.unwrap();
+        batches.pretty_print().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_engine_scan_memtable() {
+        let mut env = TestEnv::new("scan-memtable");
+        let engine = env.create_engine(MitoConfig::default()).await;
+
+        let region_id = RegionId::new(1, 1);
+        engine
+            .create_region(CreateRequestBuilder::new(region_id).build())
+            .await
+            .unwrap();
+        for (op_type, tags, timestamps) in [
+            (OpType::Put, &["b", "a", "a"][..], &[1, 2, 1][..]),
+            (OpType::Put, &["a"], &[1]),
+            (OpType::Delete, &["a"], &[2]),
+        ] {
+            engine
+                .write_region(WriteRequest {
+                    region_id,
+                    op_type,
+                    columns: build_columns(tags, timestamps),
+                })
+                .await
+                .unwrap();
+        }
+
+        let output = scan_to_string(&engine, region_id, ScanRequest::default()).await;
+        let expected = "\
++-------+---------+-------------------------+
+| tag_0 | field_0 | ts                      |
++-------+---------+-------------------------+
+| a     | 1.0     | 1970-01-01T00:00:00.001 |
+| b     | 1.0     | 1970-01-01T00:00:00.001 |
++-------+---------+-------------------------+";
+        assert_eq!(expected, output);
+
+        // Reads the snapshot before the delete.
+        let request = ScanRequest {
+            sequence: Some(1),
+            projection: Some(vec![2, 0]),
+            ..Default::default()
+        };
+        let output = scan_to_string(&engine, region_id, request).await;
+        let expected = "\
++-------------------------+-------+
+| ts                      | tag_0 |
++-------------------------+-------+
+| 1970-01-01T00:00:00.001 | a     |
+| 1970-01-01T00:00:00.002 | a     |
+| 1970-01-01T00:00:00.001 | b     |
++-------------------------+-------+";
+        assert_eq!(expected, output);
+
+        let request = ScanRequest {
+            projection: Some(vec![3]),
+            ..Default::default()
+        };
+        let err = engine.handle_query(region_id, request).await.unwrap_err();
+        assert!(matches!(err, Error::InvalidRequest { .. }), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_engine_scan_sst_and_memtable() {
+        let mut env = TestEnv::new("scan-sst-memtable");
+        let engine = env.create_engine(MitoConfig::default()).await;
+
+        let region_id = RegionId::new(1, 1);
+        let builder = CreateRequestBuilder::new(region_id);
+        engine.create_region(builder.build()).await.unwrap();
+        engine.stop().await.unwrap();
+
+        add_sst(&env, &builder, &[("a", 1), ("a", 2), ("b", 3)], 1).await;
+        add_sst(&env, &builder, &[("c", 10), ("c", 11)], 2).await;
+
+        let engine = env.reopen_engine(engine, MitoConfig::default()).await;
+        engine
+            .open_region(OpenRequest {
+                region_id,
+                region_dir: builder.region_dir().to_string(),
+                options: RegionOptions::default(),
+            })
+            .await
+            .unwrap();
+        let region = engine.inner.workers.get_region(region_id).unwrap();
+        assert_eq!(2, region.version_control.committed_sequence());
+
+        // Overwrites and deletes rows in SSTs.
+        let mut columns = build_columns(&["a", "b"], &[1, 4]);
+        columns.insert(
+            "field_0".to_string(),
+            Arc::new(Float64Vector::from_slice([100.0, 4.0])),
+        );
+        engine
+            .write_region(WriteRequest {
+                region_id,
+                op_type: OpType::Put,
+                columns,
+            })
+            .await
+            .unwrap();
+        engine
+            .write_region(WriteRequest {
+                region_id,
+                op_type: OpType::Delete,
+                columns: build_columns(&["a"], &[2]),
+            })
+            .await
+            .unwrap();
+
+        let output = scan_to_string(&engine, region_id, ScanRequest::default()).await;
+        let expected = "\
++-------+---------+-------------------------+
+| tag_0 | field_0 | ts                      |
++-------+---------+-------------------------+
+| a     | 100.0   | 1970-01-01T00:00:00.001 |
+| b     | 3.0     | 1970-01-01T00:00:00.003 |
+| b     | 4.0     | 1970-01-01T00:00:00.004 |
+| c     | 10.0    | 1970-01-01T00:00:00.010 |
+| c     | 11.0    | 1970-01-01T00:00:00.011 |
++-------+---------+-------------------------+";
+        assert_eq!(expected, output);
+
+        // Filters by time range and field.
+        let filters = vec![
+            Expr::from(col("ts").gt_eq(lit(ScalarValue::TimestampMillisecond(Some(3), None)))),
+            Expr::from(col("ts").lt(lit(ScalarValue::TimestampMillisecond(Some(11), None)))),
+            Expr::from(col("field_0").gt(lit(3.5f64))),
+        ];
+        let request = ScanRequest {
+            projection: Some(vec![0]),
+            filters,
+            ..Default::default()
+        };
+        let output = scan_to_string(&engine, region_id, request).await;
+        let expected = "\
++-------+
+| tag_0 |
++-------+
+| b     |
+| c     |
++-------+";
+        assert_eq!(expected, output);
+    }
+
+    #[tokio::test]
+    async fn test_engine_scan_overwritten_field() {
+        let mut env = TestEnv::new("scan-overwritten-field");
+        let engine = env.create_engine(MitoConfig::default()).await;
+
+        let region_id = RegionId::new(1, 1);
+        let builder = CreateRequestBuilder::new(region_id);
+        engine.create_region(builder.build()).await.unwrap();
+        engine.stop().await.unwrap();
+
+        add_sst(&env, &builder, &[("a", 1), ("b", 2)], 1).await;
+        // Overwrites the field of `a` so it no longer matches the filter.
+        let read_schema = ReadSchema::all(builder.build_metadata());
+        let mut batch_builder = BatchBuilder::new(&read_schema, 1);
+        batch_builder.push_row(
+            &[
+                Value::from("a"),
+                Value::from(100.0),
+                Value::Timestamp(Timestamp::new_millisecond(1)),
+            ],
+            2,
+            OpType::Put,
+        );
+        add_sst_batch(&env, &builder, batch_builder.finish(), 2).await;
+
+        let engine = env.reopen_engine(engine, MitoConfig::default()).await;
+        engine
+            .open_region(OpenRequest {
+                region_id,
+                region_dir: builder.region_dir().to_string(),
+                options: RegionOptions::default(),
+            })
+            .await
+            .unwrap();
+
+        let request = ScanRequest {
+            filters: vec![Expr::from(col("field_0").lt(lit(50.0f64)))],
+            ..Default::default()
+        };
+        let output = scan_to_string(&engine, region_id, request).await;
+        let expected = "\
++-------+---------+-------------------------+
+| tag_0 | field_0 | ts                      |
++-------+---------+-------------------------+
+| b     | 2.0     | 1970-01-01T00:00:00.002 |
++-------+---------+-------------------------+";
+        assert_eq!(expected, output);
+    }
 }
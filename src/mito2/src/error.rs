@@ -13,12 +13,17 @@
 // limitations under the License.
 
 use std::any::Any;
+use std::sync::Arc;
 
 use common_datasource::compression::CompressionType;
-use common_error::ext::ErrorExt;
+use common_error::ext::{BoxedError, ErrorExt};
 use common_error::status_code::StatusCode;
+use datafusion::error::DataFusionError;
+use datatypes::arrow::error::ArrowError;
+use parquet::errors::ParquetError;
 use snafu::{Location, Snafu};
 use store_api::manifest::ManifestVersion;
+use store_api::storage::RegionId;
 
 use crate::worker::WorkerId;
 
@@ -95,6 +100,193 @@ pub enum Error {
         source: tokio::sync::oneshot::error::RecvError,
         location: Location,
     },
+
+    #[snafu(display("Region {} already exists, location: {}", region_id, location))]
+    RegionExists {
+        region_id: RegionId,
+        location: Location,
+    },
+
+    #[snafu(display("Region {} not found, location: {}", region_id, location))]
+    RegionNotFound {
+        region_id: RegionId,
+        location: Location,
+    },
+
+    #[snafu(display(
+        "Invalid request to region {}, reason: {}, location: {}",
+        region_id,
+        reason,
+        location
+    ))]
+    InvalidRequest {
+        region_id: RegionId,
+        reason: String,
+        location: Location,
+    },
+
+    #[snafu(display(
+        "Failed to create default value for column {} of region {}, location: {}, source: {}",
+        column,
+        region_id,
+        location,
+        source
+    ))]
+    CreateDefault {
+        region_id: RegionId,
+        column: String,
+        location: Location,
+        source: datatypes::Error,
+    },
+
+    #[snafu(display(
+        "Failed to encode WAL entry for region {}, location: {}, source: {}",
+        region_id,
+        location,
+        source
+    ))]
+    EncodeWal {
+        region_id: RegionId,
+        location: Location,
+        source: ArrowError,
+    },
+
+    #[snafu(display(
+        "Failed to decode WAL entry for region {}, location: {}, source: {}",
+        region_id,
+        location,
+        source
+    ))]
+    DecodeWal {
+        region_id: RegionId,
+        location: Location,
+        source: ArrowError,
+    },
+
+    #[snafu(display(
+        "Corrupted WAL entry for region {}, message: {}, location: {}",
+        region_id,
+        message,
+        location
+    ))]
+    WalDataCorrupted {
+        region_id: RegionId,
+        message: String,
+        location: Location,
+    },
+
+    #[snafu(display("Failed to write WAL, location: {}, source: {}", location, source))]
+    WriteWal {
+        location: Location,
+        source: BoxedError,
+    },
+
+    #[snafu(display(
+        "Failed to read WAL of region {}, location: {}, source: {}",
+        region_id,
+        location,
+        source
+    ))]
+    ReadWal {
+        region_id: RegionId,
+        location: Location,
+        source: BoxedError,
+    },
+
+    #[snafu(display(
+        "Failed to convert arrow array, location: {}, source: {}",
+        location,
+        source
+    ))]
+    ConvertVector {
+        location: Location,
+        source: datatypes::Error,
+    },
+
+    // Shares the same error among all requests in a write group.
+    #[snafu(display("Failed to write region, location: {}, source: {}", location, source))]
+    WriteGroup {
+        location: Location,
+        source: Arc<Error>,
+    },
+
+    #[snafu(display(
+        "Failed to write parquet file, path: {}, location: {}, source: {}",
+        path,
+        location,
+        source
+    ))]
+    WriteParquet {
+        path: String,
+        location: Location,
+        source: ParquetError,
+    },
+
+    #[snafu(display(
+        "Failed to read parquet file, path: {}, location: {}, source: {}",
+        path,
+        location,
+        source
+    ))]
+    ReadParquet {
+        path: String,
+        location: Location,
+        source: ParquetError,
+    },
+
+    #[snafu(display(
+        "Invalid parquet file, path: {}, reason: {}, location: {}",
+        path,
+        reason,
+        location
+    ))]
+    InvalidParquet {
+        path: String,
+        reason: String,
+        location: Location,
+    },
+
+    #[snafu(display(
+        "Failed to build predicate for region {}, location: {}, source: {}",
+        region_id,
+        location,
+        source
+    ))]
+    BuildPredicate {
+        region_id: RegionId,
+        location: Location,
+        source: table::error::Error,
+    },
+
+    #[snafu(display(
+        "Failed to evaluate filter, location: {}, source: {}",
+        location,
+        source
+    ))]
+    EvalFilter {
+        location: Location,
+        source: DataFusionError,
+    },
+
+    #[snafu(display(
+        "Failed to filter record batch, location: {}, source: {}",
+        location,
+        source
+    ))]
+    FilterRecordBatch {
+        location: Location,
+        source: ArrowError,
+    },
+
+    #[snafu(display(
+        "Failed to create record batch, location: {}, source: {}",
+        location,
+        source
+    ))]
+    NewRecordBatch {
+        location: Location,
+        source: common_recordbatch::error::Error,
+    },
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -109,9 +301,25 @@ impl ErrorExt for Error {
                 StatusCode::Unexpected
             }
             InvalidScanIndex { .. } => StatusCode::InvalidArguments,
-            RegionMetadataNotFound { .. } | Join { .. } | WorkerStopped { .. } | Recv { .. } => {
-                StatusCode::Internal
-            }
+            RegionMetadataNotFound { .. }
+            | Join { .. }
+            | WorkerStopped { .. }
+            | Recv { .. }
+            | EncodeWal { .. }
+            | DecodeWal { .. }
+            | WalDataCorrupted { .. }
+            | ConvertVector { .. } => StatusCode::Internal,
+            RegionExists { .. } => StatusCode::TableAlreadyExists,
+            RegionNotFound { .. } => StatusCode::TableNotFound,
+            InvalidRequest { .. } => StatusCode::InvalidArguments,
+            CreateDefault { source, .. } => source.status_code(),
+            WriteWal { .. } | ReadWal { .. } => StatusCode::StorageUnavailable,
+            WriteGroup { source, .. } => source.status_code(),
+            WriteParquet { .. } | ReadParquet { .. } => StatusCode::StorageUnavailable,
+            InvalidParquet { .. } => StatusCode::Unexpected,
+            BuildPredicate { source, .. } => source.status_code(),
+            EvalFilter { .. } | FilterRecordBatch { .. } => StatusCode::Internal,
+            NewRecordBatch { source, .. } => source.status_code(),
         }
     }
 
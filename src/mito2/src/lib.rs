@@ -28,10 +28,18 @@ pub mod error;
 #[allow(unused_variables)]
 pub mod manifest;
 #[allow(dead_code)]
+mod memtable;
+#[allow(dead_code)]
 pub mod metadata;
 #[allow(dead_code)]
+mod read;
+#[allow(dead_code)]
 mod region;
 #[allow(dead_code)]
+mod sst;
+#[allow(dead_code)]
+mod wal;
+#[allow(dead_code)]
 mod worker;
 
 #[cfg_attr(doc, aquamarine::aquamarine)]
@@ -19,4 +19,5 @@ mod gc_task;
 mod helper;
 #[allow(unused_variables)]
 mod impl_;
+pub(crate) mod manager;
 mod storage;
@@ -15,14 +15,14 @@
 use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
-use snafu::OptionExt;
+use snafu::{OptionExt, ResultExt};
 use storage::metadata::VersionNumber;
 use storage::sst::{FileId, FileMeta};
-use store_api::manifest::action::{ProtocolAction, ProtocolVersion};
+use store_api::manifest::action::{ProtocolAction, ProtocolVersion, VersionHeader};
 use store_api::manifest::ManifestVersion;
 use store_api::storage::{RegionId, SequenceNumber};
 
-use crate::error::{RegionMetadataNotFoundSnafu, Result};
+use crate::error::{RegionMetadataNotFoundSnafu, Result, SerdeJsonSnafu};
 use crate::manifest::helper;
 use crate::metadata::RegionMetadata;
 
@@ -195,15 +195,38 @@ impl RegionMetaActionList {
     }
 
     /// Encode self into json in the form of string lines, starts with prev_version and then action json list.
-    fn encode(&self) -> Result<Vec<u8>> {
+    pub(crate) fn encode(&self) -> Result<Vec<u8>> {
         helper::encode_actions(self.prev_version, &self.actions)
     }
 
-    fn decode(
-        _bs: &[u8],
+    /// Decodes an action list encoded by [RegionMetaActionList::encode], also returns
+    /// the protocol action in the list if there is one.
+    pub(crate) fn decode(
+        bs: &[u8],
         _reader_version: ProtocolVersion,
     ) -> Result<(Self, Option<ProtocolAction>)> {
-        todo!()
+        let mut lines = bs
+            .split(|b| *b == helper::NEWLINE[0])
+            .filter(|line| !line.is_empty());
+
+        // Decode prev_version. An empty content fails to decode.
+        let first_line = lines.next().unwrap_or_default();
+        let header: VersionHeader = serde_json::from_slice(first_line).context(SerdeJsonSnafu)?;
+
+        let mut action_list = RegionMetaActionList {
+            actions: Vec::default(),
+            prev_version: header.prev_version,
+        };
+        let mut protocol_action = None;
+        for line in lines {
+            let action: RegionMetaAction = serde_json::from_slice(line).context(SerdeJsonSnafu)?;
+            if let RegionMetaAction::Protocol(p) = &action {
+                protocol_action = Some(p.clone());
+            }
+            action_list.actions.push(action);
+        }
+
+        Ok((action_list, protocol_action))
     }
 }
 
@@ -231,7 +254,20 @@ mod tests {
 
     #[test]
     fn test_encode_decode_action_list() {
-        // TODO(ruihang): port this test case
+        let mut action_list = RegionMetaActionList::new(vec![RegionMetaAction::Edit(RegionEdit {
+            region_version: 0,
+            flushed_sequence: Some(10),
+            files_to_add: vec![mock_file_meta()],
+            files_to_remove: vec![],
+            compaction_time_window: None,
+        })]);
+        action_list.set_protocol(ProtocolAction::new());
+        action_list.set_prev_version(3);
+
+        let bytes = action_list.encode().unwrap();
+        let (decoded, protocol) = RegionMetaActionList::decode(&bytes, 0).unwrap();
+        assert_eq!(action_list, decoded);
+        assert_eq!(Some(ProtocolAction::new()), protocol);
     }
 
     // These tests are used to ensure backward compatibility of manifest files.
@@ -259,6 +295,8 @@ mod tests {
             time_range: None,
             level: 0,
             file_size: 1024,
+            num_rows: 0,
+            tag_index_size: 0,
         }
     }
 
@@ -12,11 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::io::Write;
+
 use serde::Serialize;
-use store_api::manifest::action::ProtocolVersion;
+use serde_json::to_writer;
+use snafu::ResultExt;
+use store_api::manifest::action::{ProtocolVersion, VersionHeader};
 use store_api::manifest::ManifestVersion;
 
-use crate::error::Result;
+use crate::error::{Result, SerdeJsonSnafu};
 use crate::manifest::action::RegionCheckpoint;
 pub const NEWLINE: &[u8] = b"\n";
 
@@ -24,7 +28,22 @@ pub fn encode_actions<T: Serialize>(
     prev_version: ManifestVersion,
     actions: &[T],
 ) -> Result<Vec<u8>> {
-    todo!()
+    let mut bytes = Vec::default();
+    {
+        // Encode prev_version
+        let v = VersionHeader { prev_version };
+
+        to_writer(&mut bytes, &v).context(SerdeJsonSnafu)?;
+        // unwrap is fine here, because we write into a buffer.
+        bytes.write_all(NEWLINE).unwrap();
+    }
+
+    for action in actions {
+        to_writer(&mut bytes, action).context(SerdeJsonSnafu)?;
+        bytes.write_all(NEWLINE).unwrap();
+    }
+
+    Ok(bytes)
 }
 
 pub fn encode_checkpoint(snasphot: &RegionCheckpoint) -> Result<Vec<u8>> {
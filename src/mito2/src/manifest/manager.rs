@@ -0,0 +1,219 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Manager of the region manifest.
+
+use common_datasource::compression::CompressionType;
+use object_store::ObjectStore;
+use store_api::manifest::action::{supported_protocol_version, ProtocolAction};
+use store_api::manifest::ManifestVersion;
+
+use crate::error::Result;
+use crate::manifest::action::{
+    RegionChange, RegionManifestData, RegionManifestDataBuilder, RegionMetaAction,
+    RegionMetaActionList,
+};
+use crate::manifest::storage::ManifestObjectStore;
+use crate::metadata::RegionMetadata;
+
+/// Options to create or open a region manifest.
+#[derive(Debug, Clone)]
+pub(crate) struct RegionManifestOptions {
+    /// Directory to store manifest files.
+    pub(crate) manifest_dir: String,
+    pub(crate) object_store: ObjectStore,
+    pub(crate) compress_type: CompressionType,
+}
+
+/// Manages the manifest of a region.
+///
+/// The manager keeps the latest [RegionManifestData] in memory and persists
+/// each update as a delta file. Checkpoints are not supported yet, so opening
+/// a manifest replays all delta files.
+#[derive(Debug)]
+pub(crate) struct RegionManifestManager {
+    store: ManifestObjectStore,
+    /// Version of the last persisted action list.
+    last_version: ManifestVersion,
+    /// Latest manifest data.
+    manifest: RegionManifestData,
+}
+
+impl RegionManifestManager {
+    /// Creates a new manifest for the region with specific `metadata`.
+    pub(crate) async fn new(
+        metadata: RegionMetadata,
+        options: RegionManifestOptions,
+    ) -> Result<RegionManifestManager> {
+        let store = new_store(options);
+        let change = RegionChange {
+            committed_sequence: 0,
+            metadata,
+        };
+        let mut action_list =
+            RegionMetaActionList::with_action(RegionMetaAction::Change(change.clone()));
+        action_list.set_protocol(ProtocolAction::new());
+
+        let version = 0;
+        store.save(version, &action_list.encode()?).await?;
+
+        let mut builder = RegionManifestDataBuilder::default();
+        builder.apply_change(change);
+
+        Ok(RegionManifestManager {
+            store,
+            last_version: version,
+            manifest: builder.try_build()?,
+        })
+    }
+
+    /// Opens an existing manifest. Returns `None` if the manifest doesn't exist.
+    pub(crate) async fn open(
+        options: RegionManifestOptions,
+    ) -> Result<Option<RegionManifestManager>> {
+        let store = new_store(options);
+        let (reader_version, _) = supported_protocol_version();
+
+        let mut iter = store.scan(0, ManifestVersion::MAX).await?;
+        let mut builder = RegionManifestDataBuilder::default();
+        let mut last_version = None;
+        while let Some((version, bytes)) = iter.next_log().await? {
+            let (action_list, _) = RegionMetaActionList::decode(&bytes, reader_version)?;
+            apply_actions(&mut builder, version, action_list);
+            last_version = Some(version);
+        }
+
+        let Some(last_version) = last_version else {
+            return Ok(None);
+        };
+
+        Ok(Some(RegionManifestManager {
+            store,
+            last_version,
+            manifest: builder.try_build()?,
+        }))
+    }
+
+    /// Persists the `action_list` and applies it to the manifest, returns
+    /// the version of the action list.
+    pub(crate) async fn update(
+        &mut self,
+        mut action_list: RegionMetaActionList,
+    ) -> Result<ManifestVersion> {
+        let version = self.last_version + 1;
+        action_list.set_prev_version(self.last_version);
+        self.store.save(version, &action_list.encode()?).await?;
+
+        let mut builder = RegionManifestDataBuilder::with_checkpoint(Some(self.manifest.clone()));
+        apply_actions(&mut builder, version, action_list);
+        self.manifest = builder.try_build()?;
+        self.last_version = version;
+
+        Ok(version)
+    }
+
+    /// Returns the latest manifest data.
+    pub(crate) fn manifest(&self) -> &RegionManifestData {
+        &self.manifest
+    }
+}
+
+fn new_store(options: RegionManifestOptions) -> ManifestObjectStore {
+    ManifestObjectStore::new(
+        &options.manifest_dir,
+        options.object_store,
+        options.compress_type,
+    )
+}
+
+fn apply_actions(
+    builder: &mut RegionManifestDataBuilder,
+    version: ManifestVersion,
+    action_list: RegionMetaActionList,
+) {
+    for action in action_list.actions {
+        match action {
+            RegionMetaAction::Change(change) => builder.apply_change(change),
+            RegionMetaAction::Edit(edit) => builder.apply_edit(version, edit),
+            RegionMetaAction::Protocol(_) | RegionMetaAction::Remove(_) => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_test_util::temp_dir::create_temp_dir;
+    use datatypes::prelude::ConcreteDataType;
+    use datatypes::schema::ColumnSchema;
+    use object_store::services::Fs;
+    use store_api::storage::RegionId;
+
+    use super::*;
+    use crate::manifest::action::RegionEdit;
+    use crate::metadata::{ColumnMetadata, RegionMetadataBuilder, SemanticType};
+
+    #[tokio::test]
+    async fn test_create_update_open_manifest() {
+        let dir = create_temp_dir("manifest-manager");
+        let mut builder = Fs::default();
+        let _ = builder.root(&dir.path().to_string_lossy());
+        let options = RegionManifestOptions {
+            manifest_dir: "region/manifest/".to_string(),
+            object_store: ObjectStore::new(builder).unwrap().finish(),
+            compress_type: CompressionType::Uncompressed,
+        };
+
+        assert!(RegionManifestManager::open(options.clone())
+            .await
+            .unwrap()
+            .is_none());
+
+        let metadata = RegionMetadataBuilder::new(RegionId::new(1, 1), 0)
+            .add_column_metadata(ColumnMetadata {
+                column_schema: ColumnSchema::new(
+                    "ts",
+                    ConcreteDataType::timestamp_millisecond_datatype(),
+                    false,
+                ),
+                semantic_type: SemanticType::Timestamp,
+                column_id: 1,
+            })
+            .build();
+        let mut manager = RegionManifestManager::new(metadata.clone(), options.clone())
+            .await
+            .unwrap();
+        assert_eq!(metadata, manager.manifest().metadata);
+        assert!(manager.manifest().version.is_none());
+
+        let version = manager
+            .update(RegionMetaActionList::with_action(RegionMetaAction::Edit(
+                RegionEdit {
+                    region_version: 0,
+                    flushed_sequence: Some(5),
+                    files_to_add: vec![],
+                    files_to_remove: vec![],
+                    compaction_time_window: None,
+                },
+            )))
+            .await
+            .unwrap();
+        assert_eq!(1, version);
+
+        let manager = RegionManifestManager::open(options).await.unwrap().unwrap();
+        assert_eq!(metadata, manager.manifest().metadata);
+        let region_version = manager.manifest().version.as_ref().unwrap();
+        assert_eq!(1, region_version.manifest_version);
+        assert_eq!(Some(5), region_version.flushed_sequence);
+    }
+}
@@ -106,7 +106,7 @@ pub struct ObjectStoreLogIterator {
 }
 
 impl ObjectStoreLogIterator {
-    async fn next_log(&mut self) -> Result<Option<(ManifestVersion, Vec<u8>)>> {
+    pub(crate) async fn next_log(&mut self) -> Result<Option<(ManifestVersion, Vec<u8>)>> {
         match self.iter.next() {
             Some((v, entry)) => {
                 let compress_type = file_compress_type(entry.name());
@@ -206,7 +206,7 @@ impl CheckpointMetadata {
 }
 
 impl ManifestObjectStore {
-    async fn scan(
+    pub(crate) async fn scan(
         &self,
         start: ManifestVersion,
         end: ManifestVersion,
@@ -346,7 +346,7 @@ impl ManifestObjectStore {
         Ok(())
     }
 
-    async fn save(&self, version: ManifestVersion, bytes: &[u8]) -> Result<()> {
+    pub(crate) async fn save(&self, version: ManifestVersion, bytes: &[u8]) -> Result<()> {
         let path = self.delta_file_path(version);
         logging::debug!("Save log to manifest storage, version: {}", version);
         let data = self
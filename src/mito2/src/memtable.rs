@@ -0,0 +1,70 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Memtables are write buffers for regions.
+
+pub(crate) mod btree;
+pub(crate) mod version;
+
+use std::fmt;
+use std::sync::Arc;
+
+use common_time::range::TimestampRange;
+use store_api::storage::SequenceNumber;
+
+use crate::error::Result;
+use crate::metadata::RegionMetadataRef;
+use crate::read::{BoxedBatchReader, ReadSchemaRef};
+use crate::worker::request::Mutation;
+
+/// Id for memtables.
+///
+/// Should be unique under the same region.
+pub type MemtableId = u32;
+
+/// In memory write buffer.
+pub trait Memtable: Send + Sync + fmt::Debug {
+    /// Returns the id of this memtable.
+    fn id(&self) -> MemtableId;
+
+    /// Writes rows in the `mutation` with specific `sequence` to the memtable.
+    ///
+    /// The mutation must be built from the same metadata as the memtable.
+    fn write(&self, sequence: SequenceNumber, mutation: &Mutation) -> Result<()>;
+
+    /// Returns the number of rows in the memtable.
+    fn num_rows(&self) -> usize;
+
+    /// Returns a reader to read columns in the `read_schema` of rows in the
+    /// `time_range` whose sequences are not greater than `sequence`.
+    ///
+    /// The reader reads a snapshot of the memtable and is not affected by
+    /// subsequent writes.
+    fn iter(
+        &self,
+        read_schema: &ReadSchemaRef,
+        time_range: &TimestampRange,
+        sequence: Option<SequenceNumber>,
+    ) -> BoxedBatchReader;
+}
+
+pub type MemtableRef = Arc<dyn Memtable>;
+
+/// Builder to build a new [Memtable].
+pub trait MemtableBuilder: Send + Sync + fmt::Debug {
+    /// Builds a new memtable instance.
+    fn build(&self, metadata: &RegionMetadataRef) -> MemtableRef;
+}
+
+pub type MemtableBuilderRef = Arc<dyn MemtableBuilder>;
@@ -0,0 +1,272 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Memtable implementation based on a B-tree.
+
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, RwLock};
+
+use common_time::range::TimestampRange;
+use datatypes::value::Value;
+use store_api::storage::{OpType, SequenceNumber};
+
+use crate::error::Result;
+use crate::memtable::{Memtable, MemtableBuilder, MemtableId, MemtableRef};
+use crate::metadata::RegionMetadataRef;
+use crate::read::{BatchBuilder, BoxedBatchReader, ReadSchemaRef, RowKey, VecBatchReader};
+use crate::worker::request::Mutation;
+
+/// Value of a row in the [BTreeMemtable].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RowValue {
+    pub(crate) op_type: OpType,
+    pub(crate) fields: Vec<Value>,
+}
+
+type RowMap = BTreeMap<RowKey, RowValue>;
+
+/// Max number of rows in a batch returned by the memtable reader.
+const ITER_BATCH_SIZE: usize = 1024;
+
+/// Where to get a column of a row.
+#[derive(Debug, Clone, Copy)]
+enum ColumnSource {
+    /// The n-th column of the primary key.
+    PrimaryKey(usize),
+    /// The time index.
+    Timestamp,
+    /// The n-th field column.
+    Field(usize),
+}
+
+/// A memtable that stores rows in a [BTreeMap].
+///
+/// Writing the same key with the same sequence twice keeps the latter row.
+#[derive(Debug)]
+pub struct BTreeMemtable {
+    id: MemtableId,
+    metadata: RegionMetadataRef,
+    /// Indices of primary key columns in the region metadata.
+    primary_key_indices: Vec<usize>,
+    /// Index of the time index column in the region metadata.
+    time_index: usize,
+    /// Indices of field columns in the region metadata.
+    field_indices: Vec<usize>,
+    rows: RwLock<RowMap>,
+}
+
+impl BTreeMemtable {
+    /// Returns a new empty memtable for the region with specific `metadata`.
+    pub fn new(id: MemtableId, metadata: RegionMetadataRef) -> BTreeMemtable {
+        BTreeMemtable {
+            id,
+            primary_key_indices: metadata.primary_key_indices(),
+            time_index: metadata.time_index_column_index(),
+            field_indices: metadata.field_indices(),
+            metadata,
+            rows: RwLock::new(RowMap::new()),
+        }
+    }
+
+    /// Returns the source of the column with specific `index` in the region metadata.
+    fn column_source(&self, index: usize) -> ColumnSource {
+        if index == self.time_index {
+            return ColumnSource::Timestamp;
+        }
+        if let Some(pos) = self.primary_key_indices.iter().position(|i| *i == index) {
+            return ColumnSource::PrimaryKey(pos);
+        }
+        let pos = self.field_indices.iter().position(|i| *i == index).unwrap();
+        ColumnSource::Field(pos)
+    }
+}
+
+impl Memtable for BTreeMemtable {
+    fn id(&self) -> MemtableId {
+        self.id
+    }
+
+    fn write(&self, sequence: SequenceNumber, mutation: &Mutation) -> Result<()> {
+        debug_assert_eq!(
+            self.metadata.column_metadatas().len(),
+            mutation.columns.len()
+        );
+
+        let columns = &mutation.columns;
+        let mut rows = self.rows.write().unwrap();
+        for row in 0..mutation.num_rows() {
+            let key = RowKey {
+                primary_key: self
+                    .primary_key_indices
+                    .iter()
+                    .map(|index| columns[*index].get(row))
+                    .collect(),
+                timestamp: columns[self.time_index].get(row),
+                sequence: Reverse(sequence),
+            };
+            let value = RowValue {
+                op_type: mutation.op_type,
+                fields: self
+                    .field_indices
+                    .iter()
+                    .map(|index| columns[*index].get(row))
+                    .collect(),
+            };
+            let _ = rows.insert(key, value);
+        }
+
+        Ok(())
+    }
+
+    fn num_rows(&self) -> usize {
+        self.rows.read().unwrap().len()
+    }
+
+    fn iter(
+        &self,
+        read_schema: &ReadSchemaRef,
+        time_range: &TimestampRange,
+        sequence: Option<SequenceNumber>,
+    ) -> BoxedBatchReader {
+        let sources: Vec<_> = read_schema
+            .column_indices()
+            .iter()
+            .map(|index| self.column_source(*index))
+            .collect();
+        let mut builder = BatchBuilder::new(read_schema, ITER_BATCH_SIZE);
+        let mut batches = Vec::new();
+
+        let rows = self.rows.read().unwrap();
+        for (key, value) in rows.iter() {
+            if sequence.map_or(false, |sequence| key.sequence.0 > sequence) {
+                continue;
+            }
+            match &key.timestamp {
+                Value::Timestamp(ts) if time_range.contains(ts) => (),
+                _ => continue,
+            }
+
+            let values = sources.iter().map(|source| match source {
+                ColumnSource::PrimaryKey(pos) => &key.primary_key[*pos],
+                ColumnSource::Timestamp => &key.timestamp,
+                ColumnSource::Field(pos) => &value.fields[*pos],
+            });
+            builder.push_row(values, key.sequence.0, value.op_type);
+            if builder.len() >= ITER_BATCH_SIZE {
+                batches.push(builder.finish());
+            }
+        }
+        if !builder.is_empty() {
+            batches.push(builder.finish());
+        }
+
+        Box::new(VecBatchReader::new(batches))
+    }
+}
+
+/// Builder to build [BTreeMemtable].
+#[derive(Debug, Default)]
+pub struct BTreeMemtableBuilder {
+    next_id: AtomicU32,
+}
+
+impl MemtableBuilder for BTreeMemtableBuilder {
+    fn build(&self, metadata: &RegionMetadataRef) -> MemtableRef {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        Arc::new(BTreeMemtable::new(id, metadata.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use datatypes::prelude::{ConcreteDataType, VectorRef};
+    use datatypes::schema::ColumnSchema;
+    use datatypes::vectors::{Float64Vector, StringVector, TimestampMillisecondVector};
+    use store_api::storage::RegionId;
+
+    use super::*;
+    use crate::metadata::{ColumnMetadata, RegionMetadataBuilder, SemanticType};
+
+    fn new_metadata() -> RegionMetadataRef {
+        let builder = RegionMetadataBuilder::new(RegionId::new(1, 1), 0)
+            .add_column_metadata(ColumnMetadata {
+                column_schema: ColumnSchema::new("host", ConcreteDataType::string_datatype(), true),
+                semantic_type: SemanticType::Tag,
+                column_id: 1,
+            })
+            .add_column_metadata(ColumnMetadata {
+                column_schema: ColumnSchema::new(
+                    "ts",
+                    ConcreteDataType::timestamp_millisecond_datatype(),
+                    false,
+                ),
+                semantic_type: SemanticType::Timestamp,
+                column_id: 2,
+            })
+            .add_column_metadata(ColumnMetadata {
+                column_schema: ColumnSchema::new("cpu", ConcreteDataType::float64_datatype(), true),
+                semantic_type: SemanticType::Field,
+                column_id: 3,
+            });
+        Arc::new(builder.build())
+    }
+
+    fn new_mutation(
+        metadata: &RegionMetadataRef,
+        op_type: OpType,
+        hosts: &[&str],
+        timestamps: &[i64],
+    ) -> Mutation {
+        let cpus: Vec<_> = timestamps.iter().map(|ts| *ts as f64).collect();
+        let columns: HashMap<_, _> = [
+            (
+                "host".to_string(),
+                Arc::new(StringVector::from(hosts.to_vec())) as VectorRef,
+            ),
+            (
+                "ts".to_string(),
+                Arc::new(TimestampMillisecondVector::from_slice(timestamps)) as VectorRef,
+            ),
+            (
+                "cpu".to_string(),
+                Arc::new(Float64Vector::from_vec(cpus)) as VectorRef,
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        Mutation::new(metadata, op_type, columns).unwrap()
+    }
+
+    #[test]
+    fn test_write_memtable() {
+        let metadata = new_metadata();
+        let memtable = BTreeMemtableBuilder::default().build(&metadata);
+        assert_eq!(0, memtable.id());
+
+        let mutation = new_mutation(&metadata, OpType::Put, &["a", "b", "a"], &[1, 1, 1]);
+        memtable.write(1, &mutation).unwrap();
+        // The last row overwrites the first one as they have the same key and sequence.
+        assert_eq!(2, memtable.num_rows());
+
+        let mutation = new_mutation(&metadata, OpType::Delete, &["a"], &[1]);
+        memtable.write(2, &mutation).unwrap();
+        // Rows with different sequences are kept.
+        assert_eq!(3, memtable.num_rows());
+    }
+}
@@ -65,6 +65,77 @@ pub struct RegionMetadata {
 
 pub type RegionMetadataRef = Arc<RegionMetadata>;
 
+impl RegionMetadata {
+    /// Returns the id of the region.
+    pub fn region_id(&self) -> RegionId {
+        self.region_id
+    }
+
+    /// Returns the version of the metadata.
+    pub fn version(&self) -> VersionNumber {
+        self.version
+    }
+
+    /// Returns the schema of the region.
+    pub fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    /// Returns all columns of the region.
+    pub fn column_metadatas(&self) -> &[ColumnMetadata] {
+        &self.column_metadatas
+    }
+
+    /// Returns ids of columns in the primary key.
+    pub fn primary_key(&self) -> &[ColumnId] {
+        &self.primary_key
+    }
+
+    /// Returns the index of the column with specific `column_id`.
+    pub fn column_index_by_id(&self, column_id: ColumnId) -> Option<usize> {
+        self.column_metadatas
+            .iter()
+            .position(|column| column.column_id == column_id)
+    }
+
+    /// Returns the index of the column with specific `name`.
+    pub fn column_index_by_name(&self, name: &str) -> Option<usize> {
+        self.column_metadatas
+            .iter()
+            .position(|column| column.column_schema.name == name)
+    }
+
+    /// Returns the index of the time index column.
+    ///
+    /// # Panics
+    /// Panics if the region doesn't have a time index column, which should be
+    /// checked while creating the region.
+    pub fn time_index_column_index(&self) -> usize {
+        self.column_metadatas
+            .iter()
+            .position(|column| column.semantic_type == SemanticType::Timestamp)
+            .unwrap()
+    }
+
+    /// Returns indices of primary key columns, in the order of the primary key.
+    pub fn primary_key_indices(&self) -> Vec<usize> {
+        self.primary_key
+            .iter()
+            .filter_map(|column_id| self.column_index_by_id(*column_id))
+            .collect()
+    }
+
+    /// Returns indices of field columns.
+    pub fn field_indices(&self) -> Vec<usize> {
+        self.column_metadatas
+            .iter()
+            .enumerate()
+            .filter(|(_, column)| column.semantic_type == SemanticType::Field)
+            .map(|(index, _)| index)
+            .collect()
+    }
+}
+
 impl<'de> Deserialize<'de> for RegionMetadata {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -128,6 +199,13 @@ impl RegionMetadataBuilder {
         self
     }
 
+    /// Sets the primary key of the region, overriding the primary key derived
+    /// from tag columns.
+    pub fn primary_key(mut self, primary_key: Vec<ColumnId>) -> Self {
+        self.primary_key = primary_key;
+        self
+    }
+
     pub fn build(self) -> RegionMetadata {
         let schema = Arc::new(Schema::new(
             self.column_metadatas
@@ -149,15 +227,15 @@ impl RegionMetadataBuilder {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ColumnMetadata {
     /// Schema of this column. Is the same as `column_schema` in [SchemaRef].
-    column_schema: ColumnSchema,
+    pub column_schema: ColumnSchema,
     /// Semantic type of this column (e.g. tag or timestamp).
-    semantic_type: SemanticType,
+    pub semantic_type: SemanticType,
     /// Immutable and unique id of a region.
-    column_id: ColumnId,
+    pub column_id: ColumnId,
 }
 
 /// The semantic type of one column
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub enum SemanticType {
     /// Tag column, also is a part of primary key.
     Tag,
@@ -204,4 +282,15 @@ mod test {
         let deserialized: RegionMetadata = serde_json::from_str(&serialized).unwrap();
         assert_eq!(region_metadata, deserialized);
     }
+
+    #[test]
+    fn test_region_metadata_column_indices() {
+        let region_metadata = build_test_region_metadata();
+        assert_eq!(vec![0], region_metadata.primary_key_indices());
+        assert_eq!(vec![1], region_metadata.field_indices());
+        assert_eq!(2, region_metadata.time_index_column_index());
+        assert_eq!(Some(1), region_metadata.column_index_by_name("b"));
+        assert_eq!(None, region_metadata.column_index_by_name("d"));
+        assert_eq!(Some(2), region_metadata.column_index_by_id(3));
+    }
 }
@@ -0,0 +1,286 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Common structs and utilities for reading data.
+
+pub(crate) mod dedup;
+pub(crate) mod merge;
+pub(crate) mod scan_region;
+
+use std::cmp::Reverse;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use datatypes::prelude::{DataType, ScalarVector, Vector};
+use datatypes::schema::{Schema, SchemaRef};
+use datatypes::value::Value;
+use datatypes::vectors::{MutableVector, UInt64Vector, UInt8Vector, VectorRef};
+use store_api::storage::{OpType, SequenceNumber};
+
+use crate::error::Result;
+use crate::metadata::RegionMetadataRef;
+
+/// Columns to read from a region.
+///
+/// Readers always read the primary key and the time index as they are
+/// required to merge and deduplicate rows.
+#[derive(Debug)]
+pub(crate) struct ReadSchema {
+    metadata: RegionMetadataRef,
+    /// Indices of columns to read in the region metadata, in ascending order.
+    column_indices: Vec<usize>,
+    /// Positions of primary key columns in a [Batch], in the order of the primary key.
+    primary_key_positions: Vec<usize>,
+    /// Position of the time index column in a [Batch].
+    time_index_position: usize,
+    /// Schema of columns to read.
+    schema: SchemaRef,
+}
+
+pub(crate) type ReadSchemaRef = Arc<ReadSchema>;
+
+impl ReadSchema {
+    /// Returns a new [ReadSchema] that reads columns with specific `column_indices`
+    /// in the region metadata, together with the primary key and the time index.
+    pub(crate) fn new(metadata: RegionMetadataRef, column_indices: &[usize]) -> ReadSchema {
+        let primary_key_indices = metadata.primary_key_indices();
+        let time_index = metadata.time_index_column_index();
+        let mut indices: Vec<_> = column_indices
+            .iter()
+            .chain(primary_key_indices.iter())
+            .copied()
+            .chain(std::iter::once(time_index))
+            .collect();
+        indices.sort_unstable();
+        indices.dedup();
+
+        let position_of = |index: usize| indices.iter().position(|i| *i == index).unwrap();
+        let primary_key_positions = primary_key_indices
+            .iter()
+            .map(|index| position_of(*index))
+            .collect();
+        let time_index_position = position_of(time_index);
+        let schema = Arc::new(Schema::new(
+            indices
+                .iter()
+                .map(|index| metadata.column_metadatas()[*index].column_schema.clone())
+                .collect(),
+        ));
+
+        ReadSchema {
+            metadata,
+            column_indices: indices,
+            primary_key_positions,
+            time_index_position,
+            schema,
+        }
+    }
+
+    /// Returns a [ReadSchema] that reads all columns of the region.
+    pub(crate) fn all(metadata: RegionMetadataRef) -> ReadSchema {
+        let indices: Vec<_> = (0..metadata.column_metadatas().len()).collect();
+        ReadSchema::new(metadata, &indices)
+    }
+
+    /// Returns the metadata of the region.
+    pub(crate) fn metadata(&self) -> &RegionMetadataRef {
+        &self.metadata
+    }
+
+    /// Returns indices of columns to read in the region metadata.
+    pub(crate) fn column_indices(&self) -> &[usize] {
+        &self.column_indices
+    }
+
+    /// Returns the position of the column with specific `index` in the region
+    /// metadata in a [Batch].
+    pub(crate) fn position_of(&self, index: usize) -> Option<usize> {
+        self.column_indices.iter().position(|i| *i == index)
+    }
+
+    /// Returns positions of primary key columns in a [Batch].
+    pub(crate) fn primary_key_positions(&self) -> &[usize] {
+        &self.primary_key_positions
+    }
+
+    /// Returns the position of the time index column in a [Batch].
+    pub(crate) fn time_index_position(&self) -> usize {
+        self.time_index_position
+    }
+
+    /// Returns the schema of columns to read.
+    pub(crate) fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+}
+
+/// Key to sort rows of a region.
+///
+/// Rows are sorted by primary key and timestamp in ascending order, and then by
+/// sequence in descending order so the latest version of a row comes first.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct RowKey {
+    pub(crate) primary_key: Vec<Value>,
+    pub(crate) timestamp: Value,
+    pub(crate) sequence: Reverse<SequenceNumber>,
+}
+
+/// Rows read from a region, sorted by primary key and time index in ascending
+/// order and then by sequence in descending order.
+#[derive(Debug, Clone)]
+pub(crate) struct Batch {
+    /// Columns in the order of [ReadSchema::column_indices].
+    pub(crate) columns: Vec<VectorRef>,
+    /// Sequences of rows.
+    pub(crate) sequences: Arc<UInt64Vector>,
+    /// Op types of rows.
+    pub(crate) op_types: Arc<UInt8Vector>,
+}
+
+impl Batch {
+    /// Returns the number of rows in the batch.
+    pub(crate) fn num_rows(&self) -> usize {
+        self.sequences.len()
+    }
+
+    /// Returns true if the batch has no rows.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.num_rows() == 0
+    }
+
+    /// Returns the key of the row at `index`.
+    pub(crate) fn row_key(&self, read_schema: &ReadSchema, index: usize) -> RowKey {
+        RowKey {
+            primary_key: read_schema
+                .primary_key_positions()
+                .iter()
+                .map(|pos| self.columns[*pos].get(index))
+                .collect(),
+            timestamp: self.columns[read_schema.time_index_position()].get(index),
+            sequence: Reverse(self.sequence(index)),
+        }
+    }
+
+    /// Returns values of all columns of the row at `index`.
+    pub(crate) fn row_values(&self, index: usize) -> Vec<Value> {
+        self.columns
+            .iter()
+            .map(|column| column.get(index))
+            .collect()
+    }
+
+    /// Returns the sequence of the row at `index`.
+    pub(crate) fn sequence(&self, index: usize) -> SequenceNumber {
+        self.sequences.get_data(index).unwrap()
+    }
+
+    /// Returns the op type of the row at `index`.
+    pub(crate) fn op_type(&self, index: usize) -> OpType {
+        if self.op_types.get_data(index) == Some(OpType::Delete.as_u8()) {
+            OpType::Delete
+        } else {
+            OpType::Put
+        }
+    }
+}
+
+/// Builder to build a [Batch] row by row.
+pub(crate) struct BatchBuilder {
+    columns: Vec<Box<dyn MutableVector>>,
+    sequences: Vec<SequenceNumber>,
+    op_types: Vec<u8>,
+}
+
+impl BatchBuilder {
+    /// Returns a new builder for batches of `read_schema` with specific `capacity`.
+    pub(crate) fn new(read_schema: &ReadSchema, capacity: usize) -> BatchBuilder {
+        BatchBuilder {
+            columns: read_schema
+                .schema()
+                .column_schemas()
+                .iter()
+                .map(|column| column.data_type.create_mutable_vector(capacity))
+                .collect(),
+            sequences: Vec::with_capacity(capacity),
+            op_types: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Pushes a row.
+    ///
+    /// # Panics
+    /// Panics if the number or the types of `values` mismatch the schema.
+    pub(crate) fn push_row<'a>(
+        &mut self,
+        values: impl IntoIterator<Item = &'a Value>,
+        sequence: SequenceNumber,
+        op_type: OpType,
+    ) {
+        for (column, value) in self.columns.iter_mut().zip(values) {
+            column.push_value_ref(value.as_value_ref());
+        }
+        self.sequences.push(sequence);
+        self.op_types.push(op_type.as_u8());
+    }
+
+    /// Returns the number of rows in the builder.
+    pub(crate) fn len(&self) -> usize {
+        self.sequences.len()
+    }
+
+    /// Returns true if the builder has no rows.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.sequences.is_empty()
+    }
+
+    /// Takes rows in the builder as a [Batch] and resets the builder.
+    pub(crate) fn finish(&mut self) -> Batch {
+        Batch {
+            columns: self.columns.iter_mut().map(|c| c.to_vector()).collect(),
+            sequences: Arc::new(UInt64Vector::from_vec(std::mem::take(&mut self.sequences))),
+            op_types: Arc::new(UInt8Vector::from_vec(std::mem::take(&mut self.op_types))),
+        }
+    }
+}
+
+/// Async reader to read sorted [Batch]es.
+#[async_trait]
+pub(crate) trait BatchReader: Send {
+    /// Fetches the next batch. Returns `None` if the reader is exhausted.
+    ///
+    /// Never returns empty batches.
+    async fn next_batch(&mut self) -> Result<Option<Batch>>;
+}
+
+pub(crate) type BoxedBatchReader = Box<dyn BatchReader>;
+
+/// Reader that yields batches from a vector.
+pub(crate) struct VecBatchReader {
+    batches: std::vec::IntoIter<Batch>,
+}
+
+impl VecBatchReader {
+    pub(crate) fn new(batches: Vec<Batch>) -> VecBatchReader {
+        VecBatchReader {
+            batches: batches.into_iter(),
+        }
+    }
+}
+
+#[async_trait]
+impl BatchReader for VecBatchReader {
+    async fn next_batch(&mut self) -> Result<Option<Batch>> {
+        Ok(self.batches.find(|batch| !batch.is_empty()))
+    }
+}
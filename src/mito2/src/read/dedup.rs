@@ -0,0 +1,150 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reader to deduplicate rows with the same key.
+
+use async_trait::async_trait;
+use datatypes::value::Value;
+use store_api::storage::{OpType, SequenceNumber};
+
+use crate::error::Result;
+use crate::read::{Batch, BatchBuilder, BatchReader, BoxedBatchReader, ReadSchemaRef};
+
+/// Reader that keeps the latest visible row of each primary key and timestamp
+/// and removes deleted rows.
+///
+/// Rows from the source must be sorted by [RowKey](crate::read::RowKey).
+pub(crate) struct DedupReader {
+    read_schema: ReadSchemaRef,
+    source: BoxedBatchReader,
+    /// Rows with sequences greater than this sequence are invisible.
+    sequence: Option<SequenceNumber>,
+    /// Primary key and timestamp of the last row deduplicated.
+    last_key: Option<(Vec<Value>, Value)>,
+}
+
+impl DedupReader {
+    /// Returns a new reader that deduplicates rows from the `source`.
+    pub(crate) fn new(
+        read_schema: ReadSchemaRef,
+        source: BoxedBatchReader,
+        sequence: Option<SequenceNumber>,
+    ) -> DedupReader {
+        DedupReader {
+            read_schema,
+            source,
+            sequence,
+            last_key: None,
+        }
+    }
+
+    /// Removes duplicate and deleted rows from the `batch`.
+    fn dedup_batch(&mut self, batch: &Batch) -> Batch {
+        let mut builder = BatchBuilder::new(&self.read_schema, batch.num_rows());
+        for index in 0..batch.num_rows() {
+            let sequence = batch.sequence(index);
+            if self.sequence.map_or(false, |visible| sequence > visible) {
+                continue;
+            }
+
+            let key = batch.row_key(&self.read_schema, index);
+            let key = Some((key.primary_key, key.timestamp));
+            if key == self.last_key {
+                // The latest version of this row is already handled.
+                continue;
+            }
+            self.last_key = key;
+
+            let op_type = batch.op_type(index);
+            if op_type == OpType::Delete {
+                continue;
+            }
+            builder.push_row(&batch.row_values(index), sequence, op_type);
+        }
+
+        builder.finish()
+    }
+}
+
+#[async_trait]
+impl BatchReader for DedupReader {
+    async fn next_batch(&mut self) -> Result<Option<Batch>> {
+        while let Some(batch) = self.source.next_batch().await? {
+            let batch = self.dedup_batch(&batch);
+            if !batch.is_empty() {
+                return Ok(Some(batch));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use store_api::storage::RegionId;
+
+    use super::*;
+    use crate::read::merge::MergeReader;
+    use crate::read::{ReadSchema, VecBatchReader};
+    use crate::test_util::{collect_rows, new_batch, CreateRequestBuilder};
+
+    fn new_merge_dedup_reader(sequence: Option<SequenceNumber>) -> BoxedBatchReader {
+        let metadata = CreateRequestBuilder::new(RegionId::new(1, 1)).build_metadata();
+        let read_schema = Arc::new(ReadSchema::all(metadata));
+        let source1 = VecBatchReader::new(vec![
+            new_batch(
+                &read_schema,
+                &[("a", 1, 1, OpType::Put), ("a", 2, 1, OpType::Put)],
+            ),
+            new_batch(&read_schema, &[("b", 1, 1, OpType::Put)]),
+        ]);
+        let source2 = VecBatchReader::new(vec![new_batch(
+            &read_schema,
+            &[
+                ("a", 1, 3, OpType::Put),
+                ("a", 2, 2, OpType::Delete),
+                ("c", 1, 4, OpType::Put),
+            ],
+        )]);
+        let reader = MergeReader::new(
+            read_schema.clone(),
+            vec![Box::new(source1), Box::new(source2)],
+        );
+        let reader = DedupReader::new(read_schema, Box::new(reader), sequence);
+
+        Box::new(reader)
+    }
+
+    #[tokio::test]
+    async fn test_merge_dedup() {
+        let reader = new_merge_dedup_reader(None);
+        let rows = collect_rows(reader).await;
+        assert_eq!(
+            vec![
+                ("a".to_string(), 1, 3),
+                ("b".to_string(), 1, 1),
+                ("c".to_string(), 1, 4),
+            ],
+            rows
+        );
+
+        // Rows with greater sequences are invisible.
+        let reader = new_merge_dedup_reader(Some(2));
+        let rows = collect_rows(reader).await;
+        assert_eq!(vec![("a".to_string(), 1, 1), ("b".to_string(), 1, 1)], rows);
+    }
+}
@@ -0,0 +1,160 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Merge reader to merge sorted batches from multiple sources.
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+
+use async_trait::async_trait;
+
+use crate::error::Result;
+use crate::read::{Batch, BatchBuilder, BatchReader, BoxedBatchReader, ReadSchemaRef, RowKey};
+
+/// Default number of rows in a merged batch.
+const DEFAULT_BATCH_SIZE: usize = 1024;
+
+/// Reader that merges rows from multiple sorted sources into sorted batches.
+///
+/// The reader keeps all rows, including duplicate and deleted rows.
+pub(crate) struct MergeReader {
+    read_schema: ReadSchemaRef,
+    /// Sources to initialize.
+    uninitialized: Vec<BoxedBatchReader>,
+    /// Sources that are not exhausted, ordered by their current rows.
+    nodes: BinaryHeap<Reverse<Node>>,
+    batch_size: usize,
+}
+
+impl MergeReader {
+    /// Returns a new reader that merges rows from `sources`.
+    pub(crate) fn new(read_schema: ReadSchemaRef, sources: Vec<BoxedBatchReader>) -> MergeReader {
+        MergeReader {
+            read_schema,
+            uninitialized: sources,
+            nodes: BinaryHeap::new(),
+            batch_size: DEFAULT_BATCH_SIZE,
+        }
+    }
+
+    /// Fetches the first batch of each source.
+    async fn init_nodes(&mut self) -> Result<()> {
+        for source in std::mem::take(&mut self.uninitialized) {
+            if let Some(node) = Node::new(source, &self.read_schema).await? {
+                self.nodes.push(Reverse(node));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl BatchReader for MergeReader {
+    async fn next_batch(&mut self) -> Result<Option<Batch>> {
+        self.init_nodes().await?;
+
+        let mut builder = BatchBuilder::new(&self.read_schema, self.batch_size);
+        while builder.len() < self.batch_size {
+            let Some(Reverse(mut node)) = self.nodes.pop() else {
+                break;
+            };
+            node.push_current_row(&mut builder);
+            if node.advance(&self.read_schema).await? {
+                self.nodes.push(Reverse(node));
+            }
+        }
+
+        if builder.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(builder.finish()))
+        }
+    }
+}
+
+/// A source and its current row.
+struct Node {
+    source: BoxedBatchReader,
+    batch: Batch,
+    /// Index of the current row in the batch.
+    index: usize,
+    /// Key of the current row.
+    key: RowKey,
+}
+
+impl Node {
+    /// Returns a node positioned at the first row of the `source`, or `None`
+    /// if the source is empty.
+    async fn new(
+        mut source: BoxedBatchReader,
+        read_schema: &ReadSchemaRef,
+    ) -> Result<Option<Node>> {
+        let Some(batch) = source.next_batch().await? else {
+            return Ok(None);
+        };
+        let key = batch.row_key(read_schema, 0);
+
+        Ok(Some(Node {
+            source,
+            batch,
+            index: 0,
+            key,
+        }))
+    }
+
+    /// Pushes the current row into the `builder`.
+    fn push_current_row(&self, builder: &mut BatchBuilder) {
+        builder.push_row(
+            &self.batch.row_values(self.index),
+            self.batch.sequence(self.index),
+            self.batch.op_type(self.index),
+        );
+    }
+
+    /// Moves to the next row. Returns false if the source is exhausted.
+    async fn advance(&mut self, read_schema: &ReadSchemaRef) -> Result<bool> {
+        self.index += 1;
+        if self.index >= self.batch.num_rows() {
+            let Some(batch) = self.source.next_batch().await? else {
+                return Ok(false);
+            };
+            self.batch = batch;
+            self.index = 0;
+        }
+        self.key = self.batch.row_key(read_schema, self.index);
+
+        Ok(true)
+    }
+}
+
+impl PartialEq for Node {
+    fn eq(&self, other: &Node) -> bool {
+        self.key == other.key
+    }
+}
+
+impl Eq for Node {}
+
+impl PartialOrd for Node {
+    fn partial_cmp(&self, other: &Node) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Node {
+    fn cmp(&self, other: &Node) -> Ordering {
+        self.key.cmp(&other.key)
+    }
+}
@@ -0,0 +1,304 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Scans a region according to the scan request.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use async_stream::try_stream;
+use common_error::ext::BoxedError;
+use common_query::logical_plan::Expr;
+use common_recordbatch::error::ExternalSnafu;
+use common_recordbatch::{RecordBatch, RecordBatchStreamAdaptor, SendableRecordBatchStream};
+use common_telemetry::logging;
+use common_time::range::TimestampRange;
+use datatypes::arrow::array::{Array, BooleanArray};
+use datatypes::arrow::compute::filter_record_batch;
+use datatypes::arrow::record_batch::RecordBatch as DfRecordBatch;
+use datatypes::schema::{Schema, SchemaRef};
+use futures::StreamExt;
+use object_store::ObjectStore;
+use snafu::{ensure, ResultExt};
+use storage::sst::FileMeta;
+use store_api::storage::ScanRequest;
+use table::predicate::{Predicate, TimeRangePredicateBuilder};
+
+use crate::error::{
+    BuildPredicateSnafu, EvalFilterSnafu, FilterRecordBatchSnafu, InvalidRequestSnafu,
+    NewRecordBatchSnafu, Result,
+};
+use crate::metadata::SemanticType;
+use crate::read::dedup::DedupReader;
+use crate::read::merge::MergeReader;
+use crate::read::{Batch, BatchReader, BoxedBatchReader, ReadSchema, ReadSchemaRef};
+use crate::region::version::VersionRef;
+use crate::sst::parquet::ParquetReaderBuilder;
+use crate::sst::sst_file_path;
+
+/// Helper to scan a version of a region.
+///
+/// Reads memtables and SSTs of the version, merges rows from them, keeps the
+/// latest row of each primary key and timestamp and then applies filters and
+/// projection of the request.
+pub(crate) struct ScanRegion {
+    version: VersionRef,
+    region_dir: String,
+    object_store: ObjectStore,
+    request: ScanRequest,
+}
+
+impl ScanRegion {
+    /// Returns a new [ScanRegion] to scan the `version` of the region under `region_dir`.
+    pub(crate) fn new(
+        version: VersionRef,
+        region_dir: String,
+        object_store: ObjectStore,
+        request: ScanRequest,
+    ) -> ScanRegion {
+        ScanRegion {
+            version,
+            region_dir,
+            object_store,
+            request,
+        }
+    }
+
+    /// Scans the region and returns a stream of record batches.
+    pub(crate) async fn scan(self) -> Result<SendableRecordBatchStream> {
+        let metadata = &self.version.metadata;
+        let num_columns = metadata.column_metadatas().len();
+        let projection = match &self.request.projection {
+            Some(projection) => {
+                ensure!(
+                    projection.iter().all(|index| *index < num_columns),
+                    InvalidRequestSnafu {
+                        region_id: metadata.region_id(),
+                        reason: format!(
+                            "invalid projection {projection:?}, region has {num_columns} columns"
+                        ),
+                    }
+                );
+                projection.clone()
+            }
+            None => (0..num_columns).collect(),
+        };
+
+        let read_schema = Arc::new(ReadSchema::new(
+            metadata.clone(),
+            &self.columns_to_read(&projection),
+        ));
+        let time_range = self.build_time_range();
+        let predicate = Predicate::try_new(self.row_key_filters(), metadata.schema().clone())
+            .context(BuildPredicateSnafu {
+                region_id: metadata.region_id(),
+            })?;
+
+        let mut sources = Vec::new();
+        for memtable in self.version.memtables.memtables() {
+            sources.push(memtable.iter(&read_schema, &time_range, self.request.sequence));
+        }
+        let mut num_files = 0;
+        for file in &self.version.files {
+            if !file_in_range(file, &time_range) {
+                continue;
+            }
+            let reader = ParquetReaderBuilder::new(
+                sst_file_path(&self.region_dir, file.file_id),
+                self.object_store.clone(),
+                read_schema.clone(),
+            )
+            .predicate(predicate.clone())
+            .build()
+            .await?;
+            sources.push(Box::new(reader) as BoxedBatchReader);
+            num_files += 1;
+        }
+        logging::debug!(
+            "Scan region {}, time range: {:?}, memtables: {}, files: {}",
+            metadata.region_id(),
+            time_range,
+            sources.len() - num_files,
+            num_files
+        );
+
+        let reader = MergeReader::new(read_schema.clone(), sources);
+        let mut reader =
+            DedupReader::new(read_schema.clone(), Box::new(reader), self.request.sequence);
+        let converter = BatchConverter::new(read_schema, &self.request, &projection)?;
+        let output_schema = converter.output_schema.clone();
+        let stream = try_stream! {
+            while let Some(batch) = reader.next_batch().await? {
+                if let Some(record_batch) = converter.convert(&batch)? {
+                    yield record_batch;
+                }
+            }
+        };
+        let stream = stream
+            .map(|res: Result<RecordBatch>| res.map_err(BoxedError::new).context(ExternalSnafu));
+
+        Ok(Box::pin(RecordBatchStreamAdaptor {
+            schema: output_schema,
+            stream: Box::pin(stream),
+            output_ordering: None,
+        }))
+    }
+
+    /// Returns indices of columns that are projected or referenced by filters.
+    fn columns_to_read(&self, projection: &[usize]) -> Vec<usize> {
+        let metadata = &self.version.metadata;
+        let mut columns: HashSet<_> = projection.iter().copied().collect();
+        for filter in &self.request.filters {
+            match filter.df_expr().to_columns() {
+                Ok(referenced) => columns.extend(
+                    referenced
+                        .iter()
+                        .filter_map(|column| metadata.column_index_by_name(&column.name)),
+                ),
+                // Reads all columns if we can't tell which columns the filter uses.
+                Err(_) => return (0..metadata.column_metadatas().len()).collect(),
+            }
+        }
+
+        columns.into_iter().collect()
+    }
+
+    /// Returns filters that only reference primary key and time index columns.
+    ///
+    /// SSTs are pruned before rows are deduplicated, so pruning by fields might skip
+    /// the latest version of a row and return an older one. Field filters are applied
+    /// to deduplicated rows instead.
+    fn row_key_filters(&self) -> Vec<Expr> {
+        let metadata = &self.version.metadata;
+        self.request
+            .filters
+            .iter()
+            .filter(|filter| {
+                let Ok(columns) = filter.df_expr().to_columns() else {
+                    return false;
+                };
+                columns.iter().all(|column| {
+                    metadata
+                        .column_index_by_name(&column.name)
+                        .map_or(false, |index| {
+                            metadata.column_metadatas()[index].semantic_type != SemanticType::Field
+                        })
+                })
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Builds the time range to read from filters.
+    fn build_time_range(&self) -> TimestampRange {
+        let metadata = &self.version.metadata;
+        let ts_column = &metadata.column_metadatas()[metadata.time_index_column_index()];
+        let unit = ts_column
+            .column_schema
+            .data_type
+            .as_timestamp()
+            .expect("Timestamp column must have timestamp-compatible type")
+            .unit();
+        TimeRangePredicateBuilder::new(&ts_column.column_schema.name, unit, &self.request.filters)
+            .build()
+    }
+}
+
+/// Returns true if the time range of the SST `file` intersects the `time_range`.
+fn file_in_range(file: &FileMeta, time_range: &TimestampRange) -> bool {
+    if time_range == &TimestampRange::min_to_max() {
+        return true;
+    }
+    // The end timestamp of the file is inclusive.
+    let Some((start, end)) = file.time_range else {
+        return true;
+    };
+    TimestampRange::new_inclusive(Some(start), Some(end)).intersects(time_range)
+}
+
+/// Converts deduplicated [Batch]es into record batches of the scan output.
+struct BatchConverter {
+    /// Arrow schema of batches to convert.
+    read_arrow_schema: datatypes::arrow::datatypes::SchemaRef,
+    /// Filters evaluated against batches.
+    predicate: Predicate,
+    /// Positions of projected columns in batches.
+    positions: Vec<usize>,
+    output_schema: SchemaRef,
+}
+
+impl BatchConverter {
+    fn new(
+        read_schema: ReadSchemaRef,
+        request: &ScanRequest,
+        projection: &[usize],
+    ) -> Result<BatchConverter> {
+        let metadata = read_schema.metadata();
+        let predicate = Predicate::try_new(request.filters.clone(), read_schema.schema().clone())
+            .context(BuildPredicateSnafu {
+            region_id: metadata.region_id(),
+        })?;
+        let positions = projection
+            .iter()
+            .map(|index| read_schema.position_of(*index).unwrap())
+            .collect();
+        let output_schema = Arc::new(Schema::new(
+            projection
+                .iter()
+                .map(|index| metadata.column_metadatas()[*index].column_schema.clone())
+                .collect(),
+        ));
+
+        Ok(BatchConverter {
+            read_arrow_schema: read_schema.schema().arrow_schema().clone(),
+            predicate,
+            positions,
+            output_schema,
+        })
+    }
+
+    /// Filters and projects the `batch`. Returns `None` if all rows are filtered out.
+    fn convert(&self, batch: &Batch) -> Result<Option<RecordBatch>> {
+        let columns = batch
+            .columns
+            .iter()
+            .map(|column| column.to_arrow_array())
+            .collect();
+        let mut df_batch = DfRecordBatch::try_new(self.read_arrow_schema.clone(), columns)
+            .context(FilterRecordBatchSnafu)?;
+
+        for expr in self.predicate.exprs() {
+            let mask = expr
+                .evaluate(&df_batch)
+                .context(EvalFilterSnafu)?
+                .into_array(df_batch.num_rows());
+            // Filters are only hints for the scan, so we ignore filters that
+            // don't evaluate to booleans and leave them to the query engine.
+            let Some(mask) = mask.as_any().downcast_ref::<BooleanArray>() else {
+                continue;
+            };
+            df_batch = filter_record_batch(&df_batch, mask).context(FilterRecordBatchSnafu)?;
+        }
+        if df_batch.num_rows() == 0 {
+            return Ok(None);
+        }
+
+        let df_batch = df_batch
+            .project(&self.positions)
+            .context(FilterRecordBatchSnafu)?;
+        RecordBatch::try_from_df_record_batch(self.output_schema.clone(), df_batch)
+            .context(NewRecordBatchSnafu)
+            .map(Some)
+    }
+}
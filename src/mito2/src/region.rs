@@ -14,28 +14,71 @@
 
 //! Mito region.
 
-mod version;
+pub(crate) mod version;
 
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 
 use store_api::storage::RegionId;
 
+use crate::manifest::manager::RegionManifestManager;
 use crate::region::version::VersionControlRef;
 pub type VersionNumber = u32;
 
 /// Metadata and runtime status of a region.
 #[derive(Debug)]
 pub(crate) struct MitoRegion {
-    version_control: VersionControlRef,
+    pub(crate) region_id: RegionId,
+    /// Directory of the region, where SSTs are stored.
+    pub(crate) region_dir: String,
+    pub(crate) version_control: VersionControlRef,
+    /// Manager to maintain manifest for this region.
+    manifest_manager: RegionManifestManager,
 }
 
 pub(crate) type MitoRegionRef = Arc<MitoRegion>;
 
+impl MitoRegion {
+    /// Returns a new region.
+    pub(crate) fn new(
+        region_id: RegionId,
+        region_dir: String,
+        version_control: VersionControlRef,
+        manifest_manager: RegionManifestManager,
+    ) -> MitoRegion {
+        MitoRegion {
+            region_id,
+            region_dir,
+            version_control,
+            manifest_manager,
+        }
+    }
+}
+
 /// Regions indexed by ids.
 #[derive(Debug, Default)]
 pub(crate) struct RegionMap {
     regions: RwLock<HashMap<RegionId, MitoRegionRef>>,
 }
 
+impl RegionMap {
+    /// Returns true if the region exists.
+    pub(crate) fn is_region_exists(&self, region_id: RegionId) -> bool {
+        let regions = self.regions.read().unwrap();
+        regions.contains_key(&region_id)
+    }
+
+    /// Inserts a new region into the map.
+    pub(crate) fn insert_region(&self, region: MitoRegionRef) {
+        let mut regions = self.regions.write().unwrap();
+        let _ = regions.insert(region.region_id, region);
+    }
+
+    /// Gets region by region id.
+    pub(crate) fn get_region(&self, region_id: RegionId) -> Option<MitoRegionRef> {
+        let regions = self.regions.read().unwrap();
+        regions.get(&region_id).cloned()
+    }
+}
+
 pub(crate) type RegionMapRef = Arc<RegionMap>;
@@ -23,10 +23,65 @@
 //! Reason: data may be flushed/compacted and some data with old sequence may be removed
 //! and became invisible between step 1 and 2, so need to acquire version at first.
 
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
+use arc_swap::ArcSwap;
+use storage::sst::FileMeta;
+use store_api::storage::SequenceNumber;
+
+use crate::memtable::version::MemtableVersionRef;
+use crate::metadata::RegionMetadataRef;
+
 /// Controls version of in memory metadata for a region.
 #[derive(Debug)]
-pub(crate) struct VersionControl {}
+pub(crate) struct VersionControl {
+    /// Latest version.
+    version: ArcSwap<Version>,
+    /// Sequence number of the last committed data.
+    committed_sequence: AtomicU64,
+}
+
+impl VersionControl {
+    /// Returns a new [VersionControl] with specific `version` and `committed_sequence`.
+    pub(crate) fn new(version: Version, committed_sequence: SequenceNumber) -> VersionControl {
+        VersionControl {
+            version: ArcSwap::new(Arc::new(version)),
+            committed_sequence: AtomicU64::new(committed_sequence),
+        }
+    }
+
+    /// Returns current version.
+    pub(crate) fn current(&self) -> VersionRef {
+        self.version.load_full()
+    }
+
+    /// Returns the sequence number of the last committed data.
+    pub(crate) fn committed_sequence(&self) -> SequenceNumber {
+        self.committed_sequence.load(Ordering::Relaxed)
+    }
+
+    /// Sets the sequence number of the last committed data.
+    ///
+    /// Only the region worker should update the committed sequence.
+    pub(crate) fn set_committed_sequence(&self, sequence: SequenceNumber) {
+        self.committed_sequence.store(sequence, Ordering::Relaxed);
+    }
+}
 
 pub(crate) type VersionControlRef = Arc<VersionControl>;
+
+/// Immutable snapshot of the metadata and data of a region.
+#[derive(Debug)]
+pub(crate) struct Version {
+    /// Metadata of the region.
+    pub(crate) metadata: RegionMetadataRef,
+    /// Memtables of the region.
+    pub(crate) memtables: MemtableVersionRef,
+    /// SSTs of the region.
+    pub(crate) files: Vec<FileMeta>,
+    /// Data with sequence less than or equal to this sequence are flushed to SSTs.
+    pub(crate) flushed_sequence: SequenceNumber,
+}
+
+pub(crate) type VersionRef = Arc<Version>;
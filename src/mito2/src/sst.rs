@@ -0,0 +1,61 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Sorted strings tables.
+
+pub(crate) mod parquet;
+
+use std::sync::Arc;
+
+use common_time::Timestamp;
+use datatypes::arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use object_store::util::normalize_dir;
+use storage::sst::FileId;
+use store_api::storage::consts::{OP_TYPE_COLUMN_NAME, SEQUENCE_COLUMN_NAME};
+
+use crate::metadata::RegionMetadata;
+
+/// Information of a written SST.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct SstInfo {
+    /// Time range of rows in the SST, inclusive.
+    pub(crate) time_range: (Timestamp, Timestamp),
+    /// Size of the file in bytes.
+    pub(crate) file_size: u64,
+    /// Number of rows in the file.
+    pub(crate) num_rows: usize,
+}
+
+/// Returns the path of the SST file with specific `file_id` under the `region_dir`.
+pub(crate) fn sst_file_path(region_dir: &str, file_id: FileId) -> String {
+    format!("{}{}", normalize_dir(region_dir), file_id.as_parquet())
+}
+
+/// Returns the arrow schema of SSTs of the region.
+///
+/// SSTs store all columns of the region in the order of the region metadata,
+/// followed by the sequence and the op type of each row.
+pub(crate) fn sst_arrow_schema(metadata: &RegionMetadata) -> SchemaRef {
+    let region_schema = metadata.schema().arrow_schema();
+    let fields = region_schema
+        .fields()
+        .iter()
+        .map(|field| Field::clone(field))
+        .chain([
+            Field::new(SEQUENCE_COLUMN_NAME, DataType::UInt64, false),
+            Field::new(OP_TYPE_COLUMN_NAME, DataType::UInt8, false),
+        ])
+        .collect::<Vec<_>>();
+    Arc::new(Schema::new(fields))
+}
@@ -0,0 +1,382 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parquet reader and writer for SSTs.
+
+use std::sync::Arc;
+
+use async_compat::CompatExt;
+use async_trait::async_trait;
+use common_time::Timestamp;
+use datatypes::arrow::record_batch::RecordBatch;
+use datatypes::value::Value;
+use datatypes::vectors::{Helper, UInt64Vector, UInt8Vector};
+use futures::stream::BoxStream;
+use futures::{StreamExt, TryStreamExt};
+use object_store::ObjectStore;
+use parquet::arrow::{ArrowWriter, ParquetRecordBatchStreamBuilder, ProjectionMask};
+use parquet::basic::{Compression, Encoding, ZstdLevel};
+use parquet::file::properties::WriterProperties;
+use parquet::schema::types::ColumnPath;
+use snafu::{ensure, OptionExt, ResultExt};
+use store_api::storage::consts::SEQUENCE_COLUMN_NAME;
+use table::predicate::Predicate;
+use tokio::io::BufReader;
+
+use crate::error::{
+    ConvertVectorSnafu, InvalidParquetSnafu, OpenDalSnafu, ReadParquetSnafu, Result,
+    WriteParquetSnafu,
+};
+use crate::metadata::RegionMetadataRef;
+use crate::read::{Batch, BatchReader, BoxedBatchReader, ReadSchemaRef};
+use crate::sst::{sst_arrow_schema, SstInfo};
+
+/// Default number of rows in a row group.
+const DEFAULT_ROW_GROUP_SIZE: usize = 4096;
+
+/// Parquet SST writer.
+pub(crate) struct ParquetWriter<'a> {
+    file_path: &'a str,
+    metadata: RegionMetadataRef,
+    /// Reader that yields batches of all columns in the region.
+    source: BoxedBatchReader,
+    object_store: ObjectStore,
+}
+
+impl<'a> ParquetWriter<'a> {
+    /// Returns a new writer that writes batches from the `source` to `file_path`.
+    pub(crate) fn new(
+        file_path: &'a str,
+        metadata: RegionMetadataRef,
+        source: BoxedBatchReader,
+        object_store: ObjectStore,
+    ) -> ParquetWriter<'a> {
+        ParquetWriter {
+            file_path,
+            metadata,
+            source,
+            object_store,
+        }
+    }
+
+    /// Writes all batches from the source to the SST.
+    ///
+    /// Returns `None` and writes nothing if the source has no rows.
+    pub(crate) async fn write_all(mut self) -> Result<Option<SstInfo>> {
+        let arrow_schema = sst_arrow_schema(&self.metadata);
+        let time_index = self.metadata.time_index_column_index();
+        let time_index_name = arrow_schema.field(time_index).name().clone();
+        let props = WriterProperties::builder()
+            .set_compression(Compression::ZSTD(ZstdLevel::default()))
+            .set_max_row_group_size(DEFAULT_ROW_GROUP_SIZE)
+            .set_column_encoding(
+                ColumnPath::new(vec![SEQUENCE_COLUMN_NAME.to_string()]),
+                Encoding::DELTA_BINARY_PACKED,
+            )
+            .set_column_dictionary_enabled(
+                ColumnPath::new(vec![SEQUENCE_COLUMN_NAME.to_string()]),
+                false,
+            )
+            .set_column_encoding(
+                ColumnPath::new(vec![time_index_name]),
+                Encoding::DELTA_BINARY_PACKED,
+            )
+            .build();
+
+        let mut buffer = Vec::new();
+        let mut writer = ArrowWriter::try_new(&mut buffer, arrow_schema.clone(), Some(props))
+            .context(WriteParquetSnafu {
+                path: self.file_path,
+            })?;
+        let mut time_range: Option<(Timestamp, Timestamp)> = None;
+        let mut num_rows = 0;
+        while let Some(batch) = self.source.next_batch().await? {
+            let timestamps = &batch.columns[time_index];
+            for i in 0..timestamps.len() {
+                if let Value::Timestamp(ts) = timestamps.get(i) {
+                    time_range = Some(match time_range {
+                        Some((start, end)) => (start.min(ts), end.max(ts)),
+                        None => (ts, ts),
+                    });
+                }
+            }
+            num_rows += batch.num_rows();
+
+            let columns = batch
+                .columns
+                .iter()
+                .map(|column| column.to_arrow_array())
+                .chain([
+                    batch.sequences.to_arrow_array(),
+                    batch.op_types.to_arrow_array(),
+                ])
+                .collect();
+            let record_batch =
+                RecordBatch::try_new(arrow_schema.clone(), columns).map_err(|e| {
+                    InvalidParquetSnafu {
+                        path: self.file_path,
+                        reason: format!("invalid batch to write: {e}"),
+                    }
+                    .build()
+                })?;
+            writer.write(&record_batch).context(WriteParquetSnafu {
+                path: self.file_path,
+            })?;
+        }
+        let _ = writer.close().context(WriteParquetSnafu {
+            path: self.file_path,
+        })?;
+
+        let Some(time_range) = time_range else {
+            return Ok(None);
+        };
+        let file_size = buffer.len() as u64;
+        self.object_store
+            .write(self.file_path, buffer)
+            .await
+            .context(OpenDalSnafu)?;
+
+        Ok(Some(SstInfo {
+            time_range,
+            file_size,
+            num_rows,
+        }))
+    }
+}
+
+/// Builder to build a [ParquetReader].
+pub(crate) struct ParquetReaderBuilder {
+    file_path: String,
+    object_store: ObjectStore,
+    read_schema: ReadSchemaRef,
+    predicate: Option<Predicate>,
+}
+
+impl ParquetReaderBuilder {
+    /// Returns a new builder that reads columns in `read_schema` from `file_path`.
+    pub(crate) fn new(
+        file_path: String,
+        object_store: ObjectStore,
+        read_schema: ReadSchemaRef,
+    ) -> ParquetReaderBuilder {
+        ParquetReaderBuilder {
+            file_path,
+            object_store,
+            read_schema,
+            predicate: None,
+        }
+    }
+
+    /// Uses the `predicate` to prune row groups.
+    ///
+    /// The predicate must be built from the schema of the region. Row groups are pruned
+    /// before rows are deduplicated, so the predicate should only contain filters on
+    /// primary key and time index columns.
+    pub(crate) fn predicate(mut self, predicate: Predicate) -> Self {
+        self.predicate = Some(predicate);
+        self
+    }
+
+    /// Opens the file and builds a [ParquetReader].
+    pub(crate) async fn build(self) -> Result<ParquetReader> {
+        let file_path = self.file_path;
+        let reader = self
+            .object_store
+            .reader(&file_path)
+            .await
+            .context(OpenDalSnafu)?
+            .compat();
+        let builder = ParquetRecordBatchStreamBuilder::new(BufReader::new(reader))
+            .await
+            .context(ReadParquetSnafu { path: &file_path })?;
+
+        let num_region_columns = self.read_schema.metadata().column_metadatas().len();
+        let num_sst_columns = builder.schema().fields().len();
+        ensure!(
+            num_sst_columns == num_region_columns + 2,
+            InvalidParquetSnafu {
+                path: &file_path,
+                reason: format!(
+                    "expect {} columns, but found {}",
+                    num_region_columns + 2,
+                    num_sst_columns
+                ),
+            }
+        );
+
+        let row_groups: Vec<_> = match &self.predicate {
+            Some(predicate) => predicate
+                .prune_row_groups(builder.metadata().row_groups())
+                .into_iter()
+                .enumerate()
+                .filter_map(|(index, valid)| valid.then_some(index))
+                .collect(),
+            None => (0..builder.metadata().num_row_groups()).collect(),
+        };
+        // The sequence and op type columns are always the last two columns.
+        let projection = self
+            .read_schema
+            .column_indices()
+            .iter()
+            .copied()
+            .chain([num_region_columns, num_region_columns + 1]);
+        let projection_mask = ProjectionMask::roots(
+            builder.metadata().file_metadata().schema_descr(),
+            projection,
+        );
+        let stream = builder
+            .with_projection(projection_mask)
+            .with_row_groups(row_groups)
+            .build()
+            .context(ReadParquetSnafu { path: &file_path })?;
+        let path = file_path.clone();
+        let stream = stream
+            .map(move |res| res.context(ReadParquetSnafu { path: &path }))
+            .boxed();
+
+        Ok(ParquetReader { file_path, stream })
+    }
+}
+
+/// Reader to read [Batch]es from a parquet SST.
+pub(crate) struct ParquetReader {
+    file_path: String,
+    stream: BoxStream<'static, Result<RecordBatch>>,
+}
+
+impl ParquetReader {
+    /// Converts a record batch read from the SST to a [Batch].
+    fn convert_record_batch(&self, record_batch: &RecordBatch) -> Result<Batch> {
+        let num_columns = record_batch.num_columns();
+        let corrupted = || InvalidParquetSnafu {
+            path: &self.file_path,
+            reason: "invalid sequence or op type column",
+        };
+        let columns = Helper::try_into_vectors(&record_batch.columns()[..num_columns - 2])
+            .context(ConvertVectorSnafu)?;
+        let sequences = UInt64Vector::try_from_arrow_array(record_batch.column(num_columns - 2))
+            .ok()
+            .with_context(corrupted)?;
+        let op_types = UInt8Vector::try_from_arrow_array(record_batch.column(num_columns - 1))
+            .ok()
+            .with_context(corrupted)?;
+
+        Ok(Batch {
+            columns,
+            sequences: Arc::new(sequences),
+            op_types: Arc::new(op_types),
+        })
+    }
+}
+
+#[async_trait]
+impl BatchReader for ParquetReader {
+    async fn next_batch(&mut self) -> Result<Option<Batch>> {
+        while let Some(record_batch) = self.stream.try_next().await? {
+            if record_batch.num_rows() == 0 {
+                continue;
+            }
+            return self.convert_record_batch(&record_batch).map(Some);
+        }
+
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_test_util::temp_dir::create_temp_dir;
+    use object_store::services::Fs;
+    use store_api::storage::{OpType, RegionId};
+
+    use super::*;
+    use crate::read::{ReadSchema, VecBatchReader};
+    use crate::test_util::{collect_rows, new_batch, CreateRequestBuilder};
+
+    fn new_object_store(root: &str) -> ObjectStore {
+        let mut builder = Fs::default();
+        let _ = builder.root(root);
+        ObjectStore::new(builder).unwrap().finish()
+    }
+
+    #[tokio::test]
+    async fn test_write_read_parquet() {
+        let dir = create_temp_dir("write-read-parquet");
+        let object_store = new_object_store(dir.path().to_str().unwrap());
+        let metadata = CreateRequestBuilder::new(RegionId::new(1, 1)).build_metadata();
+        let read_schema = Arc::new(ReadSchema::all(metadata.clone()));
+        let source = VecBatchReader::new(vec![
+            new_batch(
+                &read_schema,
+                &[("a", 1, 2, OpType::Put), ("a", 3, 1, OpType::Delete)],
+            ),
+            new_batch(&read_schema, &[("b", 2, 3, OpType::Put)]),
+        ]);
+
+        let writer = ParquetWriter::new(
+            "test.parquet",
+            metadata.clone(),
+            Box::new(source),
+            object_store.clone(),
+        );
+        let info = writer.write_all().await.unwrap().unwrap();
+        assert_eq!(
+            (Timestamp::new_millisecond(1), Timestamp::new_millisecond(3)),
+            info.time_range
+        );
+        assert_eq!(3, info.num_rows);
+
+        let reader = ParquetReaderBuilder::new(
+            "test.parquet".to_string(),
+            object_store.clone(),
+            read_schema,
+        )
+        .build()
+        .await
+        .unwrap();
+        let rows = collect_rows(Box::new(reader)).await;
+        assert_eq!(
+            vec![
+                ("a".to_string(), 1, 2),
+                ("a".to_string(), 3, 1),
+                ("b".to_string(), 2, 3),
+            ],
+            rows
+        );
+
+        // Only reads the primary key and the time index.
+        let read_schema = Arc::new(ReadSchema::new(metadata.clone(), &[]));
+        let mut reader = ParquetReaderBuilder::new(
+            "test.parquet".to_string(),
+            object_store.clone(),
+            read_schema,
+        )
+        .build()
+        .await
+        .unwrap();
+        let batch = reader.next_batch().await.unwrap().unwrap();
+        assert_eq!(2, batch.columns.len());
+        assert_eq!(OpType::Delete, batch.op_type(1));
+
+        // Writes nothing if the source is empty.
+        let writer = ParquetWriter::new(
+            "empty.parquet",
+            metadata,
+            Box::new(VecBatchReader::new(Vec::new())),
+            object_store.clone(),
+        );
+        assert!(writer.write_all().await.unwrap().is_none());
+        assert!(!object_store.is_exist("empty.parquet").await.unwrap());
+    }
+}
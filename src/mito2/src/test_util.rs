@@ -14,23 +14,35 @@
 
 //! Utilities for testing.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use common_test_util::temp_dir::{create_temp_dir, TempDir};
+use common_time::Timestamp;
+use datatypes::prelude::{ConcreteDataType, VectorRef};
+use datatypes::schema::ColumnSchema;
+use datatypes::value::Value;
+use datatypes::vectors::{Float64Vector, StringVector, TimestampMillisecondVector};
 use log_store::raft_engine::log_store::RaftEngineLogStore;
 use log_store::test_util::log_store_util;
 use object_store::services::Fs;
 use object_store::util::join_dir;
 use object_store::ObjectStore;
+use store_api::storage::{OpType, RegionId, SequenceNumber};
 
 use crate::config::MitoConfig;
-use crate::engine::MitoEngine;
+use crate::engine::{CreateRequest, MitoEngine, RegionOptions};
+use crate::metadata::{ColumnMetadata, RegionMetadataBuilder, RegionMetadataRef, SemanticType};
+use crate::read::{Batch, BatchBuilder, BoxedBatchReader, ReadSchema};
 use crate::worker::WorkerGroup;
 
 /// Env to test mito engine.
 pub struct TestEnv {
     /// Path to store data.
     data_home: TempDir,
+    // Stores used by the last created engine.
+    log_store: Option<Arc<RaftEngineLogStore>>,
+    object_store: Option<ObjectStore>,
 }
 
 impl TestEnv {
@@ -38,14 +50,38 @@ impl TestEnv {
     pub fn new(prefix: &str) -> TestEnv {
         TestEnv {
             data_home: create_temp_dir(prefix),
+            log_store: None,
+            object_store: None,
         }
     }
 
     /// Creates a new engine with specific config under this env.
-    pub async fn create_engine(&self, config: MitoConfig) -> MitoEngine {
+    pub async fn create_engine(&mut self, config: MitoConfig) -> MitoEngine {
         let (log_store, object_store) = self.create_log_and_object_store().await;
+        let log_store = Arc::new(log_store);
+        self.log_store = Some(log_store.clone());
+        self.object_store = Some(object_store.clone());
 
-        MitoEngine::new(config, Arc::new(log_store), object_store)
+        MitoEngine::new(config, log_store, object_store)
+    }
+
+    /// Stops the `engine` and creates a new engine with the same stores.
+    ///
+    /// # Panics
+    /// Panics if the env hasn't created an engine.
+    pub async fn reopen_engine(&self, engine: MitoEngine, config: MitoConfig) -> MitoEngine {
+        engine.stop().await.unwrap();
+
+        MitoEngine::new(
+            config,
+            self.log_store.clone().unwrap(),
+            self.object_store.clone().unwrap(),
+        )
+    }
+
+    /// Returns the object store used by the last created engine.
+    pub fn get_object_store(&self) -> Option<ObjectStore> {
+        self.object_store.clone()
     }
 
     /// Creates a new [WorkerGroup] with specific config under this env.
@@ -68,3 +104,145 @@ impl TestEnv {
         (log_store, object_store)
     }
 }
+
+/// Builder to mock a [CreateRequest].
+///
+/// The region has a tag column `tag_0`, a field column `field_0` and a
+/// time index column `ts`.
+pub struct CreateRequestBuilder {
+    region_id: RegionId,
+    region_dir: String,
+    create_if_not_exists: bool,
+}
+
+impl CreateRequestBuilder {
+    /// Returns a new builder for region with specific `region_id`.
+    pub fn new(region_id: RegionId) -> CreateRequestBuilder {
+        CreateRequestBuilder {
+            region_id,
+            region_dir: format!("test-{}", region_id.as_u64()),
+            create_if_not_exists: false,
+        }
+    }
+
+    /// Sets whether to create the region if it doesn't exist.
+    pub fn create_if_not_exists(mut self, value: bool) -> Self {
+        self.create_if_not_exists = value;
+        self
+    }
+
+    /// Returns the directory of the region.
+    pub fn region_dir(&self) -> &str {
+        &self.region_dir
+    }
+
+    pub fn build(&self) -> CreateRequest {
+        let column_metadatas = vec![
+            ColumnMetadata {
+                column_schema: ColumnSchema::new(
+                    "tag_0",
+                    ConcreteDataType::string_datatype(),
+                    true,
+                ),
+                semantic_type: SemanticType::Tag,
+                column_id: 1,
+            },
+            ColumnMetadata {
+                column_schema: ColumnSchema::new(
+                    "field_0",
+                    ConcreteDataType::float64_datatype(),
+                    true,
+                ),
+                semantic_type: SemanticType::Field,
+                column_id: 2,
+            },
+            ColumnMetadata {
+                column_schema: ColumnSchema::new(
+                    "ts",
+                    ConcreteDataType::timestamp_millisecond_datatype(),
+                    false,
+                ),
+                semantic_type: SemanticType::Timestamp,
+                column_id: 3,
+            },
+        ];
+
+        CreateRequest {
+            region_id: self.region_id,
+            region_dir: self.region_dir.clone(),
+            column_metadatas,
+            primary_key: vec![1],
+            create_if_not_exists: self.create_if_not_exists,
+            options: RegionOptions::default(),
+        }
+    }
+
+    /// Returns the metadata of the region to create.
+    pub(crate) fn build_metadata(&self) -> RegionMetadataRef {
+        let request = self.build();
+        let mut builder = RegionMetadataBuilder::new(request.region_id, 0);
+        for column in request.column_metadatas {
+            builder = builder.add_column_metadata(column);
+        }
+        Arc::new(builder.primary_key(request.primary_key).build())
+    }
+}
+
+/// Builds columns to write to the region created by [CreateRequestBuilder].
+///
+/// The value of `field_0` is the timestamp of each row.
+pub fn build_columns(tags: &[&str], timestamps: &[i64]) -> HashMap<String, VectorRef> {
+    let fields: Vec<_> = timestamps.iter().map(|ts| *ts as f64).collect();
+    HashMap::from([
+        (
+            "tag_0".to_string(),
+            Arc::new(StringVector::from(tags.to_vec())) as VectorRef,
+        ),
+        (
+            "field_0".to_string(),
+            Arc::new(Float64Vector::from_vec(fields)) as VectorRef,
+        ),
+        (
+            "ts".to_string(),
+            Arc::new(TimestampMillisecondVector::from_slice(timestamps)) as VectorRef,
+        ),
+    ])
+}
+
+/// Builds a [Batch] of all columns in the region created by [CreateRequestBuilder].
+///
+/// Each row is a tuple of `(tag_0, ts, sequence, op_type)` and the value of
+/// `field_0` is the timestamp of the row.
+pub(crate) fn new_batch(
+    read_schema: &ReadSchema,
+    rows: &[(&str, i64, SequenceNumber, OpType)],
+) -> Batch {
+    let mut builder = BatchBuilder::new(read_schema, rows.len());
+    for (tag, ts, sequence, op_type) in rows {
+        let values = [
+            Value::from(*tag),
+            Value::from(*ts as f64),
+            Value::Timestamp(Timestamp::new_millisecond(*ts)),
+        ];
+        builder.push_row(&values, *sequence, *op_type);
+    }
+    builder.finish()
+}
+
+/// Reads all rows from a `reader` of all columns in the region created by
+/// [CreateRequestBuilder], returns `(tag_0, ts, sequence)` of each row.
+pub(crate) async fn collect_rows(
+    mut reader: BoxedBatchReader,
+) -> Vec<(String, i64, SequenceNumber)> {
+    let mut rows = Vec::new();
+    while let Some(batch) = reader.next_batch().await.unwrap() {
+        for index in 0..batch.num_rows() {
+            let values = batch.row_values(index);
+            let (Value::String(tag), Value::Timestamp(ts)) = (&values[0], &values[2]) else {
+                unreachable!()
+            };
+            rows.push((tag.as_utf8().to_string(), ts.value(), batch.sequence(index)));
+        }
+    }
+    rows
+}
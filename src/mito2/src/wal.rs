@@ -0,0 +1,381 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Write ahead log of the engine.
+
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::sync::Arc;
+
+use common_error::ext::BoxedError;
+use datatypes::arrow::ipc::reader::StreamReader;
+use datatypes::arrow::ipc::writer::StreamWriter;
+use datatypes::arrow::record_batch::RecordBatch;
+use datatypes::vectors::Helper;
+use futures::stream::BoxStream;
+use futures::{stream, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use snafu::{ensure, Location, ResultExt};
+use store_api::logstore::entry::{Entry, Id as EntryId};
+use store_api::logstore::LogStore;
+use store_api::storage::OpType;
+
+use crate::error::{
+    ConvertVectorSnafu, DecodeWalSnafu, EncodeWalSnafu, Error, ReadWalSnafu, Result,
+    SerdeJsonSnafu, WalDataCorruptedSnafu, WriteWalSnafu,
+};
+use crate::metadata::RegionMetadataRef;
+use crate::worker::request::Mutation;
+
+/// Stream of decoded WAL entries of a region.
+pub(crate) type WalEntryStream<'a> = BoxStream<'a, Result<(EntryId, Vec<Mutation>)>>;
+
+/// Write ahead log.
+///
+/// All regions in the engine share the same WAL, each region writes to its
+/// own namespace.
+#[derive(Debug)]
+pub(crate) struct Wal<S> {
+    store: Arc<S>,
+}
+
+impl<S> Clone for Wal<S> {
+    fn clone(&self) -> Self {
+        Self {
+            store: self.store.clone(),
+        }
+    }
+}
+
+impl<S: LogStore> Wal<S> {
+    /// Returns a new [Wal] based on the `store`.
+    pub(crate) fn new(store: Arc<S>) -> Self {
+        Self { store }
+    }
+
+    /// Returns a writer to write entries of multiple regions in a batch.
+    pub(crate) fn writer(&self) -> WalWriter<S> {
+        WalWriter {
+            store: self.store.clone(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Scans entries of the region with specific `metadata` whose ids are
+    /// greater than or equal to `start_id`.
+    ///
+    /// Mutations in the entries are converted to the schema of `metadata`.
+    pub(crate) async fn scan(
+        &self,
+        metadata: RegionMetadataRef,
+        start_id: EntryId,
+    ) -> Result<WalEntryStream<'_>> {
+        let region_id = metadata.region_id();
+        let namespace = self.store.namespace(region_id.into());
+        let stream = self
+            .store
+            .read(&namespace, start_id)
+            .await
+            .map_err(BoxedError::new)
+            .context(ReadWalSnafu { region_id })?
+            .map_err(move |e| Error::ReadWal {
+                region_id,
+                location: Location::default(),
+                source: BoxedError::new(e),
+            })
+            .map_ok(move |entries| {
+                let metadata = metadata.clone();
+                stream::iter(
+                    entries
+                        .into_iter()
+                        .map(move |entry| decode_entry(&metadata, &entry)),
+                )
+            })
+            .try_flatten();
+
+        Ok(Box::pin(stream))
+    }
+}
+
+/// Writer to write entries to the [Wal] in a batch.
+pub(crate) struct WalWriter<S: LogStore> {
+    store: Arc<S>,
+    entries: Vec<S::Entry>,
+}
+
+impl<S: LogStore> WalWriter<S> {
+    /// Adds an entry with specific `entry_id` that holds `mutations` to the
+    /// region with specific `metadata`.
+    pub(crate) fn add_entry(
+        &mut self,
+        metadata: &RegionMetadataRef,
+        entry_id: EntryId,
+        mutations: &[Mutation],
+    ) -> Result<()> {
+        let mut buf = Vec::new();
+        encode_entry(metadata, mutations, &mut buf)?;
+
+        let namespace = self.store.namespace(metadata.region_id().into());
+        self.entries
+            .push(self.store.entry(&buf, entry_id, namespace));
+
+        Ok(())
+    }
+
+    /// Writes all added entries to the log store.
+    pub(crate) async fn write_to_wal(self) -> Result<()> {
+        self.store
+            .append_batch(self.entries)
+            .await
+            .map_err(BoxedError::new)
+            .context(WriteWalSnafu)
+    }
+}
+
+/// Header of a WAL entry.
+#[derive(Debug, Serialize, Deserialize)]
+struct EntryHeader {
+    /// Op types of mutations in the entry.
+    op_types: Vec<u8>,
+}
+
+/// Size of the header length.
+const HEADER_LEN_SIZE: usize = std::mem::size_of::<u32>();
+
+/// Data format:
+///
+/// ```text
+/// +-------------------+------------------------+-------------------------------------+
+/// | Header Len (u32)  | Header (json, op types)| Payload (Arrow IPC, one per mutation)|
+/// +-------------------+------------------------+-------------------------------------+
+/// ```
+fn encode_entry(
+    metadata: &RegionMetadataRef,
+    mutations: &[Mutation],
+    buf: &mut Vec<u8>,
+) -> Result<()> {
+    let region_id = metadata.region_id();
+    let header = EntryHeader {
+        op_types: mutations.iter().map(|m| m.op_type.as_u8()).collect(),
+    };
+    let header = serde_json::to_vec(&header).context(SerdeJsonSnafu)?;
+    buf.extend_from_slice(&(header.len() as u32).to_le_bytes());
+    buf.extend_from_slice(&header);
+
+    let arrow_schema = metadata.schema().arrow_schema();
+    let mut writer =
+        StreamWriter::try_new(buf, arrow_schema).context(EncodeWalSnafu { region_id })?;
+    for mutation in mutations {
+        let arrays = mutation
+            .columns
+            .iter()
+            .map(|column| column.to_arrow_array())
+            .collect();
+        let batch = RecordBatch::try_new(arrow_schema.clone(), arrays)
+            .context(EncodeWalSnafu { region_id })?;
+        writer.write(&batch).context(EncodeWalSnafu { region_id })?;
+    }
+    writer.finish().context(EncodeWalSnafu { region_id })?;
+
+    Ok(())
+}
+
+/// Decodes the `entry` written by [encode_entry].
+fn decode_entry<E: Entry>(
+    metadata: &RegionMetadataRef,
+    entry: &E,
+) -> Result<(EntryId, Vec<Mutation>)> {
+    let region_id = metadata.region_id();
+    let data = entry.data();
+    ensure!(
+        data.len() >= HEADER_LEN_SIZE,
+        WalDataCorruptedSnafu {
+            region_id,
+            message: format!("entry {} is too short", entry.id()),
+        }
+    );
+    let header_len = u32::from_le_bytes(data[..HEADER_LEN_SIZE].try_into().unwrap()) as usize;
+    let payload_start = HEADER_LEN_SIZE + header_len;
+    ensure!(
+        data.len() >= payload_start,
+        WalDataCorruptedSnafu {
+            region_id,
+            message: format!(
+                "expect header of entry {} ends at {}, actual buffer length {}",
+                entry.id(),
+                payload_start,
+                data.len()
+            ),
+        }
+    );
+    let header: EntryHeader =
+        serde_json::from_slice(&data[HEADER_LEN_SIZE..payload_start]).context(SerdeJsonSnafu)?;
+
+    let reader = StreamReader::try_new(Cursor::new(&data[payload_start..]), None)
+        .context(DecodeWalSnafu { region_id })?;
+    let num_mutations = header.op_types.len();
+    let mut mutations = Vec::with_capacity(num_mutations);
+    for (batch, op_type) in reader.zip(header.op_types) {
+        let batch = batch.context(DecodeWalSnafu { region_id })?;
+        let op_type = match op_type {
+            0 => OpType::Delete,
+            1 => OpType::Put,
+            _ => {
+                return WalDataCorruptedSnafu {
+                    region_id,
+                    message: format!("unexpected op type {op_type}"),
+                }
+                .fail()
+            }
+        };
+        let columns = batch
+            .schema()
+            .fields()
+            .iter()
+            .zip(batch.columns())
+            .map(|(field, array)| {
+                let vector = Helper::try_into_vector(array).context(ConvertVectorSnafu)?;
+                Ok((field.name().clone(), vector))
+            })
+            .collect::<Result<HashMap<_, _>>>()?;
+
+        mutations.push(Mutation::new(metadata, op_type, columns)?);
+    }
+    ensure!(
+        mutations.len() == num_mutations,
+        WalDataCorruptedSnafu {
+            region_id,
+            message: format!(
+                "expect {} mutations in entry {}, but got {}",
+                num_mutations,
+                entry.id(),
+                mutations.len()
+            ),
+        }
+    );
+
+    Ok((entry.id(), mutations))
+}
+
+#[cfg(test)]
+mod tests {
+    use common_test_util::temp_dir::create_temp_dir;
+    use datatypes::prelude::{ConcreteDataType, VectorRef};
+    use datatypes::schema::ColumnSchema;
+    use datatypes::vectors::{Int64Vector, TimestampMillisecondVector};
+    use log_store::test_util::log_store_util;
+    use store_api::storage::RegionId;
+
+    use super::*;
+    use crate::metadata::{ColumnMetadata, RegionMetadataBuilder, SemanticType};
+
+    fn new_metadata(region_id: RegionId) -> RegionMetadataRef {
+        let builder = RegionMetadataBuilder::new(region_id, 0)
+            .add_column_metadata(ColumnMetadata {
+                column_schema: ColumnSchema::new(
+                    "ts",
+                    ConcreteDataType::timestamp_millisecond_datatype(),
+                    false,
+                ),
+                semantic_type: SemanticType::Timestamp,
+                column_id: 1,
+            })
+            .add_column_metadata(ColumnMetadata {
+                column_schema: ColumnSchema::new("v", ConcreteDataType::int64_datatype(), true),
+                semantic_type: SemanticType::Field,
+                column_id: 2,
+            });
+        Arc::new(builder.build())
+    }
+
+    fn new_mutation(metadata: &RegionMetadataRef, op_type: OpType, ts: &[i64]) -> Mutation {
+        let columns = HashMap::from([
+            (
+                "ts".to_string(),
+                Arc::new(TimestampMillisecondVector::from_slice(ts)) as VectorRef,
+            ),
+            (
+                "v".to_string(),
+                Arc::new(Int64Vector::from_slice(ts)) as VectorRef,
+            ),
+        ]);
+        Mutation::new(metadata, op_type, columns).unwrap()
+    }
+
+    fn assert_mutations_eq(expect: &[Mutation], actual: &[Mutation]) {
+        assert_eq!(expect.len(), actual.len());
+        for (expect, actual) in expect.iter().zip(actual) {
+            assert_eq!(expect.op_type, actual.op_type);
+            assert_eq!(expect.columns, actual.columns);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_write_scan_wal() {
+        let dir = create_temp_dir("wal");
+        let log_store =
+            log_store_util::create_tmp_local_file_log_store(dir.path().to_str().unwrap()).await;
+        let wal = Wal::new(Arc::new(log_store));
+
+        let metadata1 = new_metadata(RegionId::new(1, 1));
+        let metadata2 = new_metadata(RegionId::new(1, 2));
+        let entry1 = vec![
+            new_mutation(&metadata1, OpType::Put, &[1, 2]),
+            new_mutation(&metadata1, OpType::Delete, &[1]),
+        ];
+        let entry2 = vec![new_mutation(&metadata1, OpType::Put, &[3])];
+        let entry3 = vec![new_mutation(&metadata2, OpType::Put, &[4, 5, 6])];
+
+        let mut writer = wal.writer();
+        writer.add_entry(&metadata1, 1, &entry1).unwrap();
+        writer.add_entry(&metadata2, 1, &entry3).unwrap();
+        writer.write_to_wal().await.unwrap();
+        let mut writer = wal.writer();
+        writer.add_entry(&metadata1, 2, &entry2).unwrap();
+        writer.write_to_wal().await.unwrap();
+
+        let entries: Vec<_> = wal
+            .scan(metadata1.clone(), 1)
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(2, entries.len());
+        assert_eq!(1, entries[0].0);
+        assert_mutations_eq(&entry1, &entries[0].1);
+        assert_eq!(2, entries[1].0);
+        assert_mutations_eq(&entry2, &entries[1].1);
+
+        let entries: Vec<_> = wal
+            .scan(metadata1.clone(), 2)
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(1, entries.len());
+        assert_mutations_eq(&entry2, &entries[0].1);
+
+        let entries: Vec<_> = wal
+            .scan(metadata2.clone(), 1)
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(1, entries.len());
+        assert_mutations_eq(&entry3, &entries[0].1);
+    }
+}
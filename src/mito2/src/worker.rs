@@ -16,6 +16,7 @@
 
 mod handle_create;
 mod handle_open;
+mod handle_write;
 pub(crate) mod request;
 
 use std::collections::hash_map::DefaultHasher;
@@ -23,19 +24,29 @@ use std::hash::{Hash, Hasher};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
+use common_datasource::compression::CompressionType;
 use common_runtime::JoinHandle;
 use common_telemetry::logging;
 use futures::future::try_join_all;
+use object_store::util::join_dir;
 use object_store::ObjectStore;
 use snafu::{ensure, ResultExt};
+use storage::sst::FileMeta;
 use store_api::logstore::LogStore;
-use store_api::storage::RegionId;
+use store_api::storage::{RegionId, SequenceNumber};
 use tokio::sync::mpsc::{Receiver, Sender};
-use tokio::sync::{mpsc, Mutex};
+use tokio::sync::{mpsc, oneshot, Mutex};
 
 use crate::config::MitoConfig;
 use crate::error::{JoinSnafu, Result, WorkerStoppedSnafu};
-use crate::region::{RegionMap, RegionMapRef};
+use crate::manifest::manager::{RegionManifestManager, RegionManifestOptions};
+use crate::memtable::btree::BTreeMemtableBuilder;
+use crate::memtable::version::MemtableVersion;
+use crate::memtable::MemtableBuilderRef;
+use crate::metadata::RegionMetadataRef;
+use crate::region::version::{Version, VersionControl};
+use crate::region::{MitoRegion, MitoRegionRef, RegionMap, RegionMapRef};
+use crate::wal::Wal;
 use crate::worker::request::{RegionRequest, RequestBody, WorkerRequest};
 
 /// Identifier for a worker.
@@ -126,6 +137,11 @@ impl WorkerGroup {
             .await
     }
 
+    /// Returns the region with specific `region_id` if it is opened.
+    pub(crate) fn get_region(&self, region_id: RegionId) -> Option<MitoRegionRef> {
+        self.worker(region_id).regions.get_region(region_id)
+    }
+
     /// Get worker for specific `region_id`.
     fn worker(&self, region_id: RegionId) -> &RegionWorker {
         let mut hasher = DefaultHasher::new();
@@ -182,8 +198,9 @@ impl RegionWorker {
             id: config.id,
             regions: regions.clone(),
             receiver,
-            log_store,
+            wal: Wal::new(log_store),
             object_store,
+            memtable_builder: Arc::new(BTreeMemtableBuilder::default()),
             running: running.clone(),
             request_batch_size: config.request_batch_size,
         };
@@ -270,17 +287,19 @@ struct RegionWorkerLoop<S> {
     regions: RegionMapRef,
     /// Request receiver.
     receiver: Receiver<WorkerRequest>,
-    // TODO(yingwen): Replaced by Wal.
-    log_store: Arc<S>,
+    /// WAL of the engine.
+    wal: Wal<S>,
     /// Object store for manifest and SSTs.
     object_store: ObjectStore,
+    /// Builder to build memtables for regions.
+    memtable_builder: MemtableBuilderRef,
     /// Whether the worker thread is still running.
     running: Arc<AtomicBool>,
     /// Batch size to fetch requests from channel.
     request_batch_size: usize,
 }
 
-impl<S> RegionWorkerLoop<S> {
+impl<S: LogStore> RegionWorkerLoop<S> {
     /// Starts the worker loop.
     async fn run(&mut self) {
         logging::info!("Start region worker thread {}", self.id);
@@ -350,9 +369,7 @@ impl<S> RegionWorkerLoop<S> {
             return;
         }
 
-        // Create a write context that holds meta and sequence.
-
-        unimplemented!()
+        self.handle_write_requests(write_requests).await;
     }
 
     /// Takes and handles all ddl requests.
@@ -368,12 +385,53 @@ impl<S> RegionWorkerLoop<S> {
                 RequestBody::Write(_) => unreachable!(),
             };
 
-            if let Some(sender) = request.sender {
-                // Ignore send result.
-                let _ = sender.send(res);
-            }
+            send_result(request.sender, res);
+        }
+    }
+
+    /// Returns options to create or open the manifest of the region under `region_dir`.
+    fn manifest_options(&self, region_dir: &str) -> RegionManifestOptions {
+        RegionManifestOptions {
+            manifest_dir: join_dir(region_dir, "manifest"),
+            object_store: self.object_store.clone(),
+            compress_type: CompressionType::Uncompressed,
         }
     }
+
+    /// Returns a new region under `region_dir` with an empty mutable memtable and
+    /// specific SST `files`.
+    fn new_region(
+        &self,
+        metadata: RegionMetadataRef,
+        region_dir: &str,
+        manifest_manager: RegionManifestManager,
+        flushed_sequence: SequenceNumber,
+        files: Vec<FileMeta>,
+    ) -> MitoRegionRef {
+        let mutable = self.memtable_builder.build(&metadata);
+        let version = Version {
+            metadata: metadata.clone(),
+            memtables: Arc::new(MemtableVersion::new(mutable)),
+            files,
+            flushed_sequence,
+        };
+        let version_control = Arc::new(VersionControl::new(version, flushed_sequence));
+
+        Arc::new(MitoRegion::new(
+            metadata.region_id(),
+            region_dir.to_string(),
+            version_control,
+            manifest_manager,
+        ))
+    }
+}
+
+/// Sends the result of a request to its sender.
+fn send_result(sender: Option<oneshot::Sender<Result<()>>>, res: Result<()>) {
+    if let Some(sender) = sender {
+        // Ignore send result.
+        let _ = sender.send(res);
+    }
 }
 
 #[cfg(test)]
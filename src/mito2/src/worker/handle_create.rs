@@ -14,17 +14,55 @@
 
 //! Handling create request.
 
-use crate::error::Result;
+use std::sync::Arc;
+
+use snafu::ensure;
+use store_api::logstore::LogStore;
+
+use crate::error::{RegionExistsSnafu, Result};
+use crate::manifest::manager::RegionManifestManager;
+use crate::metadata::RegionMetadataBuilder;
 use crate::worker::request::CreateRequest;
 use crate::worker::RegionWorkerLoop;
 
-impl<S> RegionWorkerLoop<S> {
-    pub(crate) async fn handle_create_request(&mut self, _request: CreateRequest) -> Result<()> {
+impl<S: LogStore> RegionWorkerLoop<S> {
+    pub(crate) async fn handle_create_request(&mut self, request: CreateRequest) -> Result<()> {
         // 1. Checks whether the table exists.
+        if self.regions.is_region_exists(request.region_id) {
+            ensure!(
+                request.create_if_not_exists,
+                RegionExistsSnafu {
+                    region_id: request.region_id,
+                }
+            );
+
+            return Ok(());
+        }
 
         // 2. Convert the request into RegionMetadata
+        request.validate()?;
+        let mut builder = RegionMetadataBuilder::new(request.region_id, 0);
+        for column in request.column_metadatas {
+            builder = builder.add_column_metadata(column);
+        }
+        let metadata = Arc::new(builder.primary_key(request.primary_key).build());
 
         // 3. Write manifest
-        unimplemented!()
+        let manifest_manager = RegionManifestManager::new(
+            metadata.as_ref().clone(),
+            self.manifest_options(&request.region_dir),
+        )
+        .await?;
+
+        let region = self.new_region(
+            metadata,
+            &request.region_dir,
+            manifest_manager,
+            0,
+            Vec::new(),
+        );
+        self.regions.insert_region(region);
+
+        Ok(())
     }
 }
@@ -14,12 +14,96 @@
 
 //! Handling open request.
 
-use crate::error::Result;
+use std::sync::Arc;
+
+use common_telemetry::logging;
+use futures::TryStreamExt;
+use snafu::OptionExt;
+use store_api::logstore::LogStore;
+use store_api::storage::SequenceNumber;
+
+use crate::error::{RegionNotFoundSnafu, Result};
+use crate::manifest::manager::RegionManifestManager;
+use crate::region::MitoRegion;
 use crate::worker::request::OpenRequest;
 use crate::worker::RegionWorkerLoop;
 
-impl<S> RegionWorkerLoop<S> {
-    pub(crate) async fn handle_open_request(&mut self, _request: OpenRequest) -> Result<()> {
-        unimplemented!()
+impl<S: LogStore> RegionWorkerLoop<S> {
+    pub(crate) async fn handle_open_request(&mut self, request: OpenRequest) -> Result<()> {
+        if self.regions.is_region_exists(request.region_id) {
+            return Ok(());
+        }
+
+        let manifest_manager =
+            RegionManifestManager::open(self.manifest_options(&request.region_dir))
+                .await?
+                .context(RegionNotFoundSnafu {
+                    region_id: request.region_id,
+                })?;
+        let manifest = manifest_manager.manifest();
+        let metadata = Arc::new(manifest.metadata.clone());
+        let flushed_sequence = manifest
+            .version
+            .as_ref()
+            .and_then(|version| version.flushed_sequence)
+            .unwrap_or(0);
+        let files = manifest
+            .version
+            .as_ref()
+            .map(|version| version.files.values().cloned().collect())
+            .unwrap_or_default();
+        let committed_sequence = manifest.committed_sequence.max(flushed_sequence);
+
+        let region = self.new_region(
+            metadata,
+            &request.region_dir,
+            manifest_manager,
+            flushed_sequence,
+            files,
+        );
+        self.replay_wal(&region, committed_sequence).await?;
+        self.regions.insert_region(region);
+
+        Ok(())
+    }
+
+    /// Replays WAL entries after the flushed sequence of the `region` into its
+    /// mutable memtable and updates the committed sequence.
+    async fn replay_wal(
+        &self,
+        region: &MitoRegion,
+        mut committed_sequence: SequenceNumber,
+    ) -> Result<()> {
+        let version = region.version_control.current();
+        let mutable = version.memtables.mutable_memtable();
+        let mut num_entries = 0;
+        let mut num_rows = 0;
+
+        let mut stream = self
+            .wal
+            .scan(version.metadata.clone(), version.flushed_sequence + 1)
+            .await?;
+        while let Some((entry_id, mutations)) = stream.try_next().await? {
+            for mutation in &mutations {
+                mutable.write(entry_id, mutation)?;
+                num_rows += mutation.num_rows();
+            }
+            num_entries += 1;
+            committed_sequence = committed_sequence.max(entry_id);
+        }
+        region
+            .version_control
+            .set_committed_sequence(committed_sequence);
+
+        logging::info!(
+            "Replay WAL for region {}, flushed sequence: {}, committed sequence: {}, entries: {}, rows: {}",
+            region.region_id,
+            version.flushed_sequence,
+            committed_sequence,
+            num_entries,
+            num_rows
+        );
+
+        Ok(())
     }
 }
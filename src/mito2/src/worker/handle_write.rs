@@ -0,0 +1,155 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Handling write requests.
+
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_telemetry::logging;
+use snafu::ResultExt;
+use store_api::logstore::LogStore;
+use store_api::storage::SequenceNumber;
+use tokio::sync::oneshot::Sender;
+
+use crate::error::{Error, RegionNotFoundSnafu, Result, WriteGroupSnafu};
+use crate::region::version::VersionRef;
+use crate::region::MitoRegionRef;
+use crate::wal::WalWriter;
+use crate::worker::request::{Mutation, RegionRequest, RequestBody};
+use crate::worker::{send_result, RegionWorkerLoop};
+
+impl<S: LogStore> RegionWorkerLoop<S> {
+    /// Takes and handles all write requests.
+    ///
+    /// Requests to the same region are written to the WAL as one entry, then
+    /// inserted into the mutable memtable of the region.
+    pub(crate) async fn handle_write_requests(&mut self, write_requests: Vec<RegionRequest>) {
+        // Validates requests and groups them by region.
+        let mut region_ctxs: HashMap<_, RegionWriteCtx> = HashMap::new();
+        for request in write_requests {
+            let RequestBody::Write(write_request) = request.body else {
+                unreachable!()
+            };
+            let region_id = write_request.region_id;
+            let region_ctx = match region_ctxs.entry(region_id) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    let Some(region) = self.regions.get_region(region_id) else {
+                        send_result(request.sender, RegionNotFoundSnafu { region_id }.fail());
+                        continue;
+                    };
+                    entry.insert(RegionWriteCtx::new(region))
+                }
+            };
+
+            match Mutation::new(
+                &region_ctx.version.metadata,
+                write_request.op_type,
+                write_request.columns,
+            ) {
+                Ok(mutation) => region_ctx.push_mutation(mutation, request.sender),
+                Err(e) => send_result(request.sender, Err(e)),
+            }
+        }
+
+        // Writes all entries to the WAL in a batch.
+        let mut wal_writer = self.wal.writer();
+        let mut pending_ctxs = Vec::with_capacity(region_ctxs.len());
+        for region_ctx in region_ctxs.into_values() {
+            if region_ctx.mutations.is_empty() {
+                continue;
+            }
+            match region_ctx.add_wal_entry(&mut wal_writer) {
+                Ok(()) => pending_ctxs.push(region_ctx),
+                Err(e) => notify_error(region_ctx.senders, Arc::new(e)),
+            }
+        }
+
+        if let Err(e) = wal_writer.write_to_wal().await {
+            logging::error!(e; "Failed to write WAL in worker {}", self.id);
+
+            let e = Arc::new(e);
+            for region_ctx in pending_ctxs {
+                notify_error(region_ctx.senders, e.clone());
+            }
+            return;
+        }
+
+        // Inserts rows into memtables.
+        for region_ctx in pending_ctxs {
+            region_ctx.write_memtable();
+        }
+    }
+}
+
+/// Context to write requests of a region.
+struct RegionWriteCtx {
+    region: MitoRegionRef,
+    /// Version of the region while writing.
+    version: VersionRef,
+    /// Sequence of this write, which is also the id of the WAL entry.
+    sequence: SequenceNumber,
+    /// Mutations to write.
+    mutations: Vec<Mutation>,
+    /// Senders of the mutations, in the same order as `mutations`.
+    senders: Vec<Option<Sender<Result<()>>>>,
+}
+
+impl RegionWriteCtx {
+    fn new(region: MitoRegionRef) -> RegionWriteCtx {
+        let version = region.version_control.current();
+        let sequence = region.version_control.committed_sequence() + 1;
+
+        RegionWriteCtx {
+            region,
+            version,
+            sequence,
+            mutations: Vec::new(),
+            senders: Vec::new(),
+        }
+    }
+
+    fn push_mutation(&mut self, mutation: Mutation, sender: Option<Sender<Result<()>>>) {
+        self.mutations.push(mutation);
+        self.senders.push(sender);
+    }
+
+    fn add_wal_entry<S: LogStore>(&self, wal_writer: &mut WalWriter<S>) -> Result<()> {
+        wal_writer.add_entry(&self.version.metadata, self.sequence, &self.mutations)
+    }
+
+    /// Writes mutations to the mutable memtable and commits the sequence.
+    ///
+    /// The entry is already in the WAL so the sequence is committed even if
+    /// some mutations fail.
+    fn write_memtable(self) {
+        let mutable = self.version.memtables.mutable_memtable();
+        for (mutation, sender) in self.mutations.iter().zip(self.senders) {
+            send_result(sender, mutable.write(self.sequence, mutation));
+        }
+
+        self.region
+            .version_control
+            .set_committed_sequence(self.sequence);
+    }
+}
+
+/// Notifies all `senders` with the same error.
+fn notify_error(senders: Vec<Option<Sender<Result<()>>>>, e: Arc<Error>) {
+    for sender in senders {
+        send_result(sender, Err::<(), _>(e.clone()).context(WriteGroupSnafu));
+    }
+}
@@ -14,19 +14,22 @@
 
 //! Worker requests.
 
+use std::collections::{HashMap, HashSet};
 use std::time::Duration;
 
 use common_base::readable_size::ReadableSize;
-use store_api::storage::{ColumnId, CompactionStrategy, RegionId};
+use datatypes::vectors::VectorRef;
+use snafu::{ensure, OptionExt, ResultExt};
+use store_api::storage::{ColumnId, CompactionStrategy, OpType, RegionId};
 use tokio::sync::oneshot::{self, Receiver, Sender};
 
-use crate::error::Result;
-use crate::metadata::ColumnMetadata;
+use crate::error::{CreateDefaultSnafu, InvalidRequestSnafu, Result};
+use crate::metadata::{ColumnMetadata, RegionMetadata, SemanticType};
 
 /// Options that affect the entire region.
 ///
 /// Users need to specify the options while creating/opening a region.
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct RegionOptions {
     /// Region memtable max size in bytes.
     pub write_buffer_size: Option<ReadableSize>,
@@ -41,6 +44,8 @@ pub struct RegionOptions {
 pub struct CreateRequest {
     /// Region to create.
     pub region_id: RegionId,
+    /// Directory to store the region.
+    pub region_dir: String,
     /// Columns in this region.
     pub column_metadatas: Vec<ColumnMetadata>,
     /// Columns in the primary key.
@@ -53,8 +58,52 @@ pub struct CreateRequest {
 
 impl CreateRequest {
     /// Validate the request.
-    fn validate(&self) -> Result<()> {
-        unimplemented!()
+    pub(crate) fn validate(&self) -> Result<()> {
+        let region_id = self.region_id;
+        let mut column_ids = HashSet::with_capacity(self.column_metadatas.len());
+        let mut names = HashSet::with_capacity(self.column_metadatas.len());
+        let mut num_time_index = 0;
+        for column in &self.column_metadatas {
+            ensure!(
+                column_ids.insert(column.column_id),
+                InvalidRequestSnafu {
+                    region_id,
+                    reason: format!("duplicate column id {}", column.column_id),
+                }
+            );
+            ensure!(
+                names.insert(&column.column_schema.name),
+                InvalidRequestSnafu {
+                    region_id,
+                    reason: format!("duplicate column name {}", column.column_schema.name),
+                }
+            );
+            if column.semantic_type == SemanticType::Timestamp {
+                num_time_index += 1;
+            }
+        }
+        ensure!(
+            num_time_index == 1,
+            InvalidRequestSnafu {
+                region_id,
+                reason: format!("expect 1 time index column, found {num_time_index}"),
+            }
+        );
+
+        for column_id in &self.primary_key {
+            let is_tag = self.column_metadatas.iter().any(|column| {
+                column.column_id == *column_id && column.semantic_type == SemanticType::Tag
+            });
+            ensure!(
+                is_tag,
+                InvalidRequestSnafu {
+                    region_id,
+                    reason: format!("primary key column {column_id} is not a tag column"),
+                }
+            );
+        }
+
+        Ok(())
     }
 }
 
@@ -63,15 +112,118 @@ impl CreateRequest {
 pub struct OpenRequest {
     /// Region to open.
     pub region_id: RegionId,
+    /// Directory of the region.
+    pub region_dir: String,
     /// Options of the created region.
     pub options: RegionOptions,
 }
 
 /// Request to write a region.
 #[derive(Debug)]
-pub(crate) struct WriteRequest {
+pub struct WriteRequest {
     /// Region to write.
     pub region_id: RegionId,
+    /// Type of the write operation.
+    pub op_type: OpType,
+    /// Columns to write, keyed by column name. All columns must have the same
+    /// length. Missing columns are filled with their default values.
+    pub columns: HashMap<String, VectorRef>,
+}
+
+/// Rows to write, validated against the metadata of a region.
+#[derive(Debug, Clone)]
+pub(crate) struct Mutation {
+    /// Type of the write operation.
+    pub(crate) op_type: OpType,
+    /// Columns in the same order as columns in the region metadata.
+    pub(crate) columns: Vec<VectorRef>,
+}
+
+impl Mutation {
+    /// Builds a mutation from `columns` and checks them against `metadata`.
+    ///
+    /// Fills columns absent from `columns` with their default values. Field columns
+    /// of a delete operation are padded as their values are never read.
+    pub(crate) fn new(
+        metadata: &RegionMetadata,
+        op_type: OpType,
+        mut columns: HashMap<String, VectorRef>,
+    ) -> Result<Mutation> {
+        let region_id = metadata.region_id();
+        let num_rows = columns.values().next().map(|v| v.len()).unwrap_or(0);
+        ensure!(
+            columns.values().all(|v| v.len() == num_rows),
+            InvalidRequestSnafu {
+                region_id,
+                reason: "columns have different lengths",
+            }
+        );
+        if let Some(name) = columns
+            .keys()
+            .find(|name| metadata.column_index_by_name(name).is_none())
+        {
+            return InvalidRequestSnafu {
+                region_id,
+                reason: format!("unknown column {name}"),
+            }
+            .fail();
+        }
+
+        let mut output = Vec::with_capacity(metadata.column_metadatas().len());
+        for column in metadata.column_metadatas() {
+            let column_schema = &column.column_schema;
+            let vector = match columns.remove(&column_schema.name) {
+                Some(vector) => {
+                    ensure!(
+                        vector.data_type() == column_schema.data_type,
+                        InvalidRequestSnafu {
+                            region_id,
+                            reason: format!(
+                                "column {} expect type {:?}, given {:?}",
+                                column_schema.name,
+                                column_schema.data_type,
+                                vector.data_type()
+                            ),
+                        }
+                    );
+                    ensure!(
+                        column_schema.is_nullable() || vector.null_count() == 0,
+                        InvalidRequestSnafu {
+                            region_id,
+                            reason: format!("column {} is not null", column_schema.name),
+                        }
+                    );
+                    vector
+                }
+                None if op_type == OpType::Delete
+                    && column.semantic_type == SemanticType::Field =>
+                {
+                    column_schema.create_default_vector_for_padding(num_rows)
+                }
+                None => column_schema
+                    .create_default_vector(num_rows)
+                    .context(CreateDefaultSnafu {
+                        region_id,
+                        column: &column_schema.name,
+                    })?
+                    .with_context(|| InvalidRequestSnafu {
+                        region_id,
+                        reason: format!("missing column {}", column_schema.name),
+                    })?,
+            };
+            output.push(vector);
+        }
+
+        Ok(Mutation {
+            op_type,
+            columns: output,
+        })
+    }
+
+    /// Returns the number of rows in the mutation.
+    pub(crate) fn num_rows(&self) -> usize {
+        self.columns.first().map(|v| v.len()).unwrap_or(0)
+    }
 }
 
 /// Request sent to a worker
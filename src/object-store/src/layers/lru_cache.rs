@@ -12,45 +12,104 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::num::NonZeroUsize;
-use std::ops::DerefMut;
+use std::ops::Range;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use bytes::{Bytes, BytesMut};
+use futures::future::try_join_all;
 use lru::LruCache;
-use metrics::increment_counter;
-use opendal::raw::oio::{Page, Read, Reader, Write};
+use metrics::{counter, gauge, increment_counter};
+use opendal::raw::oio::{Append, Cursor, Page, Read, ReadExt, Reader, Write};
 use opendal::raw::{
-    Accessor, Layer, LayeredAccessor, OpAppend, OpDelete, OpList, OpRead, OpWrite, RpAppend,
-    RpDelete, RpList, RpRead, RpWrite,
+    Accessor, BytesRange, Layer, LayeredAccessor, OpAppend, OpDelete, OpList, OpRead, OpStat,
+    OpWrite, RpAppend, RpDelete, RpList, RpRead, RpWrite,
 };
-use opendal::{ErrorKind, Result};
+use opendal::Result;
 use tokio::sync::Mutex;
 
 use crate::metrics::{
-    OBJECT_STORE_LRU_CACHE_ERROR, OBJECT_STORE_LRU_CACHE_ERROR_KIND, OBJECT_STORE_LRU_CACHE_HIT,
-    OBJECT_STORE_LRU_CACHE_MISS,
+    OBJECT_STORE_LRU_CACHE_BYTES, OBJECT_STORE_LRU_CACHE_ERROR, OBJECT_STORE_LRU_CACHE_ERROR_KIND,
+    OBJECT_STORE_LRU_CACHE_HIT, OBJECT_STORE_LRU_CACHE_HIT_BYTES, OBJECT_STORE_LRU_CACHE_MISS,
+    OBJECT_STORE_LRU_CACHE_MISS_BYTES, OBJECT_STORE_LRU_CACHE_TIER,
 };
 
+/// Default size of ranges to cache. Reads are aligned to ranges of this size.
+pub const DEFAULT_CACHE_RANGE_SIZE: u64 = 1024 * 1024;
+/// Default capacity in bytes of the in-memory tier for parquet metadata.
+pub const DEFAULT_META_CACHE_CAPACITY: u64 = 32 * 1024 * 1024;
+/// Max number of object sizes to remember.
+const OBJECT_SIZE_CACHE_CAPACITY: usize = 10000;
+/// Reads spanning more ranges than this bypass the cache, so large reads (e.g. whole
+/// SST files read by compaction) are neither buffered in memory nor evict small hot
+/// ranges.
+const MAX_CACHED_READ_RANGES: u64 = 4;
+
+const TIER_MEMORY: &str = "memory";
+const TIER_FILE: &str = "file";
+
+/// A layer that caches ranges of objects read from the inner accessor in
+/// the `cache` accessor, usually a local disk.
+///
+/// Objects are cached by ranges of `range_size` bytes so different parts of a large
+/// object (e.g. the footer and row groups of a parquet file) are cached independently.
+/// The cache is bounded by the total bytes of ranges cached and evicts least recently
+/// used ranges. Ranges of a read are fetched concurrently, and reads spanning many
+/// ranges go to the inner accessor directly.
+///
+/// Last ranges of parquet files, where the metadata of parquet files lives, are also
+/// kept in memory.
 #[derive(Clone)]
 pub struct LruCacheLayer<C> {
     cache: Arc<C>,
-    lru_cache: Arc<Mutex<LruCache<String, ()>>>,
+    lru_cache: Arc<Mutex<SizedLru<()>>>,
+    meta_cache: Arc<Mutex<SizedLru<Bytes>>>,
+    object_sizes: Arc<Mutex<LruCache<String, u64>>>,
+    inflight: InflightReads,
+    range_size: u64,
 }
 
+/// Locks of ranges being read from the inner accessor, keyed by their cache paths.
+type InflightReads = Arc<Mutex<HashMap<String, Arc<Mutex<()>>>>>;
+
 impl<C: Accessor + Clone> LruCacheLayer<C> {
+    /// Creates a layer caching at most `capacity` bytes in the `cache` accessor.
     pub async fn new(cache: Arc<C>, capacity: usize) -> Result<Self> {
         let layer = Self {
             cache,
-            lru_cache: Arc::new(Mutex::new(LruCache::new(
-                NonZeroUsize::new(capacity).unwrap(),
+            lru_cache: Arc::new(Mutex::new(SizedLru::new(capacity as u64))),
+            meta_cache: Arc::new(Mutex::new(SizedLru::new(DEFAULT_META_CACHE_CAPACITY))),
+            object_sizes: Arc::new(Mutex::new(LruCache::new(
+                NonZeroUsize::new(OBJECT_SIZE_CACHE_CAPACITY).unwrap(),
             ))),
+            inflight: Arc::default(),
+            range_size: DEFAULT_CACHE_RANGE_SIZE,
         };
         layer.recover_keys().await?;
 
         Ok(layer)
     }
 
+    /// Sets the size of ranges to cache.
+    ///
+    /// # Panics
+    /// Panics if `range_size` is 0.
+    pub fn with_range_size(mut self, range_size: u64) -> Self {
+        assert!(range_size > 0, "range size of the cache must be positive");
+        self.range_size = range_size;
+        self
+    }
+
+    /// Sets the capacity in bytes of the in-memory tier, 0 to disable it.
+    pub fn with_meta_capacity(self, capacity: u64) -> Self {
+        Self {
+            meta_cache: Arc::new(Mutex::new(SizedLru::new(capacity))),
+            ..self
+        }
+    }
+
     /// Recover existing keys from `cache` to `lru_cache`.
     async fn recover_keys(&self) -> Result<()> {
         let (_, mut pager) = self.cache.list("/", OpList::default()).await?;
@@ -58,9 +117,21 @@ impl<C: Accessor + Clone> LruCacheLayer<C> {
         let mut lru_cache = self.lru_cache.lock().await;
         while let Some(entries) = pager.next().await? {
             for entry in entries {
-                let _ = lru_cache.push(entry.path().to_string(), ());
+                if !entry.path().contains(CACHE_FILE_INFIX) {
+                    continue;
+                }
+                let size = self
+                    .cache
+                    .stat(entry.path(), OpStat::default())
+                    .await?
+                    .into_metadata()
+                    .content_length();
+                for key in lru_cache.put(entry.path().to_string(), size, ()) {
+                    let _ = self.cache.delete(&key, OpDelete::new()).await;
+                }
             }
         }
+        gauge!(OBJECT_STORE_LRU_CACHE_BYTES, lru_cache.size as f64);
 
         Ok(())
     }
@@ -68,6 +139,15 @@ impl<C: Accessor + Clone> LruCacheLayer<C> {
     pub async fn lru_contains_key(&self, key: &str) -> bool {
         self.lru_cache.lock().await.contains(key)
     }
+
+    pub async fn meta_contains_key(&self, key: &str) -> bool {
+        self.meta_cache.lock().await.contains(key)
+    }
+
+    /// Returns the total bytes of ranges cached in the `cache` accessor.
+    pub async fn cached_bytes(&self) -> u64 {
+        self.lru_cache.lock().await.size
+    }
 }
 
 impl<I: Accessor, C: Accessor> Layer<I> for LruCacheLayer<C> {
@@ -78,7 +158,85 @@ impl<I: Accessor, C: Accessor> Layer<I> for LruCacheLayer<C> {
             inner,
             cache: self.cache.clone(),
             lru_cache: self.lru_cache.clone(),
+            meta_cache: self.meta_cache.clone(),
+            object_sizes: self.object_sizes.clone(),
+            inflight: self.inflight.clone(),
+            range_size: self.range_size,
+        }
+    }
+}
+
+/// Infix of names of cache files, between the hash of the object path and the range.
+const CACHE_FILE_INFIX: &str = ".cache-";
+
+/// Returns the prefix of names of cache files of the object at `path`.
+fn cache_path_prefix(path: &str) -> String {
+    format!("{:x}{}", md5::compute(path), CACHE_FILE_INFIX)
+}
+
+/// An LRU cache bounded by the total size of its entries.
+#[derive(Debug)]
+struct SizedLru<V> {
+    entries: LruCache<String, (u64, V)>,
+    /// Total size of entries.
+    size: u64,
+    capacity: u64,
+}
+
+impl<V> SizedLru<V> {
+    fn new(capacity: u64) -> Self {
+        Self {
+            entries: LruCache::unbounded(),
+            size: 0,
+            capacity,
+        }
+    }
+
+    fn contains(&self, key: &str) -> bool {
+        self.entries.contains(key)
+    }
+
+    /// Returns the value of the `key` and marks it as most recently used.
+    fn get(&mut self, key: &str) -> Option<&V> {
+        self.entries.get(key).map(|(_, value)| value)
+    }
+
+    /// Puts an entry of `size` bytes and returns keys of entries evicted.
+    fn put(&mut self, key: String, size: u64, value: V) -> Vec<String> {
+        if let Some((old_size, _)) = self.entries.put(key, (size, value)) {
+            self.size -= old_size;
+        }
+        self.size += size;
+
+        let mut evicted = Vec::new();
+        while self.size > self.capacity {
+            let Some((key, (size, _))) = self.entries.pop_lru() else {
+                break;
+            };
+            self.size -= size;
+            evicted.push(key);
+        }
+        evicted
+    }
+
+    fn pop(&mut self, key: &str) -> Option<V> {
+        let (size, value) = self.entries.pop(key)?;
+        self.size -= size;
+        Some(value)
+    }
+
+    /// Removes all entries whose keys start with `prefix` and returns their keys.
+    fn remove_prefix(&mut self, prefix: &str) -> Vec<String> {
+        let keys = self
+            .entries
+            .iter()
+            .filter(|(k, _)| k.starts_with(prefix))
+            .map(|(k, _)| k.clone())
+            .collect::<Vec<_>>();
+        for key in &keys {
+            let _ = self.pop(key);
         }
+        keys
     }
 }
 
@@ -86,115 +244,289 @@ impl<I: Accessor, C: Accessor> Layer<I> for LruCacheLayer<C> {
 pub struct LruCacheAccessor<I, C> {
     inner: I,
     cache: Arc<C>,
-    lru_cache: Arc<Mutex<LruCache<String, ()>>>,
+    lru_cache: Arc<Mutex<SizedLru<()>>>,
+    meta_cache: Arc<Mutex<SizedLru<Bytes>>>,
+    object_sizes: Arc<Mutex<LruCache<String, u64>>>,
+    inflight: InflightReads,
+    range_size: u64,
 }
 
-impl<I, C> LruCacheAccessor<I, C> {
-    fn cache_path(&self, path: &str, args: &OpRead) -> String {
-        format!(
-            "{:x}.cache-{}",
-            md5::compute(path),
-            args.range().to_header()
-        )
+impl<I: Accessor, C: Accessor> LruCacheAccessor<I, C> {
+    fn cache_path(&self, path: &str, range: &Range<u64>) -> String {
+        let range = BytesRange::new(Some(range.start), Some(range.end - range.start));
+        format!("{}{}", cache_path_prefix(path), range.to_header())
+    }
+
+    /// Returns the size of the object.
+    async fn object_size(&self, path: &str) -> Result<u64> {
+        if let Some(size) = self.object_sizes.lock().await.get(path) {
+            return Ok(*size);
+        }
+
+        let size = self
+            .inner
+            .stat(path, OpStat::default())
+            .await?
+            .into_metadata()
+            .content_length();
+        let _ = self.object_sizes.lock().await.put(path.to_string(), size);
+        Ok(size)
+    }
+
+    /// Resolves the `range` to read to absolute offsets of the object.
+    async fn resolve_range(&self, path: &str, range: BytesRange) -> Result<Range<u64>> {
+        match (range.offset(), range.size()) {
+            (Some(offset), Some(size)) => Ok(offset..offset + size),
+            (Some(offset), None) => {
+                let total = self.object_size(path).await?;
+                Ok(offset.min(total)..total)
+            }
+            (None, Some(size)) => {
+                let total = self.object_size(path).await?;
+                Ok(total.saturating_sub(size)..total)
+            }
+            (None, None) => Ok(0..self.object_size(path).await?),
+        }
+    }
+
+    /// Reads the range at `index` of the object, from the cache if possible.
+    ///
+    /// The range returned may be shorter than `range_size` if it reaches the end
+    /// of the object.
+    async fn read_range(&self, path: &str, index: u64) -> Result<Bytes> {
+        let start = index * self.range_size;
+        let cache_path = self.cache_path(path, &(start..start + self.range_size));
+
+        if let Some(bytes) = self.read_cached(path, start, &cache_path).await {
+            return Ok(bytes);
+        }
+
+        // Only one reader of the range reads it from the inner accessor, others
+        // wait for it and read the cache then.
+        let lock = self
+            .inflight
+            .lock()
+            .await
+            .entry(cache_path.clone())
+            .or_default()
+            .clone();
+        let result = {
+            let _guard = lock.lock().await;
+            match self.read_cached(path, start, &cache_path).await {
+                Some(bytes) => Ok(bytes),
+                None => self.read_inner_range(path, start, cache_path.clone()).await,
+            }
+        };
+        {
+            let mut inflight = self.inflight.lock().await;
+            // Held by the map and this reader only.
+            if Arc::strong_count(&lock) == 2 {
+                let _ = inflight.remove(&cache_path);
+            }
+        }
+
+        result
+    }
+
+    /// Reads the range starting at `start` from the cache, returns `None` if it isn't cached.
+    async fn read_cached(&self, path: &str, start: u64, cache_path: &str) -> Option<Bytes> {
+        if let Some(bytes) = self.meta_cache.lock().await.get(cache_path).cloned() {
+            increment_counter!(OBJECT_STORE_LRU_CACHE_HIT, OBJECT_STORE_LRU_CACHE_TIER => TIER_MEMORY);
+            counter!(OBJECT_STORE_LRU_CACHE_HIT_BYTES, bytes.len() as u64, OBJECT_STORE_LRU_CACHE_TIER => TIER_MEMORY);
+            return Some(bytes);
+        }
+
+        // Updates the lru on lookup.
+        if self.lru_cache.lock().await.get(cache_path).is_some() {
+            match read_all(self.cache.read(cache_path, OpRead::default()).await).await {
+                Ok(bytes) => {
+                    increment_counter!(OBJECT_STORE_LRU_CACHE_HIT, OBJECT_STORE_LRU_CACHE_TIER => TIER_FILE);
+                    counter!(OBJECT_STORE_LRU_CACHE_HIT_BYTES, bytes.len() as u64, OBJECT_STORE_LRU_CACHE_TIER => TIER_FILE);
+                    self.put_meta_cache(path, start, cache_path.to_string(), &bytes)
+                        .await;
+                    return Some(bytes);
+                }
+                Err(err) => {
+                    increment_counter!(OBJECT_STORE_LRU_CACHE_ERROR, OBJECT_STORE_LRU_CACHE_ERROR_KIND => format!("{}", err.kind()));
+                    // The cache file is broken, forget it and read from the inner accessor.
+                    let _ = self.lru_cache.lock().await.pop(cache_path);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Reads the range starting at `start` from the inner accessor and caches it.
+    async fn read_inner_range(&self, path: &str, start: u64, cache_path: String) -> Result<Bytes> {
+        let args = OpRead::new().with_range(BytesRange::new(Some(start), Some(self.range_size)));
+        let bytes = read_all(self.inner.read(path, args).await).await?;
+        increment_counter!(OBJECT_STORE_LRU_CACHE_MISS);
+        counter!(OBJECT_STORE_LRU_CACHE_MISS_BYTES, bytes.len() as u64);
+
+        self.put_file_cache(&cache_path, &bytes).await;
+        self.put_meta_cache(path, start, cache_path, &bytes).await;
+
+        Ok(bytes)
+    }
+
+    /// Writes the range to the `cache` accessor, errors are ignored as the range
+    /// is already read.
+    async fn put_file_cache(&self, cache_path: &str, bytes: &Bytes) {
+        let res = async {
+            let (_, mut writer) = self.cache.write(cache_path, OpWrite::new()).await?;
+            writer.write(bytes.clone()).await?;
+            writer.close().await
+        }
+        .await;
+        if let Err(err) = res {
+            increment_counter!(OBJECT_STORE_LRU_CACHE_ERROR, OBJECT_STORE_LRU_CACHE_ERROR_KIND => format!("{}", err.kind()));
+            return;
+        }
+
+        let (evicted, cached_bytes) = {
+            let mut lru_cache = self.lru_cache.lock().await;
+            let evicted = lru_cache.put(cache_path.to_string(), bytes.len() as u64, ());
+            (evicted, lru_cache.size)
+        };
+        gauge!(OBJECT_STORE_LRU_CACHE_BYTES, cached_bytes as f64);
+        // delete the evicted cache files
+        for key in evicted {
+            let _ = self.cache.delete(&key, OpDelete::new()).await;
+        }
+    }
+
+    /// Keeps the range starting at `start` in memory if it's the last range of a
+    /// parquet file, which contains the footer and usually the whole metadata of the file.
+    async fn put_meta_cache(&self, path: &str, start: u64, cache_path: String, bytes: &Bytes) {
+        if !path.ends_with(".parquet") {
+            return;
+        }
+        // The last range is a full one if the object size is a multiple of `range_size`.
+        let end = start + bytes.len() as u64;
+        let is_last_range = (bytes.len() as u64) < self.range_size
+            || matches!(self.object_size(path).await, Ok(size) if end >= size);
+        if is_last_range {
+            let _ = self
+                .meta_cache
+                .lock()
+                .await
+                .put(cache_path, bytes.len() as u64, bytes.clone());
+        }
+    }
+
+    fn invalidator(&self) -> CacheInvalidator<C> {
+        CacheInvalidator {
+            cache: self.cache.clone(),
+            lru_cache: self.lru_cache.clone(),
+            meta_cache: self.meta_cache.clone(),
+            object_sizes: self.object_sizes.clone(),
+        }
     }
 }
 
-use opendal::raw::oio::ReadExt;
+/// Removes cached ranges of objects, shared by the accessor and the writers it
+/// returns.
+struct CacheInvalidator<C> {
+    cache: Arc<C>,
+    lru_cache: Arc<Mutex<SizedLru<()>>>,
+    meta_cache: Arc<Mutex<SizedLru<Bytes>>>,
+    object_sizes: Arc<Mutex<LruCache<String, u64>>>,
+}
+
+impl<C: Accessor> CacheInvalidator<C> {
+    /// Removes all cached ranges of the object at `path`.
+    async fn invalidate(&self, path: &str) {
+        let prefix = cache_path_prefix(path);
+        let _ = self.object_sizes.lock().await.pop(path);
+        let _ = self.meta_cache.lock().await.remove_prefix(&prefix);
+
+        let (cache_files, cached_bytes) = {
+            let mut lru_cache = self.lru_cache.lock().await;
+            let cache_files = lru_cache.remove_prefix(&prefix);
+            (cache_files, lru_cache.size)
+        };
+        gauge!(OBJECT_STORE_LRU_CACHE_BYTES, cached_bytes as f64);
+        for file in cache_files {
+            let _ = self.cache.delete(&file, OpDelete::new()).await;
+        }
+    }
+}
 
 #[async_trait]
 impl<I: Accessor, C: Accessor> LayeredAccessor for LruCacheAccessor<I, C> {
     type Inner = I;
     type Reader = Box<dyn Read>;
     type BlockingReader = I::BlockingReader;
-    type Writer = I::Writer;
+    type Writer = LruCacheWriter<I::Writer, C>;
     type BlockingWriter = I::BlockingWriter;
     type Pager = I::Pager;
     type BlockingPager = I::BlockingPager;
-    type Appender = I::Appender;
+    type Appender = LruCacheWriter<I::Appender, C>;
 
     fn inner(&self) -> &Self::Inner {
         &self.inner
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
-        let path = path.to_string();
-        let cache_path = self.cache_path(&path, &args);
-        let lru_cache = &self.lru_cache;
-
-        // the args is already in the cache path, so we must create a new OpRead.
-        match self.cache.read(&cache_path, OpRead::default()).await {
-            Ok((rp, r)) => {
-                increment_counter!(OBJECT_STORE_LRU_CACHE_HIT);
-
-                // update lru when cache hit
-                let mut lru_cache = lru_cache.lock().await;
-                let _ = lru_cache.get_or_insert(cache_path.clone(), || ());
-                Ok(to_output_reader((rp, r)))
-            }
-            Err(err) if err.kind() == ErrorKind::NotFound => {
-                increment_counter!(OBJECT_STORE_LRU_CACHE_MISS);
-
-                let (_, mut reader) = self.inner.read(&path, args.clone()).await?;
-                let (_, mut writer) = self.cache.write(&cache_path, OpWrite::new()).await?;
+        let range = self.resolve_range(path, args.range()).await?;
+        if range.is_empty() {
+            return Ok((RpRead::new(0), Box::new(Cursor::from(Bytes::new()))));
+        }
 
-                while let Some(bytes) = reader.next().await {
-                    writer.write(bytes?).await?;
-                }
+        let first = range.start / self.range_size;
+        let last = (range.end - 1) / self.range_size;
+        let capacity = self.lru_cache.lock().await.capacity;
+        // Large reads bypass the cache, also reads that don't fit in the cache,
+        // otherwise they evict everything else.
+        if last - first + 1 > MAX_CACHED_READ_RANGES || range.end - range.start > capacity {
+            return self.inner.read(path, args).await.map(to_output_reader);
+        }
 
-                writer.close().await?;
-
-                match self.cache.read(&cache_path, OpRead::default()).await {
-                    Ok((rp, reader)) => {
-                        let r = {
-                            // push new cache file name to lru
-                            let mut lru_cache = lru_cache.lock().await;
-                            lru_cache.push(cache_path.clone(), ())
-                        };
-                        // delete the evicted cache file
-                        if let Some((k, _v)) = r {
-                            let _ = self.cache.delete(&k, OpDelete::new()).await;
-                        }
-                        return Ok(to_output_reader((rp, reader)));
-                    }
-                    Err(_) => return self.inner.read(&path, args).await.map(to_output_reader),
-                }
-            }
-            Err(err) => {
-                increment_counter!(OBJECT_STORE_LRU_CACHE_ERROR, OBJECT_STORE_LRU_CACHE_ERROR_KIND => format!("{}", err.kind()));
-                return self.inner.read(&path, args).await.map(to_output_reader);
+        let ranges = try_join_all((first..=last).map(|index| self.read_range(path, index))).await?;
+        let mut parts = Vec::with_capacity(ranges.len());
+        for (index, bytes) in (first..=last).zip(ranges) {
+            let range_start = index * self.range_size;
+            let start = (range.start.max(range_start) - range_start) as usize;
+            let end = ((range.end - range_start) as usize).min(bytes.len());
+            if start >= end {
+                // Reaches the end of the object.
+                break;
             }
+            parts.push(bytes.slice(start..end));
         }
+        let bytes = if parts.len() == 1 {
+            parts.pop().unwrap()
+        } else {
+            let mut buf = BytesMut::with_capacity(parts.iter().map(|b| b.len()).sum());
+            for part in parts {
+                buf.extend_from_slice(&part);
+            }
+            buf.freeze()
+        };
+
+        Ok((
+            RpRead::new(bytes.len() as u64),
+            Box::new(Cursor::from(bytes)),
+        ))
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
-        self.inner.write(path, args).await
+        let (rp, writer) = self.inner.write(path, args).await?;
+        Ok((rp, LruCacheWriter::new(writer, path, self.invalidator())))
     }
 
     async fn append(&self, path: &str, args: OpAppend) -> Result<(RpAppend, Self::Appender)> {
-        self.inner.append(path, args).await
+        let (rp, appender) = self.inner.append(path, args).await?;
+        Ok((rp, LruCacheWriter::new(appender, path, self.invalidator())))
     }
 
     async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
-        let cache_path = md5::compute(path);
-        let lru_cache = &self.lru_cache;
-
-        let cache_files: Vec<String> = {
-            let mut guard = lru_cache.lock().await;
-            let lru = guard.deref_mut();
-            let cache_files = lru
-                .iter()
-                .filter(|(k, _v)| k.starts_with(format!("{:x}.cache-", cache_path).as_str()))
-                .map(|(k, _v)| k.clone())
-                .collect::<Vec<_>>();
-            for k in &cache_files {
-                let _ = lru.pop(k);
-            }
-            cache_files
-        };
-        for file in cache_files {
-            let _ = self.cache.delete(&file, OpDelete::new()).await;
-        }
-        self.inner.delete(path, args).await
+        let result = self.inner.delete(path, args).await;
+        // Invalidates after deleting, otherwise a concurrent read may cache the
+        // object again before it's deleted.
+        self.invalidator().invalidate(path).await;
+        result
     }
 
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
@@ -214,6 +546,64 @@ impl<I: Accessor, C: Accessor> LayeredAccessor for LruCacheAccessor<I, C> {
     }
 }
 
+/// Wraps writers and appenders of the inner accessor to invalidate cached ranges
+/// of the object once it's closed, when the new content becomes visible to readers.
+pub struct LruCacheWriter<W, C> {
+    inner: W,
+    path: String,
+    invalidator: CacheInvalidator<C>,
+}
+
+impl<W, C> LruCacheWriter<W, C> {
+    fn new(inner: W, path: &str, invalidator: CacheInvalidator<C>) -> Self {
+        Self {
+            inner,
+            path: path.to_string(),
+            invalidator,
+        }
+    }
+}
+
+#[async_trait]
+impl<W: Write, C: Accessor> Write for LruCacheWriter<W, C> {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        self.inner.write(bs).await
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.inner.abort().await
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        let result = self.inner.close().await;
+        self.invalidator.invalidate(&self.path).await;
+        result
+    }
+}
+
+#[async_trait]
+impl<W: Append, C: Accessor> Append for LruCacheWriter<W, C> {
+    async fn append(&mut self, bs: Bytes) -> Result<()> {
+        self.inner.append(bs).await
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        let result = self.inner.close().await;
+        self.invalidator.invalidate(&self.path).await;
+        result
+    }
+}
+
+/// Reads all bytes from the reader returned by an accessor.
+async fn read_all<R: Read>(input: Result<(RpRead, R)>) -> Result<Bytes> {
+    let (_, mut reader) = input?;
+    let mut buf = Vec::new();
+    while let Some(bytes) = reader.next().await {
+        buf.extend_from_slice(&bytes?);
+    }
+    Ok(Bytes::from(buf))
+}
+
 #[inline]
 fn to_output_reader<R: Read + 'static>(input: (RpRead, R)) -> (RpRead, Reader) {
     (input.0, Box::new(input.1))
@@ -18,3 +18,7 @@ pub const OBJECT_STORE_LRU_CACHE_HIT: &str = "object_store.lru_cache.hit";
 pub const OBJECT_STORE_LRU_CACHE_MISS: &str = "object_store.lru_cache.miss";
 pub const OBJECT_STORE_LRU_CACHE_ERROR: &str = "object_store.lru_cache.error";
 pub const OBJECT_STORE_LRU_CACHE_ERROR_KIND: &str = "error";
+pub const OBJECT_STORE_LRU_CACHE_HIT_BYTES: &str = "object_store.lru_cache.hit_bytes";
+pub const OBJECT_STORE_LRU_CACHE_MISS_BYTES: &str = "object_store.lru_cache.miss_bytes";
+pub const OBJECT_STORE_LRU_CACHE_BYTES: &str = "object_store.lru_cache.bytes";
+pub const OBJECT_STORE_LRU_CACHE_TIER: &str = "tier";
@@ -271,9 +271,11 @@ async fn test_object_store_cache_policy() -> Result<()> {
     let cache_store = OperatorBuilder::new(cache_accessor.clone()).finish();
 
     // create operator for cache dir to verify cache file
-    let cache_layer = LruCacheLayer::new(Arc::new(cache_accessor.clone()), 3)
+    // cache at most 32 bytes in ranges of 8 bytes
+    let cache_layer = LruCacheLayer::new(Arc::new(cache_accessor.clone()), 32)
         .await
-        .unwrap();
+        .unwrap()
+        .with_range_size(8);
     let store = store.layer(cache_layer.clone());
 
     // create several object handler.
@@ -284,91 +286,276 @@ async fn test_object_store_cache_policy() -> Result<()> {
     store.write(p2, "Hello, object2!").await.unwrap();
 
     // create cache by read object
-    let _ = store.range_read(p1, 0..).await?;
-    let _ = store.read(p1).await?;
-    let _ = store.range_read(p2, 0..).await?;
-    let _ = store.range_read(p2, 7..).await?;
-    let _ = store.read(p2).await?;
+    assert_eq!(b"Hello, object1!", &store.range_read(p1, 0..).await?[..]);
+    assert_eq!(b"object2!", &store.range_read(p2, 7..).await?[..]);
+    assert_eq!(b"Hello, object1!", &store.read(p1).await?[..]);
+    assert_eq!(b"lo, ob", &store.range_read(p1, 3..9).await?[..]);
 
     assert_cache_files(
         &cache_store,
         &[
-            "6d29752bdc6e4d5ba5483b96615d6c48.cache-bytes=0-",
-            "ecfe0dce85de452eb0a325158e7bfb75.cache-bytes=7-",
-            "ecfe0dce85de452eb0a325158e7bfb75.cache-bytes=0-",
+            "6d29752bdc6e4d5ba5483b96615d6c48.cache-bytes=0-7",
+            "6d29752bdc6e4d5ba5483b96615d6c48.cache-bytes=8-15",
+            "ecfe0dce85de452eb0a325158e7bfb75.cache-bytes=0-7",
+            "ecfe0dce85de452eb0a325158e7bfb75.cache-bytes=8-15",
         ],
-        &["Hello, object1!", "object2!", "Hello, object2!"],
+        &["Hello, o", "bject1!", "Hello, o", "bject2!"],
     )
     .await?;
-    assert_lru_cache(
-        &cache_layer,
-        &[
-            "6d29752bdc6e4d5ba5483b96615d6c48.cache-bytes=0-",
-            "ecfe0dce85de452eb0a325158e7bfb75.cache-bytes=0-",
-        ],
-    )
-    .await;
-
-    store.delete(p2).await.unwrap();
-
-    assert_cache_files(
-        &cache_store,
-        &["6d29752bdc6e4d5ba5483b96615d6c48.cache-bytes=0-"],
-        &["Hello, object1!"],
-    )
-    .await?;
-    assert_lru_cache(
-        &cache_layer,
-        &["6d29752bdc6e4d5ba5483b96615d6c48.cache-bytes=0-"],
-    )
-    .await;
+    assert_eq!(30, cache_layer.cached_bytes().await);
 
+    // ranges of p2 are least recently used and evicted
     let p3 = "test_file3";
     store.write(p3, "Hello, object3!").await.unwrap();
-
-    let _ = store.read(p3).await.unwrap();
-    let _ = store.range_read(p3, 0..5).await.unwrap();
+    assert_eq!(b"Hello, object3!", &store.read(p3).await?[..]);
+    assert_eq!(b"Hello", &store.range_read(p3, 0..5).await?[..]);
 
     assert_cache_files(
         &cache_store,
         &[
-            "6d29752bdc6e4d5ba5483b96615d6c48.cache-bytes=0-",
-            "a8b1dc21e24bb55974e3e68acc77ed52.cache-bytes=0-",
-            "a8b1dc21e24bb55974e3e68acc77ed52.cache-bytes=0-4",
+            "6d29752bdc6e4d5ba5483b96615d6c48.cache-bytes=0-7",
+            "6d29752bdc6e4d5ba5483b96615d6c48.cache-bytes=8-15",
+            "a8b1dc21e24bb55974e3e68acc77ed52.cache-bytes=0-7",
+            "a8b1dc21e24bb55974e3e68acc77ed52.cache-bytes=8-15",
         ],
-        &["Hello, object1!", "Hello, object3!", "Hello"],
+        &["Hello, o", "bject1!", "Hello, o", "bject3!"],
     )
     .await?;
     assert_lru_cache(
         &cache_layer,
         &[
-            "6d29752bdc6e4d5ba5483b96615d6c48.cache-bytes=0-",
-            "a8b1dc21e24bb55974e3e68acc77ed52.cache-bytes=0-",
-            "a8b1dc21e24bb55974e3e68acc77ed52.cache-bytes=0-4",
+            "6d29752bdc6e4d5ba5483b96615d6c48.cache-bytes=0-7",
+            "6d29752bdc6e4d5ba5483b96615d6c48.cache-bytes=8-15",
+            "a8b1dc21e24bb55974e3e68acc77ed52.cache-bytes=0-7",
+            "a8b1dc21e24bb55974e3e68acc77ed52.cache-bytes=8-15",
         ],
     )
     .await;
+    assert!(
+        !cache_layer
+            .lru_contains_key("ecfe0dce85de452eb0a325158e7bfb75.cache-bytes=0-7")
+            .await
+    );
 
     let handle = metric::try_handle().unwrap();
     let metric_text = handle.render();
 
     assert!(metric_text.contains("object_store_lru_cache_hit"));
     assert!(metric_text.contains("object_store_lru_cache_miss"));
+    assert!(metric_text.contains("object_store_lru_cache_hit_bytes"));
+    assert!(metric_text.contains("object_store_lru_cache_miss_bytes"));
+
+    // deleting or overwriting an object removes its cached ranges
+    store.delete(p1).await.unwrap();
+    store.write(p3, "Hi, object3!").await.unwrap();
+    assert_cache_files(&cache_store, &[], &[]).await?;
+    assert_eq!(0, cache_layer.cached_bytes().await);
+
+    assert_eq!(b"Hi, object3!", &store.read(p3).await?[..]);
+    assert_cache_files(
+        &cache_store,
+        &[
+            "a8b1dc21e24bb55974e3e68acc77ed52.cache-bytes=0-7",
+            "a8b1dc21e24bb55974e3e68acc77ed52.cache-bytes=8-15",
+        ],
+        &["Hi, obje", "ct3!"],
+    )
+    .await?;
 
     drop(cache_layer);
-    let cache_layer = LruCacheLayer::new(Arc::new(cache_accessor), 3)
+    let cache_layer = LruCacheLayer::new(Arc::new(cache_accessor), 32)
         .await
         .unwrap();
 
     assert_lru_cache(
         &cache_layer,
         &[
-            "6d29752bdc6e4d5ba5483b96615d6c48.cache-bytes=0-",
-            "a8b1dc21e24bb55974e3e68acc77ed52.cache-bytes=0-",
-            "a8b1dc21e24bb55974e3e68acc77ed52.cache-bytes=0-4",
+            "a8b1dc21e24bb55974e3e68acc77ed52.cache-bytes=0-7",
+            "a8b1dc21e24bb55974e3e68acc77ed52.cache-bytes=8-15",
         ],
     )
     .await;
+    assert_eq!(12, cache_layer.cached_bytes().await);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_object_store_cache_parquet_meta() -> Result<()> {
+    common_telemetry::init_default_ut_logging();
+    let root_dir = create_temp_dir("test_object_store_cache_parquet_meta");
+    let store = OperatorBuilder::new(
+        Fs::default()
+            .root(&root_dir.path().to_string_lossy())
+            .atomic_write_dir(&root_dir.path().to_string_lossy())
+            .build()
+            .unwrap(),
+    )
+    .finish();
+
+    let cache_dir = create_temp_dir("test_object_store_cache_parquet_meta_cache");
+    let mut builder = Fs::default();
+    let _ = builder
+        .root(&cache_dir.path().to_string_lossy())
+        .atomic_write_dir(&cache_dir.path().to_string_lossy());
+    let cache_accessor = Arc::new(builder.build().unwrap());
+    let cache_store = OperatorBuilder::new(cache_accessor.clone()).finish();
+
+    let cache_layer = LruCacheLayer::new(Arc::new(cache_accessor), 1024)
+        .await
+        .unwrap()
+        .with_range_size(8);
+    let store = store.layer(cache_layer.clone());
+
+    let path = "test_file.parquet";
+    store.write(path, "0123456789abcdefghij").await.unwrap();
+    // reads the footer
+    assert_eq!(b"ij", &store.range_read(path, 18..).await?[..]);
+
+    // only the last range is kept in memory
+    assert!(
+        cache_layer
+            .meta_contains_key("d4ee6e53b5499841cd7c833b2f84c06a.cache-bytes=16-23")
+            .await
+    );
+    assert_cache_files(
+        &cache_store,
+        &["d4ee6e53b5499841cd7c833b2f84c06a.cache-bytes=16-23"],
+        &["ghij"],
+    )
+    .await?;
+
+    // the memory tier serves the footer even if the cache file is gone
+    cache_store
+        .delete("d4ee6e53b5499841cd7c833b2f84c06a.cache-bytes=16-23")
+        .await?;
+    assert_eq!(b"hij", &store.range_read(path, 17..20).await?[..]);
+
+    assert_eq!(b"0123456789abcdefghij", &store.read(path).await?[..]);
+    assert!(
+        !cache_layer
+            .meta_contains_key("d4ee6e53b5499841cd7c833b2f84c06a.cache-bytes=0-7")
+            .await
+    );
+
+    // the last range is a full one if the size is a multiple of the range size
+    store.write(path, "0123456789abcdef").await.unwrap();
+    assert!(
+        !cache_layer
+            .meta_contains_key("d4ee6e53b5499841cd7c833b2f84c06a.cache-bytes=16-23")
+            .await
+    );
+    assert_eq!(b"cdef", &store.range_read(path, 12..).await?[..]);
+    assert!(
+        cache_layer
+            .meta_contains_key("d4ee6e53b5499841cd7c833b2f84c06a.cache-bytes=8-15")
+            .await
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_object_store_cache_large_and_concurrent_reads() -> Result<()> {
+    common_telemetry::init_default_ut_logging();
+    let root_dir = create_temp_dir("test_object_store_cache_large_read");
+    let store = OperatorBuilder::new(
+        Fs::default()
+            .root(&root_dir.path().to_string_lossy())
+            .atomic_write_dir(&root_dir.path().to_string_lossy())
+            .build()
+            .unwrap(),
+    )
+    .finish();
+
+    let cache_dir = create_temp_dir("test_object_store_cache_large_read_cache");
+    let mut builder = Fs::default();
+    let _ = builder
+        .root(&cache_dir.path().to_string_lossy())
+        .atomic_write_dir(&cache_dir.path().to_string_lossy());
+    let cache_accessor = Arc::new(builder.build().unwrap());
+    let cache_store = OperatorBuilder::new(cache_accessor.clone()).finish();
+
+    let cache_layer = LruCacheLayer::new(Arc::new(cache_accessor), 1024)
+        .await
+        .unwrap()
+        .with_range_size(8);
+    let store = store.layer(cache_layer.clone());
+
+    let path = "test_file";
+    let content = "0123456789abcdefghijklmnopqrstuvwxyzABCD";
+    store.write(path, content).await.unwrap();
+
+    // reads spanning more than 4 ranges bypass the cache
+    assert_eq!(content.as_bytes(), &store.read(path).await?[..]);
+    assert_eq!(
+        &content.as_bytes()[1..35],
+        &store.range_read(path, 1..35).await?[..]
+    );
+    assert_cache_files(&cache_store, &[], &[]).await?;
+    assert_eq!(0, cache_layer.cached_bytes().await);
+
+    // concurrent reads of the same ranges share the cache files
+    let reads = (0..8).map(|_| store.range_read(path, 4..20));
+    for bytes in futures::future::try_join_all(reads).await? {
+        assert_eq!(&content.as_bytes()[4..20], &bytes[..]);
+    }
+    assert_cache_files(
+        &cache_store,
+        &[
+            "37b87ee8c563af911dcc0f949826b1c9.cache-bytes=0-7",
+            "37b87ee8c563af911dcc0f949826b1c9.cache-bytes=8-15",
+            "37b87ee8c563af911dcc0f949826b1c9.cache-bytes=16-23",
+        ],
+        &["01234567", "89abcdef", "ghijklmn"],
+    )
+    .await?;
+    assert_eq!(24, cache_layer.cached_bytes().await);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_object_store_cache_invalidate_after_write() -> Result<()> {
+    common_telemetry::init_default_ut_logging();
+    let root_dir = create_temp_dir("test_object_store_cache_invalidate_after_write");
+    let store = OperatorBuilder::new(
+        Fs::default()
+            .root(&root_dir.path().to_string_lossy())
+            .atomic_write_dir(&root_dir.path().to_string_lossy())
+            .build()
+            .unwrap(),
+    )
+    .finish();
+
+    let cache_dir = create_temp_dir("test_object_store_cache_invalidate_after_write_cache");
+    let mut builder = Fs::default();
+    let _ = builder
+        .root(&cache_dir.path().to_string_lossy())
+        .atomic_write_dir(&cache_dir.path().to_string_lossy());
+    let cache_accessor = Arc::new(builder.build().unwrap());
+    let cache_store = OperatorBuilder::new(cache_accessor.clone()).finish();
+
+    let cache_layer = LruCacheLayer::new(Arc::new(cache_accessor), 1024)
+        .await
+        .unwrap()
+        .with_range_size(8);
+    let store = store.layer(cache_layer.clone());
+
+    let path = "test_file";
+    store.write(path, "Hello, object!").await.unwrap();
+
+    // reads the old content while the new content is being written
+    let mut writer = store.writer(path).await?;
+    writer.write("Hi, object!").await?;
+    assert_eq!(b"Hello, object!", &store.read(path).await?[..]);
+    assert_eq!(14, cache_layer.cached_bytes().await);
+
+    // ranges cached during the write are removed once it's done
+    writer.close().await?;
+    assert_cache_files(&cache_store, &[], &[]).await?;
+    assert_eq!(0, cache_layer.cached_bytes().await);
+    assert_eq!(b"Hi, object!", &store.read(path).await?[..]);
 
     Ok(())
 }
@@ -0,0 +1,239 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::hash::{Hash, Hasher};
+
+use datafusion_expr::Operator;
+use datatypes::prelude::*;
+use serde::{Deserialize, Serialize};
+use snafu::OptionExt;
+use store_api::storage::RegionNumber;
+use twox_hash::XxHash64;
+
+use crate::error::{self, Error};
+use crate::partition::{PartitionExpr, PartitionRule};
+
+/// [HashPartitionRule] distributes rows to partitions by the hash of the partition column's value.
+/// It's generated from create table request, using MySQL's syntax:
+///
+/// ```SQL
+/// CREATE TABLE table_name (
+///     columns definition
+/// )
+/// PARTITION BY HASH (column_name) PARTITIONS num
+/// ```
+///
+/// A value goes to the partition whose remainder is `hash(value) % num`. Every frontend must route
+/// the same value to the same partition, so the hash is computed by xxHash64 with a fixed seed
+/// over a type independent encoding of the value, rather than Rust's unstable `DefaultHasher`.
+///
+/// Only the equality predicates on the partition column are able to prune partitions.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HashPartitionRule {
+    column_name: String,
+    // Regions ordered by their hash remainders, i.e., `regions[i]` holds the values whose
+    // `hash(value) % regions.len() == i`.
+    regions: Vec<RegionNumber>,
+}
+
+impl HashPartitionRule {
+    pub fn new(column_name: impl Into<String>, regions: Vec<RegionNumber>) -> Self {
+        debug_assert!(!regions.is_empty());
+        Self {
+            column_name: column_name.into(),
+            regions,
+        }
+    }
+
+    pub fn column_name(&self) -> &String {
+        &self.column_name
+    }
+
+    pub fn all_regions(&self) -> &Vec<RegionNumber> {
+        &self.regions
+    }
+
+    fn find_region_by_value(&self, value: &Value) -> RegionNumber {
+        let remainder = hash_value(value) % self.regions.len() as u64;
+        self.regions[remainder as usize]
+    }
+}
+
+impl PartitionRule for HashPartitionRule {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn partition_columns(&self) -> Vec<String> {
+        vec![self.column_name().to_string()]
+    }
+
+    fn find_region(&self, values: &[Value]) -> Result<RegionNumber, Error> {
+        debug_assert_eq!(
+            values.len(),
+            1,
+            "HashPartitionRule can only handle one partition value, actual {}",
+            values.len()
+        );
+        Ok(self.find_region_by_value(&values[0]))
+    }
+
+    fn find_regions_by_exprs(&self, exprs: &[PartitionExpr]) -> Result<Vec<RegionNumber>, Error> {
+        if exprs.is_empty() {
+            return Ok(self.regions.clone());
+        }
+        debug_assert_eq!(
+            exprs.len(),
+            1,
+            "HashPartitionRule can only handle one partition expr, actual {}",
+            exprs.len()
+        );
+
+        let PartitionExpr { column, op, value } =
+            exprs.first().context(error::FindRegionSnafu {
+                reason: "no partition expr is provided",
+            })?;
+        let regions = if column == self.column_name() && *op == Operator::Eq {
+            vec![self.find_region_by_value(value)]
+        } else {
+            self.all_regions().clone()
+        };
+        Ok(regions)
+    }
+}
+
+/// Hashes the value in a way that is stable across processes and versions.
+///
+/// Integers of different widths and signedness hash the same if they are numerically equal, so
+/// that a filter literal of a wider integer type still finds the region of the column value.
+/// `Null` always hashes to 0, which puts it in the first region.
+fn hash_value(value: &Value) -> u64 {
+    let mut hasher = XxHash64::with_seed(0);
+    match value {
+        Value::Null => return 0,
+        Value::Boolean(v) => hasher.write_u8(*v as u8),
+        Value::UInt8(v) => write_integer(&mut hasher, *v as i128),
+        Value::UInt16(v) => write_integer(&mut hasher, *v as i128),
+        Value::UInt32(v) => write_integer(&mut hasher, *v as i128),
+        Value::UInt64(v) => write_integer(&mut hasher, *v as i128),
+        Value::Int8(v) => write_integer(&mut hasher, *v as i128),
+        Value::Int16(v) => write_integer(&mut hasher, *v as i128),
+        Value::Int32(v) => write_integer(&mut hasher, *v as i128),
+        Value::Int64(v) => write_integer(&mut hasher, *v as i128),
+        Value::Float32(v) => write_float(&mut hasher, v.0 as f64),
+        Value::Float64(v) => write_float(&mut hasher, v.0),
+        Value::Decimal128(v) => {
+            hasher.write(&v.val().to_le_bytes());
+            hasher.write(&v.scale().to_le_bytes());
+        }
+        Value::String(v) => hasher.write(v.as_utf8().as_bytes()),
+        Value::Binary(v) => hasher.write(v),
+        Value::Date(v) => hasher.write(&v.val().to_le_bytes()),
+        Value::DateTime(v) => hasher.write(&v.val().to_le_bytes()),
+        Value::Timestamp(v) => v.hash(&mut hasher),
+        Value::List(v) => {
+            for item in v.items().iter().flat_map(|items| items.iter()) {
+                hasher.write(&hash_value(item).to_le_bytes());
+            }
+        }
+    }
+    hasher.finish()
+}
+
+#[inline]
+fn write_integer(hasher: &mut XxHash64, v: i128) {
+    hasher.write(&v.to_le_bytes());
+}
+
+#[inline]
+fn write_float(hasher: &mut XxHash64, v: f64) {
+    // Normalizes "-0.0" to "0.0", as they are equal.
+    let v = if v == 0.0 { 0.0 } else { v };
+    hasher.write(&v.to_bits().to_le_bytes());
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use datafusion_expr::Operator;
+    use datatypes::value::OrderedFloat;
+
+    use super::*;
+    use crate::partition::PartitionExpr;
+
+    #[test]
+    fn test_find_region() {
+        let rule = HashPartitionRule::new("a", vec![1, 2, 3, 4]);
+
+        let values: Vec<Value> = (0..100_i32).map(Value::from).collect();
+        let mut hit = HashSet::new();
+        for value in values.iter() {
+            let region = rule.find_region(&[value.clone()]).unwrap();
+            assert!(rule.all_regions().contains(&region));
+            // The same value always goes to the same region.
+            assert_eq!(region, rule.find_region(&[value.clone()]).unwrap());
+            let _ = hit.insert(region);
+        }
+        // 100 values should be able to spread over all 4 regions.
+        assert_eq!(hit.len(), 4);
+
+        assert_eq!(rule.find_region(&[Value::Null]).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_hash_value() {
+        assert_eq!(hash_value(&Value::Int32(42)), hash_value(&Value::Int64(42)));
+        assert_eq!(hash_value(&Value::UInt8(42)), hash_value(&Value::Int64(42)));
+        assert_ne!(
+            hash_value(&Value::Int32(42)),
+            hash_value(&Value::Int32(-42))
+        );
+        assert_eq!(
+            hash_value(&Value::Float64(OrderedFloat(0.0))),
+            hash_value(&Value::Float64(OrderedFloat(-0.0)))
+        );
+        assert_eq!(
+            hash_value(&Value::from("host-1")),
+            hash_value(&Value::from("host-1"))
+        );
+        assert_eq!(hash_value(&Value::Null), 0);
+    }
+
+    #[test]
+    fn test_find_regions() {
+        let rule = HashPartitionRule::new("a", vec![1, 2, 3, 4]);
+        let expected = rule.find_region(&["host-1".into()]).unwrap();
+
+        let test =
+            |column: &str, op: Operator, value: &str, expected_regions: Vec<RegionNumber>| {
+                let expr = PartitionExpr {
+                    column: column.to_string(),
+                    op,
+                    value: value.into(),
+                };
+                let regions = rule.find_regions_by_exprs(&[expr]).unwrap();
+                assert_eq!(regions, expected_regions);
+            };
+
+        test("a", Operator::Eq, "host-1", vec![expected]);
+        test("a", Operator::NotEq, "host-1", vec![1, 2, 3, 4]);
+        test("a", Operator::Lt, "host-1", vec![1, 2, 3, 4]);
+        test("a", Operator::GtEq, "host-1", vec![1, 2, 3, 4]);
+        test("b", Operator::Eq, "host-1", vec![1, 2, 3, 4]);
+
+        assert_eq!(rule.find_regions_by_exprs(&[]).unwrap(), vec![1, 2, 3, 4]);
+    }
+}
@@ -16,6 +16,7 @@
 
 pub mod columns;
 pub mod error;
+pub mod hash;
 pub mod manager;
 pub mod metrics;
 pub mod partition;
@@ -28,6 +28,7 @@ use table::requests::InsertRequest;
 
 use crate::columns::RangeColumnsPartitionRule;
 use crate::error::{FindLeaderSnafu, Result};
+use crate::hash::HashPartitionRule;
 use crate::partition::{PartitionBound, PartitionDef, PartitionExpr};
 use crate::range::RangePartitionRule;
 use crate::route::TableRoutes;
@@ -103,13 +104,23 @@ impl PartitionRuleManager {
 
     /// Find all leader peers of given table.
     pub async fn find_table_region_leaders(&self, table: &TableName) -> Result<Vec<Peer>> {
+        let peers = self.find_table_region_peers(table).await?;
+        Ok(peers.into_iter().map(|(_, peer)| peer).collect())
+    }
+
+    /// Find all regions of given table, along with their leader peers.
+    pub async fn find_table_region_peers(
+        &self,
+        table: &TableName,
+    ) -> Result<Vec<(RegionId, Peer)>> {
         let route = self.table_routes.get_route(table).await?;
         let mut peers = Vec::with_capacity(route.region_routes.len());
         for peer in &route.region_routes {
-            peers.push(peer.leader_peer.clone().with_context(|| FindLeaderSnafu {
+            let leader = peer.leader_peer.clone().with_context(|| FindLeaderSnafu {
                 region_id: peer.region.id,
                 table_name: table.to_string(),
-            })?);
+            })?;
+            peers.push((RegionId::from_u64(peer.region.id), leader));
         }
 
         Ok(peers)
@@ -173,6 +184,12 @@ impl PartitionRuleManager {
             }
         );
 
+        if let Some(PartitionBound::Hash { .. }) =
+            partitions[0].partition.partition_bounds().first()
+        {
+            return new_hash_partition_rule(table, &partitions);
+        }
+
         let regions = partitions
             .iter()
             .map(|x| x.id.region_number())
@@ -186,7 +203,7 @@ impl PartitionRuleManager {
                     .iter()
                     .filter_map(|info| match &info.partition.partition_bounds()[0] {
                         PartitionBound::Value(v) => Some(v.clone()),
-                        PartitionBound::MaxValue => None,
+                        PartitionBound::MaxValue | PartitionBound::Hash { .. } => None,
                     })
                     .collect::<Vec<Value>>();
                 Arc::new(RangePartitionRule::new(
@@ -257,6 +274,47 @@ impl PartitionRuleManager {
     }
 }
 
+/// Creates the [HashPartitionRule] from the partitions, which are sorted by their bounds, i.e.,
+/// by the hash remainders.
+fn new_hash_partition_rule(
+    table: &TableName,
+    partitions: &[PartitionInfo],
+) -> Result<PartitionRuleRef> {
+    let partition_columns = partitions[0].partition.partition_columns();
+    ensure!(
+        partition_columns.len() == 1,
+        error::InvalidTableRouteDataSnafu {
+            table_name: table.to_string(),
+            err_msg: "hash partitioning must have exactly one partition column"
+        }
+    );
+
+    let modulus = partitions.len();
+    let mut regions = Vec::with_capacity(modulus);
+    for (i, info) in partitions.iter().enumerate() {
+        let bounds = info.partition.partition_bounds();
+        ensure!(
+            matches!(
+                bounds.as_slice(),
+                [PartitionBound::Hash { modulus: m, remainder: r }]
+                    if *m as usize == modulus && *r as usize == i
+            ),
+            error::InvalidTableRouteDataSnafu {
+                table_name: table.to_string(),
+                err_msg: format!(
+                    "unexpected bounds {:?} of region {}, expect hash bound with modulus {} and remainder {}",
+                    bounds, info.id, modulus, i
+                )
+            }
+        );
+        regions.push(info.id.region_number());
+    }
+    Ok(Arc::new(HashPartitionRule::new(
+        partition_columns[0].clone(),
+        regions,
+    )))
+}
+
 fn find_regions0(partition_rule: PartitionRuleRef, filter: &Expr) -> Result<HashSet<RegionNumber>> {
     let expr = filter.df_expr();
     match expr {
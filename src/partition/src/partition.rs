@@ -43,11 +43,16 @@ pub trait PartitionRule: Sync + Send {
     fn find_regions_by_exprs(&self, exprs: &[PartitionExpr]) -> Result<Vec<RegionNumber>>;
 }
 
-/// The right bound(exclusive) of partition range.
+/// The right bound(exclusive) of partition range, or the hash bound of hash partitioning.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum PartitionBound {
     Value(Value),
     MaxValue,
+    /// The partition holds the values whose `hash(value) % modulus == remainder`.
+    Hash {
+        modulus: u32,
+        remainder: u32,
+    },
 }
 
 #[derive(Debug)]
@@ -198,4 +203,39 @@ mod tests {
         assert!(b1 < b2);
         assert!(b2 < b3);
     }
+
+    #[test]
+    fn test_hash_partition_def() {
+        let def = PartitionDef {
+            partition_columns: vec!["a".to_string()],
+            partition_bounds: vec![PartitionBound::Hash {
+                modulus: 4,
+                remainder: 1,
+            }],
+        };
+        let partition: MetaPartition = def.try_into().unwrap();
+        assert_eq!(
+            r#"{"column_list":"a","value_list":"{\"Hash\":{\"modulus\":4,\"remainder\":1}}"}"#,
+            serde_json::to_string(&partition).unwrap(),
+        );
+
+        let def: PartitionDef = partition.try_into().unwrap();
+        assert_eq!(
+            def.partition_bounds,
+            vec![PartitionBound::Hash {
+                modulus: 4,
+                remainder: 1
+            }]
+        );
+
+        let b1 = PartitionBound::Hash {
+            modulus: 4,
+            remainder: 0,
+        };
+        let b2 = PartitionBound::Hash {
+            modulus: 4,
+            remainder: 3,
+        };
+        assert!(b1 < b2);
+    }
 }
@@ -113,6 +113,17 @@ pub enum Error {
 
     #[snafu(display("Cannot find column {col}, location: {}", location))]
     ColumnNotFound { col: String, location: Location },
+
+    #[snafu(display(
+        "Invalid regular expression {regex} in function {func}, source: {source}, location: {}",
+        location
+    ))]
+    InvalidRegularExpression {
+        func: String,
+        regex: String,
+        source: regex::Error,
+        location: Location,
+    },
 }
 
 impl ErrorExt for Error {
@@ -128,6 +139,7 @@ impl ErrorExt for Error {
             | ExpectRangeSelector { .. }
             | ZeroRangeSelector { .. }
             | ColumnNotFound { .. }
+            | InvalidRegularExpression { .. }
             | Deserialize { .. } => StatusCode::InvalidArguments,
 
             UnknownTable { .. }
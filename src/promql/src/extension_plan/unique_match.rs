@@ -0,0 +1,397 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::collections::HashSet;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::arrow::row::{OwnedRow, RowConverter, SortField};
+use datafusion::arrow::util::display::array_value_to_string;
+use datafusion::common::DFSchemaRef;
+use datafusion::error::{DataFusionError, Result as DataFusionResult};
+use datafusion::execution::context::TaskContext;
+use datafusion::logical_expr::{Expr, LogicalPlan, UserDefinedLogicalNodeCore};
+use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
+use datafusion::physical_plan::{
+    DisplayFormatType, Distribution, ExecutionPlan, Partitioning, RecordBatchStream,
+    SendableRecordBatchStream, Statistics,
+};
+use futures::{Stream, StreamExt};
+
+/// The series checked by [UniqueMatch] in a binary operation with vector matching.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UniqueMatchTarget {
+    /// Series of the left side, which must be unique in each match group.
+    Left,
+    /// Series of the right side, which must be unique in each match group.
+    Right,
+    /// Series of the result of `group_left` or `group_right`, which must have
+    /// unique labels.
+    Output,
+}
+
+impl UniqueMatchTarget {
+    fn error_message(&self) -> &'static str {
+        match self {
+            UniqueMatchTarget::Left => {
+                "multiple matches for labels: many-to-one matching must be explicit (group_left/group_right)"
+            }
+            UniqueMatchTarget::Right => {
+                "found duplicate series for the match group on the right hand-side of the operation, \
+                 many-to-many matching not allowed: matching labels must be unique on one side"
+            }
+            UniqueMatchTarget::Output => {
+                "multiple matches for labels: grouping labels must ensure unique matches"
+            }
+        }
+    }
+}
+
+/// Fails the query if more than one row of the input has the same values in the given
+/// columns, usually the labels and the time index. Otherwise the input is passed through.
+///
+/// This is how Prometheus rejects the ambiguous matches of vector matching.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct UniqueMatch {
+    columns: Vec<String>,
+    target: UniqueMatchTarget,
+    input: LogicalPlan,
+}
+
+impl UserDefinedLogicalNodeCore for UniqueMatch {
+    fn name(&self) -> &str {
+        Self::name()
+    }
+
+    fn inputs(&self) -> Vec<&LogicalPlan> {
+        vec![&self.input]
+    }
+
+    fn schema(&self) -> &DFSchemaRef {
+        self.input.schema()
+    }
+
+    fn expressions(&self) -> Vec<Expr> {
+        vec![]
+    }
+
+    fn fmt_for_explain(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "PromUniqueMatch: columns={:?}, target={:?}",
+            self.columns, self.target
+        )
+    }
+
+    fn from_template(&self, _exprs: &[Expr], inputs: &[LogicalPlan]) -> Self {
+        assert!(!inputs.is_empty());
+
+        Self {
+            columns: self.columns.clone(),
+            target: self.target,
+            input: inputs[0].clone(),
+        }
+    }
+}
+
+impl UniqueMatch {
+    pub fn new(columns: Vec<String>, target: UniqueMatchTarget, input: LogicalPlan) -> Self {
+        Self {
+            columns,
+            target,
+            input,
+        }
+    }
+
+    pub const fn name() -> &'static str {
+        "UniqueMatch"
+    }
+
+    pub fn to_execution_plan(&self, exec_input: Arc<dyn ExecutionPlan>) -> Arc<dyn ExecutionPlan> {
+        Arc::new(UniqueMatchExec {
+            columns: self.columns.clone(),
+            target: self.target,
+            input: exec_input,
+            metric: ExecutionPlanMetricsSet::new(),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct UniqueMatchExec {
+    columns: Vec<String>,
+    target: UniqueMatchTarget,
+    input: Arc<dyn ExecutionPlan>,
+    metric: ExecutionPlanMetricsSet,
+}
+
+impl ExecutionPlan for UniqueMatchExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.input.schema()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    // Rows in different partitions may have the same values.
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        vec![Distribution::SinglePartition]
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        self.input.output_ordering()
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        vec![true; self.children().len()]
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        assert!(!children.is_empty());
+        Ok(Arc::new(Self {
+            columns: self.columns.clone(),
+            target: self.target,
+            input: children[0].clone(),
+            metric: self.metric.clone(),
+        }))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> DataFusionResult<SendableRecordBatchStream> {
+        let baseline_metric = BaselineMetrics::new(&self.metric, partition);
+
+        let input = self.input.execute(partition, context)?;
+        let schema = input.schema();
+        let column_indices = self
+            .columns
+            .iter()
+            .map(|column| {
+                schema
+                    .column_with_name(column)
+                    .unwrap_or_else(|| panic!("column not found {column}"))
+                    .0
+            })
+            .collect::<Vec<_>>();
+        let row_converter = RowConverter::new(
+            column_indices
+                .iter()
+                .map(|i| SortField::new(schema.field(*i).data_type().clone()))
+                .collect(),
+        )?;
+        Ok(Box::pin(UniqueMatchStream {
+            column_indices,
+            target: self.target,
+            row_converter,
+            seen: HashSet::new(),
+            schema,
+            input,
+            metric: baseline_metric,
+        }))
+    }
+
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default => {
+                write!(
+                    f,
+                    "PromUniqueMatchExec: columns={:?}, target={:?}",
+                    self.columns, self.target
+                )
+            }
+        }
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metric.clone_inner())
+    }
+
+    fn statistics(&self) -> Statistics {
+        self.input.statistics()
+    }
+}
+
+pub struct UniqueMatchStream {
+    column_indices: Vec<usize>,
+    target: UniqueMatchTarget,
+    row_converter: RowConverter,
+    /// Values of the checked columns of all rows seen so far.
+    seen: HashSet<OwnedRow>,
+    schema: SchemaRef,
+    input: SendableRecordBatchStream,
+    metric: BaselineMetrics,
+}
+
+impl RecordBatchStream for UniqueMatchStream {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+impl Stream for UniqueMatchStream {
+    type Item = DataFusionResult<RecordBatch>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let poll = match self.input.poll_next_unpin(cx) {
+            Poll::Ready(batch) => {
+                let _timer = self.metric.elapsed_compute().timer();
+                Poll::Ready(batch.map(|batch| batch.and_then(|batch| self.check(batch))))
+            }
+            Poll::Pending => Poll::Pending,
+        };
+        self.metric.record_poll(poll)
+    }
+}
+
+impl UniqueMatchStream {
+    fn check(&mut self, batch: RecordBatch) -> DataFusionResult<RecordBatch> {
+        let columns = self
+            .column_indices
+            .iter()
+            .map(|i| batch.column(*i).clone())
+            .collect::<Vec<_>>();
+        let rows = self.row_converter.convert_columns(&columns)?;
+        for (row_index, row) in rows.iter().enumerate() {
+            if self.seen.insert(row.owned()) {
+                continue;
+            }
+
+            let values = self
+                .column_indices
+                .iter()
+                .zip(&columns)
+                .map(|(i, column)| {
+                    let value = array_value_to_string(column, row_index)?;
+                    Ok(format!("{}={}", self.schema.field(*i).name(), value))
+                })
+                .collect::<DataFusionResult<Vec<_>>>()?;
+            return Err(DataFusionError::Execution(format!(
+                "{}, duplicate series: {{{}}}",
+                self.target.error_message(),
+                values.join(", ")
+            )));
+        }
+        Ok(batch)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use datafusion::arrow::array::{StringArray, TimestampMillisecondArray};
+    use datafusion::arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+    use datafusion::from_slice::FromSlice;
+    use datafusion::physical_plan::memory::MemoryExec;
+    use datafusion::prelude::SessionContext;
+
+    use super::*;
+
+    fn prepare_test_data(hosts: &[&str]) -> MemoryExec {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("host", DataType::Utf8, true),
+            Field::new(
+                "timestamp",
+                DataType::Timestamp(TimeUnit::Millisecond, None),
+                true,
+            ),
+        ]));
+        // two batches with the same timestamps
+        let batches = [0, 1]
+            .into_iter()
+            .map(|batch| {
+                let hosts = hosts
+                    .iter()
+                    .skip(batch * hosts.len() / 2)
+                    .take(hosts.len() / 2)
+                    .copied()
+                    .collect::<Vec<_>>();
+                let timestamps = vec![0; hosts.len()];
+                RecordBatch::try_new(
+                    schema.clone(),
+                    vec![
+                        Arc::new(StringArray::from_slice(hosts)) as _,
+                        Arc::new(TimestampMillisecondArray::from_slice(timestamps)) as _,
+                    ],
+                )
+                .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        MemoryExec::try_new(&[batches], schema, None).unwrap()
+    }
+
+    async fn do_unique_match_test(
+        hosts: &[&str],
+        target: UniqueMatchTarget,
+    ) -> DataFusionResult<usize> {
+        let unique_match_exec = Arc::new(UniqueMatchExec {
+            columns: vec!["host".to_string(), "timestamp".to_string()],
+            target,
+            input: Arc::new(prepare_test_data(hosts)),
+            metric: ExecutionPlanMetricsSet::new(),
+        });
+        let session_context = SessionContext::default();
+        let result =
+            datafusion::physical_plan::collect(unique_match_exec, session_context.task_ctx())
+                .await?;
+        Ok(result.iter().map(|batch| batch.num_rows()).sum())
+    }
+
+    #[tokio::test]
+    async fn unique_rows() {
+        let rows = do_unique_match_test(&["a", "b", "c", "d"], UniqueMatchTarget::Right)
+            .await
+            .unwrap();
+        assert_eq!(4, rows);
+    }
+
+    #[tokio::test]
+    async fn duplicate_rows_in_batch() {
+        let err = do_unique_match_test(&["a", "a", "c", "d"], UniqueMatchTarget::Right)
+            .await
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("found duplicate series for the match group on the right hand-side"));
+        assert!(err.to_string().contains("{host=a, timestamp="));
+    }
+
+    #[tokio::test]
+    async fn duplicate_rows_across_batches() {
+        let err = do_unique_match_test(&["a", "b", "c", "a"], UniqueMatchTarget::Output)
+            .await
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("grouping labels must ensure unique matches"));
+    }
+}
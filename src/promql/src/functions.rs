@@ -16,10 +16,13 @@ mod aggr_over_time;
 mod changes;
 mod deriv;
 mod extrapolate_rate;
+mod format_value;
+mod histogram_quantile;
 mod holt_winters;
 mod idelta;
 mod predict_linear;
 mod quantile;
+mod quantile_aggr;
 mod resets;
 #[cfg(test)]
 mod test_util;
@@ -34,10 +37,13 @@ use datafusion::error::DataFusionError;
 use datafusion::physical_plan::ColumnarValue;
 pub use deriv::Deriv;
 pub use extrapolate_rate::{Delta, Increase, Rate};
+pub use format_value::FormatValue;
+pub use histogram_quantile::{HistogramQuantile, LE_COLUMN_NAME};
 pub use holt_winters::HoltWinters;
 pub use idelta::IDelta;
 pub use predict_linear::PredictLinear;
 pub use quantile::QuantileOverTime;
+pub use quantile_aggr::QuantileAggr;
 pub use resets::Resets;
 
 pub(crate) fn extract_array(columnar_value: &ColumnarValue) -> Result<ArrayRef, DataFusionError> {
@@ -0,0 +1,117 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::Arc;
+
+use datafusion::arrow::array::{Array, Float64Array, StringArray};
+use datafusion::common::DataFusionError;
+use datafusion::logical_expr::{ScalarUDF, Signature, TypeSignature, Volatility};
+use datafusion::physical_plan::ColumnarValue;
+use datatypes::arrow::datatypes::DataType;
+
+use crate::functions::extract_array;
+
+/// Formats sample values to strings as Prometheus does, i.e. the shortest decimal
+/// representation without exponent, and `NaN`, `+Inf` or `-Inf` for special values.
+/// Used by `count_values` to put the values into labels.
+pub struct FormatValue;
+
+impl FormatValue {
+    pub const fn name() -> &'static str {
+        "prom_format_value"
+    }
+
+    pub fn scalar_udf() -> ScalarUDF {
+        ScalarUDF {
+            name: Self::name().to_string(),
+            signature: Signature::new(
+                TypeSignature::Exact(vec![DataType::Float64]),
+                Volatility::Immutable,
+            ),
+            return_type: Arc::new(|_| Ok(Arc::new(DataType::Utf8))),
+            fun: Arc::new(Self::calc),
+        }
+    }
+
+    fn calc(input: &[ColumnarValue]) -> Result<ColumnarValue, DataFusionError> {
+        assert_eq!(input.len(), 1);
+        let array = extract_array(&input[0])?;
+        let values = array
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .ok_or_else(|| {
+                DataFusionError::Execution(format!(
+                    "{}: expect Float64 as input, found {}",
+                    Self::name(),
+                    array.data_type()
+                ))
+            })?;
+
+        let result = values
+            .iter()
+            .map(|value| value.map(format_value))
+            .collect::<StringArray>();
+        Ok(ColumnarValue::Array(Arc::new(result)))
+    }
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.to_string()
+    } else {
+        // `Display` of f64 never uses exponent, and prints the shortest digits
+        // that round trip, same as `strconv.FormatFloat(v, 'f', -1, 64)` in Go.
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_value() {
+        let udf = FormatValue::scalar_udf();
+
+        let input = ColumnarValue::Array(Arc::new(Float64Array::from(vec![
+            Some(1.0),
+            Some(-0.5),
+            Some(1e21),
+            Some(1e-7),
+            Some(0.1 + 0.2),
+            Some(f64::NAN),
+            Some(f64::INFINITY),
+            Some(f64::NEG_INFINITY),
+            None,
+        ])));
+        let ColumnarValue::Array(result) = (udf.fun)(&[input]).unwrap() else {
+            unreachable!()
+        };
+        assert_eq!(
+            &StringArray::from(vec![
+                Some("1"),
+                Some("-0.5"),
+                Some("1000000000000000000000"),
+                Some("0.0000001"),
+                Some("0.30000000000000004"),
+                Some("NaN"),
+                Some("+Inf"),
+                Some("-Inf"),
+                None,
+            ]),
+            result.as_any().downcast_ref::<StringArray>().unwrap()
+        );
+    }
+}
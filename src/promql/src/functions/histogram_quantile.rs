@@ -0,0 +1,318 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use datafusion::arrow::array::{Array, ArrayRef, Float64Array, ListArray, StringArray};
+use datafusion::common::{DataFusionError, Result as DfResult, ScalarValue};
+use datafusion::logical_expr::{
+    Accumulator, AccumulatorFunctionImplementation, AggregateUDF, ReturnTypeFunction, Signature,
+    StateTypeFunction, TypeSignature, Volatility,
+};
+use datatypes::arrow::datatypes::{DataType, Field};
+
+/// The label name of bucket's upper bound in classic histograms.
+pub const LE_COLUMN_NAME: &str = "le";
+
+/// `histogram_quantile` function of PromQL. Calculates the φ-quantile (0 ≤ φ ≤ 1)
+/// from the buckets of a classic histogram.
+///
+/// This is planned as an aggregation which groups all bucket series with the same
+/// labels except `le` at the same timestamp. It accepts two arguments, the `le`
+/// label column and the bucket's (cumulative) count.
+pub struct HistogramQuantile;
+
+impl HistogramQuantile {
+    pub const fn name() -> &'static str {
+        "prom_histogram_quantile"
+    }
+
+    pub fn aggregate_udf(quantile: f64) -> AggregateUDF {
+        let return_type: ReturnTypeFunction = Arc::new(|_| Ok(Arc::new(DataType::Float64)));
+        let accumulator: AccumulatorFunctionImplementation =
+            Arc::new(move |_| Ok(Box::new(HistogramQuantileAccumulator::new(quantile))));
+        let state_type: StateTypeFunction =
+            Arc::new(|_| Ok(Arc::new(vec![Self::state_type(), Self::state_type()])));
+
+        AggregateUDF::new(
+            Self::name(),
+            &Signature::new(
+                TypeSignature::Exact(vec![DataType::Utf8, DataType::Float64]),
+                Volatility::Immutable,
+            ),
+            &return_type,
+            &accumulator,
+            &state_type,
+        )
+    }
+
+    fn state_type() -> DataType {
+        DataType::List(Arc::new(Field::new("item", DataType::Float64, true)))
+    }
+}
+
+/// Accumulator of [HistogramQuantile]. Buffers the upper bound and count of
+/// every bucket until evaluation.
+#[derive(Debug)]
+pub struct HistogramQuantileAccumulator {
+    quantile: f64,
+    upper_bounds: Vec<f64>,
+    counts: Vec<f64>,
+}
+
+impl HistogramQuantileAccumulator {
+    fn new(quantile: f64) -> Self {
+        Self {
+            quantile,
+            upper_bounds: vec![],
+            counts: vec![],
+        }
+    }
+
+    fn downcast_array<'a, T: 'static>(array: &'a ArrayRef, expect: &str) -> DfResult<&'a T> {
+        array.as_any().downcast_ref::<T>().ok_or_else(|| {
+            DataFusionError::Execution(format!(
+                "{}: expect {} array, found {}",
+                HistogramQuantile::name(),
+                expect,
+                array.data_type()
+            ))
+        })
+    }
+}
+
+impl Accumulator for HistogramQuantileAccumulator {
+    fn state(&self) -> DfResult<Vec<ScalarValue>> {
+        let to_list = |values: &[f64]| {
+            ScalarValue::new_list(
+                Some(
+                    values
+                        .iter()
+                        .map(|v| ScalarValue::Float64(Some(*v)))
+                        .collect(),
+                ),
+                DataType::Float64,
+            )
+        };
+        Ok(vec![to_list(&self.upper_bounds), to_list(&self.counts)])
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> DfResult<()> {
+        let le_array = Self::downcast_array::<StringArray>(&values[0], "Utf8")?;
+        let count_array = Self::downcast_array::<Float64Array>(&values[1], "Float64")?;
+
+        for (le, count) in le_array.iter().zip(count_array.iter()) {
+            let (Some(le), Some(count)) = (le, count) else {
+                continue;
+            };
+            let upper_bound = parse_upper_bound(le).ok_or_else(|| {
+                DataFusionError::Execution(format!(
+                    "{}: invalid bucket upper bound {le}",
+                    HistogramQuantile::name()
+                ))
+            })?;
+            self.upper_bounds.push(upper_bound);
+            self.counts.push(count);
+        }
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> DfResult<()> {
+        let upper_bounds = Self::downcast_array::<ListArray>(&states[0], "List")?;
+        let counts = Self::downcast_array::<ListArray>(&states[1], "List")?;
+
+        for (upper_bound, count) in upper_bounds.iter().zip(counts.iter()) {
+            let (Some(upper_bound), Some(count)) = (upper_bound, count) else {
+                continue;
+            };
+            let upper_bound = Self::downcast_array::<Float64Array>(&upper_bound, "Float64")?;
+            let count = Self::downcast_array::<Float64Array>(&count, "Float64")?;
+            self.upper_bounds.extend(upper_bound.iter().flatten());
+            self.counts.extend(count.iter().flatten());
+        }
+        Ok(())
+    }
+
+    fn evaluate(&self) -> DfResult<ScalarValue> {
+        if self.upper_bounds.is_empty() {
+            return Ok(ScalarValue::Float64(None));
+        }
+        let buckets = self
+            .upper_bounds
+            .iter()
+            .cloned()
+            .zip(self.counts.iter().cloned())
+            .collect();
+        Ok(ScalarValue::Float64(Some(bucket_quantile(
+            self.quantile,
+            buckets,
+        ))))
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self)
+            + (self.upper_bounds.capacity() + self.counts.capacity()) * std::mem::size_of::<f64>()
+    }
+}
+
+fn parse_upper_bound(le: &str) -> Option<f64> {
+    match le {
+        "+Inf" | "Inf" | "inf" => Some(f64::INFINITY),
+        "-Inf" | "-inf" => Some(f64::NEG_INFINITY),
+        _ => le.parse::<f64>().ok(),
+    }
+}
+
+/// Calculate the quantile from buckets of `(upper bound, cumulative count)`.
+///
+/// Refer to https://github.com/prometheus/prometheus/blob/6e2905a4d4ff9b47b1f6d201333f5bd53633f921/promql/quantile.go#L71-L121
+fn bucket_quantile(quantile: f64, mut buckets: Vec<(f64, f64)>) -> f64 {
+    if quantile.is_nan() {
+        return f64::NAN;
+    }
+    if quantile < 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if quantile > 1.0 {
+        return f64::INFINITY;
+    }
+
+    buckets.sort_unstable_by(|a, b| a.0.total_cmp(&b.0));
+    match buckets.last() {
+        Some((upper_bound, _)) if upper_bound.is_infinite() && *upper_bound > 0.0 => {}
+        _ => return f64::NAN,
+    }
+
+    // coalesce buckets with the same upper bound
+    let mut coalesced: Vec<(f64, f64)> = Vec::with_capacity(buckets.len());
+    for (upper_bound, count) in buckets {
+        match coalesced.last_mut() {
+            Some(last) if last.0 == upper_bound => last.1 += count,
+            _ => coalesced.push((upper_bound, count)),
+        }
+    }
+    let mut buckets = coalesced;
+
+    // ensure counts are monotonic, as buckets may be scraped at different moments
+    let mut max = f64::NEG_INFINITY;
+    for (_, count) in buckets.iter_mut() {
+        if *count < max {
+            *count = max;
+        } else {
+            max = *count;
+        }
+    }
+
+    if buckets.len() < 2 {
+        return f64::NAN;
+    }
+    let observations = buckets[buckets.len() - 1].1;
+    if observations == 0.0 {
+        return f64::NAN;
+    }
+    let mut rank = quantile * observations;
+    let index = buckets
+        .iter()
+        .position(|(_, count)| *count >= rank)
+        .unwrap_or(buckets.len() - 1);
+
+    if index == buckets.len() - 1 {
+        return buckets[buckets.len() - 2].0;
+    }
+    if index == 0 && buckets[0].0 <= 0.0 {
+        return buckets[0].0;
+    }
+
+    let mut bucket_start = 0.0;
+    let bucket_end = buckets[index].0;
+    let mut count = buckets[index].1;
+    if index > 0 {
+        bucket_start = buckets[index - 1].0;
+        count -= buckets[index - 1].1;
+        rank -= buckets[index - 1].1;
+    }
+    bucket_start + (bucket_end - bucket_start) * (rank / count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn buckets() -> Vec<(f64, f64)> {
+        vec![(0.5, 10.0), (2.0, 30.0), (f64::INFINITY, 40.0), (1.0, 20.0)]
+    }
+
+    #[test]
+    fn bucket_quantile_interpolation() {
+        // rank 20 falls exactly at the end of (0.5, 1.0]
+        assert_eq!(bucket_quantile(0.5, buckets()), 1.0);
+        // rank 5 is in the middle of the first bucket
+        assert_eq!(bucket_quantile(0.125, buckets()), 0.25);
+        // rank 36 is in the +Inf bucket, returns the highest finite bound
+        assert_eq!(bucket_quantile(0.9, buckets()), 2.0);
+    }
+
+    #[test]
+    fn bucket_quantile_special_cases() {
+        assert!(bucket_quantile(f64::NAN, buckets()).is_nan());
+        assert_eq!(bucket_quantile(-1.0, buckets()), f64::NEG_INFINITY);
+        assert_eq!(bucket_quantile(2.0, buckets()), f64::INFINITY);
+        // no +Inf bucket
+        assert!(bucket_quantile(0.5, vec![(0.1, 1.0), (0.5, 2.0)]).is_nan());
+        // no observation
+        assert!(bucket_quantile(0.5, vec![(0.1, 0.0), (f64::INFINITY, 0.0)]).is_nan());
+        // only one bucket
+        assert!(bucket_quantile(0.5, vec![(f64::INFINITY, 10.0)]).is_nan());
+    }
+
+    #[test]
+    fn bucket_quantile_non_monotonic() {
+        let buckets = vec![(0.5, 10.0), (1.0, 8.0), (2.0, 20.0), (f64::INFINITY, 20.0)];
+        // (1.0, 8.0) is fixed to (1.0, 10.0)
+        assert_eq!(bucket_quantile(0.75, buckets), 1.5);
+    }
+
+    #[test]
+    fn accumulate_buckets() {
+        let mut accumulator = HistogramQuantileAccumulator::new(0.5);
+        let le: ArrayRef = Arc::new(StringArray::from(vec!["0.5", "+Inf"]));
+        let count: ArrayRef = Arc::new(Float64Array::from(vec![10.0, 40.0]));
+        accumulator.update_batch(&[le, count]).unwrap();
+
+        let mut other = HistogramQuantileAccumulator::new(0.5);
+        let le: ArrayRef = Arc::new(StringArray::from(vec!["2", "1"]));
+        let count: ArrayRef = Arc::new(Float64Array::from(vec![30.0, 20.0]));
+        other.update_batch(&[le, count]).unwrap();
+        let states = other
+            .state()
+            .unwrap()
+            .into_iter()
+            .map(|state| state.to_array())
+            .collect::<Vec<_>>();
+        accumulator.merge_batch(&states).unwrap();
+
+        assert_eq!(
+            accumulator.evaluate().unwrap(),
+            ScalarValue::Float64(Some(1.0))
+        );
+    }
+
+    #[test]
+    fn invalid_upper_bound() {
+        let mut accumulator = HistogramQuantileAccumulator::new(0.5);
+        let le: ArrayRef = Arc::new(StringArray::from(vec!["abc"]));
+        let count: ArrayRef = Arc::new(Float64Array::from(vec![10.0]));
+        assert!(accumulator.update_batch(&[le, count]).is_err());
+    }
+}
@@ -0,0 +1,195 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use datafusion::arrow::array::{Array, ArrayRef, Float64Array, ListArray};
+use datafusion::common::{DataFusionError, Result as DfResult, ScalarValue};
+use datafusion::logical_expr::{
+    Accumulator, AccumulatorFunctionImplementation, AggregateUDF, ReturnTypeFunction, Signature,
+    StateTypeFunction, TypeSignature, Volatility,
+};
+use datatypes::arrow::datatypes::{DataType, Field};
+
+use crate::functions::quantile::quantile_impl;
+
+/// The `quantile` aggregation operator of PromQL. Calculates the φ-quantile
+/// (0 ≤ φ ≤ 1) over dimensions.
+pub struct QuantileAggr;
+
+impl QuantileAggr {
+    pub const fn name() -> &'static str {
+        "prom_quantile"
+    }
+
+    pub fn aggregate_udf(quantile: f64) -> AggregateUDF {
+        let return_type: ReturnTypeFunction = Arc::new(|_| Ok(Arc::new(DataType::Float64)));
+        let accumulator: AccumulatorFunctionImplementation =
+            Arc::new(move |_| Ok(Box::new(QuantileAccumulator::new(quantile))));
+        let state_type: StateTypeFunction = Arc::new(|_| Ok(Arc::new(vec![Self::state_type()])));
+
+        AggregateUDF::new(
+            Self::name(),
+            &Signature::new(
+                TypeSignature::Exact(vec![DataType::Float64]),
+                Volatility::Immutable,
+            ),
+            &return_type,
+            &accumulator,
+            &state_type,
+        )
+    }
+
+    fn state_type() -> DataType {
+        DataType::List(Arc::new(Field::new("item", DataType::Float64, true)))
+    }
+}
+
+/// Accumulator of [QuantileAggr]. All non-null values are buffered until
+/// evaluation, as the quantile cannot be merged incrementally.
+#[derive(Debug)]
+pub struct QuantileAccumulator {
+    quantile: f64,
+    values: Vec<f64>,
+}
+
+impl QuantileAccumulator {
+    fn new(quantile: f64) -> Self {
+        Self {
+            quantile,
+            values: vec![],
+        }
+    }
+
+    fn extend_from_array(&mut self, array: &ArrayRef) -> DfResult<()> {
+        let array = array
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .ok_or_else(|| {
+                DataFusionError::Execution(format!(
+                    "{}: expect Float64 as input type, found {}",
+                    QuantileAggr::name(),
+                    array.data_type()
+                ))
+            })?;
+        self.values.extend(array.iter().flatten());
+        Ok(())
+    }
+}
+
+impl Accumulator for QuantileAccumulator {
+    fn state(&self) -> DfResult<Vec<ScalarValue>> {
+        let values = self
+            .values
+            .iter()
+            .map(|v| ScalarValue::Float64(Some(*v)))
+            .collect();
+        Ok(vec![ScalarValue::new_list(Some(values), DataType::Float64)])
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> DfResult<()> {
+        self.extend_from_array(&values[0])
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> DfResult<()> {
+        let states = states[0]
+            .as_any()
+            .downcast_ref::<ListArray>()
+            .ok_or_else(|| {
+                DataFusionError::Execution(format!(
+                    "{}: expect List as state type, found {}",
+                    QuantileAggr::name(),
+                    states[0].data_type()
+                ))
+            })?;
+        for state in states.iter().flatten() {
+            self.extend_from_array(&state)?;
+        }
+        Ok(())
+    }
+
+    fn evaluate(&self) -> DfResult<ScalarValue> {
+        if self.values.is_empty() {
+            return Ok(ScalarValue::Float64(None));
+        }
+        Ok(ScalarValue::Float64(quantile_impl(
+            &self.values,
+            self.quantile,
+        )))
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self) + self.values.capacity() * std::mem::size_of::<f64>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn accumulate(quantile: f64, batches: Vec<Vec<Option<f64>>>) -> ScalarValue {
+        let mut accumulator = QuantileAccumulator::new(quantile);
+        for batch in batches {
+            let array: ArrayRef = Arc::new(Float64Array::from(batch));
+            accumulator.update_batch(&[array]).unwrap();
+        }
+        accumulator.evaluate().unwrap()
+    }
+
+    #[test]
+    fn quantile_of_batches() {
+        let result = accumulate(
+            0.5,
+            vec![vec![Some(3.0), Some(1.0)], vec![Some(5.0), None, Some(2.0)]],
+        );
+        assert_eq!(result, ScalarValue::Float64(Some(2.5)));
+
+        let result = accumulate(
+            0.25,
+            vec![
+                vec![Some(4.0), Some(1.0), Some(3.0)],
+                vec![Some(2.0), Some(5.0)],
+            ],
+        );
+        assert_eq!(result, ScalarValue::Float64(Some(2.0)));
+    }
+
+    #[test]
+    fn quantile_of_empty_input() {
+        assert_eq!(accumulate(0.5, vec![]), ScalarValue::Float64(None));
+        assert_eq!(
+            accumulate(0.5, vec![vec![None]]),
+            ScalarValue::Float64(None)
+        );
+    }
+
+    #[test]
+    fn merge_states() {
+        let mut first = QuantileAccumulator::new(0.5);
+        first
+            .update_batch(&[Arc::new(Float64Array::from(vec![1.0, 2.0])) as _])
+            .unwrap();
+        let mut second = QuantileAccumulator::new(0.5);
+        second
+            .update_batch(&[Arc::new(Float64Array::from(vec![3.0, 4.0, 5.0])) as _])
+            .unwrap();
+
+        let mut merged = QuantileAccumulator::new(0.5);
+        for accumulator in [first, second] {
+            let state = accumulator.state().unwrap()[0].to_array();
+            merged.merge_batch(&[state]).unwrap();
+        }
+        assert_eq!(merged.evaluate().unwrap(), ScalarValue::Float64(Some(3.0)));
+    }
+}
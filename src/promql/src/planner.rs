@@ -15,17 +15,20 @@
 use std::collections::{BTreeSet, HashSet, VecDeque};
 use std::str::FromStr;
 use std::sync::Arc;
-use std::time::UNIX_EPOCH;
+use std::time::{Duration, UNIX_EPOCH};
 
 use async_recursion::async_recursion;
 use catalog::table_source::DfTableSourceProvider;
 use datafusion::common::{DFSchemaRef, OwnedTableReference, Result as DfResult};
 use datafusion::datasource::DefaultTableSource;
-use datafusion::logical_expr::expr::{AggregateFunction, ScalarFunction, ScalarUDF};
-use datafusion::logical_expr::expr_rewriter::normalize_cols;
+use datafusion::logical_expr::expr::{
+    AggregateFunction, ScalarFunction, ScalarUDF, WindowFunction,
+};
+use datafusion::logical_expr::expr_rewriter::{normalize_col, normalize_cols};
 use datafusion::logical_expr::{
-    AggregateFunction as AggregateFunctionEnum, BinaryExpr, BuiltinScalarFunction, Cast, Extension,
-    LogicalPlan, LogicalPlanBuilder, Operator, ScalarUDF as ScalarUdfDef,
+    when, AggregateFunction as AggregateFunctionEnum, BinaryExpr, BuiltInWindowFunction,
+    BuiltinScalarFunction, Cast, Extension, LogicalPlan, LogicalPlanBuilder, Operator,
+    ScalarUDF as ScalarUdfDef, WindowFrame, WindowFunction as WindowFunctionEnum,
 };
 use datafusion::optimizer::utils;
 use datafusion::prelude as df_prelude;
@@ -35,32 +38,43 @@ use datafusion::sql::TableReference;
 use datatypes::arrow::datatypes::DataType as ArrowDataType;
 use promql_parser::label::{MatchOp, Matcher, Matchers, METRIC_NAME};
 use promql_parser::parser::{
-    token, AggregateExpr, BinaryExpr as PromBinaryExpr, Call, EvalStmt, Expr as PromExpr, Function,
-    LabelModifier, MatrixSelector, NumberLiteral, Offset, ParenExpr, StringLiteral, SubqueryExpr,
-    TokenType, UnaryExpr, VectorSelector,
+    token, AggregateExpr, BinModifier, BinaryExpr as PromBinaryExpr, Call, EvalStmt,
+    Expr as PromExpr, Function, LabelModifier, MatrixSelector, NumberLiteral, Offset, ParenExpr,
+    StringLiteral, SubqueryExpr, TokenType, UnaryExpr, VectorMatchCardinality, VectorSelector,
 };
+use regex::Regex;
 use snafu::{ensure, OptionExt, ResultExt};
 use table::table::adapter::DfTableProviderAdapter;
 
 use crate::error::{
     CatalogSnafu, ColumnNotFoundSnafu, DataFusionPlanningSnafu, ExpectExprSnafu,
-    ExpectRangeSelectorSnafu, MultipleVectorSnafu, Result, TableNameNotFoundSnafu,
-    TimeIndexNotFoundSnafu, UnexpectedPlanExprSnafu, UnexpectedTokenSnafu, UnknownTableSnafu,
-    UnsupportedExprSnafu, ValueNotFoundSnafu, ZeroRangeSelectorSnafu,
+    ExpectRangeSelectorSnafu, InvalidRegularExpressionSnafu, MultipleVectorSnafu, Result,
+    TableNameNotFoundSnafu, TimeIndexNotFoundSnafu, UnexpectedPlanExprSnafu, UnexpectedTokenSnafu,
+    UnknownTableSnafu, UnsupportedExprSnafu, ValueNotFoundSnafu, ZeroRangeSelectorSnafu,
 };
 use crate::extension_plan::{
     build_special_time_expr, EmptyMetric, InstantManipulate, Millisecond, RangeManipulate,
-    SeriesDivide, SeriesNormalize,
+    SeriesDivide, SeriesNormalize, UniqueMatch, UniqueMatchTarget,
 };
 use crate::functions::{
-    AbsentOverTime, AvgOverTime, Changes, CountOverTime, Delta, Deriv, HoltWinters, IDelta,
-    Increase, LastOverTime, MaxOverTime, MinOverTime, PredictLinear, PresentOverTime,
-    QuantileOverTime, Rate, Resets, StddevOverTime, StdvarOverTime, SumOverTime,
+    AbsentOverTime, AvgOverTime, Changes, CountOverTime, Delta, Deriv, FormatValue,
+    HistogramQuantile, HoltWinters, IDelta, Increase, LastOverTime, MaxOverTime, MinOverTime,
+    PredictLinear, PresentOverTime, QuantileAggr, QuantileOverTime, Rate, Resets, StddevOverTime,
+    StdvarOverTime, SumOverTime, LE_COLUMN_NAME,
 };
 
 /// `time()` function in PromQL.
 const SPECIAL_TIME_FUNCTION: &str = "time";
 
+/// `histogram_quantile()` function in PromQL.
+const HISTOGRAM_QUANTILE_FUNCTION: &str = "histogram_quantile";
+
+/// `label_replace()` function in PromQL.
+const LABEL_REPLACE_FUNCTION: &str = "label_replace";
+
+/// `label_join()` function in PromQL.
+const LABEL_JOIN_FUNCTION: &str = "label_join";
+
 const DEFAULT_TIME_INDEX_COLUMN: &str = "time";
 
 /// default value column name for empty metric
@@ -69,6 +83,19 @@ const DEFAULT_FIELD_COLUMN: &str = "value";
 /// Special modifier to project field columns under multi-field mode
 const FIELD_COLUMN_MATCHER: &str = "__field__";
 
+/// Alias of the left side's plan in binary operation with vector matching
+const LEFT_SIDE_ALIAS: &str = "lhs";
+
+/// Alias of the right side's plan in binary operation with vector matching
+const RIGHT_SIDE_ALIAS: &str = "rhs";
+
+/// Temporary column for the rank of series in `topk` and `bottomk`
+const RANK_COLUMN_NAME: &str = "__rank__";
+
+/// The default step of subqueries without one, the same as the default
+/// `evaluation_interval` of Prometheus.
+pub const DEFAULT_EVALUATION_INTERVAL: Duration = Duration::from_secs(60);
+
 #[derive(Default, Debug, Clone)]
 struct PromPlannerContext {
     // query parameters
@@ -76,6 +103,8 @@ struct PromPlannerContext {
     end: Millisecond,
     interval: Millisecond,
     lookback_delta: Millisecond,
+    /// The step of subqueries without one.
+    evaluation_interval: Millisecond,
 
     // planner states
     table_name: Option<String>,
@@ -88,12 +117,13 @@ struct PromPlannerContext {
 }
 
 impl PromPlannerContext {
-    fn from_eval_stmt(stmt: &EvalStmt) -> Self {
+    fn from_eval_stmt(stmt: &EvalStmt, evaluation_interval: Duration) -> Self {
         Self {
             start: stmt.start.duration_since(UNIX_EPOCH).unwrap().as_millis() as _,
             end: stmt.end.duration_since(UNIX_EPOCH).unwrap().as_millis() as _,
             interval: stmt.interval.as_millis() as _,
             lookback_delta: stmt.lookback_delta.as_millis() as _,
+            evaluation_interval: evaluation_interval.as_millis() as _,
             ..Default::default()
         }
     }
@@ -108,10 +138,25 @@ impl PromPlanner {
     pub async fn stmt_to_plan(
         table_provider: DfTableSourceProvider,
         stmt: EvalStmt,
+    ) -> Result<LogicalPlan> {
+        Self::stmt_to_plan_with_evaluation_interval(
+            table_provider,
+            stmt,
+            DEFAULT_EVALUATION_INTERVAL,
+        )
+        .await
+    }
+
+    /// Plans the statement, subqueries without a step are evaluated at
+    /// `evaluation_interval`.
+    pub async fn stmt_to_plan_with_evaluation_interval(
+        table_provider: DfTableSourceProvider,
+        stmt: EvalStmt,
+        evaluation_interval: Duration,
     ) -> Result<LogicalPlan> {
         let mut planner = Self {
             table_provider,
-            ctx: PromPlannerContext::from_eval_stmt(&stmt),
+            ctx: PromPlannerContext::from_eval_stmt(&stmt, evaluation_interval),
         };
         planner.prom_expr_to_plan(stmt.expr).await
     }
@@ -122,35 +167,42 @@ impl PromPlanner {
             PromExpr::Aggregate(AggregateExpr {
                 op,
                 expr,
-                // TODO(ruihang): support param
-                param: _param,
+                param,
                 modifier,
             }) => {
                 let input = self.prom_expr_to_plan(*expr.clone()).await?;
 
-                // calculate columns to group by
-                // Need to append time index column into group by columns
-                let group_exprs = modifier
-                    .as_ref()
-                    .map_or(Ok(vec![self.create_time_index_column_expr()?]), |m| {
-                        self.agg_modifier_to_col(input.schema(), m)
-                    })?;
-
-                // convert op and value columns to aggregate exprs
-                let aggr_exprs = self.create_aggregate_exprs(*op, &input)?;
-
-                // create plan
-                let group_sort_expr = group_exprs
-                    .clone()
-                    .into_iter()
-                    .map(|expr| expr.sort(true, false));
-                LogicalPlanBuilder::from(input)
-                    .aggregate(group_exprs, aggr_exprs)
-                    .context(DataFusionPlanningSnafu)?
-                    .sort(group_sort_expr)
-                    .context(DataFusionPlanningSnafu)?
-                    .build()
-                    .context(DataFusionPlanningSnafu)?
+                match op.id() {
+                    token::T_TOPK | token::T_BOTTOMK => {
+                        self.topk_bottomk_plan(*op, param, modifier, input)?
+                    }
+                    token::T_COUNT_VALUES => self.count_values_plan(param, modifier, input)?,
+                    _ => {
+                        // calculate columns to group by
+                        // Need to append time index column into group by columns
+                        let group_exprs = modifier
+                            .as_ref()
+                            .map_or(Ok(vec![self.create_time_index_column_expr()?]), |m| {
+                                self.agg_modifier_to_col(input.schema(), m)
+                            })?;
+
+                        // convert op and value columns to aggregate exprs
+                        let aggr_exprs = self.create_aggregate_exprs(*op, param, &input)?;
+
+                        // create plan
+                        let group_sort_expr = group_exprs
+                            .clone()
+                            .into_iter()
+                            .map(|expr| expr.sort(true, false));
+                        LogicalPlanBuilder::from(input)
+                            .aggregate(group_exprs, aggr_exprs)
+                            .context(DataFusionPlanningSnafu)?
+                            .sort(group_sort_expr)
+                            .context(DataFusionPlanningSnafu)?
+                            .build()
+                            .context(DataFusionPlanningSnafu)?
+                    }
+                }
             }
             PromExpr::Unary(UnaryExpr { expr }) => {
                 // Unary Expr in PromQL implys the `-` operator
@@ -249,12 +301,37 @@ impl PromPlanner {
                     (None, None) => {
                         let left_input = self.prom_expr_to_plan(*lhs.clone()).await?;
                         let left_field_columns = self.ctx.field_columns.clone();
+                        let left_tag_columns = self.ctx.tag_columns.clone();
+                        let left_time_index = self.ctx.time_index_column.clone();
+                        let left_table_name = self.ctx.table_name.clone();
                         let left_schema = left_input.schema().clone();
 
                         let right_input = self.prom_expr_to_plan(*rhs.clone()).await?;
                         let right_field_columns = self.ctx.field_columns.clone();
                         let right_schema = right_input.schema().clone();
 
+                        if let Some(modifier) = modifier && Self::has_vector_matching(modifier) {
+                            let left = VectorOperand {
+                                plan: left_input,
+                                table_name: left_table_name,
+                                time_index: left_time_index.with_context(|| {
+                                    TimeIndexNotFoundSnafu { table: "unknown" }
+                                })?,
+                                tag_columns: left_tag_columns,
+                                field_columns: left_field_columns,
+                            };
+                            let right = VectorOperand {
+                                plan: right_input,
+                                table_name: self.ctx.table_name.clone(),
+                                time_index: self.ctx.time_index_column.clone().with_context(
+                                    || TimeIndexNotFoundSnafu { table: "unknown" },
+                                )?,
+                                tag_columns: self.ctx.tag_columns.clone(),
+                                field_columns: right_field_columns,
+                            };
+                            return self.vector_matching_plan(left, right, *op, modifier);
+                        }
+
                         let mut field_columns =
                             left_field_columns.iter().zip(right_field_columns.iter());
                         // the new ctx.field_columns for the generated join plan
@@ -292,10 +369,44 @@ impl PromPlanner {
                 }
             }
             PromExpr::Paren(ParenExpr { expr }) => self.prom_expr_to_plan(*expr.clone()).await?,
-            PromExpr::Subquery(SubqueryExpr { .. }) => UnsupportedExprSnafu {
-                name: "Prom Subquery",
+            PromExpr::Subquery(SubqueryExpr {
+                expr,
+                offset,
+                range,
+                step,
+                ..
+            }) => {
+                ensure!(!range.is_zero(), ZeroRangeSelectorSnafu);
+                let range_ms = range.as_millis() as Millisecond;
+                let offset_ms = Self::offset_to_millisecond(offset);
+                let step_ms = step
+                    .map(|step| step.as_millis() as Millisecond)
+                    .filter(|step| *step > 0)
+                    .unwrap_or(self.ctx.evaluation_interval);
+
+                // evaluate the inner expression at the sub-step. The first evaluation
+                // timestamp is the first step-aligned timestamp after `start - offset - range`,
+                // the same as Prometheus does.
+                let outer_ctx = self.ctx.clone();
+                let inner_earliest = self.ctx.start - offset_ms - range_ms;
+                let mut inner_start = inner_earliest.div_euclid(step_ms) * step_ms;
+                if inner_start < inner_earliest {
+                    inner_start += step_ms;
+                }
+                self.ctx.start = inner_start;
+                self.ctx.end -= offset_ms;
+                self.ctx.interval = step_ms;
+                self.ctx.range = None;
+                let inner_plan = self.prom_expr_to_plan(*expr.clone()).await;
+
+                // restore the query parameters for the outer expression
+                self.ctx.start = outer_ctx.start;
+                self.ctx.end = outer_ctx.end;
+                self.ctx.interval = outer_ctx.interval;
+                self.ctx.range = Some(range_ms);
+
+                self.subquery_to_range_manipulate_plan(inner_plan?, offset_ms, range_ms)?
             }
-            .fail()?,
             PromExpr::NumberLiteral(NumberLiteral { val }) => {
                 self.ctx.time_index_column = Some(DEFAULT_TIME_INDEX_COLUMN.to_string());
                 self.ctx.field_columns = vec![DEFAULT_FIELD_COLUMN.to_string()];
@@ -428,6 +539,16 @@ impl PromPlanner {
                         expr: prom_expr.clone(),
                     })?)
                     .await?;
+
+                match func.name {
+                    HISTOGRAM_QUANTILE_FUNCTION => {
+                        return self.histogram_quantile_plan(args.literals, input)
+                    }
+                    LABEL_REPLACE_FUNCTION => return self.label_replace_plan(args.literals, input),
+                    LABEL_JOIN_FUNCTION => return self.label_join_plan(args.literals, input),
+                    _ => {}
+                }
+
                 let mut func_exprs = self.create_function_expr(func, args.literals)?;
                 func_exprs.insert(0, self.create_time_index_column_expr()?);
                 func_exprs.extend_from_slice(&self.create_tag_column_exprs()?);
@@ -497,11 +618,7 @@ impl PromPlanner {
         let table_name = self.ctx.table_name.clone().unwrap();
 
         // make filter exprs
-        let offset_duration = match offset {
-            Some(Offset::Pos(duration)) => duration.as_millis() as Millisecond,
-            Some(Offset::Neg(duration)) => -(duration.as_millis() as Millisecond),
-            None => 0,
-        };
+        let offset_duration = Self::offset_to_millisecond(offset);
         let range_ms = self.ctx.range.unwrap_or_default();
         let mut scan_filters = self.matchers_to_expr(label_matchers.clone())?;
         scan_filters.push(self.create_time_index_column_expr()?.gt_eq(DfExpr::Literal(
@@ -626,6 +743,60 @@ impl PromPlanner {
         Ok(logical_plan)
     }
 
+    /// Turn the instant vector evaluated by a subquery into a range vector.
+    ///
+    /// The inner plan is divided into series and then folded by [RangeManipulate] with
+    /// the outer query's step, so range functions can be applied on it like on a
+    /// matrix selector.
+    fn subquery_to_range_manipulate_plan(
+        &mut self,
+        inner_plan: LogicalPlan,
+        offset: Millisecond,
+        range: Millisecond,
+    ) -> Result<LogicalPlan> {
+        let time_index =
+            self.ctx
+                .time_index_column
+                .clone()
+                .with_context(|| TimeIndexNotFoundSnafu {
+                    table: self.ctx.table_name.clone().unwrap_or_default(),
+                })?;
+
+        let sort_plan = LogicalPlanBuilder::from(inner_plan)
+            .sort(self.create_tag_and_time_index_column_sort_exprs()?)
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)?;
+        let divide_plan = LogicalPlan::Extension(Extension {
+            node: Arc::new(SeriesDivide::new(self.ctx.tag_columns.clone(), sort_plan)),
+        });
+        let normalize_plan = LogicalPlan::Extension(Extension {
+            node: Arc::new(SeriesNormalize::new(offset, &time_index, true, divide_plan)),
+        });
+        let manipulate = RangeManipulate::new(
+            self.ctx.start,
+            self.ctx.end,
+            self.ctx.interval,
+            range,
+            time_index,
+            self.ctx.field_columns.clone(),
+            normalize_plan,
+        )
+        .context(DataFusionPlanningSnafu)?;
+
+        Ok(LogicalPlan::Extension(Extension {
+            node: Arc::new(manipulate),
+        }))
+    }
+
+    fn offset_to_millisecond(offset: &Option<Offset>) -> Millisecond {
+        match offset {
+            Some(Offset::Pos(duration)) => duration.as_millis() as Millisecond,
+            Some(Offset::Neg(duration)) => -(duration.as_millis() as Millisecond),
+            None => 0,
+        }
+    }
+
     /// Convert [LabelModifier] to [Column] exprs for aggregation.
     /// Timestamp column and tag columns will be included.
     ///
@@ -1029,24 +1200,37 @@ impl PromPlanner {
     fn create_aggregate_exprs(
         &mut self,
         op: TokenType,
+        param: &Option<Box<PromExpr>>,
         input_plan: &LogicalPlan,
     ) -> Result<Vec<DfExpr>> {
-        let aggr = match op.id() {
-            token::T_SUM => AggregateFunctionEnum::Sum,
-            token::T_AVG => AggregateFunctionEnum::Avg,
-            token::T_COUNT => AggregateFunctionEnum::Count,
-            token::T_MIN => AggregateFunctionEnum::Min,
-            token::T_MAX => AggregateFunctionEnum::Max,
-            token::T_GROUP => AggregateFunctionEnum::Grouping,
-            token::T_STDDEV => AggregateFunctionEnum::StddevPop,
-            token::T_STDVAR => AggregateFunctionEnum::VariancePop,
-            token::T_TOPK | token::T_BOTTOMK | token::T_COUNT_VALUES | token::T_QUANTILE => {
-                UnsupportedExprSnafu {
-                    name: format!("{op:?}"),
-                }
-                .fail()?
+        let aggr_builder: Box<dyn Fn(DfExpr) -> DfExpr> = match op.id() {
+            token::T_QUANTILE => {
+                let quantile = Self::aggregate_param_to_f64(op, param)?;
+                let udaf = QuantileAggr::aggregate_udf(quantile);
+                Box::new(move |expr| udaf.call(vec![expr]))
+            }
+            _ => {
+                let aggr = match op.id() {
+                    token::T_SUM => AggregateFunctionEnum::Sum,
+                    token::T_AVG => AggregateFunctionEnum::Avg,
+                    token::T_COUNT => AggregateFunctionEnum::Count,
+                    token::T_MIN => AggregateFunctionEnum::Min,
+                    token::T_MAX => AggregateFunctionEnum::Max,
+                    token::T_GROUP => AggregateFunctionEnum::Grouping,
+                    token::T_STDDEV => AggregateFunctionEnum::StddevPop,
+                    token::T_STDVAR => AggregateFunctionEnum::VariancePop,
+                    _ => UnexpectedTokenSnafu { token: op }.fail()?,
+                };
+                Box::new(move |expr| {
+                    DfExpr::AggregateFunction(AggregateFunction {
+                        fun: aggr.clone(),
+                        args: vec![expr],
+                        distinct: false,
+                        filter: None,
+                        order_by: None,
+                    })
+                })
             }
-            _ => UnexpectedTokenSnafu { token: op }.fail()?,
         };
 
         // perform aggregate operation to each value column
@@ -1054,15 +1238,7 @@ impl PromPlanner {
             .ctx
             .field_columns
             .iter()
-            .map(|col| {
-                DfExpr::AggregateFunction(AggregateFunction {
-                    fun: aggr.clone(),
-                    args: vec![DfExpr::Column(Column::from_name(col))],
-                    distinct: false,
-                    filter: None,
-                    order_by: None,
-                })
-            })
+            .map(|col| aggr_builder(DfExpr::Column(Column::from_name(col))))
             .collect();
 
         // update value column name according to the aggregators
@@ -1077,6 +1253,400 @@ impl PromPlanner {
         Ok(exprs)
     }
 
+    /// Build plan for `topk` and `bottomk`.
+    ///
+    /// Series are ranked by their value at each timestamp within the group specified by
+    /// the modifier, and only the first `k` of them are kept. Unlike other aggregations,
+    /// the original labels of the selected series are preserved.
+    fn topk_bottomk_plan(
+        &mut self,
+        op: TokenType,
+        param: &Option<Box<PromExpr>>,
+        modifier: &Option<LabelModifier>,
+        input: LogicalPlan,
+    ) -> Result<LogicalPlan> {
+        ensure!(
+            self.ctx.field_columns.len() == 1,
+            UnsupportedExprSnafu {
+                name: format!("{op:?} on multi-value input")
+            }
+        );
+        let k = Self::aggregate_param_to_f64(op, param)?;
+        let k = if k.is_nan() || k < 1.0 { 0 } else { k as u64 };
+
+        // `topk` and `bottomk` don't change the labels
+        let tag_columns = self.ctx.tag_columns.clone();
+        let partition_exprs = modifier
+            .as_ref()
+            .map_or(Ok(vec![self.create_time_index_column_expr()?]), |m| {
+                self.agg_modifier_to_col(input.schema(), m)
+            })?;
+        self.ctx.tag_columns = tag_columns;
+
+        let is_desc = op.id() == token::T_TOPK;
+        let field_column = DfExpr::Column(Column::from_name(&self.ctx.field_columns[0]));
+        // NaN values are ranked after all other values in both `topk` and `bottomk`
+        let is_nan_expr = DfExpr::ScalarFunction(ScalarFunction {
+            fun: BuiltinScalarFunction::Isnan,
+            args: vec![field_column.clone()],
+        });
+        let rank_expr = DfExpr::WindowFunction(WindowFunction::new(
+            WindowFunctionEnum::BuiltInWindowFunction(BuiltInWindowFunction::RowNumber),
+            vec![],
+            partition_exprs.clone(),
+            vec![
+                is_nan_expr.sort(true, false),
+                field_column.sort(!is_desc, false),
+            ],
+            WindowFrame::new(true),
+        ))
+        .alias(RANK_COLUMN_NAME);
+
+        let mut project_exprs = self.create_tag_column_exprs()?;
+        project_exprs.push(self.create_time_index_column_expr()?);
+        project_exprs.extend(
+            self.ctx
+                .field_columns
+                .iter()
+                .map(|col| DfExpr::Column(Column::from_name(col))),
+        );
+        let sort_exprs = partition_exprs
+            .into_iter()
+            .map(|expr| expr.sort(true, false))
+            .chain(Some(
+                DfExpr::Column(Column::from_name(RANK_COLUMN_NAME)).sort(true, false),
+            ))
+            .collect::<Vec<_>>();
+
+        LogicalPlanBuilder::from(input)
+            .window(vec![rank_expr])
+            .context(DataFusionPlanningSnafu)?
+            .filter(DfExpr::Column(Column::from_name(RANK_COLUMN_NAME)).lt_eq(df_prelude::lit(k)))
+            .context(DataFusionPlanningSnafu)?
+            .sort(sort_exprs)
+            .context(DataFusionPlanningSnafu)?
+            .project(project_exprs)
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)
+    }
+
+    /// Build plan for `count_values`.
+    ///
+    /// Count the number of series having the same value in each group. The value is
+    /// formatted as Prometheus does and put into a label named by the parameter, which
+    /// replaces the existing label of the same name.
+    fn count_values_plan(
+        &mut self,
+        param: &Option<Box<PromExpr>>,
+        modifier: &Option<LabelModifier>,
+        input: LogicalPlan,
+    ) -> Result<LogicalPlan> {
+        ensure!(
+            self.ctx.field_columns.len() == 1,
+            UnsupportedExprSnafu {
+                name: "count_values on multi-value input"
+            }
+        );
+        let label = match param.as_deref() {
+            Some(PromExpr::StringLiteral(StringLiteral { val })) => val.clone(),
+            other => UnexpectedPlanExprSnafu {
+                desc: format!("expect string literal as label name, but found {other:?}"),
+            }
+            .fail()?,
+        };
+
+        let time_index_column = self.create_time_index_column_expr()?;
+        let label_column = DfExpr::Column(Column::from_name(&label));
+        let mut group_exprs = modifier
+            .as_ref()
+            .map_or(Ok(vec![time_index_column.clone()]), |m| {
+                self.agg_modifier_to_col(input.schema(), m)
+            })?;
+        // The label of the same name is replaced by the value, so it doesn't split
+        // the groups.
+        group_exprs.retain(|expr| *expr != label_column);
+        // Only the group-by columns are kept in the aggregate output, so they are
+        // the tag columns of the result.
+        self.ctx.tag_columns = group_exprs
+            .iter()
+            .filter(|expr| **expr != time_index_column)
+            .filter_map(|expr| match expr {
+                DfExpr::Column(column) => Some(column.name.clone()),
+                _ => None,
+            })
+            .collect();
+        let field_column = DfExpr::Column(Column::from_name(&self.ctx.field_columns[0]));
+        group_exprs.push(field_column.clone());
+        let count_expr = DfExpr::AggregateFunction(AggregateFunction {
+            fun: AggregateFunctionEnum::Count,
+            args: vec![field_column.clone()],
+            distinct: false,
+            filter: None,
+            order_by: None,
+        });
+        let count_column = normalize_col(count_expr.clone(), &input)
+            .and_then(|expr| expr.display_name())
+            .context(DataFusionPlanningSnafu)?;
+
+        // project the value into a new label column
+        let mut project_exprs = self.create_tag_column_exprs()?;
+        project_exprs.push(
+            DfExpr::ScalarUDF(ScalarUDF {
+                fun: Arc::new(FormatValue::scalar_udf()),
+                args: vec![field_column],
+            })
+            .alias(&label),
+        );
+        project_exprs.push(time_index_column);
+        project_exprs.push(DfExpr::Column(Column::from_name(&count_column)));
+
+        self.ctx.tag_columns.push(label);
+        self.ctx.field_columns = vec![count_column];
+        let sort_exprs = self
+            .create_tag_column_exprs()?
+            .into_iter()
+            .chain(Some(self.create_time_index_column_expr()?))
+            .map(|expr| expr.sort(true, false))
+            .collect::<Vec<_>>();
+
+        LogicalPlanBuilder::from(input)
+            .aggregate(group_exprs, vec![count_expr])
+            .context(DataFusionPlanningSnafu)?
+            .project(project_exprs)
+            .context(DataFusionPlanningSnafu)?
+            .sort(sort_exprs)
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)
+    }
+
+    /// Build plan for `histogram_quantile`.
+    ///
+    /// Bucket series of a classic histogram are grouped by all labels except `le`
+    /// at each timestamp, and the quantile is calculated from those buckets.
+    fn histogram_quantile_plan(
+        &mut self,
+        literals: Vec<DfExpr>,
+        input: LogicalPlan,
+    ) -> Result<LogicalPlan> {
+        let quantile = match literals.first() {
+            Some(DfExpr::Literal(ScalarValue::Float64(Some(quantile)))) => *quantile,
+            other => UnexpectedPlanExprSnafu {
+                desc: format!("expect f64 literal as quantile, but found {other:?}"),
+            }
+            .fail()?,
+        };
+        ensure!(
+            self.ctx.tag_columns.iter().any(|col| col == LE_COLUMN_NAME),
+            ColumnNotFoundSnafu {
+                col: LE_COLUMN_NAME
+            }
+        );
+
+        // group by all tags except `le`
+        self.ctx.tag_columns.retain(|col| col != LE_COLUMN_NAME);
+        let mut group_exprs = self.create_tag_column_exprs()?;
+        group_exprs.push(self.create_time_index_column_expr()?);
+
+        let udaf = HistogramQuantile::aggregate_udf(quantile);
+        let aggr_exprs = self
+            .ctx
+            .field_columns
+            .iter()
+            .map(|col| {
+                udaf.call(vec![
+                    DfExpr::Column(Column::from_name(LE_COLUMN_NAME)),
+                    DfExpr::Column(Column::from_name(col)),
+                ])
+            })
+            .collect::<Vec<_>>();
+        self.ctx.field_columns = normalize_cols(aggr_exprs.iter().cloned(), &input)
+            .context(DataFusionPlanningSnafu)?
+            .iter()
+            .map(|expr| expr.display_name())
+            .collect::<DfResult<Vec<_>>>()
+            .context(DataFusionPlanningSnafu)?;
+
+        let group_sort_expr = group_exprs
+            .clone()
+            .into_iter()
+            .map(|expr| expr.sort(true, false));
+        LogicalPlanBuilder::from(input)
+            .aggregate(group_exprs, aggr_exprs)
+            .context(DataFusionPlanningSnafu)?
+            .sort(group_sort_expr)
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)
+    }
+
+    /// Build plan for `label_replace`.
+    ///
+    /// For each series, if the regex matches the value of `src_label`, `dst_label` is set
+    /// to the expanded `replacement`. Otherwise the series is returned unchanged.
+    fn label_replace_plan(
+        &mut self,
+        literals: Vec<DfExpr>,
+        input: LogicalPlan,
+    ) -> Result<LogicalPlan> {
+        let args = Self::literals_to_strings(LABEL_REPLACE_FUNCTION, literals)?;
+        ensure!(
+            args.len() == 4,
+            UnexpectedPlanExprSnafu {
+                desc: format!(
+                    "expect 4 string arguments for {LABEL_REPLACE_FUNCTION}, but found {}",
+                    args.len()
+                ),
+            }
+        );
+        let (dst_label, replacement, src_label, regex) = (&args[0], &args[1], &args[2], &args[3]);
+
+        // the regex is fully anchored, like Prometheus
+        let regex = format!("^(?:{regex})$");
+        let _ = Regex::new(&regex).context(InvalidRegularExpressionSnafu {
+            func: LABEL_REPLACE_FUNCTION,
+            regex: regex.clone(),
+        })?;
+
+        let src_expr = self.label_column_or_empty_expr(src_label);
+        let replaced_expr = DfExpr::ScalarFunction(ScalarFunction {
+            fun: BuiltinScalarFunction::RegexpReplace,
+            args: vec![
+                src_expr.clone(),
+                df_prelude::lit(regex.clone()),
+                df_prelude::lit(replacement.clone()),
+            ],
+        });
+        let is_matched_expr = DfExpr::BinaryExpr(BinaryExpr {
+            left: Box::new(src_expr),
+            op: Operator::RegexMatch,
+            right: Box::new(df_prelude::lit(regex)),
+        });
+        let label_expr = when(is_matched_expr, replaced_expr)
+            .otherwise(self.label_column_or_empty_expr(dst_label))
+            .context(DataFusionPlanningSnafu)?;
+
+        self.projection_with_label(input, dst_label, label_expr)
+    }
+
+    /// Build plan for `label_join`.
+    ///
+    /// The values of all `src_labels` are joined with `separator` and put into `dst_label`.
+    fn label_join_plan(
+        &mut self,
+        literals: Vec<DfExpr>,
+        input: LogicalPlan,
+    ) -> Result<LogicalPlan> {
+        let args = Self::literals_to_strings(LABEL_JOIN_FUNCTION, literals)?;
+        ensure!(
+            args.len() >= 2,
+            UnexpectedPlanExprSnafu {
+                desc: format!(
+                    "expect at least 2 string arguments for {LABEL_JOIN_FUNCTION}, but found {}",
+                    args.len()
+                ),
+            }
+        );
+        let (dst_label, separator, src_labels) = (&args[0], &args[1], &args[2..]);
+
+        let label_expr = if src_labels.is_empty() {
+            df_prelude::lit(String::new())
+        } else {
+            let mut concat_args = vec![df_prelude::lit(separator.clone())];
+            concat_args.extend(
+                src_labels
+                    .iter()
+                    .map(|label| self.label_column_or_empty_expr(label)),
+            );
+            DfExpr::ScalarFunction(ScalarFunction {
+                fun: BuiltinScalarFunction::ConcatWithSeparator,
+                args: concat_args,
+            })
+        };
+
+        self.projection_with_label(input, dst_label, label_expr)
+    }
+
+    /// Convert string literal arguments of label manipulation functions to [String]s.
+    fn literals_to_strings(func: &str, literals: Vec<DfExpr>) -> Result<Vec<String>> {
+        literals
+            .into_iter()
+            .map(|literal| match literal {
+                DfExpr::Literal(ScalarValue::Utf8(Some(val))) => Ok(val),
+                other => UnexpectedPlanExprSnafu {
+                    desc: format!(
+                        "expect string literal as argument of {func}, but found {other:?}"
+                    ),
+                }
+                .fail(),
+            })
+            .collect()
+    }
+
+    /// Column expr of the given label. Nonexistent labels are regarded as empty.
+    fn label_column_or_empty_expr(&self, label: &str) -> DfExpr {
+        if self.ctx.tag_columns.iter().any(|tag| tag == label) {
+            DfExpr::Column(Column::from_name(label))
+        } else {
+            df_prelude::lit(String::new())
+        }
+    }
+
+    /// Build a projection that preserves all columns, except the tag column `label`
+    /// which is (re)written by `label_expr`.
+    ///
+    /// # Side effect
+    ///
+    /// This method will add `label` to tag columns in context if it's a new label.
+    fn projection_with_label(
+        &mut self,
+        input: LogicalPlan,
+        label: &str,
+        label_expr: DfExpr,
+    ) -> Result<LogicalPlan> {
+        let mut exprs = self
+            .ctx
+            .tag_columns
+            .iter()
+            .map(|tag| {
+                if tag == label {
+                    label_expr.clone().alias(label)
+                } else {
+                    DfExpr::Column(Column::from_name(tag))
+                }
+            })
+            .collect::<Vec<_>>();
+        if !self.ctx.tag_columns.iter().any(|tag| tag == label) {
+            exprs.push(label_expr.alias(label));
+            self.ctx.tag_columns.push(label.to_string());
+        }
+        exprs.push(self.create_time_index_column_expr()?);
+        exprs.extend(
+            self.ctx
+                .field_columns
+                .iter()
+                .map(|col| DfExpr::Column(Column::from_name(col))),
+        );
+
+        LogicalPlanBuilder::from(input)
+            .project(exprs)
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)
+    }
+
+    /// Extract the numeric parameter of aggregation operators like `topk` or `quantile`.
+    fn aggregate_param_to_f64(op: TokenType, param: &Option<Box<PromExpr>>) -> Result<f64> {
+        match param.as_deref().and_then(Self::try_build_literal_expr) {
+            Some(DfExpr::Literal(ScalarValue::Float64(Some(val)))) => Ok(val),
+            other => UnexpectedPlanExprSnafu {
+                desc: format!("expect f64 literal as parameter of {op:?}, but found {other:?}"),
+            }
+            .fail(),
+        }
+    }
+
     /// Try to build a DataFusion Literal Expression from PromQL Expr, return
     /// `None` if the input is not a literal expression.
     fn try_build_literal_expr(expr: &PromExpr) -> Option<DfExpr> {
@@ -1174,6 +1744,274 @@ impl PromPlanner {
         )
     }
 
+    /// Whether the binary modifier requires vector matching other than the default
+    /// one-to-one matching on all labels.
+    fn has_vector_matching(modifier: &BinModifier) -> bool {
+        modifier.matching.is_some() || !matches!(modifier.card, VectorMatchCardinality::OneToOne)
+    }
+
+    /// Build plan for binary operation between two vectors with
+    /// [vector matching](https://prometheus.io/docs/prometheus/latest/querying/operators/#vector-matching),
+    /// i.e., `on`/`ignoring` and `group_left`/`group_right` modifiers.
+    ///
+    /// Both sides are aliased to distinguish the columns with the same name. For
+    /// many-to-one and one-to-many matching, the labels of the "many" side are preserved
+    /// and the extra labels listed in the group modifier are copied from the "one" side.
+    /// Like Prometheus, the query fails if a match group has more than one series on
+    /// a side that must be unique.
+    fn vector_matching_plan(
+        &mut self,
+        mut left: VectorOperand,
+        mut right: VectorOperand,
+        op: TokenType,
+        modifier: &BinModifier,
+    ) -> Result<LogicalPlan> {
+        let is_comparison_op = Self::is_token_a_comparison_op(op);
+        let should_filter = is_comparison_op && !modifier.return_bool;
+        ensure!(
+            left.field_columns.len() == right.field_columns.len(),
+            UnsupportedExprSnafu {
+                name: "binary operation between vectors with different number of value columns"
+            }
+        );
+        ensure!(
+            !should_filter || left.field_columns.len() == 1,
+            UnsupportedExprSnafu {
+                name: "filter on multi-value input"
+            }
+        );
+
+        // labels to match on
+        let contains = |labels: &[String], label: &String| labels.iter().any(|l| l == label);
+        let mut matching_labels = match &modifier.matching {
+            Some(LabelModifier::Include(labels)) => labels
+                .iter()
+                .filter(|label| {
+                    contains(&left.tag_columns, label) && contains(&right.tag_columns, label)
+                })
+                .cloned()
+                .collect::<Vec<_>>(),
+            Some(LabelModifier::Exclude(labels)) => left
+                .tag_columns
+                .iter()
+                .filter(|label| {
+                    !labels.iter().any(|l| l == *label) && contains(&right.tag_columns, label)
+                })
+                .cloned()
+                .collect(),
+            None => left
+                .tag_columns
+                .iter()
+                .filter(|label| contains(&right.tag_columns, label))
+                .cloned()
+                .collect(),
+        };
+        matching_labels.sort();
+
+        // labels of the result vector, with the side they come from
+        let output_labels = match &modifier.card {
+            VectorMatchCardinality::OneToOne => match &modifier.matching {
+                Some(LabelModifier::Include(_)) => matching_labels
+                    .iter()
+                    .map(|label| (LEFT_SIDE_ALIAS, label.clone()))
+                    .collect(),
+                Some(LabelModifier::Exclude(labels)) => left
+                    .tag_columns
+                    .iter()
+                    .filter(|label| !labels.iter().any(|l| l == *label))
+                    .map(|label| (LEFT_SIDE_ALIAS, label.clone()))
+                    .collect(),
+                None => left
+                    .tag_columns
+                    .iter()
+                    .map(|label| (LEFT_SIDE_ALIAS, label.clone()))
+                    .collect(),
+            },
+            VectorMatchCardinality::ManyToOne(extra_labels) => Self::group_modifier_labels(
+                (LEFT_SIDE_ALIAS, &left.tag_columns),
+                (RIGHT_SIDE_ALIAS, &right.tag_columns),
+                extra_labels.iter().cloned().collect(),
+            ),
+            VectorMatchCardinality::OneToMany(extra_labels) => Self::group_modifier_labels(
+                (RIGHT_SIDE_ALIAS, &right.tag_columns),
+                (LEFT_SIDE_ALIAS, &left.tag_columns),
+                extra_labels.iter().cloned().collect(),
+            ),
+            VectorMatchCardinality::ManyToMany => UnsupportedExprSnafu {
+                name: "set operator between vectors",
+            }
+            .fail()?,
+        };
+
+        // series must be unique in each match group on the "one" side
+        let (left_unique, right_unique) = match &modifier.card {
+            VectorMatchCardinality::ManyToOne(_) => (false, true),
+            VectorMatchCardinality::OneToMany(_) => (true, false),
+            _ => (true, true),
+        };
+        if left_unique {
+            left.plan = Self::unique_match_plan(
+                left.plan,
+                &matching_labels,
+                &left.time_index,
+                UniqueMatchTarget::Left,
+            );
+        }
+        if right_unique {
+            right.plan = Self::unique_match_plan(
+                right.plan,
+                &matching_labels,
+                &right.time_index,
+                UniqueMatchTarget::Right,
+            );
+        }
+
+        // join two sides on matching labels and time index
+        let join_keys = |alias: &str, time_index: &str| {
+            matching_labels
+                .iter()
+                .map(|label| Column::new(Some(alias.to_string()), label))
+                .chain(Some(Column::new(Some(alias.to_string()), time_index)))
+                .collect::<Vec<_>>()
+        };
+        let left_keys = join_keys(LEFT_SIDE_ALIAS, &left.time_index);
+        let right_keys = join_keys(RIGHT_SIDE_ALIAS, &right.time_index);
+        let right_plan = LogicalPlanBuilder::from(right.plan)
+            .alias(RIGHT_SIDE_ALIAS.to_string())
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)?;
+        let mut builder = LogicalPlanBuilder::from(left.plan)
+            .alias(LEFT_SIDE_ALIAS.to_string())
+            .context(DataFusionPlanningSnafu)?
+            .join(right_plan, JoinType::Inner, (left_keys, right_keys), None)
+            .context(DataFusionPlanningSnafu)?;
+
+        // build value exprs
+        let binary_expr_builder = Self::prom_token_to_binary_expr_builder(op)?;
+        let mut value_exprs = Vec::with_capacity(left.field_columns.len());
+        let mut new_field_columns = Vec::with_capacity(left.field_columns.len());
+        for (left_col_name, right_col_name) in
+            left.field_columns.iter().zip(right.field_columns.iter())
+        {
+            let left_col = DfExpr::Column(Column::new(
+                Some(LEFT_SIDE_ALIAS.to_string()),
+                left_col_name,
+            ));
+            let right_col = DfExpr::Column(Column::new(
+                Some(RIGHT_SIDE_ALIAS.to_string()),
+                right_col_name,
+            ));
+            let mut binary_expr = binary_expr_builder(left_col.clone(), right_col)?;
+            // name the result by unqualified columns so the aliases don't leak into the output
+            let mut name_expr = binary_expr_builder(
+                DfExpr::Column(Column::from_name(left_col_name)),
+                DfExpr::Column(Column::from_name(right_col_name)),
+            )?;
+            if should_filter {
+                // comparison without `bool` filters the left side's value
+                builder = builder
+                    .filter(binary_expr)
+                    .context(DataFusionPlanningSnafu)?;
+                binary_expr = left_col;
+                name_expr = DfExpr::Column(Column::from_name(left_col_name));
+            } else if is_comparison_op {
+                binary_expr = DfExpr::Cast(Cast {
+                    expr: Box::new(binary_expr),
+                    data_type: ArrowDataType::Float64,
+                });
+                name_expr = DfExpr::Cast(Cast {
+                    expr: Box::new(name_expr),
+                    data_type: ArrowDataType::Float64,
+                });
+            }
+            let name = name_expr.display_name().context(DataFusionPlanningSnafu)?;
+            value_exprs.push(binary_expr.alias(&name));
+            new_field_columns.push(name);
+        }
+
+        // project the result, and restore the qualifier with table name
+        let project_exprs = output_labels
+            .iter()
+            .map(|(alias, label)| {
+                DfExpr::Column(Column::new(Some(alias.to_string()), label)).alias(label)
+            })
+            .chain(Some(
+                DfExpr::Column(Column::new(
+                    Some(LEFT_SIDE_ALIAS.to_string()),
+                    &left.time_index,
+                ))
+                .alias(&left.time_index),
+            ))
+            .chain(value_exprs)
+            .collect::<Vec<_>>();
+        let mut plan = builder
+            .project(project_exprs)
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)?;
+        // series of the "many" side must not end up with the same labels
+        if !left_unique || !right_unique {
+            let labels = output_labels
+                .iter()
+                .map(|(_, label)| label.clone())
+                .collect::<Vec<_>>();
+            plan =
+                Self::unique_match_plan(plan, &labels, &left.time_index, UniqueMatchTarget::Output);
+        }
+        let plan = LogicalPlanBuilder::from(plan)
+            .alias(left.table_name.clone().unwrap_or_default())
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)?;
+
+        self.ctx.table_name = left.table_name;
+        self.ctx.tag_columns = output_labels.into_iter().map(|(_, label)| label).collect();
+        self.ctx.time_index_column = Some(left.time_index);
+        self.ctx.field_columns = new_field_columns;
+
+        Ok(plan)
+    }
+
+    /// Fails the query at execution if more than one series in the `input` has the same
+    /// `labels` at the same timestamp.
+    fn unique_match_plan(
+        input: LogicalPlan,
+        labels: &[String],
+        time_index: &str,
+        target: UniqueMatchTarget,
+    ) -> LogicalPlan {
+        let columns = labels
+            .iter()
+            .cloned()
+            .chain(Some(time_index.to_string()))
+            .collect();
+        LogicalPlan::Extension(Extension {
+            node: Arc::new(UniqueMatch::new(columns, target, input)),
+        })
+    }
+
+    /// Labels of the result for `group_left` and `group_right`: all labels from the "many"
+    /// side except extra labels, which are copied from the "one" side.
+    fn group_modifier_labels(
+        (many_side, many_labels): (&'static str, &[String]),
+        (one_side, one_labels): (&'static str, &[String]),
+        mut extra_labels: Vec<String>,
+    ) -> Vec<(&'static str, String)> {
+        extra_labels.sort();
+        many_labels
+            .iter()
+            .filter(|label| !extra_labels.contains(label))
+            .map(|label| (many_side, label.clone()))
+            .chain(
+                extra_labels
+                    .into_iter()
+                    .filter(|label| one_labels.contains(label))
+                    .map(|label| (one_side, label)),
+            )
+            .collect()
+    }
+
     /// Build a inner join on time index column and tag columns to concat two logical plans.
     fn join_on_non_field_columns(
         &self,
@@ -1290,6 +2128,15 @@ impl PromPlanner {
     }
 }
 
+/// One side of a binary operation between two vectors.
+struct VectorOperand {
+    plan: LogicalPlan,
+    table_name: Option<String>,
+    time_index: String,
+    tag_columns: Vec<String>,
+    field_columns: Vec<String>,
+}
+
 #[derive(Default, Debug)]
 struct FunctionArgs {
     input: Option<PromExpr>,
@@ -1326,10 +2173,20 @@ mod test {
         num_tag: usize,
         num_field: usize,
     ) -> DfTableSourceProvider {
+        let tags = (0..num_tag).map(|i| format!("tag_{i}")).collect::<Vec<_>>();
+        build_test_table_provider_with_tags(table_name, &tags, num_field).await
+    }
+
+    async fn build_test_table_provider_with_tags(
+        table_name: String,
+        tags: &[String],
+        num_field: usize,
+    ) -> DfTableSourceProvider {
+        let num_tag = tags.len();
         let mut columns = vec![];
-        for i in 0..num_tag {
+        for tag in tags {
             columns.push(ColumnSchema::new(
-                format!("tag_{i}"),
+                tag.clone(),
                 ConcreteDataType::string_datatype(),
                 false,
             ));
@@ -1695,27 +2552,77 @@ mod test {
     }
 
     #[tokio::test]
-    #[should_panic]
     async fn aggregate_top_k() {
-        do_aggregate_expr_plan("topk", "").await;
+        let query = "topk by (tag_0) (3, some_metric)";
+        let plan = indie_query_plan(query).await;
+
+        assert!(plan.contains(
+            "Projection: some_metric.tag_0, some_metric.timestamp, some_metric.field_0 [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]"
+        ));
+        assert!(plan.contains("Filter: __rank__ <= UInt64(3)"));
+        assert!(plan.contains(
+            "ROW_NUMBER() PARTITION BY [some_metric.tag_0, some_metric.timestamp] ORDER BY [isnan(some_metric.field_0) ASC NULLS LAST, some_metric.field_0 DESC NULLS LAST]"
+        ));
     }
 
     #[tokio::test]
-    #[should_panic]
     async fn aggregate_bottom_k() {
-        do_aggregate_expr_plan("bottomk", "").await;
+        let query = "bottomk(1, some_metric)";
+        let plan = indie_query_plan(query).await;
+
+        assert!(plan.contains("Filter: __rank__ <= UInt64(1)"));
+        assert!(plan.contains(
+            "ROW_NUMBER() PARTITION BY [some_metric.timestamp] ORDER BY [isnan(some_metric.field_0) ASC NULLS LAST, some_metric.field_0 ASC NULLS LAST]"
+        ));
     }
 
     #[tokio::test]
-    #[should_panic]
     async fn aggregate_count_values() {
-        do_aggregate_expr_plan("count_values", "").await;
+        let query = r#"count_values by (tag_0) ("value", some_metric)"#;
+        let plan = indie_query_plan(query).await;
+
+        assert!(plan.contains(
+            "Projection: some_metric.tag_0, prom_format_value(some_metric.field_0) AS value, some_metric.timestamp, COUNT(some_metric.field_0)"
+        ));
+        assert!(plan.contains(
+            "Aggregate: groupBy=[[some_metric.tag_0, some_metric.timestamp, some_metric.field_0]], aggr=[[COUNT(some_metric.field_0)]]"
+        ));
+    }
+
+    #[tokio::test]
+    async fn aggregate_count_values_without_modifier() {
+        let query = r#"count_values("value", some_metric)"#;
+        let plan = indie_query_plan(query).await;
+
+        assert!(plan.contains(
+            "Projection: prom_format_value(some_metric.field_0) AS value, some_metric.timestamp, COUNT(some_metric.field_0)"
+        ));
+        assert!(plan.contains(
+            "Aggregate: groupBy=[[some_metric.timestamp, some_metric.field_0]], aggr=[[COUNT(some_metric.field_0)]]"
+        ));
+    }
+
+    #[tokio::test]
+    async fn aggregate_count_values_replace_label() {
+        let query = r#"count_values by (tag_0) ("tag_0", some_metric)"#;
+        let plan = indie_query_plan(query).await;
+
+        assert!(plan.contains(
+            "Projection: prom_format_value(some_metric.field_0) AS tag_0, some_metric.timestamp, COUNT(some_metric.field_0)"
+        ));
+        assert!(plan.contains(
+            "Aggregate: groupBy=[[some_metric.timestamp, some_metric.field_0]], aggr=[[COUNT(some_metric.field_0)]]"
+        ));
     }
 
     #[tokio::test]
-    #[should_panic]
     async fn aggregate_quantile() {
-        do_aggregate_expr_plan("quantile", "").await;
+        let query = "quantile by (tag_0) (0.9, some_metric)";
+        let plan = indie_query_plan(query).await;
+
+        assert!(plan.contains(
+            "Aggregate: groupBy=[[some_metric.tag_0, some_metric.timestamp]], aggr=[[prom_quantile(some_metric.field_0)]]"
+        ));
     }
 
     // TODO(ruihang): add range fn tests once exprs are ready.
@@ -1780,6 +2687,123 @@ mod test {
         assert_eq!(plan.display_indent_schema().to_string(), expected);
     }
 
+    async fn indie_query_plan(query: &str) -> String {
+        let prom_expr = parser::parse(query).unwrap();
+        let eval_stmt = EvalStmt {
+            expr: prom_expr,
+            start: UNIX_EPOCH,
+            end: UNIX_EPOCH
+                .checked_add(Duration::from_secs(100_000))
+                .unwrap(),
+            interval: Duration::from_secs(5),
+            lookback_delta: Duration::from_secs(1),
+        };
+
+        let table_provider = build_test_table_provider("some_metric".to_string(), 1, 1).await;
+        PromPlanner::stmt_to_plan(table_provider, eval_stmt)
+            .await
+            .unwrap()
+            .display_indent_schema()
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn binary_op_on_matching() {
+        let query = r#"some_metric{tag_0="foo"} + on(tag_0) some_metric{tag_0="bar"}"#;
+        let plan = indie_query_plan(query).await;
+
+        assert!(plan.contains(
+            "Projection: lhs.tag_0 AS tag_0, lhs.timestamp AS timestamp, lhs.field_0 + rhs.field_0 AS field_0 + field_0"
+        ));
+        assert!(plan.contains("Inner Join: lhs.tag_0 = rhs.tag_0, lhs.timestamp = rhs.timestamp"));
+        assert!(plan.contains("SubqueryAlias: lhs"));
+        assert!(plan.contains("SubqueryAlias: rhs"));
+        // both sides must be unique in one-to-one matching
+        assert!(plan.contains("PromUniqueMatch: columns=[\"tag_0\", \"timestamp\"], target=Left"));
+        assert!(plan.contains("PromUniqueMatch: columns=[\"tag_0\", \"timestamp\"], target=Right"));
+        assert!(!plan.contains("target=Output"));
+    }
+
+    async fn vector_matching_plan(query: &str) -> LogicalPlan {
+        let prom_expr = parser::parse(query).unwrap();
+        let eval_stmt = EvalStmt {
+            expr: prom_expr,
+            start: UNIX_EPOCH,
+            end: UNIX_EPOCH
+                .checked_add(Duration::from_secs(100_000))
+                .unwrap(),
+            interval: Duration::from_secs(5),
+            lookback_delta: Duration::from_secs(1),
+        };
+
+        let table_provider = build_test_table_provider("some_metric".to_string(), 2, 1).await;
+        PromPlanner::stmt_to_plan(table_provider, eval_stmt)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn binary_op_ignoring_matching() {
+        let plan = vector_matching_plan("some_metric / ignoring(tag_1) some_metric").await;
+        let plan_str = plan.display_indent_schema().to_string();
+
+        assert!(
+            plan_str.contains("Inner Join: lhs.tag_0 = rhs.tag_0, lhs.timestamp = rhs.timestamp")
+        );
+        let mut fields = plan.schema().field_names();
+        fields.sort();
+        assert_eq!(
+            fields,
+            vec![
+                "some_metric.field_0 / field_0",
+                "some_metric.tag_0",
+                "some_metric.timestamp"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn binary_op_group_left() {
+        let plan =
+            vector_matching_plan("some_metric * on(tag_0) group_left(tag_1) some_metric").await;
+        let plan_str = plan.display_indent_schema().to_string();
+
+        // labels of the "many" side are kept, extra label is copied from the "one" side
+        assert!(plan_str.contains(
+            "Projection: lhs.tag_0 AS tag_0, rhs.tag_1 AS tag_1, lhs.timestamp AS timestamp"
+        ));
+        assert!(
+            plan_str.contains("Inner Join: lhs.tag_0 = rhs.tag_0, lhs.timestamp = rhs.timestamp")
+        );
+        // only the "one" side must be unique in each match group
+        assert!(
+            plan_str.contains("PromUniqueMatch: columns=[\"tag_0\", \"timestamp\"], target=Right")
+        );
+        assert!(!plan_str.contains("target=Left"));
+        // and the result must have unique labels
+        assert!(plan_str.contains(
+            "PromUniqueMatch: columns=[\"tag_0\", \"tag_1\", \"timestamp\"], target=Output"
+        ));
+    }
+
+    #[tokio::test]
+    async fn binary_op_group_right() {
+        let plan = vector_matching_plan("some_metric > on(tag_0) group_right some_metric").await;
+        let plan_str = plan.display_indent_schema().to_string();
+
+        // labels are from the right side, and the left value is filtered
+        assert!(plan_str.contains(
+            "Projection: rhs.tag_0 AS tag_0, rhs.tag_1 AS tag_1, lhs.timestamp AS timestamp, lhs.field_0 AS field_0"
+        ));
+        assert!(plan_str.contains("Filter: lhs.field_0 > rhs.field_0"));
+        assert!(
+            plan_str.contains("PromUniqueMatch: columns=[\"tag_0\", \"timestamp\"], target=Left")
+        );
+        assert!(plan_str.contains(
+            "PromUniqueMatch: columns=[\"tag_0\", \"tag_1\", \"timestamp\"], target=Output"
+        ));
+    }
+
     async fn indie_query_plan_compare(query: &str, expected: String) {
         let prom_expr = parser::parse(query).unwrap();
         let eval_stmt = EvalStmt {
@@ -1911,6 +2935,175 @@ mod test {
         indie_query_plan_compare(query, expected).await;
     }
 
+    #[tokio::test]
+    async fn subquery_over_range_function() {
+        let prom_expr = parser::parse("max_over_time(rate(some_metric[5m])[1h:1m])").unwrap();
+        let eval_stmt = EvalStmt {
+            expr: prom_expr,
+            start: UNIX_EPOCH,
+            end: UNIX_EPOCH
+                .checked_add(Duration::from_secs(100_000))
+                .unwrap(),
+            interval: Duration::from_secs(5),
+            lookback_delta: Duration::from_secs(1),
+        };
+
+        let table_provider = build_test_table_provider("some_metric".to_string(), 1, 1).await;
+        let plan = PromPlanner::stmt_to_plan(table_provider, eval_stmt)
+            .await
+            .unwrap()
+            .display_indent_schema()
+            .to_string();
+
+        // the outer range function folds the subquery result with the outer step
+        assert!(plan.contains(
+            "PromRangeManipulate: req range=[0..100000000], interval=[5000], eval range=[3600000], time index=[timestamp]"
+        ));
+        // the inner expression is evaluated at the sub-step, aligned to it
+        assert!(plan.contains(
+            "PromRangeManipulate: req range=[-3600000..100000000], interval=[60000], eval range=[300000], time index=[timestamp]"
+        ));
+        assert!(plan.contains(
+            "PromSeriesNormalize: offset=[0], time index=[timestamp], filter NaN: [true]"
+        ));
+        assert!(plan.contains("prom_max_over_time"));
+        assert!(plan.contains("prom_rate"));
+    }
+
+    #[tokio::test]
+    async fn subquery_with_offset_and_default_step() {
+        let prom_expr = parser::parse("avg_over_time(some_metric[10m:] offset 1m)").unwrap();
+        let eval_stmt = EvalStmt {
+            expr: prom_expr,
+            start: UNIX_EPOCH,
+            end: UNIX_EPOCH
+                .checked_add(Duration::from_secs(100_000))
+                .unwrap(),
+            interval: Duration::from_secs(5),
+            lookback_delta: Duration::from_secs(1),
+        };
+
+        let table_provider = build_test_table_provider("some_metric".to_string(), 1, 1).await;
+        let plan = PromPlanner::stmt_to_plan(table_provider, eval_stmt)
+            .await
+            .unwrap()
+            .display_indent_schema()
+            .to_string();
+
+        assert!(plan.contains(
+            "PromRangeManipulate: req range=[0..100000000], interval=[5000], eval range=[600000], time index=[timestamp]"
+        ));
+        assert!(plan.contains(
+            "PromSeriesNormalize: offset=[60000], time index=[timestamp], filter NaN: [true]"
+        ));
+        assert!(plan.contains(
+            "PromInstantManipulate: range=[-660000..99940000], lookback=[1000], interval=[60000], time index=[timestamp]"
+        ));
+    }
+
+    #[tokio::test]
+    async fn histogram_quantile() {
+        let prom_expr =
+            parser::parse("histogram_quantile(0.99, sum by (le, tag_0) (rate(some_metric[5m])))")
+                .unwrap();
+        let eval_stmt = EvalStmt {
+            expr: prom_expr,
+            start: UNIX_EPOCH,
+            end: UNIX_EPOCH
+                .checked_add(Duration::from_secs(100_000))
+                .unwrap(),
+            interval: Duration::from_secs(5),
+            lookback_delta: Duration::from_secs(1),
+        };
+
+        let table_provider = build_test_table_provider_with_tags(
+            "some_metric".to_string(),
+            &["tag_0".to_string(), "le".to_string()],
+            1,
+        )
+        .await;
+        let plan = PromPlanner::stmt_to_plan(table_provider, eval_stmt)
+            .await
+            .unwrap();
+        let plan_str = plan.display_indent_schema().to_string();
+
+        assert!(plan_str
+            .contains("Aggregate: groupBy=[[some_metric.tag_0, some_metric.timestamp]], aggr=[[prom_histogram_quantile("));
+        // `le` is removed from the output
+        let mut fields = plan.schema().field_names();
+        fields.sort();
+        assert_eq!(fields.len(), 3);
+        assert!(!fields.iter().any(|field| field.ends_with("le")));
+    }
+
+    #[tokio::test]
+    async fn histogram_quantile_without_le() {
+        let prom_expr = parser::parse("histogram_quantile(0.99, some_metric)").unwrap();
+        let eval_stmt = EvalStmt {
+            expr: prom_expr,
+            start: UNIX_EPOCH,
+            end: UNIX_EPOCH
+                .checked_add(Duration::from_secs(100_000))
+                .unwrap(),
+            interval: Duration::from_secs(5),
+            lookback_delta: Duration::from_secs(1),
+        };
+
+        let table_provider = build_test_table_provider("some_metric".to_string(), 1, 1).await;
+        assert!(PromPlanner::stmt_to_plan(table_provider, eval_stmt)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn label_replace() {
+        let query = r#"label_replace(some_metric, "foo", "$1", "tag_0", "(.*):.*")"#;
+        let plan = indie_query_plan(query).await;
+
+        assert!(plan.contains(
+            "Projection: some_metric.tag_0, CASE WHEN some_metric.tag_0 ~ Utf8(\"^(?:(.*):.*)$\") THEN regexp_replace(some_metric.tag_0, Utf8(\"^(?:(.*):.*)$\"), Utf8(\"$1\")) ELSE Utf8(\"\") END AS foo, some_metric.timestamp, some_metric.field_0"
+        ));
+
+        // overwrite an existing label
+        let query = r#"label_replace(some_metric, "tag_0", "$1", "tag_0", "(.*):.*")"#;
+        let plan = indie_query_plan(query).await;
+        assert!(plan.contains(
+            "ELSE some_metric.tag_0 END AS tag_0, some_metric.timestamp, some_metric.field_0"
+        ));
+    }
+
+    #[tokio::test]
+    async fn label_replace_invalid_regex() {
+        let prom_expr =
+            parser::parse(r#"label_replace(some_metric, "foo", "$1", "tag_0", "(.*")"#).unwrap();
+        let eval_stmt = EvalStmt {
+            expr: prom_expr,
+            start: UNIX_EPOCH,
+            end: UNIX_EPOCH
+                .checked_add(Duration::from_secs(100_000))
+                .unwrap(),
+            interval: Duration::from_secs(5),
+            lookback_delta: Duration::from_secs(1),
+        };
+
+        let table_provider = build_test_table_provider("some_metric".to_string(), 1, 1).await;
+        let result = PromPlanner::stmt_to_plan(table_provider, eval_stmt).await;
+        assert!(matches!(
+            result,
+            Err(crate::error::Error::InvalidRegularExpression { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn label_join() {
+        let query = r#"label_join(some_metric, "foo", ",", "tag_0", "tag_1")"#;
+        let plan = indie_query_plan(query).await;
+
+        assert!(plan.contains(
+            "Projection: some_metric.tag_0, concat_ws(Utf8(\",\"), some_metric.tag_0, Utf8(\"\")) AS foo, some_metric.timestamp, some_metric.field_0"
+        ));
+    }
+
     #[tokio::test]
     async fn value_matcher() {
         // template
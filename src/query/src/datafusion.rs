@@ -363,33 +363,32 @@ impl QueryExecutor for DatafusionQueryEngine {
         plan: &Arc<dyn PhysicalPlan>,
     ) -> Result<SendableRecordBatchStream> {
         let _timer = timer!(metrics::METRIC_EXEC_PLAN_ELAPSED);
-        match plan.output_partitioning().partition_count() {
-            0 => Ok(Box::pin(EmptyRecordBatchStream::new(plan.schema()))),
-            1 => Ok(plan
-                .execute(0, ctx.state().task_ctx())
-                .context(error::ExecutePhysicalPlanSnafu)
-                .map_err(BoxedError::new)
-                .context(QueryExecutionSnafu))?,
-            _ => {
-                // merge into a single partition
-                let plan =
-                    CoalescePartitionsExec::new(Arc::new(DfPhysicalPlanAdapter(plan.clone())));
-                // CoalescePartitionsExec must produce a single partition
-                assert_eq!(1, plan.output_partitioning().partition_count());
-                let df_stream = plan
-                    .execute(0, ctx.state().task_ctx())
-                    .context(error::DatafusionSnafu {
-                        msg: "Failed to execute DataFusion merge exec",
-                    })
-                    .map_err(BoxedError::new)
-                    .context(QueryExecutionSnafu)?;
-                let stream = RecordBatchStreamAdapter::try_new(df_stream)
-                    .context(error::ConvertDfRecordBatchStreamSnafu)
-                    .map_err(BoxedError::new)
-                    .context(QueryExecutionSnafu)?;
-                Ok(Box::pin(stream))
-            }
-        }
+        let df_plan = match plan.as_any().downcast_ref::<PhysicalPlanAdapter>() {
+            Some(adapter) => adapter.df_plan(),
+            None => Arc::new(DfPhysicalPlanAdapter(plan.clone())),
+        };
+        let df_plan = match df_plan.output_partitioning().partition_count() {
+            0 => return Ok(Box::pin(EmptyRecordBatchStream::new(plan.schema()))),
+            1 => df_plan,
+            // merge into a single partition
+            _ => Arc::new(CoalescePartitionsExec::new(df_plan)),
+        };
+        // CoalescePartitionsExec must produce a single partition
+        assert_eq!(1, df_plan.output_partitioning().partition_count());
+        let df_stream = df_plan
+            .execute(0, ctx.state().task_ctx())
+            .context(error::DatafusionSnafu {
+                msg: "Failed to execute DataFusion physical plan",
+            })
+            .map_err(BoxedError::new)
+            .context(QueryExecutionSnafu)?;
+        // Keeps the plan alongside the stream, so the metrics of the whole plan tree could
+        // be reported (e.g. to the frontend in distributed mode) once the stream ends.
+        let stream = RecordBatchStreamAdapter::try_new_with_plan(df_stream, df_plan)
+            .context(error::ConvertDfRecordBatchStreamSnafu)
+            .map_err(BoxedError::new)
+            .context(QueryExecutionSnafu)?;
+        Ok(Box::pin(stream))
     }
 }
 
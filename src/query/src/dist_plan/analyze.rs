@@ -0,0 +1,209 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+use std::time::Instant;
+
+use arrow_schema::SchemaRef as ArrowSchemaRef;
+use common_meta::peer::Peer;
+use common_query::physical_plan::TaskContext;
+use common_recordbatch::{DfRecordBatch, DfSendableRecordBatchStream, RecordBatchMetrics};
+use datafusion::arrow::array::StringBuilder;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::{
+    DisplayFormatType, DisplayableExecutionPlan, Distribution, ExecutionPlan, Partitioning,
+};
+use datafusion_common::{DataFusionError, Result, Statistics};
+use datafusion_physical_expr::PhysicalSortExpr;
+use futures_util::{stream, StreamExt};
+use store_api::storage::RegionId;
+
+use crate::dist_plan::merge_scan::MergeScanExec;
+
+/// Like DataFusion's [AnalyzeExec](datafusion::physical_plan::analyze::AnalyzeExec), but
+/// also reports the metrics of the sub-plans executed by datanodes through [MergeScanExec].
+#[derive(Debug)]
+pub struct DistAnalyzeExec {
+    verbose: bool,
+    input: Arc<dyn ExecutionPlan>,
+    schema: ArrowSchemaRef,
+}
+
+impl DistAnalyzeExec {
+    pub fn new(verbose: bool, input: Arc<dyn ExecutionPlan>, schema: ArrowSchemaRef) -> Self {
+        Self {
+            verbose,
+            input,
+            schema,
+        }
+    }
+}
+
+impl ExecutionPlan for DistAnalyzeExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> ArrowSchemaRef {
+        self.schema.clone()
+    }
+
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        vec![Distribution::SinglePartition]
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        mut children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(Self::new(
+            self.verbose,
+            children.swap_remove(0),
+            self.schema.clone(),
+        )))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<DfSendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Internal(format!(
+                "DistAnalyzeExec invalid partition. Expected 0, got {partition}"
+            )));
+        }
+        let input_partitions = self.input.output_partitioning().partition_count();
+        if input_partitions != 1 {
+            return Err(DataFusionError::Internal(format!(
+                "DistAnalyzeExec invalid number of input partitions. Expected 1, got {input_partitions}"
+            )));
+        }
+
+        let start = Instant::now();
+        let mut input_stream = self.input.execute(0, context)?;
+        let input = self.input.clone();
+        let schema = self.schema.clone();
+        let verbose = self.verbose;
+
+        let output = async move {
+            let mut total_rows = 0;
+            while let Some(batch) = input_stream.next().await {
+                total_rows += batch?.num_rows();
+            }
+            let duration = Instant::now() - start;
+
+            let mut type_builder = StringBuilder::new();
+            let mut plan_builder = StringBuilder::new();
+
+            type_builder.append_value("Plan with Metrics");
+            plan_builder.append_value(
+                DisplayableExecutionPlan::with_metrics(input.as_ref())
+                    .indent()
+                    .to_string(),
+            );
+
+            let mut sub_stage_metrics = vec![];
+            collect_sub_stage_metrics(input.as_ref(), &mut sub_stage_metrics);
+            for (region_id, peer, metrics) in sub_stage_metrics {
+                type_builder.append_value(format!(
+                    "Remote Plan with Metrics (region {region_id}, {peer})"
+                ));
+                plan_builder.append_value(metrics.to_string());
+            }
+
+            if verbose {
+                type_builder.append_value("Plan with Full Metrics");
+                plan_builder.append_value(
+                    DisplayableExecutionPlan::with_full_metrics(input.as_ref())
+                        .indent()
+                        .to_string(),
+                );
+
+                type_builder.append_value("Output Rows");
+                plan_builder.append_value(total_rows.to_string());
+
+                type_builder.append_value("Duration");
+                plan_builder.append_value(format!("{duration:?}"));
+            }
+
+            DfRecordBatch::try_new(
+                schema,
+                vec![
+                    Arc::new(type_builder.finish()),
+                    Arc::new(plan_builder.finish()),
+                ],
+            )
+            .map_err(DataFusionError::from)
+        };
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema.clone(),
+            stream::once(output),
+        )))
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "DistAnalyzeExec verbose={}", self.verbose)
+    }
+}
+
+/// Collects the metrics reported by datanodes to all [MergeScanExec]s in the `plan` tree.
+fn collect_sub_stage_metrics(
+    plan: &dyn ExecutionPlan,
+    result: &mut Vec<(RegionId, Peer, RecordBatchMetrics)>,
+) {
+    if let Some(merge_scan) = plan.as_any().downcast_ref::<MergeScanExec>() {
+        result.extend(merge_scan.sub_stage_metrics());
+    }
+    for child in plan.children() {
+        collect_sub_stage_metrics(child.as_ref(), result);
+    }
+}
+
+/// Makes all [MergeScanExec]s in the `plan` tree collect the metrics of the plans
+/// executed by datanodes, which are not requested by default.
+pub(crate) fn enable_sub_stage_metrics(
+    plan: Arc<dyn ExecutionPlan>,
+) -> Result<Arc<dyn ExecutionPlan>> {
+    if let Some(merge_scan) = plan.as_any().downcast_ref::<MergeScanExec>() {
+        return Ok(Arc::new(merge_scan.with_sub_stage_metrics()));
+    }
+    let children = plan.children();
+    if children.is_empty() {
+        return Ok(plan);
+    }
+    let children = children
+        .into_iter()
+        .map(enable_sub_stage_metrics)
+        .collect::<Result<Vec<_>>>()?;
+    plan.with_new_children(children)
+}
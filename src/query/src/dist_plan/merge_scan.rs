@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use std::any::Any;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use arrow_schema::SchemaRef as ArrowSchemaRef;
 use async_stream::try_stream;
@@ -28,14 +28,17 @@ use common_query::Output;
 use common_recordbatch::adapter::DfRecordBatchStreamAdapter;
 use common_recordbatch::error::ExternalSnafu;
 use common_recordbatch::{
-    DfSendableRecordBatchStream, RecordBatchStreamAdaptor, SendableRecordBatchStream,
+    DfSendableRecordBatchStream, RecordBatchMetrics, RecordBatchStreamAdaptor,
+    SendableRecordBatchStream,
 };
+use datafusion::physical_plan::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
 use datafusion::physical_plan::{DisplayFormatType, ExecutionPlan, Partitioning};
 use datafusion_common::{DataFusionError, Result, Statistics};
 use datafusion_expr::{Extension, LogicalPlan, UserDefinedLogicalNodeCore};
 use datafusion_physical_expr::PhysicalSortExpr;
 use futures_util::StreamExt;
 use snafu::ResultExt;
+use store_api::storage::RegionId;
 
 use crate::error::{ConvertSchemaSnafu, RemoteRequestSnafu, UnexpectedOutputKindSnafu};
 
@@ -109,45 +112,86 @@ impl MergeScanLogicalPlan {
 #[derive(Debug)]
 pub struct MergeScanExec {
     table: TableName,
-    peers: Vec<Peer>,
+    /// Regions to scan, along with the peers serving them.
+    regions: Vec<(RegionId, Peer)>,
     substrait_plan: Bytes,
     arrow_schema: ArrowSchemaRef,
     clients: Arc<DatanodeClients>,
+    metric: ExecutionPlanMetricsSet,
+    /// Whether to ask peers for the metrics of the plans they execute. Only
+    /// `EXPLAIN ANALYZE` needs them.
+    request_sub_stage_metrics: bool,
+    /// Metrics of the plans executed for each region, reported once their streams end.
+    sub_stage_metrics: Arc<Mutex<Vec<(RegionId, Peer, RecordBatchMetrics)>>>,
 }
 
 impl MergeScanExec {
     pub fn new(
         table: TableName,
-        peers: Vec<Peer>,
+        regions: Vec<(RegionId, Peer)>,
         substrait_plan: Bytes,
         arrow_schema: ArrowSchemaRef,
         clients: Arc<DatanodeClients>,
     ) -> Self {
         Self {
             table,
-            peers,
+            regions,
             substrait_plan,
             arrow_schema,
             clients,
+            metric: ExecutionPlanMetricsSet::new(),
+            request_sub_stage_metrics: false,
+            sub_stage_metrics: Arc::default(),
         }
     }
 
-    pub fn to_stream(&self) -> Result<SendableRecordBatchStream> {
+    /// Returns a copy of this plan that also collects the metrics of the plans
+    /// executed by peers, see [MergeScanExec::sub_stage_metrics].
+    pub fn with_sub_stage_metrics(&self) -> Self {
+        Self {
+            table: self.table.clone(),
+            regions: self.regions.clone(),
+            substrait_plan: self.substrait_plan.clone(),
+            arrow_schema: self.arrow_schema.clone(),
+            clients: self.clients.clone(),
+            metric: ExecutionPlanMetricsSet::new(),
+            request_sub_stage_metrics: true,
+            sub_stage_metrics: Arc::default(),
+        }
+    }
+
+    pub fn to_stream(&self, partition: usize) -> Result<SendableRecordBatchStream> {
         let substrait_plan = self.substrait_plan.to_vec();
-        let peers = self.peers.clone();
+        let regions = self.regions.clone();
         let clients = self.clients.clone();
         let table = self.table.clone();
+        let metric = BaselineMetrics::new(&self.metric, partition);
+        let request_sub_stage_metrics = self.request_sub_stage_metrics;
+        let sub_stage_metrics = self.sub_stage_metrics.clone();
 
         let stream = try_stream! {
-            for peer in peers {
+            for (region_id, peer) in regions {
                 let client = clients.get_client(&peer).await;
                 let database = Database::new(&table.catalog_name, &table.schema_name, client);
-                let output: Output = database
-                    .logical_plan(substrait_plan.clone())
-                    .await
-                    .context(RemoteRequestSnafu)
-                    .map_err(BoxedError::new)
-                    .context(ExternalSnafu)?;
+                let (output, metrics) = if request_sub_stage_metrics {
+                    database
+                        .logical_plan_with_metrics(substrait_plan.clone())
+                        .await
+                } else {
+                    database
+                        .logical_plan(substrait_plan.clone())
+                        .await
+                        .map(|output| (output, None))
+                }
+                .context(RemoteRequestSnafu)
+                .map_err(BoxedError::new)
+                .context(ExternalSnafu)?;
+                if let Some(metrics) = metrics {
+                    sub_stage_metrics
+                        .lock()
+                        .unwrap()
+                        .push((region_id, peer.clone(), metrics));
+                }
 
                 match output {
                     Output::AffectedRows(_) => {
@@ -162,12 +206,15 @@ impl MergeScanExec {
                     }
                     Output::RecordBatches(record_batches) => {
                         for batch in record_batches.into_iter() {
+                            metric.record_output(batch.num_rows());
                             yield batch;
                         }
                     }
                     Output::Stream(mut stream) => {
                         while let Some(batch) = stream.next().await {
-                            yield batch?;
+                            let batch = batch?;
+                            metric.record_output(batch.num_rows());
+                            yield batch;
                         }
                     }
                 }
@@ -185,6 +232,11 @@ impl MergeScanExec {
             output_ordering: None,
         }))
     }
+
+    /// Returns the metrics of the plans executed for each region so far.
+    pub fn sub_stage_metrics(&self) -> Vec<(RegionId, Peer, RecordBatchMetrics)> {
+        self.sub_stage_metrics.lock().unwrap().clone()
+    }
 }
 
 impl ExecutionPlan for MergeScanExec {
@@ -219,10 +271,12 @@ impl ExecutionPlan for MergeScanExec {
 
     fn execute(
         &self,
-        _partition: usize,
+        partition: usize,
         _context: Arc<TaskContext>,
     ) -> Result<DfSendableRecordBatchStream> {
-        Ok(Box::pin(DfRecordBatchStreamAdapter::new(self.to_stream()?)))
+        Ok(Box::pin(DfRecordBatchStreamAdapter::new(
+            self.to_stream(partition)?,
+        )))
     }
 
     fn statistics(&self) -> Statistics {
@@ -231,9 +285,13 @@ impl ExecutionPlan for MergeScanExec {
 
     fn fmt_as(&self, _t: DisplayFormatType, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(f, "MergeScanExec: peers=[")?;
-        for peer in self.peers.iter() {
+        for (_, peer) in self.regions.iter() {
             write!(f, "{}, ", peer)?;
         }
         write!(f, "]")
     }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metric.clone_inner())
+    }
 }
@@ -32,6 +32,7 @@ use datafusion_common::{DataFusionError, TableReference};
 use datafusion_expr::{LogicalPlan, UserDefinedLogicalNode};
 use partition::manager::PartitionRuleManager;
 use snafu::ResultExt;
+use store_api::storage::RegionId;
 use substrait::{DFLogicalSubstraitConvertor, SubstraitPlan};
 pub use table::metadata::TableType;
 use table::table::adapter::DfTableProviderAdapter;
@@ -90,12 +91,12 @@ impl ExtensionPlanner for DistExtensionPlanner {
                     .encode(input_plan.clone())
                     .context(error::EncodeSubstraitLogicalPlanSnafu)?
                     .into();
-                let peers = self.get_peers(&table_name).await;
-                match peers {
-                    Ok(peers) => {
+                let regions = self.get_regions(&table_name).await;
+                match regions {
+                    Ok(regions) => {
                         let exec = MergeScanExec::new(
                             table_name,
-                            peers,
+                            regions,
                             substrait_plan,
                             Arc::new(input_schema.as_ref().into()),
                             self.clients.clone(),
@@ -129,9 +130,9 @@ impl DistExtensionPlanner {
         plan.transform(&|plan| TableNameRewriter::rewrite_table_name(plan, name))
     }
 
-    async fn get_peers(&self, table_name: &TableName) -> Result<Vec<Peer>> {
+    async fn get_regions(&self, table_name: &TableName) -> Result<Vec<(RegionId, Peer)>> {
         self.partition_manager
-            .find_table_region_leaders(table_name)
+            .find_table_region_peers(table_name)
             .await
             .with_context(|_| error::RoutePartitionSnafu {
                 table: table_name.clone(),
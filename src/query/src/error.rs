@@ -93,6 +93,12 @@ pub enum Error {
         location: Location,
     },
 
+    #[snafu(display("Failed to parse InfluxQL: {}", reason))]
+    ParseInfluxql { reason: String, location: Location },
+
+    #[snafu(display("Unsupported InfluxQL: {}", reason))]
+    UnsupportedInfluxql { reason: String, location: Location },
+
     #[snafu(display("The SQL string has multiple statements, query: {}", query))]
     MultipleStatements { query: String, location: Location },
 
@@ -232,7 +238,9 @@ impl ErrorExt for Error {
         use Error::*;
 
         match self {
-            QueryParse { .. } | MultipleStatements { .. } => StatusCode::InvalidSyntax,
+            QueryParse { .. } | MultipleStatements { .. } | ParseInfluxql { .. } => {
+                StatusCode::InvalidSyntax
+            }
             UnsupportedExpr { .. }
             | Unimplemented { .. }
             | CatalogNotFound { .. }
@@ -241,6 +249,7 @@ impl ErrorExt for Error {
             | ParseTimestamp { .. }
             | ParseFloat { .. }
             | MissingRequiredField { .. }
+            | UnsupportedInfluxql { .. }
             | BuildRegex { .. }
             | ConvertSchema { .. } => StatusCode::InvalidArguments,
 
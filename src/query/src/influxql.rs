@@ -0,0 +1,25 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! A subset of InfluxQL (the InfluxDB v1 query language), planned into DataFusion logical plans
+//! over the tables created by the line protocol writer.
+//!
+//! Supported statements are `SELECT`, `SHOW MEASUREMENTS`, `SHOW TAG KEYS` and
+//! `SHOW FIELD KEYS`.
+
+pub mod ast;
+pub mod parser;
+pub mod planner;
+
+pub use ast::{InfluxqlStatement, SeriesLayout};
+pub use parser::parse_influxql;
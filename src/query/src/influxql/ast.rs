@@ -0,0 +1,193 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::fmt::{Display, Formatter};
+
+/// Output column of `SHOW MEASUREMENTS` and of the measurement name in `SHOW TAG KEYS` and
+/// `SHOW FIELD KEYS`.
+pub const NAME_COLUMN: &str = "name";
+pub const TAG_KEY_COLUMN: &str = "tagKey";
+pub const FIELD_KEY_COLUMN: &str = "fieldKey";
+pub const FIELD_TYPE_COLUMN: &str = "fieldType";
+/// Name of the time column in InfluxQL, regardless of the time index name of the table.
+pub const TIME_COLUMN: &str = "time";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InfluxqlStatement {
+    Select(SelectStatement),
+    ShowMeasurements { limit: Option<usize> },
+    ShowTagKeys { from: Option<String> },
+    ShowFieldKeys { from: Option<String> },
+}
+
+/// Describes how the rows of a planned statement are split into InfluxDB series.
+///
+/// The output columns of the plan start with the series name column (if the name is not
+/// fixed), followed by the tag columns of the series, then the columns of the series values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeriesLayout {
+    /// Name shared by all series, or `None` if the first column holds the series name.
+    pub name: Option<String>,
+    /// Number of tag columns identifying a series.
+    pub num_tags: usize,
+}
+
+impl InfluxqlStatement {
+    pub fn series_layout(&self) -> SeriesLayout {
+        match self {
+            InfluxqlStatement::Select(select) => SeriesLayout {
+                name: Some(select.from.clone()),
+                num_tags: select.group_by_tags_len(),
+            },
+            InfluxqlStatement::ShowMeasurements { .. } => SeriesLayout {
+                name: Some("measurements".to_string()),
+                num_tags: 0,
+            },
+            InfluxqlStatement::ShowTagKeys { .. } | InfluxqlStatement::ShowFieldKeys { .. } => {
+                SeriesLayout {
+                    name: None,
+                    num_tags: 0,
+                }
+            }
+        }
+    }
+}
+
+impl Display for InfluxqlStatement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InfluxqlStatement::Select(select) => write!(f, "SELECT ... FROM {}", select.from),
+            InfluxqlStatement::ShowMeasurements { limit } => {
+                write!(f, "SHOW MEASUREMENTS")?;
+                limit.iter().try_for_each(|n| write!(f, " LIMIT {n}"))
+            }
+            InfluxqlStatement::ShowTagKeys { from } => {
+                write!(f, "SHOW TAG KEYS")?;
+                from.iter().try_for_each(|m| write!(f, " FROM {m}"))
+            }
+            InfluxqlStatement::ShowFieldKeys { from } => {
+                write!(f, "SHOW FIELD KEYS")?;
+                from.iter().try_for_each(|m| write!(f, " FROM {m}"))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectStatement {
+    pub fields: Vec<SelectField>,
+    pub from: String,
+    pub condition: Option<Expr>,
+    pub group_by: Vec<Dimension>,
+    pub fill: Option<Fill>,
+    pub order_desc: bool,
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+}
+
+impl SelectStatement {
+    /// Interval of `GROUP BY time(...)` in nanoseconds.
+    pub fn group_by_time(&self) -> Option<i64> {
+        self.group_by.iter().find_map(|d| match d {
+            Dimension::Time(interval) => Some(*interval),
+            _ => None,
+        })
+    }
+
+    fn group_by_tags_len(&self) -> usize {
+        self.group_by
+            .iter()
+            .filter(|d| !matches!(d, Dimension::Time(_)))
+            .count()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SelectField {
+    Wildcard,
+    Expr { expr: Expr, alias: Option<String> },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Dimension {
+    /// `time(interval)` with the interval in nanoseconds.
+    Time(i64),
+    Tag(String),
+}
+
+/// Option of `fill()`, i.e. the values of `GROUP BY time()` buckets without points.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Fill {
+    Null,
+    None,
+    Previous,
+    Linear,
+    Value(f64),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Column(String),
+    /// `*` in function arguments, e.g. `count(*)`.
+    Wildcard,
+    Integer(i64),
+    Float(f64),
+    String(String),
+    Regex(String),
+    Boolean(bool),
+    /// A duration literal in nanoseconds.
+    Duration(i64),
+    Call {
+        name: String,
+        args: Vec<Expr>,
+    },
+    Binary {
+        left: Box<Expr>,
+        op: BinaryOp,
+        right: Box<Expr>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+    RegexMatch,
+    RegexNotMatch,
+    And,
+    Or,
+}
+
+impl BinaryOp {
+    pub fn is_comparison(&self) -> bool {
+        matches!(
+            self,
+            BinaryOp::Eq
+                | BinaryOp::NotEq
+                | BinaryOp::Lt
+                | BinaryOp::LtEq
+                | BinaryOp::Gt
+                | BinaryOp::GtEq
+                | BinaryOp::RegexMatch
+                | BinaryOp::RegexNotMatch
+        )
+    }
+}
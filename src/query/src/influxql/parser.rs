@@ -0,0 +1,884 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Hand written lexer and recursive descent parser for the supported InfluxQL subset.
+
+use std::iter::Peekable;
+use std::str::Chars;
+
+use snafu::ensure;
+
+use crate::error::{ParseInfluxqlSnafu, Result};
+use crate::influxql::ast::{
+    BinaryOp, Dimension, Expr, Fill, InfluxqlStatement, SelectField, SelectStatement,
+};
+
+const NANOS_PER_MICRO: i64 = 1_000;
+const NANOS_PER_MILLI: i64 = 1_000_000;
+const NANOS_PER_SECOND: i64 = 1_000_000_000;
+
+/// Parses `query` into statements. Statements are separated by `;`.
+pub fn parse_influxql(query: &str) -> Result<Vec<InfluxqlStatement>> {
+    let tokens = Lexer::new(query).tokenize()?;
+    let mut parser = Parser { tokens, pos: 0 };
+
+    let mut statements = vec![];
+    loop {
+        while parser.consume(&Token::Semicolon) {}
+        if parser.peek().is_none() {
+            break;
+        }
+        statements.push(parser.parse_statement()?);
+        if parser.peek().is_some() {
+            parser.expect(&Token::Semicolon)?;
+        }
+    }
+    ensure!(
+        !statements.is_empty(),
+        ParseInfluxqlSnafu {
+            reason: "empty query",
+        }
+    );
+    Ok(statements)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    QuotedIdent(String),
+    String(String),
+    Integer(i64),
+    Float(f64),
+    Duration(i64),
+    Regex(String),
+    Comma,
+    Semicolon,
+    LParen,
+    RParen,
+    Dot,
+    DoubleColon,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+    RegexMatch,
+    RegexNotMatch,
+}
+
+struct Lexer<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(input: &'a str) -> Self {
+        Self {
+            chars: input.chars().peekable(),
+        }
+    }
+
+    fn tokenize(mut self) -> Result<Vec<Token>> {
+        let mut tokens: Vec<Token> = vec![];
+        while let Some(&c) = self.chars.peek() {
+            let token = match c {
+                c if c.is_whitespace() => {
+                    let _ = self.chars.next();
+                    continue;
+                }
+                // A `/` right after a regex operator starts a regex literal, otherwise it's a
+                // division.
+                '/' if matches!(
+                    tokens.last(),
+                    Some(Token::RegexMatch) | Some(Token::RegexNotMatch)
+                ) =>
+                {
+                    let _ = self.chars.next();
+                    Token::Regex(self.read_quoted('/')?)
+                }
+                '\'' => {
+                    let _ = self.chars.next();
+                    Token::String(self.read_quoted('\'')?)
+                }
+                '"' => {
+                    let _ = self.chars.next();
+                    Token::QuotedIdent(self.read_quoted('"')?)
+                }
+                c if c.is_ascii_digit() => self.read_number()?,
+                c if c.is_alphabetic() || c == '_' => {
+                    let mut ident = String::new();
+                    while let Some(&c) = self.chars.peek() {
+                        if c.is_alphanumeric() || c == '_' {
+                            ident.push(c);
+                            let _ = self.chars.next();
+                        } else {
+                            break;
+                        }
+                    }
+                    Token::Ident(ident)
+                }
+                _ => {
+                    let _ = self.chars.next();
+                    let next = self.chars.peek().copied();
+                    let (token, two_chars) = match (c, next) {
+                        (':', Some(':')) => (Token::DoubleColon, true),
+                        ('!', Some('=')) => (Token::NotEq, true),
+                        ('!', Some('~')) => (Token::RegexNotMatch, true),
+                        ('=', Some('~')) => (Token::RegexMatch, true),
+                        ('<', Some('>')) => (Token::NotEq, true),
+                        ('<', Some('=')) => (Token::LtEq, true),
+                        ('>', Some('=')) => (Token::GtEq, true),
+                        ('<', _) => (Token::Lt, false),
+                        ('>', _) => (Token::Gt, false),
+                        ('=', _) => (Token::Eq, false),
+                        (',', _) => (Token::Comma, false),
+                        (';', _) => (Token::Semicolon, false),
+                        ('(', _) => (Token::LParen, false),
+                        (')', _) => (Token::RParen, false),
+                        ('.', _) => (Token::Dot, false),
+                        ('+', _) => (Token::Add, false),
+                        ('-', _) => (Token::Sub, false),
+                        ('*', _) => (Token::Mul, false),
+                        ('/', _) => (Token::Div, false),
+                        _ => {
+                            return ParseInfluxqlSnafu {
+                                reason: format!("unexpected character: {c}"),
+                            }
+                            .fail()
+                        }
+                    };
+                    if two_chars {
+                        let _ = self.chars.next();
+                    }
+                    token
+                }
+            };
+            tokens.push(token);
+        }
+        Ok(tokens)
+    }
+
+    /// Reads until the unescaped `quote`, the opening quote is already consumed.
+    fn read_quoted(&mut self, quote: char) -> Result<String> {
+        let mut s = String::new();
+        while let Some(c) = self.chars.next() {
+            match c {
+                '\\' => match self.chars.next() {
+                    Some(next) if next == quote => s.push(next),
+                    // Regex keeps its escapes, e.g. `\d`.
+                    Some(next) if quote == '/' => {
+                        s.push('\\');
+                        s.push(next);
+                    }
+                    Some('\\') => s.push('\\'),
+                    Some('n') => s.push('\n'),
+                    Some(next) => {
+                        s.push('\\');
+                        s.push(next);
+                    }
+                    None => break,
+                },
+                c if c == quote => return Ok(s),
+                c => s.push(c),
+            }
+        }
+        ParseInfluxqlSnafu {
+            reason: format!("unterminated {quote}"),
+        }
+        .fail()
+    }
+
+    /// Reads a number, or a duration like `5m` or `1h30m`.
+    fn read_number(&mut self) -> Result<Token> {
+        let digits = self.read_digits();
+        let mut is_float = false;
+        let mut number = digits.clone();
+        if self.chars.peek() == Some(&'.') {
+            is_float = true;
+            let _ = self.chars.next();
+            number.push('.');
+            number.push_str(&self.read_digits());
+        }
+
+        let starts_unit = |c: Option<&char>| matches!(c, Some(c) if c.is_alphabetic());
+        if !starts_unit(self.chars.peek()) {
+            let token = if is_float {
+                number.parse().map(Token::Float).ok()
+            } else {
+                number.parse().map(Token::Integer).ok()
+            };
+            return token.ok_or_else(|| {
+                ParseInfluxqlSnafu {
+                    reason: format!("invalid number: {number}"),
+                }
+                .build()
+            });
+        }
+        ensure!(
+            !is_float,
+            ParseInfluxqlSnafu {
+                reason: format!("invalid duration: {number}"),
+            }
+        );
+
+        let mut nanos = 0i64;
+        let mut digits = digits;
+        loop {
+            let mut unit = String::new();
+            while let Some(&c) = self.chars.peek() {
+                if c.is_alphabetic() {
+                    unit.push(c);
+                    let _ = self.chars.next();
+                } else {
+                    break;
+                }
+            }
+            let value = digits.parse::<i64>().ok();
+            let part = value
+                .zip(duration_unit_nanos(&unit))
+                .and_then(|(v, n)| v.checked_mul(n));
+            nanos = part
+                .and_then(|part| nanos.checked_add(part))
+                .ok_or_else(|| {
+                    ParseInfluxqlSnafu {
+                        reason: format!("invalid duration: {digits}{unit}"),
+                    }
+                    .build()
+                })?;
+
+            match self.chars.peek() {
+                Some(c) if c.is_ascii_digit() => digits = self.read_digits(),
+                _ => return Ok(Token::Duration(nanos)),
+            }
+        }
+    }
+
+    fn read_digits(&mut self) -> String {
+        let mut digits = String::new();
+        while let Some(&c) = self.chars.peek() {
+            if c.is_ascii_digit() {
+                digits.push(c);
+                let _ = self.chars.next();
+            } else {
+                break;
+            }
+        }
+        digits
+    }
+}
+
+fn duration_unit_nanos(unit: &str) -> Option<i64> {
+    let nanos = match unit {
+        "ns" => 1,
+        "u" | "µ" => NANOS_PER_MICRO,
+        "ms" => NANOS_PER_MILLI,
+        "s" => NANOS_PER_SECOND,
+        "m" => 60 * NANOS_PER_SECOND,
+        "h" => 3600 * NANOS_PER_SECOND,
+        "d" => 86400 * NANOS_PER_SECOND,
+        "w" => 7 * 86400 * NANOS_PER_SECOND,
+        _ => return None,
+    };
+    Some(nanos)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn consume(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &Token) -> Result<()> {
+        ensure!(
+            self.consume(token),
+            ParseInfluxqlSnafu {
+                reason: format!("expect {token:?}, found {:?}", self.peek()),
+            }
+        );
+        Ok(())
+    }
+
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Ident(ident)) if ident.eq_ignore_ascii_case(keyword))
+    }
+
+    fn consume_keyword(&mut self, keyword: &str) -> bool {
+        if self.peek_keyword(keyword) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<()> {
+        ensure!(
+            self.consume_keyword(keyword),
+            ParseInfluxqlSnafu {
+                reason: format!("expect {keyword}, found {:?}", self.peek()),
+            }
+        );
+        Ok(())
+    }
+
+    fn parse_identifier(&mut self) -> Result<String> {
+        match self.next() {
+            Some(Token::Ident(ident)) | Some(Token::QuotedIdent(ident)) => Ok(ident),
+            other => ParseInfluxqlSnafu {
+                reason: format!("expect identifier, found {other:?}"),
+            }
+            .fail(),
+        }
+    }
+
+    fn parse_unsigned(&mut self) -> Result<usize> {
+        match self.next() {
+            Some(Token::Integer(n)) if n >= 0 => Ok(n as usize),
+            other => ParseInfluxqlSnafu {
+                reason: format!("expect unsigned integer, found {other:?}"),
+            }
+            .fail(),
+        }
+    }
+
+    fn parse_statement(&mut self) -> Result<InfluxqlStatement> {
+        if self.consume_keyword("SELECT") {
+            self.parse_select().map(InfluxqlStatement::Select)
+        } else if self.consume_keyword("SHOW") {
+            self.parse_show()
+        } else {
+            ParseInfluxqlSnafu {
+                reason: format!("unsupported statement starting with {:?}", self.peek()),
+            }
+            .fail()
+        }
+    }
+
+    fn parse_show(&mut self) -> Result<InfluxqlStatement> {
+        let statement = if self.consume_keyword("MEASUREMENTS") {
+            self.skip_on_database()?;
+            let limit = if self.consume_keyword("LIMIT") {
+                Some(self.parse_unsigned()?)
+            } else {
+                None
+            };
+            InfluxqlStatement::ShowMeasurements { limit }
+        } else if self.consume_keyword("TAG") {
+            self.expect_keyword("KEYS")?;
+            self.skip_on_database()?;
+            InfluxqlStatement::ShowTagKeys {
+                from: self.parse_optional_from()?,
+            }
+        } else if self.consume_keyword("FIELD") {
+            self.expect_keyword("KEYS")?;
+            self.skip_on_database()?;
+            InfluxqlStatement::ShowFieldKeys {
+                from: self.parse_optional_from()?,
+            }
+        } else {
+            return ParseInfluxqlSnafu {
+                reason: format!("unsupported SHOW {:?}", self.peek()),
+            }
+            .fail();
+        };
+        Ok(statement)
+    }
+
+    /// The database is given by the `db` parameter of the request, `ON <database>` is ignored.
+    fn skip_on_database(&mut self) -> Result<()> {
+        if self.consume_keyword("ON") {
+            let _ = self.parse_identifier()?;
+        }
+        Ok(())
+    }
+
+    fn parse_optional_from(&mut self) -> Result<Option<String>> {
+        if self.consume_keyword("FROM") {
+            self.parse_measurement().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Parses `[database.[retention_policy].]measurement` and returns the measurement.
+    fn parse_measurement(&mut self) -> Result<String> {
+        let mut measurement = self.parse_identifier()?;
+        while self.consume(&Token::Dot) {
+            // `db..measurement` skips the retention policy.
+            let _ = self.consume(&Token::Dot);
+            measurement = self.parse_identifier()?;
+        }
+        Ok(measurement)
+    }
+
+    fn parse_select(&mut self) -> Result<SelectStatement> {
+        let mut fields = vec![self.parse_select_field()?];
+        while self.consume(&Token::Comma) {
+            fields.push(self.parse_select_field()?);
+        }
+
+        self.expect_keyword("FROM")?;
+        let from = self.parse_measurement()?;
+
+        let condition = if self.consume_keyword("WHERE") {
+            Some(self.parse_expr()?)
+        } else {
+            None
+        };
+
+        let mut group_by = vec![];
+        if self.consume_keyword("GROUP") {
+            self.expect_keyword("BY")?;
+            group_by.push(self.parse_dimension()?);
+            while self.consume(&Token::Comma) {
+                group_by.push(self.parse_dimension()?);
+            }
+        }
+
+        let fill = if self.consume_keyword("FILL") {
+            Some(self.parse_fill()?)
+        } else {
+            None
+        };
+
+        let mut order_desc = false;
+        if self.consume_keyword("ORDER") {
+            self.expect_keyword("BY")?;
+            let column = self.parse_identifier()?;
+            ensure!(
+                column.eq_ignore_ascii_case("time"),
+                ParseInfluxqlSnafu {
+                    reason: format!("only ORDER BY time is supported, found {column}"),
+                }
+            );
+            if self.consume_keyword("DESC") {
+                order_desc = true;
+            } else {
+                let _ = self.consume_keyword("ASC");
+            }
+        }
+
+        let limit = if self.consume_keyword("LIMIT") {
+            Some(self.parse_unsigned()?)
+        } else {
+            None
+        };
+        let offset = if self.consume_keyword("OFFSET") {
+            Some(self.parse_unsigned()?)
+        } else {
+            None
+        };
+
+        Ok(SelectStatement {
+            fields,
+            from,
+            condition,
+            group_by,
+            fill,
+            order_desc,
+            limit,
+            offset,
+        })
+    }
+
+    fn parse_fill(&mut self) -> Result<Fill> {
+        self.expect(&Token::LParen)?;
+        let negative = self.consume(&Token::Sub);
+        let fill = match self.next() {
+            Some(Token::Ident(option)) if !negative => match option.to_lowercase().as_str() {
+                "null" => Fill::Null,
+                "none" => Fill::None,
+                "previous" => Fill::Previous,
+                "linear" => Fill::Linear,
+                _ => {
+                    return ParseInfluxqlSnafu {
+                        reason: format!("unknown fill option {option}"),
+                    }
+                    .fail()
+                }
+            },
+            Some(Token::Integer(n)) => Fill::Value(if negative { -n } else { n } as f64),
+            Some(Token::Float(n)) => Fill::Value(if negative { -n } else { n }),
+            other => {
+                return ParseInfluxqlSnafu {
+                    reason: format!("expect fill option, found {other:?}"),
+                }
+                .fail()
+            }
+        };
+        self.expect(&Token::RParen)?;
+        Ok(fill)
+    }
+
+    fn parse_select_field(&mut self) -> Result<SelectField> {
+        if self.consume(&Token::Mul) {
+            return Ok(SelectField::Wildcard);
+        }
+        let expr = self.parse_additive()?;
+        let alias = if self.consume_keyword("AS") {
+            Some(self.parse_identifier()?)
+        } else {
+            None
+        };
+        Ok(SelectField::Expr { expr, alias })
+    }
+
+    fn parse_dimension(&mut self) -> Result<Dimension> {
+        if self.peek_keyword("time") && self.tokens.get(self.pos + 1) == Some(&Token::LParen) {
+            self.pos += 2;
+            let interval = match self.next() {
+                Some(Token::Duration(nanos)) if nanos > 0 => nanos,
+                other => {
+                    return ParseInfluxqlSnafu {
+                        reason: format!("expect positive duration in time(), found {other:?}"),
+                    }
+                    .fail()
+                }
+            };
+            self.expect(&Token::RParen)?;
+            return Ok(Dimension::Time(interval));
+        }
+        ensure!(
+            self.peek() != Some(&Token::Mul),
+            ParseInfluxqlSnafu {
+                reason: "GROUP BY * is not supported, list the tags instead",
+            }
+        );
+        let tag = self.parse_identifier()?;
+        self.skip_type_hint()?;
+        Ok(Dimension::Tag(tag))
+    }
+
+    /// Skips the `::tag` or `::field` hint after an identifier.
+    fn skip_type_hint(&mut self) -> Result<()> {
+        if self.consume(&Token::DoubleColon) {
+            let _ = self.parse_identifier()?;
+        }
+        Ok(())
+    }
+
+    fn parse_expr(&mut self) -> Result<Expr> {
+        let mut left = self.parse_and()?;
+        while self.consume_keyword("OR") {
+            let right = self.parse_and()?;
+            left = binary(left, BinaryOp::Or, right);
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr> {
+        let mut left = self.parse_comparison()?;
+        while self.consume_keyword("AND") {
+            let right = self.parse_comparison()?;
+            left = binary(left, BinaryOp::And, right);
+        }
+        Ok(left)
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expr> {
+        let left = self.parse_additive()?;
+        let op = match self.peek() {
+            Some(Token::Eq) => BinaryOp::Eq,
+            Some(Token::NotEq) => BinaryOp::NotEq,
+            Some(Token::Lt) => BinaryOp::Lt,
+            Some(Token::LtEq) => BinaryOp::LtEq,
+            Some(Token::Gt) => BinaryOp::Gt,
+            Some(Token::GtEq) => BinaryOp::GtEq,
+            Some(Token::RegexMatch) => BinaryOp::RegexMatch,
+            Some(Token::RegexNotMatch) => BinaryOp::RegexNotMatch,
+            _ => return Ok(left),
+        };
+        self.pos += 1;
+        let right = self.parse_additive()?;
+        Ok(binary(left, op, right))
+    }
+
+    fn parse_additive(&mut self) -> Result<Expr> {
+        let mut left = self.parse_multiplicative()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Add) => BinaryOp::Add,
+                Some(Token::Sub) => BinaryOp::Sub,
+                _ => return Ok(left),
+            };
+            self.pos += 1;
+            let right = self.parse_multiplicative()?;
+            left = binary(left, op, right);
+        }
+    }
+
+    fn parse_multiplicative(&mut self) -> Result<Expr> {
+        let mut left = self.parse_primary()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Mul) => BinaryOp::Mul,
+                Some(Token::Div) => BinaryOp::Div,
+                _ => return Ok(left),
+            };
+            self.pos += 1;
+            let right = self.parse_primary()?;
+            left = binary(left, op, right);
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr> {
+        let expr = match self.next() {
+            Some(Token::LParen) => {
+                let expr = self.parse_expr()?;
+                self.expect(&Token::RParen)?;
+                expr
+            }
+            Some(Token::Sub) => match self.next() {
+                Some(Token::Integer(n)) => Expr::Integer(-n),
+                Some(Token::Float(n)) => Expr::Float(-n),
+                Some(Token::Duration(n)) => Expr::Duration(-n),
+                other => {
+                    return ParseInfluxqlSnafu {
+                        reason: format!("expect number after '-', found {other:?}"),
+                    }
+                    .fail()
+                }
+            },
+            Some(Token::Integer(n)) => Expr::Integer(n),
+            Some(Token::Float(n)) => Expr::Float(n),
+            Some(Token::Duration(n)) => Expr::Duration(n),
+            Some(Token::String(s)) => Expr::String(s),
+            Some(Token::Regex(s)) => Expr::Regex(s),
+            Some(Token::QuotedIdent(ident)) => {
+                self.skip_type_hint()?;
+                Expr::Column(ident)
+            }
+            Some(Token::Ident(ident)) => {
+                if self.consume(&Token::LParen) {
+                    let mut args = vec![];
+                    if !self.consume(&Token::RParen) {
+                        loop {
+                            if self.consume(&Token::Mul) {
+                                args.push(Expr::Wildcard);
+                            } else {
+                                args.push(self.parse_expr()?);
+                            }
+                            if self.consume(&Token::RParen) {
+                                break;
+                            }
+                            self.expect(&Token::Comma)?;
+                        }
+                    }
+                    Expr::Call {
+                        name: ident.to_lowercase(),
+                        args,
+                    }
+                } else if ident.eq_ignore_ascii_case("true") {
+                    Expr::Boolean(true)
+                } else if ident.eq_ignore_ascii_case("false") {
+                    Expr::Boolean(false)
+                } else {
+                    self.skip_type_hint()?;
+                    Expr::Column(ident)
+                }
+            }
+            other => {
+                return ParseInfluxqlSnafu {
+                    reason: format!("unexpected token {other:?}"),
+                }
+                .fail()
+            }
+        };
+        Ok(expr)
+    }
+}
+
+fn binary(left: Expr, op: BinaryOp, right: Expr) -> Expr {
+    Expr::Binary {
+        left: Box::new(left),
+        op,
+        right: Box::new(right),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_one(query: &str) -> InfluxqlStatement {
+        let mut statements = parse_influxql(query).unwrap();
+        assert_eq!(1, statements.len());
+        statements.remove(0)
+    }
+
+    fn column(name: &str) -> Box<Expr> {
+        Box::new(Expr::Column(name.to_string()))
+    }
+
+    #[test]
+    fn test_parse_show() {
+        assert_eq!(
+            InfluxqlStatement::ShowMeasurements { limit: Some(100) },
+            parse_one("SHOW MEASUREMENTS ON mydb LIMIT 100")
+        );
+        assert_eq!(
+            InfluxqlStatement::ShowTagKeys {
+                from: Some("cpu".to_string())
+            },
+            parse_one(r#"show tag keys from "mydb"."autogen"."cpu""#)
+        );
+        assert_eq!(
+            InfluxqlStatement::ShowFieldKeys { from: None },
+            parse_one("SHOW FIELD KEYS;")
+        );
+
+        let statements = parse_influxql("SHOW MEASUREMENTS; SHOW TAG KEYS FROM cpu").unwrap();
+        assert_eq!(2, statements.len());
+
+        assert!(parse_influxql("SHOW RETENTION POLICIES").is_err());
+        assert!(parse_influxql("SHOW MEASUREMENTS SHOW TAG KEYS").is_err());
+        assert!(parse_influxql(" ; ").is_err());
+    }
+
+    #[test]
+    fn test_parse_select() {
+        let statement = parse_one(
+            r#"SELECT mean("usage_user") AS usage, max(usage_system::field) FROM "cpu"
+               WHERE "host" =~ /^web\d+$/ AND time >= now() - 1h30m
+               GROUP BY time(5m), "host" fill(null) ORDER BY time DESC LIMIT 10 OFFSET 2"#,
+        );
+        let InfluxqlStatement::Select(select) = statement else { unreachable!() };
+        assert_eq!(
+            select.fields,
+            vec![
+                SelectField::Expr {
+                    expr: Expr::Call {
+                        name: "mean".to_string(),
+                        args: vec![Expr::Column("usage_user".to_string())],
+                    },
+                    alias: Some("usage".to_string()),
+                },
+                SelectField::Expr {
+                    expr: Expr::Call {
+                        name: "max".to_string(),
+                        args: vec![Expr::Column("usage_system".to_string())],
+                    },
+                    alias: None,
+                },
+            ]
+        );
+        assert_eq!(select.from, "cpu");
+        assert_eq!(
+            select.condition,
+            Some(Expr::Binary {
+                left: Box::new(Expr::Binary {
+                    left: column("host"),
+                    op: BinaryOp::RegexMatch,
+                    right: Box::new(Expr::Regex(r"^web\d+$".to_string())),
+                }),
+                op: BinaryOp::And,
+                right: Box::new(Expr::Binary {
+                    left: column("time"),
+                    op: BinaryOp::GtEq,
+                    right: Box::new(Expr::Binary {
+                        left: Box::new(Expr::Call {
+                            name: "now".to_string(),
+                            args: vec![],
+                        }),
+                        op: BinaryOp::Sub,
+                        right: Box::new(Expr::Duration(5400 * NANOS_PER_SECOND)),
+                    }),
+                }),
+            })
+        );
+        assert_eq!(
+            select.group_by,
+            vec![
+                Dimension::Time(300 * NANOS_PER_SECOND),
+                Dimension::Tag("host".to_string())
+            ]
+        );
+        assert_eq!(select.group_by_time(), Some(300 * NANOS_PER_SECOND));
+        assert_eq!(select.fill, Some(Fill::Null));
+        assert!(select.order_desc);
+        assert_eq!(select.limit, Some(10));
+        assert_eq!(select.offset, Some(2));
+    }
+
+    #[test]
+    fn test_parse_select_expressions() {
+        let statement = parse_one(r"select *, a + b * 2 from m where s = 'it\'s' or x / 2 > -1.5");
+        let InfluxqlStatement::Select(select) = statement else { unreachable!() };
+        assert_eq!(select.fields[0], SelectField::Wildcard);
+        assert_eq!(
+            select.fields[1],
+            SelectField::Expr {
+                expr: Expr::Binary {
+                    left: column("a"),
+                    op: BinaryOp::Add,
+                    right: Box::new(Expr::Binary {
+                        left: column("b"),
+                        op: BinaryOp::Mul,
+                        right: Box::new(Expr::Integer(2)),
+                    }),
+                },
+                alias: None,
+            }
+        );
+        assert!(matches!(
+            select.condition,
+            Some(Expr::Binary {
+                op: BinaryOp::Or,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for query in [
+            "SELECT FROM cpu",
+            "SELECT a FROM",
+            "SELECT a FROM cpu GROUP BY *",
+            "SELECT a FROM cpu GROUP BY time(0s)",
+            "SELECT a FROM cpu ORDER BY host",
+            "SELECT a FROM cpu WHERE host = 'web",
+            "SELECT a FROM cpu WHERE time > 1.5h",
+            "SELECT a FROM cpu WHERE time > 1y",
+            "SELECT a FROM cpu LIMIT -1",
+            "SELECT mean(a) FROM cpu GROUP BY time(1m) fill(zero)",
+            "DROP MEASUREMENT cpu",
+            "SELECT a FROM cpu WHERE a # 1",
+        ] {
+            assert!(parse_influxql(query).is_err(), "query: {query}");
+        }
+    }
+}
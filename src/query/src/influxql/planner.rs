@@ -0,0 +1,968 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use catalog::table_source::DfTableSourceProvider;
+use catalog::CatalogManagerRef;
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use common_time::util::current_time_millis;
+use datafusion::arrow::datatypes::{DataType, IntervalMonthDayNanoType, TimeUnit};
+use datafusion::common::{DFField, DFSchema, OwnedTableReference};
+use datafusion::datasource::DefaultTableSource;
+use datafusion::logical_expr::expr::{AggregateFunction, ScalarFunction, WindowFunction};
+use datafusion::logical_expr::expr_rewriter::normalize_col;
+use datafusion::logical_expr::{
+    binary_expr, AggregateFunction as AggregateFunctionEnum, BuiltInWindowFunction,
+    BuiltinScalarFunction, EmptyRelation, LogicalPlan, LogicalPlanBuilder, Operator, TableSource,
+    WindowFrame, WindowFunction as WindowFunctionEnum,
+};
+use datafusion::prelude::{lit, Column, Expr as DfExpr};
+use datafusion::scalar::ScalarValue;
+use datatypes::prelude::ConcreteDataType;
+use session::context::QueryContext;
+use snafu::{ensure, OptionExt, ResultExt};
+use table::table::adapter::DfTableProviderAdapter;
+use table::TableRef;
+
+use crate::error::{
+    CatalogSnafu, DataFusionSnafu, MissingTimestampColumnSnafu, Result, TableNotFoundSnafu,
+    UnsupportedInfluxqlSnafu,
+};
+use crate::influxql::ast::{
+    BinaryOp, Dimension, Expr, Fill, InfluxqlStatement, SelectField, SelectStatement,
+    FIELD_KEY_COLUMN, FIELD_TYPE_COLUMN, NAME_COLUMN, TAG_KEY_COLUMN, TIME_COLUMN,
+};
+
+/// Column of the row number of each series, used to apply LIMIT and OFFSET per series.
+const SERIES_ROW_NUMBER_COLUMN: &str = "__series_row_number";
+
+/// Columns of a measurement, i.e. a table with a time index.
+struct Measurement {
+    time_index: String,
+    time_unit: TimeUnit,
+    tags: Vec<String>,
+    /// Field names and types.
+    fields: Vec<(String, ConcreteDataType)>,
+}
+
+impl Measurement {
+    fn try_new(table: &TableRef, name: &str) -> Result<Self> {
+        let schema = table.schema();
+        let time_index = schema
+            .timestamp_column()
+            .context(MissingTimestampColumnSnafu { table_name: name })?;
+        let time_unit = match time_index.data_type.as_arrow_type() {
+            DataType::Timestamp(unit, _) => unit,
+            _ => TimeUnit::Millisecond,
+        };
+        let table_info = table.table_info();
+        let meta = &table_info.meta;
+        let fields = meta
+            .field_column_names()
+            .filter_map(|name| {
+                schema
+                    .column_schema_by_name(name)
+                    .map(|c| (name.clone(), c.data_type.clone()))
+            })
+            .collect();
+
+        Ok(Self {
+            time_index: time_index.name.clone(),
+            time_unit,
+            tags: meta.row_key_column_names().cloned().collect(),
+            fields,
+        })
+    }
+
+    fn is_time(&self, expr: &Expr) -> bool {
+        matches!(expr, Expr::Column(name) if name.eq_ignore_ascii_case(TIME_COLUMN) || name == &self.time_index)
+    }
+
+    /// Converts nanoseconds since epoch into a literal of the time index type.
+    fn time_literal(&self, nanos: i64) -> DfExpr {
+        let value = match self.time_unit {
+            TimeUnit::Second => {
+                ScalarValue::TimestampSecond(Some(nanos.div_euclid(1_000_000_000)), None)
+            }
+            TimeUnit::Millisecond => {
+                ScalarValue::TimestampMillisecond(Some(nanos.div_euclid(1_000_000)), None)
+            }
+            TimeUnit::Microsecond => {
+                ScalarValue::TimestampMicrosecond(Some(nanos.div_euclid(1_000)), None)
+            }
+            TimeUnit::Nanosecond => ScalarValue::TimestampNanosecond(Some(nanos), None),
+        };
+        DfExpr::Literal(value)
+    }
+}
+
+/// Plans [InfluxqlStatement]s into DataFusion logical plans.
+pub struct InfluxqlPlanner {
+    table_provider: DfTableSourceProvider,
+    catalog_manager: CatalogManagerRef,
+    catalog: String,
+    schema: String,
+}
+
+impl InfluxqlPlanner {
+    pub fn new(
+        catalog_manager: CatalogManagerRef,
+        disallow_cross_schema_query: bool,
+        query_ctx: &QueryContext,
+    ) -> Self {
+        Self {
+            table_provider: DfTableSourceProvider::new(
+                catalog_manager.clone(),
+                disallow_cross_schema_query,
+                query_ctx,
+            ),
+            catalog_manager,
+            catalog: query_ctx.current_catalog(),
+            schema: query_ctx.current_schema(),
+        }
+    }
+
+    pub async fn plan(&mut self, stmt: InfluxqlStatement) -> Result<LogicalPlan> {
+        match stmt {
+            InfluxqlStatement::Select(select) => self.plan_select(select).await,
+            InfluxqlStatement::ShowMeasurements { limit } => {
+                let mut measurements = self.measurements().await?;
+                if let Some(limit) = limit {
+                    measurements.truncate(limit);
+                }
+                let rows = measurements
+                    .into_iter()
+                    .map(|(name, _)| vec![name])
+                    .collect();
+                string_rows_plan(&[NAME_COLUMN], rows)
+            }
+            InfluxqlStatement::ShowTagKeys { from } => {
+                let rows = self
+                    .measurements_of(from)
+                    .await?
+                    .into_iter()
+                    .flat_map(|(name, measurement)| {
+                        measurement
+                            .tags
+                            .into_iter()
+                            .map(move |tag| vec![name.clone(), tag])
+                    })
+                    .collect();
+                string_rows_plan(&[NAME_COLUMN, TAG_KEY_COLUMN], rows)
+            }
+            InfluxqlStatement::ShowFieldKeys { from } => {
+                let rows = self
+                    .measurements_of(from)
+                    .await?
+                    .into_iter()
+                    .flat_map(|(name, measurement)| {
+                        measurement
+                            .fields
+                            .into_iter()
+                            .map(move |(field, data_type)| {
+                                vec![name.clone(), field, field_type(&data_type).to_string()]
+                            })
+                    })
+                    .collect();
+                string_rows_plan(&[NAME_COLUMN, FIELD_KEY_COLUMN, FIELD_TYPE_COLUMN], rows)
+            }
+        }
+    }
+
+    /// Returns all tables with a time index in the current schema, sorted by name.
+    async fn measurements(&self) -> Result<Vec<(String, Measurement)>> {
+        let mut table_names = self
+            .catalog_manager
+            .table_names(&self.catalog, &self.schema)
+            .await
+            .context(CatalogSnafu)?;
+        table_names.sort();
+
+        let mut measurements = Vec::with_capacity(table_names.len());
+        for name in table_names {
+            let table = self
+                .catalog_manager
+                .table(&self.catalog, &self.schema, &name)
+                .await
+                .context(CatalogSnafu)?;
+            if let Some(table) = table && let Ok(measurement) = Measurement::try_new(&table, &name) {
+                measurements.push((name, measurement));
+            }
+        }
+        Ok(measurements)
+    }
+
+    async fn measurements_of(&self, from: Option<String>) -> Result<Vec<(String, Measurement)>> {
+        let Some(name) = from else {
+            return self.measurements().await;
+        };
+        let table = self
+            .catalog_manager
+            .table(&self.catalog, &self.schema, &name)
+            .await
+            .context(CatalogSnafu)?
+            .context(TableNotFoundSnafu { table: &name })?;
+        let measurement = Measurement::try_new(&table, &name)?;
+        Ok(vec![(name, measurement)])
+    }
+
+    async fn plan_select(&mut self, select: SelectStatement) -> Result<LogicalPlan> {
+        let table_ref = OwnedTableReference::bare(select.from.clone());
+        let source = self
+            .table_provider
+            .resolve_table(table_ref.clone())
+            .await
+            .context(CatalogSnafu)?;
+        let measurement = Measurement::try_new(&table_of(&source, &select.from)?, &select.from)?;
+
+        let mut builder =
+            LogicalPlanBuilder::scan(table_ref, source, None).context(DataFusionSnafu)?;
+        if let Some(condition) = &select.condition {
+            let predicate = condition_expr(condition, &measurement)?;
+            builder = builder.filter(predicate).context(DataFusionSnafu)?;
+        }
+        let input = builder.build().context(DataFusionSnafu)?;
+
+        let group_tags = select
+            .group_by
+            .iter()
+            .filter_map(|d| match d {
+                Dimension::Tag(tag) => Some(tag.clone()),
+                Dimension::Time(_) => None,
+            })
+            .collect::<Vec<_>>();
+        for tag in &group_tags {
+            ensure!(
+                measurement.tags.contains(tag),
+                UnsupportedInfluxqlSnafu {
+                    reason: format!("GROUP BY {tag}, which is not a tag of {}", select.from),
+                }
+            );
+        }
+
+        let mut projection = group_tags.iter().map(|t| ident(t)).collect::<Vec<_>>();
+        let mut names = FieldNames::default();
+        let is_aggregate = select.fields.iter().any(|f| match f {
+            SelectField::Expr { expr, .. } => has_call(expr),
+            SelectField::Wildcard => false,
+        });
+
+        // Buckets without points are omitted, which is `fill(none)`.
+        if let Some(fill) = &select.fill {
+            ensure!(
+                *fill == Fill::None,
+                UnsupportedInfluxqlSnafu {
+                    reason: "fill() options other than none",
+                }
+            );
+        }
+
+        let builder = if is_aggregate {
+            let mut group_exprs = group_tags.iter().map(|t| ident(t)).collect::<Vec<_>>();
+            let time_expr = match select.group_by_time() {
+                Some(interval) => {
+                    let bucket = normalize_col(date_bin(interval, &measurement), &input)
+                        .context(DataFusionSnafu)?;
+                    let bucket_name = bucket.display_name().context(DataFusionSnafu)?;
+                    group_exprs.push(bucket);
+                    ident(&bucket_name)
+                }
+                // Like InfluxDB, the time of an aggregate over all points is the epoch.
+                None => DfExpr::Literal(ScalarValue::TimestampNanosecond(Some(0), None)),
+            };
+            projection.push(time_expr.alias(TIME_COLUMN));
+
+            let mut aggr_exprs = Aggregates {
+                input: &input,
+                exprs: vec![],
+            };
+            for field in &select.fields {
+                let SelectField::Expr { expr, alias } = field else {
+                    return UnsupportedInfluxqlSnafu {
+                        reason: "mixing * with aggregate functions",
+                    }
+                    .fail();
+                };
+                let df_expr = value_expr(expr, &measurement, Some(&mut aggr_exprs))?;
+                projection.push(df_expr.alias(names.name_of(expr, alias.as_ref())));
+            }
+
+            let aggr_exprs = aggr_exprs.exprs;
+            LogicalPlanBuilder::from(input)
+                .aggregate(group_exprs, aggr_exprs)
+                .context(DataFusionSnafu)?
+        } else {
+            ensure!(
+                select.group_by_time().is_none(),
+                UnsupportedInfluxqlSnafu {
+                    reason: "GROUP BY time() requires an aggregate function",
+                }
+            );
+            projection.push(ident(&measurement.time_index).alias(TIME_COLUMN));
+            for field in &select.fields {
+                match field {
+                    SelectField::Wildcard => {
+                        for column in measurement
+                            .tags
+                            .iter()
+                            .filter(|t| !group_tags.contains(t))
+                            .chain(measurement.fields.iter().map(|(f, _)| f))
+                        {
+                            let name = names.name_of(&Expr::Column(column.clone()), None);
+                            projection.push(ident(column).alias(name));
+                        }
+                    }
+                    SelectField::Expr { expr, alias } => {
+                        let df_expr = value_expr(expr, &measurement, None)?;
+                        projection.push(df_expr.alias(names.name_of(expr, alias.as_ref())));
+                    }
+                }
+            }
+            LogicalPlanBuilder::from(input)
+        };
+
+        // Rows of a series are adjacent and ordered by time.
+        let mut sort_exprs = group_tags
+            .iter()
+            .map(|t| ident(t).sort(true, false))
+            .collect::<Vec<_>>();
+        sort_exprs.push(ident(TIME_COLUMN).sort(!select.order_desc, false));
+
+        let mut builder = builder.project(projection).context(DataFusionSnafu)?;
+        if select.limit.is_none() && select.offset.is_none() {
+            builder = builder.sort(sort_exprs).context(DataFusionSnafu)?;
+        } else if group_tags.is_empty() {
+            builder = builder
+                .sort(sort_exprs)
+                .context(DataFusionSnafu)?
+                .limit(select.offset.unwrap_or(0), select.limit)
+                .context(DataFusionSnafu)?;
+        } else {
+            builder = limit_per_series(builder, &group_tags, &select, sort_exprs)?;
+        }
+        builder.build().context(DataFusionSnafu)
+    }
+}
+
+/// Applies LIMIT and OFFSET to each series instead of the whole result, by numbering the
+/// rows of each series in time order.
+fn limit_per_series(
+    builder: LogicalPlanBuilder,
+    group_tags: &[String],
+    select: &SelectStatement,
+    sort_exprs: Vec<DfExpr>,
+) -> Result<LogicalPlanBuilder> {
+    let output = builder
+        .schema()
+        .fields()
+        .iter()
+        .map(|f| ident(f.name()))
+        .collect::<Vec<_>>();
+    let row_number = DfExpr::WindowFunction(WindowFunction::new(
+        WindowFunctionEnum::BuiltInWindowFunction(BuiltInWindowFunction::RowNumber),
+        vec![],
+        group_tags.iter().map(|t| ident(t)).collect(),
+        vec![ident(TIME_COLUMN).sort(!select.order_desc, false)],
+        WindowFrame::new(true),
+    ))
+    .alias(SERIES_ROW_NUMBER_COLUMN);
+
+    let offset = select.offset.unwrap_or(0) as u64;
+    let mut predicate = ident(SERIES_ROW_NUMBER_COLUMN).gt(lit(offset));
+    if let Some(limit) = select.limit {
+        predicate =
+            predicate.and(ident(SERIES_ROW_NUMBER_COLUMN).lt_eq(lit(offset + limit as u64)));
+    }
+
+    builder
+        .window(vec![row_number])
+        .context(DataFusionSnafu)?
+        .filter(predicate)
+        .context(DataFusionSnafu)?
+        .sort(sort_exprs)
+        .context(DataFusionSnafu)?
+        .project(output)
+        .context(DataFusionSnafu)
+}
+
+fn table_of(source: &Arc<dyn TableSource>, name: &str) -> Result<TableRef> {
+    source
+        .as_any()
+        .downcast_ref::<DefaultTableSource>()
+        .and_then(|s| {
+            s.table_provider
+                .as_any()
+                .downcast_ref::<DfTableProviderAdapter>()
+        })
+        .map(|adapter| adapter.table())
+        .context(TableNotFoundSnafu { table: name })
+}
+
+/// Column expression that doesn't parse `.` in `name` as a qualifier.
+fn ident(name: &str) -> DfExpr {
+    DfExpr::Column(Column::from_name(name))
+}
+
+fn has_call(expr: &Expr) -> bool {
+    match expr {
+        Expr::Call { .. } => true,
+        Expr::Binary { left, right, .. } => has_call(left) || has_call(right),
+        _ => false,
+    }
+}
+
+fn date_bin(interval_nanos: i64, measurement: &Measurement) -> DfExpr {
+    DfExpr::ScalarFunction(ScalarFunction {
+        fun: BuiltinScalarFunction::DateBin,
+        args: vec![
+            lit(ScalarValue::IntervalMonthDayNano(Some(
+                IntervalMonthDayNanoType::make_value(0, 0, interval_nanos),
+            ))),
+            ident(&measurement.time_index),
+            lit(ScalarValue::TimestampNanosecond(Some(0), None)),
+        ],
+    })
+}
+
+/// Output names of the selected fields, deduplicated like InfluxDB does (`max`, `max_1`).
+#[derive(Default)]
+struct FieldNames {
+    used: HashMap<String, usize>,
+}
+
+impl FieldNames {
+    fn name_of(&mut self, expr: &Expr, alias: Option<&String>) -> String {
+        let name = match (alias, expr) {
+            (Some(alias), _) => alias.clone(),
+            (None, Expr::Column(column)) => column.clone(),
+            (None, Expr::Call { name, .. }) => name.clone(),
+            (None, expr) => {
+                let mut columns = vec![];
+                collect_names(expr, &mut columns);
+                columns.join("_")
+            }
+        };
+        let count = self.used.entry(name.clone()).or_insert(0);
+        *count += 1;
+        if *count == 1 {
+            name
+        } else {
+            format!("{name}_{}", *count - 1)
+        }
+    }
+}
+
+fn collect_names(expr: &Expr, names: &mut Vec<String>) {
+    match expr {
+        Expr::Column(name) | Expr::Call { name, .. } => names.push(name.clone()),
+        Expr::Binary { left, right, .. } => {
+            collect_names(left, names);
+            collect_names(right, names);
+        }
+        _ => {}
+    }
+}
+
+/// Aggregate expressions of a select, normalized against its input so they can be referred by
+/// name after the aggregation.
+struct Aggregates<'a> {
+    input: &'a LogicalPlan,
+    exprs: Vec<DfExpr>,
+}
+
+impl Aggregates<'_> {
+    /// Adds an aggregate expression and returns the column referring to its result.
+    fn add(&mut self, fun: AggregateFunctionEnum, arg: DfExpr) -> Result<DfExpr> {
+        let expr = DfExpr::AggregateFunction(AggregateFunction {
+            fun,
+            args: vec![arg],
+            distinct: false,
+            filter: None,
+            order_by: None,
+        });
+        let expr = normalize_col(expr, self.input).context(DataFusionSnafu)?;
+        let name = expr.display_name().context(DataFusionSnafu)?;
+        if !self
+            .exprs
+            .iter()
+            .any(|e| e.display_name().ok().as_ref() == Some(&name))
+        {
+            self.exprs.push(expr);
+        }
+        Ok(ident(&name))
+    }
+}
+
+fn aggregate_expr(
+    name: &str,
+    args: &[Expr],
+    measurement: &Measurement,
+    aggregates: &mut Aggregates,
+) -> Result<DfExpr> {
+    let [arg] = args else {
+        return UnsupportedInfluxqlSnafu {
+            reason: format!("{name}() expects exactly one argument"),
+        }
+        .fail();
+    };
+    let arg = match arg {
+        Expr::Wildcard if name == "count" => lit(1i64),
+        Expr::Wildcard => {
+            return UnsupportedInfluxqlSnafu {
+                reason: format!("{name}(*)"),
+            }
+            .fail()
+        }
+        arg => value_expr(arg, measurement, None)?,
+    };
+
+    let fun = match name {
+        "mean" => AggregateFunctionEnum::Avg,
+        "median" => AggregateFunctionEnum::Median,
+        "sum" => AggregateFunctionEnum::Sum,
+        "count" => AggregateFunctionEnum::Count,
+        "min" => AggregateFunctionEnum::Min,
+        "max" => AggregateFunctionEnum::Max,
+        "stddev" => AggregateFunctionEnum::Stddev,
+        "spread" => {
+            let max = aggregates.add(AggregateFunctionEnum::Max, arg.clone())?;
+            let min = aggregates.add(AggregateFunctionEnum::Min, arg)?;
+            return Ok(binary_expr(max, Operator::Minus, min));
+        }
+        _ => {
+            return UnsupportedInfluxqlSnafu {
+                reason: format!(
+                    "function {name}(), supported functions are mean, median, sum, count, min, \
+                     max, stddev and spread"
+                ),
+            }
+            .fail()
+        }
+    };
+    aggregates.add(fun, arg)
+}
+
+/// Plans an expression over the columns of `measurement`. Aggregate functions are only allowed
+/// when `aggregates` is given.
+fn value_expr(
+    expr: &Expr,
+    measurement: &Measurement,
+    aggregates: Option<&mut Aggregates>,
+) -> Result<DfExpr> {
+    let df_expr = match expr {
+        expr if measurement.is_time(expr) => ident(&measurement.time_index),
+        Expr::Column(name) => ident(name),
+        Expr::Integer(v) => lit(*v),
+        Expr::Float(v) => lit(*v),
+        Expr::String(v) | Expr::Regex(v) => lit(v.clone()),
+        Expr::Boolean(v) => lit(*v),
+        Expr::Call { name, args } => match aggregates {
+            Some(aggregates) => aggregate_expr(name, args, measurement, aggregates)?,
+            None => {
+                return UnsupportedInfluxqlSnafu {
+                    reason: format!("function {name}() in this context"),
+                }
+                .fail()
+            }
+        },
+        Expr::Binary { left, op, right } => {
+            let op = match op {
+                BinaryOp::Add => Operator::Plus,
+                BinaryOp::Sub => Operator::Minus,
+                BinaryOp::Mul => Operator::Multiply,
+                BinaryOp::Div => Operator::Divide,
+                op => {
+                    return UnsupportedInfluxqlSnafu {
+                        reason: format!("operator {op:?} in field expressions"),
+                    }
+                    .fail()
+                }
+            };
+            let (left, right) = match aggregates {
+                Some(aggregates) => (
+                    value_expr(left, measurement, Some(&mut *aggregates))?,
+                    value_expr(right, measurement, Some(aggregates))?,
+                ),
+                None => (
+                    value_expr(left, measurement, None)?,
+                    value_expr(right, measurement, None)?,
+                ),
+            };
+            binary_expr(left, op, right)
+        }
+        Expr::Wildcard | Expr::Duration(_) => {
+            return UnsupportedInfluxqlSnafu {
+                reason: format!("{expr:?} in this context"),
+            }
+            .fail()
+        }
+    };
+    Ok(df_expr)
+}
+
+fn condition_expr(expr: &Expr, measurement: &Measurement) -> Result<DfExpr> {
+    let Expr::Binary { left, op, right } = expr else {
+        return UnsupportedInfluxqlSnafu {
+            reason: format!("condition {expr:?}"),
+        }
+        .fail();
+    };
+
+    let operator = match op {
+        BinaryOp::And => {
+            return Ok(condition_expr(left, measurement)?.and(condition_expr(right, measurement)?))
+        }
+        BinaryOp::Or => {
+            return Ok(condition_expr(left, measurement)?.or(condition_expr(right, measurement)?))
+        }
+        BinaryOp::Eq => Operator::Eq,
+        BinaryOp::NotEq => Operator::NotEq,
+        BinaryOp::Lt => Operator::Lt,
+        BinaryOp::LtEq => Operator::LtEq,
+        BinaryOp::Gt => Operator::Gt,
+        BinaryOp::GtEq => Operator::GtEq,
+        BinaryOp::RegexMatch => Operator::RegexMatch,
+        BinaryOp::RegexNotMatch => Operator::RegexNotMatch,
+        BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div => {
+            return UnsupportedInfluxqlSnafu {
+                reason: format!("condition {expr:?}"),
+            }
+            .fail()
+        }
+    };
+    if matches!(op, BinaryOp::RegexMatch | BinaryOp::RegexNotMatch) {
+        ensure!(
+            matches!(right.as_ref(), Expr::Regex(_)),
+            UnsupportedInfluxqlSnafu {
+                reason: format!("{op:?} expects a regular expression, found {right:?}"),
+            }
+        );
+    }
+
+    let (left, right) = if measurement.is_time(left) {
+        (
+            ident(&measurement.time_index),
+            measurement.time_literal(time_nanos(right)?),
+        )
+    } else if measurement.is_time(right) {
+        (
+            measurement.time_literal(time_nanos(left)?),
+            ident(&measurement.time_index),
+        )
+    } else {
+        (
+            value_expr(left, measurement, None)?,
+            value_expr(right, measurement, None)?,
+        )
+    };
+    Ok(binary_expr(left, operator, right))
+}
+
+/// Evaluates a time expression like `now() - 1h` or `'2023-06-01T00:00:00Z'` to nanoseconds.
+fn time_nanos(expr: &Expr) -> Result<i64> {
+    let invalid_time = || {
+        UnsupportedInfluxqlSnafu {
+            reason: format!("time expression {expr:?}"),
+        }
+        .build()
+    };
+
+    match expr {
+        Expr::Call { name, args } if name == "now" && args.is_empty() => {
+            Ok(current_time_millis() * 1_000_000)
+        }
+        Expr::Integer(nanos) | Expr::Duration(nanos) => Ok(*nanos),
+        Expr::Float(nanos) => Ok(*nanos as i64),
+        Expr::String(s) => parse_time_string(s).ok_or_else(invalid_time),
+        Expr::Binary {
+            left,
+            op: BinaryOp::Add,
+            right,
+        } => time_nanos(left)?
+            .checked_add(time_nanos(right)?)
+            .ok_or_else(invalid_time),
+        Expr::Binary {
+            left,
+            op: BinaryOp::Sub,
+            right,
+        } => time_nanos(left)?
+            .checked_sub(time_nanos(right)?)
+            .ok_or_else(invalid_time),
+        _ => Err(invalid_time()),
+    }
+}
+
+fn parse_time_string(s: &str) -> Option<i64> {
+    if let Ok(t) = DateTime::parse_from_rfc3339(s) {
+        return Some(t.timestamp_nanos());
+    }
+    if let Ok(t) = NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f") {
+        return Some(t.timestamp_nanos());
+    }
+    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .ok()
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|t| t.timestamp_nanos())
+}
+
+/// Field type names of `SHOW FIELD KEYS`.
+fn field_type(data_type: &ConcreteDataType) -> &'static str {
+    if data_type.is_float() {
+        "float"
+    } else if data_type.is_boolean() {
+        "boolean"
+    } else if data_type.is_unsigned()
+        || matches!(
+            data_type,
+            ConcreteDataType::Int8(_)
+                | ConcreteDataType::Int16(_)
+                | ConcreteDataType::Int32(_)
+                | ConcreteDataType::Int64(_)
+        )
+    {
+        "integer"
+    } else {
+        "string"
+    }
+}
+
+/// Plans rows of string values with the given column names.
+fn string_rows_plan(columns: &[&str], rows: Vec<Vec<String>>) -> Result<LogicalPlan> {
+    if rows.is_empty() {
+        let fields = columns
+            .iter()
+            .map(|c| DFField::new_unqualified(c, DataType::Utf8, false))
+            .collect();
+        let schema =
+            DFSchema::new_with_metadata(fields, HashMap::new()).context(DataFusionSnafu)?;
+        return Ok(LogicalPlan::EmptyRelation(EmptyRelation {
+            produce_one_row: false,
+            schema: Arc::new(schema),
+        }));
+    }
+
+    let values: Vec<Vec<DfExpr>> = rows
+        .into_iter()
+        .map(|row| row.into_iter().map(lit).collect())
+        .collect();
+    // Columns of a values plan are named `column1`, `column2` and so on.
+    let projection = columns
+        .iter()
+        .enumerate()
+        .map(|(i, c)| ident(&format!("column{}", i + 1)).alias(*c));
+    LogicalPlanBuilder::values(values)
+        .and_then(|builder| builder.project(projection))
+        .and_then(|builder| builder.build())
+        .context(DataFusionSnafu)
+}
+
+#[cfg(test)]
+mod tests {
+    use catalog::local::MemoryCatalogManager;
+    use catalog::{CatalogManager, RegisterTableRequest};
+    use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
+    use datatypes::schema::{ColumnSchema, Schema};
+    use table::metadata::{TableInfoBuilder, TableMetaBuilder};
+    use table::test_util::EmptyTable;
+
+    use super::*;
+    use crate::influxql::parse_influxql;
+
+    async fn create_planner() -> InfluxqlPlanner {
+        let catalog_manager = Arc::new(MemoryCatalogManager::default());
+        let columns = vec![
+            ColumnSchema::new("host", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("region", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new(
+                "ts",
+                ConcreteDataType::timestamp_millisecond_datatype(),
+                false,
+            )
+            .with_time_index(true),
+            ColumnSchema::new("usage_user", ConcreteDataType::float64_datatype(), true),
+            ColumnSchema::new("cores", ConcreteDataType::int64_datatype(), true),
+        ];
+        let table_meta = TableMetaBuilder::default()
+            .schema(Arc::new(Schema::new(columns)))
+            .primary_key_indices(vec![0, 1])
+            .value_indices(vec![3, 4])
+            .next_column_id(1024)
+            .build()
+            .unwrap();
+        let table_info = TableInfoBuilder::default()
+            .name("cpu")
+            .meta(table_meta)
+            .build()
+            .unwrap();
+        let table = Arc::new(EmptyTable::from_table_info(&table_info));
+        assert!(catalog_manager
+            .register_table(RegisterTableRequest {
+                catalog: DEFAULT_CATALOG_NAME.to_string(),
+                schema: DEFAULT_SCHEMA_NAME.to_string(),
+                table_name: "cpu".to_string(),
+                table_id: 1024,
+                table,
+            })
+            .await
+            .is_ok());
+
+        InfluxqlPlanner::new(catalog_manager, false, &QueryContext::new())
+    }
+
+    async fn plan(query: &str) -> Result<LogicalPlan> {
+        let stmt = parse_influxql(query).unwrap().remove(0);
+        create_planner().await.plan(stmt).await
+    }
+
+    fn output_columns(plan: &LogicalPlan) -> Vec<String> {
+        plan.schema()
+            .fields()
+            .iter()
+            .map(|f| f.name().clone())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_plan_raw_select() {
+        let plan = plan(
+            "SELECT usage_user * 100 AS pct, * FROM cpu \
+             WHERE host =~ /web/ AND time >= '2023-06-01T00:00:00Z' \
+             ORDER BY time DESC LIMIT 10",
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            output_columns(&plan),
+            vec!["time", "pct", "host", "region", "usage_user", "cores"]
+        );
+        let display = plan.display_indent().to_string();
+        assert!(display.contains("Limit: skip=0, fetch=10"), "{display}");
+        assert!(display.contains("Sort: time DESC NULLS LAST"), "{display}");
+        assert!(
+            display.contains("cpu.ts >= TimestampMillisecond(1685577600000, None)"),
+            "{display}"
+        );
+        assert!(display.contains("cpu.host ~ Utf8(\"web\")"), "{display}");
+    }
+
+    #[tokio::test]
+    async fn test_plan_aggregate_select() {
+        let plan = plan(
+            "SELECT mean(usage_user), max(usage_user), spread(usage_user), count(*) FROM cpu \
+             WHERE time > now() - 1h GROUP BY time(1m), host fill(none)",
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            output_columns(&plan),
+            vec!["host", "time", "mean", "max", "spread", "count"]
+        );
+        let display = plan.display_indent().to_string();
+        assert!(
+            display.contains("Aggregate: groupBy=[[cpu.host, date_bin("),
+            "{display}"
+        );
+        assert!(
+            display.contains("Sort: cpu.host ASC NULLS LAST, time ASC NULLS LAST"),
+            "{display}"
+        );
+
+        let plan = plan("SELECT sum(cores) FROM cpu").await.unwrap();
+        assert_eq!(output_columns(&plan), vec!["time", "sum"]);
+    }
+
+    #[tokio::test]
+    async fn test_plan_limit_per_series() {
+        let plan = plan(
+            "SELECT mean(usage_user) FROM cpu GROUP BY time(1m), host \
+             ORDER BY time DESC LIMIT 3 OFFSET 1",
+        )
+        .await
+        .unwrap();
+        assert_eq!(output_columns(&plan), vec!["host", "time", "mean"]);
+        let display = plan.display_indent().to_string();
+        assert!(!display.contains("Limit:"), "{display}");
+        assert!(
+            display.contains("ROW_NUMBER() PARTITION BY [cpu.host]"),
+            "{display}"
+        );
+        assert!(
+            display
+                .contains("__series_row_number > UInt64(1) AND __series_row_number <= UInt64(4)"),
+            "{display}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_plan_unsupported_select() {
+        for query in [
+            "SELECT usage_user FROM cpu GROUP BY time(1m)",
+            "SELECT mean(usage_user) FROM cpu GROUP BY usage_user",
+            "SELECT first(usage_user) FROM cpu",
+            "SELECT *, mean(usage_user) FROM cpu",
+            "SELECT mean(usage_user) FROM cpu WHERE time > 'yesterday'",
+            "SELECT usage_user FROM cpu WHERE host =~ 'web'",
+            "SELECT usage_user FROM memory",
+            "SELECT mean(usage_user) FROM cpu GROUP BY time(1m) fill(null)",
+            "SELECT mean(usage_user) FROM cpu GROUP BY time(1m) fill(0)",
+        ] {
+            assert!(plan(query).await.is_err(), "query: {query}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_plan_show() {
+        let plan_show = |query| async move { output_columns(&plan(query).await.unwrap()) };
+
+        assert_eq!(plan_show("SHOW MEASUREMENTS").await, vec![NAME_COLUMN]);
+        assert_eq!(
+            plan_show("SHOW TAG KEYS FROM cpu").await,
+            vec![NAME_COLUMN, TAG_KEY_COLUMN]
+        );
+        assert_eq!(
+            plan_show("SHOW FIELD KEYS").await,
+            vec![NAME_COLUMN, FIELD_KEY_COLUMN, FIELD_TYPE_COLUMN]
+        );
+        assert!(plan("SHOW TAG KEYS FROM memory").await.is_err());
+    }
+
+    #[test]
+    fn test_field_type() {
+        assert_eq!("float", field_type(&ConcreteDataType::float64_datatype()));
+        assert_eq!("integer", field_type(&ConcreteDataType::int64_datatype()));
+        assert_eq!("integer", field_type(&ConcreteDataType::uint32_datatype()));
+        assert_eq!("boolean", field_type(&ConcreteDataType::boolean_datatype()));
+        assert_eq!("string", field_type(&ConcreteDataType::string_datatype()));
+    }
+
+    #[test]
+    fn test_time_nanos() {
+        assert_eq!(
+            1685577600000000000,
+            time_nanos(&Expr::String("2023-06-01T00:00:00Z".to_string())).unwrap()
+        );
+        assert_eq!(
+            1685577600000000000,
+            time_nanos(&Expr::String("2023-06-01".to_string())).unwrap()
+        );
+        let expr = Expr::Binary {
+            left: Box::new(Expr::Integer(1685577600000000000)),
+            op: BinaryOp::Sub,
+            right: Box::new(Expr::Duration(60_000_000_000)),
+        };
+        assert_eq!(1685577540000000000, time_nanos(&expr).unwrap());
+        assert!(time_nanos(&Expr::Column("host".to_string())).is_err());
+    }
+}
@@ -20,6 +20,7 @@ pub mod dist_plan;
 pub mod error;
 pub mod executor;
 pub mod extension_serializer;
+pub mod influxql;
 pub mod logical_optimizer;
 mod metrics;
 mod optimizer;
@@ -33,6 +33,7 @@ use crate::error::{
     MultipleStatementsSnafu, ParseFloatSnafu, ParseTimestampSnafu, QueryParseSnafu, Result,
     UnimplementedSnafu,
 };
+use crate::influxql::InfluxqlStatement;
 use crate::metrics::{METRIC_PARSE_PROMQL_ELAPSED, METRIC_PARSE_SQL_ELAPSED};
 
 const DEFAULT_LOOKBACK: u64 = 5 * 60; // 5m
@@ -44,6 +45,7 @@ pub const ANALYZE_NODE_NAME: &str = "ANALYZE";
 pub enum QueryStatement {
     Sql(Statement),
     Promql(EvalStmt),
+    Influxql(InfluxqlStatement),
 }
 
 impl QueryStatement {
@@ -53,6 +55,10 @@ impl QueryStatement {
                 operation: "sql post process",
             }
             .fail(),
+            QueryStatement::Influxql(_) => UnimplementedSnafu {
+                operation: "influxql post process",
+            }
+            .fail(),
             QueryStatement::Promql(eval_stmt) => {
                 let node_name = match params.get("name") {
                     Some(name) => name.as_str(),
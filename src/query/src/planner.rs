@@ -26,6 +26,8 @@ use snafu::ResultExt;
 use sql::statements::statement::Statement;
 
 use crate::error::{PlanSqlSnafu, QueryPlanSnafu, Result, SqlSnafu};
+use crate::influxql::planner::InfluxqlPlanner;
+use crate::influxql::InfluxqlStatement;
 use crate::parser::QueryStatement;
 use crate::plan::LogicalPlan;
 use crate::query_engine::QueryEngineState;
@@ -87,11 +89,30 @@ impl DfLogicalPlanner {
             self.engine_state.disallow_cross_schema_query(),
             query_ctx.as_ref(),
         );
-        PromPlanner::stmt_to_plan(table_provider, stmt)
-            .await
-            .map(LogicalPlan::DfPlan)
-            .map_err(BoxedError::new)
-            .context(QueryPlanSnafu)
+        PromPlanner::stmt_to_plan_with_evaluation_interval(
+            table_provider,
+            stmt,
+            self.engine_state.promql_evaluation_interval(),
+        )
+        .await
+        .map(LogicalPlan::DfPlan)
+        .map_err(BoxedError::new)
+        .context(QueryPlanSnafu)
+    }
+
+    async fn plan_influxql(
+        &self,
+        stmt: InfluxqlStatement,
+        query_ctx: QueryContextRef,
+    ) -> Result<LogicalPlan> {
+        InfluxqlPlanner::new(
+            self.engine_state.catalog_manager().clone(),
+            self.engine_state.disallow_cross_schema_query(),
+            query_ctx.as_ref(),
+        )
+        .plan(stmt)
+        .await
+        .map(LogicalPlan::DfPlan)
     }
 }
 
@@ -101,6 +122,7 @@ impl LogicalPlanner for DfLogicalPlanner {
         match stmt {
             QueryStatement::Sql(stmt) => self.plan_sql(stmt, query_ctx).await,
             QueryStatement::Promql(stmt) => self.plan_pql(stmt, query_ctx).await,
+            QueryStatement::Influxql(stmt) => self.plan_influxql(stmt, query_ctx).await,
         }
     }
 }
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::time::Duration;
+
 use common_catalog::consts::INFORMATION_SCHEMA_NAME;
 use session::context::QueryContextRef;
 use snafu::ensure;
@@ -21,6 +23,9 @@ use crate::error::{QueryAccessDeniedSnafu, Result};
 #[derive(Default, Clone)]
 pub struct QueryOptions {
     pub disallow_cross_schema_query: bool,
+    /// The step of PromQL subqueries without one, defaults to
+    /// [promql::planner::DEFAULT_EVALUATION_INTERVAL].
+    pub promql_evaluation_interval: Option<Duration>,
 }
 
 // TODO(shuiyisong): remove one method after #559 is done
@@ -15,6 +15,7 @@
 use std::collections::HashMap;
 use std::fmt;
 use std::sync::{Arc, RwLock};
+use std::time::Duration;
 
 use async_trait::async_trait;
 use catalog::CatalogManagerRef;
@@ -34,15 +35,18 @@ use datafusion::physical_optimizer::sort_enforcement::EnforceSorting;
 use datafusion::physical_optimizer::PhysicalOptimizerRule;
 use datafusion::physical_plan::planner::{DefaultPhysicalPlanner, ExtensionPlanner};
 use datafusion::physical_plan::{ExecutionPlan, PhysicalPlanner};
-use datafusion_expr::LogicalPlan as DfLogicalPlan;
+use datafusion_expr::{Analyze, LogicalPlan as DfLogicalPlan};
 use datafusion_optimizer::analyzer::Analyzer;
 use datafusion_optimizer::optimizer::Optimizer;
 use partition::manager::PartitionRuleManager;
 use promql::extension_plan::PromExtensionPlanner;
+use promql::planner::DEFAULT_EVALUATION_INTERVAL;
 use table::table::adapter::DfTableProviderAdapter;
 use table::TableRef;
 
-use crate::dist_plan::{DistExtensionPlanner, DistPlannerAnalyzer};
+use crate::dist_plan::{
+    enable_sub_stage_metrics, DistAnalyzeExec, DistExtensionPlanner, DistPlannerAnalyzer,
+};
 use crate::extension_serializer::ExtensionSerializer;
 use crate::optimizer::order_hint::OrderHintRule;
 use crate::optimizer::type_conversion::TypeConversionRule;
@@ -189,6 +193,13 @@ impl QueryEngineState {
             .unwrap_or(false)
     }
 
+    pub(crate) fn promql_evaluation_interval(&self) -> Duration {
+        self.plugins
+            .map::<QueryOptions, _, _>(|x| x.promql_evaluation_interval)
+            .flatten()
+            .unwrap_or(DEFAULT_EVALUATION_INTERVAL)
+    }
+
     pub(crate) fn session_state(&self) -> SessionState {
         self.df_context.state()
     }
@@ -202,6 +213,7 @@ impl QueryEngineState {
 
 struct DfQueryPlanner {
     physical_planner: DefaultPhysicalPlanner,
+    is_distributed: bool,
 }
 
 #[async_trait]
@@ -211,6 +223,9 @@ impl QueryPlanner for DfQueryPlanner {
         logical_plan: &DfLogicalPlan,
         session_state: &SessionState,
     ) -> DfResult<Arc<dyn ExecutionPlan>> {
+        if self.is_distributed && let DfLogicalPlan::Analyze(analyze) = logical_plan {
+            return self.create_dist_analyze_plan(analyze, session_state).await;
+        }
         self.physical_planner
             .create_physical_plan(logical_plan, session_state)
             .await
@@ -224,12 +239,39 @@ impl DfQueryPlanner {
     ) -> Self {
         let mut planners: Vec<Arc<dyn ExtensionPlanner + Send + Sync>> =
             vec![Arc::new(PromExtensionPlanner)];
+        let mut is_distributed = false;
         if let Some(partition_manager) = partition_manager
          && let Some(datanode_clients) = datanode_clients {
             planners.push(Arc::new(DistExtensionPlanner::new(partition_manager, datanode_clients)));
+            is_distributed = true;
         }
         Self {
             physical_planner: DefaultPhysicalPlanner::with_extension_planners(planners),
+            is_distributed,
+        }
+    }
+
+    /// Plans `EXPLAIN ANALYZE` with [DistAnalyzeExec], so the metrics of the sub-plans
+    /// executed by datanodes are reported too.
+    async fn create_dist_analyze_plan(
+        &self,
+        analyze: &Analyze,
+        session_state: &SessionState,
+    ) -> DfResult<Arc<dyn ExecutionPlan>> {
+        let input = self
+            .physical_planner
+            .create_physical_plan(&analyze.input, session_state)
+            .await?;
+        let input = enable_sub_stage_metrics(input)?;
+        let mut plan: Arc<dyn ExecutionPlan> = Arc::new(DistAnalyzeExec::new(
+            analyze.verbose,
+            input,
+            Arc::new(analyze.schema.as_ref().into()),
+        ));
+        // Optimize again for the new root, e.g. to merge the input into a single partition.
+        for optimizer in session_state.physical_optimizers() {
+            plan = optimizer.optimize(plan, session_state.config_options())?;
         }
+        Ok(plan)
     }
 }
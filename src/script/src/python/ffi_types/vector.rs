@@ -516,6 +516,7 @@ pub fn val_to_pyobj(val: value::Value, vm: &VirtualMachine) -> PyResult {
         value::Value::Int64(v) => vm.ctx.new_int(v).into(),
         value::Value::Float32(v) => vm.ctx.new_float(v.0 as f64).into(),
         value::Value::Float64(v) => vm.ctx.new_float(v.0).into(),
+        value::Value::Decimal128(v) => vm.ctx.new_float(v.to_f64()).into(),
         value::Value::String(s) => vm.ctx.new_str(s.as_utf8()).into(),
         // is this copy necessary?
         value::Value::Binary(b) => vm.ctx.new_bytes(b.deref().to_vec()).into(),
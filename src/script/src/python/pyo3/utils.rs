@@ -65,6 +65,7 @@ pub fn val_to_py_any(py: Python<'_>, val: Value) -> PyResult<PyObject> {
         Value::Int64(val) => val.to_object(py),
         Value::Float32(val) => val.0.to_object(py),
         Value::Float64(val) => val.0.to_object(py),
+        Value::Decimal128(val) => val.to_f64().to_object(py),
         Value::String(val) => val.as_utf8().to_object(py),
         Value::Binary(val) => val.to_object(py),
         Value::Date(val) => val.val().to_object(py),
@@ -20,8 +20,12 @@ use secrecy::SecretString;
 use session::context::UserInfo;
 use snafu::{Location, OptionExt, Snafu};
 
+use crate::auth::file_user_provider::FileUserProvider;
+use crate::auth::permission::{Grant, Permission};
 use crate::auth::user_provider::StaticUserProvider;
 
+pub mod file_user_provider;
+pub mod permission;
 pub mod user_provider;
 
 #[async_trait::async_trait]
@@ -36,6 +40,51 @@ pub trait UserProvider: Send + Sync {
     /// This method should be called after [`authenticate`].
     async fn authorize(&self, catalog: &str, schema: &str, user_info: &UserInfo) -> Result<()>;
 
+    /// [`authorize_permission`] checks whether a user has the [`Permission`]
+    /// on a certain catalog/schema, which is required by the statement to execute.
+    /// Providers without a permission model fall back to [`authorize`].
+    async fn authorize_permission(
+        &self,
+        catalog: &str,
+        schema: &str,
+        user_info: &UserInfo,
+        _permission: Permission,
+    ) -> Result<()> {
+        self.authorize(catalog, schema, user_info).await
+    }
+
+    /// [`create_user`] handles `CREATE USER`, only supported by providers managing users.
+    async fn create_user(
+        &self,
+        _username: &str,
+        _password: &str,
+        _if_not_exists: bool,
+    ) -> Result<()> {
+        UnsupportedOperationSnafu {
+            provider: self.name(),
+            operation: "CREATE USER",
+        }
+        .fail()
+    }
+
+    /// [`grant`] handles `GRANT`, only supported by providers managing users.
+    async fn grant(&self, _grant: &Grant) -> Result<()> {
+        UnsupportedOperationSnafu {
+            provider: self.name(),
+            operation: "GRANT",
+        }
+        .fail()
+    }
+
+    /// [`revoke`] handles `REVOKE`, only supported by providers managing users.
+    async fn revoke(&self, _grant: &Grant) -> Result<()> {
+        UnsupportedOperationSnafu {
+            provider: self.name(),
+            operation: "REVOKE",
+        }
+        .fail()
+    }
+
     /// [`auth`] is a combination of [`authenticate`] and [`authorize`].
     /// In most cases it's preferred for both convenience and performance.
     async fn auth(
@@ -82,6 +131,11 @@ pub fn user_provider_from_option(opt: &String) -> Result<UserProviderRef> {
                 StaticUserProvider::try_from(content).map(|p| Arc::new(p) as UserProviderRef)?;
             Ok(provider)
         }
+        file_user_provider::FILE_USER_PROVIDER => {
+            let provider =
+                FileUserProvider::try_from(content).map(|p| Arc::new(p) as UserProviderRef)?;
+            Ok(provider)
+        }
         _ => InvalidConfigSnafu {
             value: name.to_string(),
             msg: "Invalid UserProviderOption",
@@ -134,6 +188,42 @@ pub enum Error {
         schema: String,
         username: String,
     },
+
+    #[snafu(display(
+        "Permission denied for user '{}' to {} database '{}-{}'",
+        username,
+        permission,
+        catalog,
+        schema
+    ))]
+    PermissionDenied {
+        catalog: String,
+        schema: String,
+        username: String,
+        permission: Permission,
+    },
+
+    #[snafu(display("User already exists, username: {}", username))]
+    UserAlreadyExists { username: String },
+
+    #[snafu(display("Role not found, role: {}", role))]
+    RoleNotFound { role: String },
+
+    #[snafu(display("User provider {} does not support {}", provider, operation))]
+    UnsupportedOperation { provider: String, operation: String },
+
+    #[snafu(display("Failed to parse user file {}, source: {}", path, source))]
+    ParseUserFile {
+        path: String,
+        source: serde_json::Error,
+        location: Location,
+    },
+
+    #[snafu(display("Failed to serialize user file, source: {}", source))]
+    SerializeUserFile {
+        source: serde_json::Error,
+        location: Location,
+    },
 }
 
 impl ErrorExt for Error {
@@ -148,7 +238,14 @@ impl ErrorExt for Error {
             Error::UserNotFound { .. } => StatusCode::UserNotFound,
             Error::UnsupportedPasswordType { .. } => StatusCode::UnsupportedPasswordType,
             Error::UserPasswordMismatch { .. } => StatusCode::UserPasswordMismatch,
-            Error::AccessDenied { .. } => StatusCode::AccessDenied,
+            Error::AccessDenied { .. } | Error::PermissionDenied { .. } => StatusCode::AccessDenied,
+
+            Error::UserAlreadyExists { .. } | Error::RoleNotFound { .. } => {
+                StatusCode::InvalidArguments
+            }
+            Error::UnsupportedOperation { .. } => StatusCode::Unsupported,
+            Error::ParseUserFile { .. } => StatusCode::InvalidArguments,
+            Error::SerializeUserFile { .. } => StatusCode::Internal,
         }
     }
 
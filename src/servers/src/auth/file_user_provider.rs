@@ -0,0 +1,395 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use secrecy::ExposeSecret;
+use session::context::UserInfo;
+use snafu::{ensure, OptionExt, ResultExt};
+
+use crate::auth::permission::{verify_password, Grant, Permission, PermissionStore};
+use crate::auth::user_provider::{auth_mysql_hash_stage_2, check_password};
+use crate::auth::{
+    AccessDeniedSnafu, Error, Identity, IllegalParamSnafu, InvalidConfigSnafu, IoSnafu,
+    ParseUserFileSnafu, Password, PermissionDeniedSnafu, Result, SerializeUserFileSnafu,
+    UnsupportedPasswordTypeSnafu, UserNotFoundSnafu, UserPasswordMismatchSnafu, UserProvider,
+};
+
+pub const FILE_USER_PROVIDER: &str = "file_user_provider";
+
+/// A [UserProvider] backed by a json file of users, roles and their grants, e.g.
+///
+/// ```json
+/// {
+///   "users": {
+///     "root": { "password": "123456", "grants": { "*": ["read", "write"] } },
+///     "alice": { "password": "654321", "roles": ["analyst"] }
+///   },
+///   "roles": { "analyst": { "public": ["read"] } }
+/// }
+/// ```
+///
+/// Changes made by `CREATE USER`, `GRANT` and `REVOKE` are written back to the file.
+/// Users created by `CREATE USER` only have a salted hash of their password and the
+/// `SHA1(SHA1(password))` checked by MySQL native password authentication.
+pub struct FileUserProvider {
+    path: PathBuf,
+    store: RwLock<PermissionStore>,
+}
+
+impl TryFrom<&str> for FileUserProvider {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self> {
+        let path = Path::new(value);
+        ensure!(
+            path.exists() && path.is_file(),
+            InvalidConfigSnafu {
+                value: value.to_string(),
+                msg: "FileUserProviderOption must be a valid file path",
+            }
+        );
+
+        let content = fs::read_to_string(path).context(IoSnafu)?;
+        let store = serde_json::from_str(&content).context(ParseUserFileSnafu { path: value })?;
+        Ok(FileUserProvider {
+            path: path.to_path_buf(),
+            store: RwLock::new(store),
+        })
+    }
+}
+
+impl FileUserProvider {
+    /// Applies `f` to a copy of the store and persists it, the store is left
+    /// untouched if either fails.
+    fn update<F>(&self, f: F) -> Result<()>
+    where
+        F: FnOnce(&mut PermissionStore) -> Result<()>,
+    {
+        let mut store = self.store.write();
+        let mut updated = store.clone();
+        f(&mut updated)?;
+
+        let content = serde_json::to_string_pretty(&updated).context(SerializeUserFileSnafu)?;
+        fs::write(&self.path, content).context(IoSnafu)?;
+        *store = updated;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl UserProvider for FileUserProvider {
+    fn name(&self) -> &str {
+        FILE_USER_PROVIDER
+    }
+
+    async fn authenticate(
+        &self,
+        input_id: Identity<'_>,
+        input_pwd: Password<'_>,
+    ) -> Result<UserInfo> {
+        match input_id {
+            Identity::UserId(username, _) => {
+                ensure!(
+                    !username.is_empty(),
+                    IllegalParamSnafu {
+                        msg: "blank username"
+                    }
+                );
+                let user =
+                    self.store
+                        .read()
+                        .user(username)
+                        .cloned()
+                        .context(UserNotFoundSnafu {
+                            username: username.to_string(),
+                        })?;
+
+                match (user.password_hash, user.password) {
+                    (Some(password_hash), _) => check_password_hash(
+                        username,
+                        &password_hash,
+                        user.mysql_native_password.as_deref(),
+                        input_pwd,
+                    ),
+                    (None, Some(save_pwd)) => {
+                        check_password(username, save_pwd.as_bytes(), input_pwd)
+                    }
+                    (None, None) => UserPasswordMismatchSnafu {
+                        username: username.to_string(),
+                    }
+                    .fail(),
+                }
+            }
+        }
+    }
+
+    async fn authorize(&self, catalog: &str, schema: &str, user_info: &UserInfo) -> Result<()> {
+        let username = user_info.username();
+        ensure!(
+            self.store
+                .read()
+                .has_any_permission(username, catalog, schema),
+            AccessDeniedSnafu {
+                catalog,
+                schema,
+                username,
+            }
+        );
+        Ok(())
+    }
+
+    async fn authorize_permission(
+        &self,
+        catalog: &str,
+        schema: &str,
+        user_info: &UserInfo,
+        permission: Permission,
+    ) -> Result<()> {
+        let username = user_info.username();
+        ensure!(
+            self.store
+                .read()
+                .has_permission(username, catalog, schema, permission),
+            PermissionDeniedSnafu {
+                catalog,
+                schema,
+                username,
+                permission,
+            }
+        );
+        Ok(())
+    }
+
+    async fn create_user(&self, username: &str, password: &str, if_not_exists: bool) -> Result<()> {
+        ensure!(
+            !username.is_empty() && !password.is_empty(),
+            IllegalParamSnafu {
+                msg: "blank username or password"
+            }
+        );
+        self.update(|store| store.create_user(username, password, if_not_exists))
+    }
+
+    async fn grant(&self, grant: &Grant) -> Result<()> {
+        self.update(|store| store.grant(grant))
+    }
+
+    async fn revoke(&self, grant: &Grant) -> Result<()> {
+        self.update(|store| store.revoke(grant))
+    }
+}
+
+/// Checks the password sent by client against the salted hash, or against the
+/// `SHA1(SHA1(password))` for MySQL native passwords.
+fn check_password_hash(
+    username: &str,
+    password_hash: &str,
+    mysql_native_password: Option<&str>,
+    input_pwd: Password<'_>,
+) -> Result<UserInfo> {
+    match input_pwd {
+        Password::PlainText(pwd) => {
+            ensure!(
+                verify_password(password_hash, pwd.expose_secret()),
+                UserPasswordMismatchSnafu {
+                    username: username.to_string(),
+                }
+            );
+            Ok(UserInfo::new(username))
+        }
+        Password::MysqlNativePassword(auth_data, salt) => {
+            ensure!(
+                auth_data.len() == 20,
+                IllegalParamSnafu {
+                    msg: "Illegal MySQL native password format, length != 20"
+                }
+            );
+            // A hand-written hash comes without the stage 2 hash to check against.
+            let hash_stage_2 = mysql_native_password
+                .and_then(|hash| hex::decode(hash).ok())
+                .context(UnsupportedPasswordTypeSnafu {
+                    password_type: "mysql_native_password",
+                })?;
+            auth_mysql_hash_stage_2(auth_data, salt, username, &hash_stage_2)
+                .map(|_| UserInfo::new(username))
+        }
+        Password::PgMD5(_, _) => UnsupportedPasswordTypeSnafu {
+            password_type: "pg_md5",
+        }
+        .fail(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_test_util::temp_dir::create_temp_dir;
+    use sha1::{Digest, Sha1};
+
+    use super::*;
+    use crate::auth::permission::Grantee;
+
+    fn write_user_file(dir: &Path) -> String {
+        let path = dir.join("users.json");
+        fs::write(
+            &path,
+            r#"{
+                "users": {
+                    "root": {"password": "123456", "grants": {"*": ["read", "write"]}},
+                    "alice": {"password": "654321", "roles": ["analyst"]}
+                },
+                "roles": {"analyst": {"public": ["read"]}}
+            }"#,
+        )
+        .unwrap();
+        path.to_str().unwrap().to_string()
+    }
+
+    async fn authenticate(provider: &dyn UserProvider, username: &str, password: &str) -> bool {
+        provider
+            .authenticate(
+                Identity::UserId(username, None),
+                Password::PlainText(password.to_string().into()),
+            )
+            .await
+            .is_ok()
+    }
+
+    #[tokio::test]
+    async fn test_authenticate_and_authorize() {
+        let dir = create_temp_dir("test_file_user_provider_auth");
+        let provider = FileUserProvider::try_from(write_user_file(dir.path()).as_str()).unwrap();
+
+        assert!(authenticate(&provider, "root", "123456").await);
+        assert!(authenticate(&provider, "alice", "654321").await);
+        assert!(!authenticate(&provider, "alice", "123456").await);
+        assert!(!authenticate(&provider, "bob", "123456").await);
+
+        let alice = UserInfo::new("alice");
+        provider
+            .authorize("greptime", "public", &alice)
+            .await
+            .unwrap();
+        assert!(provider
+            .authorize("greptime", "other", &alice)
+            .await
+            .is_err());
+        provider
+            .authorize_permission("greptime", "public", &alice, Permission::Read)
+            .await
+            .unwrap();
+        assert!(provider
+            .authorize_permission("greptime", "public", &alice, Permission::Write)
+            .await
+            .is_err());
+    }
+
+    /// Scrambles the password like a MySQL client does for `mysql_native_password`.
+    fn mysql_scramble(password: &str, salt: &[u8]) -> Vec<u8> {
+        let stage_1 = Sha1::digest(password.as_bytes());
+        let stage_2 = Sha1::digest(stage_1);
+        let mut hasher = Sha1::new();
+        hasher.update(salt);
+        hasher.update(stage_2);
+        let tmp = hasher.finalize();
+        stage_1.iter().zip(tmp.iter()).map(|(a, b)| a ^ b).collect()
+    }
+
+    #[tokio::test]
+    async fn test_mysql_native_password_of_created_user() {
+        let dir = create_temp_dir("test_file_user_provider_mysql_native");
+        let provider = FileUserProvider::try_from(write_user_file(dir.path()).as_str()).unwrap();
+        provider.create_user("bob", "pwd", false).await.unwrap();
+
+        let salt = b"01234567890123456789";
+        let scramble = mysql_scramble("pwd", salt);
+        let _ = provider
+            .authenticate(
+                Identity::UserId("bob", None),
+                Password::MysqlNativePassword(&scramble, salt),
+            )
+            .await
+            .unwrap();
+
+        let scramble = mysql_scramble("other", salt);
+        assert!(provider
+            .authenticate(
+                Identity::UserId("bob", None),
+                Password::MysqlNativePassword(&scramble, salt),
+            )
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_user_management_is_persisted() {
+        let dir = create_temp_dir("test_file_user_provider_persist");
+        let path = write_user_file(dir.path());
+        let provider = FileUserProvider::try_from(path.as_str()).unwrap();
+
+        provider.create_user("bob", "pwd", false).await.unwrap();
+        assert!(provider.create_user("bob", "pwd", false).await.is_err());
+        provider
+            .grant(&Grant::Role {
+                role: "analyst".to_string(),
+                user: "bob".to_string(),
+            })
+            .await
+            .unwrap();
+        provider
+            .grant(&Grant::Permissions {
+                permissions: vec![Permission::Write],
+                database: "public".to_string(),
+                grantee: Grantee::User("bob".to_string()),
+            })
+            .await
+            .unwrap();
+        provider
+            .revoke(&Grant::Permissions {
+                permissions: vec![Permission::Read],
+                database: "public".to_string(),
+                grantee: Grantee::Role("analyst".to_string()),
+            })
+            .await
+            .unwrap();
+
+        // reload from the file, which only has the hash of the password
+        assert!(!fs::read_to_string(&path).unwrap().contains("\"pwd\""));
+        let provider = FileUserProvider::try_from(path.as_str()).unwrap();
+        let bob = UserInfo::new("bob");
+        assert!(authenticate(&provider, "bob", "pwd").await);
+        assert!(!authenticate(&provider, "bob", "other").await);
+        provider
+            .authorize_permission("greptime", "public", &bob, Permission::Write)
+            .await
+            .unwrap();
+        assert!(provider
+            .authorize_permission("greptime", "public", &bob, Permission::Read)
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn test_invalid_file() {
+        assert!(FileUserProvider::try_from("/not/exist/users.json").is_err());
+
+        let dir = create_temp_dir("test_file_user_provider_invalid");
+        let path = dir.path().join("users.json");
+        fs::write(&path, "root=123456").unwrap();
+        assert!(FileUserProvider::try_from(path.to_str().unwrap()).is_err());
+    }
+}
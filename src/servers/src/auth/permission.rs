@@ -0,0 +1,424 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+use std::num::NonZeroU32;
+
+use common_catalog::build_db_string;
+use ring::pbkdf2;
+use serde::{Deserialize, Serialize};
+use snafu::{ensure, OptionExt};
+
+use crate::auth::user_provider::double_sha1;
+use crate::auth::{Result, RoleNotFoundSnafu, UserAlreadyExistsSnafu, UserNotFoundSnafu};
+
+/// The database key in grants that matches all databases.
+pub const ANY_DATABASE: &str = "*";
+
+const SALT_LEN: usize = 16;
+const HASH_LEN: usize = 32;
+/// Prefix of the hashes built by [hash_password], which also names the KDF.
+const PBKDF2_SHA256: &str = "pbkdf2-sha256";
+/// Iterations of PBKDF2 for new hashes, as recommended by OWASP for HMAC-SHA256.
+/// Tests run unoptimized, so they use far fewer.
+const PBKDF2_ITERATIONS: u32 = if cfg!(test) { 1_000 } else { 600_000 };
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Permission {
+    Read,
+    Write,
+}
+
+impl Display for Permission {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Permission::Read => write!(f, "read"),
+            Permission::Write => write!(f, "write"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Grantee {
+    User(String),
+    Role(String),
+}
+
+/// Permissions or roles issued by `GRANT` and `REVOKE`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Grant {
+    /// `permissions` on `database`, which is either a database string built by
+    /// [build_db_string] or [ANY_DATABASE].
+    Permissions {
+        permissions: Vec<Permission>,
+        database: String,
+        grantee: Grantee,
+    },
+    Role {
+        role: String,
+        user: String,
+    },
+}
+
+/// Permissions granted on databases, keyed by the database string or [ANY_DATABASE].
+pub type Grants = BTreeMap<String, BTreeSet<Permission>>;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserEntry {
+    /// The plain text password of users written to the file by hand.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    /// The salted hash of the password built by [hash_password], users created
+    /// by `CREATE USER` only have the hash.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password_hash: Option<String>,
+    /// The hex encoded `SHA1(SHA1(password))` of users created by `CREATE USER`,
+    /// which is what the MySQL `mysql_native_password` authentication checks
+    /// the client's scramble against.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mysql_native_password: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub roles: BTreeSet<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub grants: Grants,
+}
+
+/// Users, roles and their grants. A user's permissions are the union of the
+/// permissions granted to itself and to its roles.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PermissionStore {
+    #[serde(default)]
+    pub users: BTreeMap<String, UserEntry>,
+    #[serde(default)]
+    pub roles: BTreeMap<String, Grants>,
+}
+
+impl PermissionStore {
+    pub fn user(&self, username: &str) -> Option<&UserEntry> {
+        self.users.get(username)
+    }
+
+    /// Returns whether the user has `permission` on the database.
+    pub fn has_permission(
+        &self,
+        username: &str,
+        catalog: &str,
+        schema: &str,
+        permission: Permission,
+    ) -> bool {
+        let Some(user) = self.users.get(username) else { return false };
+        let database = build_db_string(catalog, schema);
+        let granted = |grants: &Grants| {
+            [database.as_str(), ANY_DATABASE].iter().any(|key| {
+                grants
+                    .get(*key)
+                    .map(|permissions| permissions.contains(&permission))
+                    .unwrap_or(false)
+            })
+        };
+
+        granted(&user.grants)
+            || user
+                .roles
+                .iter()
+                .filter_map(|role| self.roles.get(role))
+                .any(granted)
+    }
+
+    /// Returns whether the user has any permission on the database.
+    pub fn has_any_permission(&self, username: &str, catalog: &str, schema: &str) -> bool {
+        [Permission::Read, Permission::Write]
+            .into_iter()
+            .any(|permission| self.has_permission(username, catalog, schema, permission))
+    }
+
+    pub fn create_user(
+        &mut self,
+        username: &str,
+        password: &str,
+        if_not_exists: bool,
+    ) -> Result<()> {
+        if self.users.contains_key(username) {
+            ensure!(
+                if_not_exists,
+                UserAlreadyExistsSnafu {
+                    username: username.to_string()
+                }
+            );
+            return Ok(());
+        }
+        let _ = self.users.insert(
+            username.to_string(),
+            UserEntry {
+                password_hash: Some(hash_password(password)),
+                mysql_native_password: Some(hex::encode(double_sha1(password.as_bytes()))),
+                ..Default::default()
+            },
+        );
+        Ok(())
+    }
+
+    pub fn grant(&mut self, grant: &Grant) -> Result<()> {
+        match grant {
+            Grant::Permissions {
+                permissions,
+                database,
+                grantee,
+            } => {
+                let grants = self.grants_mut(grantee, true)?;
+                grants
+                    .entry(database.clone())
+                    .or_default()
+                    .extend(permissions.iter().cloned());
+            }
+            Grant::Role { role, user } => {
+                ensure!(
+                    self.roles.contains_key(role),
+                    RoleNotFoundSnafu { role: role.clone() }
+                );
+                let _ = self.user_mut(user)?.roles.insert(role.clone());
+            }
+        }
+        Ok(())
+    }
+
+    pub fn revoke(&mut self, grant: &Grant) -> Result<()> {
+        match grant {
+            Grant::Permissions {
+                permissions,
+                database,
+                grantee,
+            } => {
+                let grants = self.grants_mut(grantee, false)?;
+                if let Some(granted) = grants.get_mut(database) {
+                    granted.retain(|permission| !permissions.contains(permission));
+                    if granted.is_empty() {
+                        let _ = grants.remove(database);
+                    }
+                }
+            }
+            Grant::Role { role, user } => {
+                let _ = self.user_mut(user)?.roles.remove(role);
+            }
+        }
+        Ok(())
+    }
+
+    fn user_mut(&mut self, username: &str) -> Result<&mut UserEntry> {
+        self.users.get_mut(username).context(UserNotFoundSnafu {
+            username: username.to_string(),
+        })
+    }
+
+    /// Returns the grants of `grantee`. Roles are created on their first grant.
+    fn grants_mut(&mut self, grantee: &Grantee, create_role: bool) -> Result<&mut Grants> {
+        match grantee {
+            Grantee::User(username) => Ok(&mut self.user_mut(username)?.grants),
+            Grantee::Role(role) if create_role => Ok(self.roles.entry(role.clone()).or_default()),
+            Grantee::Role(role) => self
+                .roles
+                .get_mut(role)
+                .context(RoleNotFoundSnafu { role: role.clone() }),
+        }
+    }
+}
+
+/// Hashes the password by PBKDF2-HMAC-SHA256 with a random salt. The result is
+/// `pbkdf2-sha256$<iterations>$<salt>$<hash>` where the salt and the hash are hex
+/// encoded, so the iterations can be raised without breaking existing hashes.
+pub fn hash_password(password: &str) -> String {
+    let salt: [u8; SALT_LEN] = rand::random();
+    let iterations = NonZeroU32::new(PBKDF2_ITERATIONS).unwrap();
+    let mut hash = [0u8; HASH_LEN];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        &salt,
+        password.as_bytes(),
+        &mut hash,
+    );
+    format!(
+        "{PBKDF2_SHA256}${iterations}${}${}",
+        hex::encode(salt),
+        hex::encode(hash)
+    )
+}
+
+/// Checks the password against the hash built by [hash_password]. The hashes
+/// are compared in constant time.
+pub fn verify_password(password_hash: &str, password: &str) -> bool {
+    let mut parts = password_hash.split('$');
+    let (Some(PBKDF2_SHA256), Some(iterations), Some(salt), Some(hash), None) = (
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+    ) else {
+        return false;
+    };
+    let Ok(iterations) = iterations.parse::<NonZeroU32>() else { return false };
+    match (hex::decode(salt), hex::decode(hash)) {
+        (Ok(salt), Ok(hash)) => pbkdf2::verify(
+            pbkdf2::PBKDF2_HMAC_SHA256,
+            iterations,
+            &salt,
+            password.as_bytes(),
+            &hash,
+        )
+        .is_ok(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store() -> PermissionStore {
+        serde_json::from_str(
+            r#"{
+                "users": {
+                    "root": {"password": "123456", "grants": {"*": ["read", "write"]}},
+                    "alice": {"password": "654321", "roles": ["analyst"]}
+                },
+                "roles": {"analyst": {"public": ["read"], "other-metrics": ["read"]}}
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_has_permission() {
+        let store = store();
+        assert!(store.has_permission("root", "greptime", "public", Permission::Write));
+        assert!(store.has_permission("root", "other", "db", Permission::Read));
+
+        assert!(store.has_permission("alice", "greptime", "public", Permission::Read));
+        assert!(store.has_permission("alice", "other", "metrics", Permission::Read));
+        assert!(!store.has_permission("alice", "greptime", "public", Permission::Write));
+        assert!(!store.has_permission("alice", "greptime", "other", Permission::Read));
+        assert!(!store.has_any_permission("alice", "greptime", "other"));
+
+        assert!(!store.has_any_permission("unknown", "greptime", "public"));
+    }
+
+    #[test]
+    fn test_create_user() {
+        let mut store = store();
+        store.create_user("bob", "pwd", false).unwrap();
+        let bob = store.user("bob").unwrap();
+        assert!(bob.password.is_none());
+        assert!(verify_password(bob.password_hash.as_ref().unwrap(), "pwd"));
+        assert_eq!(
+            bob.mysql_native_password,
+            Some(hex::encode(double_sha1(b"pwd")))
+        );
+        assert!(!store.has_any_permission("bob", "greptime", "public"));
+
+        assert!(store.create_user("bob", "other", false).is_err());
+        store.create_user("bob", "other", true).unwrap();
+        let bob = store.user("bob").unwrap();
+        assert!(verify_password(bob.password_hash.as_ref().unwrap(), "pwd"));
+        assert!(!verify_password(
+            bob.password_hash.as_ref().unwrap(),
+            "other"
+        ));
+    }
+
+    #[test]
+    fn test_hash_password() {
+        let hash = hash_password("123456");
+        assert!(!hash.contains("123456"));
+        assert!(verify_password(&hash, "123456"));
+        assert!(!verify_password(&hash, "654321"));
+        // salted
+        assert_ne!(hash, hash_password("123456"));
+
+        assert!(!verify_password("123456", "123456"));
+        assert!(!verify_password("not-hex$123456", "123456"));
+        assert!(hash.starts_with("pbkdf2-sha256$1000$"));
+        // the iterations are read from the hash
+        let (_, rest) = hash.split_once("$1000$").unwrap();
+        assert!(!verify_password(
+            &format!("pbkdf2-sha256$1001${rest}"),
+            "123456"
+        ));
+    }
+
+    #[test]
+    fn test_grant_and_revoke() {
+        let mut store = store();
+        let grant = Grant::Permissions {
+            permissions: vec![Permission::Write],
+            database: "public".to_string(),
+            grantee: Grantee::Role("writer".to_string()),
+        };
+        store.grant(&grant).unwrap();
+        store
+            .grant(&Grant::Role {
+                role: "writer".to_string(),
+                user: "alice".to_string(),
+            })
+            .unwrap();
+        assert!(store.has_permission("alice", "greptime", "public", Permission::Write));
+
+        store.revoke(&grant).unwrap();
+        assert!(!store.has_permission("alice", "greptime", "public", Permission::Write));
+        assert!(store.roles.get("writer").unwrap().is_empty());
+
+        store
+            .revoke(&Grant::Role {
+                role: "analyst".to_string(),
+                user: "alice".to_string(),
+            })
+            .unwrap();
+        assert!(!store.has_any_permission("alice", "greptime", "public"));
+
+        let grant = Grant::Permissions {
+            permissions: vec![Permission::Read],
+            database: "public".to_string(),
+            grantee: Grantee::User("alice".to_string()),
+        };
+        store.grant(&grant).unwrap();
+        assert!(store.has_permission("alice", "greptime", "public", Permission::Read));
+    }
+
+    #[test]
+    fn test_grant_errors() {
+        let mut store = store();
+        assert!(store
+            .grant(&Grant::Role {
+                role: "unknown".to_string(),
+                user: "alice".to_string(),
+            })
+            .is_err());
+        assert!(store
+            .grant(&Grant::Permissions {
+                permissions: vec![Permission::Read],
+                database: "public".to_string(),
+                grantee: Grantee::User("unknown".to_string()),
+            })
+            .is_err());
+        assert!(store
+            .revoke(&Grant::Permissions {
+                permissions: vec![Permission::Read],
+                database: "public".to_string(),
+                grantee: Grantee::Role("unknown".to_string()),
+            })
+            .is_err());
+    }
+}
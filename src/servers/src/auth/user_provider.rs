@@ -21,6 +21,7 @@ use std::path::Path;
 use async_trait::async_trait;
 use digest;
 use digest::Digest;
+use ring::constant_time;
 use secrecy::ExposeSecret;
 use session::context::UserInfo;
 use sha1::Sha1;
@@ -118,64 +119,86 @@ impl UserProvider for StaticUserProvider {
                     username: username.to_string(),
                 })?;
 
-                match input_pwd {
-                    Password::PlainText(pwd) => {
-                        ensure!(
-                            !pwd.expose_secret().is_empty(),
-                            IllegalParamSnafu {
-                                msg: "blank password"
-                            }
-                        );
-                        return if save_pwd == pwd.expose_secret().as_bytes() {
-                            Ok(UserInfo::new(username))
-                        } else {
-                            UserPasswordMismatchSnafu {
-                                username: username.to_string(),
-                            }
-                            .fail()
-                        };
-                    }
-                    Password::MysqlNativePassword(auth_data, salt) => {
-                        ensure!(
-                            auth_data.len() == 20,
-                            IllegalParamSnafu {
-                                msg: "Illegal MySQL native password format, length != 20"
-                            }
-                        );
-                        auth_mysql(auth_data, salt, username, save_pwd)
-                            .map(|_| UserInfo::new(username))
-                    }
-                    Password::PgMD5(_, _) => UnsupportedPasswordTypeSnafu {
-                        password_type: "pg_md5",
-                    }
-                    .fail(),
-                }
+                check_password(username, save_pwd, input_pwd)
             }
         }
     }
 
+    /// The static provider is a list of credentials set by the operator and has
+    /// no grants to check, so every user it authenticates may access every
+    /// database with any permission. Use the file user provider to restrict users.
     async fn authorize(&self, _catalog: &str, _schema: &str, _user_info: &UserInfo) -> Result<()> {
-        // default allow all
         Ok(())
     }
 }
 
+/// Checks the password sent by client against the saved plain text password.
+pub(crate) fn check_password(
+    username: &str,
+    save_pwd: &[u8],
+    input_pwd: Password<'_>,
+) -> Result<UserInfo> {
+    match input_pwd {
+        Password::PlainText(pwd) => {
+            ensure!(
+                !pwd.expose_secret().is_empty(),
+                IllegalParamSnafu {
+                    msg: "blank password"
+                }
+            );
+            if constant_time::verify_slices_are_equal(save_pwd, pwd.expose_secret().as_bytes())
+                .is_ok()
+            {
+                Ok(UserInfo::new(username))
+            } else {
+                UserPasswordMismatchSnafu {
+                    username: username.to_string(),
+                }
+                .fail()
+            }
+        }
+        Password::MysqlNativePassword(auth_data, salt) => {
+            ensure!(
+                auth_data.len() == 20,
+                IllegalParamSnafu {
+                    msg: "Illegal MySQL native password format, length != 20"
+                }
+            );
+            auth_mysql(auth_data, salt, username, save_pwd).map(|_| UserInfo::new(username))
+        }
+        Password::PgMD5(_, _) => UnsupportedPasswordTypeSnafu {
+            password_type: "pg_md5",
+        }
+        .fail(),
+    }
+}
+
 pub fn auth_mysql(
     auth_data: HashedPassword,
     salt: Salt,
     username: &str,
     save_pwd: &[u8],
+) -> Result<()> {
+    auth_mysql_hash_stage_2(auth_data, salt, username, &double_sha1(save_pwd))
+}
+
+/// Checks the MySQL native password against `hash_stage_2`, which is
+/// `SHA1(SHA1(password))`, so the plain text password is not required.
+pub(crate) fn auth_mysql_hash_stage_2(
+    auth_data: HashedPassword,
+    salt: Salt,
+    username: &str,
+    hash_stage_2: &[u8],
 ) -> Result<()> {
     // ref: https://github.com/mysql/mysql-server/blob/a246bad76b9271cb4333634e954040a970222e0a/sql/auth/password.cc#L62
-    let hash_stage_2 = double_sha1(save_pwd);
-    let tmp = sha1_two(salt, &hash_stage_2);
+    let tmp = sha1_two(salt, hash_stage_2);
     // xor auth_data and tmp
     let mut xor_result = [0u8; 20];
     for i in 0..20 {
         xor_result[i] = auth_data[i] ^ tmp[i];
     }
     let candidate_stage_2 = sha1_one(&xor_result);
-    if candidate_stage_2 == hash_stage_2 {
+    if constant_time::verify_slices_are_equal(&candidate_stage_2, hash_stage_2).is_ok() {
         Ok(())
     } else {
         UserPasswordMismatchSnafu {
@@ -198,7 +221,7 @@ fn sha1_one(data: &[u8]) -> Vec<u8> {
     hasher.finalize().to_vec()
 }
 
-fn double_sha1(data: &[u8]) -> Vec<u8> {
+pub(crate) fn double_sha1(data: &[u8]) -> Vec<u8> {
     sha1_one(&sha1_one(data))
 }
 
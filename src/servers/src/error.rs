@@ -130,6 +130,19 @@ pub enum Error {
         source: common_grpc::error::Error,
     },
 
+    #[snafu(display("Failed to write Graphite data points, source: {}", source))]
+    GraphiteLinesWrite {
+        location: Location,
+        source: common_grpc::error::Error,
+    },
+
+    #[snafu(display("Invalid Graphite template: {}, reason: {}", template, reason))]
+    InvalidGraphiteTemplate {
+        template: String,
+        reason: String,
+        location: Location,
+    },
+
     #[snafu(display("Failed to write prometheus series, source: {}", source))]
     PromSeriesWrite {
         location: Location,
@@ -163,6 +176,18 @@ pub enum Error {
         source: prost::DecodeError,
     },
 
+    #[snafu(display("Failed to decode OTLP request, source: {}", source))]
+    DecodeOtlpRequest {
+        location: Location,
+        source: prost::DecodeError,
+    },
+
+    #[snafu(display("Failed to write OTLP metrics, source: {}", source))]
+    OtlpMetricsWrite {
+        location: Location,
+        source: common_grpc::error::Error,
+    },
+
     #[snafu(display("Failed to decompress prometheus remote request, source: {}", source))]
     DecompressPromRemoteRequest {
         location: Location,
@@ -355,7 +380,9 @@ impl ErrorExt for Error {
             | ConnResetByPeer { .. }
             | InvalidOpentsdbLine { .. }
             | InvalidOpentsdbJsonRequest { .. }
+            | InvalidGraphiteTemplate { .. }
             | DecodePromRemoteRequest { .. }
+            | DecodeOtlpRequest { .. }
             | DecompressPromRemoteRequest { .. }
             | InvalidPromRemoteRequest { .. }
             | InvalidFlightTicket { .. }
@@ -364,9 +391,10 @@ impl ErrorExt for Error {
             | PreparedStmtTypeMismatch { .. }
             | TimePrecision { .. } => StatusCode::InvalidArguments,
 
-            InfluxdbLinesWrite { source, .. } | PromSeriesWrite { source, .. } => {
-                source.status_code()
-            }
+            InfluxdbLinesWrite { source, .. }
+            | GraphiteLinesWrite { source, .. }
+            | PromSeriesWrite { source, .. }
+            | OtlpMetricsWrite { source, .. } => source.status_code(),
 
             Hyper { .. } => StatusCode::Unknown,
             TlsRequired { .. } => StatusCode::Unknown,
@@ -478,10 +506,15 @@ impl IntoResponse for Error {
             | Error::InvalidOpentsdbLine { .. }
             | Error::InvalidOpentsdbJsonRequest { .. }
             | Error::DecodePromRemoteRequest { .. }
+            | Error::DecodeOtlpRequest { .. }
+            | Error::OtlpMetricsWrite { .. }
             | Error::DecompressPromRemoteRequest { .. }
             | Error::InvalidPromRemoteRequest { .. }
             | Error::InvalidQuery { .. }
             | Error::TimePrecision { .. } => (HttpStatusCode::BAD_REQUEST, self.to_string()),
+            _ if self.status_code() == StatusCode::RateLimited => {
+                (HttpStatusCode::TOO_MANY_REQUESTS, self.to_string())
+            }
             _ => {
                 logging::error!(self; "Failed to handle HTTP request");
 
@@ -0,0 +1,382 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::collections::HashMap;
+
+use api::v1::column::SemanticType;
+use api::v1::{InsertRequest as GrpcInsertRequest, InsertRequests};
+use common_grpc::writer::{LinesWriter, Precision};
+use common_telemetry::logging::warn;
+use common_time::util::current_time_millis;
+use metrics::counter;
+use snafu::ResultExt;
+
+use crate::error::{self, Result};
+use crate::graphite::template::Templates;
+use crate::metrics::METRIC_GRAPHITE_DROPPED_DATA_POINTS;
+
+pub const GRAPHITE_TIMESTAMP_COLUMN_NAME: &str = "greptime_timestamp";
+pub const GRAPHITE_FIELD_COLUMN_NAME: &str = "greptime_value";
+
+/// A data point of the Graphite plaintext protocol: `metric.path value timestamp`, where the
+/// path may carry tags as in `metric.path;tag1=value1;tag2=value2`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataPoint {
+    table: String,
+    field: String,
+    tags: Vec<(String, String)>,
+    value: f64,
+    ts_millis: i64,
+}
+
+impl DataPoint {
+    pub fn try_create(line: &str, templates: &Templates) -> Result<Self> {
+        let tokens = line.split_whitespace().collect::<Vec<&str>>();
+        if tokens.len() != 3 {
+            return error::InvalidQuerySnafu {
+                reason: format!(
+                    "graphite: expect `path value timestamp`, got {} tokens",
+                    tokens.len()
+                ),
+            }
+            .fail();
+        }
+
+        let mut path_tokens = tokens[0].split(';');
+        // `split` always yields at least one item.
+        let path = path_tokens.next().unwrap();
+        if path.is_empty() {
+            return error::InvalidQuerySnafu {
+                reason: format!("graphite: empty metric path: {}", tokens[0]),
+            }
+            .fail();
+        }
+
+        let mut tagged = Vec::new();
+        for token in path_tokens {
+            let (tagk, tagv) = match token.split_once('=') {
+                Some((k, v)) if !k.is_empty() && !v.is_empty() => (k, v),
+                _ => {
+                    return error::InvalidQuerySnafu {
+                        reason: format!("graphite: invalid tag: {token}"),
+                    }
+                    .fail()
+                }
+            };
+            if tagged.iter().any(|(k, _)| k == tagk) {
+                return error::InvalidQuerySnafu {
+                    reason: format!("graphite: duplicate tag: {tagk}"),
+                }
+                .fail();
+            }
+            tagged.push((tagk.to_string(), tagv.to_string()));
+        }
+
+        let value = tokens[1].parse::<f64>().map_err(|_| {
+            error::InvalidQuerySnafu {
+                reason: format!("graphite: invalid value: {}", tokens[1]),
+            }
+            .build()
+        })?;
+
+        // Timestamps are in (possibly fractional) seconds, `-1` asks the server to use its
+        // current time.
+        let ts_millis = match tokens[2].parse::<f64>() {
+            Ok(t) if t == -1.0 => current_time_millis(),
+            Ok(t) if t.is_finite() && t >= 0.0 => (t * 1000.0) as i64,
+            _ => {
+                return error::InvalidQuerySnafu {
+                    reason: format!("graphite: invalid timestamp: {}", tokens[2]),
+                }
+                .fail()
+            }
+        };
+
+        let metric_path = templates.apply(path);
+        // Tags given in the line take precedence over the ones derived by templates.
+        let mut tags = metric_path
+            .tags
+            .into_iter()
+            .filter(|(k, _)| !tagged.iter().any(|(tagk, _)| tagk == k))
+            .collect::<Vec<_>>();
+        tags.extend(tagged);
+        let field = metric_path
+            .field
+            .unwrap_or_else(|| GRAPHITE_FIELD_COLUMN_NAME.to_string());
+
+        // Each column is either the timestamp, the field or a tag.
+        let conflict = if field == GRAPHITE_TIMESTAMP_COLUMN_NAME {
+            Some(field.as_str())
+        } else {
+            tags.iter()
+                .map(|(k, _)| k.as_str())
+                .find(|k| *k == field || *k == GRAPHITE_TIMESTAMP_COLUMN_NAME)
+        };
+        if let Some(column) = conflict {
+            return error::InvalidQuerySnafu {
+                reason: format!("graphite: column {column} is used more than once"),
+            }
+            .fail();
+        }
+
+        Ok(DataPoint {
+            table: metric_path.table,
+            field,
+            tags,
+            value,
+            ts_millis,
+        })
+    }
+
+    pub fn table(&self) -> &str {
+        &self.table
+    }
+
+    pub fn field(&self) -> &str {
+        &self.field
+    }
+
+    pub fn tags(&self) -> &Vec<(String, String)> {
+        &self.tags
+    }
+
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    pub fn ts_millis(&self) -> i64 {
+        self.ts_millis
+    }
+}
+
+/// Batches data points into one insert request per table.
+///
+/// A data point is skipped if any of its columns has a different semantic type in the former
+/// data points of the same table, e.g. its tag is the field of another data point, so that it
+/// doesn't fail the whole batch.
+pub fn to_grpc_insert_requests(data_points: &[DataPoint]) -> Result<InsertRequests> {
+    let mut writers: HashMap<&str, (LinesWriter, HashMap<&str, SemanticType>)> = HashMap::new();
+    let mut dropped = 0;
+    for data_point in data_points {
+        let (writer, column_types) = writers.entry(data_point.table()).or_insert_with(|| {
+            (
+                LinesWriter::with_lines(data_points.len()),
+                HashMap::from([(GRAPHITE_TIMESTAMP_COLUMN_NAME, SemanticType::Timestamp)]),
+            )
+        });
+
+        let columns = std::iter::once((data_point.field(), SemanticType::Field)).chain(
+            data_point
+                .tags()
+                .iter()
+                .map(|(tagk, _)| (tagk.as_str(), SemanticType::Tag)),
+        );
+        if let Some((column, _)) = columns.clone().find(|(column, semantic_type)| {
+            column_types
+                .get(column)
+                .map_or(false, |existing| existing != semantic_type)
+        }) {
+            warn!(
+                "Dropped Graphite data point of table {}, column {} conflicts with other data points",
+                data_point.table(),
+                column
+            );
+            dropped += 1;
+            continue;
+        }
+        column_types.extend(columns);
+
+        writer
+            .write_ts(
+                GRAPHITE_TIMESTAMP_COLUMN_NAME,
+                (data_point.ts_millis(), Precision::Millisecond),
+            )
+            .context(error::GraphiteLinesWriteSnafu)?;
+        writer
+            .write_f64(data_point.field(), data_point.value())
+            .context(error::GraphiteLinesWriteSnafu)?;
+        for (tagk, tagv) in data_point.tags() {
+            writer
+                .write_tag(tagk, tagv)
+                .context(error::GraphiteLinesWriteSnafu)?;
+        }
+        writer.commit();
+    }
+    if dropped > 0 {
+        counter!(METRIC_GRAPHITE_DROPPED_DATA_POINTS, dropped);
+    }
+
+    let inserts = writers
+        .into_iter()
+        .map(|(table_name, (writer, _))| {
+            let (columns, row_count) = writer.finish();
+            GrpcInsertRequest {
+                table_name: table_name.to_string(),
+                region_number: 0,
+                columns,
+                row_count,
+            }
+        })
+        .collect();
+    Ok(InsertRequests { inserts })
+}
+
+#[cfg(test)]
+mod tests {
+    use api::v1::ColumnDataType;
+
+    use super::*;
+
+    #[test]
+    fn test_try_create() {
+        let templates = Templates::default();
+        let try_create = DataPoint::try_create;
+
+        let data_point = try_create("sys.cpu.load 0.5 1686000000", &templates).unwrap();
+        assert_eq!(data_point.table(), "sys.cpu.load");
+        assert_eq!(data_point.field(), GRAPHITE_FIELD_COLUMN_NAME);
+        assert_eq!(data_point.value(), 0.5);
+        assert_eq!(data_point.ts_millis(), 1686000000000);
+        assert!(data_point.tags().is_empty());
+
+        let data_point =
+            try_create("sys.cpu.load;host=web01;dc=a 1 1686000000.25", &templates).unwrap();
+        assert_eq!(data_point.table(), "sys.cpu.load");
+        assert_eq!(data_point.ts_millis(), 1686000000250);
+        assert_eq!(
+            data_point.tags(),
+            &vec![
+                ("host".to_string(), "web01".to_string()),
+                ("dc".to_string(), "a".to_string())
+            ]
+        );
+
+        let data_point = try_create("sys.cpu.load 1 -1", &templates).unwrap();
+        assert!(data_point.ts_millis() > 0);
+
+        assert!(try_create("sys.cpu.load 1", &templates).is_err());
+        assert!(try_create("sys.cpu.load 1 2 3", &templates).is_err());
+        assert!(try_create(";host=web01 1 1686000000", &templates).is_err());
+        assert!(try_create("sys.cpu.load;host 1 1686000000", &templates).is_err());
+        assert!(try_create("sys.cpu.load;host=a;host=b 1 1686000000", &templates).is_err());
+        assert!(try_create("sys.cpu.load abc 1686000000", &templates).is_err());
+        assert!(try_create("sys.cpu.load 1 abc", &templates).is_err());
+        assert!(try_create("sys.cpu.load 1 -2", &templates).is_err());
+        assert!(try_create("sys.cpu.load;greptime_value=a 1 1686000000", &templates).is_err());
+        assert!(try_create("sys.cpu.load;greptime_timestamp=a 1 1686000000", &templates).is_err());
+    }
+
+    #[test]
+    fn test_try_create_with_templates() {
+        let templates = Templates::try_new(&["servers.* .host.measurement.field* dc=a"]).unwrap();
+
+        let data_point =
+            DataPoint::try_create("servers.web01.cpu.load;dc=b 0.5 1686000000", &templates)
+                .unwrap();
+        assert_eq!(data_point.table(), "cpu");
+        assert_eq!(data_point.field(), "load");
+        assert_eq!(
+            data_point.tags(),
+            &vec![
+                ("host".to_string(), "web01".to_string()),
+                ("dc".to_string(), "b".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_to_grpc_insert_requests() {
+        let templates = Templates::default();
+        let data_points = [
+            "sys.cpu.load;host=web01 0.5 1686000000",
+            "sys.cpu.load;host=web02 0.7 1686000001",
+            "sys.mem.used 1024 1686000000",
+        ]
+        .iter()
+        .map(|line| DataPoint::try_create(line, &templates).unwrap())
+        .collect::<Vec<_>>();
+
+        let mut requests = to_grpc_insert_requests(&data_points).unwrap().inserts;
+        requests.sort_by(|a, b| a.table_name.cmp(&b.table_name));
+        assert_eq!(requests.len(), 2);
+
+        let cpu = &requests[0];
+        assert_eq!(cpu.table_name, "sys.cpu.load");
+        assert_eq!(cpu.row_count, 2);
+        assert_eq!(cpu.columns.len(), 3);
+
+        let ts = &cpu.columns[0];
+        assert_eq!(ts.column_name, GRAPHITE_TIMESTAMP_COLUMN_NAME);
+        assert_eq!(ts.semantic_type, SemanticType::Timestamp as i32);
+        assert_eq!(
+            ts.values.as_ref().unwrap().ts_millisecond_values,
+            vec![1686000000000, 1686000001000]
+        );
+
+        let value = &cpu.columns[1];
+        assert_eq!(value.column_name, GRAPHITE_FIELD_COLUMN_NAME);
+        assert_eq!(value.datatype, ColumnDataType::Float64 as i32);
+        assert_eq!(value.values.as_ref().unwrap().f64_values, vec![0.5, 0.7]);
+
+        let host = &cpu.columns[2];
+        assert_eq!(host.column_name, "host");
+        assert_eq!(host.semantic_type, SemanticType::Tag as i32);
+        assert_eq!(
+            host.values.as_ref().unwrap().string_values,
+            vec!["web01".to_string(), "web02".to_string()]
+        );
+
+        let mem = &requests[1];
+        assert_eq!(mem.table_name, "sys.mem.used");
+        assert_eq!(mem.row_count, 1);
+        assert_eq!(mem.columns.len(), 2);
+    }
+
+    #[test]
+    fn test_to_grpc_insert_requests_with_conflicts() {
+        let templates = Templates::try_new(&["servers.* .host.measurement.field"]).unwrap();
+        let data_points = [
+            "servers.web01.cpu.load 0.5 1686000000",
+            // `load` is a field of the former data point.
+            "servers.web02.cpu.idle;load=high 0.7 1686000000",
+            // `host` is a tag of the former data point.
+            "servers.web03.cpu.host 1 1686000000",
+            "servers.web04.cpu.load 0.9 1686000000",
+            // Conflicts in other tables don't matter.
+            "other;load=high 1 1686000000",
+        ]
+        .iter()
+        .map(|line| DataPoint::try_create(line, &templates).unwrap())
+        .collect::<Vec<_>>();
+
+        let mut requests = to_grpc_insert_requests(&data_points).unwrap().inserts;
+        requests.sort_by(|a, b| a.table_name.cmp(&b.table_name));
+        assert_eq!(requests.len(), 2);
+
+        let cpu = &requests[0];
+        assert_eq!(cpu.table_name, "cpu");
+        assert_eq!(cpu.row_count, 2);
+        let host = cpu
+            .columns
+            .iter()
+            .find(|column| column.column_name == "host")
+            .unwrap();
+        assert_eq!(
+            host.values.as_ref().unwrap().string_values,
+            vec!["web01".to_string(), "web04".to_string()]
+        );
+
+        assert_eq!(requests[1].table_name, "other");
+        assert_eq!(requests[1].row_count, 1);
+    }
+}
@@ -0,0 +1,309 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_telemetry::logging::{error, warn};
+use common_telemetry::timer;
+use metrics::increment_counter;
+use session::context::{QueryContext, QueryContextRef};
+use snafu::ResultExt;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, BufReader};
+
+use crate::error::{self, Result};
+use crate::graphite::codec::DataPoint;
+use crate::graphite::template::Templates;
+use crate::metrics::METRIC_GRAPHITE_DROPPED_DATA_POINTS;
+use crate::query_handler::GraphiteProtocolHandlerRef;
+use crate::shutdown::Shutdown;
+
+/// Max number of data points buffered before they are written.
+const MAX_BATCH_SIZE: usize = 1024;
+/// Max length of a line in bytes. Longer lines are dropped without being buffered as a whole.
+const MAX_LINE_LENGTH: usize = 64 * 1024;
+/// Buffered data points are written at least this often, so that a slow sender does not
+/// delay its own writes for long.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Per-connection handler. Reads `\n` terminated lines from the connection and writes them to
+/// [GraphiteProtocolHandler](crate::query_handler::GraphiteProtocolHandler) in batches.
+///
+/// Graphite senders never read from the socket, so malformed lines and failed writes are logged
+/// instead of being replied.
+pub(crate) struct Handler<S: AsyncRead + Unpin> {
+    query_handler: GraphiteProtocolHandlerRef,
+    templates: Arc<Templates>,
+    lines: LineReader<S>,
+    shutdown: Shutdown,
+}
+
+/// Reads `\n` terminated lines, and skips the lines longer than [MAX_LINE_LENGTH].
+struct LineReader<S: AsyncRead + Unpin> {
+    reader: BufReader<S>,
+    /// The line being read, kept across [LineReader::next_line] calls.
+    line: Vec<u8>,
+    /// Whether the rest of a too long line is being skipped.
+    skipping: bool,
+}
+
+impl<S: AsyncRead + Unpin> Handler<S> {
+    pub(crate) fn new(
+        query_handler: GraphiteProtocolHandlerRef,
+        templates: Arc<Templates>,
+        stream: S,
+        shutdown: Shutdown,
+    ) -> Self {
+        Self {
+            query_handler,
+            templates,
+            lines: LineReader {
+                reader: BufReader::new(stream),
+                line: Vec::new(),
+                skipping: false,
+            },
+            shutdown,
+        }
+    }
+
+    pub(crate) async fn run(&mut self) -> Result<()> {
+        let ctx = QueryContext::arc();
+        let mut batch = Vec::with_capacity(MAX_BATCH_SIZE);
+        let mut flush_interval = tokio::time::interval(FLUSH_INTERVAL);
+
+        while !self.shutdown.is_shutdown() {
+            let flush = tokio::select! {
+                line = self.lines.next_line() => {
+                    // The peer closed the socket.
+                    let Some(line) = line? else {
+                        break;
+                    };
+                    if let Some(data_point) = self.parse_line(&line) {
+                        batch.push(data_point);
+                    }
+                    batch.len() >= MAX_BATCH_SIZE
+                }
+                _ = flush_interval.tick() => true,
+                _ = self.shutdown.recv() => break,
+            };
+
+            if flush {
+                self.flush(&mut batch, &ctx).await;
+            }
+        }
+
+        // Writes whatever is left, whether the peer is gone or the server is shutting down.
+        self.flush(&mut batch, &ctx).await;
+        Ok(())
+    }
+
+    /// Parses a line into a data point, or returns `None` if it's empty or malformed.
+    fn parse_line(&self, line: &[u8]) -> Option<DataPoint> {
+        let line = match std::str::from_utf8(line) {
+            Ok(line) => line.trim(),
+            Err(e) => {
+                warn!(
+                    "Dropped Graphite line: {}, error: {}",
+                    String::from_utf8_lossy(line),
+                    e
+                );
+                increment_counter!(METRIC_GRAPHITE_DROPPED_DATA_POINTS);
+                return None;
+            }
+        };
+        if line.is_empty() {
+            return None;
+        }
+        match DataPoint::try_create(line, &self.templates) {
+            Ok(data_point) => Some(data_point),
+            Err(e) => {
+                warn!("Dropped Graphite line: {}, error: {}", line, e);
+                increment_counter!(METRIC_GRAPHITE_DROPPED_DATA_POINTS);
+                None
+            }
+        }
+    }
+
+    async fn flush(&self, batch: &mut Vec<DataPoint>, ctx: &QueryContextRef) {
+        if batch.is_empty() {
+            return;
+        }
+
+        let _timer = timer!(crate::metrics::METRIC_TCP_GRAPHITE_LINE_WRITE_ELAPSED);
+        if let Err(e) = self.query_handler.exec(batch.as_slice(), ctx.clone()).await {
+            error!(e; "Failed to write {} Graphite data points", batch.len());
+        }
+        batch.clear();
+    }
+}
+
+impl<S: AsyncRead + Unpin> LineReader<S> {
+    /// Reads the next line without the line terminator, or returns `None` if the peer closed
+    /// the socket. Lines longer than [MAX_LINE_LENGTH] are skipped.
+    ///
+    /// It is cancel safe as the partially read line is kept in the reader.
+    async fn next_line(&mut self) -> Result<Option<Vec<u8>>> {
+        loop {
+            let limit = (MAX_LINE_LENGTH + 1 - self.line.len()) as u64;
+            let n = (&mut self.reader)
+                .take(limit)
+                .read_until(b'\n', &mut self.line)
+                .await
+                .context(error::InternalIoSnafu)?;
+
+            if self.line.last() == Some(&b'\n') {
+                let mut line = std::mem::take(&mut self.line);
+                if std::mem::take(&mut self.skipping) {
+                    // The end of a too long line.
+                    continue;
+                }
+                let _ = line.pop();
+                return Ok(Some(line));
+            }
+            if n == 0 {
+                // The last line may not be terminated.
+                let line = std::mem::take(&mut self.line);
+                return Ok((!line.is_empty() && !self.skipping).then_some(line));
+            }
+            if self.line.len() > MAX_LINE_LENGTH {
+                if !self.skipping {
+                    warn!(
+                        "Dropped Graphite line longer than {} bytes: {}...",
+                        MAX_LINE_LENGTH,
+                        String::from_utf8_lossy(&self.line[..64])
+                    );
+                    increment_counter!(METRIC_GRAPHITE_DROPPED_DATA_POINTS);
+                    self.skipping = true;
+                }
+                self.line.clear();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use async_trait::async_trait;
+    use tokio::io::AsyncWriteExt;
+    use tokio::net::{TcpListener, TcpStream};
+    use tokio::sync::{broadcast, mpsc};
+
+    use super::*;
+    use crate::query_handler::GraphiteProtocolHandler;
+
+    struct DummyQueryHandler {
+        tx: mpsc::Sender<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl GraphiteProtocolHandler for DummyQueryHandler {
+        async fn exec(&self, data_points: &[DataPoint], _ctx: QueryContextRef) -> Result<()> {
+            let tables = data_points.iter().map(|p| p.table().to_string()).collect();
+            self.tx.send(tables).await.unwrap();
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run() {
+        let (tx, mut rx) = mpsc::channel(100);
+
+        let query_handler = Arc::new(DummyQueryHandler { tx });
+        let templates = Arc::new(Templates::try_new(&["servers.* .host.measurement"]).unwrap());
+        let (notify_shutdown, _) = broadcast::channel(1);
+        let addr = start_server(query_handler, templates, notify_shutdown.clone()).await;
+
+        let mut client = TcpStream::connect(addr).await.unwrap();
+        client
+            .write_all(
+                b"servers.web01.cpu 0.5 1686000000\n\
+                  invalid line\n\
+                  sys.mem;host=web01 1024 1686000000\r\n",
+            )
+            .await
+            .unwrap();
+
+        // Buffered data points are written by the flush interval.
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            vec!["cpu".to_string(), "sys.mem".to_string()]
+        );
+
+        // The rest is written when the peer closes the connection.
+        client
+            .write_all(b"servers.web02.disk 1 1686000000\n")
+            .await
+            .unwrap();
+        drop(client);
+        assert_eq!(rx.recv().await.unwrap(), vec!["disk".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_skip_long_line() {
+        let (tx, mut rx) = mpsc::channel(100);
+
+        let query_handler = Arc::new(DummyQueryHandler { tx });
+        let templates = Arc::new(Templates::default());
+        let (notify_shutdown, _) = broadcast::channel(1);
+        let addr = start_server(query_handler, templates, notify_shutdown.clone()).await;
+
+        let mut client = TcpStream::connect(addr).await.unwrap();
+        let long_path = "a".repeat(MAX_LINE_LENGTH * 2);
+        client
+            .write_all(format!("{long_path} 1 1686000000\nsys.cpu 1 1686000000\n").as_bytes())
+            .await
+            .unwrap();
+        // Invalid UTF-8 is dropped too, and the unterminated last line is kept.
+        client
+            .write_all(b"sys.\xff 1 1686000000\nsys.mem 1 1686000000")
+            .await
+            .unwrap();
+        drop(client);
+
+        let mut tables = Vec::new();
+        while let Some(batch) = rx.recv().await {
+            tables.extend(batch);
+            if tables.len() >= 2 {
+                break;
+            }
+        }
+        assert_eq!(tables, vec!["sys.cpu".to_string(), "sys.mem".to_string()]);
+    }
+
+    async fn start_server(
+        query_handler: GraphiteProtocolHandlerRef,
+        templates: Arc<Templates>,
+        notify_shutdown: broadcast::Sender<()>,
+    ) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let _handle = tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+
+                let query_handler = query_handler.clone();
+                let templates = templates.clone();
+                let shutdown = Shutdown::new(notify_shutdown.subscribe());
+                let _handle = tokio::spawn(async move {
+                    Handler::new(query_handler, templates, stream, shutdown)
+                        .run()
+                        .await
+                });
+            }
+        });
+        addr
+    }
+}
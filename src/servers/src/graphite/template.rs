@@ -0,0 +1,298 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Template rules that map a Graphite metric path to a table, a field and tags.
+//!
+//! A rule is written as `[filter] template [tags]`, for example
+//! `servers.* .host.measurement.field*  region=us-west`:
+//!
+//! - `filter` is a dot separated pattern where `*` matches exactly one segment. A rule without
+//!   filter is the default rule, used when no filtered rule matches the path.
+//! - `template` names each path segment. `measurement` segments are joined into the table name,
+//!   `field` segments into the value column name, an empty name skips the segment and any other
+//!   name turns the segment into a tag. A trailing `*` (`measurement*` or `field*`) consumes all
+//!   the remaining segments.
+//! - `tags` is a comma separated list of `key=value` pairs added to every matched data point.
+
+use snafu::ensure;
+
+use crate::error::{self, Result};
+
+#[derive(Debug, Clone, PartialEq)]
+enum TemplatePart {
+    Skip,
+    Measurement { greedy: bool },
+    Field { greedy: bool },
+    Tag(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Template {
+    filter: Vec<String>,
+    parts: Vec<TemplatePart>,
+    tags: Vec<(String, String)>,
+}
+
+/// Result of applying templates to a metric path.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricPath {
+    pub table: String,
+    pub field: Option<String>,
+    pub tags: Vec<(String, String)>,
+}
+
+/// Ordered set of template rules.
+#[derive(Debug, Clone, Default)]
+pub struct Templates {
+    templates: Vec<Template>,
+}
+
+impl Templates {
+    pub fn try_new<S: AsRef<str>>(rules: &[S]) -> Result<Self> {
+        let templates = rules
+            .iter()
+            .map(|rule| Template::try_new(rule.as_ref()))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { templates })
+    }
+
+    /// Applies the first filtered rule matching `path`, falling back to the first rule without
+    /// filter. Without any applicable rule, the whole path becomes the table name.
+    pub fn apply(&self, path: &str) -> MetricPath {
+        let segments = path.split('.').collect::<Vec<_>>();
+        let template = self
+            .templates
+            .iter()
+            .find(|t| !t.filter.is_empty() && t.matches(&segments))
+            .or_else(|| self.templates.iter().find(|t| t.filter.is_empty()));
+
+        match template {
+            Some(template) => template.apply(path, &segments),
+            None => MetricPath {
+                table: path.to_string(),
+                field: None,
+                tags: vec![],
+            },
+        }
+    }
+}
+
+impl Template {
+    fn try_new(rule: &str) -> Result<Self> {
+        let tokens = rule.split_whitespace().collect::<Vec<_>>();
+        let (filter, template, tags) = match tokens.as_slice() {
+            [template] => (None, *template, None),
+            [template, tags] if tags.contains('=') => (None, *template, Some(*tags)),
+            [filter, template] => (Some(*filter), *template, None),
+            [filter, template, tags] => (Some(*filter), *template, Some(*tags)),
+            _ => {
+                return error::InvalidGraphiteTemplateSnafu {
+                    template: rule,
+                    reason: "expect `[filter] template [tags]`",
+                }
+                .fail()
+            }
+        };
+
+        let filter = filter
+            .map(|f| f.split('.').map(|s| s.to_string()).collect::<Vec<_>>())
+            .unwrap_or_default();
+        ensure!(
+            filter.iter().all(|s| !s.is_empty()),
+            error::InvalidGraphiteTemplateSnafu {
+                template: rule,
+                reason: "empty segment in filter",
+            }
+        );
+
+        let parts = template
+            .split('.')
+            .map(|name| match name {
+                "" => TemplatePart::Skip,
+                "measurement" => TemplatePart::Measurement { greedy: false },
+                "measurement*" => TemplatePart::Measurement { greedy: true },
+                "field" => TemplatePart::Field { greedy: false },
+                "field*" => TemplatePart::Field { greedy: true },
+                tag => TemplatePart::Tag(tag.to_string()),
+            })
+            .collect::<Vec<_>>();
+        let greedy_pos = parts.iter().position(|p| {
+            matches!(
+                p,
+                TemplatePart::Measurement { greedy: true } | TemplatePart::Field { greedy: true }
+            )
+        });
+        ensure!(
+            greedy_pos.map_or(true, |pos| pos == parts.len() - 1),
+            error::InvalidGraphiteTemplateSnafu {
+                template: rule,
+                reason: "`measurement*` or `field*` must be the last part",
+            }
+        );
+
+        let tags = match tags {
+            Some(tags) => tags
+                .split(',')
+                .map(|kv| match kv.split_once('=') {
+                    Some((k, v)) if !k.is_empty() && !v.is_empty() => {
+                        Ok((k.to_string(), v.to_string()))
+                    }
+                    _ => error::InvalidGraphiteTemplateSnafu {
+                        template: rule,
+                        reason: format!("invalid tag: {kv}"),
+                    }
+                    .fail(),
+                })
+                .collect::<Result<Vec<_>>>()?,
+            None => vec![],
+        };
+
+        Ok(Self {
+            filter,
+            parts,
+            tags,
+        })
+    }
+
+    fn matches(&self, segments: &[&str]) -> bool {
+        self.filter.len() <= segments.len()
+            && self
+                .filter
+                .iter()
+                .zip(segments)
+                .all(|(f, s)| f == "*" || f == s)
+    }
+
+    fn apply(&self, path: &str, segments: &[&str]) -> MetricPath {
+        let mut measurement = vec![];
+        let mut field = vec![];
+        let mut tags: Vec<(String, String)> = vec![];
+
+        for (i, (part, segment)) in self.parts.iter().zip(segments).enumerate() {
+            match part {
+                TemplatePart::Skip => {}
+                TemplatePart::Measurement { greedy: false } => measurement.push(*segment),
+                TemplatePart::Measurement { greedy: true } => measurement.extend(&segments[i..]),
+                TemplatePart::Field { greedy: false } => field.push(*segment),
+                TemplatePart::Field { greedy: true } => field.extend(&segments[i..]),
+                TemplatePart::Tag(name) => {
+                    // Segments named by the same tag are joined, like `measurement`.
+                    match tags.iter_mut().find(|(k, _)| k == name) {
+                        Some((_, v)) => {
+                            v.push('.');
+                            v.push_str(segment);
+                        }
+                        None => tags.push((name.clone(), segment.to_string())),
+                    }
+                }
+            }
+        }
+
+        for (k, v) in &self.tags {
+            if !tags.iter().any(|(name, _)| name == k) {
+                tags.push((k.clone(), v.clone()));
+            }
+        }
+
+        MetricPath {
+            table: if measurement.is_empty() {
+                path.to_string()
+            } else {
+                measurement.join(".")
+            },
+            field: (!field.is_empty()).then(|| field.join(".")),
+            tags,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_apply_without_templates() {
+        let templates = Templates::default();
+        let path = templates.apply("servers.web01.cpu.load");
+        assert_eq!(
+            MetricPath {
+                table: "servers.web01.cpu.load".to_string(),
+                field: None,
+                tags: vec![],
+            },
+            path
+        );
+    }
+
+    #[test]
+    fn test_apply_templates() {
+        let templates = Templates::try_new(&[
+            "servers.* .host.measurement.field* region=us-west,dc=a",
+            "collectd.*.*.* .host.measurement.measurement",
+            "measurement.measurement.tenant",
+        ])
+        .unwrap();
+
+        let path = templates.apply("servers.web01.cpu.load.shortterm");
+        assert_eq!(
+            MetricPath {
+                table: "cpu".to_string(),
+                field: Some("load.shortterm".to_string()),
+                tags: tags(&[("host", "web01"), ("region", "us-west"), ("dc", "a")]),
+            },
+            path
+        );
+
+        let path = templates.apply("collectd.db01.memory.used");
+        assert_eq!(
+            MetricPath {
+                table: "memory.used".to_string(),
+                field: None,
+                tags: tags(&[("host", "db01")]),
+            },
+            path
+        );
+
+        // Too short for the collectd filter, falls back to the default rule.
+        let path = templates.apply("collectd.db01.uptime");
+        assert_eq!(
+            MetricPath {
+                table: "collectd.db01".to_string(),
+                field: None,
+                tags: tags(&[("tenant", "uptime")]),
+            },
+            path
+        );
+    }
+
+    #[test]
+    fn test_invalid_templates() {
+        for rule in [
+            "",
+            "a b c d",
+            "servers..web measurement",
+            "servers.* measurement*.host",
+            "measurement host=",
+            "servers.* measurement region",
+        ] {
+            assert!(Templates::try_new(&[rule]).is_err(), "rule: {rule}");
+        }
+    }
+}
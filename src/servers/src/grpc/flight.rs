@@ -24,7 +24,7 @@ use arrow_flight::{
     HandshakeRequest, HandshakeResponse, PutResult, SchemaResult, Ticket,
 };
 use async_trait::async_trait;
-use common_grpc::flight::{FlightEncoder, FlightMessage};
+use common_grpc::flight::{FlightEncoder, FlightMessage, FLIGHT_METRICS_KEY};
 use common_query::Output;
 use futures::Stream;
 use prost::Message;
@@ -85,13 +85,14 @@ impl FlightService for FlightHandler {
     type DoGetStream = TonicStream<FlightData>;
 
     async fn do_get(&self, request: Request<Ticket>) -> TonicResult<Response<Self::DoGetStream>> {
+        let send_metrics = request.metadata().contains_key(FLIGHT_METRICS_KEY);
         let ticket = request.into_inner().ticket;
         let request =
             GreptimeRequest::decode(ticket.as_ref()).context(error::InvalidFlightTicketSnafu)?;
 
         let output = self.handler.handle_request(request).await?;
 
-        let stream = to_flight_data_stream(output);
+        let stream = to_flight_data_stream(output, send_metrics);
         Ok(Response::new(stream))
     }
 
@@ -129,14 +130,14 @@ impl FlightService for FlightHandler {
     }
 }
 
-fn to_flight_data_stream(output: Output) -> TonicStream<FlightData> {
+fn to_flight_data_stream(output: Output, send_metrics: bool) -> TonicStream<FlightData> {
     match output {
         Output::Stream(stream) => {
-            let stream = FlightRecordBatchStream::new(stream);
+            let stream = FlightRecordBatchStream::new(stream, send_metrics);
             Box::pin(stream) as _
         }
         Output::RecordBatches(x) => {
-            let stream = FlightRecordBatchStream::new(x.as_stream());
+            let stream = FlightRecordBatchStream::new(x.as_stream(), send_metrics);
             Box::pin(stream) as _
         }
         Output::AffectedRows(rows) => {
@@ -39,12 +39,13 @@ pub(super) struct FlightRecordBatchStream {
 }
 
 impl FlightRecordBatchStream {
-    pub(super) fn new(recordbatches: SendableRecordBatchStream) -> Self {
+    /// Creates a stream of Flight data from `recordbatches`. The plan metrics of
+    /// `recordbatches` are sent after the last record batch if `send_metrics` is true.
+    pub(super) fn new(recordbatches: SendableRecordBatchStream, send_metrics: bool) -> Self {
         let (tx, rx) = mpsc::channel::<TonicResult<FlightMessage>>(1);
-        let join_handle =
-            common_runtime::spawn_read(
-                async move { Self::flight_data_stream(recordbatches, tx).await },
-            );
+        let join_handle = common_runtime::spawn_read(async move {
+            Self::flight_data_stream(recordbatches, send_metrics, tx).await
+        });
         Self {
             rx,
             join_handle,
@@ -55,6 +56,7 @@ impl FlightRecordBatchStream {
 
     async fn flight_data_stream(
         mut recordbatches: SendableRecordBatchStream,
+        send_metrics: bool,
         mut tx: Sender<TonicResult<FlightMessage>>,
     ) {
         let schema = recordbatches.schema();
@@ -80,6 +82,20 @@ impl FlightRecordBatchStream {
                 }
             }
         }
+
+        if !send_metrics {
+            return;
+        }
+        if let Some(metrics) = recordbatches.metrics() {
+            match serde_json::to_string(&metrics) {
+                Ok(metrics) => {
+                    if let Err(e) = tx.send(Ok(FlightMessage::Metrics(metrics))).await {
+                        warn!("stop sending Flight data, err: {e}");
+                    }
+                }
+                Err(e) => warn!("failed to serialize plan metrics, err: {e}"),
+            }
+        }
     }
 }
 
@@ -146,7 +162,7 @@ mod test {
         let recordbatches = RecordBatches::try_new(schema.clone(), vec![recordbatch.clone()])
             .unwrap()
             .as_stream();
-        let mut stream = FlightRecordBatchStream::new(recordbatches);
+        let mut stream = FlightRecordBatchStream::new(recordbatches, true);
 
         let mut raw_data = Vec::with_capacity(2);
         raw_data.push(stream.next().await.unwrap().unwrap());
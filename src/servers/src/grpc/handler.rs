@@ -114,7 +114,7 @@ impl GreptimeRequestHandler {
             })
             .context(NotFoundAuthHeaderSnafu)?;
 
-        let _ = match auth_scheme {
+        let user_info = match auth_scheme {
             AuthScheme::Basic(Basic { username, password }) => user_provider
                 .auth(
                     Identity::UserId(&username, None),
@@ -135,6 +135,7 @@ impl GreptimeRequestHandler {
             );
             Status::unauthenticated(e.to_string())
         })?;
+        query_ctx.set_current_user(Some(user_info));
         Ok(())
     }
 }
@@ -14,10 +14,12 @@
 
 mod admin;
 pub mod authorize;
+pub mod format;
 pub mod handler;
 pub mod influxdb;
 pub mod mem_prof;
 pub mod opentsdb;
+pub mod otlp;
 mod pprof;
 pub mod prom_store;
 pub mod script;
@@ -35,7 +37,7 @@ use async_trait::async_trait;
 use axum::body::BoxBody;
 use axum::error_handling::HandleErrorLayer;
 use axum::extract::{DefaultBodyLimit, MatchedPath};
-use axum::http::Request;
+use axum::http::{Request, StatusCode as HttpStatusCode};
 use axum::middleware::{self, Next};
 use axum::response::{Html, IntoResponse, Json};
 use axum::{routing, BoxError, Extension, Router};
@@ -60,7 +62,9 @@ use tower_http::auth::AsyncRequireAuthorizationLayer;
 use tower_http::trace::TraceLayer;
 
 use self::authorize::HttpAuth;
-use self::influxdb::{influxdb_health, influxdb_ping, influxdb_write_v1, influxdb_write_v2};
+use self::influxdb::{
+    influxdb_health, influxdb_ping, influxdb_query, influxdb_write_v1, influxdb_write_v2,
+};
 use crate::auth::UserProviderRef;
 use crate::configurator::ConfiguratorRef;
 use crate::error::{AlreadyStartedSnafu, Result, StartHttpSnafu};
@@ -73,8 +77,8 @@ use crate::metrics_handler::MetricsHandler;
 use crate::query_handler::grpc::ServerGrpcQueryHandlerRef;
 use crate::query_handler::sql::ServerSqlQueryHandlerRef;
 use crate::query_handler::{
-    InfluxdbLineProtocolHandlerRef, OpentsdbProtocolHandlerRef, PromStoreProtocolHandlerRef,
-    ScriptHandlerRef,
+    InfluxdbLineProtocolHandlerRef, OpenTelemetryProtocolHandlerRef, OpentsdbProtocolHandlerRef,
+    PromStoreProtocolHandlerRef, ScriptHandlerRef,
 };
 use crate::server::Server;
 
@@ -105,11 +109,17 @@ pub(crate) async fn query_context_from_db(
 
 pub const HTTP_API_VERSION: &str = "v1";
 pub const HTTP_API_PREFIX: &str = "/v1/";
+/// Prefix of the Prometheus HTTP API served by [crate::prometheus::PrometheusServer].
+pub const PROMETHEUS_API_PREFIX: &str = "/api/v1/";
 /// Default http body limit (64M).
 const DEFAULT_BODY_LIMIT: ReadableSize = ReadableSize::mb(64);
 
 // TODO(fys): This is a temporary workaround, it will be improved later
-pub static PUBLIC_APIS: [&str; 2] = ["/v1/influxdb/ping", "/v1/influxdb/health"];
+pub static PUBLIC_APIS: [&str; 3] = [
+    "/v1/influxdb/ping",
+    "/v1/influxdb/health",
+    "/api/v1/status/buildinfo",
+];
 
 #[derive(Default)]
 pub struct HttpServer {
@@ -119,6 +129,7 @@ pub struct HttpServer {
     influxdb_handler: Option<InfluxdbLineProtocolHandlerRef>,
     opentsdb_handler: Option<OpentsdbProtocolHandlerRef>,
     prom_handler: Option<PromStoreProtocolHandlerRef>,
+    otlp_handler: Option<OpenTelemetryProtocolHandlerRef>,
     script_handler: Option<ScriptHandlerRef>,
     shutdown_tx: Mutex<Option<Sender<()>>>,
     user_provider: Option<UserProviderRef>,
@@ -348,6 +359,16 @@ impl JsonResponse {
         self.code
     }
 
+    /// Returns `429 Too Many Requests` if the request is rejected by rate limits,
+    /// otherwise `200 OK` as the error is carried in the body.
+    fn http_status(&self) -> HttpStatusCode {
+        if self.code == StatusCode::RateLimited as u32 {
+            HttpStatusCode::TOO_MANY_REQUESTS
+        } else {
+            HttpStatusCode::OK
+        }
+    }
+
     pub fn success(&self) -> bool {
         self.code == (StatusCode::Success as u32)
     }
@@ -399,6 +420,7 @@ impl HttpServerBuilder {
                 opentsdb_handler: None,
                 influxdb_handler: None,
                 prom_handler: None,
+                otlp_handler: None,
                 user_provider: None,
                 script_handler: None,
                 metrics_handler: None,
@@ -439,6 +461,11 @@ impl HttpServerBuilder {
         self
     }
 
+    pub fn with_otlp_handler(&mut self, handler: OpenTelemetryProtocolHandlerRef) -> &mut Self {
+        let _ = self.inner.otlp_handler.get_or_insert(handler);
+        self
+    }
+
     pub fn with_user_provider(&mut self, user_provider: UserProviderRef) -> &mut Self {
         let _ = self.inner.user_provider.get_or_insert(user_provider);
         self
@@ -521,6 +548,13 @@ impl HttpServer {
             );
         }
 
+        if let Some(otlp_handler) = self.otlp_handler.clone() {
+            router = router.nest(
+                &format!("/{HTTP_API_VERSION}/otlp"),
+                self.route_otlp(otlp_handler),
+            );
+        }
+
         if let Some(metrics_handler) = self.metrics_handler {
             router = router.nest("", self.route_metrics(metrics_handler));
         }
@@ -638,6 +672,7 @@ impl HttpServer {
             .route("/api/v2/write", routing::post(influxdb_write_v2))
             .route("/ping", routing::get(influxdb_ping))
             .route("/health", routing::get(influxdb_health))
+            .route("/query", routing::get(influxdb_query).post(influxdb_query))
             .with_state(influxdb_handler)
     }
 
@@ -647,6 +682,12 @@ impl HttpServer {
             .with_state(opentsdb_handler)
     }
 
+    fn route_otlp<S>(&self, otlp_handler: OpenTelemetryProtocolHandlerRef) -> Router<S> {
+        Router::new()
+            .route("/v1/metrics", routing::post(otlp::metrics))
+            .with_state(otlp_handler)
+    }
+
     fn route_admin<S>(&self, grpc_handler: ServerGrpcQueryHandlerRef) -> Router<S> {
         Router::new()
             .route("/flush", routing::post(flush))
@@ -864,6 +905,20 @@ mod test {
         assert_eq!(Duration::from_secs(30), default.timeout)
     }
 
+    #[test]
+    fn test_json_response_http_status() {
+        use common_error::status_code::StatusCode as ErrorCode;
+
+        let resp = JsonResponse::with_error("quota exceeded".to_string(), ErrorCode::RateLimited);
+        assert_eq!(StatusCode::TOO_MANY_REQUESTS, resp.http_status());
+        let resp = JsonResponse::with_error("internal".to_string(), ErrorCode::Internal);
+        assert_eq!(StatusCode::OK, resp.http_status());
+        assert_eq!(
+            StatusCode::OK,
+            JsonResponse::with_output(None).http_status()
+        );
+    }
+
     #[tokio::test]
     async fn test_http_server_request_timeout() {
         let (tx, _rx) = mpsc::channel(100);
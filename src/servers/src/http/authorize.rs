@@ -16,6 +16,7 @@ use std::marker::PhantomData;
 
 use axum::http::{self, Request, StatusCode};
 use axum::response::Response;
+use common_catalog::consts::DEFAULT_SCHEMA_NAME;
 use common_error::ext::ErrorExt;
 use common_telemetry::warn;
 use futures::future::BoxFuture;
@@ -33,7 +34,7 @@ use crate::error::{
     self, AuthSnafu, InvalidAuthorizationHeaderSnafu, InvisibleASCIISnafu, NotFoundInfluxAuthSnafu,
     Result, UnsupportedAuthSchemeSnafu,
 };
-use crate::http::HTTP_API_PREFIX;
+use crate::http::{HTTP_API_PREFIX, PROMETHEUS_API_PREFIX};
 
 pub struct HttpAuth<RespBody> {
     user_provider: Option<UserProviderRef>,
@@ -143,9 +144,17 @@ fn extract_catalog_and_schema<B: Send + Sync + 'static>(
 ) -> crate::auth::Result<(&str, &str)> {
     // try get database name
     let query = request.uri().query().unwrap_or_default();
-    let input_database = extract_db_from_query(query).context(IllegalParamSnafu {
-        msg: "db not provided or corrupted",
-    })?;
+    let input_database = match extract_db_from_query(query) {
+        Some(db) => db,
+        // The Prometheus API falls back to the default schema when `db` is absent.
+        None if request.uri().path().starts_with(PROMETHEUS_API_PREFIX) => DEFAULT_SCHEMA_NAME,
+        None => {
+            return IllegalParamSnafu {
+                msg: "db not provided or corrupted",
+            }
+            .fail()
+        }
+    };
 
     Ok(crate::parse_catalog_and_schema_from_client_database_name(
         input_database,
@@ -274,7 +283,7 @@ fn need_auth<B>(req: &Request<B>) -> bool {
         }
     }
 
-    path.starts_with(HTTP_API_PREFIX)
+    path.starts_with(HTTP_API_PREFIX) || path.starts_with(PROMETHEUS_API_PREFIX)
 }
 
 fn extract_db_from_query(query: &str) -> Option<&str> {
@@ -330,6 +339,20 @@ mod tests {
             .unwrap();
 
         assert!(need_auth(&req));
+
+        let req = Request::builder()
+            .uri("http://127.0.0.1/api/v1/query_range")
+            .body(())
+            .unwrap();
+
+        assert!(need_auth(&req));
+
+        let req = Request::builder()
+            .uri("http://127.0.0.1/api/v1/status/buildinfo")
+            .body(())
+            .unwrap();
+
+        assert!(!need_auth(&req));
     }
 
     #[test]
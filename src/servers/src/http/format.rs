@@ -0,0 +1,234 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Streaming response formats of the HTTP query APIs.
+//!
+//! Unlike [JsonResponse], which collects all rows in memory before serializing them, these
+//! formats encode the record batches one by one as they are pulled from the query output
+//! stream, and send them to the client with chunked transfer encoding.
+
+use axum::body::StreamBody;
+use axum::http::header;
+use axum::response::{IntoResponse, Json, Response};
+use axum::BoxError;
+use bytes::Bytes;
+use common_datasource::share_buffer::SharedBuffer;
+use common_error::status_code::StatusCode;
+use common_query::Output;
+use common_recordbatch::SendableRecordBatchStream;
+use datatypes::arrow::error::ArrowError;
+use datatypes::arrow::ipc::writer::StreamWriter;
+use datatypes::arrow::record_batch::RecordBatch as DfRecordBatch;
+use datatypes::arrow::{csv, json};
+use futures::StreamExt;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::http::JsonResponse;
+
+/// Format of the query result, selected by the `format` parameter of `/sql` and `/promql`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ResponseFormat {
+    /// The default [JsonResponse].
+    #[default]
+    Json,
+    /// Arrow IPC streaming format.
+    Arrow,
+    /// CSV with a header line.
+    Csv,
+    /// Newline-delimited JSON, one object per row.
+    NdJson,
+}
+
+impl ResponseFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ResponseFormat::Json => "application/json",
+            ResponseFormat::Arrow => "application/vnd.apache.arrow.stream",
+            ResponseFormat::Csv => "text/csv",
+            ResponseFormat::NdJson => "application/x-ndjson",
+        }
+    }
+}
+
+/// Streams the records of a query output in `format`, which must not be
+/// [ResponseFormat::Json].
+///
+/// Only one statement is allowed in a streaming response. Errors and outputs without records
+/// are still returned as [JsonResponse].
+pub async fn stream_output(format: ResponseFormat, mut outputs: Vec<Result<Output>>) -> Response {
+    if outputs.len() != 1 {
+        return Json(JsonResponse::with_error(
+            format!(
+                "Format {format:?} only supports a single statement, found {}",
+                outputs.len()
+            ),
+            StatusCode::InvalidArguments,
+        ))
+        .into_response();
+    }
+
+    let stream = match outputs.pop() {
+        Some(Ok(Output::Stream(stream))) => stream,
+        Some(Ok(Output::RecordBatches(recordbatches))) => recordbatches.as_stream(),
+        output => {
+            return Json(JsonResponse::from_output(output.into_iter().collect()).await)
+                .into_response()
+        }
+    };
+
+    match stream_body(format, stream) {
+        Ok(body) => ([(header::CONTENT_TYPE, format.content_type())], body).into_response(),
+        Err(e) => Json(JsonResponse::with_error(
+            format!("Failed to encode records: {e}"),
+            StatusCode::Internal,
+        ))
+        .into_response(),
+    }
+}
+
+fn stream_body(
+    format: ResponseFormat,
+    stream: SendableRecordBatchStream,
+) -> std::result::Result<
+    StreamBody<impl futures::Stream<Item = std::result::Result<Bytes, BoxError>>>,
+    ArrowError,
+> {
+    let buffer = SharedBuffer::default();
+    let encoder = RecordBatchEncoder::try_new(format, buffer.clone(), &stream)?;
+
+    let chunks = futures::stream::unfold(Some((stream, encoder, buffer)), |state| async move {
+        let (mut stream, mut encoder, buffer) = state?;
+        let chunk = match stream.next().await {
+            Some(Ok(batch)) => encoder
+                .write(batch.df_record_batch())
+                .map(|_| take_bytes(&buffer))
+                .map_err(BoxError::from),
+            Some(Err(e)) => Err(BoxError::from(e)),
+            None => {
+                // Writes the remaining bytes, like the end-of-stream marker of Arrow IPC.
+                let chunk = encoder
+                    .finish()
+                    .map(|_| take_bytes(&buffer))
+                    .map_err(BoxError::from);
+                return Some((chunk, None));
+            }
+        };
+        // Stops the stream after the first error, the response is truncated.
+        let next_state = chunk.is_ok().then_some((stream, encoder, buffer));
+        Some((chunk, next_state))
+    });
+
+    Ok(StreamBody::new(chunks))
+}
+
+fn take_bytes(buffer: &SharedBuffer) -> Bytes {
+    buffer.buffer.lock().unwrap().split().freeze()
+}
+
+enum RecordBatchEncoder {
+    Arrow(StreamWriter<SharedBuffer>),
+    Csv(csv::Writer<SharedBuffer>),
+    NdJson(json::LineDelimitedWriter<SharedBuffer>),
+}
+
+impl RecordBatchEncoder {
+    fn try_new(
+        format: ResponseFormat,
+        buffer: SharedBuffer,
+        stream: &SendableRecordBatchStream,
+    ) -> std::result::Result<Self, ArrowError> {
+        Ok(match format {
+            ResponseFormat::Arrow => {
+                let schema = stream.schema();
+                RecordBatchEncoder::Arrow(StreamWriter::try_new(buffer, schema.arrow_schema())?)
+            }
+            ResponseFormat::Csv => RecordBatchEncoder::Csv(csv::Writer::new(buffer)),
+            ResponseFormat::NdJson => {
+                RecordBatchEncoder::NdJson(json::LineDelimitedWriter::new(buffer))
+            }
+            ResponseFormat::Json => unreachable!("JSON format is not streamed"),
+        })
+    }
+
+    fn write(&mut self, batch: &DfRecordBatch) -> std::result::Result<(), ArrowError> {
+        match self {
+            RecordBatchEncoder::Arrow(writer) => writer.write(batch),
+            RecordBatchEncoder::Csv(writer) => writer.write(batch),
+            RecordBatchEncoder::NdJson(writer) => writer.write(batch),
+        }
+    }
+
+    fn finish(&mut self) -> std::result::Result<(), ArrowError> {
+        match self {
+            RecordBatchEncoder::Arrow(writer) => writer.finish(),
+            RecordBatchEncoder::Csv(_) => Ok(()),
+            RecordBatchEncoder::NdJson(writer) => writer.finish(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use common_recordbatch::RecordBatches;
+    use datatypes::arrow::ipc::reader::StreamReader;
+    use datatypes::prelude::ConcreteDataType;
+    use datatypes::schema::{ColumnSchema, Schema};
+
+    use super::*;
+
+    async fn body_bytes(response: Response) -> Bytes {
+        hyper::body::to_bytes(response.into_body()).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_stream_output_fallback_to_json() {
+        let outputs = vec![Ok(Output::AffectedRows(1)), Ok(Output::AffectedRows(2))];
+        let response = stream_output(ResponseFormat::Csv, outputs).await;
+        let json: JsonResponse = serde_json::from_slice(&body_bytes(response).await).unwrap();
+        assert!(!json.success());
+        assert_eq!(
+            Some(&"Format Csv only supports a single statement, found 2".to_string()),
+            json.error()
+        );
+
+        let response =
+            stream_output(ResponseFormat::Arrow, vec![Ok(Output::AffectedRows(1))]).await;
+        let json: JsonResponse = serde_json::from_slice(&body_bytes(response).await).unwrap();
+        assert!(json.success());
+    }
+
+    #[tokio::test]
+    async fn test_stream_empty_output() {
+        let schema = Arc::new(Schema::new(vec![ColumnSchema::new(
+            "n",
+            ConcreteDataType::int64_datatype(),
+            true,
+        )]));
+        let output = Output::RecordBatches(RecordBatches::try_new(schema.clone(), vec![]).unwrap());
+        let response = stream_output(ResponseFormat::Arrow, vec![Ok(output)]).await;
+        let body = body_bytes(response).await;
+        let reader = StreamReader::try_new(body.as_ref(), None).unwrap();
+        assert_eq!(schema.arrow_schema().fields(), reader.schema().fields());
+        assert_eq!(0, reader.count());
+
+        let output = Output::RecordBatches(RecordBatches::try_new(schema, vec![]).unwrap());
+        let response = stream_output(ResponseFormat::Csv, vec![Ok(output)]).await;
+        assert!(body_bytes(response).await.is_empty());
+    }
+}
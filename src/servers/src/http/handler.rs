@@ -27,6 +27,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use session::context::UserInfo;
 
+use crate::http::format::{self, ResponseFormat};
 use crate::http::{ApiState, GreptimeOptionsConfigState, JsonResponse};
 use crate::metrics::JEMALLOC_COLLECTOR;
 use crate::metrics_handler::MetricsHandler;
@@ -35,6 +36,7 @@ use crate::metrics_handler::MetricsHandler;
 pub struct SqlQuery {
     pub db: Option<String>,
     pub sql: Option<String>,
+    pub format: Option<ResponseFormat>,
 }
 
 /// Handler to execute sql
@@ -42,15 +44,18 @@ pub struct SqlQuery {
 pub async fn sql(
     State(state): State<ApiState>,
     Query(query_params): Query<SqlQuery>,
-    // TODO(fys): pass _user_info into query context
-    _user_info: Extension<UserInfo>,
+    Extension(user_info): Extension<UserInfo>,
     Form(form_params): Form<SqlQuery>,
-) -> Json<JsonResponse> {
+) -> Response {
     let sql_handler = &state.sql_handler;
 
     let start = Instant::now();
     let sql = query_params.sql.or(form_params.sql);
     let db = query_params.db.or(form_params.db);
+    let format = query_params
+        .format
+        .or(form_params.format)
+        .unwrap_or_default();
     let _timer = timer!(
         crate::metrics::METRIC_HTTP_SQL_ELAPSED,
         &[(
@@ -62,7 +67,12 @@ pub async fn sql(
     let resp = if let Some(sql) = &sql {
         match crate::http::query_context_from_db(sql_handler.clone(), db).await {
             Ok(query_ctx) => {
-                JsonResponse::from_output(sql_handler.do_query(sql, query_ctx).await).await
+                query_ctx.set_current_user(Some(user_info));
+                let outputs = sql_handler.do_query(sql, query_ctx).await;
+                if format != ResponseFormat::Json {
+                    return format::stream_output(format, outputs).await;
+                }
+                JsonResponse::from_output(outputs).await
             }
             Err(resp) => resp,
         }
@@ -73,7 +83,12 @@ pub async fn sql(
         )
     };
 
-    Json(resp.with_execution_time(start.elapsed().as_millis()))
+    let status = resp.http_status();
+    (
+        status,
+        Json(resp.with_execution_time(start.elapsed().as_millis())),
+    )
+        .into_response()
 }
 
 #[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
@@ -83,6 +98,7 @@ pub struct PromqlQuery {
     pub end: String,
     pub step: String,
     pub db: Option<String>,
+    pub format: Option<ResponseFormat>,
 }
 
 impl From<PromqlQuery> for PromQuery {
@@ -101,12 +117,12 @@ impl From<PromqlQuery> for PromQuery {
 pub async fn promql(
     State(state): State<ApiState>,
     Query(params): Query<PromqlQuery>,
-    // TODO(fys): pass _user_info into query context
-    _user_info: Extension<UserInfo>,
-) -> Json<JsonResponse> {
+    Extension(user_info): Extension<UserInfo>,
+) -> Response {
     let sql_handler = &state.sql_handler;
     let exec_start = Instant::now();
     let db = params.db.clone();
+    let format = params.format.unwrap_or_default();
     let _timer = timer!(
         crate::metrics::METRIC_HTTP_PROMQL_ELAPSED,
         &[(
@@ -118,13 +134,22 @@ pub async fn promql(
     let prom_query = params.into();
     let resp = match super::query_context_from_db(sql_handler.clone(), db).await {
         Ok(query_ctx) => {
-            JsonResponse::from_output(sql_handler.do_promql_query(&prom_query, query_ctx).await)
-                .await
+            query_ctx.set_current_user(Some(user_info));
+            let outputs = sql_handler.do_promql_query(&prom_query, query_ctx).await;
+            if format != ResponseFormat::Json {
+                return format::stream_output(format, outputs).await;
+            }
+            JsonResponse::from_output(outputs).await
         }
         Err(resp) => resp,
     };
 
-    Json(resp.with_execution_time(exec_start.elapsed().as_millis()))
+    let status = resp.http_status();
+    (
+        status,
+        Json(resp.with_execution_time(exec_start.elapsed().as_millis())),
+    )
+        .into_response()
 }
 
 pub(crate) fn sql_docs(op: TransformOperation) -> TransformOperation {
@@ -12,18 +12,27 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 
 use axum::extract::{Query, State};
 use axum::http::StatusCode;
-use axum::response::IntoResponse;
+use axum::response::{IntoResponse, Json};
+use axum::{Extension, Form};
+use chrono::{SecondsFormat, TimeZone, Utc};
 use common_catalog::consts::DEFAULT_SCHEMA_NAME;
 use common_grpc::writer::Precision;
+use common_query::Output;
+use common_recordbatch::{util, RecordBatch};
 use common_telemetry::timer;
-use session::context::QueryContext;
+use common_time::timestamp::{TimeUnit, Timestamp};
+use datatypes::value::Value;
+use query::influxql::{parse_influxql, InfluxqlStatement, SeriesLayout};
+use serde::{Deserialize, Serialize};
+use session::context::{QueryContext, QueryContextRef, UserInfo};
+use snafu::{OptionExt, ResultExt};
 
-use crate::error::{Result, TimePrecisionSnafu};
+use crate::error::{CollectRecordbatchSnafu, InvalidQuerySnafu, Result, TimePrecisionSnafu};
 use crate::influxdb::InfluxdbRequest;
 use crate::parse_catalog_and_schema_from_client_database_name;
 use crate::query_handler::InfluxdbLineProtocolHandlerRef;
@@ -44,6 +53,7 @@ pub async fn influxdb_health() -> Result<impl IntoResponse> {
 pub async fn influxdb_write_v1(
     State(handler): State<InfluxdbLineProtocolHandlerRef>,
     Query(mut params): Query<HashMap<String, String>>,
+    Extension(user_info): Extension<UserInfo>,
     lines: String,
 ) -> Result<impl IntoResponse> {
     let db = params
@@ -55,13 +65,14 @@ pub async fn influxdb_write_v1(
         .map(|val| parse_time_precision(val))
         .transpose()?;
 
-    influxdb_write(&db, precision, lines, handler).await
+    influxdb_write(&db, precision, lines, handler, user_info).await
 }
 
 #[axum_macros::debug_handler]
 pub async fn influxdb_write_v2(
     State(handler): State<InfluxdbLineProtocolHandlerRef>,
     Query(mut params): Query<HashMap<String, String>>,
+    Extension(user_info): Extension<UserInfo>,
     lines: String,
 ) -> Result<impl IntoResponse> {
     let db = params
@@ -73,7 +84,7 @@ pub async fn influxdb_write_v2(
         .map(|val| parse_time_precision(val))
         .transpose()?;
 
-    influxdb_write(&db, precision, lines, handler).await
+    influxdb_write(&db, precision, lines, handler, user_info).await
 }
 
 pub async fn influxdb_write(
@@ -81,6 +92,7 @@ pub async fn influxdb_write(
     precision: Option<Precision>,
     lines: String,
     handler: InfluxdbLineProtocolHandlerRef,
+    user_info: UserInfo,
 ) -> Result<impl IntoResponse> {
     let _timer = timer!(
         crate::metrics::METRIC_HTTP_INFLUXDB_WRITE_ELAPSED,
@@ -89,6 +101,7 @@ pub async fn influxdb_write(
 
     let (catalog, schema) = parse_catalog_and_schema_from_client_database_name(db);
     let ctx = Arc::new(QueryContext::with(catalog, schema));
+    ctx.set_current_user(Some(user_info));
 
     let request = InfluxdbRequest { precision, lines };
 
@@ -97,6 +110,223 @@ pub async fn influxdb_write(
     Ok((StatusCode::NO_CONTENT, ()))
 }
 
+/// Response body of the InfluxDB v1 `/query` API.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct InfluxdbQueryResponse {
+    pub results: Vec<InfluxdbStatementResult>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct InfluxdbStatementResult {
+    pub statement_id: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub series: Vec<InfluxdbSeries>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct InfluxdbSeries {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tags: BTreeMap<String, String>,
+    pub columns: Vec<String>,
+    pub values: Vec<Vec<serde_json::Value>>,
+}
+
+// https://docs.influxdata.com/influxdb/v1.8/tools/api/#query-http-endpoint
+#[axum_macros::debug_handler]
+pub async fn influxdb_query(
+    State(handler): State<InfluxdbLineProtocolHandlerRef>,
+    Query(mut params): Query<HashMap<String, String>>,
+    Extension(user_info): Extension<UserInfo>,
+    form: Option<Form<HashMap<String, String>>>,
+) -> Result<impl IntoResponse> {
+    // Clients may send the parameters either in the url or as an urlencoded POST body.
+    if let Some(Form(form)) = form {
+        for (key, value) in form {
+            let _ = params.entry(key).or_insert(value);
+        }
+    }
+
+    let query = params.remove("q").context(InvalidQuerySnafu {
+        reason: "missing query parameter `q`",
+    })?;
+    let db = params
+        .remove("db")
+        .unwrap_or_else(|| DEFAULT_SCHEMA_NAME.to_string());
+    let epoch = params
+        .get("epoch")
+        .map(|val| parse_time_precision(val))
+        .transpose()?;
+
+    let _timer = timer!(
+        crate::metrics::METRIC_HTTP_INFLUXDB_QUERY_ELAPSED,
+        &[(crate::metrics::METRIC_DB_LABEL, db.clone())]
+    );
+
+    let statements = parse_influxql(&query).map_err(|e| {
+        InvalidQuerySnafu {
+            reason: e.to_string(),
+        }
+        .build()
+    })?;
+
+    let (catalog, schema) = parse_catalog_and_schema_from_client_database_name(&db);
+    let ctx = Arc::new(QueryContext::with(catalog, schema));
+    ctx.set_current_user(Some(user_info));
+
+    let mut results = Vec::with_capacity(statements.len());
+    for (statement_id, stmt) in statements.into_iter().enumerate() {
+        let layout = stmt.series_layout();
+        let result = match execute_statement(&handler, stmt, ctx.clone()).await {
+            Ok(recordbatches) => to_influxdb_series(recordbatches, &layout, epoch),
+            Err(e) => Err(e.to_string()),
+        };
+
+        match result {
+            Ok(series) => results.push(InfluxdbStatementResult {
+                statement_id,
+                series,
+                error: None,
+            }),
+            Err(error) => {
+                // Like InfluxDB, statements after a failed one are not executed.
+                results.push(InfluxdbStatementResult {
+                    statement_id,
+                    series: vec![],
+                    error: Some(error),
+                });
+                break;
+            }
+        }
+    }
+
+    Ok(Json(InfluxdbQueryResponse { results }))
+}
+
+async fn execute_statement(
+    handler: &InfluxdbLineProtocolHandlerRef,
+    stmt: InfluxqlStatement,
+    ctx: QueryContextRef,
+) -> Result<Vec<RecordBatch>> {
+    match handler.query(stmt, ctx).await? {
+        Output::AffectedRows(_) => Ok(vec![]),
+        Output::RecordBatches(recordbatches) => Ok(recordbatches.take()),
+        Output::Stream(stream) => util::collect(stream).await.context(CollectRecordbatchSnafu),
+    }
+}
+
+/// Splits the result rows of a statement into InfluxDB series. Consecutive rows with the same
+/// series name and tag values belong to the same series.
+fn to_influxdb_series(
+    recordbatches: Vec<RecordBatch>,
+    layout: &SeriesLayout,
+    epoch: Option<Precision>,
+) -> std::result::Result<Vec<InfluxdbSeries>, String> {
+    let Some(first) = recordbatches.first() else {
+        return Ok(vec![]);
+    };
+
+    let column_names = first
+        .schema
+        .column_schemas()
+        .iter()
+        .map(|cs| cs.name.clone())
+        .collect::<Vec<_>>();
+    let tags_start = usize::from(layout.name.is_none());
+    let values_start = tags_start + layout.num_tags;
+    if column_names.len() < values_start {
+        return Err(format!(
+            "Expect at least {} columns in the result of InfluxQL statement, found {}",
+            values_start,
+            column_names.len()
+        ));
+    }
+    let tag_names = &column_names[tags_start..values_start];
+    let value_names = &column_names[values_start..];
+
+    let mut series: Vec<InfluxdbSeries> = vec![];
+    for recordbatch in &recordbatches {
+        for row in recordbatch.rows() {
+            let name = match &layout.name {
+                Some(name) => name.clone(),
+                None => value_to_string(&row[0]),
+            };
+            let tags = tag_names
+                .iter()
+                .zip(&row[tags_start..values_start])
+                .map(|(tag, value)| (tag.clone(), value_to_string(value)))
+                .collect::<BTreeMap<_, _>>();
+            let values = row
+                .into_iter()
+                .skip(values_start)
+                .map(|value| value_to_json(value, epoch))
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+
+            match series.last_mut() {
+                Some(last) if last.name == name && last.tags == tags => last.values.push(values),
+                _ => series.push(InfluxdbSeries {
+                    name,
+                    tags,
+                    columns: value_names.to_vec(),
+                    values: vec![values],
+                }),
+            }
+        }
+    }
+
+    Ok(series)
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+fn value_to_json(
+    value: Value,
+    epoch: Option<Precision>,
+) -> std::result::Result<serde_json::Value, String> {
+    match value {
+        Value::Timestamp(ts) => timestamp_to_json(ts, epoch),
+        other => serde_json::Value::try_from(other).map_err(|e| e.to_string()),
+    }
+}
+
+/// Formats timestamps as RFC3339 strings, or as integers in the `epoch` precision if requested.
+fn timestamp_to_json(
+    ts: Timestamp,
+    epoch: Option<Precision>,
+) -> std::result::Result<serde_json::Value, String> {
+    let Some(precision) = epoch else {
+        let datetime = ts
+            .to_chrono_datetime()
+            .ok_or_else(|| format!("Timestamp {ts:?} is out of range"))?;
+        return Ok(serde_json::Value::from(
+            Utc.from_utc_datetime(&datetime)
+                .to_rfc3339_opts(SecondsFormat::AutoSi, true),
+        ));
+    };
+
+    let (unit, divisor) = match precision {
+        Precision::Nanosecond => (TimeUnit::Nanosecond, 1),
+        Precision::Microsecond => (TimeUnit::Microsecond, 1),
+        Precision::Millisecond => (TimeUnit::Millisecond, 1),
+        Precision::Second => (TimeUnit::Second, 1),
+        Precision::Minute => (TimeUnit::Second, 60),
+        Precision::Hour => (TimeUnit::Second, 3600),
+    };
+    let value = ts
+        .convert_to(unit)
+        .ok_or_else(|| format!("Timestamp {ts:?} is out of range of {unit:?}"))?
+        .value()
+        .div_euclid(divisor);
+    Ok(serde_json::Value::from(value))
+}
+
 fn parse_time_precision(value: &str) -> Result<Precision> {
     // Precision conversion needs to be compatible with influxdb v1 v2 api.
     // For details, see the Influxdb documents.
@@ -118,9 +348,17 @@ fn parse_time_precision(value: &str) -> Result<Precision> {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use common_grpc::writer::Precision;
+    use common_recordbatch::RecordBatch;
+    use datatypes::prelude::ConcreteDataType;
+    use datatypes::schema::{ColumnSchema, Schema};
+    use datatypes::vectors::{Float64Vector, StringVector, TimestampMillisecondVector};
+    use query::influxql::SeriesLayout;
+    use serde_json::json;
 
-    use crate::http::influxdb::parse_time_precision;
+    use super::*;
 
     #[test]
     fn test_parse_time_precision() {
@@ -134,4 +372,109 @@ mod tests {
         assert_eq!(Precision::Hour, parse_time_precision("h").unwrap());
         assert!(parse_time_precision("unknown").is_err());
     }
+
+    fn cpu_recordbatch() -> RecordBatch {
+        let schema = Arc::new(Schema::new(vec![
+            ColumnSchema::new("host", ConcreteDataType::string_datatype(), true),
+            ColumnSchema::new(
+                "time",
+                ConcreteDataType::timestamp_millisecond_datatype(),
+                false,
+            ),
+            ColumnSchema::new("usage", ConcreteDataType::float64_datatype(), true),
+        ]));
+        RecordBatch::new(
+            schema,
+            vec![
+                Arc::new(StringVector::from(vec!["host1", "host1", "host2"])) as _,
+                Arc::new(TimestampMillisecondVector::from_vec(vec![0, 1500, 60_000])) as _,
+                Arc::new(Float64Vector::from_vec(vec![1.0, 2.5, 3.0])) as _,
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_to_influxdb_series() {
+        let layout = SeriesLayout {
+            name: Some("cpu".to_string()),
+            num_tags: 1,
+        };
+        let series = to_influxdb_series(vec![cpu_recordbatch()], &layout, None).unwrap();
+        assert_eq!(2, series.len());
+        assert_eq!(
+            json!([
+                {
+                    "name": "cpu",
+                    "tags": {"host": "host1"},
+                    "columns": ["time", "usage"],
+                    "values": [["1970-01-01T00:00:00Z", 1.0], ["1970-01-01T00:00:01.500Z", 2.5]]
+                },
+                {
+                    "name": "cpu",
+                    "tags": {"host": "host2"},
+                    "columns": ["time", "usage"],
+                    "values": [["1970-01-01T00:01:00Z", 3.0]]
+                }
+            ]),
+            serde_json::to_value(&series).unwrap()
+        );
+
+        // Without tags all rows belong to a single series, the tag column is a value column.
+        let layout = SeriesLayout {
+            name: Some("cpu".to_string()),
+            num_tags: 0,
+        };
+        let series =
+            to_influxdb_series(vec![cpu_recordbatch()], &layout, Some(Precision::Second)).unwrap();
+        assert_eq!(
+            json!([{
+                "name": "cpu",
+                "columns": ["host", "time", "usage"],
+                "values": [["host1", 0, 1.0], ["host1", 1, 2.5], ["host2", 60, 3.0]]
+            }]),
+            serde_json::to_value(&series).unwrap()
+        );
+
+        // The first column holds the series name.
+        let layout = SeriesLayout {
+            name: None,
+            num_tags: 0,
+        };
+        let series = to_influxdb_series(vec![cpu_recordbatch()], &layout, None).unwrap();
+        assert_eq!(
+            vec!["host1", "host2"],
+            series.iter().map(|s| s.name.as_str()).collect::<Vec<_>>()
+        );
+        assert_eq!(vec!["time", "usage"], series[0].columns);
+
+        assert!(to_influxdb_series(vec![], &layout, None)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_timestamp_to_json() {
+        let ts = Timestamp::new_millisecond(7_200_123);
+        assert_eq!(
+            json!("1970-01-01T02:00:00.123Z"),
+            timestamp_to_json(ts, None).unwrap()
+        );
+        assert_eq!(
+            json!(7_200_123_000_000i64),
+            timestamp_to_json(ts, Some(Precision::Nanosecond)).unwrap()
+        );
+        assert_eq!(
+            json!(7_200_123),
+            timestamp_to_json(ts, Some(Precision::Millisecond)).unwrap()
+        );
+        assert_eq!(
+            json!(120),
+            timestamp_to_json(ts, Some(Precision::Minute)).unwrap()
+        );
+        assert_eq!(
+            json!(2),
+            timestamp_to_json(ts, Some(Precision::Hour)).unwrap()
+        );
+    }
 }
@@ -18,13 +18,14 @@ use api::prom_store::remote::{ReadRequest, WriteRequest};
 use axum::extract::{Query, RawBody, State};
 use axum::http::{header, StatusCode};
 use axum::response::IntoResponse;
+use axum::Extension;
 use common_catalog::consts::DEFAULT_SCHEMA_NAME;
 use common_telemetry::timer;
 use hyper::Body;
 use prost::Message;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use session::context::QueryContext;
+use session::context::{QueryContext, UserInfo};
 use snafu::prelude::*;
 
 use crate::error::{self, Result};
@@ -49,6 +50,7 @@ impl Default for DatabaseQuery {
 pub async fn remote_write(
     State(handler): State<PromStoreProtocolHandlerRef>,
     Query(params): Query<DatabaseQuery>,
+    Extension(user_info): Extension<UserInfo>,
     RawBody(body): RawBody,
 ) -> Result<(StatusCode, ())> {
     let request = decode_remote_write_request(body).await?;
@@ -66,6 +68,7 @@ pub async fn remote_write(
     } else {
         QueryContext::arc()
     };
+    ctx.set_current_user(Some(user_info));
 
     // TODO(shuiyisong): add more error log
     handler.write(request, ctx).await?;
@@ -89,6 +92,7 @@ impl IntoResponse for PromStoreResponse {
 pub async fn remote_read(
     State(handler): State<PromStoreProtocolHandlerRef>,
     Query(params): Query<DatabaseQuery>,
+    Extension(user_info): Extension<UserInfo>,
     RawBody(body): RawBody,
 ) -> Result<PromStoreResponse> {
     let request = decode_remote_read_request(body).await?;
@@ -106,6 +110,7 @@ pub async fn remote_read(
     } else {
         QueryContext::arc()
     };
+    ctx.set_current_user(Some(user_info));
 
     // TODO(shuiyisong): add more error log
     handler.read(request, ctx).await
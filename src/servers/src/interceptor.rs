@@ -18,6 +18,7 @@ use std::sync::Arc;
 use api::v1::greptime_request::Request;
 use common_error::ext::ErrorExt;
 use common_query::Output;
+use query::influxql::InfluxqlStatement;
 use query::parser::PromQuery;
 use query::plan::LogicalPlan;
 use session::context::QueryContextRef;
@@ -68,6 +69,11 @@ pub trait SqlQueryInterceptor {
     ) -> Result<Output, Self::Error> {
         Ok(output)
     }
+
+    /// Called when the execution failed after `pre_execute` succeeded, in
+    /// place of `post_execute`. The implementation can release the resources
+    /// acquired in `pre_execute`.
+    fn on_execute_error(&self, _error: &Self::Error, _query_ctx: QueryContextRef) {}
 }
 
 pub type SqlQueryInterceptorRef<E> =
@@ -127,6 +133,12 @@ where
             Ok(output)
         }
     }
+
+    fn on_execute_error(&self, error: &Self::Error, query_ctx: QueryContextRef) {
+        if let Some(this) = self {
+            this.on_execute_error(error, query_ctx)
+        }
+    }
 }
 
 /// GrpcQueryInterceptor can track life cycle of a grpc request and customize or
@@ -152,6 +164,11 @@ pub trait GrpcQueryInterceptor {
     ) -> Result<Output, Self::Error> {
         Ok(output)
     }
+
+    /// Called when the execution failed after `pre_execute` succeeded, in
+    /// place of `post_execute`. The implementation can release the resources
+    /// acquired in `pre_execute`.
+    fn on_execute_error(&self, _error: &Self::Error, _query_ctx: QueryContextRef) {}
 }
 
 pub type GrpcQueryInterceptorRef<E> =
@@ -186,6 +203,12 @@ where
             Ok(output)
         }
     }
+
+    fn on_execute_error(&self, error: &Self::Error, query_ctx: QueryContextRef) {
+        if let Some(this) = self {
+            this.on_execute_error(error, query_ctx)
+        }
+    }
 }
 
 /// PromQueryInterceptor can track life cycle of a prometheus request and customize or
@@ -211,6 +234,11 @@ pub trait PromQueryInterceptor {
     ) -> Result<Output, Self::Error> {
         Ok(output)
     }
+
+    /// Called when the execution failed after `pre_execute` succeeded, in
+    /// place of `post_execute`. The implementation can release the resources
+    /// acquired in `pre_execute`.
+    fn on_execute_error(&self, _error: &Self::Error, _query_ctx: QueryContextRef) {}
 }
 
 pub type PromQueryInterceptorRef<E> =
@@ -245,4 +273,80 @@ where
             Ok(output)
         }
     }
+
+    fn on_execute_error(&self, error: &Self::Error, query_ctx: QueryContextRef) {
+        if let Some(this) = self {
+            this.on_execute_error(error, query_ctx)
+        }
+    }
+}
+
+/// InfluxqlQueryInterceptor can track life cycle of an InfluxQL query and customize or
+/// abort its execution at given point.
+pub trait InfluxqlQueryInterceptor {
+    type Error: ErrorExt;
+
+    /// Called before the statement is actually executed.
+    fn pre_execute(
+        &self,
+        _statement: &InfluxqlStatement,
+        _query_ctx: QueryContextRef,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Called after execution finished. The implementation can modify the
+    /// output if needed.
+    fn post_execute(
+        &self,
+        output: Output,
+        _query_ctx: QueryContextRef,
+    ) -> Result<Output, Self::Error> {
+        Ok(output)
+    }
+
+    /// Called when the execution failed after `pre_execute` succeeded, in
+    /// place of `post_execute`. The implementation can release the resources
+    /// acquired in `pre_execute`.
+    fn on_execute_error(&self, _error: &Self::Error, _query_ctx: QueryContextRef) {}
+}
+
+pub type InfluxqlQueryInterceptorRef<E> =
+    Arc<dyn InfluxqlQueryInterceptor<Error = E> + Send + Sync + 'static>;
+
+impl<E> InfluxqlQueryInterceptor for Option<InfluxqlQueryInterceptorRef<E>>
+where
+    E: ErrorExt,
+{
+    type Error = E;
+
+    fn pre_execute(
+        &self,
+        statement: &InfluxqlStatement,
+        query_ctx: QueryContextRef,
+    ) -> Result<(), Self::Error> {
+        if let Some(this) = self {
+            this.pre_execute(statement, query_ctx)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn post_execute(
+        &self,
+        output: Output,
+        query_ctx: QueryContextRef,
+    ) -> Result<Output, Self::Error> {
+        if let Some(this) = self {
+            this.post_execute(output, query_ctx)
+        } else {
+            Ok(output)
+        }
+    }
+
+    fn on_execute_error(&self, error: &Self::Error, query_ctx: QueryContextRef) {
+        if let Some(this) = self {
+            this.on_execute_error(error, query_ctx)
+        }
+    }
 }
@@ -23,6 +23,7 @@ use serde::{Deserialize, Serialize};
 pub mod auth;
 pub mod configurator;
 pub mod error;
+pub mod graphite;
 pub mod grpc;
 pub mod heartbeat_options;
 pub mod http;
@@ -33,6 +34,7 @@ mod metrics;
 pub mod metrics_handler;
 pub mod mysql;
 pub mod opentsdb;
+pub mod otlp;
 pub mod postgres;
 pub mod prom_store;
 pub mod prometheus;
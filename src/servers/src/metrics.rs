@@ -40,11 +40,17 @@ pub(crate) const METRIC_HTTP_SQL_ELAPSED: &str = "servers.http_sql_elapsed";
 pub(crate) const METRIC_HTTP_PROMQL_ELAPSED: &str = "servers.http_promql_elapsed";
 pub(crate) const METRIC_AUTH_FAILURE: &str = "servers.auth_failure_count";
 pub(crate) const METRIC_HTTP_INFLUXDB_WRITE_ELAPSED: &str = "servers.http_influxdb_write_elapsed";
+pub(crate) const METRIC_HTTP_INFLUXDB_QUERY_ELAPSED: &str = "servers.http_influxdb_query_elapsed";
 pub(crate) const METRIC_HTTP_PROM_STORE_WRITE_ELAPSED: &str =
     "servers.http_prometheus_write_elapsed";
 pub(crate) const METRIC_HTTP_PROM_STORE_READ_ELAPSED: &str = "servers.http_prometheus_read_elapsed";
+pub(crate) const METRIC_HTTP_OTLP_METRICS_ELAPSED: &str = "servers.http_otlp_metrics_elapsed";
+pub(crate) const METRIC_OTLP_DROPPED_DATA_POINTS: &str = "servers.otlp_dropped_data_points";
 pub(crate) const METRIC_TCP_OPENTSDB_LINE_WRITE_ELAPSED: &str =
     "servers.opentsdb_line_write_elapsed";
+pub(crate) const METRIC_TCP_GRAPHITE_LINE_WRITE_ELAPSED: &str =
+    "servers.graphite_line_write_elapsed";
+pub(crate) const METRIC_GRAPHITE_DROPPED_DATA_POINTS: &str = "servers.graphite_dropped_data_points";
 
 pub(crate) const METRIC_MYSQL_CONNECTIONS: &str = "servers.mysql_connection_count";
 pub(crate) const METRIC_MYSQL_QUERY_TIMER: &str = "servers.mysql_query_elapsed";
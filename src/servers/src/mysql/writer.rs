@@ -14,6 +14,8 @@
 
 use std::ops::Deref;
 
+use common_error::ext::ErrorExt;
+use common_error::status_code::StatusCode;
 use common_query::Output;
 use common_recordbatch::{util, RecordBatch};
 use common_telemetry::warn;
@@ -176,6 +178,7 @@ impl<'a, W: AsyncWrite + Unpin> MysqlResultWriter<'a, W> {
                     Value::Int64(v) => row_writer.write_col(v)?,
                     Value::Float32(v) => row_writer.write_col(v.0)?,
                     Value::Float64(v) => row_writer.write_col(v.0)?,
+                    Value::Decimal128(v) => row_writer.write_col(v.to_string())?,
                     Value::String(v) => row_writer.write_col(v.as_utf8())?,
                     Value::Binary(v) => row_writer.write_col(v.deref())?,
                     Value::Date(v) => row_writer.write_col(v.to_chrono_date())?,
@@ -208,7 +211,10 @@ impl<'a, W: AsyncWrite + Unpin> MysqlResultWriter<'a, W> {
             &[(METRIC_PROTOCOL_LABEL, METRIC_ERROR_COUNTER_LABEL_MYSQL)]
         );
 
-        let kind = ErrorKind::ER_INTERNAL_ERROR;
+        let kind = match error.status_code() {
+            StatusCode::RateLimited => ErrorKind::ER_USER_LIMIT_REACHED,
+            _ => ErrorKind::ER_INTERNAL_ERROR,
+        };
         w.error(kind, error.to_string().as_bytes()).await?;
         Ok(())
     }
@@ -232,6 +238,7 @@ pub(crate) fn create_mysql_column(
         }
         ConcreteDataType::Float32(_) => Ok(ColumnType::MYSQL_TYPE_FLOAT),
         ConcreteDataType::Float64(_) => Ok(ColumnType::MYSQL_TYPE_DOUBLE),
+        ConcreteDataType::Decimal128(_) => Ok(ColumnType::MYSQL_TYPE_NEWDECIMAL),
         ConcreteDataType::Binary(_) | ConcreteDataType::String(_) => {
             Ok(ColumnType::MYSQL_TYPE_VARCHAR)
         }
@@ -0,0 +1,549 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Converts OpenTelemetry metrics to insert requests.
+//!
+//! Each metric is written to a table named after it, in the same way as the
+//! Prometheus remote write: data point values are written to the
+//! `greptime_value` column and timestamps to the `greptime_timestamp` column.
+//! Resource attributes, scope attributes and data point attributes become tags.
+//!
+//! Histograms and summaries are split into several tables like Prometheus does:
+//! - histogram `foo` is written to `foo_bucket` (with the `le` tag), `foo_sum`
+//!   and `foo_count`;
+//! - summary `foo` is written to `foo` (with the `quantile` tag), `foo_sum` and
+//!   `foo_count`.
+//!
+//! Exponential histograms are not supported yet. Their data points are dropped,
+//! logged and counted by the `servers.otlp_dropped_data_points` metric.
+
+use std::collections::{BTreeMap, HashMap};
+
+use api::v1::{InsertRequest as GrpcInsertRequest, InsertRequests};
+use common_grpc::writer::{LinesWriter, Precision};
+use common_telemetry::warn;
+use metrics::counter;
+use opentelemetry_proto::tonic::collector::metrics::v1::ExportMetricsServiceRequest;
+use opentelemetry_proto::tonic::common::v1::{any_value, AnyValue, KeyValue};
+use opentelemetry_proto::tonic::metrics::v1::{
+    metric, number_data_point, HistogramDataPoint, Metric, NumberDataPoint, SummaryDataPoint,
+};
+use snafu::ResultExt;
+
+use crate::error::{self, Result};
+use crate::metrics::METRIC_OTLP_DROPPED_DATA_POINTS;
+use crate::prom_store::{FIELD_COLUMN_NAME, TIMESTAMP_COLUMN_NAME};
+
+const LE_TAG: &str = "le";
+const QUANTILE_TAG: &str = "quantile";
+
+type Tags = BTreeMap<String, String>;
+
+/// Converts the OpenTelemetry metrics to insert requests, and returns the
+/// requests and the number of rows.
+pub fn to_grpc_insert_requests(
+    request: ExportMetricsServiceRequest,
+) -> Result<(InsertRequests, usize)> {
+    let mut writers: HashMap<String, LinesWriter> = HashMap::new();
+    let mut dropped = 0;
+    for resource_metrics in &request.resource_metrics {
+        let mut resource_tags = Tags::new();
+        if let Some(resource) = &resource_metrics.resource {
+            append_tags(&mut resource_tags, &resource.attributes);
+        }
+
+        for scope_metrics in &resource_metrics.scope_metrics {
+            let mut scope_tags = resource_tags.clone();
+            if let Some(scope) = &scope_metrics.scope {
+                append_tags(&mut scope_tags, &scope.attributes);
+            }
+
+            for metric in &scope_metrics.metrics {
+                dropped += write_metric(metric, &scope_tags, &mut writers)?;
+            }
+        }
+    }
+    if dropped > 0 {
+        warn!(
+            "Dropped {dropped} OTLP data points of exponential histograms, which are not supported"
+        );
+        counter!(METRIC_OTLP_DROPPED_DATA_POINTS, dropped as u64);
+    }
+
+    let mut row_counts = 0;
+    let inserts = writers
+        .into_iter()
+        .map(|(table_name, writer)| {
+            let (columns, row_count) = writer.finish();
+            row_counts += row_count as usize;
+            GrpcInsertRequest {
+                table_name,
+                region_number: 0,
+                columns,
+                row_count,
+            }
+        })
+        .collect();
+    Ok((InsertRequests { inserts }, row_counts))
+}
+
+/// Writes the data points of the metric, and returns the number of data points
+/// dropped as they are not supported.
+fn write_metric(
+    metric: &Metric,
+    tags: &Tags,
+    writers: &mut HashMap<String, LinesWriter>,
+) -> Result<usize> {
+    let name = normalize_name(&metric.name);
+    match &metric.data {
+        Some(metric::Data::Gauge(gauge)) => {
+            write_number_data_points(&name, &gauge.data_points, tags, writers)?
+        }
+        Some(metric::Data::Sum(sum)) => {
+            write_number_data_points(&name, &sum.data_points, tags, writers)?
+        }
+        Some(metric::Data::Histogram(histogram)) => {
+            for data_point in &histogram.data_points {
+                write_histogram_data_point(&name, data_point, tags, writers)?;
+            }
+        }
+        Some(metric::Data::Summary(summary)) => {
+            for data_point in &summary.data_points {
+                write_summary_data_point(&name, data_point, tags, writers)?;
+            }
+        }
+        Some(metric::Data::ExponentialHistogram(histogram)) => {
+            return Ok(histogram.data_points.len())
+        }
+        None => {}
+    }
+    Ok(0)
+}
+
+fn write_number_data_points(
+    name: &str,
+    data_points: &[NumberDataPoint],
+    tags: &Tags,
+    writers: &mut HashMap<String, LinesWriter>,
+) -> Result<()> {
+    for data_point in data_points {
+        let value = match data_point.value {
+            Some(number_data_point::Value::AsDouble(value)) => value,
+            Some(number_data_point::Value::AsInt(value)) => value as f64,
+            None => continue,
+        };
+        let tags = data_point_tags(tags, &data_point.attributes);
+        write_row(writers, name, &tags, data_point.time_unix_nano, value)?;
+    }
+    Ok(())
+}
+
+fn write_histogram_data_point(
+    name: &str,
+    data_point: &HistogramDataPoint,
+    tags: &Tags,
+    writers: &mut HashMap<String, LinesWriter>,
+) -> Result<()> {
+    let mut tags = data_point_tags(tags, &data_point.attributes);
+    let ts = data_point.time_unix_nano;
+
+    // Bucket counts of OpenTelemetry are not cumulative, while the `le` buckets
+    // are. The last bucket has no explicit bound, and is the `+Inf` bucket.
+    let bucket_table = format!("{name}_bucket");
+    let mut cumulative_count = 0;
+    for (i, count) in data_point.bucket_counts.iter().enumerate() {
+        cumulative_count += count;
+        let le = data_point
+            .explicit_bounds
+            .get(i)
+            .map(|bound| bound.to_string())
+            .unwrap_or_else(|| "+Inf".to_string());
+        let _ = tags.insert(LE_TAG.to_string(), le);
+        write_row(writers, &bucket_table, &tags, ts, cumulative_count as f64)?;
+    }
+    let _ = tags.remove(LE_TAG);
+
+    if let Some(sum) = data_point.sum {
+        write_row(writers, &format!("{name}_sum"), &tags, ts, sum)?;
+    }
+    write_row(
+        writers,
+        &format!("{name}_count"),
+        &tags,
+        ts,
+        data_point.count as f64,
+    )
+}
+
+fn write_summary_data_point(
+    name: &str,
+    data_point: &SummaryDataPoint,
+    tags: &Tags,
+    writers: &mut HashMap<String, LinesWriter>,
+) -> Result<()> {
+    let mut tags = data_point_tags(tags, &data_point.attributes);
+    let ts = data_point.time_unix_nano;
+
+    for quantile in &data_point.quantile_values {
+        let _ = tags.insert(QUANTILE_TAG.to_string(), quantile.quantile.to_string());
+        write_row(writers, name, &tags, ts, quantile.value)?;
+    }
+    let _ = tags.remove(QUANTILE_TAG);
+
+    write_row(writers, &format!("{name}_sum"), &tags, ts, data_point.sum)?;
+    write_row(
+        writers,
+        &format!("{name}_count"),
+        &tags,
+        ts,
+        data_point.count as f64,
+    )
+}
+
+fn write_row(
+    writers: &mut HashMap<String, LinesWriter>,
+    table_name: &str,
+    tags: &Tags,
+    time_unix_nano: u64,
+    value: f64,
+) -> Result<()> {
+    let writer = writers
+        .entry(table_name.to_string())
+        .or_insert_with(|| LinesWriter::with_lines(16));
+    for (name, value) in tags {
+        writer
+            .write_tag(name, value)
+            .context(error::OtlpMetricsWriteSnafu)?;
+    }
+    writer
+        .write_ts(
+            TIMESTAMP_COLUMN_NAME,
+            (time_unix_nano as i64, Precision::Nanosecond),
+        )
+        .context(error::OtlpMetricsWriteSnafu)?;
+    writer
+        .write_f64(FIELD_COLUMN_NAME, value)
+        .context(error::OtlpMetricsWriteSnafu)?;
+    writer.commit();
+    Ok(())
+}
+
+/// Returns the tags of a data point. Its attributes override the resource and
+/// scope attributes of the same names.
+fn data_point_tags(tags: &Tags, attributes: &[KeyValue]) -> Tags {
+    let mut tags = tags.clone();
+    append_tags(&mut tags, attributes);
+    tags
+}
+
+fn append_tags(tags: &mut Tags, attributes: &[KeyValue]) {
+    for attribute in attributes {
+        let value = attribute
+            .value
+            .as_ref()
+            .map(any_value_to_string)
+            .unwrap_or_default();
+        let _ = tags.insert(normalize_name(&attribute.key), value);
+    }
+}
+
+fn any_value_to_string(value: &AnyValue) -> String {
+    match &value.value {
+        Some(any_value::Value::StringValue(v)) => v.clone(),
+        Some(any_value::Value::BoolValue(v)) => v.to_string(),
+        Some(any_value::Value::IntValue(v)) => v.to_string(),
+        Some(any_value::Value::DoubleValue(v)) => v.to_string(),
+        Some(any_value::Value::BytesValue(v)) => hex::encode(v),
+        Some(any_value::Value::ArrayValue(array)) => {
+            let values = array
+                .values
+                .iter()
+                .map(any_value_to_string)
+                .collect::<Vec<_>>();
+            format!("[{}]", values.join(","))
+        }
+        Some(any_value::Value::KvlistValue(kvlist)) => {
+            let values = kvlist
+                .values
+                .iter()
+                .map(|kv| {
+                    let value = kv.value.as_ref().map(any_value_to_string);
+                    format!("{}={}", kv.key, value.unwrap_or_default())
+                })
+                .collect::<Vec<_>>();
+            format!("{{{}}}", values.join(","))
+        }
+        None => String::new(),
+    }
+}
+
+/// Replaces the characters that are invalid in Prometheus metric and label
+/// names, like the dots in `http.server.duration`, with underscores.
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use api::v1::Column;
+    use opentelemetry_proto::tonic::metrics::v1::summary_data_point::ValueAtQuantile;
+    use opentelemetry_proto::tonic::metrics::v1::{
+        ExponentialHistogram, ExponentialHistogramDataPoint, Gauge, Histogram, ResourceMetrics,
+        ScopeMetrics, Sum, Summary,
+    };
+    use opentelemetry_proto::tonic::resource::v1::Resource;
+
+    use super::*;
+
+    fn key_value(key: &str, value: &str) -> KeyValue {
+        KeyValue {
+            key: key.to_string(),
+            value: Some(AnyValue {
+                value: Some(any_value::Value::StringValue(value.to_string())),
+            }),
+        }
+    }
+
+    fn metric(name: &str, data: metric::Data) -> Metric {
+        Metric {
+            name: name.to_string(),
+            data: Some(data),
+            ..Default::default()
+        }
+    }
+
+    fn number_data_point(value: number_data_point::Value) -> NumberDataPoint {
+        NumberDataPoint {
+            attributes: vec![key_value("host", "host1")],
+            time_unix_nano: 1_000_000_000,
+            value: Some(value),
+            ..Default::default()
+        }
+    }
+
+    fn mock_request() -> ExportMetricsServiceRequest {
+        let metrics = vec![
+            metric(
+                "system.cpu.usage",
+                metric::Data::Gauge(Gauge {
+                    data_points: vec![number_data_point(number_data_point::Value::AsDouble(0.5))],
+                }),
+            ),
+            metric(
+                "http.requests",
+                metric::Data::Sum(Sum {
+                    data_points: vec![number_data_point(number_data_point::Value::AsInt(42))],
+                    ..Default::default()
+                }),
+            ),
+            metric(
+                "http.duration",
+                metric::Data::Histogram(Histogram {
+                    data_points: vec![HistogramDataPoint {
+                        time_unix_nano: 2_000_000_000,
+                        count: 6,
+                        sum: Some(12.5),
+                        bucket_counts: vec![1, 2, 3],
+                        explicit_bounds: vec![0.5, 1.0],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }),
+            ),
+            metric(
+                "rpc.latency",
+                metric::Data::Summary(Summary {
+                    data_points: vec![SummaryDataPoint {
+                        time_unix_nano: 3_000_000_000,
+                        count: 10,
+                        sum: 20.0,
+                        quantile_values: vec![
+                            ValueAtQuantile {
+                                quantile: 0.5,
+                                value: 1.5,
+                            },
+                            ValueAtQuantile {
+                                quantile: 0.99,
+                                value: 3.0,
+                            },
+                        ],
+                        ..Default::default()
+                    }],
+                }),
+            ),
+        ];
+
+        ExportMetricsServiceRequest {
+            resource_metrics: vec![ResourceMetrics {
+                resource: Some(Resource {
+                    attributes: vec![key_value("service.name", "app"), key_value("host", "x")],
+                    ..Default::default()
+                }),
+                scope_metrics: vec![ScopeMetrics {
+                    metrics,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        }
+    }
+
+    fn find_column<'a>(insert: &'a GrpcInsertRequest, name: &str) -> &'a Column {
+        insert
+            .columns
+            .iter()
+            .find(|column| column.column_name == name)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_to_grpc_insert_requests() {
+        let (requests, rows) = to_grpc_insert_requests(mock_request()).unwrap();
+        let mut inserts = requests.inserts;
+        inserts.sort_unstable_by(|l, r| l.table_name.cmp(&r.table_name));
+
+        let table_names = inserts
+            .iter()
+            .map(|insert| insert.table_name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "http_duration_bucket",
+                "http_duration_count",
+                "http_duration_sum",
+                "http_requests",
+                "rpc_latency",
+                "rpc_latency_count",
+                "rpc_latency_sum",
+                "system_cpu_usage",
+            ],
+            table_names
+        );
+        assert_eq!(11, rows);
+
+        // Data point attributes override resource attributes.
+        let gauge = &inserts[7];
+        assert_eq!(1, gauge.row_count);
+        assert_eq!(4, gauge.columns.len());
+        let values = find_column(gauge, "host").values.as_ref().unwrap();
+        assert_eq!(vec!["host1"], values.string_values);
+        let values = find_column(gauge, "service_name").values.as_ref().unwrap();
+        assert_eq!(vec!["app"], values.string_values);
+        let values = find_column(gauge, TIMESTAMP_COLUMN_NAME)
+            .values
+            .as_ref()
+            .unwrap();
+        assert_eq!(vec![1000], values.ts_millisecond_values);
+        let values = find_column(gauge, FIELD_COLUMN_NAME)
+            .values
+            .as_ref()
+            .unwrap();
+        assert_eq!(vec![0.5], values.f64_values);
+
+        let sum = &inserts[3];
+        let values = find_column(sum, FIELD_COLUMN_NAME).values.as_ref().unwrap();
+        assert_eq!(vec![42.0], values.f64_values);
+
+        let bucket = &inserts[0];
+        assert_eq!(3, bucket.row_count);
+        let values = find_column(bucket, LE_TAG).values.as_ref().unwrap();
+        assert_eq!(vec!["0.5", "1", "+Inf"], values.string_values);
+        let values = find_column(bucket, FIELD_COLUMN_NAME)
+            .values
+            .as_ref()
+            .unwrap();
+        assert_eq!(vec![1.0, 3.0, 6.0], values.f64_values);
+        let values = find_column(&inserts[1], FIELD_COLUMN_NAME)
+            .values
+            .as_ref()
+            .unwrap();
+        assert_eq!(vec![6.0], values.f64_values);
+        let values = find_column(&inserts[2], FIELD_COLUMN_NAME)
+            .values
+            .as_ref()
+            .unwrap();
+        assert_eq!(vec![12.5], values.f64_values);
+        assert!(inserts[2]
+            .columns
+            .iter()
+            .all(|column| column.column_name != LE_TAG));
+
+        let summary = &inserts[4];
+        assert_eq!(2, summary.row_count);
+        let values = find_column(summary, QUANTILE_TAG).values.as_ref().unwrap();
+        assert_eq!(vec!["0.5", "0.99"], values.string_values);
+        let values = find_column(summary, FIELD_COLUMN_NAME)
+            .values
+            .as_ref()
+            .unwrap();
+        assert_eq!(vec![1.5, 3.0], values.f64_values);
+        let values = find_column(&inserts[6], FIELD_COLUMN_NAME)
+            .values
+            .as_ref()
+            .unwrap();
+        assert_eq!(vec![20.0], values.f64_values);
+    }
+
+    #[test]
+    fn test_drop_exponential_histogram() {
+        let metric = metric(
+            "http.duration",
+            metric::Data::ExponentialHistogram(ExponentialHistogram {
+                data_points: vec![
+                    ExponentialHistogramDataPoint::default(),
+                    ExponentialHistogramDataPoint::default(),
+                ],
+                ..Default::default()
+            }),
+        );
+        let mut writers = HashMap::new();
+        assert_eq!(
+            2,
+            write_metric(&metric, &Tags::new(), &mut writers).unwrap()
+        );
+        assert!(writers.is_empty());
+    }
+
+    #[test]
+    fn test_any_value_to_string() {
+        let value = AnyValue {
+            value: Some(any_value::Value::ArrayValue(
+                opentelemetry_proto::tonic::common::v1::ArrayValue {
+                    values: vec![
+                        AnyValue {
+                            value: Some(any_value::Value::IntValue(1)),
+                        },
+                        AnyValue {
+                            value: Some(any_value::Value::BoolValue(true)),
+                        },
+                    ],
+                },
+            )),
+        };
+        assert_eq!("[1,true]", any_value_to_string(&value));
+
+        assert_eq!(
+            "http_server_duration",
+            normalize_name("http.server.duration")
+        );
+    }
+}
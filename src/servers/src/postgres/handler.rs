@@ -15,6 +15,8 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use common_error::ext::ErrorExt;
+use common_error::status_code::StatusCode;
 use common_query::Output;
 use common_recordbatch::error::Result as RecordBatchResult;
 use common_recordbatch::RecordBatch;
@@ -92,11 +94,19 @@ fn output_to_query_response<'a>(
             let schema = recordbatches.schema();
             recordbatches_to_query_response(recordbatches.as_stream(), schema, field_format)
         }
-        Err(e) => Ok(Response::Error(Box::new(ErrorInfo::new(
-            "ERROR".to_string(),
-            "XX000".to_string(),
-            e.to_string(),
-        )))),
+        Err(e) => {
+            let code = match e.status_code() {
+                // configuration_limit_exceeded
+                StatusCode::RateLimited => "53400",
+                // internal_error
+                _ => "XX000",
+            };
+            Ok(Response::Error(Box::new(ErrorInfo::new(
+                "ERROR".to_string(),
+                code.to_string(),
+                e.to_string(),
+            ))))
+        }
     }
 }
 
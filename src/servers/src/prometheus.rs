@@ -20,7 +20,8 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use axum::body::BoxBody;
 use axum::extract::{Path, Query, State};
-use axum::{middleware, routing, Form, Json, Router};
+use axum::{middleware, routing, Extension, Form, Json, Router};
+use catalog::CatalogManagerRef;
 use common_catalog::consts::DEFAULT_SCHEMA_NAME;
 use common_error::ext::ErrorExt;
 use common_error::status_code::StatusCode;
@@ -30,6 +31,7 @@ use common_telemetry::info;
 use common_time::util::{current_time_rfc3339, yesterday_rfc3339};
 use datatypes::prelude::ConcreteDataType;
 use datatypes::scalars::ScalarVector;
+use datatypes::schema::COMMENT_KEY;
 use datatypes::vectors::{Float64Vector, StringVector, TimestampMillisecondVector};
 use futures::FutureExt;
 use promql_parser::label::METRIC_NAME;
@@ -41,7 +43,7 @@ use query::parser::{PromQuery, DEFAULT_LOOKBACK_STRING};
 use schemars::JsonSchema;
 use serde::de::{self, MapAccess, Visitor};
 use serde::{Deserialize, Serialize};
-use session::context::{QueryContext, QueryContextRef};
+use session::context::{QueryContext, QueryContextRef, UserInfo};
 use snafu::{ensure, Location, OptionExt, ResultExt};
 use tokio::sync::oneshot::Sender;
 use tokio::sync::{oneshot, Mutex};
@@ -52,8 +54,8 @@ use tower_http::trace::TraceLayer;
 
 use crate::auth::UserProviderRef;
 use crate::error::{
-    AlreadyStartedSnafu, CollectRecordbatchSnafu, Error, InternalSnafu, InvalidQuerySnafu, Result,
-    StartHttpSnafu, UnexpectedResultSnafu,
+    AlreadyStartedSnafu, CatalogErrorSnafu, CollectRecordbatchSnafu, Error, InternalSnafu,
+    InvalidQuerySnafu, Result, StartHttpSnafu, UnexpectedResultSnafu,
 };
 use crate::http::authorize::HttpAuth;
 use crate::http::track_metrics;
@@ -67,6 +69,15 @@ pub type PrometheusHandlerRef = Arc<dyn PrometheusHandler + Send + Sync>;
 #[async_trait]
 pub trait PrometheusHandler {
     async fn do_query(&self, query: &PromQuery, query_ctx: QueryContextRef) -> Result<Output>;
+
+    /// Returns the metadata of metrics in the current database, which should be
+    /// retrieved by [retrieve_metadata] once the request is authorized.
+    async fn metric_metadata(
+        &self,
+        metric: Option<String>,
+        limit: Option<usize>,
+        query_ctx: QueryContextRef,
+    ) -> Result<HashMap<String, Vec<PromMetadata>>>;
 }
 
 /// PromServer represents PrometheusServer which handles the compliance with prometheus HTTP API
@@ -91,11 +102,21 @@ impl PrometheusServer {
     }
 
     pub fn make_app(&self) -> Router {
-        // TODO(ruihang): implement format_query, series, values, query_examplars and targets methods
+        // TODO(ruihang): implement targets methods
 
         let router = Router::new()
             .route("/query", routing::post(instant_query).get(instant_query))
             .route("/query_range", routing::post(range_query).get(range_query))
+            .route(
+                "/query_exemplars",
+                routing::post(query_exemplars).get(query_exemplars),
+            )
+            .route(
+                "/format_query",
+                routing::post(format_query).get(format_query),
+            )
+            .route("/metadata", routing::get(metadata_query))
+            .route("/status/buildinfo", routing::get(build_info_query))
             .route("/labels", routing::post(labels_query).get(labels_query))
             .route("/series", routing::post(series_query).get(series_query))
             .route(
@@ -187,6 +208,30 @@ pub struct PromData {
     pub result: Vec<PromSeries>,
 }
 
+/// Build information returned by `/status/buildinfo`
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PromBuildInfo {
+    pub version: String,
+    pub revision: String,
+    pub branch: String,
+    pub build_user: String,
+    pub build_date: String,
+    pub go_version: String,
+}
+
+/// Metadata of a metric returned by `/metadata`
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct PromMetadata {
+    #[serde(rename = "type")]
+    pub metric_type: String,
+    pub help: String,
+    pub unit: String,
+}
+
+/// The variants are deserialized from JSON in order, and each variant must not
+/// be deserializable from the JSON of the variants after it, except the ones of
+/// the same JSON type, i.e. `Labels`, `Series` and `LabelValues`.
 #[derive(Debug, Serialize, Deserialize, JsonSchema, PartialEq)]
 #[serde(untagged)]
 pub enum PrometheusResponse {
@@ -194,6 +239,9 @@ pub enum PrometheusResponse {
     Labels(Vec<String>),
     Series(Vec<HashMap<String, String>>),
     LabelValues(Vec<String>),
+    FormatQuery(String),
+    BuildInfo(PromBuildInfo),
+    Metadata(HashMap<String, Vec<PromMetadata>>),
 }
 
 impl Default for PrometheusResponse {
@@ -425,6 +473,7 @@ pub struct InstantQuery {
 #[axum_macros::debug_handler]
 pub async fn instant_query(
     State(handler): State<PrometheusHandlerRef>,
+    Extension(user_info): Extension<UserInfo>,
     Query(params): Query<InstantQuery>,
     Form(form_params): Form<InstantQuery>,
 ) -> Json<PrometheusJsonResponse> {
@@ -444,6 +493,7 @@ pub async fn instant_query(
     let (catalog, schema) = crate::parse_catalog_and_schema_from_client_database_name(db);
 
     let query_ctx = QueryContext::with(catalog, schema);
+    query_ctx.set_current_user(Some(user_info));
 
     let result = handler.do_query(&prom_query, Arc::new(query_ctx)).await;
     let (metric_name, result_type) = match retrieve_metric_name_and_result_type(&prom_query.query) {
@@ -468,6 +518,7 @@ pub struct RangeQuery {
 #[axum_macros::debug_handler]
 pub async fn range_query(
     State(handler): State<PrometheusHandlerRef>,
+    Extension(user_info): Extension<UserInfo>,
     Query(params): Query<RangeQuery>,
     Form(form_params): Form<RangeQuery>,
 ) -> Json<PrometheusJsonResponse> {
@@ -482,6 +533,7 @@ pub async fn range_query(
     let (catalog, schema) = crate::parse_catalog_and_schema_from_client_database_name(db);
 
     let query_ctx = QueryContext::with(catalog, schema);
+    query_ctx.set_current_user(Some(user_info));
 
     let result = handler.do_query(&prom_query, Arc::new(query_ctx)).await;
     let metric_name = match retrieve_metric_name_and_result_type(&prom_query.query) {
@@ -540,6 +592,7 @@ impl<'de> Deserialize<'de> for Matches {
 #[axum_macros::debug_handler]
 pub async fn labels_query(
     State(handler): State<PrometheusHandlerRef>,
+    Extension(user_info): Extension<UserInfo>,
     Query(params): Query<LabelsQuery>,
     Form(form_params): Form<LabelsQuery>,
 ) -> Json<PrometheusJsonResponse> {
@@ -563,6 +616,7 @@ pub async fn labels_query(
     let db = &params.db.unwrap_or(DEFAULT_SCHEMA_NAME.to_string());
     let (catalog, schema) = crate::parse_catalog_and_schema_from_client_database_name(db);
     let query_ctx = Arc::new(QueryContext::with(catalog, schema));
+    query_ctx.set_current_user(Some(user_info));
 
     let mut labels = HashSet::new();
     let _ = labels.insert(METRIC_NAME.to_string());
@@ -776,6 +830,7 @@ pub struct LabelValueQuery {
 #[axum_macros::debug_handler]
 pub async fn label_values_query(
     State(handler): State<PrometheusHandlerRef>,
+    Extension(user_info): Extension<UserInfo>,
     Path(label_name): Path<String>,
     Query(params): Query<LabelValueQuery>,
 ) -> Json<PrometheusJsonResponse> {
@@ -789,6 +844,7 @@ pub async fn label_values_query(
     let db = &params.db.unwrap_or(DEFAULT_SCHEMA_NAME.to_string());
     let (catalog, schema) = crate::parse_catalog_and_schema_from_client_database_name(db);
     let query_ctx = Arc::new(QueryContext::with(catalog, schema));
+    query_ctx.set_current_user(Some(user_info));
 
     let mut label_values = HashSet::new();
 
@@ -888,6 +944,7 @@ pub struct SeriesQuery {
 #[axum_macros::debug_handler]
 pub async fn series_query(
     State(handler): State<PrometheusHandlerRef>,
+    Extension(user_info): Extension<UserInfo>,
     Query(params): Query<SeriesQuery>,
     Form(form_params): Form<SeriesQuery>,
 ) -> Json<PrometheusJsonResponse> {
@@ -910,6 +967,7 @@ pub async fn series_query(
     let db = &params.db.unwrap_or(DEFAULT_SCHEMA_NAME.to_string());
     let (catalog, schema) = super::parse_catalog_and_schema_from_client_database_name(db);
     let query_ctx = Arc::new(QueryContext::with(catalog, schema));
+    query_ctx.set_current_user(Some(user_info));
 
     let mut series = Vec::new();
     for query in queries {
@@ -929,3 +987,168 @@ pub async fn series_query(
     }
     PrometheusJsonResponse::success(PrometheusResponse::Series(series))
 }
+
+/// Exemplars are not stored, so querying them is unsupported.
+#[axum_macros::debug_handler]
+pub async fn query_exemplars() -> Json<PrometheusJsonResponse> {
+    PrometheusJsonResponse::error(
+        StatusCode::Unsupported.to_string(),
+        "Querying exemplars is not supported",
+    )
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct FormatQuery {
+    query: Option<String>,
+}
+
+#[axum_macros::debug_handler]
+pub async fn format_query(
+    Query(params): Query<FormatQuery>,
+    Form(form_params): Form<FormatQuery>,
+) -> Json<PrometheusJsonResponse> {
+    let query = params.query.or(form_params.query).unwrap_or_default();
+    match promql_parser::parser::parse(&query) {
+        Ok(expr) => {
+            PrometheusJsonResponse::success(PrometheusResponse::FormatQuery(expr.to_string()))
+        }
+        Err(reason) => {
+            let err = InvalidQuerySnafu { reason }.build();
+            PrometheusJsonResponse::error(err.status_code().to_string(), err.to_string())
+        }
+    }
+}
+
+#[axum_macros::debug_handler]
+pub async fn build_info_query() -> Json<PrometheusJsonResponse> {
+    PrometheusJsonResponse::success(PrometheusResponse::BuildInfo(PromBuildInfo {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        revision: env!("GIT_COMMIT").to_string(),
+        branch: env!("GIT_BRANCH").to_string(),
+        build_user: "greptime".to_string(),
+        build_date: env!("SOURCE_TIMESTAMP").to_string(),
+        go_version: env!("RUSTC_VERSION").to_string(),
+    }))
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct MetadataQuery {
+    metric: Option<String>,
+    limit: Option<usize>,
+    db: Option<String>,
+}
+
+#[axum_macros::debug_handler]
+pub async fn metadata_query(
+    State(handler): State<PrometheusHandlerRef>,
+    Extension(user_info): Extension<UserInfo>,
+    Query(params): Query<MetadataQuery>,
+) -> Json<PrometheusJsonResponse> {
+    let db = &params.db.unwrap_or(DEFAULT_SCHEMA_NAME.to_string());
+    let (catalog, schema) = crate::parse_catalog_and_schema_from_client_database_name(db);
+    let query_ctx = Arc::new(QueryContext::with(catalog, schema));
+    query_ctx.set_current_user(Some(user_info));
+
+    match handler
+        .metric_metadata(params.metric, params.limit, query_ctx)
+        .await
+    {
+        Ok(metadata) => PrometheusJsonResponse::success(PrometheusResponse::Metadata(metadata)),
+        Err(err) => PrometheusJsonResponse::error(err.status_code().to_string(), err.to_string()),
+    }
+}
+
+/// Retrieve metadata of metrics (tables) from the catalog. The help text is
+/// the comment of the table, or the comment of its first commented field column.
+pub async fn retrieve_metadata(
+    catalog_manager: CatalogManagerRef,
+    catalog: &str,
+    schema: &str,
+    metric: Option<String>,
+    limit: Option<usize>,
+) -> Result<HashMap<String, Vec<PromMetadata>>> {
+    let mut table_names = match metric {
+        Some(metric) => vec![metric],
+        None => catalog_manager
+            .table_names(catalog, schema)
+            .await
+            .context(CatalogErrorSnafu)?,
+    };
+    table_names.sort();
+
+    let mut metadata = HashMap::new();
+    for table_name in table_names {
+        if limit.is_some_and(|limit| metadata.len() >= limit) {
+            break;
+        }
+        let table = catalog_manager
+            .table(catalog, schema, &table_name)
+            .await
+            .context(CatalogErrorSnafu)?;
+        let Some(table) = table else { continue };
+
+        let table_info = table.table_info();
+        let table_comment = table_info.desc.clone().filter(|desc| !desc.is_empty());
+        let help = table_comment
+            .or_else(|| {
+                let table_schema = &table_info.meta.schema;
+                table_info.meta.field_column_names().find_map(|name| {
+                    table_schema
+                        .column_schema_by_name(name)
+                        .and_then(|column| column.metadata().get(COMMENT_KEY).cloned())
+                })
+            })
+            .unwrap_or_default();
+
+        let _ = metadata.insert(
+            table_name,
+            vec![PromMetadata {
+                // metric types are not recorded
+                metric_type: "unknown".to_string(),
+                help,
+                unit: String::new(),
+            }],
+        );
+    }
+    Ok(metadata)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prometheus_response_round_trip() {
+        let responses = vec![
+            PrometheusResponse::PromData(PromData {
+                result_type: "vector".to_string(),
+                result: vec![PromSeries {
+                    metric: HashMap::from([(METRIC_NAME.to_string(), "demo".to_string())]),
+                    value: Some((1.0, "1".to_string())),
+                    ..Default::default()
+                }],
+            }),
+            PrometheusResponse::PromData(PromData::default()),
+            PrometheusResponse::Labels(vec!["host".to_string()]),
+            PrometheusResponse::FormatQuery("sum(demo)".to_string()),
+            PrometheusResponse::BuildInfo(PromBuildInfo {
+                version: "0.3.2".to_string(),
+                ..Default::default()
+            }),
+            PrometheusResponse::BuildInfo(PromBuildInfo::default()),
+            PrometheusResponse::Metadata(HashMap::from([(
+                "demo".to_string(),
+                vec![PromMetadata {
+                    metric_type: "unknown".to_string(),
+                    ..Default::default()
+                }],
+            )])),
+            PrometheusResponse::Metadata(HashMap::new()),
+        ];
+        for response in responses {
+            let json = serde_json::to_string(&response).unwrap();
+            let deserialized: PrometheusResponse = serde_json::from_str(&json).unwrap();
+            assert_eq!(response, deserialized, "json: {json}");
+        }
+    }
+}
@@ -31,16 +31,23 @@ use std::sync::Arc;
 use api::prom_store::remote::{ReadRequest, WriteRequest};
 use async_trait::async_trait;
 use common_query::Output;
+use opentelemetry_proto::tonic::collector::metrics::v1::{
+    ExportMetricsServiceRequest, ExportMetricsServiceResponse,
+};
+use query::influxql::InfluxqlStatement;
 use session::context::QueryContextRef;
 
 use crate::error::Result;
+use crate::graphite::codec::DataPoint as GraphiteDataPoint;
 use crate::influxdb::InfluxdbRequest;
 use crate::opentsdb::codec::DataPoint;
 use crate::prom_store::Metrics;
 
 pub type OpentsdbProtocolHandlerRef = Arc<dyn OpentsdbProtocolHandler + Send + Sync>;
+pub type GraphiteProtocolHandlerRef = Arc<dyn GraphiteProtocolHandler + Send + Sync>;
 pub type InfluxdbLineProtocolHandlerRef = Arc<dyn InfluxdbLineProtocolHandler + Send + Sync>;
 pub type PromStoreProtocolHandlerRef = Arc<dyn PromStoreProtocolHandler + Send + Sync>;
+pub type OpenTelemetryProtocolHandlerRef = Arc<dyn OpenTelemetryProtocolHandler + Send + Sync>;
 pub type ScriptHandlerRef = Arc<dyn ScriptHandler + Send + Sync>;
 
 #[async_trait]
@@ -59,6 +66,9 @@ pub trait InfluxdbLineProtocolHandler {
     /// A successful request will not return a response.
     /// Only on error will the socket return a line of data.
     async fn exec(&self, request: &InfluxdbRequest, ctx: QueryContextRef) -> Result<()>;
+
+    /// Executes a parsed InfluxQL statement, serving the InfluxDB v1 `/query` API.
+    async fn query(&self, stmt: InfluxqlStatement, ctx: QueryContextRef) -> Result<Output>;
 }
 
 #[async_trait]
@@ -68,6 +78,13 @@ pub trait OpentsdbProtocolHandler {
     async fn exec(&self, data_point: &DataPoint, ctx: QueryContextRef) -> Result<()>;
 }
 
+#[async_trait]
+pub trait GraphiteProtocolHandler {
+    /// Writes a batch of data points received from one connection. The Graphite plaintext
+    /// protocol has no response, so errors are only logged by the server.
+    async fn exec(&self, data_points: &[GraphiteDataPoint], ctx: QueryContextRef) -> Result<()>;
+}
+
 pub struct PromStoreResponse {
     pub content_type: String,
     pub content_encoding: String,
@@ -83,3 +100,13 @@ pub trait PromStoreProtocolHandler {
     /// Handling push gateway requests
     async fn ingest_metrics(&self, metrics: Metrics) -> Result<()>;
 }
+
+#[async_trait]
+pub trait OpenTelemetryProtocolHandler {
+    /// Handling opentelemetry metrics request
+    async fn metrics(
+        &self,
+        request: ExportMetricsServiceRequest,
+        ctx: QueryContextRef,
+    ) -> Result<ExportMetricsServiceResponse>;
+}
@@ -0,0 +1,111 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use common_runtime::Builder as RuntimeBuilder;
+use servers::error::Result;
+use servers::graphite::codec::DataPoint;
+use servers::graphite::template::Templates;
+use servers::graphite::GraphiteServer;
+use servers::query_handler::GraphiteProtocolHandler;
+use servers::server::Server;
+use session::context::QueryContextRef;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpStream;
+use tokio::sync::mpsc;
+
+struct DummyGraphiteInstance {
+    tx: mpsc::Sender<DataPoint>,
+}
+
+#[async_trait]
+impl GraphiteProtocolHandler for DummyGraphiteInstance {
+    async fn exec(&self, data_points: &[DataPoint], _ctx: QueryContextRef) -> Result<()> {
+        for data_point in data_points {
+            let _ = self.tx.send(data_point.clone()).await;
+        }
+        Ok(())
+    }
+}
+
+fn create_graphite_server(tx: mpsc::Sender<DataPoint>) -> Result<Box<dyn Server>> {
+    let query_handler = Arc::new(DummyGraphiteInstance { tx });
+    let templates = Templates::try_new(&["collectd.* .host.measurement.field*"])?;
+    let io_runtime = Arc::new(
+        RuntimeBuilder::default()
+            .worker_threads(2)
+            .thread_name("graphite-io-handlers")
+            .build()
+            .unwrap(),
+    );
+    Ok(GraphiteServer::create_server(
+        query_handler,
+        templates,
+        io_runtime,
+    ))
+}
+
+#[tokio::test]
+async fn test_start_graphite_server() -> Result<()> {
+    let (tx, _) = mpsc::channel(100);
+    let server = create_graphite_server(tx)?;
+    let listening = "127.0.0.1:0".parse::<SocketAddr>().unwrap();
+    let result = server.start(listening).await;
+    let _ = result.unwrap();
+
+    let result = server.start(listening).await;
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("Graphite server has been started."));
+
+    server.shutdown().await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_graphite_write() -> Result<()> {
+    let (tx, mut rx) = mpsc::channel(100);
+    let server = create_graphite_server(tx)?;
+    let listening = "127.0.0.1:0".parse::<SocketAddr>().unwrap();
+    let addr = server.start(listening).await?;
+
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    stream
+        .write_all(b"collectd.web01.load.shortterm 0.5 1686000000\n")
+        .await
+        .unwrap();
+    stream
+        .write_all(b"sys.uptime;host=web01 3600 1686000000\n")
+        .await
+        .unwrap();
+    drop(stream);
+
+    let data_point = rx.recv().await.unwrap();
+    assert_eq!(data_point.table(), "load");
+    assert_eq!(data_point.field(), "shortterm");
+    assert_eq!(
+        data_point.tags(),
+        &vec![("host".to_string(), "web01".to_string())]
+    );
+    assert_eq!(data_point.value(), 0.5);
+    assert_eq!(data_point.ts_millis(), 1686000000000);
+
+    let data_point = rx.recv().await.unwrap();
+    assert_eq!(data_point.table(), "sys.uptime");
+    assert_eq!(data_point.value(), 3600.0);
+
+    server.shutdown().await
+}
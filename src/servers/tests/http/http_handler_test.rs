@@ -18,12 +18,14 @@ use axum::body::{Body, Bytes};
 use axum::extract::{Json, Query, RawBody, State};
 use axum::Form;
 use common_telemetry::metric;
+use datatypes::arrow::ipc::reader::StreamReader;
 use http_body::combinators::UnsyncBoxBody;
 use hyper::Response;
 use metrics::counter;
+use servers::http::format::ResponseFormat;
 use servers::http::{
     handler as http_handler, script as script_handler, ApiState, GreptimeOptionsConfigState,
-    JsonOutput,
+    JsonOutput, JsonResponse,
 };
 use servers::metrics_handler::MetricsHandler;
 use session::context::UserInfo;
@@ -37,14 +39,17 @@ use crate::{
 #[tokio::test]
 async fn test_sql_not_provided() {
     let sql_handler = create_testing_sql_query_handler(MemTable::default_numbers_table());
-    let Json(json) = http_handler::sql(
-        State(ApiState {
-            sql_handler,
-            script_handler: None,
-        }),
-        Query(http_handler::SqlQuery::default()),
-        axum::Extension(UserInfo::default()),
-        Form(http_handler::SqlQuery::default()),
+    let json = into_json_response(
+        http_handler::sql(
+            State(ApiState {
+                sql_handler,
+                script_handler: None,
+            }),
+            Query(http_handler::SqlQuery::default()),
+            axum::Extension(UserInfo::default()),
+            Form(http_handler::SqlQuery::default()),
+        )
+        .await,
     )
     .await;
     assert!(!json.success());
@@ -62,14 +67,17 @@ async fn test_sql_output_rows() {
     let query = create_query();
     let sql_handler = create_testing_sql_query_handler(MemTable::default_numbers_table());
 
-    let Json(json) = http_handler::sql(
-        State(ApiState {
-            sql_handler,
-            script_handler: None,
-        }),
-        query,
-        axum::Extension(UserInfo::default()),
-        Form(http_handler::SqlQuery::default()),
+    let json = into_json_response(
+        http_handler::sql(
+            State(ApiState {
+                sql_handler,
+                script_handler: None,
+            }),
+            query,
+            axum::Extension(UserInfo::default()),
+            Form(http_handler::SqlQuery::default()),
+        )
+        .await,
     )
     .await;
     assert!(json.success(), "{json:?}");
@@ -108,14 +116,17 @@ async fn test_sql_form() {
     let form = create_form();
     let sql_handler = create_testing_sql_query_handler(MemTable::default_numbers_table());
 
-    let Json(json) = http_handler::sql(
-        State(ApiState {
-            sql_handler,
-            script_handler: None,
-        }),
-        Query(http_handler::SqlQuery::default()),
-        axum::Extension(UserInfo::default()),
-        form,
+    let json = into_json_response(
+        http_handler::sql(
+            State(ApiState {
+                sql_handler,
+                script_handler: None,
+            }),
+            Query(http_handler::SqlQuery::default()),
+            axum::Extension(UserInfo::default()),
+            form,
+        )
+        .await,
     )
     .await;
     assert!(json.success(), "{json:?}");
@@ -342,10 +353,72 @@ fn create_invalid_script_query() -> Query<script_handler::ScriptQuery> {
     })
 }
 
+#[tokio::test]
+async fn test_sql_output_formats() {
+    let sql = "select sum(uint32s) from numbers limit 20";
+    let query = |format| {
+        Query(http_handler::SqlQuery {
+            sql: Some(sql.to_string()),
+            db: None,
+            format: Some(format),
+        })
+    };
+    let state = || ApiState {
+        sql_handler: create_testing_sql_query_handler(MemTable::default_numbers_table()),
+        script_handler: None,
+    };
+
+    let response = http_handler::sql(
+        State(state()),
+        query(ResponseFormat::Csv),
+        axum::Extension(UserInfo::default()),
+        Form(http_handler::SqlQuery::default()),
+    )
+    .await;
+    assert_eq!("text/csv", response.headers()["content-type"]);
+    let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    assert_eq!("SUM(numbers.uint32s)\n4950\n", body);
+
+    let response = http_handler::sql(
+        State(state()),
+        query(ResponseFormat::NdJson),
+        axum::Extension(UserInfo::default()),
+        Form(http_handler::SqlQuery::default()),
+    )
+    .await;
+    assert_eq!("application/x-ndjson", response.headers()["content-type"]);
+    let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    assert_eq!("{\"SUM(numbers.uint32s)\":4950}\n", body);
+
+    let response = http_handler::sql(
+        State(state()),
+        query(ResponseFormat::Arrow),
+        axum::Extension(UserInfo::default()),
+        Form(http_handler::SqlQuery::default()),
+    )
+    .await;
+    assert_eq!(
+        "application/vnd.apache.arrow.stream",
+        response.headers()["content-type"]
+    );
+    let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    let reader = StreamReader::try_new(body.as_ref(), None).unwrap();
+    let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(1, batches.len());
+    assert_eq!(1, batches[0].num_rows());
+    assert_eq!("SUM(numbers.uint32s)", batches[0].schema().field(0).name());
+}
+
+async fn into_json_response(response: axum::response::Response) -> JsonResponse {
+    let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
 fn create_query() -> Query<http_handler::SqlQuery> {
     Query(http_handler::SqlQuery {
         sql: Some("select sum(uint32s) from numbers limit 20".to_string()),
         db: None,
+        format: None,
     })
 }
 
@@ -353,6 +426,7 @@ fn create_form() -> Form<http_handler::SqlQuery> {
     Form(http_handler::SqlQuery {
         sql: Some("select sum(uint32s) from numbers limit 20".to_string()),
         db: None,
+        format: None,
     })
 }
 
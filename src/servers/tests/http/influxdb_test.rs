@@ -20,7 +20,12 @@ use async_trait::async_trait;
 use axum::{http, Router};
 use axum_test_helper::TestClient;
 use common_query::Output;
+use common_recordbatch::RecordBatches;
 use common_test_util::ports;
+use datatypes::prelude::ConcreteDataType;
+use datatypes::schema::{ColumnSchema, Schema};
+use datatypes::vectors::{Float64Vector, StringVector, TimestampMillisecondVector};
+use query::influxql::InfluxqlStatement;
 use query::parser::PromQuery;
 use query::plan::LogicalPlan;
 use query::query_engine::DescribeResult;
@@ -62,6 +67,30 @@ impl InfluxdbLineProtocolHandler for DummyInstance {
 
         Ok(())
     }
+
+    async fn query(&self, stmt: InfluxqlStatement, ctx: QueryContextRef) -> Result<Output> {
+        let _ = self.tx.send((ctx.current_schema(), stmt.to_string())).await;
+
+        let schema = Arc::new(Schema::new(vec![
+            ColumnSchema::new("host", ConcreteDataType::string_datatype(), true),
+            ColumnSchema::new(
+                "time",
+                ConcreteDataType::timestamp_millisecond_datatype(),
+                false,
+            ),
+            ColumnSchema::new("cpu", ConcreteDataType::float64_datatype(), true),
+        ]));
+        let recordbatches = RecordBatches::try_from_columns(
+            schema,
+            vec![
+                Arc::new(StringVector::from(vec!["host1", "host2"])) as _,
+                Arc::new(TimestampMillisecondVector::from_vec(vec![1000, 2000])) as _,
+                Arc::new(Float64Vector::from_vec(vec![1.2, 0.5])) as _,
+            ],
+        )
+        .unwrap();
+        Ok(Output::RecordBatches(recordbatches))
+    }
 }
 
 #[async_trait]
@@ -212,3 +241,86 @@ async fn test_influxdb_write() {
         ]
     );
 }
+
+#[tokio::test]
+async fn test_influxdb_query() {
+    let (tx, mut rx) = mpsc::channel(100);
+    let app = make_test_app(Arc::new(tx), None);
+    let client = TestClient::new(app);
+
+    let result = client
+        .get("/v1/influxdb/query?db=public&q=SELECT%20cpu%20FROM%20monitor%20GROUP%20BY%20host")
+        .header(http::header::AUTHORIZATION, "token greptime:greptime")
+        .send()
+        .await;
+    assert_eq!(result.status(), 200);
+    let body: serde_json::Value = serde_json::from_str(&result.text().await).unwrap();
+    assert_eq!(
+        body,
+        serde_json::json!({
+            "results": [{
+                "statement_id": 0,
+                "series": [
+                    {
+                        "name": "monitor",
+                        "tags": {"host": "host1"},
+                        "columns": ["time", "cpu"],
+                        "values": [["1970-01-01T00:00:01Z", 1.2]]
+                    },
+                    {
+                        "name": "monitor",
+                        "tags": {"host": "host2"},
+                        "columns": ["time", "cpu"],
+                        "values": [["1970-01-01T00:00:02Z", 0.5]]
+                    }
+                ]
+            }]
+        })
+    );
+
+    // query sent as a form body, with epoch precision
+    let result = client
+        .post("/v1/influxdb/query?epoch=ms&u=greptime&p=greptime")
+        .header(
+            http::header::CONTENT_TYPE,
+            "application/x-www-form-urlencoded",
+        )
+        .body("db=public&q=SHOW+MEASUREMENTS%3B+SELECT+cpu+FROM+monitor")
+        .send()
+        .await;
+    assert_eq!(result.status(), 200);
+    let body: serde_json::Value = serde_json::from_str(&result.text().await).unwrap();
+    assert_eq!(body["results"].as_array().unwrap().len(), 2);
+    assert_eq!(body["results"][0]["series"][0]["name"], "measurements");
+    assert_eq!(
+        body["results"][1]["series"][0]["values"],
+        serde_json::json!([["host1", 1000, 1.2], ["host2", 2000, 0.5]])
+    );
+
+    // bad requests
+    let result = client
+        .get("/v1/influxdb/query?db=public")
+        .header(http::header::AUTHORIZATION, "token greptime:greptime")
+        .send()
+        .await;
+    assert_eq!(result.status(), 400);
+    let result = client
+        .get("/v1/influxdb/query?db=public&q=DROP%20MEASUREMENT%20monitor")
+        .header(http::header::AUTHORIZATION, "token greptime:greptime")
+        .send()
+        .await;
+    assert_eq!(result.status(), 400);
+
+    let mut queries = vec![];
+    while let Ok(s) = rx.try_recv() {
+        queries.push(s);
+    }
+    assert_eq!(
+        queries,
+        vec![
+            ("public".to_string(), "SELECT ... FROM monitor".to_string()),
+            ("public".to_string(), "SHOW MEASUREMENTS".to_string()),
+            ("public".to_string(), "SELECT ... FROM monitor".to_string()),
+        ]
+    );
+}
@@ -17,4 +17,5 @@ mod http_handler_test;
 mod http_test;
 mod influxdb_test;
 mod opentsdb_test;
+mod otlp_test;
 mod prom_store_test;
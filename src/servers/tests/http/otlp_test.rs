@@ -0,0 +1,178 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use api::v1::greptime_request::Request;
+use async_trait::async_trait;
+use axum::Router;
+use axum_test_helper::TestClient;
+use common_query::Output;
+use common_test_util::ports;
+use opentelemetry_proto::tonic::collector::metrics::v1::{
+    ExportMetricsServiceRequest, ExportMetricsServiceResponse,
+};
+use opentelemetry_proto::tonic::metrics::v1::{
+    metric, number_data_point, Gauge, Metric, NumberDataPoint, ResourceMetrics, ScopeMetrics,
+};
+use prost::Message;
+use query::parser::PromQuery;
+use query::plan::LogicalPlan;
+use query::query_engine::DescribeResult;
+use servers::error::{Error, Result};
+use servers::http::{HttpOptions, HttpServerBuilder};
+use servers::query_handler::grpc::GrpcQueryHandler;
+use servers::query_handler::sql::SqlQueryHandler;
+use servers::query_handler::OpenTelemetryProtocolHandler;
+use session::context::QueryContextRef;
+use tokio::sync::mpsc;
+
+struct DummyInstance {
+    tx: mpsc::Sender<(String, ExportMetricsServiceRequest)>,
+}
+
+#[async_trait]
+impl GrpcQueryHandler for DummyInstance {
+    type Error = Error;
+
+    async fn do_query(
+        &self,
+        _query: Request,
+        _ctx: QueryContextRef,
+    ) -> std::result::Result<Output, Self::Error> {
+        unimplemented!()
+    }
+}
+
+#[async_trait]
+impl OpenTelemetryProtocolHandler for DummyInstance {
+    async fn metrics(
+        &self,
+        request: ExportMetricsServiceRequest,
+        ctx: QueryContextRef,
+    ) -> Result<ExportMetricsServiceResponse> {
+        let _ = self.tx.send((ctx.current_schema(), request)).await;
+
+        Ok(ExportMetricsServiceResponse {
+            partial_success: None,
+        })
+    }
+}
+
+#[async_trait]
+impl SqlQueryHandler for DummyInstance {
+    type Error = Error;
+
+    async fn do_query(&self, _: &str, _: QueryContextRef) -> Vec<Result<Output>> {
+        unimplemented!()
+    }
+
+    async fn do_exec_plan(
+        &self,
+        _plan: LogicalPlan,
+        _query_ctx: QueryContextRef,
+    ) -> std::result::Result<Output, Self::Error> {
+        unimplemented!()
+    }
+
+    async fn do_promql_query(
+        &self,
+        _: &PromQuery,
+        _: QueryContextRef,
+    ) -> Vec<std::result::Result<Output, Self::Error>> {
+        unimplemented!()
+    }
+
+    async fn do_describe(
+        &self,
+        _stmt: sql::statements::statement::Statement,
+        _query_ctx: QueryContextRef,
+    ) -> Result<Option<DescribeResult>> {
+        unimplemented!()
+    }
+
+    async fn is_valid_schema(&self, _catalog: &str, _schema: &str) -> Result<bool> {
+        Ok(true)
+    }
+}
+
+fn make_test_app(tx: mpsc::Sender<(String, ExportMetricsServiceRequest)>) -> Router {
+    let http_opts = HttpOptions {
+        addr: format!("127.0.0.1:{}", ports::get_port()),
+        ..Default::default()
+    };
+
+    let instance = Arc::new(DummyInstance { tx });
+    let server = HttpServerBuilder::new(http_opts)
+        .with_grpc_handler(instance.clone())
+        .with_sql_handler(instance.clone())
+        .with_otlp_handler(instance)
+        .build();
+    server.build(server.make_app())
+}
+
+#[tokio::test]
+async fn test_otlp_metrics() {
+    let (tx, mut rx) = mpsc::channel(100);
+
+    let app = make_test_app(tx);
+    let client = TestClient::new(app);
+
+    let request = ExportMetricsServiceRequest {
+        resource_metrics: vec![ResourceMetrics {
+            scope_metrics: vec![ScopeMetrics {
+                metrics: vec![Metric {
+                    name: "system.cpu.usage".to_string(),
+                    data: Some(metric::Data::Gauge(Gauge {
+                        data_points: vec![NumberDataPoint {
+                            time_unix_nano: 1_000_000_000,
+                            value: Some(number_data_point::Value::AsDouble(0.5)),
+                            ..Default::default()
+                        }],
+                    })),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        }],
+    };
+
+    let result = client
+        .post("/v1/otlp/v1/metrics?db=otlp")
+        .body(request.encode_to_vec())
+        .send()
+        .await;
+    assert_eq!(result.status(), 200);
+    assert_eq!(
+        Some("application/x-protobuf"),
+        result
+            .headers()
+            .get("content-type")
+            .map(|x| x.to_str().unwrap())
+    );
+
+    let result = client
+        .post("/v1/otlp/v1/metrics")
+        .body("not a protobuf message")
+        .send()
+        .await;
+    assert_eq!(result.status(), 400);
+
+    let mut requests = vec![];
+    while let Ok(s) = rx.try_recv() {
+        requests.push(s);
+    }
+    assert_eq!(vec![("otlp".to_string(), request)], requests);
+}
@@ -18,9 +18,13 @@ use std::sync::Arc;
 use api::v1::greptime_request::Request;
 use api::v1::{InsertRequest, InsertRequests};
 use common_query::Output;
+use query::influxql::InfluxqlStatement;
 use query::parser::PromQuery;
 use servers::error::{self, InternalSnafu, NotSupportedSnafu, Result};
-use servers::interceptor::{GrpcQueryInterceptor, PromQueryInterceptor, SqlQueryInterceptor};
+use servers::interceptor::{
+    GrpcQueryInterceptor, InfluxqlQueryInterceptor, InfluxqlQueryInterceptorRef,
+    PromQueryInterceptor, SqlQueryInterceptor,
+};
 use session::context::{QueryContext, QueryContextRef};
 use snafu::ensure;
 
@@ -132,3 +136,42 @@ fn test_prom_interceptor() {
     assert!(two.is_ok());
     matches!(two.unwrap(), Output::AffectedRows(2));
 }
+
+impl InfluxqlQueryInterceptor for NoopInterceptor {
+    type Error = error::Error;
+
+    fn pre_execute(
+        &self,
+        statement: &InfluxqlStatement,
+        _query_ctx: QueryContextRef,
+    ) -> std::result::Result<(), Self::Error> {
+        match statement {
+            InfluxqlStatement::ShowMeasurements { .. } => InternalSnafu { err_msg: "test" }.fail(),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[test]
+fn test_influxql_interceptor() {
+    let di = NoopInterceptor;
+    let ctx = Arc::new(QueryContext::new());
+
+    let fail = InfluxqlQueryInterceptor::pre_execute(
+        &di,
+        &InfluxqlStatement::ShowMeasurements { limit: None },
+        ctx.clone(),
+    );
+    assert!(fail.is_err());
+    InfluxqlQueryInterceptor::pre_execute(
+        &di,
+        &InfluxqlStatement::ShowTagKeys { from: None },
+        ctx.clone(),
+    )
+    .unwrap();
+
+    // Nothing is intercepted without the interceptor.
+    let none: Option<InfluxqlQueryInterceptorRef<error::Error>> = None;
+    none.pre_execute(&InfluxqlStatement::ShowMeasurements { limit: None }, ctx)
+        .unwrap();
+}
@@ -37,6 +37,7 @@ use sql::statements::statement::Statement;
 use table::test_util::MemTable;
 
 mod auth;
+mod graphite;
 mod grpc;
 mod http;
 mod interceptor;
@@ -31,6 +31,7 @@ pub struct QueryContext {
     current_catalog: ArcSwap<String>,
     current_schema: ArcSwap<String>,
     time_zone: ArcSwap<Option<TimeZone>>,
+    current_user: ArcSwap<Option<UserInfo>>,
     sql_dialect: Box<dyn Dialect + Send + Sync>,
 }
 
@@ -61,6 +62,7 @@ impl QueryContext {
             current_catalog: ArcSwap::new(Arc::new(DEFAULT_CATALOG_NAME.to_string())),
             current_schema: ArcSwap::new(Arc::new(DEFAULT_SCHEMA_NAME.to_string())),
             time_zone: ArcSwap::new(Arc::new(None)),
+            current_user: ArcSwap::new(Arc::new(None)),
             sql_dialect: Box::new(GreptimeDbDialect {}),
         }
     }
@@ -78,6 +80,7 @@ impl QueryContext {
             current_catalog: ArcSwap::new(Arc::new(catalog.to_string())),
             current_schema: ArcSwap::new(Arc::new(schema.to_string())),
             time_zone: ArcSwap::new(Arc::new(None)),
+            current_user: ArcSwap::new(Arc::new(None)),
             sql_dialect,
         }
     }
@@ -132,6 +135,18 @@ impl QueryContext {
     pub fn set_time_zone(&self, tz: Option<TimeZone>) {
         let _ = self.time_zone.swap(Arc::new(tz));
     }
+
+    /// Returns the authenticated user issuing the query, or `None` if the
+    /// query is not issued by an authenticated user.
+    #[inline]
+    pub fn current_user(&self) -> Option<UserInfo> {
+        self.current_user.load().as_ref().clone()
+    }
+
+    #[inline]
+    pub fn set_current_user(&self, user: Option<UserInfo>) {
+        let _ = self.current_user.swap(Arc::new(user));
+    }
 }
 
 pub const DEFAULT_USERNAME: &str = "greptime";
@@ -225,8 +240,10 @@ mod test {
         let session = Session::new(Some("127.0.0.1:9000".parse().unwrap()), Channel::Mysql);
         // test user_info
         assert_eq!(session.user_info().username(), "greptime");
+        assert!(session.context().current_user().is_none());
         session.set_user_info(UserInfo::new("root"));
         assert_eq!(session.user_info().username(), "root");
+        assert_eq!(session.context().current_user().unwrap().username(), "root");
 
         // test channel
         assert_eq!(session.conn_info().channel, Channel::Mysql);
@@ -67,6 +67,7 @@ impl Session {
 
     #[inline]
     pub fn set_user_info(&self, user_info: UserInfo) {
+        self.query_ctx.set_current_user(Some(user_info.clone()));
         self.user_info.store(Arc::new(user_info));
     }
 }
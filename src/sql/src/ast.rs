@@ -14,6 +14,6 @@
 
 pub use sqlparser::ast::{
     visit_expressions_mut, BinaryOperator, ColumnDef, ColumnOption, ColumnOptionDef, DataType,
-    Expr, Function, FunctionArg, FunctionArgExpr, Ident, ObjectName, SqlOption, TableConstraint,
-    TimezoneInfo, Value, VisitMut, Visitor,
+    ExactNumberInfo, Expr, Function, FunctionArg, FunctionArgExpr, Ident, ObjectName, Query,
+    SqlOption, TableConstraint, TimezoneInfo, Value, Visit, VisitMut, Visitor,
 };
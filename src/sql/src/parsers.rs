@@ -20,3 +20,4 @@ pub(crate) mod insert_parser;
 pub(crate) mod query_parser;
 pub(crate) mod tql_parser;
 pub(crate) mod truncate_parser;
+pub(crate) mod user_parser;
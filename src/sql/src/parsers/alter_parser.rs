@@ -15,7 +15,7 @@
 use common_query::AddColumnLocation;
 use snafu::ResultExt;
 use sqlparser::keywords::Keyword;
-use sqlparser::parser::ParserError;
+use sqlparser::parser::{Parser, ParserError};
 use sqlparser::tokenizer::Token;
 
 use crate::error::{self, Result};
@@ -74,24 +74,58 @@ impl<'a> ParserContext<'a> {
                 )));
             }
         } else if parser.parse_keyword(Keyword::RENAME) {
-            let new_table_name_obj = parser.parse_object_name()?;
-            let new_table_name = match &new_table_name_obj.0[..] {
-                [table] => table.value.clone(),
-                _ => {
-                    return Err(ParserError::ParserError(format!(
-                        "expect table name, actual: {new_table_name_obj}"
-                    )))
+            if parser.parse_keyword(Keyword::COLUMN) {
+                let column_name = parser.parse_identifier()?;
+                parser.expect_keyword(Keyword::TO)?;
+                let new_column_name = parser.parse_identifier()?;
+                AlterTableOperation::RenameColumn {
+                    column_name,
+                    new_column_name,
                 }
-            };
-            AlterTableOperation::RenameTable { new_table_name }
+            } else {
+                let new_table_name_obj = parser.parse_object_name()?;
+                let new_table_name = match &new_table_name_obj.0[..] {
+                    [table] => table.value.clone(),
+                    _ => {
+                        return Err(ParserError::ParserError(format!(
+                            "expect table name, actual: {new_table_name_obj}"
+                        )))
+                    }
+                };
+                AlterTableOperation::RenameTable { new_table_name }
+            }
+        } else if parser.parse_keyword(Keyword::SET) {
+            // `parse_options` expects the leading keyword.
+            parser.prev_token();
+            let options = parser.parse_options(Keyword::SET)?;
+            AlterTableOperation::SetTableOptions { options }
+        } else if Self::parse_keyword_modify(parser) {
+            let _ = parser.parse_keyword(Keyword::COLUMN);
+            let column_name = parser.parse_identifier()?;
+            let target_type = parser.parse_data_type()?;
+            AlterTableOperation::ModifyColumnType {
+                column_name,
+                target_type,
+            }
         } else {
             return Err(ParserError::ParserError(format!(
-                "expect keyword ADD or DROP or RENAME after ALTER TABLE, found {}",
+                "expect keyword ADD, DROP, MODIFY, RENAME or SET after ALTER TABLE, found {}",
                 parser.peek_token()
             )));
         };
         Ok(AlterTable::new(table_name, alter_operation))
     }
+
+    /// Consumes the next token if it is `MODIFY`, which is not a keyword of sqlparser.
+    fn parse_keyword_modify(parser: &mut Parser) -> bool {
+        if let Token::Word(word) = parser.peek_token().token {
+            if word.value.to_ascii_uppercase() == "MODIFY" {
+                let _ = parser.next_token();
+                return true;
+            }
+        }
+        false
+    }
 }
 
 #[cfg(test)]
@@ -102,6 +136,7 @@ mod tests {
 
     use super::*;
     use crate::dialect::GreptimeDbDialect;
+    use crate::util::to_lowercase_options_map;
 
     #[test]
     fn test_parse_alter_add_column() {
@@ -247,7 +282,7 @@ mod tests {
         let result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap_err();
         assert!(result
             .to_string()
-            .contains("expect keyword ADD or DROP or RENAME after ALTER TABLE"));
+            .contains("expect keyword ADD, DROP, MODIFY, RENAME or SET after ALTER TABLE"));
 
         let sql = "ALTER TABLE test_table RENAME table_t";
         let mut result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
@@ -271,4 +306,96 @@ mod tests {
             _ => unreachable!(),
         }
     }
+
+    #[test]
+    fn test_parse_alter_set_table_options() {
+        let sql = "ALTER TABLE test_table SET ('ttl'='30d', write_buffer_size='2MB')";
+        let mut result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
+        assert_eq!(1, result.len());
+
+        let statement = result.remove(0);
+        match statement {
+            Statement::Alter(alter_table) => {
+                assert_eq!("test_table", alter_table.table_name().0[0].value);
+
+                match alter_table.alter_operation() {
+                    AlterTableOperation::SetTableOptions { options } => {
+                        let options = to_lowercase_options_map(options);
+                        assert_eq!(2, options.len());
+                        assert_eq!("30d", options["ttl"]);
+                        assert_eq!("2MB", options["write_buffer_size"]);
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            _ => unreachable!(),
+        }
+
+        let sql = "ALTER TABLE test_table SET ()";
+        assert!(ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).is_err());
+    }
+
+    #[test]
+    fn test_parse_alter_modify_column() {
+        for sql in [
+            "ALTER TABLE test_table MODIFY COLUMN a DOUBLE",
+            "ALTER TABLE test_table modify a DOUBLE",
+        ] {
+            let mut result =
+                ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
+            assert_eq!(1, result.len());
+
+            let statement = result.remove(0);
+            match statement {
+                Statement::Alter(alter_table) => {
+                    assert_eq!("test_table", alter_table.table_name().0[0].value);
+
+                    match alter_table.alter_operation() {
+                        AlterTableOperation::ModifyColumnType {
+                            column_name,
+                            target_type,
+                        } => {
+                            assert_eq!("a", column_name.value);
+                            assert_eq!(DataType::Double, *target_type);
+                        }
+                        _ => unreachable!(),
+                    }
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        let sql = "ALTER TABLE test_table MODIFY COLUMN a";
+        assert!(ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).is_err());
+    }
+
+    #[test]
+    fn test_parse_alter_rename_column() {
+        let sql = "ALTER TABLE test_table RENAME COLUMN a TO b";
+        let mut result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
+        assert_eq!(1, result.len());
+
+        let statement = result.remove(0);
+        match statement {
+            Statement::Alter(alter_table) => {
+                assert_eq!("test_table", alter_table.table_name().0[0].value);
+
+                match alter_table.alter_operation() {
+                    AlterTableOperation::RenameColumn {
+                        column_name,
+                        new_column_name,
+                    } => {
+                        assert_eq!("a", column_name.value);
+                        assert_eq!("b", new_column_name.value);
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            _ => unreachable!(),
+        }
+
+        let sql = "ALTER TABLE test_table RENAME COLUMN a b";
+        let result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap_err();
+        assert!(result.to_string().contains("Expected TO"));
+    }
 }
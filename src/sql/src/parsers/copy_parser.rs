@@ -21,7 +21,7 @@ use sqlparser::tokenizer::Token::Word;
 
 use crate::error::{self, Result};
 use crate::parser::ParserContext;
-use crate::statements::copy::{CopyDatabaseArgument, CopyTable, CopyTableArgument};
+use crate::statements::copy::{CopyDatabase, CopyDatabaseArgument, CopyTable, CopyTableArgument};
 use crate::statements::statement::Statement;
 use crate::util::parse_option_string;
 
@@ -45,7 +45,7 @@ impl<'a> ParserContext<'a> {
         Ok(Statement::Copy(copy))
     }
 
-    fn parser_copy_database(&mut self) -> Result<CopyDatabaseArgument> {
+    fn parser_copy_database(&mut self) -> Result<CopyDatabase> {
         let database_name =
             self.parser
                 .parse_object_name()
@@ -55,17 +55,26 @@ impl<'a> ParserContext<'a> {
                     actual: self.peek_token_as_string(),
                 })?;
 
-        self.parser
-            .expect_keyword(Keyword::TO)
-            .context(error::SyntaxSnafu { sql: self.sql })?;
-
-        let (with, connection, location) = self.parse_copy_to()?;
-        Ok(CopyDatabaseArgument {
-            database_name,
-            with,
-            connection,
-            location,
-        })
+        if self.parser.parse_keyword(Keyword::TO) {
+            let (with, connection, location) = self.parse_copy_to()?;
+            Ok(CopyDatabase::To(CopyDatabaseArgument {
+                database_name,
+                with,
+                connection,
+                location,
+            }))
+        } else {
+            self.parser
+                .expect_keyword(Keyword::FROM)
+                .context(error::SyntaxSnafu { sql: self.sql })?;
+            let (with, connection, location) = self.parse_copy_to()?;
+            Ok(CopyDatabase::From(CopyDatabaseArgument {
+                database_name,
+                with,
+                connection,
+                location,
+            }))
+        }
     }
 
     fn parse_copy_table(&mut self) -> Result<CopyTable> {
@@ -360,7 +369,7 @@ mod tests {
             .pop()
             .unwrap();
 
-        let Statement::Copy(crate::statements::copy::Copy::CopyDatabase(stmt)) = stmt else { unreachable!() };
+        let Statement::Copy(crate::statements::copy::Copy::CopyDatabase(CopyDatabase::To(stmt))) = stmt else { unreachable!() };
         assert_eq!(
             ObjectName(vec![Ident::new("catalog0"), Ident::new("schema0")]),
             stmt.database_name
@@ -382,4 +391,30 @@ mod tests {
             stmt.connection
         );
     }
+
+    #[test]
+    fn test_copy_database_from() {
+        let sql = "COPY DATABASE catalog0.schema0 FROM '/tmp/backup/' WITH (FORMAT = 'parquet')";
+        let stmt = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {})
+            .unwrap()
+            .pop()
+            .unwrap();
+
+        let Statement::Copy(crate::statements::copy::Copy::CopyDatabase(CopyDatabase::From(stmt))) = stmt else { unreachable!() };
+        assert_eq!(
+            ObjectName(vec![Ident::new("catalog0"), Ident::new("schema0")]),
+            stmt.database_name
+        );
+        assert_eq!("/tmp/backup/", stmt.location);
+        assert_eq!(
+            [("format".to_string(), "parquet".to_string())]
+                .into_iter()
+                .collect::<HashMap<_, _>>(),
+            stmt.with
+        );
+        assert!(stmt.connection.is_empty());
+
+        let sql = "COPY DATABASE catalog0.schema0 INTO '/tmp/backup/'";
+        assert!(ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).is_err());
+    }
 }
@@ -30,8 +30,10 @@ use crate::error::{
     SyntaxSnafu,
 };
 use crate::parser::ParserContext;
+use crate::parsers::user_parser;
 use crate::statements::create::{
-    CreateDatabase, CreateExternalTable, CreateTable, PartitionEntry, Partitions, TIME_INDEX,
+    CreateDatabase, CreateExternalTable, CreateTable, PartitionEntry, PartitionMethod, Partitions,
+    TIME_INDEX,
 };
 use crate::statements::statement::Statement;
 use crate::statements::{sql_data_type_to_concrete_data_type, sql_value_to_value};
@@ -39,6 +41,8 @@ use crate::util::parse_option_string;
 
 const ENGINE: &str = "ENGINE";
 const MAXVALUE: &str = "MAXVALUE";
+const HASH: &str = "HASH";
+const PARTITIONS: &str = "PARTITIONS";
 
 static LESS: Lazy<Token> = Lazy::new(|| Token::make_keyword("LESS"));
 static THAN: Lazy<Token> = Lazy::new(|| Token::make_keyword("THAN"));
@@ -54,6 +58,8 @@ impl<'a> ParserContext<'a> {
 
                 Keyword::EXTERNAL => self.parse_create_external_table(),
 
+                _ if w.value.eq_ignore_ascii_case(user_parser::USER) => self.parse_create_user(),
+
                 _ => self.unsupported(w.to_string()),
             },
             unexpected => self.unsupported(unexpected.to_string()),
@@ -166,15 +172,28 @@ impl<'a> ParserContext<'a> {
 
     // "PARTITION BY ..." syntax:
     // https://dev.mysql.com/doc/refman/8.0/en/partitioning-columns-range.html
+    // https://dev.mysql.com/doc/refman/8.0/en/partitioning-hash.html
     fn parse_partitions(&mut self) -> Result<Option<Partitions>> {
         if !self.parser.parse_keyword(Keyword::PARTITION) {
             return Ok(None);
         }
         self.parser
-            .expect_keywords(&[Keyword::BY, Keyword::RANGE, Keyword::COLUMNS])
+            .expect_keyword(Keyword::BY)
+            .context(error::UnexpectedSnafu {
+                sql: self.sql,
+                expected: "BY",
+                actual: self.peek_token_as_string(),
+            })?;
+
+        if self.consume_token(HASH) {
+            return self.parse_hash_partitions().map(Some);
+        }
+
+        self.parser
+            .expect_keywords(&[Keyword::RANGE, Keyword::COLUMNS])
             .context(error::UnexpectedSnafu {
                 sql: self.sql,
-                expected: "BY, RANGE, COLUMNS",
+                expected: "RANGE, COLUMNS",
                 actual: self.peek_token_as_string(),
             })?;
 
@@ -186,11 +205,41 @@ impl<'a> ParserContext<'a> {
         let entries = self.parse_comma_separated(Self::parse_partition_entry)?;
 
         Ok(Some(Partitions {
+            method: PartitionMethod::RangeColumns,
             column_list,
             entries,
         }))
     }
 
+    /// Parses the rest of "PARTITION BY HASH (column) PARTITIONS num".
+    fn parse_hash_partitions(&mut self) -> Result<Partitions> {
+        let column_list = self
+            .parser
+            .parse_parenthesized_column_list(Mandatory, false)
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+
+        if !self.consume_token(PARTITIONS) {
+            return self.expected("PARTITIONS", self.parser.peek_token());
+        }
+        let partitions = self
+            .parser
+            .parse_literal_uint()
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+        let partitions =
+            u32::try_from(partitions)
+                .ok()
+                .filter(|n| *n > 0)
+                .context(error::InvalidSqlSnafu {
+                    msg: format!("Invalid number of hash partitions: {partitions}"),
+                })?;
+
+        Ok(Partitions {
+            method: PartitionMethod::Hash { partitions },
+            column_list,
+            entries: vec![],
+        })
+    }
+
     fn parse_partition_entry(&mut self) -> Result<PartitionEntry> {
         self.parser
             .expect_keyword(Keyword::PARTITION)
@@ -632,6 +681,16 @@ fn validate_time_index(create_table: &CreateTable) -> Result<()> {
 fn validate_partitions(columns: &[ColumnDef], partitions: &Partitions) -> Result<()> {
     let partition_columns = ensure_partition_columns_defined(columns, partitions)?;
 
+    if let PartitionMethod::Hash { .. } = partitions.method {
+        ensure!(
+            partition_columns.len() == 1,
+            error::InvalidSqlSnafu {
+                msg: "Only one column is allowed in 'PARTITION BY HASH'.",
+            }
+        );
+        return Ok(());
+    }
+
     ensure_partition_names_no_duplicate(partitions)?;
 
     ensure_value_list_len_matches_columns(partitions, &partition_columns)?;
@@ -737,7 +796,7 @@ fn ensure_value_list_len_matches_columns(
     Ok(())
 }
 
-/// Ensure that all columns used in "PARTITION BY RANGE COLUMNS" are defined in create table.
+/// Ensure that all columns used in "PARTITION BY" are defined in create table.
 fn ensure_partition_columns_defined<'a>(
     columns: &'a [ColumnDef],
     partitions: &'a Partitions,
@@ -1330,6 +1389,71 @@ ENGINE=mito";
         }
     }
 
+    #[test]
+    fn test_parse_create_table_with_hash_partitions() {
+        let sql = r"
+CREATE TABLE monitor (
+  host_id    INT,
+  ts         TIMESTAMP,
+  cpu        DOUBLE DEFAULT 0,
+  TIME INDEX (ts),
+)
+PARTITION BY HASH (host_id) PARTITIONS 8
+ENGINE=mito";
+        let result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
+        assert_eq!(result.len(), 1);
+        match &result[0] {
+            Statement::CreateTable(c) => {
+                let partitions = c.partitions.as_ref().unwrap();
+                assert_eq!(partitions.method, PartitionMethod::Hash { partitions: 8 });
+                assert_eq!(partitions.column_list, vec![Ident::new("host_id")]);
+                assert!(partitions.entries.is_empty());
+                assert_eq!(c.engine, "mito");
+            }
+            _ => unreachable!(),
+        }
+
+        let sql = r"
+CREATE TABLE monitor ( host_id INT, idc STRING, ts TIMESTAMP, TIME INDEX (ts) )
+PARTITION BY HASH (host_id, idc) PARTITIONS 8
+ENGINE=mito";
+        let result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {});
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Only one column is allowed in 'PARTITION BY HASH'."));
+
+        let sql = r"
+CREATE TABLE monitor ( host_id INT, ts TIMESTAMP, TIME INDEX (ts) )
+PARTITION BY HASH (host) PARTITIONS 8
+ENGINE=mito";
+        let result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {});
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Partition column \"host\" not defined!"));
+
+        let sql = r"
+CREATE TABLE monitor ( host_id INT, ts TIMESTAMP, TIME INDEX (ts) )
+PARTITION BY HASH (host_id) PARTITIONS 0
+ENGINE=mito";
+        let result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {});
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Invalid number of hash partitions: 0"));
+
+        let sql = r"
+CREATE TABLE monitor ( host_id INT, ts TIMESTAMP, TIME INDEX (ts) )
+PARTITION BY HASH (host_id) 8
+ENGINE=mito";
+        let result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {});
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Expected PARTITIONS, found: 8"));
+    }
+
     #[test]
     fn test_parse_partitions_with_error_syntax() {
         let sql = r"
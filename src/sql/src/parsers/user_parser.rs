@@ -0,0 +1,264 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snafu::ResultExt;
+use sqlparser::ast::Ident;
+use sqlparser::keywords::Keyword;
+use sqlparser::tokenizer::Token;
+
+use crate::error::{self, Result};
+use crate::parser::ParserContext;
+use crate::statements::statement::Statement;
+use crate::statements::user::{CreateUser, GrantObject, Grantee, Privilege};
+
+pub(crate) const USER: &str = "USER";
+const ROLE: &str = "ROLE";
+const IDENTIFIED: &str = "IDENTIFIED";
+
+impl<'a> ParserContext<'a> {
+    /// Parses `CREATE USER [IF NOT EXISTS] <name> IDENTIFIED BY '<password>'`.
+    pub(crate) fn parse_create_user(&mut self) -> Result<Statement> {
+        let _ = self.parser.next_token();
+        let if_not_exists =
+            self.parser
+                .parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
+        let name = self.parse_user_management_ident("a user name")?;
+
+        if !self.consume_token(IDENTIFIED) {
+            return self.expected("IDENTIFIED BY", self.parser.peek_token());
+        }
+        self.parser
+            .expect_keyword(Keyword::BY)
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+        let password =
+            self.parser
+                .parse_literal_string()
+                .with_context(|_| error::UnexpectedSnafu {
+                    sql: self.sql,
+                    expected: "a password string",
+                    actual: self.peek_token_as_string(),
+                })?;
+
+        Ok(Statement::CreateUser(CreateUser {
+            name,
+            password,
+            if_not_exists,
+        }))
+    }
+
+    pub(crate) fn parse_grant(&mut self) -> Result<Statement> {
+        let _ = self.parser.next_token();
+        self.parse_grant_object(Keyword::TO).map(Statement::Grant)
+    }
+
+    pub(crate) fn parse_revoke(&mut self) -> Result<Statement> {
+        let _ = self.parser.next_token();
+        self.parse_grant_object(Keyword::FROM)
+            .map(Statement::Revoke)
+    }
+
+    /// Parses the object of `GRANT`/`REVOKE`, `preposition` is `TO` for `GRANT`
+    /// and `FROM` for `REVOKE`.
+    fn parse_grant_object(&mut self, preposition: Keyword) -> Result<GrantObject> {
+        if self.consume_token(ROLE) {
+            let role = self.parse_user_management_ident("a role name")?;
+            self.parser
+                .expect_keyword(preposition)
+                .context(error::SyntaxSnafu { sql: self.sql })?;
+            let user = self.parse_user_management_ident("a user name")?;
+            return Ok(GrantObject::Role { role, user });
+        }
+
+        let privileges = self.parse_privileges()?;
+        self.parser
+            .expect_keyword(Keyword::ON)
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+        let database = if self.parser.consume_token(&Token::Mul) {
+            None
+        } else {
+            Some(
+                self.parser
+                    .parse_object_name()
+                    .with_context(|_| error::UnexpectedSnafu {
+                        sql: self.sql,
+                        expected: "a database name or *",
+                        actual: self.peek_token_as_string(),
+                    })?,
+            )
+        };
+        self.parser
+            .expect_keyword(preposition)
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+        let grantee = if self.consume_token(ROLE) {
+            Grantee::Role(self.parse_user_management_ident("a role name")?)
+        } else {
+            Grantee::User(self.parse_user_management_ident("a user name")?)
+        };
+
+        Ok(GrantObject::Privileges {
+            privileges,
+            database,
+            grantee,
+        })
+    }
+
+    /// Parses a comma separated list of `READ`, `WRITE` and `ALL [PRIVILEGES]`.
+    fn parse_privileges(&mut self) -> Result<Vec<Privilege>> {
+        let mut privileges = vec![];
+        loop {
+            if self.consume_token("READ") {
+                privileges.push(Privilege::Read);
+            } else if self.consume_token("WRITE") {
+                privileges.push(Privilege::Write);
+            } else if self.consume_token("ALL") {
+                let _ = self.consume_token("PRIVILEGES");
+                privileges.extend([Privilege::Read, Privilege::Write]);
+            } else {
+                return self.expected("READ, WRITE or ALL", self.parser.peek_token());
+            }
+
+            if !self.parser.consume_token(&Token::Comma) {
+                break;
+            }
+        }
+        privileges.dedup();
+        Ok(privileges)
+    }
+
+    fn parse_user_management_ident(&mut self, expected: &str) -> Result<String> {
+        self.parser
+            .parse_identifier()
+            .map(|Ident { value, .. }| value)
+            .with_context(|_| error::UnexpectedSnafu {
+                sql: self.sql,
+                expected,
+                actual: self.peek_token_as_string(),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlparser::ast::ObjectName;
+
+    use super::*;
+    use crate::dialect::GreptimeDbDialect;
+
+    fn parse(sql: &str) -> Statement {
+        let mut stmts = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
+        assert_eq!(1, stmts.len());
+        stmts.pop().unwrap()
+    }
+
+    #[test]
+    fn test_parse_create_user() {
+        assert_eq!(
+            parse("CREATE USER alice IDENTIFIED BY 'secret'"),
+            Statement::CreateUser(CreateUser {
+                name: "alice".to_string(),
+                password: "secret".to_string(),
+                if_not_exists: false,
+            })
+        );
+        assert_eq!(
+            parse("create user if not exists bob identified by 'pwd'"),
+            Statement::CreateUser(CreateUser {
+                name: "bob".to_string(),
+                password: "pwd".to_string(),
+                if_not_exists: true,
+            })
+        );
+
+        let result = ParserContext::create_with_dialect("CREATE USER alice", &GreptimeDbDialect {});
+        assert!(result.is_err(), "result is: {result:?}");
+        let result = ParserContext::create_with_dialect(
+            "CREATE USER alice IDENTIFIED BY secret",
+            &GreptimeDbDialect {},
+        );
+        assert!(result.is_err(), "result is: {result:?}");
+    }
+
+    #[test]
+    fn test_parse_grant() {
+        assert_eq!(
+            parse("GRANT READ ON public TO alice"),
+            Statement::Grant(GrantObject::Privileges {
+                privileges: vec![Privilege::Read],
+                database: Some(ObjectName(vec![Ident::new("public")])),
+                grantee: Grantee::User("alice".to_string()),
+            })
+        );
+        assert_eq!(
+            parse("GRANT READ, WRITE ON greptime.public TO ROLE writer"),
+            Statement::Grant(GrantObject::Privileges {
+                privileges: vec![Privilege::Read, Privilege::Write],
+                database: Some(ObjectName(vec![
+                    Ident::new("greptime"),
+                    Ident::new("public")
+                ])),
+                grantee: Grantee::Role("writer".to_string()),
+            })
+        );
+        assert_eq!(
+            parse("GRANT ALL PRIVILEGES ON * TO admin"),
+            Statement::Grant(GrantObject::Privileges {
+                privileges: vec![Privilege::Read, Privilege::Write],
+                database: None,
+                grantee: Grantee::User("admin".to_string()),
+            })
+        );
+        assert_eq!(
+            parse("GRANT ROLE analyst TO alice"),
+            Statement::Grant(GrantObject::Role {
+                role: "analyst".to_string(),
+                user: "alice".to_string(),
+            })
+        );
+
+        for sql in [
+            "GRANT DELETE ON public TO alice",
+            "GRANT READ public TO alice",
+            "GRANT READ ON public FROM alice",
+            "GRANT ROLE analyst",
+        ] {
+            let result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {});
+            assert!(result.is_err(), "sql: {sql}, result is: {result:?}");
+        }
+    }
+
+    #[test]
+    fn test_parse_revoke() {
+        assert_eq!(
+            parse("REVOKE WRITE ON public FROM ROLE writer"),
+            Statement::Revoke(GrantObject::Privileges {
+                privileges: vec![Privilege::Write],
+                database: Some(ObjectName(vec![Ident::new("public")])),
+                grantee: Grantee::Role("writer".to_string()),
+            })
+        );
+        assert_eq!(
+            parse("REVOKE ROLE analyst FROM alice"),
+            Statement::Revoke(GrantObject::Role {
+                role: "analyst".to_string(),
+                user: "alice".to_string(),
+            })
+        );
+
+        let result = ParserContext::create_with_dialect(
+            "REVOKE READ ON public TO alice",
+            &GreptimeDbDialect {},
+        );
+        assert!(result.is_err(), "result is: {result:?}");
+    }
+}
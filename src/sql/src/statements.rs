@@ -25,6 +25,7 @@ pub mod show;
 pub mod statement;
 pub mod tql;
 pub mod truncate;
+pub mod user;
 
 use std::str::FromStr;
 
@@ -34,6 +35,7 @@ use api::v1::add_column::Location;
 use common_base::bytes::Bytes;
 use common_query::AddColumnLocation;
 use common_time::Timestamp;
+use datatypes::decimal::{self, Decimal128, DECIMAL128_MAX_PRECISION, DECIMAL_DEFAULT_SCALE};
 use datatypes::prelude::ConcreteDataType;
 use datatypes::schema::{ColumnDefaultConstraint, ColumnSchema, COMMENT_KEY};
 use datatypes::types::TimestampType;
@@ -41,8 +43,8 @@ use datatypes::value::Value;
 use snafu::{ensure, OptionExt, ResultExt};
 
 use crate::ast::{
-    ColumnDef, ColumnOption, ColumnOptionDef, DataType as SqlDataType, Expr, TimezoneInfo,
-    Value as SqlValue,
+    ColumnDef, ColumnOption, ColumnOptionDef, DataType as SqlDataType, ExactNumberInfo, Expr,
+    TimezoneInfo, Value as SqlValue,
 };
 use crate::error::{
     self, ColumnTypeMismatchSnafu, ConvertSqlValueSnafu, ConvertToGrpcDataTypeSnafu,
@@ -146,6 +148,17 @@ macro_rules! parse_number_to_value {
 
 /// Convert a sql value into datatype's value
 pub fn sql_number_to_value(data_type: &ConcreteDataType, n: &str) -> Result<Value> {
+    if let ConcreteDataType::Decimal128(t) = data_type {
+        return Decimal128::from_str_with(n, t.precision(), t.scale())
+            .map(Value::Decimal128)
+            .map_err(|e| {
+                ParseSqlValueSnafu {
+                    msg: format!("Fail to parse number {n} to {data_type}, {e}"),
+                }
+                .build()
+            });
+    }
+
     parse_number_to_value!(
         data_type,
         n,
@@ -226,6 +239,7 @@ pub fn value_to_sql_value(val: &Value) -> Result<SqlValue> {
         Value::UInt64(v) => SqlValue::Number(v.to_string(), false),
         Value::Float32(v) => SqlValue::Number(v.to_string(), false),
         Value::Float64(v) => SqlValue::Number(v.to_string(), false),
+        Value::Decimal128(v) => SqlValue::Number(v.to_string(), false),
         Value::Boolean(b) => SqlValue::Boolean(*b),
         Value::Date(d) => SqlValue::SingleQuotedString(d.to_string()),
         Value::DateTime(d) => SqlValue::SingleQuotedString(d.to_string()),
@@ -357,6 +371,26 @@ pub fn sql_data_type_to_concrete_data_type(data_type: &SqlDataType) -> Result<Co
             Ok(ConcreteDataType::binary_datatype())
         }
         SqlDataType::Datetime(_) => Ok(ConcreteDataType::datetime_datatype()),
+        SqlDataType::Decimal(info) | SqlDataType::Numeric(info) => {
+            let (precision, scale) = match info {
+                ExactNumberInfo::None => (
+                    DECIMAL128_MAX_PRECISION as u64,
+                    DECIMAL_DEFAULT_SCALE as u64,
+                ),
+                ExactNumberInfo::Precision(precision) => (*precision, 0),
+                ExactNumberInfo::PrecisionAndScale(precision, scale) => (*precision, *scale),
+            };
+            let not_supported = || {
+                error::SqlTypeNotSupportedSnafu {
+                    t: data_type.clone(),
+                }
+                .build()
+            };
+            let precision = u8::try_from(precision).map_err(|_| not_supported())?;
+            let scale = i8::try_from(scale).map_err(|_| not_supported())?;
+            decimal::validate_precision_scale(precision, scale).map_err(|_| not_supported())?;
+            Ok(ConcreteDataType::decimal128_datatype(precision, scale))
+        }
         SqlDataType::Timestamp(precision, _) => Ok(precision
             .as_ref()
             .map(|v| TimestampType::try_from(*v))
@@ -397,6 +431,9 @@ pub fn concrete_data_type_to_sql_data_type(data_type: &ConcreteDataType) -> Resu
             TimezoneInfo::None,
         )),
         ConcreteDataType::Binary(_) => Ok(SqlDataType::Varbinary(None)),
+        ConcreteDataType::Decimal128(t) => Ok(SqlDataType::Decimal(
+            ExactNumberInfo::PrecisionAndScale(t.precision() as u64, t.scale().max(0) as u64),
+        )),
         ConcreteDataType::Null(_) | ConcreteDataType::List(_) | ConcreteDataType::Dictionary(_) => {
             unreachable!()
         }
@@ -495,7 +532,27 @@ mod tests {
         check_type(
             SqlDataType::Datetime(None),
             ConcreteDataType::datetime_datatype(),
-        )
+        );
+        check_type(
+            SqlDataType::Decimal(ExactNumberInfo::PrecisionAndScale(10, 2)),
+            ConcreteDataType::decimal128_datatype(10, 2),
+        );
+        check_type(
+            SqlDataType::Numeric(ExactNumberInfo::Precision(5)),
+            ConcreteDataType::decimal128_datatype(5, 0),
+        );
+        check_type(
+            SqlDataType::Decimal(ExactNumberInfo::None),
+            ConcreteDataType::decimal128_datatype(38, 10),
+        );
+        assert!(sql_data_type_to_concrete_data_type(&SqlDataType::Decimal(
+            ExactNumberInfo::PrecisionAndScale(39, 2)
+        ))
+        .is_err());
+        assert!(sql_data_type_to_concrete_data_type(&SqlDataType::Decimal(
+            ExactNumberInfo::PrecisionAndScale(5, 6)
+        ))
+        .is_err());
     }
 
     #[test]
@@ -508,6 +565,12 @@ mod tests {
 
         let v = sql_number_to_value(&ConcreteDataType::string_datatype(), "999");
         assert!(v.is_err(), "parse value error is: {v:?}");
+
+        let decimal_type = ConcreteDataType::decimal128_datatype(5, 2);
+        let v = sql_number_to_value(&decimal_type, "123.45").unwrap();
+        assert_eq!(Value::Decimal128(Decimal128::new(12345, 5, 2)), v);
+        let v = sql_number_to_value(&decimal_type, "1234.5");
+        assert!(v.is_err(), "parse value error is: {v:?}");
     }
 
     #[test]
@@ -736,6 +799,18 @@ mod tests {
 
         let grpc_column_def = sql_column_def_to_grpc_column_def(&column_def).unwrap();
         assert!(!grpc_column_def.is_nullable);
+
+        // gRPC column definitions are not able to carry decimals yet.
+        let column_def = ColumnDef {
+            name: "col".into(),
+            data_type: SqlDataType::Decimal(ExactNumberInfo::PrecisionAndScale(10, 2)),
+            collation: None,
+            options: vec![],
+        };
+        assert_matches!(
+            sql_column_def_to_grpc_column_def(&column_def),
+            Err(error::Error::ConvertToGrpcDataType { .. })
+        );
     }
 
     #[test]
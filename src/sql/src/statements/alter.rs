@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use common_query::AddColumnLocation;
-use sqlparser::ast::{ColumnDef, Ident, ObjectName, TableConstraint};
+use sqlparser::ast::{ColumnDef, DataType, Ident, ObjectName, SqlOption, TableConstraint};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AlterTable {
@@ -51,4 +51,16 @@ pub enum AlterTableOperation {
     DropColumn { name: Ident },
     /// `RENAME <new_table_name>`
     RenameTable { new_table_name: String },
+    /// `SET (<option> = <value> [, ...])`
+    SetTableOptions { options: Vec<SqlOption> },
+    /// `MODIFY [ COLUMN ] <column_name> <target_type>`
+    ModifyColumnType {
+        column_name: Ident,
+        target_type: DataType,
+    },
+    /// `RENAME COLUMN <column_name> TO <new_column_name>`
+    RenameColumn {
+        column_name: Ident,
+        new_column_name: Ident,
+    },
 }
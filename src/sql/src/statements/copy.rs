@@ -19,7 +19,7 @@ use sqlparser::ast::ObjectName;
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Copy {
     CopyTable(CopyTable),
-    CopyDatabase(CopyDatabaseArgument),
+    CopyDatabase(CopyDatabase),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -28,11 +28,18 @@ pub enum CopyTable {
     From(CopyTableArgument),
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CopyDatabase {
+    To(CopyDatabaseArgument),
+    From(CopyDatabaseArgument),
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CopyDatabaseArgument {
     pub database_name: ObjectName,
     pub with: HashMap<String, String>,
     pub connection: HashMap<String, String>,
+    /// Copy database [To|From] 'location'.
     pub location: String,
 }
 
@@ -124,10 +124,20 @@ impl CreateTable {
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Partitions {
+    pub method: PartitionMethod,
     pub column_list: Vec<Ident>,
+    /// Partition entries of "RANGE COLUMNS" partitioning, always empty for "HASH" partitioning.
     pub entries: Vec<PartitionEntry>,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum PartitionMethod {
+    /// `PARTITION BY RANGE COLUMNS (columns) (PARTITION ... VALUES LESS THAN (...), ...)`
+    RangeColumns,
+    /// `PARTITION BY HASH (column) PARTITIONS num`
+    Hash { partitions: u32 },
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct PartitionEntry {
     pub name: Ident,
@@ -147,14 +157,22 @@ impl Display for PartitionEntry {
 
 impl Display for Partitions {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            r#"PARTITION BY RANGE COLUMNS ({}) (
+        match self.method {
+            PartitionMethod::RangeColumns => write!(
+                f,
+                r#"PARTITION BY RANGE COLUMNS ({}) (
 {}
 )"#,
-            format_list_comma!(self.column_list),
-            format_list_indent!(self.entries),
-        )
+                format_list_comma!(self.column_list),
+                format_list_indent!(self.entries),
+            ),
+            PartitionMethod::Hash { partitions } => write!(
+                f,
+                "PARTITION BY HASH ({}) PARTITIONS {}",
+                format_list_comma!(self.column_list),
+                partitions,
+            ),
+        }
     }
 }
 
@@ -264,4 +282,31 @@ WITH(
             _ => unreachable!(),
         }
     }
+
+    #[test]
+    fn test_display_create_table_with_hash_partitions() {
+        let sql = r"create table demo(
+                             host_id int,
+                             ts timestamp,
+                             cpu double,
+                             TIME INDEX (ts),
+                       )
+                       PARTITION BY HASH (host_id) PARTITIONS 4
+                       engine=mito;
+         ";
+        let result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
+        assert_eq!(1, result.len());
+
+        match &result[0] {
+            Statement::CreateTable(c) => {
+                let new_sql = format!("\n{}", c);
+                assert!(new_sql.contains("PARTITION BY HASH (host_id) PARTITIONS 4\nENGINE=mito"));
+
+                let new_result =
+                    ParserContext::create_with_dialect(&new_sql, &GreptimeDbDialect {}).unwrap();
+                assert_eq!(result, new_result);
+            }
+            _ => unreachable!(),
+        }
+    }
 }
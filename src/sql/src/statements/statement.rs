@@ -27,6 +27,7 @@ use crate::statements::query::Query;
 use crate::statements::show::{ShowCreateTable, ShowDatabases, ShowTables};
 use crate::statements::tql::Tql;
 use crate::statements::truncate::TruncateTable;
+use crate::statements::user::{CreateUser, GrantObject};
 
 /// Tokens parsed by `DFParser` are converted into these values.
 #[allow(clippy::large_enum_variant)]
@@ -64,6 +65,12 @@ pub enum Statement {
     Tql(Tql),
     // TRUNCATE TABLE
     TruncateTable(TruncateTable),
+    // CREATE USER
+    CreateUser(CreateUser),
+    // GRANT
+    Grant(GrantObject),
+    // REVOKE
+    Revoke(GrantObject),
 }
 
 /// Comment hints from SQL.
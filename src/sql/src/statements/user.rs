@@ -0,0 +1,81 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use sqlparser::ast::ObjectName;
+
+/// Privileges that can be granted on a database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Privilege {
+    Read,
+    Write,
+}
+
+/// The user or role that privileges are granted to or revoked from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Grantee {
+    User(String),
+    Role(String),
+}
+
+/// `CREATE USER [IF NOT EXISTS] <name> IDENTIFIED BY '<password>'`
+#[derive(Clone, PartialEq, Eq)]
+pub struct CreateUser {
+    pub name: String,
+    pub password: String,
+    pub if_not_exists: bool,
+}
+
+/// Redacts the password, statements may be logged or put into error messages.
+impl fmt::Debug for CreateUser {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CreateUser")
+            .field("name", &self.name)
+            .field("password", &"******")
+            .field("if_not_exists", &self.if_not_exists)
+            .finish()
+    }
+}
+
+/// The object of `GRANT` and `REVOKE` statements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GrantObject {
+    /// `<privileges> ON <database> TO|FROM [ROLE] <grantee>`, the database is
+    /// `None` for `*` which means all databases.
+    Privileges {
+        privileges: Vec<Privilege>,
+        database: Option<ObjectName>,
+        grantee: Grantee,
+    },
+    /// `ROLE <role> TO|FROM <user>`
+    Role { role: String, user: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_user_debug_redacts_password() {
+        let create_user = CreateUser {
+            name: "alice".to_string(),
+            password: "654321".to_string(),
+            if_not_exists: false,
+        };
+        let debug = format!("{create_user:?}");
+        assert!(debug.contains("alice"));
+        assert!(!debug.contains("654321"));
+    }
+}
@@ -20,7 +20,7 @@ use common_recordbatch::OrderOption;
 use common_telemetry::logging;
 use common_time::range::TimestampRange;
 use snafu::ResultExt;
-use store_api::storage::{Chunk, ChunkReader, RegionId, SchemaRef, SequenceNumber};
+use store_api::storage::{Chunk, ChunkReader, RegionId, ScanMetricsRef, SchemaRef, SequenceNumber};
 use table::predicate::{Predicate, TimeRangePredicateBuilder};
 
 use crate::error::{self, Error, Result};
@@ -40,6 +40,7 @@ pub struct ChunkReaderImpl {
     schema: ProjectedSchemaRef,
     batch_reader: BoxedBatchReader,
     output_ordering: Option<Vec<OrderOption>>,
+    metrics: ScanMetricsRef,
 }
 
 #[async_trait]
@@ -68,6 +69,10 @@ impl ChunkReader for ChunkReaderImpl {
     fn output_ordering(&self) -> Option<Vec<OrderOption>> {
         self.output_ordering.clone()
     }
+
+    fn metrics(&self) -> Option<ScanMetricsRef> {
+        Some(self.metrics.clone())
+    }
 }
 
 impl ChunkReaderImpl {
@@ -75,11 +80,13 @@ impl ChunkReaderImpl {
         schema: ProjectedSchemaRef,
         batch_reader: BoxedBatchReader,
         output_ordering: Option<Vec<OrderOption>>,
+        metrics: ScanMetricsRef,
     ) -> ChunkReaderImpl {
         ChunkReaderImpl {
             schema,
             batch_reader,
             output_ordering,
+            metrics,
         }
     }
 
@@ -101,6 +108,7 @@ pub struct ChunkReaderBuilder {
     files_to_read: Vec<FileHandle>,
     output_ordering: Option<Vec<OrderOption>>,
     use_chain_reader: bool,
+    metrics: ScanMetricsRef,
 }
 
 impl ChunkReaderBuilder {
@@ -116,6 +124,7 @@ impl ChunkReaderBuilder {
             files_to_read: Vec::new(),
             output_ordering: None,
             use_chain_reader: false,
+            metrics: ScanMetricsRef::default(),
         }
     }
 
@@ -256,6 +265,7 @@ impl ChunkReaderBuilder {
             projected_schema: schema.clone(),
             predicate,
             time_range: *time_range,
+            metrics: self.metrics.clone(),
         };
 
         let mut num_read_files = 0;
@@ -274,6 +284,7 @@ impl ChunkReaderBuilder {
             reader_builder = reader_builder.push_batch_reader(reader);
             num_read_files += 1;
         }
+        self.metrics.add_sst_files(num_read_files);
 
         logging::debug!(
             "build reader done, region_id: {}, time_range: {:?}, total_files: {}, num_read_files: {}",
@@ -295,6 +306,7 @@ impl ChunkReaderBuilder {
                 .context(error::InvalidProjectionSnafu)?,
         );
         self.iter_ctx.projected_schema = Some(schema.clone());
+        let metrics = self.metrics.clone();
 
         let mut output_ordering = None;
         let reader = if let Some(ordering) = self.output_ordering.take() &&
@@ -308,7 +320,12 @@ impl ChunkReaderBuilder {
             self.build_reader(&schema, &time_range_predicate).await?
         };
 
-        Ok(ChunkReaderImpl::new(schema, reader, output_ordering))
+        Ok(ChunkReaderImpl::new(
+            schema,
+            reader,
+            output_ordering,
+            metrics,
+        ))
     }
 
     async fn build_chained(
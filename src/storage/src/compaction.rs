@@ -150,6 +150,8 @@ mod tests {
                 )),
                 level,
                 file_size: 0,
+                num_rows: 0,
+                tag_index_size: 0,
             },
             layer,
             file_purger,
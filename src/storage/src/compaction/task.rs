@@ -264,13 +264,16 @@ impl CompactionOutput {
                 |SstInfo {
                      time_range,
                      file_size,
-                     ..
+                     num_rows,
+                     tag_index_size,
                  }| FileMeta {
                     region_id,
                     file_id: self.output_file_id,
                     time_range,
                     level: self.output_level,
                     file_size,
+                    num_rows: num_rows as u64,
+                    tag_index_size,
                 },
             );
         Ok(meta)
@@ -255,7 +255,8 @@ mod tests {
         let SstInfo {
             time_range,
             file_size,
-            ..
+            num_rows,
+            tag_index_size,
         } = writer
             .write_sst(&sst::WriteOptions::default())
             .await
@@ -268,6 +269,8 @@ mod tests {
                 time_range,
                 level: 0,
                 file_size,
+                num_rows: num_rows as u64,
+                tag_index_size,
             },
             Arc::new(crate::test_util::access_layer_util::MockAccessLayer {}),
             new_noop_file_purger(),
@@ -535,6 +538,8 @@ mod tests {
                         level: 1,
                         time_range: None,
                         file_size: 0,
+                        num_rows: 0,
+                        tag_index_size: 0,
                     },
                     Arc::new(crate::test_util::access_layer_util::MockAccessLayer {}),
                     new_noop_file_purger(),
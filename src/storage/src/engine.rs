@@ -539,7 +539,7 @@ mod tests {
     use log_store::raft_engine::log_store::RaftEngineLogStore;
     use log_store::test_util::log_store_util;
     use object_store::services::Fs;
-    use store_api::storage::{FlushContext, Region, WriteContext, WriteRequest};
+    use store_api::storage::{FlushContext, Region, RegionOptions, WriteContext, WriteRequest};
 
     use super::*;
     use crate::compaction::noop::NoopCompactionScheduler;
@@ -636,6 +636,37 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_set_region_options() {
+        let dir = create_temp_dir("test_set_options");
+        let log_file_dir = create_temp_dir("test_set_options_wal");
+
+        let region_name = "region-0";
+        let region_id = 123456;
+        let mut config = EngineConfig::default();
+        config.global_ttl = Some(Duration::from_secs(3600));
+        let default_buffer_size = config.region_write_buffer_size.as_bytes() as usize;
+
+        let (_engine, region) =
+            create_engine_and_region(&dir, &log_file_dir, region_name, region_id, config).await;
+        assert_eq!(Some(Duration::from_secs(3600)), region.ttl().await);
+
+        region
+            .set_options(RegionOptions {
+                write_buffer_size: Some(1024),
+                ttl: Some(Duration::from_secs(60)),
+            })
+            .await
+            .unwrap();
+        assert_eq!(1024, region.write_buffer_size().await);
+        assert_eq!(Some(Duration::from_secs(60)), region.ttl().await);
+
+        // Unset options fall back to the engine config.
+        region.set_options(RegionOptions::default()).await.unwrap();
+        assert_eq!(default_buffer_size, region.write_buffer_size().await);
+        assert_eq!(Some(Duration::from_secs(3600)), region.ttl().await);
+    }
+
     #[tokio::test]
     async fn test_drop_region() {
         common_telemetry::init_default_ut_logging();
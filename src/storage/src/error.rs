@@ -361,6 +361,19 @@ pub enum Error {
     #[snafu(display("Failed to read column {}, no proper default value for it", column))]
     NoDefaultToRead { column: String, location: Location },
 
+    #[snafu(display(
+        "Failed to read column {} as {:?} type, source: {}",
+        column,
+        data_type,
+        source
+    ))]
+    CastColumnToRead {
+        column: String,
+        data_type: ConcreteDataType,
+        source: ArrowError,
+        location: Location,
+    },
+
     #[snafu(display(
         "Failed to convert arrow chunk to batch, name: {}, source: {}",
         name,
@@ -589,6 +602,7 @@ impl ErrorExt for Error {
             | CompatRead { .. }
             | CreateDefaultToRead { .. }
             | NoDefaultToRead { .. }
+            | CastColumnToRead { .. }
             | NewRecordBatch { .. }
             | BatchCorrupted { .. }
             | DecodeArrow { .. }
@@ -160,6 +160,8 @@ mod tests {
                     time_range: None,
                     level: 0,
                     file_size: sst_info.file_size,
+                    num_rows: sst_info.num_rows as u64,
+                    tag_index_size: sst_info.tag_index_size,
                 },
                 layer.clone(),
                 file_purger,
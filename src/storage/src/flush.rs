@@ -280,13 +280,16 @@ impl<S: LogStore> FlushJob<S> {
                         |SstInfo {
                              time_range,
                              file_size,
-                             ..
+                             num_rows,
+                             tag_index_size,
                          }| FileMeta {
                             region_id,
                             file_id,
                             time_range,
                             level: 0,
                             file_size,
+                            num_rows: num_rows as u64,
+                            tag_index_size,
                         },
                     ))
             });
@@ -357,6 +357,8 @@ mod tests {
             time_range: None,
             level: 0,
             file_size: 1024,
+            num_rows: 0,
+            tag_index_size: 0,
         }
     }
 
@@ -59,6 +59,8 @@ pub fn build_region_edit(
                 time_range: None,
                 level: 0,
                 file_size: DEFAULT_TEST_FILE_SIZE,
+                num_rows: 0,
+                tag_index_size: 0,
             })
             .collect(),
         files_to_remove: files_to_remove
@@ -69,6 +71,8 @@ pub fn build_region_edit(
                 time_range: None,
                 level: 0,
                 file_size: DEFAULT_TEST_FILE_SIZE,
+                num_rows: 0,
+                tag_index_size: 0,
             })
             .collect(),
         compaction_time_window: None,
@@ -19,7 +19,8 @@ use std::sync::Arc;
 
 use common_error::ext::ErrorExt;
 use common_error::status_code::StatusCode;
-use datatypes::data_type::ConcreteDataType;
+use datatypes::arrow::compute::can_cast_types;
+use datatypes::data_type::{ConcreteDataType, DataType};
 use datatypes::schema::{ColumnSchema, Metadata, COMMENT_KEY};
 use serde::{Deserialize, Serialize};
 use snafu::{ensure, Location, OptionExt, ResultExt, Snafu};
@@ -84,6 +85,27 @@ pub enum Error {
     #[snafu(display("Failed to drop column {} as it is an internal column", name))]
     DropInternalColumn { name: String },
 
+    #[snafu(display("Failed to alter column as there is no column named {}", name))]
+    AlterAbsentColumn { name: String },
+
+    #[snafu(display("Failed to modify type of column {} as it is part of key", name))]
+    ModifyKeyColumn { name: String },
+
+    #[snafu(display(
+        "Failed to modify column {} from {:?} type to {:?} type as the data can not be casted",
+        name,
+        from,
+        to
+    ))]
+    ModifyIncompatibleType {
+        name: String,
+        from: ConcreteDataType,
+        to: ConcreteDataType,
+    },
+
+    #[snafu(display("Failed to rename column {} to an existing name {}", name, new_name))]
+    RenameToExistColumn { name: String, new_name: String },
+
     // End of variants for validating `AlterRequest`.
     #[snafu(display("Failed to convert to column schema, source: {}", source))]
     ToColumnSchema {
@@ -244,6 +266,12 @@ impl RegionMetadata {
                     self.validate_drop_column(name)?;
                 }
             }
+            AlterOperation::ModifyColumnType { name, data_type } => {
+                self.validate_modify_column_type(name, data_type)?;
+            }
+            AlterOperation::RenameColumn { name, new_name } => {
+                self.validate_rename_column(name, new_name)?;
+            }
         }
 
         Ok(())
@@ -309,6 +337,45 @@ impl RegionMetadata {
         Ok(())
     }
 
+    fn validate_modify_column_type(&self, name: &str, data_type: &ConcreteDataType) -> Result<()> {
+        let user_schema = self.schema.user_schema();
+        let column_schema = user_schema
+            .column_schema_by_name(name)
+            .context(AlterAbsentColumnSnafu { name })?;
+        ensure!(
+            !self.schema.store_schema().is_key_column(name),
+            ModifyKeyColumnSnafu { name }
+        );
+        // Data in old SSTs and memtables is casted to the new type while reading.
+        ensure!(
+            can_cast_types(
+                &column_schema.data_type.as_arrow_type(),
+                &data_type.as_arrow_type()
+            ),
+            ModifyIncompatibleTypeSnafu {
+                name,
+                from: column_schema.data_type.clone(),
+                to: data_type.clone(),
+            }
+        );
+
+        Ok(())
+    }
+
+    fn validate_rename_column(&self, name: &str, new_name: &str) -> Result<()> {
+        let store_schema = self.schema.store_schema();
+        ensure!(
+            self.schema.user_schema().contains_column(name),
+            AlterAbsentColumnSnafu { name }
+        );
+        ensure!(
+            !store_schema.contains_column(new_name),
+            RenameToExistColumnSnafu { name, new_name }
+        );
+
+        Ok(())
+    }
+
     fn to_descriptor(&self) -> RegionDescriptor {
         let row_key = self.columns.to_row_key_descriptor();
         let mut builder = RegionDescriptorBuilder::default()
@@ -1290,6 +1357,114 @@ mod tests {
             names: vec![String::from("v0")],
         };
         metadata.validate_alter(&req).unwrap();
+
+        // Modify absent column.
+        req.operation = AlterOperation::ModifyColumnType {
+            name: String::from("v2"),
+            data_type: ConcreteDataType::float64_datatype(),
+        };
+        assert!(matches!(
+            metadata.validate_alter(&req).err().unwrap(),
+            Error::AlterAbsentColumn { .. }
+        ));
+
+        // Modify key column.
+        req.operation = AlterOperation::ModifyColumnType {
+            name: String::from("k0"),
+            data_type: ConcreteDataType::int64_datatype(),
+        };
+        assert!(matches!(
+            metadata.validate_alter(&req).err().unwrap(),
+            Error::ModifyKeyColumn { .. }
+        ));
+
+        // Modify to a type that could not be casted from.
+        req.operation = AlterOperation::ModifyColumnType {
+            name: String::from("v0"),
+            data_type: ConcreteDataType::binary_datatype(),
+        };
+        assert!(matches!(
+            metadata.validate_alter(&req).err().unwrap(),
+            Error::ModifyIncompatibleType { .. }
+        ));
+
+        req.operation = AlterOperation::ModifyColumnType {
+            name: String::from("v0"),
+            data_type: ConcreteDataType::float64_datatype(),
+        };
+        metadata.validate_alter(&req).unwrap();
+
+        // Rename absent column.
+        req.operation = AlterOperation::RenameColumn {
+            name: String::from("v2"),
+            new_name: String::from("v3"),
+        };
+        assert!(matches!(
+            metadata.validate_alter(&req).err().unwrap(),
+            Error::AlterAbsentColumn { .. }
+        ));
+
+        // Rename to existing or internal column.
+        req.operation = AlterOperation::RenameColumn {
+            name: String::from("v0"),
+            new_name: String::from("v1"),
+        };
+        assert!(matches!(
+            metadata.validate_alter(&req).err().unwrap(),
+            Error::RenameToExistColumn { .. }
+        ));
+        req.operation = AlterOperation::RenameColumn {
+            name: String::from("v0"),
+            new_name: String::from(consts::OP_TYPE_COLUMN_NAME),
+        };
+        assert!(matches!(
+            metadata.validate_alter(&req).err().unwrap(),
+            Error::RenameToExistColumn { .. }
+        ));
+
+        req.operation = AlterOperation::RenameColumn {
+            name: String::from("k0"),
+            new_name: String::from("k1"),
+        };
+        metadata.validate_alter(&req).unwrap();
+    }
+
+    #[test]
+    fn test_alter_metadata_modify_and_rename_column() {
+        let region_name = "region-0";
+        let metadata: RegionMetadata = RegionDescBuilder::new(region_name)
+            .push_key_column(("k1", LogicalTypeId::Int32, false))
+            .push_field_column(("v1", LogicalTypeId::Float32, true))
+            .build()
+            .try_into()
+            .unwrap();
+        let v1_id = metadata.schema.user_schema().column_index_by_name("v1");
+        let v1_id = metadata.schema.column_metadata(v1_id.unwrap()).id();
+
+        let req = AlterRequest {
+            operation: AlterOperation::ModifyColumnType {
+                name: String::from("v1"),
+                data_type: ConcreteDataType::float64_datatype(),
+            },
+            version: 0,
+        };
+        let metadata = metadata.alter(&req).unwrap();
+        let req = AlterRequest {
+            operation: AlterOperation::RenameColumn {
+                name: String::from("v1"),
+                new_name: String::from("v2"),
+            },
+            version: 1,
+        };
+        let metadata = metadata.alter(&req).unwrap();
+
+        assert_eq!(2, metadata.version());
+        let user_schema = metadata.user_schema();
+        assert!(!user_schema.contains_column("v1"));
+        let idx = user_schema.column_index_by_name("v2").unwrap();
+        let column = metadata.schema.column_metadata(idx);
+        assert_eq!(v1_id, column.id());
+        assert_eq!(ConcreteDataType::float64_datatype(), column.desc.data_type);
     }
 
     #[test]
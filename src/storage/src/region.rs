@@ -33,7 +33,8 @@ use store_api::manifest::{
 };
 use store_api::storage::{
     AlterRequest, CloseContext, CompactContext, CompactionStrategy, FlushContext, FlushReason,
-    OpenOptions, ReadContext, Region, RegionId, SequenceNumber, WriteContext, WriteResponse,
+    OpenOptions, ReadContext, Region, RegionId, RegionOptions, RegionStat, SequenceNumber,
+    WriteContext, WriteResponse,
 };
 
 use crate::compaction::{
@@ -132,6 +133,17 @@ impl<S: LogStore> Region for RegionImpl<S> {
         self.inner.alter(request).await
     }
 
+    async fn set_options(&self, options: RegionOptions) -> Result<()> {
+        logging::info!(
+            "Set options of region {}, name: {}, options: {:?}",
+            self.inner.shared.id,
+            self.inner.shared.name,
+            options
+        );
+
+        self.inner.writer.set_options(options).await
+    }
+
     async fn drop_region(&self) -> Result<()> {
         decrement_gauge!(crate::metrics::REGION_COUNT, 1.0);
         self.inner.drop_region().await
@@ -147,6 +159,33 @@ impl<S: LogStore> Region for RegionImpl<S> {
             .sum()
     }
 
+    fn region_stat(&self) -> RegionStat {
+        let version = self.inner.version_control().current();
+        let mut stat = RegionStat {
+            region_id: self.id().into(),
+            ..Default::default()
+        };
+        for file in version
+            .ssts()
+            .levels()
+            .iter()
+            .flat_map(|level| level.files())
+        {
+            stat.disk_usage_bytes += file.file_size();
+            stat.num_rows += file.num_rows();
+        }
+        let memtables = version.memtables();
+        for memtable in memtables
+            .immutable_memtables()
+            .iter()
+            .chain(std::iter::once(memtables.mutable_memtable()))
+        {
+            stat.num_rows += memtable.num_rows() as u64;
+            stat.memtable_size += memtable.stats().bytes_allocated() as u64;
+        }
+        stat
+    }
+
     async fn flush(&self, ctx: &FlushContext) -> Result<()> {
         self.inner.flush(ctx).await
     }
@@ -597,6 +636,10 @@ impl<S: LogStore> RegionImpl<S> {
     pub(crate) async fn write_buffer_size(&self) -> usize {
         self.inner.writer.write_buffer_size().await
     }
+
+    pub(crate) async fn ttl(&self) -> Option<Duration> {
+        self.inner.writer.ttl().await
+    }
 }
 
 /// Shared data of region.
@@ -18,7 +18,9 @@ use std::sync::Arc;
 use common_test_util::temp_dir::create_temp_dir;
 use datatypes::prelude::*;
 use datatypes::timestamp::TimestampMillisecond;
-use datatypes::vectors::{Int64Vector, StringVector, TimestampMillisecondVector, VectorRef};
+use datatypes::vectors::{
+    Float64Vector, Int64Vector, StringVector, TimestampMillisecondVector, VectorRef,
+};
 use log_store::raft_engine::log_store::RaftEngineLogStore;
 use store_api::storage::{
     AddColumn, AlterOperation, AlterRequest, Chunk, ChunkReader, ColumnDescriptor,
@@ -489,3 +491,86 @@ async fn test_replay_metadata_after_open() {
     let schema = tester.schema();
     check_schema_names(&schema, &["k1", "timestamp", "v0"]);
 }
+
+#[tokio::test]
+async fn test_read_old_data_after_modify_and_rename_column() {
+    common_telemetry::init_default_ut_logging();
+
+    let dir = create_temp_dir("modify-rename-column");
+    let store_dir = dir.path().to_str().unwrap();
+    let mut tester = AlterTester::new(store_dir).await;
+
+    let req = add_column_req(&[
+        (new_column_desc(4, "k0"), true),  // key column k0
+        (new_column_desc(5, "v1"), false), // value column v1
+    ]);
+    tester.alter(req).await;
+
+    // Write data to SST and memtable before altering.
+    let data = vec![
+        DataRow::new(Some(10000), 1000, Some(100), Some(200)),
+        DataRow::new(Some(10001), 1001, Some(101), Some(201)),
+    ];
+    tester.put(&data).await;
+    tester.flush(None).await;
+    let data = vec![DataRow::new(Some(10002), 1002, Some(102), Some(202))];
+    tester.put(&data).await;
+
+    tester
+        .alter(AlterRequest {
+            operation: AlterOperation::RenameColumn {
+                name: "v0".to_string(),
+                new_name: "v0_renamed".to_string(),
+            },
+            version: 0,
+        })
+        .await;
+    tester
+        .alter(AlterRequest {
+            operation: AlterOperation::ModifyColumnType {
+                name: "v1".to_string(),
+                data_type: ConcreteDataType::float64_datatype(),
+            },
+            version: 0,
+        })
+        .await;
+    tester.reopen().await;
+
+    let schema = tester.schema();
+    check_schema_names(&schema, &["k0", "timestamp", "v0_renamed", "v1"]);
+
+    let read_ctx = &tester.base().read_ctx;
+    let snapshot = tester.base().region.snapshot(read_ctx).unwrap();
+    let mut reader = snapshot
+        .scan(read_ctx, ScanRequest::default())
+        .await
+        .unwrap()
+        .reader;
+    let mut v0 = Vec::new();
+    let mut v1 = Vec::new();
+    while let Some(chunk) = reader.next_chunk().await.unwrap() {
+        let chunk = reader.project_chunk(chunk);
+        let v0_vector = chunk.columns[2]
+            .as_any()
+            .downcast_ref::<StringVector>()
+            .unwrap();
+        let v1_vector = chunk.columns[3]
+            .as_any()
+            .downcast_ref::<Float64Vector>()
+            .unwrap();
+        for i in 0..v0_vector.len() {
+            v0.push(v0_vector.get_data(i).map(|s| s.to_string()));
+            v1.push(v1_vector.get_data(i));
+        }
+    }
+
+    assert_eq!(
+        vec![
+            Some("100".to_string()),
+            Some("101".to_string()),
+            Some("102".to_string())
+        ],
+        v0
+    );
+    assert_eq!(vec![Some(200.0), Some(201.0), Some(202.0)], v1);
+}
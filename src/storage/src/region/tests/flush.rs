@@ -223,6 +223,33 @@ async fn test_flush_and_reopen() {
     assert_eq!(2, i);
 }
 
+#[tokio::test]
+async fn test_region_stat_after_flush() {
+    common_telemetry::init_default_ut_logging();
+    let dir = create_temp_dir("region_stat");
+    let store_dir = dir.path().to_str().unwrap();
+    let flush_switch = Arc::new(FlushSwitch::default());
+    let mut tester = FlushTester::new(store_dir, flush_switch.clone()).await;
+
+    tester.put(&[(1000, Some(100)), (2000, Some(200))]).await;
+    let stat = tester.base().region.region_stat();
+    assert_eq!(2, stat.num_rows);
+    assert_eq!(0, stat.disk_usage_bytes);
+    assert!(stat.memtable_size > 0);
+
+    tester.flush(Some(true)).await;
+    let stat = tester.base().region.region_stat();
+    assert_eq!(2, stat.num_rows);
+    assert!(stat.disk_usage_bytes > 0);
+
+    // The number of rows in SST files is persisted in the manifest.
+    tester.reopen().await;
+    let reopened = tester.base().region.region_stat();
+    assert_eq!(2, reopened.num_rows);
+    assert_eq!(stat.disk_usage_bytes, reopened.disk_usage_bytes);
+    assert_eq!(0, reopened.memtable_size);
+}
+
 #[tokio::test]
 async fn test_flush_empty() {
     let dir = create_temp_dir("flush-empty");
@@ -23,7 +23,8 @@ use snafu::{ensure, ResultExt};
 use store_api::logstore::LogStore;
 use store_api::manifest::{Manifest, ManifestLogStorage, ManifestVersion, MetaAction};
 use store_api::storage::{
-    AlterRequest, FlushContext, FlushReason, SequenceNumber, WriteContext, WriteResponse,
+    AlterRequest, FlushContext, FlushReason, RegionOptions, SequenceNumber, WriteContext,
+    WriteResponse,
 };
 use tokio::sync::{oneshot, Mutex};
 
@@ -262,6 +263,21 @@ where
         Ok(())
     }
 
+    /// Updates the ttl and write buffer size of the writer, options not set fall back
+    /// to the values in the engine config.
+    pub async fn set_options(&self, options: RegionOptions) -> Result<()> {
+        let mut inner = self.inner.lock().await;
+
+        ensure!(!inner.is_closed(), error::ClosedRegionSnafu);
+
+        inner.ttl = options.ttl.or(inner.engine_config.global_ttl);
+        inner.write_buffer_size = options
+            .write_buffer_size
+            .unwrap_or(inner.engine_config.region_write_buffer_size.as_bytes() as usize);
+
+        Ok(())
+    }
+
     pub async fn close(&self) -> Result<()> {
         // In order to close a writer
         // 1. Acquires the write lock.
@@ -408,6 +424,10 @@ where
     pub(crate) async fn write_buffer_size(&self) -> usize {
         self.inner.lock().await.write_buffer_size
     }
+
+    pub(crate) async fn ttl(&self) -> Option<Duration> {
+        self.inner.lock().await.ttl
+    }
 }
 
 /// Structs needed by triggering a compaction.
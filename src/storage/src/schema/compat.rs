@@ -14,8 +14,10 @@
 
 //! Utilities for resolving schema compatibility problems.
 
+use datatypes::arrow::compute::{self, can_cast_types, CastOptions};
 use datatypes::arrow::record_batch::RecordBatch;
-use datatypes::schema::SchemaRef;
+use datatypes::data_type::DataType;
+use datatypes::schema::{ColumnSchema, SchemaRef};
 use datatypes::vectors::{Helper, VectorRef};
 use snafu::{ensure, OptionExt, ResultExt};
 
@@ -37,6 +39,10 @@ pub trait CompatWrite {
 
 /// Checks whether column with `source_column` could be read as a column with `dest_column`.
 ///
+/// Columns are identified by their ids, so a renamed column is still the same column. A
+/// column whose type has been modified could be read if the source data could be casted to
+/// the type of `dest_column`.
+///
 /// Returns
 /// - `Ok(true)` if `source_column` is compatible to read using `dest_column` as schema.
 /// - `Ok(false)` if they are considered different columns.
@@ -45,23 +51,16 @@ fn is_source_column_compatible(
     source_column: &ColumnMetadata,
     dest_column: &ColumnMetadata,
 ) -> Result<bool> {
-    ensure!(
-        source_column.name() == dest_column.name(),
-        error::CompatReadSnafu {
-            reason: format!(
-                "try to use column in {} for column {}",
-                source_column.name(),
-                dest_column.name()
-            ),
-        }
-    );
-
     if source_column.id() != dest_column.id() {
         return Ok(false);
     }
 
     ensure!(
-        source_column.desc.data_type == dest_column.desc.data_type,
+        source_column.desc.data_type == dest_column.desc.data_type
+            || can_cast_types(
+                &source_column.desc.data_type.as_arrow_type(),
+                &dest_column.desc.data_type.as_arrow_type()
+            ),
         error::CompatReadSnafu {
             reason: format!(
                 "could not read column {} from {:?} type as {:?} type",
@@ -97,6 +96,11 @@ pub struct ReadAdapter {
     ///
     /// This vec would be left empty if `source_version == dest_version`.
     indices_in_result: Vec<Option<usize>>,
+    /// For each column in dest schema, stores whether the column read from source
+    /// needs to be casted to the type in dest schema.
+    ///
+    /// This vec would be left empty if `source_version == dest_version`.
+    need_cast: Vec<bool>,
     /// For each column in source schema, stores whether we need to read that column. All
     /// columns are needed by default.
     is_source_needed: Vec<bool>,
@@ -137,6 +141,7 @@ impl ReadAdapter {
             source_schema,
             dest_schema,
             indices_in_result: Vec::new(),
+            need_cast: Vec::new(),
             is_source_needed,
         })
     }
@@ -147,15 +152,18 @@ impl ReadAdapter {
     ) -> Result<ReadAdapter> {
         let schema_to_read = dest_schema.schema_to_read();
         let mut indices_in_result = vec![None; schema_to_read.num_columns()];
+        let mut need_cast = vec![false; schema_to_read.num_columns()];
         let mut is_source_needed = vec![true; source_schema.num_columns()];
         // Number of columns in result from source data.
         let mut num_columns_in_result = 0;
 
         for (idx, source_column) in source_schema.columns().iter().enumerate() {
-            // For each column in source schema, check whether we need to read it.
+            // For each column in source schema, check whether we need to read it. Columns are
+            // matched by id so a renamed column could still be read from source data.
             if let Some(dest_idx) = schema_to_read
-                .schema()
-                .column_index_by_name(source_column.name())
+                .columns()
+                .iter()
+                .position(|column| column.id() == source_column.id())
             {
                 let dest_column = &schema_to_read.columns()[dest_idx];
                 // Check whether we could read this column.
@@ -164,6 +172,8 @@ impl ReadAdapter {
                     // columns in source schema would be skipped, we should not use
                     // the source column's index directly.
                     indices_in_result[dest_idx] = Some(num_columns_in_result);
+                    need_cast[dest_idx] =
+                        source_column.desc.data_type != dest_column.desc.data_type;
                     num_columns_in_result += 1;
                 } else {
                     // This column is not the same column in dest schema, should be fill by default value
@@ -180,6 +190,7 @@ impl ReadAdapter {
             source_schema,
             dest_schema,
             indices_in_result,
+            need_cast,
             is_source_needed,
         })
     }
@@ -279,10 +290,15 @@ impl ReadAdapter {
         let columns = self
             .indices_in_result
             .iter()
+            .zip(&self.need_cast)
             .zip(column_schemas)
-            .map(|(index_opt, column_schema)| {
+            .map(|((index_opt, need_cast), column_schema)| {
                 if let Some(idx) = index_opt {
-                    Ok(source[*idx].clone())
+                    if *need_cast {
+                        cast_vector(&source[*idx], column_schema)
+                    } else {
+                        Ok(source[*idx].clone())
+                    }
                 } else {
                     let vector = column_schema
                         .create_default_vector(num_rows)
@@ -301,12 +317,33 @@ impl ReadAdapter {
     }
 }
 
+/// Casts `vector` read from source data to the type of `column_schema`.
+///
+/// Values that could not be casted are reported as an error instead of being read as null,
+/// so modifying the type of a column never loses data silently.
+fn cast_vector(vector: &VectorRef, column_schema: &ColumnSchema) -> Result<VectorRef> {
+    let array = compute::cast_with_options(
+        &vector.to_arrow_array(),
+        &column_schema.data_type.as_arrow_type(),
+        &CastOptions { safe: false },
+    )
+    .context(error::CastColumnToReadSnafu {
+        column: &column_schema.name,
+        data_type: column_schema.data_type.clone(),
+    })?;
+
+    Helper::try_into_vector(array).context(error::ConvertChunkSnafu {
+        name: &column_schema.name,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
 
     use datatypes::data_type::ConcreteDataType;
     use datatypes::schema::Schema;
+    use datatypes::vectors::{Float64Vector, StringVector};
     use store_api::storage::ColumnDescriptorBuilder;
 
     use super::*;
@@ -596,16 +633,85 @@ mod tests {
         let desc = new_column_desc_builder().build().unwrap();
         let source = ColumnMetadata { cf_id: 1, desc };
 
+        // A renamed column keeps its id.
         let desc = new_column_desc_builder()
             .name(format!("{}_other", source.desc.name))
             .build()
             .unwrap();
         let dest = ColumnMetadata { cf_id: 1, desc };
+        assert!(is_source_column_compatible(&source, &dest).unwrap());
+    }
+
+    #[test]
+    fn test_read_column_with_different_type() {
+        let desc = new_column_desc_builder().build().unwrap();
+        let source = ColumnMetadata { cf_id: 1, desc };
+
+        let desc = new_column_desc_builder()
+            .data_type(ConcreteDataType::float64_datatype())
+            .build()
+            .unwrap();
+        let dest = ColumnMetadata { cf_id: 1, desc };
+        assert!(is_source_column_compatible(&source, &dest).unwrap());
 
+        let desc = new_column_desc_builder()
+            .data_type(ConcreteDataType::binary_datatype())
+            .build()
+            .unwrap();
+        let dest = ColumnMetadata { cf_id: 1, desc };
         let err = is_source_column_compatible(&source, &dest).unwrap_err();
         assert!(
             matches!(err, Error::CompatRead { .. }),
             "{err:?} is not CompatRead",
         );
     }
+
+    #[test]
+    fn test_compat_renamed_and_modified_column() {
+        // (k0, timestamp, v0, v1) with version 0.
+        let region_schema_old = Arc::new(schema_util::new_region_schema(0, 2));
+
+        let mut descriptor = descriptor_util::desc_with_field_columns(tests::REGION_NAME, 2);
+        // Rename v0 and modify the type of v1.
+        descriptor.default_cf.columns[0].name = "v0_new".to_string();
+        descriptor.default_cf.columns[1].data_type = ConcreteDataType::float64_datatype();
+        let metadata: RegionMetadata = descriptor.try_into().unwrap();
+        let columns = metadata.columns;
+        // (k0, timestamp, v0_new, v1) with version 2.
+        let region_schema_new = Arc::new(RegionSchema::new(columns, 2).unwrap());
+
+        let projected_schema = Arc::new(ProjectedSchema::no_projection(region_schema_new));
+        let source_schema = region_schema_old.store_schema().clone();
+        let adapter = ReadAdapter::new(source_schema, projected_schema).unwrap();
+
+        assert_eq!(&[true, true], adapter.source_key_needed());
+        assert_eq!(&[true, true], adapter.source_value_needed());
+
+        let batch = tests::new_batch_with_num_values(2);
+        let check_batch = |new_batch: Batch| {
+            assert_eq!(batch.num_columns(), new_batch.num_columns());
+            assert_eq!(batch.column(2), new_batch.column(2));
+            let expect: VectorRef = Arc::new(Float64Vector::from_slice([1.0, 1.0, 1.0]));
+            assert_eq!(&expect, new_batch.column(3));
+        };
+        check_batch(call_batch_from_parts(&adapter, &batch, 2));
+        check_batch(call_arrow_chunk_to_batch(&adapter, &batch));
+    }
+
+    #[test]
+    fn test_cast_vector() {
+        let column_schema = ColumnSchema::new("v0", ConcreteDataType::float64_datatype(), true);
+
+        let vector: VectorRef = Arc::new(StringVector::from(vec![Some("1.5"), None]));
+        let casted = cast_vector(&vector, &column_schema).unwrap();
+        let expect: VectorRef = Arc::new(Float64Vector::from(vec![Some(1.5), None]));
+        assert_eq!(expect, casted);
+
+        let vector: VectorRef = Arc::new(StringVector::from(vec!["1.5", "not a number"]));
+        let err = cast_vector(&vector, &column_schema).unwrap_err();
+        assert!(
+            matches!(err, Error::CastColumnToRead { .. }),
+            "{err:?} is not CastColumnToRead",
+        );
+    }
 }
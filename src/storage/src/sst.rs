@@ -15,6 +15,7 @@
 pub(crate) mod parquet;
 mod pruning;
 mod stream_writer;
+mod tag_index;
 
 use std::collections::HashMap;
 use std::fmt;
@@ -23,6 +24,7 @@ use std::str::FromStr;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
+use arc_swap::ArcSwapOption;
 use async_trait::async_trait;
 use common_base::readable_size::ReadableSize;
 use common_recordbatch::SendableRecordBatchStream;
@@ -34,7 +36,7 @@ use futures_util::StreamExt;
 use object_store::{util, ObjectStore};
 use serde::{Deserialize, Deserializer, Serialize};
 use snafu::{ResultExt, Snafu};
-use store_api::storage::{ChunkReader, RegionId};
+use store_api::storage::{ChunkReader, RegionId, ScanMetricsRef};
 use table::predicate::Predicate;
 use uuid::Uuid;
 
@@ -47,6 +49,7 @@ use crate::read::{Batch, BatchReader, BoxedBatchReader};
 use crate::scheduler::Scheduler;
 use crate::schema::ProjectedSchemaRef;
 use crate::sst::parquet::{ChunkStream, ParquetReader, ParquetWriter};
+use crate::sst::tag_index::TagIndex;
 
 /// Maximum level of SSTs.
 pub const MAX_LEVEL: u8 = 2;
@@ -275,6 +278,14 @@ impl FileHandle {
             .sst_file_path(&self.inner.meta.file_id.as_parquet())
     }
 
+    /// Returns the path of the tag index file of the SST.
+    #[inline]
+    pub fn tag_index_path(&self) -> String {
+        self.inner
+            .sst_layer
+            .sst_file_path(&self.inner.meta.file_id.as_tag_index())
+    }
+
     #[inline]
     pub fn file_id(&self) -> FileId {
         self.inner.meta.file_id
@@ -316,6 +327,28 @@ impl FileHandle {
     pub fn file_size(&self) -> u64 {
         self.inner.meta.file_size
     }
+
+    #[inline]
+    pub fn num_rows(&self) -> u64 {
+        self.inner.meta.num_rows
+    }
+
+    #[inline]
+    pub fn tag_index_size(&self) -> u64 {
+        self.inner.meta.tag_index_size
+    }
+
+    /// Returns the decoded tag index cached in the handle.
+    #[inline]
+    pub(crate) fn cached_tag_index(&self) -> Option<Arc<TagIndex>> {
+        self.inner.tag_index.load_full()
+    }
+
+    /// Caches the decoded tag index so following scans don't need to read it again.
+    #[inline]
+    pub(crate) fn cache_tag_index(&self, index: Arc<TagIndex>) {
+        self.inner.tag_index.store(Some(index));
+    }
 }
 
 /// Actually data of [FileHandle].
@@ -327,6 +360,8 @@ struct FileHandleInner {
     deleted: AtomicBool,
     sst_layer: AccessLayerRef,
     file_purger: FilePurgerRef,
+    /// Decoded tag index of the file, loaded on the first scan that uses it.
+    tag_index: ArcSwapOption<TagIndex>,
 }
 
 impl fmt::Debug for FileHandleInner {
@@ -377,6 +412,7 @@ impl FileHandleInner {
             deleted: AtomicBool::new(false),
             sst_layer,
             file_purger,
+            tag_index: ArcSwapOption::empty(),
         }
     }
 }
@@ -405,6 +441,11 @@ impl FileId {
     pub fn as_parquet(&self) -> String {
         format!("{}{}", self.0.hyphenated(), ".parquet")
     }
+
+    /// Append `.tag_index` to file id to make the file name of the tag index.
+    pub fn as_tag_index(&self) -> String {
+        format!("{}{}", self.0.hyphenated(), ".tag_index")
+    }
 }
 
 impl fmt::Display for FileId {
@@ -437,6 +478,10 @@ pub struct FileMeta {
     pub level: Level,
     /// Size of the file.
     pub file_size: u64,
+    /// Number of rows in the file, 0 for files written by old versions.
+    pub num_rows: u64,
+    /// Size of the tag index file, 0 if the file has no tag index.
+    pub tag_index_size: u64,
 }
 
 fn deserialize_from_string<'de, D>(deserializer: D) -> std::result::Result<FileId, D::Error>
@@ -471,6 +516,8 @@ pub struct ReadOptions {
 
     pub predicate: Predicate,
     pub time_range: TimestampRange,
+    /// Metrics of the scan this SST is read for.
+    pub metrics: ScanMetricsRef,
 }
 
 #[derive(Debug, PartialEq)]
@@ -478,6 +525,8 @@ pub struct SstInfo {
     pub time_range: Option<(Timestamp, Timestamp)>,
     pub file_size: u64,
     pub num_rows: usize,
+    /// Size of the tag index file, 0 if no index is written.
+    pub tag_index_size: u64,
 }
 
 /// SST access layer.
@@ -586,7 +635,9 @@ impl AccessLayer for FsAccessLayer {
         // Now we only supports parquet format. We may allow caller to specific SST format in
         // WriteOptions in the future.
         let file_path = self.sst_file_path(&file_id.as_parquet());
-        let writer = ParquetWriter::new(&file_path, source, self.object_store.clone());
+        let tag_index_path = self.sst_file_path(&file_id.as_tag_index());
+        let writer = ParquetWriter::new(&file_path, source, self.object_store.clone())
+            .with_tag_index_path(&tag_index_path);
         writer.write_sst(opts).await
     }
 
@@ -602,14 +653,23 @@ impl AccessLayer for FsAccessLayer {
             opts.projected_schema.clone(),
             opts.predicate.clone(),
             opts.time_range,
-        );
+        )
+        .with_metrics(opts.metrics.clone());
 
         Ok(Box::new(LazyParquetBatchReader::new(reader)))
     }
 
-    /// Deletes a SST file with given file id.
+    /// Deletes a SST file and its tag index with given file id.
     async fn delete_sst(&self, file_id: FileId) -> Result<()> {
         let path = self.sst_file_path(&file_id.as_parquet());
+        self.object_store
+            .delete(&path)
+            .await
+            .context(DeleteSstSnafu)?;
+
+        // Deleting a file that doesn't exist is not an error, so we don't need
+        // to know whether the SST has a tag index.
+        let path = self.sst_file_path(&file_id.as_tag_index());
         self.object_store
             .delete(&path)
             .await
@@ -731,9 +791,20 @@ mod tests {
             time_range: None,
             level,
             file_size: 0,
+            num_rows: 0,
+            tag_index_size: 0,
         }
     }
 
+    #[test]
+    fn test_file_id_as_tag_index() {
+        let id = FileId::from_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+        assert_eq!(
+            "67e55044-10b1-426f-9247-bb680e5fe0c8.tag_index",
+            id.as_tag_index()
+        );
+    }
+
     #[test]
     fn test_level_metas_add_and_remove() {
         let layer = Arc::new(crate::test_util::access_layer_util::MockAccessLayer {});
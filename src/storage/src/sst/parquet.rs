@@ -21,7 +21,7 @@ use std::sync::Arc;
 use async_compat::CompatExt;
 use async_stream::try_stream;
 use async_trait::async_trait;
-use common_telemetry::{debug, error};
+use common_telemetry::{debug, error, warn};
 use common_time::range::TimestampRange;
 use common_time::timestamp::TimeUnit;
 use common_time::Timestamp;
@@ -37,16 +37,20 @@ use parquet::format::FileMetaData;
 use parquet::schema::types::ColumnPath;
 use snafu::{OptionExt, ResultExt};
 use store_api::storage::consts::SEQUENCE_COLUMN_NAME;
+use store_api::storage::ScanMetricsRef;
 use table::predicate::Predicate;
 use tokio::io::BufReader;
 
-use crate::error::{self, DecodeParquetTimeRangeSnafu, ReadObjectSnafu, ReadParquetSnafu, Result};
+use crate::error::{
+    self, DecodeParquetTimeRangeSnafu, ReadObjectSnafu, ReadParquetSnafu, Result, WriteObjectSnafu,
+};
 use crate::read::{Batch, BatchReader};
 use crate::schema::compat::ReadAdapter;
 use crate::schema::{ProjectedSchemaRef, StoreSchema};
 use crate::sst;
 use crate::sst::pruning::build_row_filter;
 use crate::sst::stream_writer::BufferedWriter;
+use crate::sst::tag_index::{TagIndex, TagIndexBuilder};
 use crate::sst::{FileHandle, Source, SstInfo};
 
 /// Parquet sst writer.
@@ -55,6 +59,8 @@ pub struct ParquetWriter<'a> {
     source: Source,
     object_store: ObjectStore,
     max_row_group_size: usize,
+    /// Path to write the tag index of the SST, `None` to skip the index.
+    tag_index_path: Option<&'a str>,
 }
 
 impl<'a> ParquetWriter<'a> {
@@ -64,9 +70,16 @@ impl<'a> ParquetWriter<'a> {
             source,
             object_store,
             max_row_group_size: 4096, // TODO(hl): make this configurable
+            tag_index_path: None,
         }
     }
 
+    /// Also writes a tag index of the SST to `tag_index_path`.
+    pub fn with_tag_index_path(mut self, tag_index_path: &'a str) -> Self {
+        self.tag_index_path = Some(tag_index_path);
+        self
+    }
+
     pub async fn write_sst(self, opts: &sst::WriteOptions) -> Result<Option<SstInfo>> {
         self.write_rows(None, opts).await
     }
@@ -117,10 +130,17 @@ impl<'a> ParquetWriter<'a> {
         )
         .await?;
         let mut rows_written = 0;
+        let mut index_builder = self
+            .tag_index_path
+            .and_then(|_| StoreSchema::try_from(schema.arrow_schema().clone()).ok())
+            .and_then(|store_schema| TagIndexBuilder::new(&store_schema));
 
         while let Some(batch) = self.source.next_batch().await? {
             buffered_writer.write(&batch).await?;
             rows_written += batch.num_rows();
+            if let Some(builder) = &mut index_builder {
+                builder.update(&batch);
+            }
         }
 
         if rows_written == 0 {
@@ -132,11 +152,30 @@ impl<'a> ParquetWriter<'a> {
         let (file_meta, file_size) = buffered_writer.close().await?;
         let time_range = decode_timestamp_range(&file_meta, &schema).ok().flatten();
 
+        let mut tag_index_size = 0;
+        let index = index_builder.and_then(|builder| {
+            builder.build(
+                file_meta
+                    .row_groups
+                    .iter()
+                    .map(|row_group| row_group.num_rows as usize),
+            )
+        });
+        if let (Some(path), Some(index)) = (self.tag_index_path, index) {
+            let bytes = index.encode()?;
+            tag_index_size = bytes.len() as u64;
+            self.object_store
+                .write(path, bytes)
+                .await
+                .context(WriteObjectSnafu { path })?;
+        }
+
         // object_store.write will make sure all bytes are written or an error is raised.
         Ok(Some(SstInfo {
             time_range,
             file_size,
             num_rows: rows_written,
+            tag_index_size,
         }))
     }
 }
@@ -226,6 +265,7 @@ pub struct ParquetReader {
     projected_schema: ProjectedSchemaRef,
     predicate: Predicate,
     time_range: TimestampRange,
+    metrics: Option<ScanMetricsRef>,
 }
 
 impl ParquetReader {
@@ -242,10 +282,68 @@ impl ParquetReader {
             projected_schema,
             predicate,
             time_range,
+            metrics: None,
+        }
+    }
+
+    /// Reports the number of row groups to read to `metrics`.
+    pub fn with_metrics(mut self, metrics: ScanMetricsRef) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Reads the tag index of the SST if the predicate may use it.
+    ///
+    /// Returns `None` if the index is absent or can't be read, as the index is
+    /// only an optimization. The decoded index is cached in the file handle.
+    async fn read_tag_index(&self) -> Option<Arc<TagIndex>> {
+        if self.file_handle.tag_index_size() == 0 || self.predicate.exprs().is_empty() {
+            return None;
+        }
+        if let Some(index) = self.file_handle.cached_tag_index() {
+            return Some(index);
+        }
+
+        let path = self.file_handle.tag_index_path();
+        let bytes = match self.object_store.read(&path).await {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                warn!("Failed to read tag index {}, err: {}", path, e);
+                return None;
+            }
+        };
+        match TagIndex::decode(&bytes) {
+            Ok(index) => {
+                let index = Arc::new(index);
+                self.file_handle.cache_tag_index(index.clone());
+                Some(index)
+            }
+            Err(e) => {
+                warn!(e; "Failed to decode tag index {}", path);
+                None
+            }
         }
     }
 
     pub async fn chunk_stream(&self) -> Result<ChunkStream> {
+        let index_row_groups = self.read_tag_index().await.map(|index| {
+            index.prune_row_groups(&self.predicate, self.projected_schema.schema_to_read())
+        });
+        if let Some(row_groups) = &index_row_groups {
+            if !row_groups.iter().any(|valid| *valid) {
+                // No row group contains the tags, so we don't need to open the file.
+                let adapter = ReadAdapter::new(
+                    self.projected_schema.schema_to_read().clone(),
+                    self.projected_schema.clone(),
+                )?;
+                return ChunkStream::new(
+                    self.file_handle.clone(),
+                    adapter,
+                    Box::pin(futures_util::stream::empty()),
+                );
+            }
+        }
+
         let file_path = self.file_handle.file_path();
         let operator = self.object_store.clone();
 
@@ -272,8 +370,22 @@ impl ParquetReader {
             .prune_row_groups(builder.metadata().row_groups())
             .into_iter()
             .enumerate()
-            .filter_map(|(idx, valid)| if valid { Some(idx) } else { None })
+            .filter_map(|(idx, valid)| {
+                // The index is built from the same file so it has the same row groups.
+                let in_index = index_row_groups
+                    .as_ref()
+                    .and_then(|row_groups| row_groups.get(idx).copied())
+                    .unwrap_or(true);
+                if valid && in_index {
+                    Some(idx)
+                } else {
+                    None
+                }
+            })
             .collect::<Vec<_>>();
+        if let Some(metrics) = &self.metrics {
+            metrics.add_row_groups(pruned_row_groups.len());
+        }
 
         let parquet_schema_desc = builder.metadata().file_metadata().schema_descr_ptr();
 
@@ -347,20 +459,26 @@ mod tests {
 
     use common_base::readable_size::ReadableSize;
     use common_test_util::temp_dir::create_temp_dir;
-    use datatypes::arrow::array::{Array, UInt64Array, UInt8Array};
+    use datafusion_expr::{col, lit, Expr as DfExpr};
+    use datatypes::arrow::array::{Array, Decimal128Array, UInt64Array, UInt8Array};
     use datatypes::prelude::{ScalarVector, Vector};
+    use datatypes::type_id::LogicalTypeId;
     use datatypes::types::{TimestampMillisecondType, TimestampType};
-    use datatypes::vectors::TimestampMillisecondVector;
+    use datatypes::vectors::{
+        Decimal128Vector, StringVector, TimestampMillisecondVector, UInt64Vector, VectorRef,
+    };
     use object_store::services::Fs;
-    use store_api::storage::OpType;
+    use store_api::storage::{OpType, ScanMetrics};
 
     use super::*;
     use crate::file_purger::noop::new_noop_file_purger;
     use crate::memtable::{
-        tests as memtable_tests, DefaultMemtableBuilder, IterContext, MemtableBuilder,
+        tests as memtable_tests, DefaultMemtableBuilder, IterContext, KeyValues, MemtableBuilder,
     };
-    use crate::schema::ProjectedSchema;
+    use crate::metadata::RegionMetadata;
+    use crate::schema::{ProjectedSchema, RegionSchemaRef};
     use crate::sst::{FileId, FileMeta};
+    use crate::test_util::descriptor_util::RegionDescBuilder;
 
     fn create_object_store(root: &str) -> ObjectStore {
         let mut builder = Fs::default();
@@ -570,6 +688,8 @@ mod tests {
                 )),
                 level: 0,
                 file_size: 0,
+                num_rows: 0,
+                tag_index_size: 0,
             },
             layer,
             file_purger,
@@ -812,4 +932,206 @@ mod tests {
         // The file should not exist when no row has been written.
         assert!(!object_store.is_exist(sst_file_name).await.unwrap());
     }
+
+    /// Writes 6 rows of hosts `a`, `b`, `c` to an SST with 3 row groups, one host per
+    /// row group.
+    async fn write_sst_with_tag_index(
+        object_store: &ObjectStore,
+        file_id: FileId,
+    ) -> (RegionSchemaRef, SstInfo) {
+        let desc = RegionDescBuilder::new("test")
+            .push_key_column(("host", LogicalTypeId::String, true))
+            .push_field_column(("v0", LogicalTypeId::UInt64, true))
+            .build();
+        let metadata: RegionMetadata = desc.try_into().unwrap();
+        let schema = metadata.schema().clone();
+        let memtable = DefaultMemtableBuilder::default().build(schema.clone());
+        let kvs = KeyValues {
+            sequence: 10,
+            op_type: OpType::Put,
+            start_index_in_batch: 0,
+            keys: vec![Arc::new(StringVector::from(vec!["a", "a", "b", "b", "c", "c"])) as _],
+            values: vec![Arc::new(UInt64Vector::from_slice([1, 2, 3, 4, 5, 6])) as _],
+            timestamp: Some(Arc::new(TimestampMillisecondVector::from_values([
+                1000, 1001, 1000, 1001, 1000, 1001,
+            ])) as _),
+        };
+        memtable.write(&kvs).unwrap();
+
+        let sst_file_name = file_id.as_parquet();
+        let tag_index_name = file_id.as_tag_index();
+        let iter = memtable.iter(IterContext::default()).unwrap();
+        let mut writer =
+            ParquetWriter::new(&sst_file_name, Source::Iter(iter), object_store.clone())
+                .with_tag_index_path(&tag_index_name);
+        writer.max_row_group_size = 2;
+        let sst_info = writer
+            .write_sst(&sst::WriteOptions::default())
+            .await
+            .unwrap()
+            .unwrap();
+
+        (schema, sst_info)
+    }
+
+    async fn read_rows_with_filter(
+        file_handle: FileHandle,
+        object_store: ObjectStore,
+        schema: RegionSchemaRef,
+        filter: DfExpr,
+    ) -> (usize, usize) {
+        let projected_schema = Arc::new(ProjectedSchema::new(schema, None).unwrap());
+        let store_schema = projected_schema.schema_to_read().clone();
+        let predicate =
+            Predicate::try_new(vec![filter.into()], store_schema.schema().clone()).unwrap();
+        let metrics = Arc::new(ScanMetrics::default());
+        let reader = ParquetReader::new(
+            file_handle,
+            object_store,
+            projected_schema,
+            predicate,
+            TimestampRange::min_to_max(),
+        )
+        .with_metrics(metrics.clone());
+
+        let mut stream = reader.chunk_stream().await.unwrap();
+        let mut num_rows = 0;
+        while let Some(batch) = stream.next_batch().await.unwrap() {
+            num_rows += batch.num_rows();
+        }
+
+        (num_rows, metrics.row_groups())
+    }
+
+    #[tokio::test]
+    async fn test_parquet_reader_with_tag_index() {
+        common_telemetry::init_default_ut_logging();
+        let dir = create_temp_dir("read-tag-index");
+        let object_store = create_object_store(dir.path().to_str().unwrap());
+        let file_id = FileId::random();
+        let (schema, sst_info) = write_sst_with_tag_index(&object_store, file_id).await;
+        assert_ne!(0, sst_info.tag_index_size);
+        assert!(object_store
+            .is_exist(&file_id.as_tag_index())
+            .await
+            .unwrap());
+
+        let file_handle = FileHandle::new(
+            FileMeta {
+                region_id: 0.into(),
+                file_id,
+                time_range: sst_info.time_range,
+                level: 0,
+                file_size: sst_info.file_size,
+                num_rows: sst_info.num_rows as u64,
+                tag_index_size: sst_info.tag_index_size,
+            },
+            Arc::new(crate::test_util::access_layer_util::MockAccessLayer {}),
+            new_noop_file_purger(),
+        );
+
+        assert!(file_handle.cached_tag_index().is_none());
+        // Only the row group of host `b` is read.
+        let (num_rows, row_groups) = read_rows_with_filter(
+            file_handle.clone(),
+            object_store.clone(),
+            schema.clone(),
+            col("host").eq(lit("b")),
+        )
+        .await;
+        assert_eq!((2, 1), (num_rows, row_groups));
+        // The decoded index is cached for following scans.
+        assert!(file_handle.cached_tag_index().is_some());
+
+        let (num_rows, row_groups) = read_rows_with_filter(
+            file_handle.clone(),
+            object_store.clone(),
+            schema.clone(),
+            col("host").in_list(vec![lit("a"), lit("c")], false),
+        )
+        .await;
+        assert_eq!((4, 2), (num_rows, row_groups));
+
+        // The file is skipped as no row group contains host `x`.
+        let (num_rows, row_groups) = read_rows_with_filter(
+            file_handle.clone(),
+            object_store.clone(),
+            schema.clone(),
+            col("host").eq(lit("x")),
+        )
+        .await;
+        assert_eq!((0, 0), (num_rows, row_groups));
+
+        // Filters on other columns can't use the index.
+        let (num_rows, row_groups) =
+            read_rows_with_filter(file_handle, object_store, schema, col("v0").gt(lit(0u64))).await;
+        assert_eq!((6, 3), (num_rows, row_groups));
+    }
+
+    #[tokio::test]
+    async fn test_parquet_decimal_round_trip() {
+        common_telemetry::init_default_ut_logging();
+        let desc = RegionDescBuilder::new("test")
+            .push_key_column(("host", LogicalTypeId::String, true))
+            .push_field_column(("price", LogicalTypeId::Decimal128, true))
+            .build();
+        let metadata: RegionMetadata = desc.try_into().unwrap();
+        let schema = metadata.schema().clone();
+        let memtable = DefaultMemtableBuilder::default().build(schema.clone());
+        let prices: VectorRef = Arc::new(
+            Decimal128Vector::from(Decimal128Array::from(vec![
+                Some(12345),
+                None,
+                Some(-1),
+                Some(i128::MAX / 1000),
+            ]))
+            .with_precision_and_scale(38, 10)
+            .unwrap(),
+        );
+        let kvs = KeyValues {
+            sequence: 10,
+            op_type: OpType::Put,
+            start_index_in_batch: 0,
+            keys: vec![Arc::new(StringVector::from(vec!["a", "a", "b", "b"])) as _],
+            values: vec![prices.clone()],
+            timestamp: Some(Arc::new(TimestampMillisecondVector::from_values([
+                100, 200, 100, 200,
+            ])) as _),
+        };
+        memtable.write(&kvs).unwrap();
+
+        let dir = create_temp_dir("decimal-round-trip");
+        let object_store = create_object_store(dir.path().to_str().unwrap());
+        let file_handle = new_file_handle(FileId::random());
+        let sst_file_name = file_handle.file_name();
+        let iter = memtable.iter(IterContext::default()).unwrap();
+        let writer = ParquetWriter::new(&sst_file_name, Source::Iter(iter), object_store.clone());
+        let sst_info = writer
+            .write_sst(&sst::WriteOptions::default())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(4, sst_info.num_rows);
+
+        let projected_schema = Arc::new(ProjectedSchema::new(schema, None).unwrap());
+        let store_schema = projected_schema.schema_to_read().clone();
+        let price_index = store_schema.schema().column_index_by_name("price").unwrap();
+        let reader = ParquetReader::new(
+            file_handle,
+            object_store,
+            projected_schema,
+            Predicate::empty(store_schema.schema().clone()),
+            TimestampRange::min_to_max(),
+        );
+        let mut stream = reader.chunk_stream().await.unwrap();
+        let batch = stream.next_batch().await.unwrap().unwrap();
+        assert!(stream.next_batch().await.unwrap().is_none());
+
+        let column = batch.column(price_index);
+        assert_eq!(
+            ConcreteDataType::decimal128_datatype(38, 10),
+            column.data_type()
+        );
+        assert_eq!(prices, **column);
+    }
 }
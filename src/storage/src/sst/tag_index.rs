@@ -0,0 +1,519 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Inverted index of tag columns in SSTs.
+//!
+//! The index maps each value of string tag columns in an SST to row groups
+//! that contain the value. It is stored in a sidecar file next to the SST so
+//! point lookups on tags can skip row groups and files without reading them.
+//!
+//! Columns are indexed by column id so the index stays valid after columns are
+//! renamed. Columns with more than [MAX_INDEXED_VALUES] distinct values are not
+//! indexed, as listing all their values costs more than the index saves.
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+
+use datafusion_common::ScalarValue;
+use datafusion_expr::Operator;
+use datafusion_physical_expr::expressions::{BinaryExpr, Column, InListExpr, Literal};
+use datafusion_physical_expr::PhysicalExpr;
+use datatypes::prelude::{ConcreteDataType, ScalarVector};
+use datatypes::vectors::StringVector;
+use serde::{Deserialize, Serialize};
+use snafu::ResultExt;
+use store_api::storage::ColumnId;
+use table::predicate::Predicate;
+
+use crate::error::{DecodeJsonSnafu, EncodeJsonSnafu, Result};
+use crate::read::Batch;
+use crate::schema::StoreSchema;
+
+/// Max number of distinct values of a column to index.
+pub const MAX_INDEXED_VALUES: usize = 4096;
+
+/// Inverted index of tag columns in an SST.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TagIndex {
+    /// Number of row groups in the SST.
+    num_row_groups: usize,
+    /// Row groups of each value, indexed by column id and then by value.
+    columns: HashMap<ColumnId, HashMap<String, Vec<usize>>>,
+}
+
+impl TagIndex {
+    /// Encodes the index into bytes.
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        serde_json::to_vec(self).context(EncodeJsonSnafu)
+    }
+
+    /// Decodes the index from bytes.
+    pub fn decode(bytes: &[u8]) -> Result<TagIndex> {
+        serde_json::from_slice(bytes).context(DecodeJsonSnafu)
+    }
+
+    /// Evaluates the predicate against the index, column names in the predicate are
+    /// resolved to column ids by the `store_schema` of the region.
+    ///
+    /// Returns a vector of boolean values, among which `false` means the row group
+    /// can be skipped.
+    pub fn prune_row_groups(&self, predicate: &Predicate, store_schema: &StoreSchema) -> Vec<bool> {
+        let mut res = vec![true; self.num_row_groups];
+        for expr in predicate.exprs() {
+            if let Some(row_groups) = self.row_groups_of(expr, store_schema) {
+                for (idx, valid) in res.iter_mut().enumerate() {
+                    *valid &= row_groups.contains(&idx);
+                }
+            }
+        }
+        res
+    }
+
+    /// Returns row groups that may contain rows matching the `expr`, or `None` if
+    /// the index can't tell.
+    fn row_groups_of(
+        &self,
+        expr: &Arc<dyn PhysicalExpr>,
+        store_schema: &StoreSchema,
+    ) -> Option<BTreeSet<usize>> {
+        if let Some(binary) = expr.as_any().downcast_ref::<BinaryExpr>() {
+            return match binary.op() {
+                Operator::And => {
+                    match (
+                        self.row_groups_of(binary.left(), store_schema),
+                        self.row_groups_of(binary.right(), store_schema),
+                    ) {
+                        (Some(left), Some(right)) => {
+                            Some(left.intersection(&right).copied().collect())
+                        }
+                        (Some(row_groups), None) | (None, Some(row_groups)) => Some(row_groups),
+                        (None, None) => None,
+                    }
+                }
+                Operator::Or => {
+                    let mut left = self.row_groups_of(binary.left(), store_schema)?;
+                    left.extend(self.row_groups_of(binary.right(), store_schema)?);
+                    Some(left)
+                }
+                Operator::Eq => {
+                    let (column, value) = column_and_string(binary.left(), binary.right())
+                        .or_else(|| column_and_string(binary.right(), binary.left()))?;
+                    self.row_groups_of_values(store_schema, column, [value])
+                }
+                _ => None,
+            };
+        }
+
+        if let Some(in_list) = expr.as_any().downcast_ref::<InListExpr>() {
+            if in_list.negated() {
+                return None;
+            }
+            let column = in_list.expr().as_any().downcast_ref::<Column>()?;
+            let values = in_list
+                .list()
+                .iter()
+                .map(string_literal)
+                .collect::<Option<Vec<_>>>()?;
+            return self.row_groups_of_values(store_schema, column.name(), values);
+        }
+
+        None
+    }
+
+    /// Returns row groups that contain any of the `values` of the `column`, or
+    /// `None` if the column is not indexed.
+    fn row_groups_of_values<'a>(
+        &self,
+        store_schema: &StoreSchema,
+        column: &str,
+        values: impl IntoIterator<Item = &'a str>,
+    ) -> Option<BTreeSet<usize>> {
+        let column_id = store_schema
+            .columns()
+            .iter()
+            .find(|column_meta| column_meta.desc.name == column)?
+            .desc
+            .id;
+        let index = self.columns.get(&column_id)?;
+        Some(
+            values
+                .into_iter()
+                .filter_map(|value| index.get(value))
+                .flatten()
+                .copied()
+                .collect(),
+        )
+    }
+}
+
+/// Returns the column name and the string value if `column` is a column and
+/// `value` is a string literal.
+fn column_and_string<'a>(
+    column: &'a Arc<dyn PhysicalExpr>,
+    value: &'a Arc<dyn PhysicalExpr>,
+) -> Option<(&'a str, &'a str)> {
+    let column = column.as_any().downcast_ref::<Column>()?;
+    Some((column.name(), string_literal(value)?))
+}
+
+/// Returns the string value if the `expr` is a string literal.
+fn string_literal(expr: &Arc<dyn PhysicalExpr>) -> Option<&str> {
+    match expr.as_any().downcast_ref::<Literal>()?.value() {
+        ScalarValue::Utf8(Some(value)) | ScalarValue::LargeUtf8(Some(value)) => Some(value),
+        _ => None,
+    }
+}
+
+/// Builds a [TagIndex] from batches written to an SST.
+pub struct TagIndexBuilder {
+    columns: Vec<ColumnIndexBuilder>,
+    /// Columns with more distinct values than this are not indexed.
+    max_values: usize,
+}
+
+/// Values of an indexed column in all rows written.
+struct ColumnIndexBuilder {
+    /// Index of the column in batches.
+    index: usize,
+    id: ColumnId,
+    /// Ids of distinct values.
+    value_ids: HashMap<String, usize>,
+    /// Value id of each row, `None` for null.
+    rows: Vec<Option<usize>>,
+}
+
+impl TagIndexBuilder {
+    /// Returns a builder that indexes string tag columns in the `store_schema`, or
+    /// `None` if there is no such column.
+    pub fn new(store_schema: &StoreSchema) -> Option<TagIndexBuilder> {
+        let columns: Vec<_> = store_schema.columns()[..store_schema.timestamp_index()]
+            .iter()
+            .enumerate()
+            .filter(|(_, column)| matches!(column.desc.data_type, ConcreteDataType::String(_)))
+            .map(|(index, column)| ColumnIndexBuilder {
+                index,
+                id: column.desc.id,
+                value_ids: HashMap::new(),
+                rows: Vec::new(),
+            })
+            .collect();
+
+        (!columns.is_empty()).then_some(TagIndexBuilder {
+            columns,
+            max_values: MAX_INDEXED_VALUES,
+        })
+    }
+
+    #[cfg(test)]
+    fn with_max_values(mut self, max_values: usize) -> Self {
+        self.max_values = max_values;
+        self
+    }
+
+    /// Records values of indexed columns in the `batch`.
+    pub fn update(&mut self, batch: &Batch) {
+        for column in &mut self.columns {
+            let Some(vector) = batch.column(column.index).as_any().downcast_ref::<StringVector>() else {
+                continue;
+            };
+            for value in vector.iter_data() {
+                let id = value.map(|value| match column.value_ids.get(value) {
+                    Some(id) => *id,
+                    None => {
+                        let id = column.value_ids.len();
+                        let _ = column.value_ids.insert(value.to_string(), id);
+                        id
+                    }
+                });
+                column.rows.push(id);
+            }
+        }
+        // Stops indexing columns with too many distinct values.
+        let max_values = self.max_values;
+        self.columns
+            .retain(|column| column.value_ids.len() <= max_values);
+    }
+
+    /// Builds the index with numbers of rows in each row group of the SST.
+    ///
+    /// Returns `None` if no column is left to index.
+    pub fn build(self, row_group_sizes: impl IntoIterator<Item = usize>) -> Option<TagIndex> {
+        if self.columns.is_empty() {
+            return None;
+        }
+
+        let row_group_sizes: Vec<_> = row_group_sizes.into_iter().collect();
+        let columns = self
+            .columns
+            .into_iter()
+            .map(|column| {
+                let mut row_groups_of_ids = vec![Vec::new(); column.value_ids.len()];
+                let mut start = 0;
+                for (row_group, size) in row_group_sizes.iter().enumerate() {
+                    let end = (start + size).min(column.rows.len());
+                    let ids: BTreeSet<_> = column.rows[start..end].iter().flatten().collect();
+                    for id in ids {
+                        row_groups_of_ids[*id].push(row_group);
+                    }
+                    start = end;
+                }
+
+                let values = column
+                    .value_ids
+                    .into_iter()
+                    .map(|(value, id)| (value, std::mem::take(&mut row_groups_of_ids[id])))
+                    .collect();
+                (column.id, values)
+            })
+            .collect();
+
+        Some(TagIndex {
+            num_row_groups: row_group_sizes.len(),
+            columns,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion_expr::{col, lit, Expr as DfExpr};
+    use datatypes::schema::{ColumnSchema, Schema};
+    use datatypes::type_id::LogicalTypeId;
+    use datatypes::vectors::{TimestampMillisecondVector, UInt64Vector, UInt8Vector};
+
+    use super::*;
+    use crate::metadata::RegionMetadata;
+    use crate::test_util::descriptor_util::RegionDescBuilder;
+
+    fn new_metadata() -> RegionMetadata {
+        RegionDescBuilder::new("test")
+            .push_key_column(("host", LogicalTypeId::String, true))
+            .push_field_column(("v0", LogicalTypeId::UInt64, true))
+            .build()
+            .try_into()
+            .unwrap()
+    }
+
+    fn column_id(store_schema: &StoreSchema, name: &str) -> ColumnId {
+        store_schema
+            .columns()
+            .iter()
+            .find(|column| column.desc.name == name)
+            .unwrap()
+            .desc
+            .id
+    }
+
+    fn new_index() -> TagIndex {
+        let host = HashMap::from([
+            ("a".to_string(), vec![0]),
+            ("b".to_string(), vec![0, 1]),
+            ("c".to_string(), vec![2]),
+        ]);
+        let host_id = column_id(new_metadata().schema().store_schema(), "host");
+        TagIndex {
+            num_row_groups: 3,
+            columns: HashMap::from([(host_id, host)]),
+        }
+    }
+
+    fn prune_with_schema(
+        index: &TagIndex,
+        store_schema: &StoreSchema,
+        filters: Vec<DfExpr>,
+    ) -> Vec<bool> {
+        let schema = Arc::new(Schema::new(
+            store_schema
+                .columns()
+                .iter()
+                .map(|column| {
+                    ColumnSchema::new(&column.desc.name, column.desc.data_type.clone(), true)
+                })
+                .collect(),
+        ));
+        let predicate =
+            Predicate::try_new(filters.into_iter().map(Into::into).collect(), schema).unwrap();
+        index.prune_row_groups(&predicate, store_schema)
+    }
+
+    fn prune(index: &TagIndex, filters: Vec<DfExpr>) -> Vec<bool> {
+        prune_with_schema(index, new_metadata().schema().store_schema(), filters)
+    }
+
+    #[test]
+    fn test_prune_eq() {
+        let index = new_index();
+
+        assert_eq!(
+            vec![true, true, false],
+            prune(&index, vec![col("host").eq(lit("b"))])
+        );
+        assert_eq!(
+            vec![true, true, false],
+            prune(&index, vec![lit("b").eq(col("host"))])
+        );
+        assert_eq!(
+            vec![false, false, false],
+            prune(&index, vec![col("host").eq(lit("x"))])
+        );
+        // Filters the index can't evaluate keep all row groups.
+        assert_eq!(
+            vec![true, true, true],
+            prune(&index, vec![col("host").not_eq(lit("b"))])
+        );
+        assert_eq!(
+            vec![true, true, true],
+            prune(&index, vec![col("v0").eq(lit(1u64))])
+        );
+    }
+
+    #[test]
+    fn test_prune_in_list() {
+        let index = new_index();
+
+        assert_eq!(
+            vec![true, false, true],
+            prune(
+                &index,
+                vec![col("host").in_list(vec![lit("a"), lit("c")], false)]
+            )
+        );
+        assert_eq!(
+            vec![true, true, true],
+            prune(&index, vec![col("host").in_list(vec![lit("a")], true)])
+        );
+    }
+
+    #[test]
+    fn test_prune_and_or() {
+        let index = new_index();
+
+        assert_eq!(
+            vec![true, false, false],
+            prune(
+                &index,
+                vec![col("host").eq(lit("a")).and(col("host").eq(lit("b")))]
+            )
+        );
+        assert_eq!(
+            vec![true, true, false],
+            prune(
+                &index,
+                vec![col("host").eq(lit("b")).and(col("v0").gt(lit(1u64)))]
+            )
+        );
+        assert_eq!(
+            vec![true, false, true],
+            prune(
+                &index,
+                vec![col("host").eq(lit("a")).or(col("host").eq(lit("c")))]
+            )
+        );
+        assert_eq!(
+            vec![true, true, true],
+            prune(
+                &index,
+                vec![col("host").eq(lit("a")).or(col("v0").gt(lit(1u64)))]
+            )
+        );
+        // Multiple filters are combined by `AND`.
+        assert_eq!(
+            vec![false, false, true],
+            prune(
+                &index,
+                vec![
+                    col("host").in_list(vec![lit("b"), lit("c")], false),
+                    col("host").eq(lit("c")),
+                ]
+            )
+        );
+    }
+
+    fn new_batch(hosts: &[Option<&str>]) -> Batch {
+        let num_rows = hosts.len();
+        Batch::new(vec![
+            Arc::new(StringVector::from(hosts.to_vec())),
+            Arc::new(TimestampMillisecondVector::from_values(0..num_rows as i64)),
+            Arc::new(UInt64Vector::from_vec(vec![1; num_rows])),
+            Arc::new(UInt64Vector::from_vec(vec![1; num_rows])),
+            Arc::new(UInt8Vector::from_vec(vec![1; num_rows])),
+        ])
+    }
+
+    #[test]
+    fn test_prune_renamed_column() {
+        let index = new_index();
+        // `host` is renamed to `h` and a new column is named `host`.
+        let metadata: RegionMetadata = RegionDescBuilder::new("test")
+            .push_key_column(("h", LogicalTypeId::String, true))
+            .push_key_column(("host", LogicalTypeId::String, true))
+            .push_field_column(("v0", LogicalTypeId::UInt64, true))
+            .build()
+            .try_into()
+            .unwrap();
+        let store_schema = metadata.schema().store_schema();
+        assert_eq!(
+            column_id(new_metadata().schema().store_schema(), "host"),
+            column_id(store_schema, "h")
+        );
+
+        assert_eq!(
+            vec![true, true, false],
+            prune_with_schema(&index, store_schema, vec![col("h").eq(lit("b"))])
+        );
+        // The new `host` column is not indexed.
+        assert_eq!(
+            vec![true, true, true],
+            prune_with_schema(&index, store_schema, vec![col("host").eq(lit("x"))])
+        );
+    }
+
+    #[test]
+    fn test_build_index() {
+        let metadata = new_metadata();
+        let store_schema = metadata.schema().store_schema();
+
+        let mut builder = TagIndexBuilder::new(store_schema).unwrap();
+        builder.update(&new_batch(&[Some("a"), Some("b"), Some("b")]));
+        builder.update(&new_batch(&[Some("b"), None, Some("c")]));
+        let index = builder.build([2, 2, 2]).unwrap();
+
+        assert_eq!(new_index(), index);
+        assert_eq!(index, TagIndex::decode(&index.encode().unwrap()).unwrap());
+    }
+
+    #[test]
+    fn test_skip_high_cardinality_column() {
+        let metadata = new_metadata();
+        let store_schema = metadata.schema().store_schema();
+
+        let mut builder = TagIndexBuilder::new(store_schema)
+            .unwrap()
+            .with_max_values(2);
+        builder.update(&new_batch(&[Some("a"), Some("b"), Some("a")]));
+        builder.update(&new_batch(&[Some("c")]));
+        assert!(builder.build([4]).is_none());
+    }
+
+    #[test]
+    fn test_no_tag_to_index() {
+        let metadata: RegionMetadata = RegionDescBuilder::new("test")
+            .push_field_column(("v0", LogicalTypeId::UInt64, true))
+            .build()
+            .try_into()
+            .unwrap();
+
+        assert!(TagIndexBuilder::new(metadata.schema().store_schema()).is_none());
+    }
+}
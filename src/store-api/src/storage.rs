@@ -30,11 +30,11 @@ pub use datatypes::schema::{
     ColumnDefaultConstraint, ColumnSchema, Schema, SchemaBuilder, SchemaRef,
 };
 
-pub use self::chunk::{Chunk, ChunkReader};
+pub use self::chunk::{Chunk, ChunkReader, ScanMetrics, ScanMetricsRef};
 pub use self::descriptors::*;
 pub use self::engine::{
-    CloseOptions, CompactionStrategy, CreateOptions, EngineContext, OpenOptions, StorageEngine,
-    TwcsOptions,
+    CloseOptions, CompactionStrategy, CreateOptions, EngineContext, OpenOptions, RegionOptions,
+    StorageEngine, TwcsOptions,
 };
 pub use self::metadata::RegionMeta;
 pub use self::region::{
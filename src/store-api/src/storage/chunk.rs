@@ -12,6 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use common_error::ext::ErrorExt;
 use common_recordbatch::OrderOption;
@@ -50,4 +53,38 @@ pub trait ChunkReader: Send {
     fn output_ordering(&self) -> Option<Vec<OrderOption>> {
         None
     }
+
+    /// Metrics of the scan, updated while the reader is being read.
+    fn metrics(&self) -> Option<ScanMetricsRef> {
+        None
+    }
+}
+
+/// Counters of what a [ChunkReader] actually reads from storage.
+#[derive(Debug, Default)]
+pub struct ScanMetrics {
+    sst_files: AtomicUsize,
+    row_groups: AtomicUsize,
+}
+
+pub type ScanMetricsRef = Arc<ScanMetrics>;
+
+impl ScanMetrics {
+    pub fn add_sst_files(&self, num: usize) {
+        let _ = self.sst_files.fetch_add(num, Ordering::Relaxed);
+    }
+
+    pub fn add_row_groups(&self, num: usize) {
+        let _ = self.row_groups.fetch_add(num, Ordering::Relaxed);
+    }
+
+    /// Number of SST files to read after pruning by time range.
+    pub fn sst_files(&self) -> usize {
+        self.sst_files.load(Ordering::Relaxed)
+    }
+
+    /// Number of parquet row groups to read after pruning by predicates.
+    pub fn row_groups(&self) -> usize {
+        self.row_groups.load(Ordering::Relaxed)
+    }
 }
@@ -117,6 +117,15 @@ pub struct OpenOptions {
     pub compaction_strategy: CompactionStrategy,
 }
 
+/// Options of an opened region that could be changed at runtime.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RegionOptions {
+    /// Region memtable max size in bytes, `None` falls back to the engine default
+    pub write_buffer_size: Option<usize>,
+    /// Region SST files TTL, `None` falls back to the engine default
+    pub ttl: Option<Duration>,
+}
+
 /// Options to close a region.
 #[derive(Debug, Clone, Default)]
 pub struct CloseOptions {
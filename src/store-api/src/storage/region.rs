@@ -35,7 +35,7 @@
 use async_trait::async_trait;
 use common_error::ext::ErrorExt;
 
-use crate::storage::engine::OpenOptions;
+use crate::storage::engine::{OpenOptions, RegionOptions};
 use crate::storage::metadata::RegionMeta;
 use crate::storage::requests::{AlterRequest, WriteRequest};
 use crate::storage::responses::WriteResponse;
@@ -73,6 +73,10 @@ pub trait Region: Send + Sync + Clone + std::fmt::Debug + 'static {
 
     async fn alter(&self, request: AlterRequest) -> Result<(), Self::Error>;
 
+    /// Updates the options of the opened region, takes effect on following writes,
+    /// flushes and compactions.
+    async fn set_options(&self, options: RegionOptions) -> Result<(), Self::Error>;
+
     async fn drop_region(&self) -> Result<(), Self::Error>;
 
     fn disk_usage_bytes(&self) -> u64;
@@ -81,6 +85,7 @@ pub trait Region: Send + Sync + Clone + std::fmt::Debug + 'static {
         RegionStat {
             region_id: self.id().into(),
             disk_usage_bytes: self.disk_usage_bytes(),
+            ..Default::default()
         }
     }
 
@@ -93,7 +98,12 @@ pub trait Region: Send + Sync + Clone + std::fmt::Debug + 'static {
 #[derive(Default, Debug)]
 pub struct RegionStat {
     pub region_id: u64,
+    /// Size of the SST files.
     pub disk_usage_bytes: u64,
+    /// Approximate number of rows in SST files and memtables.
+    pub num_rows: u64,
+    /// Bytes allocated by memtables.
+    pub memtable_size: u64,
 }
 
 /// Context for write operations.
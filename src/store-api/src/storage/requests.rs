@@ -17,6 +17,7 @@ use std::collections::{HashMap, HashSet};
 use common_error::ext::ErrorExt;
 use common_query::logical_plan::Expr;
 use common_recordbatch::OrderOption;
+use datatypes::prelude::ConcreteDataType;
 use datatypes::vectors::VectorRef;
 
 use crate::storage::{ColumnDescriptor, RegionDescriptor, SequenceNumber};
@@ -84,6 +85,20 @@ pub enum AlterOperation {
         /// Name of columns to drop.
         names: Vec<String>,
     },
+    /// Change the data type of a value column, the column keeps its id.
+    ModifyColumnType {
+        /// Name of the column to modify.
+        name: String,
+        /// New data type of the column.
+        data_type: ConcreteDataType,
+    },
+    /// Rename a column, the column keeps its id.
+    RenameColumn {
+        /// Name of the column to rename.
+        name: String,
+        /// New name of the column.
+        new_name: String,
+    },
 }
 
 impl AlterOperation {
@@ -96,6 +111,16 @@ impl AlterOperation {
             AlterOperation::DropColumns { names } => {
                 Self::apply_drop(names, descriptor);
             }
+            AlterOperation::ModifyColumnType { name, data_type } => {
+                if let Some(column) = Self::find_column_mut(name, descriptor) {
+                    column.data_type = data_type.clone();
+                }
+            }
+            AlterOperation::RenameColumn { name, new_name } => {
+                if let Some(column) = Self::find_column_mut(name, descriptor) {
+                    column.name = new_name.clone();
+                }
+            }
         }
     }
 
@@ -127,6 +152,25 @@ impl AlterOperation {
             cf.columns.retain(|col| !name_set.contains(&col.name));
         }
     }
+
+    /// Finds the column named `name` in all columns of the [RegionDescriptor].
+    fn find_column_mut<'a>(
+        name: &str,
+        descriptor: &'a mut RegionDescriptor,
+    ) -> Option<&'a mut ColumnDescriptor> {
+        let RegionDescriptor {
+            row_key,
+            default_cf,
+            extra_cfs,
+            ..
+        } = descriptor;
+
+        std::iter::once(&mut row_key.timestamp)
+            .chain(row_key.columns.iter_mut())
+            .chain(default_cf.columns.iter_mut())
+            .chain(extra_cfs.iter_mut().flat_map(|cf| cf.columns.iter_mut()))
+            .find(|col| col.name == name)
+    }
 }
 
 /// Alter region request.
@@ -213,5 +257,31 @@ mod tests {
         op.apply(&mut desc);
         assert_eq!(1, desc.row_key.columns.len());
         assert_eq!(1, desc.default_cf.columns.len());
+
+        let op = AlterOperation::ModifyColumnType {
+            name: String::from("4"),
+            data_type: ConcreteDataType::float64_datatype(),
+        };
+        op.apply(&mut desc);
+        assert_eq!(4, desc.default_cf.columns[0].id);
+        assert_eq!(
+            ConcreteDataType::float64_datatype(),
+            desc.default_cf.columns[0].data_type
+        );
+
+        let op = AlterOperation::RenameColumn {
+            name: String::from("3"),
+            new_name: String::from("k3"),
+        };
+        op.apply(&mut desc);
+        assert_eq!(3, desc.row_key.columns[0].id);
+        assert_eq!("k3", desc.row_key.columns[0].name);
+
+        let op = AlterOperation::RenameColumn {
+            name: String::from("1"),
+            new_name: String::from("ts"),
+        };
+        op.apply(&mut desc);
+        assert_eq!("ts", desc.row_key.timestamp.name);
     }
 }
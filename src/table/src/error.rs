@@ -18,6 +18,7 @@ use common_error::ext::{BoxedError, ErrorExt};
 use common_error::status_code::StatusCode;
 use datafusion::error::DataFusionError;
 use datatypes::arrow::error::ArrowError;
+use datatypes::prelude::ConcreteDataType;
 use snafu::{Location, Snafu};
 
 use crate::metadata::TableId;
@@ -93,6 +94,32 @@ pub enum Error {
         location: Location,
     },
 
+    #[snafu(display(
+        "Not allowed to modify type of index column {} in table {}",
+        column_name,
+        table_name
+    ))]
+    ModifyColumnInIndex {
+        column_name: String,
+        table_name: String,
+        location: Location,
+    },
+
+    #[snafu(display(
+        "Column {} in table {} could not be modified from {:?} type to {:?} type",
+        column_name,
+        table_name,
+        from,
+        to
+    ))]
+    IncompatibleColumnType {
+        column_name: String,
+        table_name: String,
+        from: ConcreteDataType,
+        to: ConcreteDataType,
+        location: Location,
+    },
+
     #[snafu(display(
         "Failed to build column descriptor for table: {}, column: {}, source: {}",
         table_name,
@@ -141,9 +168,10 @@ impl ErrorExt for Error {
             Error::Datafusion { .. }
             | Error::SchemaConversion { .. }
             | Error::TableProjection { .. } => StatusCode::EngineExecuteQuery,
-            Error::RemoveColumnInIndex { .. } | Error::BuildColumnDescriptor { .. } => {
-                StatusCode::InvalidArguments
-            }
+            Error::RemoveColumnInIndex { .. }
+            | Error::ModifyColumnInIndex { .. }
+            | Error::IncompatibleColumnType { .. }
+            | Error::BuildColumnDescriptor { .. } => StatusCode::InvalidArguments,
             Error::TablesRecordBatch { .. } | Error::DuplicatedExecuteCall { .. } => {
                 StatusCode::Unexpected
             }
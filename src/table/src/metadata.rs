@@ -19,15 +19,17 @@ use chrono::{DateTime, Utc};
 use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
 use common_query::AddColumnLocation;
 use datafusion_expr::TableProviderFilterPushDown;
+use datatypes::arrow::compute::can_cast_types;
+use datatypes::data_type::{ConcreteDataType, DataType};
 pub use datatypes::error::{Error as ConvertError, Result as ConvertResult};
 use datatypes::schema::{ColumnSchema, RawSchema, Schema, SchemaBuilder, SchemaRef};
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
-use snafu::{ensure, ResultExt};
+use snafu::{ensure, OptionExt, ResultExt};
 use store_api::storage::{ColumnDescriptor, ColumnDescriptorBuilder, ColumnId};
 
 use crate::error::{self, Result};
-use crate::requests::{AddColumnRequest, AlterKind, TableOptions};
+use crate::requests::{AddColumnRequest, AlterKind, TableOptions, REGIONS_KEY};
 
 pub type TableId = u32;
 pub type TableVersion = u64;
@@ -193,6 +195,14 @@ impl TableMeta {
                     .next_column_id(self.next_column_id);
                 Ok(meta_builder)
             }
+            AlterKind::SetTableOptions { options } => self.set_table_options(options),
+            AlterKind::ModifyColumnType {
+                column_name,
+                target_type,
+            } => self.modify_column_type(table_name, column_name, target_type),
+            AlterKind::RenameColumn { name, new_name } => {
+                self.rename_column(table_name, name, new_name)
+            }
         }
     }
 
@@ -387,6 +397,136 @@ impl TableMeta {
         Ok(meta_builder)
     }
 
+    fn set_table_options(&self, options: &HashMap<String, String>) -> Result<TableMetaBuilder> {
+        ensure!(
+            !options.contains_key(REGIONS_KEY),
+            error::UnsupportedSnafu {
+                operation: "altering regions of a table",
+            }
+        );
+
+        // Options not being set are left unchanged.
+        let mut new_options = HashMap::from(&self.options);
+        new_options.extend(options.iter().map(|(k, v)| (k.clone(), v.clone())));
+        let new_options = TableOptions::try_from(&new_options)?;
+
+        let mut meta_builder = self.new_meta_builder();
+        let _ = meta_builder
+            .schema(self.schema.clone())
+            .primary_key_indices(self.primary_key_indices.clone())
+            .options(new_options);
+
+        Ok(meta_builder)
+    }
+
+    fn modify_column_type(
+        &self,
+        table_name: &str,
+        column_name: &str,
+        target_type: &ConcreteDataType,
+    ) -> Result<TableMetaBuilder> {
+        let table_schema = &self.schema;
+        let index = table_schema.column_index_by_name(column_name).context(
+            error::ColumnNotExistsSnafu {
+                column_name,
+                table_name,
+            },
+        )?;
+        ensure!(
+            !self.primary_key_indices.contains(&index)
+                && table_schema.timestamp_index() != Some(index),
+            error::ModifyColumnInIndexSnafu {
+                column_name,
+                table_name,
+            }
+        );
+
+        let column_schema = &table_schema.column_schemas()[index];
+        ensure!(
+            can_cast_types(
+                &column_schema.data_type.as_arrow_type(),
+                &target_type.as_arrow_type()
+            ),
+            error::IncompatibleColumnTypeSnafu {
+                column_name,
+                table_name,
+                from: column_schema.data_type.clone(),
+                to: target_type.clone(),
+            }
+        );
+
+        let mut new_column = column_schema.clone();
+        new_column.data_type = target_type.clone();
+        // Validates the default constraint against the new type.
+        let new_column = new_column
+            .with_default_constraint(column_schema.default_constraint().cloned())
+            .with_context(|_| error::SchemaBuildSnafu {
+                msg: format!(
+                    "Table {table_name} cannot modify column {column_name} to {target_type:?} type"
+                ),
+            })?;
+        let mut columns = table_schema.column_schemas().to_vec();
+        columns[index] = new_column;
+
+        self.builder_with_columns(table_name, columns)
+    }
+
+    fn rename_column(
+        &self,
+        table_name: &str,
+        column_name: &str,
+        new_name: &str,
+    ) -> Result<TableMetaBuilder> {
+        let table_schema = &self.schema;
+        let index = table_schema.column_index_by_name(column_name).context(
+            error::ColumnNotExistsSnafu {
+                column_name,
+                table_name,
+            },
+        )?;
+        ensure!(
+            !table_schema.contains_column(new_name),
+            error::ColumnExistsSnafu {
+                column_name: new_name,
+                table_name,
+            }
+        );
+
+        let mut columns = table_schema.column_schemas().to_vec();
+        columns[index].name = new_name.to_string();
+
+        self.builder_with_columns(table_name, columns)
+    }
+
+    /// Returns a builder with a new schema built from `columns`, the columns should
+    /// keep the same order as the current schema so the indices are still valid.
+    fn builder_with_columns(
+        &self,
+        table_name: &str,
+        columns: Vec<ColumnSchema>,
+    ) -> Result<TableMetaBuilder> {
+        let table_schema = &self.schema;
+        let mut builder = SchemaBuilder::try_from_columns(columns)
+            .with_context(|_| error::SchemaBuildSnafu {
+                msg: format!("Failed to convert column schemas into schema for table {table_name}"),
+            })?
+            // Also bump the schema version.
+            .version(table_schema.version() + 1);
+        for (k, v) in table_schema.metadata().iter() {
+            builder = builder.add_metadata(k, v);
+        }
+        let new_schema = builder.build().with_context(|_| error::SchemaBuildSnafu {
+            msg: format!("Table {table_name} cannot alter columns"),
+        })?;
+
+        let mut meta_builder = self.new_meta_builder();
+        let _ = meta_builder
+            .schema(Arc::new(new_schema))
+            .primary_key_indices(self.primary_key_indices.clone());
+
+        Ok(meta_builder)
+    }
+
     /// Split requests into different groups using column location info.
     fn split_requests_by_column_location<'a>(
         &self,
@@ -766,6 +906,169 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_set_table_options() {
+        let schema = Arc::new(new_test_schema());
+        let meta = TableMetaBuilder::default()
+            .schema(schema.clone())
+            .primary_key_indices(vec![0])
+            .engine("engine")
+            .next_column_id(3)
+            .options(TableOptions {
+                write_buffer_size: Some("1MB".parse().unwrap()),
+                ..Default::default()
+            })
+            .build()
+            .unwrap();
+
+        let alter_kind = AlterKind::SetTableOptions {
+            options: HashMap::from([("ttl".to_string(), "30d".to_string())]),
+        };
+        let new_meta = meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(schema, new_meta.schema);
+        assert_eq!(
+            Some(std::time::Duration::from_secs(30 * 24 * 3600)),
+            new_meta.options.ttl
+        );
+        assert_eq!(
+            meta.options.write_buffer_size,
+            new_meta.options.write_buffer_size
+        );
+
+        let alter_kind = AlterKind::SetTableOptions {
+            options: HashMap::from([("ttl".to_string(), "abc".to_string())]),
+        };
+        let err = meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .err()
+            .unwrap();
+        assert_eq!(StatusCode::InvalidArguments, err.status_code());
+
+        let alter_kind = AlterKind::SetTableOptions {
+            options: HashMap::from([("regions".to_string(), "2".to_string())]),
+        };
+        let err = meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .err()
+            .unwrap();
+        assert_eq!(StatusCode::Unsupported, err.status_code());
+    }
+
+    #[test]
+    fn test_modify_column_type() {
+        let schema = Arc::new(new_test_schema());
+        let meta = TableMetaBuilder::default()
+            .schema(schema.clone())
+            .primary_key_indices(vec![0])
+            .engine("engine")
+            .next_column_id(3)
+            .build()
+            .unwrap();
+
+        let alter_kind = AlterKind::ModifyColumnType {
+            column_name: "col2".to_string(),
+            target_type: ConcreteDataType::int64_datatype(),
+        };
+        let new_meta = meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .unwrap()
+            .build()
+            .unwrap();
+        let column_schema = new_meta.schema.column_schema_by_name("col2").unwrap();
+        assert_eq!(ConcreteDataType::int64_datatype(), column_schema.data_type);
+        assert_eq!(schema.version() + 1, new_meta.schema.version());
+        assert_eq!(meta.primary_key_indices, new_meta.primary_key_indices);
+        assert_eq!(meta.next_column_id, new_meta.next_column_id);
+
+        // Key columns are not allowed to modify.
+        for column_name in ["col1", "ts"] {
+            let alter_kind = AlterKind::ModifyColumnType {
+                column_name: column_name.to_string(),
+                target_type: ConcreteDataType::int64_datatype(),
+            };
+            let err = meta
+                .builder_with_alter_kind("my_table", &alter_kind)
+                .err()
+                .unwrap();
+            assert!(matches!(err, error::Error::ModifyColumnInIndex { .. }));
+        }
+
+        let alter_kind = AlterKind::ModifyColumnType {
+            column_name: "col2".to_string(),
+            target_type: ConcreteDataType::binary_datatype(),
+        };
+        let err = meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .err()
+            .unwrap();
+        assert!(matches!(err, error::Error::IncompatibleColumnType { .. }));
+
+        let alter_kind = AlterKind::ModifyColumnType {
+            column_name: "unknown".to_string(),
+            target_type: ConcreteDataType::int64_datatype(),
+        };
+        let err = meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .err()
+            .unwrap();
+        assert_eq!(StatusCode::TableColumnNotFound, err.status_code());
+    }
+
+    #[test]
+    fn test_rename_column() {
+        let schema = Arc::new(new_test_schema());
+        let meta = TableMetaBuilder::default()
+            .schema(schema.clone())
+            .primary_key_indices(vec![0])
+            .engine("engine")
+            .next_column_id(3)
+            .build()
+            .unwrap();
+
+        let alter_kind = AlterKind::RenameColumn {
+            name: "ts".to_string(),
+            new_name: "time".to_string(),
+        };
+        let new_meta = meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .unwrap()
+            .build()
+            .unwrap();
+        let names: Vec<_> = new_meta
+            .schema
+            .column_schemas()
+            .iter()
+            .map(|column_schema| column_schema.name.as_str())
+            .collect();
+        assert_eq!(&["col1", "time", "col2"], &names[..]);
+        assert_eq!("time", new_meta.schema.timestamp_column().unwrap().name);
+        assert_eq!(schema.version() + 1, new_meta.schema.version());
+
+        let alter_kind = AlterKind::RenameColumn {
+            name: "col1".to_string(),
+            new_name: "col2".to_string(),
+        };
+        let err = meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .err()
+            .unwrap();
+        assert_eq!(StatusCode::TableColumnExists, err.status_code());
+
+        let alter_kind = AlterKind::RenameColumn {
+            name: "unknown".to_string(),
+            new_name: "col3".to_string(),
+        };
+        let err = meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .err()
+            .unwrap();
+        assert_eq!(StatusCode::TableColumnNotFound, err.status_code());
+    }
+
     #[test]
     fn test_remove_multiple_columns_before_timestamp() {
         let column_schemas = vec![
@@ -66,6 +66,20 @@ macro_rules! impl_min_max_values {
                 if !stats.has_min_max_set() {
                     return None;
                 }
+                if let DataType::Decimal128(precision, scale) = data_type {
+                    // Decimals are stored as integers or fixed length bytes in parquet.
+                    let value = match stats {
+                        ParquetStats::Int32(s) => Some(*s.$min_max() as i128),
+                        ParquetStats::Int64(s) => Some(*s.$min_max() as i128),
+                        ParquetStats::FixedLenByteArray(s) => {
+                            paste! {
+                                decimal_from_be_bytes(s.[<$min_max _bytes>]())
+                            }
+                        }
+                        _ => None,
+                    };
+                    return value.map(|v| ScalarValue::Decimal128(Some(v), *precision, *scale));
+                }
                 match stats {
                     ParquetStats::Boolean(s) => Some(ScalarValue::Boolean(Some(*s.$min_max()))),
                     ParquetStats::Int32(s) => Some(ScalarValue::Int32(Some(*s.$min_max()))),
@@ -91,6 +105,17 @@ macro_rules! impl_min_max_values {
     }};
 }
 
+/// Decodes a big-endian two's complement decimal from parquet fixed length bytes.
+fn decimal_from_be_bytes(bytes: &[u8]) -> Option<i128> {
+    if bytes.is_empty() || bytes.len() > 16 {
+        return None;
+    }
+    let fill = if bytes[0] & 0x80 != 0 { 0xff } else { 0 };
+    let mut buf = [fill; 16];
+    buf[16 - bytes.len()..].copy_from_slice(bytes);
+    Some(i128::from_be_bytes(buf))
+}
+
 impl<'a> PruningStatistics for RowGroupPruningStatistics<'a> {
     fn min_values(&self, column: &Column) -> Option<ArrayRef> {
         impl_min_max_values!(self, column, min)
@@ -116,3 +141,17 @@ impl<'a> PruningStatistics for RowGroupPruningStatistics<'a> {
         Some(Arc::new(UInt64Array::from(values)))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decimal_from_be_bytes() {
+        assert_eq!(Some(12345), decimal_from_be_bytes(&12345i128.to_be_bytes()));
+        assert_eq!(Some(258), decimal_from_be_bytes(&[0x01, 0x02]));
+        assert_eq!(Some(-2), decimal_from_be_bytes(&[0xff, 0xfe]));
+        assert_eq!(None, decimal_from_be_bytes(&[]));
+        assert_eq!(None, decimal_from_be_bytes(&[0; 17]));
+    }
+}
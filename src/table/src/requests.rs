@@ -21,7 +21,7 @@ use std::time::Duration;
 use common_base::readable_size::ReadableSize;
 use common_query::AddColumnLocation;
 use common_time::range::TimestampRange;
-use datatypes::prelude::VectorRef;
+use datatypes::prelude::{ConcreteDataType, VectorRef};
 use datatypes::schema::{ColumnSchema, RawSchema};
 use serde::{Deserialize, Serialize};
 use store_api::storage::RegionNumber;
@@ -192,9 +192,27 @@ pub struct AddColumnRequest {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AlterKind {
-    AddColumns { columns: Vec<AddColumnRequest> },
-    DropColumns { names: Vec<String> },
-    RenameTable { new_table_name: String },
+    AddColumns {
+        columns: Vec<AddColumnRequest>,
+    },
+    DropColumns {
+        names: Vec<String>,
+    },
+    RenameTable {
+        new_table_name: String,
+    },
+    /// Sets table options, options not in `options` are left unchanged.
+    SetTableOptions {
+        options: HashMap<String, String>,
+    },
+    ModifyColumnType {
+        column_name: String,
+        target_type: ConcreteDataType,
+    },
+    RenameColumn {
+        name: String,
+        new_name: String,
+    },
 }
 
 /// Drop table request
@@ -24,7 +24,9 @@ use common_query::physical_plan::{Partitioning, PhysicalPlan, PhysicalPlanRef};
 use common_recordbatch::error::Result as RecordBatchResult;
 use common_recordbatch::{RecordBatch, RecordBatchStream, SendableRecordBatchStream};
 use datafusion::execution::context::TaskContext;
-use datafusion::physical_plan::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
+use datafusion::physical_plan::metrics::{
+    BaselineMetrics, ExecutionPlanMetricsSet, MetricBuilder, MetricsSet,
+};
 use datafusion_physical_expr::PhysicalSortExpr;
 use datatypes::schema::SchemaRef;
 use futures::{Stream, StreamExt};
@@ -101,6 +103,8 @@ impl PhysicalPlan for StreamScanAdapter {
         Ok(Box::pin(StreamWithMetricWrapper {
             stream,
             metric: baseline_metric,
+            metric_set: self.metric.clone(),
+            stream_metrics_recorded: false,
         }))
     }
 
@@ -112,6 +116,28 @@ impl PhysicalPlan for StreamScanAdapter {
 pub struct StreamWithMetricWrapper {
     stream: SendableRecordBatchStream,
     metric: BaselineMetrics,
+    metric_set: ExecutionPlanMetricsSet,
+    stream_metrics_recorded: bool,
+}
+
+impl StreamWithMetricWrapper {
+    /// Records the metrics reported by the wrapped stream (e.g. the number of SST
+    /// files scanned) into the plan's metrics set.
+    fn record_stream_metrics(&mut self) {
+        if self.stream_metrics_recorded {
+            return;
+        }
+        self.stream_metrics_recorded = true;
+
+        let Some(metrics) = self.stream.metrics() else { return };
+        for plan_metrics in metrics.plan_metrics {
+            for (name, value) in plan_metrics.metrics {
+                MetricBuilder::new(&self.metric_set)
+                    .global_counter(name)
+                    .add(value);
+            }
+        }
+    }
 }
 
 impl Stream for StreamWithMetricWrapper {
@@ -119,10 +145,16 @@ impl Stream for StreamWithMetricWrapper {
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let this = self.get_mut();
-        let _timer = this.metric.elapsed_compute().timer();
-        let poll = this.stream.poll_next_unpin(cx);
-        if let Poll::Ready(Option::Some(Result::Ok(record_batch))) = &poll {
-            this.metric.record_output(record_batch.num_rows());
+        let poll = {
+            let _timer = this.metric.elapsed_compute().timer();
+            let poll = this.stream.poll_next_unpin(cx);
+            if let Poll::Ready(Option::Some(Result::Ok(record_batch))) = &poll {
+                this.metric.record_output(record_batch.num_rows());
+            }
+            poll
+        };
+        if let Poll::Ready(None) = &poll {
+            this.record_stream_metrics();
         }
 
         poll
@@ -137,7 +169,7 @@ impl RecordBatchStream for StreamWithMetricWrapper {
 
 #[cfg(test)]
 mod test {
-    use common_recordbatch::{util, RecordBatch, RecordBatches};
+    use common_recordbatch::{util, PlanMetrics, RecordBatch, RecordBatchMetrics, RecordBatches};
     use datafusion::prelude::SessionContext;
     use datatypes::data_type::ConcreteDataType;
     use datatypes::schema::{ColumnSchema, Schema};
@@ -187,4 +219,66 @@ mod test {
             _ => unreachable!(),
         }
     }
+
+    #[tokio::test]
+    async fn test_scan_with_stream_metrics() {
+        struct StreamWithScanMetrics {
+            inner: SendableRecordBatchStream,
+        }
+
+        impl RecordBatchStream for StreamWithScanMetrics {
+            fn schema(&self) -> SchemaRef {
+                self.inner.schema()
+            }
+
+            fn metrics(&self) -> Option<RecordBatchMetrics> {
+                Some(RecordBatchMetrics {
+                    plan_metrics: vec![PlanMetrics {
+                        plan: "TestScan".to_string(),
+                        level: 0,
+                        metrics: vec![("sst_files".to_string(), 2)],
+                    }],
+                })
+            }
+        }
+
+        impl Stream for StreamWithScanMetrics {
+            type Item = RecordBatchResult<RecordBatch>;
+
+            fn poll_next(
+                mut self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+            ) -> Poll<Option<Self::Item>> {
+                self.inner.poll_next_unpin(cx)
+            }
+        }
+
+        let ctx = SessionContext::new();
+        let schema = Arc::new(Schema::new(vec![ColumnSchema::new(
+            "a",
+            ConcreteDataType::int32_datatype(),
+            false,
+        )]));
+        let batch = RecordBatch::new(
+            schema.clone(),
+            vec![Arc::new(Int32Vector::from_slice([1, 2])) as _],
+        )
+        .unwrap();
+        let inner = RecordBatches::try_new(schema, vec![batch])
+            .unwrap()
+            .as_stream();
+        let scan = StreamScanAdapter::new(Box::pin(StreamWithScanMetrics { inner }));
+
+        let stream = scan.execute(0, ctx.task_ctx()).unwrap();
+        let _ = util::collect(stream).await.unwrap();
+
+        let metrics = scan.metrics().unwrap();
+        assert_eq!(metrics.output_rows(), Some(2));
+        assert_eq!(
+            metrics
+                .sum_by_name("sst_files")
+                .map(|value| value.as_usize()),
+            Some(2)
+        );
+    }
 }
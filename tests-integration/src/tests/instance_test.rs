@@ -1425,6 +1425,60 @@ async fn test_information_schema_dot_columns(instance: Arc<dyn MockInstance>) {
     check_output_stream(output, expected).await;
 }
 
+#[apply(both_instances_cases)]
+async fn test_information_schema_dot_partitions(instance: Arc<dyn MockInstance>) {
+    let is_distributed_mode = instance.is_distributed_mode();
+    let instance = instance.frontend();
+
+    let sql = "create table demo(host string, ts bigint time index)";
+    let output = execute_sql(&instance, sql).await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+
+    let sql = "select table_name, partition_ordinal_position, partition_method, partition_expression, partition_description from information_schema.partitions where table_name = 'demo'";
+    let output = execute_sql(&instance, sql).await;
+    let expected = match is_distributed_mode {
+        true => {
+            "\
++------------+----------------------------+------------------+----------------------+-----------------------------+
+| table_name | partition_ordinal_position | partition_method | partition_expression | partition_description       |
++------------+----------------------------+------------------+----------------------+-----------------------------+
+| demo       | 1                          | RANGE COLUMNS    | ts                   | VALUES LESS THAN (MAXVALUE) |
++------------+----------------------------+------------------+----------------------+-----------------------------+"
+        }
+        false => {
+            "\
++------------+----------------------------+------------------+----------------------+-----------------------+
+| table_name | partition_ordinal_position | partition_method | partition_expression | partition_description |
++------------+----------------------------+------------------+----------------------+-----------------------+
+| demo       | 1                          |                  |                      |                       |
++------------+----------------------------+------------------+----------------------+-----------------------+"
+        }
+    };
+    check_output_stream(output, expected).await;
+
+    let sql = "select peer_type, is_leader from information_schema.cluster_info where peer_type != 'DATANODE'";
+    let output = execute_sql(&instance, sql).await;
+    let expected = match is_distributed_mode {
+        true => {
+            "\
++-----------+-----------+
+| peer_type | is_leader |
++-----------+-----------+
+| METASRV   | true      |
++-----------+-----------+"
+        }
+        false => {
+            "\
++------------+-----------+
+| peer_type  | is_leader |
++------------+-----------+
+| STANDALONE |           |
++------------+-----------+"
+        }
+    };
+    check_output_stream(output, expected).await;
+}
+
 async fn execute_sql(instance: &Arc<Instance>, sql: &str) -> Output {
     execute_sql_with(instance, sql, QueryContext::arc()).await
 }
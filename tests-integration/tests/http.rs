@@ -409,6 +409,52 @@ pub async fn test_prom_http_api(store_type: StorageType) {
     assert!(prom_resp.error.is_none());
     assert!(prom_resp.error_type.is_none());
 
+    // query exemplars
+    let res = client
+        .get("/api/v1/query_exemplars?query=demo&start=0&end=600")
+        .send()
+        .await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = serde_json::from_str::<PrometheusJsonResponse>(&res.text().await).unwrap();
+    assert_eq!(body.status, "error");
+    assert_eq!(body.error_type.unwrap(), ErrorCode::Unsupported.to_string());
+
+    // format query
+    let res = client
+        .get("/api/v1/format_query?query=sum(rate(demo[5m]))")
+        .send()
+        .await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = serde_json::from_str::<PrometheusJsonResponse>(&res.text().await).unwrap();
+    assert_eq!(body.status, "success");
+    let PrometheusResponse::FormatQuery(formatted) = body.data else {
+        unreachable!()
+    };
+    assert!(formatted.contains("demo"));
+
+    // metadata
+    let res = client.get("/api/v1/metadata").send().await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = serde_json::from_str::<PrometheusJsonResponse>(&res.text().await).unwrap();
+    assert_eq!(body.status, "success");
+    let PrometheusResponse::Metadata(metadata) = body.data else {
+        unreachable!()
+    };
+    assert_eq!(metadata.get("demo").unwrap()[0].metric_type, "unknown");
+    let res = client.get("/api/v1/metadata?metric=not_exist").send().await;
+    let body = serde_json::from_str::<PrometheusJsonResponse>(&res.text().await).unwrap();
+    assert_eq!(body.status, "success");
+
+    // build info
+    let res = client.get("/api/v1/status/buildinfo").send().await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = serde_json::from_str::<PrometheusJsonResponse>(&res.text().await).unwrap();
+    assert_eq!(body.status, "success");
+    let PrometheusResponse::BuildInfo(build_info) = body.data else {
+        unreachable!()
+    };
+    assert_eq!(build_info.version, env!("CARGO_PKG_VERSION"));
+
     guard.remove_all().await;
 }
 
@@ -9,6 +9,7 @@ INSERT INTO test VALUES (1, 1, "a"), (1, 1, "b"), (2, 2, "a");
 -- SQLNESS REPLACE (-+) -
 -- SQLNESS REPLACE (\s\s+) _
 -- SQLNESS REPLACE (peer-.*) REDACTED
+-- SQLNESS REPLACE (region.*) REDACTED
 TQL ANALYZE (0, 10, '5s') test;
 
 DROP TABLE test;
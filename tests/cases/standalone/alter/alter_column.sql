@@ -0,0 +1,24 @@
+CREATE TABLE test_alter_col(h STRING, i INTEGER, j BIGINT TIME INDEX, PRIMARY KEY(h));
+
+INSERT INTO test_alter_col VALUES ('a', 1, 1), ('b', 2, 2);
+
+ALTER TABLE test_alter_col MODIFY COLUMN i BIGINT;
+
+ALTER TABLE test_alter_col RENAME COLUMN i TO k;
+
+INSERT INTO test_alter_col VALUES ('c', 3, 3);
+
+SELECT * FROM test_alter_col ORDER BY j;
+
+DESC TABLE test_alter_col;
+
+ALTER TABLE test_alter_col MODIFY COLUMN h INTEGER;
+
+ALTER TABLE test_alter_col RENAME COLUMN k TO h;
+
+ALTER TABLE test_alter_col SET ('ttl'='7d', 'write_buffer_size'='1MB');
+
+-- SQLNESS ARG restart=true
+SELECT * FROM test_alter_col ORDER BY j;
+
+DROP TABLE test_alter_col;
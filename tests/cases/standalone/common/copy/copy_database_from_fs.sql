@@ -0,0 +1,17 @@
+CREATE DATABASE copy_db_src;
+
+CREATE TABLE copy_db_src.demo(host string, cpu double, memory double, ts TIMESTAMP time index);
+
+insert into copy_db_src.demo(host, cpu, memory, ts) values ('host1', 66.6, 1024, 1655276557000), ('host2', 88.8,  333.3, 1655276558000);
+
+COPY DATABASE copy_db_src TO '/tmp/demo/export/copy_database/';
+
+CREATE DATABASE copy_db_dst;
+
+COPY DATABASE copy_db_dst FROM '/tmp/demo/export/copy_database/';
+
+select * from copy_db_dst.demo order by ts;
+
+drop table copy_db_src.demo;
+
+drop table copy_db_dst.demo;
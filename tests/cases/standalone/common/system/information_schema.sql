@@ -25,4 +25,8 @@ where table_catalog = 'greptime'
   and table_schema != 'public'
 order by table_schema, table_name;
 
+select catalog_name, schema_name, default_character_set_name, default_collation_name, sql_path
+from information_schema.schemata
+where schema_name = 'my_db';
+
 use public;
@@ -0,0 +1,19 @@
+create table region_stat_test
+(
+    ts bigint time index
+);
+
+insert into region_stat_test values (1), (2);
+
+select table_name, partition_ordinal_position, partition_method, partition_expression, partition_description, peer_addr
+from information_schema.partitions
+where table_name = 'region_stat_test';
+
+select table_name, region_number, region_rows, disk_size, peer_id, peer_addr
+from information_schema.region_statistics
+where table_name = 'region_stat_test';
+
+select peer_id, peer_type, peer_addr, is_leader, last_active_time
+from information_schema.cluster_info;
+
+drop table region_stat_test;